@@ -27,6 +27,8 @@ const {
   deriveVerifyProgress,
   jobWalkProgress,
   jobStrategyMix,
+  jobThrottle,
+  throttleChip,
 } = await import(moduleUrl);
 
 const row = (over = {}) => ({
@@ -369,4 +371,37 @@ assert.equal(jobStrategyMix({ objects_processed: 0 }), null, 'nothing copied →
   assert.equal(mix.segments[0].count, 9, 'verbatim kept as reported');
 }
 
+// ── jobThrottle / throttleChip: window beats bandwidth, null when free ──────
+assert.equal(jobThrottle(null), null, 'null row → null');
+assert.equal(jobThrottle(row({ detail: {} })), null, 'no throttle detail → null');
+assert.equal(jobThrottle(row({ detail: { throttle: null } })), null, 'unthrottled rule → null');
+{
+  const closed = jobThrottle(
+    row({ detail: { throttle: { window_open: false, opens_at: 100, bandwidth_limit_mbps: 50, throttled: true, throttled_seconds: 7 } } }),
+  );
+  assert.deepEqual(closed, {
+    window_open: false,
+    opens_at: 100,
+    bandwidth_limit_mbps: 50,
+    throttled: true,
+    throttled_seconds: 7,
+  });
+  const chip = throttleChip(closed, (u) => `t${u}`);
+  assert.equal(chip.label, 'outside window', 'a closed window outranks the bandwidth cap');
+  assert.match(chip.hint, /t100/, 'hint names the reopening time');
+}
+{
+  const capped = jobThrottle(row({ detail: { throttle: { window_open: true, bandwidth_limit_mbps: 50, throttled: true } } }));
+  assert.equal(capped.opens_at, null);
+  assert.equal(capped.throttled_seconds, 0, 'missing counter defaults to 0');
+  const chip = throttleChip(capped);
+  assert.equal(chip.label, 'throttled');
+  assert.match(chip.hint, /50 Mbit\/s/);
+}
+assert.equal(
+  throttleChip(jobThrottle(row({ detail: { throttle: { window_open: true, bandwidth_limit_mbps: 50, throttled: false } } }))),
+  null,
+  'open window + not waiting → no chip',
+);
+
 console.log('jobs view regression checks passed');
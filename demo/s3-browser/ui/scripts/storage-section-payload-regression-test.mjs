@@ -71,6 +71,7 @@ const defaultFields = {
   public_prefixes: [],
   quota_bytes: null,
   replication_target_only: false,
+  read_fallback: null,
 };
 
 // (1) Synthetic ids are unique + monotonic, and NEVER appear in the wire.
@@ -112,6 +113,7 @@ const defaultFields = {
     public_prefixes: null,
     quota_bytes: 1073741824,
     replication_target_only: null,
+    read_fallback: null,
   });
 }
 
@@ -136,6 +138,49 @@ const defaultFields = {
   assert.equal(un.body.buckets.u.replication_target_only, null);
 }
 
+// (2c) read_fallback passthrough: same silent-deletion hazard as (2b) for a
+//      policy whose only field is the replica fallback.
+{
+  const fallback = { bucket: 'releases-dr', writes: 'queue' };
+  const row = policyToRow('releases', { read_fallback: fallback });
+  assert.equal(isAllDefaultRow(row), false, 'fallback-only row is NOT all-default');
+  const res = buildBucketPayload([row], ['releases']);
+  assert.equal(res.ok, true);
+  assert.deepEqual(res.body.buckets.releases.read_fallback, fallback);
+}
+
+// (2d) mirror passthrough: same hazard for a mirror-only policy.
+{
+  const mirror = { backend: 'b2-archive', write_quorum: 1 };
+  const row = policyToRow('ledgers', { mirror });
+  assert.equal(isAllDefaultRow(row), false, 'mirror-only row is NOT all-default');
+  const res = buildBucketPayload([row], ['ledgers']);
+  assert.equal(res.ok, true);
+  assert.deepEqual(res.body.buckets.ledgers.mirror, mirror);
+}
+
+// (2e) span passthrough: same hazard for a span-only policy.
+{
+  const span = {
+    backends: [{ backend: 'hetzner-fsn1', capacity_bytes: 1024 }, { backend: 'b2-archive' }],
+  };
+  const row = policyToRow('archive', { span });
+  assert.equal(isAllDefaultRow(row), false, 'span-only row is NOT all-default');
+  const res = buildBucketPayload([row], ['archive']);
+  assert.equal(res.ok, true);
+  assert.deepEqual(res.body.buckets.archive.span, span);
+}
+
+// (2f) hedge passthrough: same hazard for a hedge-only policy.
+{
+  const hedge = { after_ms: 250 };
+  const row = policyToRow('ci-cache', { hedge });
+  assert.equal(isAllDefaultRow(row), false, 'hedge-only row is NOT all-default');
+  const res = buildBucketPayload([row], ['ci-cache']);
+  assert.equal(res.ok, true);
+  assert.deepEqual(res.body.buckets['ci-cache'].hedge, hedge);
+}
+
 // (3) compression:null is preserved as explicit null (merge-clears the key).
 {
   const res = buildBucketPayload([
@@ -466,6 +511,11 @@ const {
   assert.deepEqual(r.destination, { bucket: '', prefix: '' });
   assert.deepEqual(r.exclude_globs, ['.dg/*']);
   assert.equal(r.conflict, 'newer-wins');
+  assert.equal(r.direction, 'one-way');
+  assert.equal(r.tombstone_retention, '30d');
+  // A bidirectional rule from the server keeps its direction.
+  const bidi = normalizeReplication({ rules: [{ name: 'b', direction: 'bidirectional' }] });
+  assert.equal(bidi.rules[0].direction, 'bidirectional');
 }
 
 function emptyReplRule(name) {
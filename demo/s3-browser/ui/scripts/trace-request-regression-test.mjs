@@ -64,4 +64,14 @@ assert.deepEqual(
   { method: 'DELETE', path: '/b/k', authenticated: true, query: 'list-type=2', source_ip: '2001:db8::1' },
 );
 
+// Access key is included, trimmed; absent or blank → key omitted.
+assert.deepEqual(
+  buildTraceBody({ method: 'GET', path: '/b/k', query: '', sourceIp: '', authenticated: true, accessKey: ' AKIAPARTNER ' }),
+  { method: 'GET', path: '/b/k', authenticated: true, access_key: 'AKIAPARTNER' },
+);
+assert.ok(
+  !('access_key' in buildTraceBody({ method: 'GET', path: '/', query: '', sourceIp: '', authenticated: true, accessKey: '  ' })),
+  'whitespace-only access_key must not add the key',
+);
+
 console.log('trace request body regression checks passed');
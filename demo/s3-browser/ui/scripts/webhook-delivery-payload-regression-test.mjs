@@ -497,4 +497,122 @@ function route(name, bucket, globs, channel) {
   assert.ok(r.matches.length === 0 && !r.fellBackToChannel, 'no match, no fallback → posted nowhere');
 }
 
+// ─────────────────────────────────────────────────────────────────────────
+// Named endpoints
+// ─────────────────────────────────────────────────────────────────────────
+
+// ── endpoints round-trip: filter, headers, retry overrides survive ──
+{
+  const wire = {
+    endpoints: [
+      {
+        name: 'media',
+        enabled: true,
+        url: 'https://hooks.example/media',
+        format: 'raw',
+        headers: { Authorization: SENTINEL },
+        filter: {
+          kinds: ['ObjectCreated'],
+          buckets: ['media'],
+          include_globs: ['videos/**'],
+          min_size: 1024,
+          metadata: { team: 'video' },
+        },
+        max_attempts: 3,
+        retry_base: '1s',
+      },
+    ],
+  };
+  const res = buildFromWire(wire);
+  assert.ok(res.ok, JSON.stringify(res.errors));
+  assert.deepEqual(ed(res).endpoints, wire.endpoints, 'endpoint round-trips unchanged');
+}
+
+// ── removing every endpoint emits an empty list (clears it server-side) ──
+{
+  const res = buildFromWire({ endpoints: [{ name: 'a', url: 'https://a.example' }] }, (f) => {
+    f.endpoints = [];
+  });
+  assert.ok(res.ok, JSON.stringify(res.errors));
+  assert.deepEqual(ed(res).endpoints, []);
+}
+
+// ── enabling with only a routed endpoint is fine ──
+{
+  const res = buildFromWire({ endpoints: [{ name: 'a', url: 'https://a.example' }] }, (f) => {
+    f.enabled = true;
+  });
+  assert.ok(res.ok, `an enabled endpoint is a destination, got ${JSON.stringify(res.errors)}`);
+}
+
+// ── renaming an endpoint whose secrets are masked is blocked ──
+{
+  const res = buildFromWire(
+    { endpoints: [{ name: 'a', url: 'https://a.example', signing: { secret: SENTINEL } }] },
+    (f) => {
+      f.endpoints[0].name = 'b';
+    }
+  );
+  assert.ok(!res.ok && res.errors.some((e) => /renamed/.test(e)), 'masked rename must fail');
+}
+
+// ── names are required and unique; metadata must be key=value ──
+{
+  const res = buildFromWire(
+    {
+      endpoints: [
+        { name: 'a', url: 'https://a.example' },
+        { name: 'a', url: 'https://b.example' },
+      ],
+    },
+    (f) => {
+      f.endpoints[1].metadataText = 'team';
+    }
+  );
+  assert.ok(!res.ok && res.errors.some((e) => /twice/.test(e)), 'duplicate name rejected');
+  assert.ok(res.errors.some((e) => /key=value/.test(e)), 'malformed metadata rejected');
+}
+
+// ═════════════════════════════════════════════════════════════════════════
+// Access events
+// ═════════════════════════════════════════════════════════════════════════
+
+// ── access_events round-trips and is always sent whole ──
+{
+  const wire = {
+    access_events: {
+      enabled: true,
+      buckets: ['releases'],
+      include_globs: ['v*/**'],
+      exclude_globs: [],
+      include_head: false,
+      sample_rate: 0.5,
+      security_events: true,
+      retention: '7d',
+      max_rows: 5000,
+    },
+  };
+  const res = buildFromWire(wire, () => {});
+  assert.ok(res.ok, `access events round-trip, got ${JSON.stringify(res.errors)}`);
+  assert.deepEqual(ed(res).access_events, wire.access_events);
+}
+
+// ── an absent access_events block loads as the backend defaults ──
+{
+  const res = buildFromWire({}, () => {});
+  assert.equal(ed(res).access_events.enabled, false);
+  assert.equal(ed(res).access_events.sample_rate, 1);
+  assert.equal(ed(res).access_events.retention, '24h');
+}
+
+// ── sample rate and retention are validated ──
+{
+  const res = buildFromWire({}, (f) => {
+    f.accessEvents.sampleRate = 1.5;
+    f.accessEvents.retention = 'soon';
+  });
+  assert.ok(!res.ok && res.errors.some((e) => /sample rate/.test(e)), 'sample rate > 1 rejected');
+  assert.ok(res.errors.some((e) => /retention "soon"/.test(e)), 'bad retention rejected');
+}
+
 console.log('webhook-delivery-payload-regression-test: all assertions passed');
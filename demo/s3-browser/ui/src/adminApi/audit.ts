@@ -1,11 +1,13 @@
 // ─────────────────────────────────────────────────────────────
 // Audit log (Wave 11 — Diagnostics → Audit panel)
 // ─────────────────────────────────────────────────────────────
-import { fetchJson } from './core';
+import { throwApiError } from '../errorHandling';
+import { adminFetch, fetchJson } from './core';
 
 /**
- * One entry from the in-memory audit ring. Server-side type lives
- * in `src/audit.rs::AuditEntry` — keep this in sync if either side
+ * One audit entry. Server-side types live in `src/audit.rs::AuditEntry`
+ * (ring) and `src/audit_store.rs::StoredAuditEntry` (durable store, which
+ * adds `id` and the hash-chain fields) — keep this in sync if either side
  * adds fields.
  */
 export interface AuditEntry {
@@ -17,18 +19,85 @@ export interface AuditEntry {
   ua: string;
   bucket: string;
   path: string;
+  /** Store entries only. */
+  id?: number;
+  prev_hash?: string;
+  hash?: string;
+}
+
+/** Server-side filters; empty fields are ignored. `from`/`to` are ISO-8601. */
+export interface AuditFilters {
+  user?: string;
+  bucket?: string;
+  /** Key prefix. */
+  key?: string;
+  action?: string;
+  from?: string;
+  to?: string;
 }
 
 interface AuditResponse {
   entries: AuditEntry[];
   limit: number;
+  /** `store` = durable audit store (`advanced.audit`), `ring` = in-memory. */
+  source: 'ring' | 'store';
+  store_enabled: boolean;
+  /** Pass back as `before` for the next (older) page; store only. */
+  next_before?: number;
+}
+
+/** Result of recomputing the store's hash chain. */
+export interface AuditVerification {
+  valid: boolean;
+  chain_id: string | null;
+  entries: number;
+  first_id: number | null;
+  last_id: number | null;
+  anchor_hash: string | null;
+  head_id: number;
+  head_hash: string | null;
+  archived_id: number;
+  broken_at: number | null;
+  problem: string | null;
+  /** Entries the writer dropped, per the `audit_entries_dropped` entries checked. */
+  dropped: number;
+}
+
+function auditQuery(filters: AuditFilters, extra: Record<string, string | number | undefined>): string {
+  const params = new URLSearchParams();
+  for (const [k, v] of Object.entries({ ...filters, ...extra })) {
+    if (v !== undefined && String(v).trim() !== '') params.set(k, String(v).trim());
+  }
+  const qs = params.toString();
+  return qs ? `?${qs}` : '';
 }
 
 /**
- * Fetch the most-recent `limit` audit entries (newest first). The
- * server caps `limit` at 500 regardless; the ring size itself is
- * governed by `DGP_AUDIT_RING_SIZE` (default 500).
+ * Fetch up to `limit` audit entries (newest first) matching `filters`,
+ * older than the store id `before` when paging. The server caps `limit`
+ * at 500; the ring itself holds `DGP_AUDIT_RING_SIZE` (default 500).
  */
-export async function fetchAudit(limit = 100): Promise<AuditResponse> {
-  return fetchJson(`/api/admin/audit?limit=${encodeURIComponent(limit)}`, 'Audit fetch');
+export async function fetchAudit(
+  limit = 100,
+  filters: AuditFilters = {},
+  before?: number,
+): Promise<AuditResponse> {
+  return fetchJson(`/api/admin/audit${auditQuery(filters, { limit, before })}`, 'Audit fetch');
+}
+
+/** Download every matching entry, oldest first, as CSV or JSON Lines. */
+export async function exportAudit(
+  format: 'csv' | 'jsonl',
+  filters: AuditFilters = {},
+): Promise<{ blob: Blob; filename: string }> {
+  const res = await adminFetch(`/api/admin/audit/export${auditQuery(filters, { format })}`);
+  if (!res.ok) await throwApiError(res, 'Audit export');
+  const cd = res.headers.get('content-disposition') ?? '';
+  const m = cd.match(/filename="?([^";]+)"?/i);
+  return { blob: await res.blob(), filename: m?.[1] ?? `dgp-audit.${format}` };
+}
+
+/** Recompute the durable store's hash chain. */
+export async function verifyAudit(): Promise<AuditVerification> {
+  return fetchJson('/api/admin/audit/verify', 'Audit verify');
 }
@@ -78,3 +78,12 @@ export async function probeBackend(
   if (!res.ok) await throwApiError(res, `Test connection to ${name}`);
   return safeJson(res);
 }
+
+/** Rewrite missing and stale shards of an erasure-coded backend. */
+export async function repairErasureBackend(
+  name: string,
+): Promise<import('./core').ErasureRepairReport> {
+  const res = await adminFetch(`/api/admin/erasure/${encodeURIComponent(name)}/repair`, 'POST');
+  if (!res.ok) await throwApiError(res, `Repair shards of ${name}`);
+  return safeJson(res);
+}
@@ -94,6 +94,28 @@ export interface AdminConfig {
       /** Declared replication destination: client writes 403; replication
        *  is the single writer (safe on non-CAS backends like B2). */
       replication_target_only?: boolean;
+      /** Serve reads from `bucket` (a replica on another backend) while
+       *  this bucket's backend is down. */
+      read_fallback?: {
+        bucket: string;
+        on_error?: boolean;
+        writes?: 'block' | 'queue';
+      };
+      /** Synchronous second copy of this bucket on another backend. */
+      mirror?: {
+        backend: string;
+        alias?: string;
+        write_quorum?: 1 | 2;
+      };
+      /** Store the bucket across these backends, filled in order. */
+      span?: {
+        backends: { backend: string; alias?: string; capacity_bytes?: number }[];
+      };
+      /** Race slow passthrough GETs against the mirror / read_fallback
+       *  copy. `after_ms` unset = p95 of the backend's time to first byte. */
+      hedge?: {
+        after_ms?: number;
+      };
     }
   >;
   // Multi-backend
@@ -137,13 +159,24 @@ interface AdmissionMatch {
   path_glob?: string;
   authenticated?: boolean;
   config_flag?: string;
+  /** Throttle blocks only. */
+  access_key?: string[];
+  /** Throttle blocks only. */
+  group?: string[];
 }
 
 type AdmissionAction =
   | 'allow-anonymous'
   | 'deny'
   | 'continue'
-  | { type: 'reject'; status: number; message?: string };
+  | { type: 'reject'; status: number; message?: string }
+  | {
+      type: 'throttle';
+      per?: 'global' | 'access-key' | 'group' | 'bucket' | 'source-ip';
+      requests_per_sec?: number;
+      burst?: number;
+      bytes_per_sec?: number;
+    };
 
 /**
  * Per-backend encryption status. Non-secret-only — the raw key never
@@ -184,6 +217,10 @@ export interface BackendInfo {
   endpoint: string | null;
   region: string | null;
   force_path_style: boolean | null;
+  /** Azure storage account (Azure backends only). */
+  account?: string | null;
+  /** GCP project (GCS backends only). */
+  project?: string | null;
   has_credentials: boolean;
   /**
    * Per-backend encryption status (Step 6/7 per-backend refactor).
@@ -218,6 +255,26 @@ export interface BackendInfo {
    * Absent when probing is disabled (DGP_BOOT_BACKEND_PROBE=off).
    */
   health?: BackendHealthEntry;
+  /** Shard layout of an erasure-coded backend (`type: erasure` only). */
+  erasure?: BackendErasureSummary;
+}
+
+/** k + m shard layout; each shard carries its shard backend's own health. */
+export interface BackendErasureSummary {
+  data_shards: number;
+  parity_shards: number;
+  write_quorum: number;
+  shards: { backend: string; health?: BackendHealthEntry }[];
+}
+
+/** Mirror of the server's storage::ErasureRepairReport. */
+export interface ErasureRepairReport {
+  buckets: number;
+  blobs_scanned: number;
+  shards_repaired: number;
+  unrecoverable: number;
+  orphans_removed: number;
+  failed: number;
 }
 
 /** Mirror of the server's coordination::CapabilityVerdict (kebab-case tag). */
@@ -31,6 +31,18 @@ interface EventOutboxCounts {
   failed: number;
 }
 
+/** Delivery progress of one named `event_delivery.endpoints` entry. */
+export interface EventEndpointStatus {
+  name: string;
+  enabled: boolean;
+  cursor: number | null;
+  cursor_updated_at: number | null;
+  attempts: number;
+  next_attempt_at: number | null;
+  last_error: string | null;
+  skipped: number;
+}
+
 interface EventOutboxResponse {
   rows: EventOutboxRecord[];
   counts: EventOutboxCounts;
@@ -42,6 +54,8 @@ interface EventOutboxResponse {
   order: string;
   delivery_enabled: boolean;
   delivery_active: boolean;
+  endpoints?: EventEndpointStatus[];
+  latest_event_id?: number | null;
 }
 
 interface EventOutboxRequeueResponse {
@@ -43,6 +43,23 @@ interface JobFailureEntry {
   error: string;
 }
 
+/** A key a bidirectional rule holds for manual resolution (the "Conflicts"
+ *  tab). Keys are relative to the rule's prefixes; a missing stamp/size
+ *  means that side deleted the key. */
+export interface JobConflictEntry {
+  key: string;
+  kind: 'both-created' | 'both-modified' | 'deleted-on-source' | 'deleted-on-destination';
+  source_key: string;
+  destination_key: string;
+  source_stamp?: string;
+  destination_stamp?: string;
+  source_size?: number;
+  destination_size?: number;
+  detected_at: number;
+}
+
+export type ConflictSide = 'source' | 'destination';
+
 // === Replication parity audit (the "Verify" tab) ===
 export type Verifier = 'sha256' | 'etag_size' | 'size_only';
 type FindingKind = 'match' | 'checksum_mismatch' | 'missing_on_dest' | 'orphan_on_dest';
@@ -217,6 +234,27 @@ export async function getJobFailures(id: string): Promise<{ failures: JobFailure
   return fetchJson(`/api/admin/jobs/${encodeURIComponent(id)}/failures`, 'Job failures');
 }
 
+export async function getJobConflicts(
+  id: string
+): Promise<{ conflicts: JobConflictEntry[]; total: number }> {
+  return fetchJson(`/api/admin/jobs/${encodeURIComponent(id)}/conflicts`, 'Job conflicts');
+}
+
+/** Keep one side's version (or its deletion) of a conflicted key on both sides. */
+export async function resolveJobConflict(
+  id: string,
+  key: string,
+  keep: ConflictSide
+): Promise<{ key: string; kept: ConflictSide; resolution: 'copied' | 'deleted' }> {
+  const res = await adminFetch(
+    `/api/admin/jobs/${encodeURIComponent(id)}/conflicts/resolve`,
+    'POST',
+    { key, keep }
+  );
+  if (!res.ok) await throwApiError(res, 'Resolve conflict');
+  return safeJson(res);
+}
+
 /** Uniform action dispatch; returns the action's JSON payload (if any). */
 export async function runJobAction(id: string, action: JobAction): Promise<unknown> {
   const res = await adminFetch(`/api/admin/jobs/${encodeURIComponent(id)}/${action}`, 'POST');
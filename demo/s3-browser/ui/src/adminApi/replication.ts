@@ -2,9 +2,41 @@
 
 type ReplicationConflictPolicy = 'newer-wins' | 'content-diff' | 'skip-if-dest-exists';
 
+/** `bidirectional` = active-active; both endpoints take writes. */
+export type ReplicationDirection = 'one-way' | 'bidirectional';
+
+/**
+ * Allowed-hours window: `days` like `mon-fri` / `sat,sun` / `daily`, `start`
+ * and `end` as `HH:MM` (an end before the start runs past midnight), IANA
+ * `timezone` (default UTC).
+ */
+export interface ReplicationWindow {
+  days: string;
+  start: string;
+  end: string;
+  timezone: string;
+}
+
+/**
+ * A rule side on an arbitrary S3 endpoint (not a proxy backend), reached
+ * with its own credentials. Credentials come back cleared from the server.
+ */
+export interface ExternalS3Endpoint {
+  /** Absent = AWS S3. */
+  endpoint?: string;
+  region: string;
+  force_path_style: boolean;
+  access_key_id?: string;
+  secret_access_key?: string;
+  allow_local?: boolean;
+  /** Plain objects (default true); `false` = DeltaGlider storage layout. */
+  hydrated?: boolean;
+}
+
 interface ReplicationEndpoint {
   bucket: string;
   prefix: string;
+  external?: ExternalS3Endpoint;
 }
 
 export interface ReplicationRuleConfig {
@@ -15,7 +47,15 @@ export interface ReplicationRuleConfig {
   interval: string;
   batch_size: number;
   replicate_deletes: boolean;
+  direction: ReplicationDirection;
+  /** Bidirectional only: how long delete tombstones are kept. */
+  tombstone_retention: string;
+  /** One-way rules only; bidirectional conflicts are resolved by hand. */
   conflict: ReplicationConflictPolicy;
+  /** Copy throughput ceiling for this rule; absent = uncapped. */
+  bandwidth_limit_mbps?: number;
+  /** Copies only run inside these windows; absent/empty = any time. */
+  windows?: ReplicationWindow[];
   include_globs: string[];
   exclude_globs: string[];
 }
@@ -26,5 +66,9 @@ export interface ReplicationConfig {
   lease_ttl: string;
   heartbeat_interval: string;
   max_failures_retained: number;
+  /** Ceiling shared by every rule's copies; absent = uncapped. */
+  bandwidth_limit_mbps?: number;
+  /** Windows every rule must also be inside; absent/empty = any time. */
+  windows?: ReplicationWindow[];
   rules: ReplicationRuleConfig[];
 }
@@ -40,6 +40,8 @@ interface EncryptionPatch {
 interface BackendShapeSource {
   name: string;
   backend_type: string;
+  account?: string | null;
+  project?: string | null;
   path?: string | null;
   endpoint?: string | null;
   region?: string | null;
@@ -98,6 +100,8 @@ export function buildEncryptionSectionBody(
     if (b.path) backendShape.path = b.path;
     if (b.endpoint) backendShape.endpoint = b.endpoint;
     if (b.region) backendShape.region = b.region;
+    if (b.account) backendShape.account = b.account;
+    if (b.project) backendShape.project = b.project;
     if (b.force_path_style !== null && b.force_path_style !== undefined) {
       backendShape.force_path_style = b.force_path_style;
     }
@@ -0,0 +1,155 @@
+/**
+ * AccessEventsCard — the `advanced.event_delivery.access_events` editor.
+ *
+ * Renders INSIDE WebhookDeliveryPanel and edits the SAME `useSectionEditor`
+ * value (`form.accessEvents` via `setField`), like EventEndpointsCard.
+ *
+ * Read (`ObjectAccessed`) and security (`AccessDenied` / `AuthFailed`) events
+ * are recorded into the outbox with their own retention; only routed
+ * endpoints that tick those kinds receive them. No Select popups — switches,
+ * numbers and one-entry-per-line text areas only.
+ */
+import { Input, InputNumber, Space, Switch, Typography } from 'antd';
+import { EyeOutlined } from '@ant-design/icons';
+import SectionHeader from './SectionHeader';
+import FormField from './FormField';
+import { AdvancedDisclosure } from './ruleEditorFields';
+import type { AccessEventsForm, WebhookFormState } from './webhookDeliveryPayload';
+
+const { Text } = Typography;
+const { TextArea } = Input;
+
+interface Props {
+  form: WebhookFormState;
+  setField: (
+    patch: Partial<WebhookFormState> | ((prev: WebhookFormState) => Partial<WebhookFormState>),
+  ) => void;
+  inputRadius: React.CSSProperties;
+}
+
+export default function AccessEventsCard({ form, setField, inputRadius }: Props) {
+  const a = form.accessEvents;
+  const update = (patch: Partial<AccessEventsForm>) =>
+    setField((prev) => ({ accessEvents: { ...prev.accessEvents, ...patch } }));
+  const mono: React.CSSProperties = { ...inputRadius, fontFamily: 'var(--font-mono)', fontSize: 14 };
+  const subscribed = form.endpoints.some((e) =>
+    e.kinds.some((k) => k === 'ObjectAccessed' || k === 'AccessDenied' || k === 'AuthFailed'),
+  );
+
+  return (
+    <>
+      <SectionHeader
+        icon={<EyeOutlined />}
+        title="Access events"
+        description="Record who downloaded what, and denied or failed sign-ins. Kept in the outbox under their own retention; only routed endpoints that tick these kinds receive them."
+      />
+      <Space size="large" wrap style={{ marginBottom: 8 }}>
+        <Space>
+          <Switch checked={a.enabled} onChange={(enabled) => update({ enabled })} />
+          <Text>Object reads</Text>
+        </Space>
+        <Space>
+          <Switch
+            checked={a.securityEvents}
+            onChange={(securityEvents) => update({ securityEvents })}
+          />
+          <Text>Denials and failed sign-ins</Text>
+        </Space>
+      </Space>
+      {(a.enabled || a.securityEvents) && !subscribed && (
+        <Text type="warning" style={{ display: 'block', fontSize: 13, marginBottom: 8 }}>
+          No routed endpoint ticks ObjectAccessed, AccessDenied or AuthFailed — events are only kept
+          in the outbox.
+        </Text>
+      )}
+      {a.enabled && (
+        <>
+          <FormField
+            label="Buckets"
+            yamlPath="advanced.event_delivery.access_events.buckets"
+            helpText="One bucket per line. Empty = every bucket."
+          >
+            <TextArea
+              value={a.bucketsText}
+              onChange={(e) => update({ bucketsText: e.target.value })}
+              autoSize={{ minRows: 1, maxRows: 4 }}
+              placeholder="releases"
+              style={mono}
+            />
+          </FormField>
+          <FormField
+            label="Key globs"
+            yamlPath="advanced.event_delivery.access_events.include_globs"
+            helpText="One glob per line. Empty = every key."
+          >
+            <TextArea
+              value={a.includeText}
+              onChange={(e) => update({ includeText: e.target.value })}
+              autoSize={{ minRows: 1, maxRows: 4 }}
+              placeholder="v*/**"
+              style={mono}
+            />
+          </FormField>
+        </>
+      )}
+      <AdvancedDisclosure title="Sampling, HEAD requests and retention">
+        <FormField
+          label="Exclude globs"
+          yamlPath="advanced.event_delivery.access_events.exclude_globs"
+          helpText="Reads of keys matching any of these are never recorded."
+        >
+          <TextArea
+            value={a.excludeText}
+            onChange={(e) => update({ excludeText: e.target.value })}
+            autoSize={{ minRows: 1, maxRows: 4 }}
+            placeholder="**/*.sha256"
+            style={mono}
+          />
+        </FormField>
+        <FormField
+          label="Record HEAD requests"
+          yamlPath="advanced.event_delivery.access_events.include_head"
+          helpText="Off by default: most SDKs HEAD an object before reading it."
+        >
+          <Switch checked={a.includeHead} onChange={(includeHead) => update({ includeHead })} />
+        </FormField>
+        <FormField
+          label="Sample rate"
+          yamlPath="advanced.event_delivery.access_events.sample_rate"
+          helpText="Fraction of matching reads recorded, 0–1. Security events are never sampled."
+        >
+          <InputNumber
+            value={a.sampleRate}
+            min={0}
+            max={1}
+            step={0.1}
+            onChange={(v) => update({ sampleRate: typeof v === 'number' ? v : 1 })}
+          />
+        </FormField>
+        <FormField
+          label="Retention"
+          yamlPath="advanced.event_delivery.access_events.retention"
+          helpText="Access and security rows older than this are pruned. Format: 30s, 5m, 24h."
+        >
+          <Input
+            value={a.retention}
+            onChange={(e) => update({ retention: e.target.value })}
+            placeholder="24h"
+            style={{ ...mono, maxWidth: 200 }}
+          />
+        </FormField>
+        <FormField
+          label="Max rows"
+          yamlPath="advanced.event_delivery.access_events.max_rows"
+          helpText="Newest access and security rows kept. They never count against the delivered-row cap."
+        >
+          <InputNumber
+            value={a.maxRows}
+            min={0}
+            onChange={(v) => update({ maxRows: typeof v === 'number' ? v : 0 })}
+          />
+        </FormField>
+      </AdvancedDisclosure>
+    </>
+  );
+}
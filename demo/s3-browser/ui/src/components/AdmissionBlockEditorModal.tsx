@@ -15,9 +15,11 @@
  *       2. **Source IP** — mutually-exclusive single IP or IP list
  *          (with the list editor below).
  *       3. **Path & Bucket** — bucket name + path glob.
- *       4. **Auth state** — anonymous / authenticated / any.
+ *       4. **Auth state** — anonymous / authenticated / any, plus
+ *          access-key / group lists for throttle blocks.
  *   * Action radio group at the bottom with a conditional Reject
- *     sub-form (status + optional message). Destructive actions
+ *     sub-form (status + optional message) or Throttle sub-form
+ *     (partition key + rates). Destructive actions
  *     (deny, reject) get a muted reminder bar: "This will 403/5xx
  *     all matching requests."
  *
@@ -48,6 +50,7 @@ import {
   actionKind,
   admissionBlockSchema,
   METHODS,
+  THROTTLE_KEYS,
   type AdmissionBlockForm,
 } from '../schemas/admissionSchema';
 import { useColors } from '../ThemeContext';
@@ -96,6 +99,8 @@ export default function AdmissionBlockEditorModal({
         path_glob: initial.match.path_glob,
         authenticated: initial.match.authenticated,
         config_flag: initial.match.config_flag,
+        access_key: initial.match.access_key,
+        group: initial.match.group,
       },
       action: initial.action as AdmissionBlockForm['action'],
     };
@@ -162,6 +167,9 @@ export default function AdmissionBlockEditorModal({
       compact.authenticated = m.authenticated;
     if (m.config_flag && m.config_flag.trim())
       compact.config_flag = m.config_flag.trim();
+    if (m.access_key && m.access_key.length > 0)
+      compact.access_key = m.access_key;
+    if (m.group && m.group.length > 0) compact.group = m.group;
 
     onSave({ name: data.name, match: compact, action: data.action });
   };
@@ -399,6 +407,57 @@ export default function AdmissionBlockEditorModal({
             )}
           />
         </FormField>
+        {kind === 'throttle' && (
+          <>
+            <FormField
+              label="Access keys"
+              yamlPath="match.access_key"
+              helpText="Signing access key IDs. Empty = any caller. Throttle blocks only."
+            >
+              <Controller
+                control={control}
+                name="match.access_key"
+                render={({ field }) => (
+                  <Select
+                    mode="tags"
+                    placeholder="(any)"
+                    value={field.value ?? []}
+                    onChange={(v: string[]) =>
+                      field.onChange(v.length > 0 ? v : undefined)
+                    }
+                    style={{ width: '100%' }}
+                  />
+                )}
+              />
+            </FormField>
+            <FormField
+              label="IAM groups"
+              yamlPath="match.group"
+              helpText="Matches callers in any listed group. Empty = any caller. Throttle blocks only."
+            >
+              <Controller
+                control={control}
+                name="match.group"
+                render={({ field }) => (
+                  <Select
+                    mode="tags"
+                    placeholder="(any)"
+                    value={field.value ?? []}
+                    onChange={(v: string[]) =>
+                      field.onChange(v.length > 0 ? v : undefined)
+                    }
+                    style={{ width: '100%' }}
+                  />
+                )}
+              />
+            </FormField>
+          </>
+        )}
+        {errors.match?.group && (
+          <Text type="danger" style={{ fontSize: 12 }}>
+            {errors.match.group.message}
+          </Text>
+        )}
         <FormField
           label="Named config flag"
           yamlPath="match.config_flag"
@@ -434,8 +493,14 @@ export default function AdmissionBlockEditorModal({
                 const v = e.target.value;
                 if (v === 'reject') {
                   field.onChange({ type: 'reject', status: 503, message: '' });
+                } else if (v === 'throttle') {
+                  field.onChange({ type: 'throttle', per: 'access-key', requests_per_sec: 10 });
                 } else {
                   field.onChange(v);
+                  // Identity predicates are throttle-only; drop them so
+                  // the block stays valid after switching away.
+                  setValue('match.access_key', undefined);
+                  setValue('match.group', undefined);
                 }
               }}
             >
@@ -443,6 +508,7 @@ export default function AdmissionBlockEditorModal({
               <Radio value="deny">Deny (S3-style 403)</Radio>
               <Radio value="reject">Reject (custom status)</Radio>
               <Radio value="continue">Continue</Radio>
+              <Radio value="throttle">Throttle</Radio>
             </Radio.Group>
           )}
         />
@@ -458,7 +524,7 @@ export default function AdmissionBlockEditorModal({
             }
           />
         )}
-        {kind === 'reject' && typeof currentAction !== 'string' && (
+        {kind === 'reject' && typeof currentAction !== 'string' && currentAction.type === 'reject' && (
           <div style={{ marginTop: 12 }}>
             <FormField
               label="Status code"
@@ -503,7 +569,111 @@ export default function AdmissionBlockEditorModal({
             </FormField>
           </div>
         )}
+        {kind === 'throttle' &&
+          typeof currentAction !== 'string' &&
+          currentAction.type === 'throttle' && (
+            <div style={{ marginTop: 12 }}>
+              <Alert
+                type="info"
+                showIcon
+                style={{ marginBottom: 12 }}
+                message="Throttle blocks never decide a request. Every matching throttle block applies after authentication, regardless of its position in the chain. Budgets are per instance."
+              />
+              <FormField
+                label="Budget per"
+                yamlPath="action.per"
+                helpText="global = one shared budget; otherwise one budget per access key, group, bucket, or source IP."
+              >
+                <Select
+                  value={currentAction.per ?? 'global'}
+                  onChange={(v) => setValue('action', { ...currentAction, per: v })}
+                  options={THROTTLE_KEYS.map((k) => ({ value: k, label: k }))}
+                  style={{ width: 200 }}
+                />
+              </FormField>
+              <FormField
+                label="Requests per second"
+                yamlPath="action.requests_per_sec"
+                helpText="Sustained rate. Excess requests get 503 SlowDown. Empty = unlimited."
+              >
+                <InputNumber
+                  min={1}
+                  value={currentAction.requests_per_sec}
+                  onChange={(v) =>
+                    setValue(
+                      'action',
+                      { ...currentAction, requests_per_sec: v ?? undefined },
+                      { shouldValidate: true }
+                    )
+                  }
+                  style={{ width: 160 }}
+                />
+              </FormField>
+              <FormField
+                label="Burst"
+                yamlPath="action.burst"
+                helpText="Requests allowed at once above the sustained rate. Defaults to the rate."
+              >
+                <InputNumber
+                  min={1}
+                  value={currentAction.burst}
+                  onChange={(v) =>
+                    setValue(
+                      'action',
+                      { ...currentAction, burst: v ?? undefined },
+                      { shouldValidate: true }
+                    )
+                  }
+                  style={{ width: 160 }}
+                />
+              </FormField>
+              <FormField
+                label="Bytes per second"
+                yamlPath="action.bytes_per_sec"
+                helpText="Body bandwidth, each direction. Excess is paced, not rejected. Empty = unlimited."
+                examples={[1048576, 10485760, 104857600]}
+                onExampleClick={(v) =>
+                  setValue(
+                    'action',
+                    { ...currentAction, bytes_per_sec: Number(v) },
+                    { shouldValidate: true }
+                  )
+                }
+              >
+                <InputNumber
+                  min={1}
+                  value={currentAction.bytes_per_sec}
+                  onChange={(v) =>
+                    setValue(
+                      'action',
+                      { ...currentAction, bytes_per_sec: v ?? undefined },
+                      { shouldValidate: true }
+                    )
+                  }
+                  style={{ width: 200 }}
+                />
+              </FormField>
+              {errors.action && (
+                <Text type="danger" style={{ fontSize: 12 }}>
+                  {throttleErrorMessage(errors.action)}
+                </Text>
+              )}
+            </div>
+          )}
       </div>
     </Modal>
   );
 }
+
+/**
+ * The throttle refinements attach their messages to `requests_per_sec`
+ * / `burst` inside the action object; surface whichever is present.
+ */
+function throttleErrorMessage(error: unknown): string | undefined {
+  const e = error as {
+    message?: string;
+    requests_per_sec?: { message?: string };
+    burst?: { message?: string };
+  };
+  return e.message ?? e.requests_per_sec?.message ?? e.burst?.message;
+}
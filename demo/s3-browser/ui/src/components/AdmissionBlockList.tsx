@@ -225,11 +225,14 @@ function ActionBadge({ action }: { action: AdmissionBlock['action'] }) {
     deny: 'red',
     continue: 'blue',
     reject: 'orange',
+    throttle: 'purple',
   };
   const label =
-    kind === 'reject' && typeof action !== 'string'
-      ? `reject ${action.status}`
-      : kind;
+    typeof action === 'string'
+      ? kind
+      : action.type === 'reject'
+        ? `reject ${action.status}`
+        : `throttle per ${action.per ?? 'global'}`;
   return <Tag color={colour[kind]}>{label}</Tag>;
 }
 
@@ -254,6 +257,10 @@ function matchSummary(match: AdmissionBlock['match']): string {
   if (match.authenticated !== undefined)
     parts.push(`auth: ${match.authenticated ? 'yes' : 'no'}`);
   if (match.config_flag) parts.push(`flag: ${match.config_flag}`);
+  if (match.access_key && match.access_key.length > 0)
+    parts.push(`access_key: ${match.access_key.join(',')}`);
+  if (match.group && match.group.length > 0)
+    parts.push(`group: ${match.group.join(',')}`);
   if (parts.length === 0) return 'matches every request';
   return parts.join(' · ');
 }
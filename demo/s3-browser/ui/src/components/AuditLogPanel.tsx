@@ -17,21 +17,31 @@
  *     operator can flip it on while reproducing something.
  *   * Refresh button for manual one-shot refetch.
  *
- * This is a read-only surface. No mutations, no pagination (the
- * ring is bounded — fetching the whole thing is always cheap).
- * For long-term audit, stdout remains authoritative: this panel
- * is a triage convenience, not a compliance substitute.
+ * With the durable audit store on (`advanced.audit`), the panel reads
+ * the store instead: server-side filters (user / bucket / key prefix /
+ * action / time range), "Load older" paging, CSV / JSON Lines export
+ * of everything matching, and a hash-chain verification. Without it,
+ * the ring stays a triage convenience, not a compliance substitute.
  */
 import { useEffect, useMemo, useState } from 'react';
-import { Typography, Input, Button, Tag, Alert, Space, Switch } from 'antd';
+import { Typography, Input, Button, Tag, Alert, Space, Switch, message } from 'antd';
 import { useVisiblePolling } from '../useVisiblePolling';
 import {
   ReloadOutlined,
   SearchOutlined,
   FileTextOutlined,
+  DownloadOutlined,
+  SafetyCertificateOutlined,
 } from '@ant-design/icons';
 import { useColors } from '../ThemeContext';
-import { fetchAudit, type AuditEntry } from '../adminApi';
+import {
+  exportAudit,
+  fetchAudit,
+  verifyAudit,
+  type AuditEntry,
+  type AuditFilters,
+  type AuditVerification,
+} from '../adminApi';
 import { LoadingState } from './StatePlaceholders';
 import { relativeTime } from '../utils';
 import { contentColumn, CONTENT_WIDE } from './shared-styles';
@@ -65,6 +75,13 @@ function actionColour(action: string): string {
   return 'default';
 }
 
+/** `datetime-local` input value → ISO-8601 UTC (empty stays empty). */
+function localToIso(value: string | undefined): string | undefined {
+  if (!value) return undefined;
+  const d = new Date(value);
+  return Number.isNaN(d.getTime()) ? undefined : d.toISOString();
+}
+
 export default function AuditLogPanel({ onSessionExpired }: Props) {
   const colors = useColors();
   const [entries, setEntries] = useState<AuditEntry[]>([]);
@@ -72,16 +89,32 @@ export default function AuditLogPanel({ onSessionExpired }: Props) {
   const [error, setError] = useState<string | null>(null);
   const [filter, setFilter] = useState('');
   const [autoRefresh, setAutoRefresh] = useState(false);
+  // Server-side filters: `draft` is what the inputs show, `applied` what
+  // the last fetch used (exports and "Load older" reuse it).
+  const [draft, setDraft] = useState<AuditFilters>({});
+  const [applied, setApplied] = useState<AuditFilters>({});
+  const [source, setSource] = useState<'ring' | 'store'>('ring');
+  const [nextBefore, setNextBefore] = useState<number | undefined>();
+  const [verification, setVerification] = useState<AuditVerification | null>(null);
+  const [busy, setBusy] = useState<'csv' | 'jsonl' | 'verify' | null>(null);
   // `now` is refreshed on the same cadence as the fetch so the
   // relative-time column stays honest without re-rendering the
   // world every second.
   const [now, setNow] = useState(() => new Date());
 
-  const refresh = async () => {
+  const isoFilters = (f: AuditFilters): AuditFilters => ({
+    ...f,
+    from: localToIso(f.from),
+    to: localToIso(f.to),
+  });
+
+  const refresh = async (filters: AuditFilters = applied, older?: number) => {
     try {
       setLoading(true);
-      const res = await fetchAudit(500);
-      setEntries(res.entries);
+      const res = await fetchAudit(500, isoFilters(filters), older);
+      setEntries((prev) => (older === undefined ? res.entries : [...prev, ...res.entries]));
+      setSource(res.source);
+      setNextBefore(res.next_before);
       setNow(new Date());
       setError(null);
     } catch (e) {
@@ -106,6 +139,51 @@ export default function AuditLogPanel({ onSessionExpired }: Props) {
   // polling and catches up on return, instead of hammering the audit endpoint.
   useVisiblePolling(() => void refresh(), 3000, autoRefresh);
 
+  const applyFilters = () => {
+    setApplied(draft);
+    void refresh(draft);
+  };
+
+  const download = async (format: 'csv' | 'jsonl') => {
+    setBusy(format);
+    try {
+      const { blob, filename } = await exportAudit(format, isoFilters(applied));
+      const url = URL.createObjectURL(blob);
+      const a = document.createElement('a');
+      a.href = url;
+      a.download = filename;
+      a.click();
+      URL.revokeObjectURL(url);
+    } catch (e) {
+      message.error('Export failed: ' + normalizeUiError(e, 'unknown'));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const verify = async () => {
+    setBusy('verify');
+    try {
+      setVerification(await verifyAudit());
+    } catch (e) {
+      message.error('Verification failed: ' + normalizeUiError(e, 'unknown'));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const draftInput = (field: keyof AuditFilters, placeholder: string, width = 150) => (
+    <Input
+      size="middle"
+      placeholder={placeholder}
+      value={draft[field] ?? ''}
+      onChange={(e) => setDraft((d) => ({ ...d, [field]: e.target.value }))}
+      onPressEnter={applyFilters}
+      style={{ width }}
+      allowClear
+    />
+  );
+
   // Client-side filter — free-text substring match across action,
   // user, target, ip, bucket, and path. Deliberately lenient:
   // operators paste IPs, access keys, bucket names verbatim and
@@ -151,24 +229,111 @@ export default function AuditLogPanel({ onSessionExpired }: Props) {
         }}
       >
         <FileTextOutlined style={{ color: colors.ACCENT_BLUE, flexShrink: 0 }} aria-hidden />
-        <span>
-          Newest {entries.length || 500} entries kept in memory for quick inspection.{' '}
-          <code
-            title="Environment variable controlling the ring size (default 500)"
-            style={{ fontFamily: 'var(--font-mono)', fontSize: 11, opacity: 0.6, color: colors.TEXT_MUTED }}
-          >
-            DGP_AUDIT_RING_SIZE
-          </code>
-        </span>
+        {source === 'store' ? (
+          <span>
+            Durable audit store: hash-chained, archived and kept per{' '}
+            <code
+              title="Config section controlling retention and the archive bucket"
+              style={{ fontFamily: 'var(--font-mono)', fontSize: 11, opacity: 0.6, color: colors.TEXT_MUTED }}
+            >
+              advanced.audit
+            </code>
+            . Filters and exports cover the whole store.
+          </span>
+        ) : (
+          <span>
+            Newest {entries.length || 500} entries kept in memory for quick inspection.{' '}
+            <code
+              title="Environment variable controlling the ring size (default 500)"
+              style={{ fontFamily: 'var(--font-mono)', fontSize: 11, opacity: 0.6, color: colors.TEXT_MUTED }}
+            >
+              DGP_AUDIT_RING_SIZE
+            </code>
+          </span>
+        )}
       </div>
 
       {error && <Alert type="error" showIcon message="Fetch failed" description={error} />}
 
+      {verification && (
+        <Alert
+          type={verification.valid ? 'success' : 'error'}
+          showIcon
+          closable
+          onClose={() => setVerification(null)}
+          message={
+            verification.valid
+              ? `Hash chain intact: ${verification.entries} entries (ids ${verification.first_id ?? '—'}–${verification.last_id ?? '—'})`
+              : `Hash chain broken${verification.broken_at ? ` at entry ${verification.broken_at}` : ''}`
+          }
+          description={
+            verification.problem ??
+            `Head ${verification.head_hash?.slice(0, 16) ?? '—'}…, archived up to entry ${verification.archived_id}.` +
+              (verification.dropped > 0
+                ? ` ${verification.dropped} entries were dropped while the writer fell behind.`
+                : '')
+          }
+        />
+      )}
+
+      {/* Server-side filters + export */}
+      <Space size="small" style={{ flexWrap: 'wrap' }}>
+        {draftInput('user', 'User')}
+        {draftInput('bucket', 'Bucket')}
+        {draftInput('key', 'Key prefix', 180)}
+        {draftInput('action', 'Action')}
+        <Input
+          type="datetime-local"
+          size="middle"
+          aria-label="From"
+          value={draft.from ?? ''}
+          onChange={(e) => setDraft((d) => ({ ...d, from: e.target.value }))}
+          style={{ width: 200 }}
+        />
+        <Input
+          type="datetime-local"
+          size="middle"
+          aria-label="To"
+          value={draft.to ?? ''}
+          onChange={(e) => setDraft((d) => ({ ...d, to: e.target.value }))}
+          style={{ width: 200 }}
+        />
+        <Button size="middle" type="primary" onClick={applyFilters}>
+          Apply
+        </Button>
+        <Button
+          size="middle"
+          icon={<DownloadOutlined />}
+          loading={busy === 'csv'}
+          onClick={() => void download('csv')}
+        >
+          CSV
+        </Button>
+        <Button
+          size="middle"
+          icon={<DownloadOutlined />}
+          loading={busy === 'jsonl'}
+          onClick={() => void download('jsonl')}
+        >
+          JSONL
+        </Button>
+        {source === 'store' && (
+          <Button
+            size="middle"
+            icon={<SafetyCertificateOutlined />}
+            loading={busy === 'verify'}
+            onClick={() => void verify()}
+          >
+            Verify chain
+          </Button>
+        )}
+      </Space>
+
       {/* Toolbar */}
       <Space size="middle" style={{ flexWrap: 'wrap' }}>
         <Input
           size="middle"
-          placeholder="Filter (action / user / ip / bucket / path)..."
+          placeholder="Quick filter (action / user / ip / bucket / path)..."
           prefix={<SearchOutlined style={{ color: colors.TEXT_MUTED }} />}
           value={filter}
           onChange={(e) => setFilter(e.target.value)}
@@ -257,7 +422,7 @@ export default function AuditLogPanel({ onSessionExpired }: Props) {
         ) : (
           filtered.map((e, i) => (
             <div
-              key={`${e.timestamp}-${i}`}
+              key={e.id !== undefined ? `id-${e.id}` : `${e.timestamp}-${i}`}
               style={{
                 display: 'grid',
                 gridTemplateColumns: '170px 150px 140px 120px 100px minmax(120px, 1fr)',
@@ -323,6 +488,16 @@ export default function AuditLogPanel({ onSessionExpired }: Props) {
           ))
         )}
       </div>
+      {nextBefore !== undefined && (
+        <Button
+          size="middle"
+          style={{ alignSelf: 'center' }}
+          loading={loading}
+          onClick={() => void refresh(applied, nextBefore)}
+        >
+          Load older
+        </Button>
+      )}
     </div>
   );
 }
@@ -6,7 +6,7 @@ import { qk } from '../queries/keys';
 import { Button, Input, Modal, Radio, Switch, Typography, Space, Alert, Spin } from 'antd';
 import { PlusOutlined, DeleteOutlined, DatabaseOutlined, CloudOutlined, CheckCircleOutlined, ApiOutlined } from '@ant-design/icons';
 import type { BackendHealthEntry, BackendInfo, CreateBackendRequest } from '../adminApi';
-import { createBackend, deleteBackend, probeBackend, testS3Connection, updateAdminConfig, putSection } from '../adminApi';
+import { createBackend, deleteBackend, probeBackend, repairErasureBackend, testS3Connection, updateAdminConfig, putSection } from '../adminApi';
 import { useAdminConfig } from '../queries/config';
 import { useBackends, useBucketOrigins } from '../queries/backends';
 import CreateBucketModal from './CreateBucketModal';
@@ -138,6 +138,7 @@ export default function BackendsPanel({ onSessionExpired }: Props) {
   const [saveResult, setSaveResult] = useState<{ ok: boolean; message: string } | null>(null);
 
   const [testingBackend, setTestingBackend] = useState<string | null>(null);
+  const [repairingBackend, setRepairingBackend] = useState<string | null>(null);
   const [testResult, setTestResult] = useState<{ name: string; ok: boolean; message: string } | null>(null);
 
   // After a mutation, invalidate the shared cache so any panel reading
@@ -272,6 +273,25 @@ export default function BackendsPanel({ onSessionExpired }: Props) {
     }
   };
 
+  // Shard repair reads every blob whose shards disagree; the report is
+  // shown in the same slot as the connection test result.
+  const handleRepair = async (b: BackendInfo) => {
+    setRepairingBackend(b.name);
+    setTestResult(null);
+    try {
+      const r = await repairErasureBackend(b.name);
+      setTestResult({
+        name: b.name,
+        ok: r.failed === 0 && r.unrecoverable === 0,
+        message: `Scanned ${r.blobs_scanned} objects in ${r.buckets} buckets: ${r.shards_repaired} shards repaired, ${r.unrecoverable} unrecoverable, ${r.orphans_removed} orphans removed, ${r.failed} failed.`,
+      });
+    } catch (e) {
+      setTestResult({ name: b.name, ok: false, message: normalizeUiError(e, 'Repair failed') });
+    } finally {
+      setRepairingBackend(null);
+    }
+  };
+
   const resetForm = () => {
     setFormName(''); setFormType('filesystem'); setFormPath('./data');
     setFormEndpoint(''); setFormRegion('us-east-1'); setFormForcePathStyle(true);
@@ -422,8 +442,24 @@ export default function BackendsPanel({ onSessionExpired }: Props) {
                   <div style={{ fontSize: 12, color: colors.TEXT_MUTED, fontFamily: 'var(--font-mono)', overflowWrap: 'anywhere' }}>
                     {b.backend_type === 'filesystem'
                       ? `filesystem: ${b.path}`
-                      : `s3: ${b.endpoint || 'AWS'} (${b.region})`}
+                      : b.backend_type === 'azure'
+                        ? `azure: ${b.endpoint || `${b.account}.blob.core.windows.net`}`
+                        : b.backend_type === 'gcs'
+                          ? `gcs: ${b.endpoint || 'storage.googleapis.com'}${b.project ? ` (${b.project})` : ''}`
+                          : b.backend_type === 'erasure' && b.erasure
+                            ? `erasure: ${b.erasure.data_shards}+${b.erasure.parity_shards} shards, write quorum ${b.erasure.write_quorum}`
+                            : `s3: ${b.endpoint || 'AWS'} (${b.region})`}
                   </div>
+                  {b.erasure && (
+                    <div style={{ display: 'flex', flexWrap: 'wrap', gap: 6, marginTop: 4, fontSize: 11 }}>
+                      {b.erasure.shards.map((shard, i) => (
+                        <span key={shard.backend} style={{ fontFamily: 'var(--font-mono)', color: colors.TEXT_MUTED }}>
+                          {i < b.erasure!.data_shards ? 'data' : 'parity'} {shard.backend}
+                          {shard.health && <HealthBadge h={shard.health} colors={colors} />}
+                        </span>
+                      ))}
+                    </div>
+                  )}
                   <div style={{ fontSize: 11, color: colors.TEXT_MUTED, marginTop: 2 }}>
                     {(() => {
                       const n = countByBackend[b.name] ?? 0;
@@ -450,6 +486,16 @@ export default function BackendsPanel({ onSessionExpired }: Props) {
                 >
                   Test connection
                 </Button>
+                {b.erasure && (
+                  <Button
+                    size="small"
+                    loading={repairingBackend === b.name}
+                    onClick={() => handleRepair(b)}
+                    title="Rewrite missing and stale shards from the surviving ones"
+                  >
+                    Repair shards
+                  </Button>
+                )}
                 {!b.is_synthesized && (
                   <Button size="small" icon={<DeleteOutlined />} danger onClick={() => handleDelete(b.name)} title="Remove backend" />
                 )}
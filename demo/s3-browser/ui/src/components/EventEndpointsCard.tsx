@@ -0,0 +1,308 @@
+/**
+ * EventEndpointsCard — the `advanced.event_delivery.endpoints` editor.
+ *
+ * Renders INSIDE WebhookDeliveryPanel and edits the SAME `useSectionEditor`
+ * value (the panel passes `form` + `setField` + the per-instance `nextId`), so
+ * discard / dirty-dot / ⌘S stay correct — no parallel state mirror.
+ *
+ * Each named endpoint has its own filter, format, headers, signing secret and
+ * retry policy, and walks the outbox from its own cursor: one that is down
+ * only holds back itself. Secrets follow the header UX (masked → "unchanged —
+ * type to replace"); they are restored server-side BY ENDPOINT NAME, which is
+ * why `buildPayloadFromForm` blocks renaming an endpoint with masked secrets.
+ *
+ * No tooltips/popovers and no AntD Select popups (broken in this layout) —
+ * checkboxes, Radio.Button and one-entry-per-line text areas only.
+ */
+import { Button, Checkbox, Input, InputNumber, Radio, Space, Switch, Tag, Typography } from 'antd';
+import { DeleteOutlined, NodeIndexOutlined } from '@ant-design/icons';
+import SectionHeader from './SectionHeader';
+import FormField from './FormField';
+import RowListEditor from './RowListEditor';
+import MaskedSecretInput from './MaskedSecretInput';
+import { AdvancedDisclosure } from './ruleEditorFields';
+import type { EventEndpointStatus } from '../adminApi';
+import {
+  ENDPOINT_EVENT_KINDS,
+  newEndpointRow,
+  type EventEndpointRow,
+  type WebhookFormState,
+  type WebhookHeaderRow,
+} from './webhookDeliveryPayload';
+
+const { Text } = Typography;
+const { TextArea } = Input;
+
+interface Props {
+  form: WebhookFormState;
+  setField: (
+    patch: Partial<WebhookFormState> | ((prev: WebhookFormState) => Partial<WebhookFormState>),
+  ) => void;
+  nextId: () => string;
+  inputRadius: React.CSSProperties;
+  /** Live delivery progress by endpoint name (best-effort; may be empty). */
+  statuses: EventEndpointStatus[];
+  latestEventId: number | null;
+}
+
+export default function EventEndpointsCard({
+  form,
+  setField,
+  nextId,
+  inputRadius,
+  statuses,
+  latestEventId,
+}: Props) {
+  const mono: React.CSSProperties = { ...inputRadius, fontFamily: 'var(--font-mono)', fontSize: 14 };
+
+  return (
+    <>
+      <SectionHeader
+        icon={<NodeIndexOutlined />}
+        title="Routed endpoints"
+        description="Named endpoints that each receive only the events their filter matches, with their own retry policy. A failing endpoint never holds back the others."
+      />
+      <RowListEditor<EventEndpointRow>
+        items={form.endpoints}
+        onChange={(endpoints) => setField({ endpoints })}
+        newItem={() => newEndpointRow(nextId)}
+        addLabel="Add routed endpoint"
+        emptyHint={
+          <Text type="secondary" style={{ fontSize: 13 }}>
+            No routed endpoints. Add one to send a team only its own bucket's events.
+          </Text>
+        }
+        renderRow={(row, update, remove) => {
+          const status = statuses.find((s) => s.name === row.origName);
+          const lag =
+            status?.cursor != null && latestEventId != null
+              ? Math.max(0, latestEventId - status.cursor)
+              : null;
+          return (
+            <div
+              style={{
+                border: '1px solid var(--ant-color-border, #d9d9d9)',
+                borderRadius: 8,
+                padding: 12,
+                marginBottom: 12,
+              }}
+            >
+              <Space.Compact style={{ width: '100%', marginBottom: 8 }}>
+                <Input
+                  value={row.name}
+                  onChange={(e) => update({ name: e.target.value })}
+                  placeholder="media-team"
+                  style={{ ...mono, width: '30%' }}
+                />
+                {row.urlMasked ? (
+                  <MaskedSecretInput
+                    mode="sentinel"
+                    reveal
+                    value={row.url}
+                    masked
+                    onChange={(url) => update({ url, urlMasked: false })}
+                    style={{ ...inputRadius, fontSize: 14, flex: 1 }}
+                  />
+                ) : (
+                  <Input
+                    value={row.url}
+                    onChange={(e) => update({ url: e.target.value })}
+                    placeholder="https://hooks.example.com/media"
+                    style={{ ...mono, flex: 1 }}
+                  />
+                )}
+                <Button icon={<DeleteOutlined />} onClick={remove} title="Remove endpoint" />
+              </Space.Compact>
+
+              <Space size="middle" wrap style={{ marginBottom: 8 }}>
+                <Switch
+                  checked={row.enabled}
+                  onChange={(enabled) => update({ enabled })}
+                  checkedChildren="On"
+                  unCheckedChildren="Off"
+                />
+                <Radio.Group
+                  value={row.format}
+                  onChange={(e) => update({ format: e.target.value as 'raw' | 'slack' })}
+                  size="small"
+                >
+                  <Radio.Button value="raw">Raw</Radio.Button>
+                  <Radio.Button value="slack">Slack</Radio.Button>
+                </Radio.Group>
+                {status && (
+                  <Text type="secondary" style={{ fontSize: 12 }}>
+                    {lag != null ? `${lag} events behind` : 'starting'}
+                    {status.attempts > 0 && ` · retrying (attempt ${status.attempts})`}
+                    {status.skipped > 0 && ` · ${status.skipped} skipped`}
+                  </Text>
+                )}
+                {status?.last_error && status.attempts > 0 && (
+                  <Tag color="red" title={status.last_error}>
+                    {status.last_error.slice(0, 60)}
+                  </Tag>
+                )}
+              </Space>
+
+              <FormField
+                label="Event kinds"
+                yamlPath="advanced.event_delivery.endpoints[].filter.kinds"
+                helpText="None checked = every object event. Read and security events (ObjectAccessed, AccessDenied, AuthFailed) only arrive when checked."
+              >
+                <Checkbox.Group
+                  value={row.kinds}
+                  onChange={(kinds) => update({ kinds: kinds as string[] })}
+                  options={ENDPOINT_EVENT_KINDS.map((k) => ({ label: k, value: k }))}
+                />
+              </FormField>
+              <FormField
+                label="Buckets"
+                yamlPath="advanced.event_delivery.endpoints[].filter.buckets"
+                helpText="One bucket per line. Empty = every bucket."
+              >
+                <TextArea
+                  value={row.bucketsText}
+                  onChange={(e) => update({ bucketsText: e.target.value })}
+                  autoSize={{ minRows: 1, maxRows: 4 }}
+                  placeholder="media"
+                  style={mono}
+                />
+              </FormField>
+              <FormField
+                label="Key globs"
+                yamlPath="advanced.event_delivery.endpoints[].filter.include_globs"
+                helpText="One glob per line, e.g. videos/** or **/*.zip. Empty = every key."
+              >
+                <TextArea
+                  value={row.includeText}
+                  onChange={(e) => update({ includeText: e.target.value })}
+                  autoSize={{ minRows: 1, maxRows: 4 }}
+                  placeholder="videos/**"
+                  style={mono}
+                />
+              </FormField>
+
+              <AdvancedDisclosure title="More filters, headers, signing and retry">
+                <FormField
+                  label="Exclude globs"
+                  yamlPath="advanced.event_delivery.endpoints[].filter.exclude_globs"
+                  helpText="Keys matching any of these never match (exclude wins)."
+                >
+                  <TextArea
+                    value={row.excludeText}
+                    onChange={(e) => update({ excludeText: e.target.value })}
+                    autoSize={{ minRows: 1, maxRows: 4 }}
+                    placeholder="**/*.tmp"
+                    style={mono}
+                  />
+                </FormField>
+                <FormField
+                  label="Size range (bytes)"
+                  yamlPath="advanced.event_delivery.endpoints[].filter.min_size"
+                  helpText="Only objects within this size. Setting either bound limits the endpoint to object writes."
+                >
+                  <Space>
+                    <InputNumber
+                      value={row.minSize}
+                      min={0}
+                      placeholder="min"
+                      onChange={(v) => update({ minSize: typeof v === 'number' ? v : null })}
+                    />
+                    <InputNumber
+                      value={row.maxSize}
+                      min={0}
+                      placeholder="max"
+                      onChange={(v) => update({ maxSize: typeof v === 'number' ? v : null })}
+                    />
+                  </Space>
+                </FormField>
+                <FormField
+                  label="User metadata"
+                  yamlPath="advanced.event_delivery.endpoints[].filter.metadata"
+                  helpText="One key=value per line (x-amz-meta-* without the prefix). Every pair must match."
+                >
+                  <TextArea
+                    value={row.metadataText}
+                    onChange={(e) => update({ metadataText: e.target.value })}
+                    autoSize={{ minRows: 1, maxRows: 4 }}
+                    placeholder="team=video"
+                    style={mono}
+                  />
+                </FormField>
+                <FormField
+                  label="Headers"
+                  yamlPath="advanced.event_delivery.endpoints[].headers"
+                  helpText="Static headers for this endpoint. Values are shown masked; leave a masked value untouched to keep it."
+                >
+                  <RowListEditor<WebhookHeaderRow>
+                    items={row.headerRows}
+                    onChange={(headerRows) => update({ headerRows })}
+                    newItem={() => ({ id: nextId(), name: '', value: '', origName: '', masked: false })}
+                    addLabel="Add header"
+                    renderRow={(h, updateHeader, removeHeader) => (
+                      <Space.Compact style={{ width: '100%' }}>
+                        <Input
+                          value={h.name}
+                          onChange={(e) => updateHeader({ name: e.target.value })}
+                          placeholder="Authorization"
+                          style={{ ...mono, width: '40%' }}
+                        />
+                        <MaskedSecretInput
+                          mode="sentinel"
+                          reveal
+                          value={h.value}
+                          masked={h.masked}
+                          onChange={(value) => updateHeader({ value, masked: false })}
+                          style={{ ...inputRadius, fontSize: 14, flex: 1 }}
+                        />
+                        <Button icon={<DeleteOutlined />} onClick={removeHeader} title="Remove header" />
+                      </Space.Compact>
+                    )}
+                  />
+                </FormField>
+                <FormField
+                  label="Signing secret"
+                  yamlPath="advanced.event_delivery.endpoints[].signing.secret"
+                  helpText="Signs each delivery with an X-DGP-Signature header. Empty = unsigned."
+                >
+                  <MaskedSecretInput
+                    mode="sentinel"
+                    value={row.signingSecret}
+                    masked={row.signingMasked}
+                    onChange={(signingSecret) => update({ signingSecret, signingMasked: false })}
+                    style={{ ...inputRadius, fontSize: 14, maxWidth: 360 }}
+                  />
+                </FormField>
+                <FormField
+                  label="Retry policy"
+                  yamlPath="advanced.event_delivery.endpoints[].max_attempts"
+                  helpText="Max attempts per event, initial and maximum backoff. Empty = the delivery-tuning defaults. An event that runs out of attempts is skipped."
+                >
+                  <Space wrap>
+                    <InputNumber
+                      value={row.maxAttempts}
+                      min={1}
+                      placeholder={String(form.max_attempts)}
+                      onChange={(v) => update({ maxAttempts: typeof v === 'number' ? v : null })}
+                    />
+                    <Input
+                      value={row.retryBase}
+                      onChange={(e) => update({ retryBase: e.target.value })}
+                      placeholder={form.retry_base}
+                      style={{ ...mono, width: 100 }}
+                    />
+                    <Input
+                      value={row.retryMax}
+                      onChange={(e) => update({ retryMax: e.target.value })}
+                      placeholder={form.retry_max}
+                      style={{ ...mono, width: 100 }}
+                    />
+                  </Space>
+                </FormField>
+              </AdvancedDisclosure>
+            </div>
+          );
+        }}
+      />
+    </>
+  );
+}
@@ -32,11 +32,16 @@ export default function ReplicationApplySummary({ replication }: { replication:
             <Text code>{rule.name}</Text>{' '}
             <Tag color={rule.enabled ? 'success' : 'default'}>{rule.enabled ? 'enabled' : 'disabled'}</Tag>
             <div>
-              <Text strong>Source:</Text> {rule.source.bucket}/{rule.source.prefix || '*'} →{' '}
+              <Text strong>Source:</Text> {rule.source.bucket}/{rule.source.prefix || '*'} {rule.direction === 'bidirectional' ? '↔' : '→'}{' '}
               <Text strong>Destination:</Text> {rule.destination.bucket}/{rule.destination.prefix || '(same key)'}
             </div>
             <div>
-              Conflict: <Text code>{rule.conflict}</Text> · every {rule.interval} · batch {rule.batch_size} · deletes {rule.replicate_deletes ? 'replicated' : 'not replicated'}
+              {rule.direction === 'bidirectional' ? (
+                <>Bidirectional · tombstones {rule.tombstone_retention}</>
+              ) : (
+                <>Conflict: <Text code>{rule.conflict}</Text></>
+              )}{' '}
+              · every {rule.interval} · batch {rule.batch_size} · deletes {rule.replicate_deletes ? 'replicated' : 'not replicated'}
             </div>
             {(rule.include_globs.length > 0 || rule.exclude_globs.length > 0) && (
               <div>
@@ -25,6 +25,7 @@ export default function ReplicationRuleFields({
   onChange: (patch: Partial<ReplicationRuleConfig>) => void;
   onRename: (nextName: string) => void;
 }) {
+  const bidirectional = rule.direction === 'bidirectional';
   return (
     <div>
       <div style={{ display: 'grid', gridTemplateColumns: 'repeat(auto-fit, minmax(260px, 1fr))', gap: 14 }}>
@@ -72,6 +73,19 @@ export default function ReplicationRuleFields({
             prefixPlaceholder="mirror/releases/"
           />
         </FormField>
+        <FormField
+          label="Direction"
+          yamlPath="storage.replication.rules[].direction"
+          helpText="Bidirectional keeps both endpoints in sync when both take writes. Keys changed on both sides are held as conflicts for you to resolve in the Conflicts tab."
+        >
+          <Radio.Group
+            value={rule.direction}
+            onChange={(e) => onChange({ direction: e.target.value })}
+          >
+            <Radio value="one-way">One-way</Radio>
+            <Radio value="bidirectional">Bidirectional</Radio>
+          </Radio.Group>
+        </FormField>
       </div>
 
       <AdvancedDisclosure title="Advanced rule behavior">
@@ -101,24 +115,55 @@ export default function ReplicationRuleFields({
             />
           </FormField>
           <FormField
-            label="Conflict policy"
-            yamlPath="storage.replication.rules[].conflict"
-            helpText="How to resolve when the destination object already exists."
+            label="Bandwidth cap (Mbit/s)"
+            yamlPath="storage.replication.rules[].bandwidth_limit_mbps"
+            helpText="Ceiling on this rule's copy throughput, shared by the reconcile sweep and event-driven copies. Empty = uncapped (the global replication cap still applies). Allowed-hours windows are set in YAML (rules[].windows)."
           >
-            <Radio.Group
-              value={rule.conflict}
-              onChange={(e) => onChange({ conflict: e.target.value })}
-              style={{ display: 'flex', flexDirection: 'column', gap: 6 }}
-            >
-              <Radio value="newer-wins">Newer wins — safest default</Radio>
-              <Radio value="content-diff">Content diff — mirror (copy only when bytes differ)</Radio>
-              <Radio value="skip-if-dest-exists">Skip existing destination objects</Radio>
-            </Radio.Group>
+            <InputNumber
+              value={rule.bandwidth_limit_mbps ?? null}
+              onChange={(v) => onChange({ bandwidth_limit_mbps: v || undefined })}
+              min={1}
+              placeholder="uncapped"
+              style={{ width: '100%', ...inputRadius }}
+            />
           </FormField>
+          {bidirectional ? (
+            <FormField
+              label="Tombstone retention"
+              yamlPath="storage.replication.rules[].tombstone_retention"
+              helpText="How long a propagated delete is remembered. A stale copy reappearing within this window is deleted again; after it, it is treated as a new object. Default 30d."
+            >
+              <Input
+                value={rule.tombstone_retention}
+                onChange={(e) => onChange({ tombstone_retention: e.target.value })}
+                style={{ ...inputRadius, fontFamily: 'var(--font-mono)' }}
+              />
+            </FormField>
+          ) : (
+            <FormField
+              label="Conflict policy"
+              yamlPath="storage.replication.rules[].conflict"
+              helpText="How to resolve when the destination object already exists."
+            >
+              <Radio.Group
+                value={rule.conflict}
+                onChange={(e) => onChange({ conflict: e.target.value })}
+                style={{ display: 'flex', flexDirection: 'column', gap: 6 }}
+              >
+                <Radio value="newer-wins">Newer wins — safest default</Radio>
+                <Radio value="content-diff">Content diff — mirror (copy only when bytes differ)</Radio>
+                <Radio value="skip-if-dest-exists">Skip existing destination objects</Radio>
+              </Radio.Group>
+            </FormField>
+          )}
           <FormField
             label="Delete replication"
             yamlPath="storage.replication.rules[].replicate_deletes"
-            helpText="Make the destination a faithful mirror: any object not present at source is deleted."
+            helpText={
+              bidirectional
+                ? 'Propagate deletes in both directions. Off: a key deleted on one side is restored from the other.'
+                : 'Make the destination a faithful mirror: any object not present at source is deleted.'
+            }
           >
             <Switch
               checked={rule.replicate_deletes}
@@ -128,7 +173,11 @@ export default function ReplicationRuleFields({
               type="warning"
               showIcon
               message="Deletes are destructive"
-              description="When enabled, the destination is a faithful mirror of the source: ANY destination object absent at source is deleted — including objects written by other tools. The destination bucket must be dedicated to this rule."
+              description={
+                bidirectional
+                  ? 'When enabled, deleting a key on either side deletes it on the other. A key deleted on one side and modified on the other is held as a conflict instead.'
+                  : 'When enabled, the destination is a faithful mirror of the source: ANY destination object absent at source is deleted — including objects written by other tools. The destination bucket must be dedicated to this rule.'
+              }
               style={{ marginTop: 8 }}
             />
           </FormField>
@@ -70,6 +70,8 @@ interface TraceDecision {
 interface TraceResponse {
   resolved: TraceResolved;
   admission: TraceDecision;
+  /** Throttle blocks that would charge the request (after SigV4). */
+  throttles?: string[];
 }
 
 interface Props {
@@ -85,6 +87,7 @@ export default function TracePanel({ onSessionExpired }: Props) {
   const [query, setQuery] = useState('');
   const [sourceIp, setSourceIp] = useState('');
   const [authenticated, setAuthenticated] = useState(false);
+  const [accessKey, setAccessKey] = useState('');
   const [running, setRunning] = useState(false);
   const [result, setResult] = useState<TraceResponse | null>(null);
   const [error, setError] = useState<string | null>(null);
@@ -93,7 +96,14 @@ export default function TracePanel({ onSessionExpired }: Props) {
     setRunning(true);
     setError(null);
     setResult(null);
-    const body = buildTraceBody({ method, path, query, sourceIp, authenticated });
+    const body = buildTraceBody({
+      method,
+      path,
+      query,
+      sourceIp,
+      authenticated,
+      accessKey: authenticated ? accessKey : '',
+    });
     try {
       const res = await adminFetch('/api/admin/config/trace', 'POST', body);
       if (!res.ok) {
@@ -193,6 +203,20 @@ export default function TracePanel({ onSessionExpired }: Props) {
             </Space>
           </FormField>
 
+          {authenticated && (
+            <FormField
+              label="Access key"
+              helpText="Optional. Lists the throttle blocks this key would be charged to; its IAM groups are resolved server-side."
+            >
+              <Input
+                value={accessKey}
+                onChange={(e) => setAccessKey(e.target.value)}
+                placeholder="(any)"
+                style={{ ...inputRadius, fontFamily: 'var(--font-mono)', fontSize: 13 }}
+              />
+            </FormField>
+          )}
+
           <Button
             type="primary"
             icon={<ExperimentOutlined />}
@@ -390,6 +414,10 @@ function ReasonPath({ result }: { result: TraceResponse }) {
             ? 'action: continue (fall through to next layer — SigV4 auth)'
             : `action: ${admission.decision}`;
   lines.push(`  → ${actionLine}`);
+  // Line 4: throttle blocks, which apply independently of the decision.
+  if (result.throttles && result.throttles.length > 0) {
+    lines.push(`  → throttled by: ${result.throttles.join(', ')}`);
+  }
 
   return (
     <pre
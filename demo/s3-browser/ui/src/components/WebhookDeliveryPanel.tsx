@@ -14,6 +14,11 @@
  * still-masked header is BLOCKED (the secret can't follow the rename) — the
  * operator must re-type the value or remove/re-add.
  *
+ * Named routed endpoints (`event_delivery.endpoints`) live in
+ * EventEndpointsCard below the destination zone, and the opt-in read and
+ * security events (`event_delivery.access_events`) in AccessEventsCard; both
+ * edit the same value.
+ *
  * Usability invariants (usability bugs ARE bugs): enabling with no endpoint is
  * blocked; duration fields hint the format; numeric ranges validated; rows use
  * stable ids; the masked sentinel is never shown or saved as a real value;
@@ -34,7 +39,7 @@ import {
 } from 'antd';
 import { ApiOutlined, DeleteOutlined } from '@ant-design/icons';
 import type { SectionApplyResponse } from '../adminApi';
-import { fetchEventOutbox } from '../adminApi';
+import { fetchEventOutbox, type EventEndpointStatus } from '../adminApi';
 import { useCardStyles, contentColumn, CONTENT_WIDE } from './shared-styles';
 import { LoadingState } from './StatePlaceholders';
 import { useColors } from '../ThemeContext';
@@ -46,11 +51,14 @@ import FormField from './FormField';
 import ApplyDialog from './ApplyDialog';
 import { AdvancedDisclosure } from './ruleEditorFields';
 import SlackConnectorCard from './SlackConnectorCard';
+import EventEndpointsCard from './EventEndpointsCard';
+import AccessEventsCard from './AccessEventsCard';
 import StickyDirtyBar from './StickyDirtyBar';
 import RowListEditor from './RowListEditor';
 import MaskedSecretInput from './MaskedSecretInput';
 import {
   formFromWire,
+  accessEventsFromWire,
   buildPayloadFromForm,
   type WebhookFormState,
   type WebhookHeaderRow,
@@ -90,6 +98,8 @@ const EMPTY_FORM: WebhookFormState = {
   slackExcludeRows: [],
   slackNotifyKinds: ['ObjectCreated'],
   slackRoutes: [],
+  endpoints: [],
+  accessEvents: accessEventsFromWire(undefined),
 };
 
 function PanelShell({ children }: { children: React.ReactNode }) {
@@ -196,6 +206,8 @@ export default function WebhookDeliveryPanel({ onSessionExpired }: Props) {
     failed: number;
     enabled: boolean;
     active: boolean;
+    endpoints: EventEndpointStatus[];
+    latestEventId: number | null;
   } | null>(null);
   useEffect(() => {
     let alive = true;
@@ -207,6 +219,8 @@ export default function WebhookDeliveryPanel({ onSessionExpired }: Props) {
           failed: r.counts.failed,
           enabled: r.delivery_enabled,
           active: r.delivery_active,
+          endpoints: r.endpoints ?? [],
+          latestEventId: r.latest_event_id ?? null,
         });
       })
       .catch(() => {});
@@ -541,6 +555,25 @@ export default function WebhookDeliveryPanel({ onSessionExpired }: Props) {
         )}
       </div>
 
+      {/* ── 3. ROUTED ENDPOINTS — filtered, independently-cursored destinations ── */}
+      <ConnectorDivider label="Routed endpoints" />
+      <div style={connectorCardStyle}>
+        <EventEndpointsCard
+          form={form}
+          setField={setField}
+          nextId={nextId}
+          inputRadius={inputRadius}
+          statuses={outbox?.endpoints ?? []}
+          latestEventId={outbox?.latestEventId ?? null}
+        />
+      </div>
+
+      {/* ── 4. ACCESS EVENTS — opt-in reads and security events ── */}
+      <ConnectorDivider label="Access events" />
+      <div style={connectorCardStyle}>
+        <AccessEventsCard form={form} setField={setField} inputRadius={inputRadius} />
+      </div>
+
       <ApplyDialog
         open={applyOpen}
         section="advanced"
@@ -62,6 +62,15 @@ export interface BucketPolicyRow {
    *  policy is the marker doesn't round-trip as "all default" and get its
    *  policy DELETED on apply (the silent-field-loss bug class). */
   replication_target_only: boolean;
+  /** Read-only passthrough of `read_fallback` (YAML-only, same reason as
+   *  the marker above). */
+  read_fallback: ReadFallback | null;
+  /** Read-only passthrough of `mirror` (YAML-only, same reason). */
+  mirror: Mirror | null;
+  /** Read-only passthrough of `span` (YAML-only, same reason). */
+  span: Span | null;
+  /** Read-only passthrough of `hedge` (YAML-only, same reason). */
+  hedge: Hedge | null;
 }
 
 /** One bucket's merge-patch: every clearable field present (value or null). */
@@ -79,8 +88,26 @@ export interface BucketPolicyPatch {
   /** `true` preserved verbatim; `null` clears (the field has no editor here,
    *  so it always mirrors what the server sent at fetch time). */
   replication_target_only: boolean | null;
+  /** Preserved verbatim; `null` clears. */
+  read_fallback: ReadFallback | null;
+  /** Preserved verbatim; `null` clears. */
+  mirror: Mirror | null;
+  /** Preserved verbatim; `null` clears. */
+  span: Span | null;
+  /** Preserved verbatim; `null` clears. */
+  hedge: Hedge | null;
 }
 
+type ReadFallback = NonNullable<
+  NonNullable<AdminConfig['bucket_policies']>[string]['read_fallback']
+>;
+
+type Mirror = NonNullable<NonNullable<AdminConfig['bucket_policies']>[string]['mirror']>;
+
+type Span = NonNullable<NonNullable<AdminConfig['bucket_policies']>[string]['span']>;
+
+type Hedge = NonNullable<NonNullable<AdminConfig['bucket_policies']>[string]['hedge']>;
+
 /** The storage-section merge body: per-bucket patch, or `null` = delete policy. */
 type BucketsPatchBody = { buckets: Record<string, BucketPolicyPatch | null> };
 
@@ -94,6 +121,10 @@ export const DEFAULT_ROW_FIELDS: Omit<BucketPolicyRow, '_id' | 'name'> = Object.
   public_prefixes: [],
   quota_bytes: null,
   replication_target_only: false,
+  read_fallback: null,
+  mirror: null,
+  span: null,
+  hedge: null,
 });
 
 let rowIdCounter = 0;
@@ -134,6 +165,10 @@ export function policyToRow(
     public_prefixes: prefixes.map((value) => ({ id: freshId(), value })),
     quota_bytes: p.quota_bytes ?? null,
     replication_target_only: p.replication_target_only ?? false,
+    read_fallback: p.read_fallback ?? null,
+    mirror: p.mirror ?? null,
+    span: p.span ?? null,
+    hedge: p.hedge ?? null,
   };
 }
 
@@ -156,6 +191,10 @@ export function isAllDefaultRow(row: BucketPolicyRow): boolean {
     row.alias === '' &&
     row.quota_bytes === null &&
     !row.replication_target_only &&
+    !row.read_fallback &&
+    !row.mirror &&
+    !row.span &&
+    !row.hedge &&
     (row.publicMode === 'none' ||
       (row.publicMode === 'prefixes' && cleanedPrefixes(row).length === 0))
   );
@@ -187,6 +226,10 @@ function rowToPolicy(row: BucketPolicyRow): BucketPolicyPatch {
     public_prefixes,
     quota_bytes: row.quota_bytes ?? null,
     replication_target_only: row.replication_target_only ? true : null,
+    read_fallback: row.read_fallback ?? null,
+    mirror: row.mirror ?? null,
+    span: row.span ?? null,
+    hedge: row.hedge ?? null,
   };
 }
 
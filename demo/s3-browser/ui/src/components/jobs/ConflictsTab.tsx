@@ -0,0 +1,122 @@
+/**
+ * Conflicts tab for a bidirectional replication rule: keys changed on both
+ * sides (or deleted on one, modified on the other) that the sweep holds for
+ * manual resolution. "Keep source" / "Keep destination" makes that side's
+ * version — or its deletion — win on both sides.
+ */
+import { Alert, Button, Popconfirm, Space, Tag, Typography, message } from 'antd';
+import type { ConflictSide, JobConflictEntry } from '../../adminApi';
+import { useJobConflicts, useResolveConflict } from '../../queries/jobs';
+import { formatBytes } from '../../utils';
+import TimeAgo from '../TimeAgo';
+import RecordList from './RecordList';
+
+const { Text } = Typography;
+
+const KIND_LABEL: Record<JobConflictEntry['kind'], string> = {
+  'both-created': 'created on both sides',
+  'both-modified': 'modified on both sides',
+  'deleted-on-source': 'deleted on source, modified on destination',
+  'deleted-on-destination': 'deleted on destination, modified on source',
+};
+
+function sideSummary(size?: number, stamp?: string): string {
+  if (size === undefined || size === null) return 'deleted';
+  return stamp ? `${formatBytes(size)} · ${stamp}` : formatBytes(size);
+}
+
+export default function ConflictsTab({ jobId }: { jobId: string }) {
+  const query = useJobConflicts(jobId);
+  const resolve = useResolveConflict(jobId);
+  const conflicts = query.data?.conflicts ?? [];
+  const total = query.data?.total ?? 0;
+
+  const keep = (c: JobConflictEntry, side: ConflictSide) =>
+    resolve.mutate(
+      { key: c.key, keep: side },
+      {
+        onSuccess: () => message.success(`Kept the ${side} version of ${c.key}`),
+        onError: (e) => message.error(e.message),
+      },
+    );
+
+  return (
+    <div>
+      {total > conflicts.length && (
+        <Alert
+          type="info"
+          showIcon
+          style={{ marginBottom: 12 }}
+          message={`Showing the oldest ${conflicts.length} of ${total} conflicts`}
+        />
+      )}
+      <RecordList
+        rows={conflicts}
+        rowKey={(c) => c.key}
+        empty="No conflicts — both sides agree"
+        columns={[
+          {
+            key: 'object',
+            label: 'Object',
+            track: 'minmax(0,1.4fr)',
+            render: (c) => (
+              <div style={{ minWidth: 0 }}>
+                <Text style={{ fontFamily: 'var(--font-mono)' }} ellipsis={{ tooltip: c.source_key }}>
+                  {c.key}
+                </Text>
+                <div>
+                  <Tag>{KIND_LABEL[c.kind] ?? c.kind}</Tag>
+                  <Text type="secondary" style={{ fontSize: 12 }}>
+                    <TimeAgo ts={c.detected_at} />
+                  </Text>
+                </div>
+              </div>
+            ),
+          },
+          {
+            key: 'sides',
+            label: 'Versions',
+            track: 'minmax(0,1fr)',
+            render: (c) => (
+              <div style={{ fontSize: 12 }}>
+                <div>
+                  <Text type="secondary">source </Text>
+                  {sideSummary(c.source_size, c.source_stamp)}
+                </div>
+                <div>
+                  <Text type="secondary">destination </Text>
+                  {sideSummary(c.destination_size, c.destination_stamp)}
+                </div>
+              </div>
+            ),
+          },
+          {
+            key: 'resolve',
+            label: 'Resolve',
+            track: 'max-content',
+            render: (c) => (
+              <Space direction="vertical" size={4}>
+                {(['source', 'destination'] as const).map((side) => (
+                  <Popconfirm
+                    key={side}
+                    title={`Keep the ${side} version?`}
+                    description={
+                      (side === 'source' ? c.source_size : c.destination_size) === undefined
+                        ? 'The key is deleted on the other side too.'
+                        : 'The other side is overwritten with this version.'
+                    }
+                    onConfirm={() => keep(c, side)}
+                  >
+                    <Button size="small" loading={resolve.isPending && resolve.variables?.key === c.key}>
+                      Keep {side}
+                    </Button>
+                  </Popconfirm>
+                ))}
+              </Space>
+            ),
+          },
+        ]}
+      />
+    </div>
+  );
+}
@@ -1,6 +1,7 @@
 /**
  * Job detail drawer: Definition (editable for rule kinds via the parent's
- * section editors; read-only parameters for one-offs), Runs, Failures.
+ * section editors; read-only parameters for one-offs), Runs, Failures, and
+ * Conflicts for bidirectional replication rules.
  */
 import { useEffect, useRef, useMemo } from 'react';
 import { useQueryClient } from '@tanstack/react-query';
@@ -11,13 +12,17 @@ import type { LifecycleConfig, ReplicationConfig } from '../../adminApi';
 import type { JobRow } from '../../jobsView';
 import {
   isActiveJobStatus,
+  jobConflictCount,
+  jobExternal,
   jobInFlight,
   jobWalkProgress,
   jobStrategyMix,
   jobStatusLabel,
   jobStatusTone,
+  jobThrottle,
   kindLabel,
   parseJobId,
+  throttleChip,
 } from '../../jobsView';
 import StrategyBand from './StrategyBand';
 import { formatBytes } from '../../utils';
@@ -29,6 +34,7 @@ import OutcomeMeter from './OutcomeMeter';
 import ReplicationRuleFields from '../ReplicationRuleFields';
 import LifecycleRuleFields from '../LifecycleRuleFields';
 import VerifyTab from './VerifyTab';
+import ConflictsTab from './ConflictsTab';
 import { useBucketNames } from '../../queries/backends';
 
 const { Text } = Typography;
@@ -78,6 +84,13 @@ export default function JobDrawer({
   // run). Shown as an activity indicator so a long HEAD-heavy scan reads as
   // "working, here" rather than "stuck at 0 copied".
   const walk = jobWalkProgress(serverRow);
+  // Bidirectional replication rules only (null otherwise).
+  const conflictCount = jobConflictCount(serverRow);
+  // Bandwidth cap / transfer window (replication rules that set either).
+  const throttle = jobThrottle(serverRow);
+  const chip = throttleChip(throttle);
+  // External S3 endpoints (replication rules with a non-proxy side).
+  const external = jobExternal(serverRow);
   // Runs/failures only exist for jobs the SERVER knows (not drafts).
   // These are NOT polled — the jobs LIST already polls (2s while active) and
   // carries the live progress in `serverRow`. We overlay that onto the running
@@ -325,9 +338,10 @@ export default function JobDrawer({
     if (serverRow) {
       tabs.push('runs', 'failures');
       if (parsed?.subsystem === 'replication') tabs.push('verify');
+      if (conflictCount !== null) tabs.push('conflicts');
     }
     return tabs;
-  }, [serverRow, parsed]);
+  }, [serverRow, parsed, conflictCount]);
 
   const clampedTab = activeTab && availableTabs.includes(activeTab) ? activeTab : 'definition';
 
@@ -357,6 +371,59 @@ export default function JobDrawer({
         )
       }
     >
+      {throttle && (
+        <div
+          style={{
+            marginBottom: 12,
+            padding: '8px 12px',
+            borderRadius: 8,
+            background: c.BG_ELEVATED,
+            fontSize: 12.5,
+          }}
+        >
+          {chip && (
+            <Tag color={chip.color} style={{ marginRight: 8 }}>
+              {chip.label}
+            </Tag>
+          )}
+          <Typography.Text type="secondary">
+            {chip
+              ? chip.hint
+              : throttle.bandwidth_limit_mbps != null
+                ? `Bandwidth cap ${throttle.bandwidth_limit_mbps} Mbit/s; inside the transfer window.`
+                : 'Inside the transfer window.'}
+            {throttle.throttled_seconds > 0 &&
+              ` Held back ${throttle.throttled_seconds.toLocaleString()}s by the cap since the proxy started.`}
+          </Typography.Text>
+        </div>
+      )}
+      {external && (
+        <div
+          style={{
+            marginBottom: 12,
+            padding: '8px 12px',
+            borderRadius: 8,
+            background: c.BG_ELEVATED,
+            fontSize: 12.5,
+          }}
+        >
+          {(['source', 'destination'] as const).map((side) => {
+            const ext = external[side];
+            if (!ext) return null;
+            return (
+              <div key={side}>
+                <Tag style={{ marginRight: 8 }}>external {side}</Tag>
+                <Typography.Text type="secondary">
+                  <span style={{ fontFamily: 'var(--font-mono)' }}>
+                    {ext.endpoint ?? `AWS S3 (${ext.region})`}
+                  </span>
+                  {ext.hydrated ? ' · plain objects' : ' · DeltaGlider layout'}
+                </Typography.Text>
+              </div>
+            );
+          })}
+        </div>
+      )}
       {walk && (
         <div
           style={{
@@ -444,6 +511,15 @@ export default function JobDrawer({
                 },
               ]
             : []),
+          ...(serverRow && jobId && conflictCount !== null
+            ? [
+                {
+                  key: 'conflicts',
+                  label: conflictCount > 0 ? `Conflicts (${conflictCount})` : 'Conflicts',
+                  children: <ConflictsTab jobId={jobId} />,
+                },
+              ]
+            : []),
         ]}
       />
     </Drawer>
@@ -34,8 +34,10 @@ import {
   availableActions,
   jobStatusLabel,
   jobStatusTone,
+  jobThrottle,
   kindLabel,
   mergeDraftRules,
+  throttleChip,
   triggerLabel,
 } from '../../jobsView';
 import { qk } from '../../queries/keys';
@@ -384,7 +386,9 @@ export default function JobsPanel({ onSessionExpired, search }: Props) {
       track: 'minmax(140px,1.2fr)',
       render: (d) => {
         const scope = `${d.row.scope.bucket}${d.row.scope.prefix ? `/${d.row.scope.prefix}` : ''}${
-          d.row.scope.target ? ` → ${d.row.scope.target}` : ''
+          d.row.scope.target
+            ? ` ${d.row.detail?.direction === 'bidirectional' ? '↔' : '→'} ${d.row.scope.target}`
+            : ''
         }`;
         return (
           <Text
@@ -414,6 +418,7 @@ export default function JobsPanel({ onSessionExpired, search }: Props) {
       track: 'minmax(0,1.4fr)',
       render: (d) => {
         const live = d.row.trigger === 'oneoff' && (d.row.status === 'running' || d.row.status === 'cancelling' || d.row.status === 'queued');
+        const chip = throttleChip(jobThrottle(d.row));
         return (
           <div style={{ minWidth: 0 }}>
             {live ? (
@@ -430,6 +435,11 @@ export default function JobsPanel({ onSessionExpired, search }: Props) {
                 {jobStatusLabel(d.row)}
               </Tag>
             )}
+            {chip && (
+              <Tag color={chip.color} style={{ margin: '0 0 0 4px' }} title={chip.hint}>
+                {chip.label}
+              </Tag>
+            )}
             {!live && d.row.last_error && (
               <Text type="danger" style={{ display: 'block', fontSize: 11, marginTop: 2 }} ellipsis title={d.row.last_error}>
                 {d.row.last_error}
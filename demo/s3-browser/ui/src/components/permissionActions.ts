@@ -3,9 +3,11 @@
  *
  * The IAM action set is {read, write, delete, list, admin} — a SET, not a
  * cumulative ladder, so "write without delete" is expressible. The wildcard
- * `*` is the collapsed form of all five. These helpers keep the toggle logic
- * (expand `*`, collapse-to-`*`, canonical ordering) out of the component so it
- * is unit-testable without React. No React/antd imports.
+ * `*` is the collapsed form of all five. Rules may also carry operation-level
+ * `s3:` actions authored in YAML/JSON; those pass through the chip toggles
+ * untouched. These helpers keep the toggle logic (expand `*`, collapse-to-`*`,
+ * canonical ordering) out of the component so it is unit-testable without
+ * React. No React/antd imports.
  */
 
 import { parseResourcePattern } from '../storagePath';
@@ -58,7 +60,15 @@ export function toggleAction(actions: string[], value: string): string[] {
   if (set.has(value)) set.delete(value);
   else set.add(value);
   if (set.size === ATOMIC_ACTIONS.length) return ['*'];
-  return ATOMIC_ACTIONS.filter((a) => set.has(a));
+  return [...ATOMIC_ACTIONS.filter((a) => set.has(a)), ...operationActions(actions)];
+}
+
+/**
+ * Operation-level `s3:` actions (`s3:PutObjectTagging`, `s3:Get*`) held by the
+ * rule. The chips don't render them, but toggling a verb must not drop them.
+ */
+export function operationActions(actions: string[]): string[] {
+  return actions.filter((a) => a.toLowerCase().startsWith('s3:'));
 }
 
 /**
@@ -76,7 +86,7 @@ export function reconcileActionsForScope(actions: string[], prefixScoped: boolea
   const set = effectiveActions(actions);
   if (!set.has('admin')) return actions;
   set.delete('admin');
-  return ATOMIC_ACTIONS.filter((a) => set.has(a));
+  return [...ATOMIC_ACTIONS.filter((a) => set.has(a)), ...operationActions(actions)];
 }
 
 /**
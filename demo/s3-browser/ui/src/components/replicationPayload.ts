@@ -36,6 +36,8 @@ export function emptyRule(existing: ReplicationRuleConfig[]): ReplicationRuleCon
     interval: '15m',
     batch_size: 100,
     replicate_deletes: false,
+    direction: 'one-way',
+    tombstone_retention: '30d',
     conflict: 'newer-wins',
     include_globs: [],
     exclude_globs: ['.dg/*'],
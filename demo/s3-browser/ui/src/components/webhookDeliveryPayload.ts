@@ -31,6 +31,16 @@
  *    token is a SECRET with the SAME mask round-trip as header values: masked to
  *    the sentinel on GET, passed through untouched (= "unchanged", server
  *    restores), overwritten when retyped, cleared to `null` when emptied.
+ *
+ * 5. **Named endpoints.** `endpoints` is a LIST, so the merge-patch replaces
+ *    it wholesale: removed endpoints/headers simply drop out, no `null`s. Their
+ *    secrets (header values, signing secret, a Slack-format URL) come back
+ *    masked and the server restores them BY ENDPOINT NAME — so renaming an
+ *    endpoint whose secrets are still masked is blocked, like a masked header.
+ *
+ * 6. **Access events.** `access_events` (read and security events) is a plain
+ *    object with no secrets; it is always sent whole, so a merge-patch leaves
+ *    nothing stale behind.
  */
 
 const WEBHOOK_REDACTED_SENTINEL = '__redacted__';
@@ -46,6 +56,12 @@ export const SLACK_NOTIFY_KINDS = [
   'LifecycleExpired',
 ] as const;
 
+/** Kinds a named endpoint can subscribe to: the object events plus the
+ *  opt-in read and security events, which go ONLY to endpoints listing them.
+ *  Mirrors `ENDPOINT_EVENT_KINDS` in src/event_endpoints.rs. */
+export const ACCESS_EVENT_KINDS = ['ObjectAccessed', 'AccessDenied', 'AuthFailed'] as const;
+export const ENDPOINT_EVENT_KINDS = [...SLACK_NOTIFY_KINDS, ...ACCESS_EVENT_KINDS] as const;
+
 type EventDeliveryFormat = 'raw' | 'slack';
 
 /** One bucket/prefix → channel routing rule, wire shape. Mirrors `SlackRoute`
@@ -118,6 +134,46 @@ const DEFAULT_EVENT_DELIVERY: EventDeliveryConfig = {
   slack_routes: [],
 };
 
+/** Wire shape of one named endpoint's filter. Mirrors `EventFilter`. */
+interface EventFilterWire {
+  kinds?: string[];
+  buckets?: string[];
+  include_globs?: string[];
+  exclude_globs?: string[];
+  min_size?: number | null;
+  max_size?: number | null;
+  metadata?: Record<string, string>;
+}
+
+/** Wire shape of one named endpoint. Mirrors `EventEndpoint` in
+ *  src/config_sections.rs. */
+interface EventEndpointWire {
+  name: string;
+  enabled?: boolean;
+  url: string;
+  format?: EventDeliveryFormat;
+  headers?: Record<string, string>;
+  signing?: { secret: string; previous_secret?: string | null } | null;
+  filter?: EventFilterWire;
+  max_attempts?: number | null;
+  retry_base?: string | null;
+  retry_max?: string | null;
+}
+
+/** Wire shape of `event_delivery.access_events`. Mirrors
+ *  `AccessEventsConfig` in src/config_sections.rs. */
+interface AccessEventsWire {
+  enabled?: boolean;
+  buckets?: string[];
+  include_globs?: string[];
+  exclude_globs?: string[];
+  include_head?: boolean;
+  sample_rate?: number;
+  security_events?: boolean;
+  retention?: string;
+  max_rows?: number;
+}
+
 /** The wire shape of `event_delivery` as the server GET returns it. */
 export interface EventDeliveryWire {
   enabled?: boolean;
@@ -143,6 +199,8 @@ export interface EventDeliveryWire {
   slack_exclude_globs?: string[];
   slack_notify_kinds?: string[];
   slack_routes?: SlackRouteWire[];
+  endpoints?: EventEndpointWire[];
+  access_events?: AccessEventsWire;
 }
 
 export interface AdvancedSectionWebhookBody {
@@ -241,7 +299,8 @@ function routeHasContent(r: SlackRouteNorm): boolean {
  */
 function buildEventDeliveryPayload(
   local: EventDeliveryConfig,
-  baseline: EventDeliveryConfig
+  baseline: EventDeliveryConfig,
+  enabledEndpoints = 0
 ): ValidationResult {
   const errors: string[] = [];
 
@@ -258,7 +317,7 @@ function buildEventDeliveryPayload(
 
   if (!isSlack) {
     // Usability invariant: enabling delivery with no endpoint is a no-op trap.
-    if (local.enabled && urls.length === 0) {
+    if (local.enabled && urls.length === 0 && enabledEndpoints === 0) {
       errors.push('Delivery is enabled but no endpoint is set — add at least one webhook URL or turn delivery off.');
     }
   } else {
@@ -271,7 +330,7 @@ function buildEventDeliveryPayload(
       if (local.slack_channel.trim().length === 0 && meaningfulRoutes.length === 0) {
         errors.push('Slack bot-token mode needs a channel.');
       }
-    } else if (local.enabled && urls.length === 0) {
+    } else if (local.enabled && urls.length === 0 && enabledEndpoints === 0) {
       // Webhook mode: the hooks.slack.com URL is the only delivery path.
       errors.push('Delivery is enabled but no Slack Incoming Webhook URL is set — add the hooks.slack.com URL or turn delivery off.');
     }
@@ -455,6 +514,52 @@ export interface WebhookHeaderRow {
   masked: boolean;
 }
 
+/** One named endpoint (`event_delivery.endpoints[]`) in the editor. List
+ *  fields are edited as one-entry-per-line text; `metadataText` as
+ *  `key=value` lines. Retry fields left empty inherit the top-level policy. */
+export interface EventEndpointRow {
+  id: string;
+  name: string;
+  /** The name as loaded ('' for a new row). Secrets are restored by name, so
+   *  a rename while any secret is masked would lose them. */
+  origName: string;
+  enabled: boolean;
+  url: string;
+  /** True while `url` is the server sentinel (a masked Slack webhook URL). */
+  urlMasked: boolean;
+  format: EventDeliveryFormat;
+  headerRows: WebhookHeaderRow[];
+  /** Signing secret; '' = unsigned. Equals the sentinel while masked. */
+  signingSecret: string;
+  signingMasked: boolean;
+  /** Carried through untouched (sentinel while masked); '' = none. */
+  signingPrevious: string;
+  kinds: string[];
+  bucketsText: string;
+  includeText: string;
+  excludeText: string;
+  minSize: number | null;
+  maxSize: number | null;
+  metadataText: string;
+  maxAttempts: number | null;
+  retryBase: string;
+  retryMax: string;
+}
+
+/** `event_delivery.access_events` in the editor; lists as one-per-line text. */
+export interface AccessEventsForm {
+  enabled: boolean;
+  bucketsText: string;
+  includeText: string;
+  excludeText: string;
+  includeHead: boolean;
+  /** Fraction of matching reads recorded, 0–1. */
+  sampleRate: number;
+  securityEvents: boolean;
+  retention: string;
+  maxRows: number;
+}
+
 export interface WebhookFormState {
   enabled: boolean;
   urlRows: WebhookUrlRow[];
@@ -498,6 +603,10 @@ export interface WebhookFormState {
   /** Per-bucket / per-prefix → channel routes (bot-token mode only). Empty =
    *  the single-channel behavior (`slackChannel` is the only destination). */
   slackRoutes: SlackRouteRow[];
+  /** Named endpoints, each with its own filter, cursor and retry policy. */
+  endpoints: EventEndpointRow[];
+  /** Opt-in read and security events. */
+  accessEvents: AccessEventsForm;
 }
 
 // Deterministic id generator INJECTED by the caller so this module stays pure
@@ -549,9 +658,207 @@ export function formFromWire(
       prefixGlobs: r.prefixGlobs.map((glob) => ({ id: nextId(), glob })),
       channel: r.channel,
     })),
+    endpoints: (raw?.endpoints ?? []).map((e) => endpointFromWire(e, nextId)),
+    accessEvents: accessEventsFromWire(raw?.access_events),
+  };
+}
+
+const lines = (v: string[] | undefined): string => (v ?? []).join('\n');
+const parseLines = (text: string): string[] =>
+  text
+    .split('\n')
+    .map((l) => l.trim())
+    .filter((l) => l.length > 0);
+
+function endpointFromWire(e: EventEndpointWire, nextId: IdGen): EventEndpointRow {
+  const f = e.filter ?? {};
+  return {
+    id: nextId(),
+    name: e.name,
+    origName: e.name,
+    enabled: e.enabled ?? true,
+    url: e.url,
+    urlMasked: e.url === WEBHOOK_REDACTED_SENTINEL,
+    format: e.format === 'slack' ? 'slack' : 'raw',
+    headerRows: Object.entries(e.headers ?? {}).map(([name, value]) => ({
+      id: nextId(),
+      name,
+      value,
+      origName: name,
+      masked: value === WEBHOOK_REDACTED_SENTINEL,
+    })),
+    signingSecret: e.signing?.secret ?? '',
+    signingMasked: e.signing?.secret === WEBHOOK_REDACTED_SENTINEL,
+    signingPrevious: e.signing?.previous_secret ?? '',
+    kinds: [...(f.kinds ?? [])],
+    bucketsText: lines(f.buckets),
+    includeText: lines(f.include_globs),
+    excludeText: lines(f.exclude_globs),
+    minSize: f.min_size ?? null,
+    maxSize: f.max_size ?? null,
+    metadataText: Object.entries(f.metadata ?? {})
+      .map(([k, v]) => `${k}=${v}`)
+      .join('\n'),
+    maxAttempts: e.max_attempts ?? null,
+    retryBase: e.retry_base ?? '',
+    retryMax: e.retry_max ?? '',
+  };
+}
+
+/** Access-events form state; absent fields take the backend defaults. */
+export function accessEventsFromWire(a: AccessEventsWire | undefined): AccessEventsForm {
+  return {
+    enabled: a?.enabled ?? false,
+    bucketsText: lines(a?.buckets),
+    includeText: lines(a?.include_globs),
+    excludeText: lines(a?.exclude_globs),
+    includeHead: a?.include_head ?? false,
+    sampleRate: a?.sample_rate ?? 1,
+    securityEvents: a?.security_events ?? false,
+    retention: a?.retention ?? '24h',
+    maxRows: a?.max_rows ?? 100000,
+  };
+}
+
+/** Validate the access-events form and build its wire object. */
+function buildAccessEvents(form: AccessEventsForm): {
+  errors: string[];
+  accessEvents: AccessEventsWire;
+} {
+  const errors: string[] = [];
+  if (!(form.sampleRate >= 0 && form.sampleRate <= 1)) {
+    errors.push('Access events: sample rate must be between 0 and 1.');
+  }
+  if (!DURATION_RE.test(form.retention)) {
+    errors.push(
+      `Access events: retention "${form.retention}" is not a duration like 30s, 5m, or 24h.`
+    );
+  }
+  if (!Number.isInteger(form.maxRows) || form.maxRows < 0) {
+    errors.push('Access events: max rows must be an integer ≥ 0.');
+  }
+  return {
+    errors,
+    accessEvents: {
+      enabled: form.enabled,
+      buckets: parseLines(form.bucketsText),
+      include_globs: parseLines(form.includeText),
+      exclude_globs: parseLines(form.excludeText),
+      include_head: form.includeHead,
+      sample_rate: form.sampleRate,
+      security_events: form.securityEvents,
+      retention: form.retention.trim(),
+      max_rows: form.maxRows,
+    },
   };
 }
 
+/** A fresh, enabled endpoint row with an empty filter (= every event). */
+export function newEndpointRow(nextId: IdGen): EventEndpointRow {
+  return endpointFromWire({ name: '', url: '' }, nextId);
+}
+
+/** Validate the named endpoints and build their wire list. */
+function buildEndpoints(rows: EventEndpointRow[]): {
+  errors: string[];
+  endpoints: EventEndpointWire[];
+} {
+  const errors: string[] = [];
+  const endpoints: EventEndpointWire[] = [];
+  const seen = new Set<string>();
+  rows.forEach((row, i) => {
+    const name = row.name.trim();
+    const label = name ? `Endpoint "${name}"` : `Endpoint ${i + 1}`;
+    if (!name) errors.push(`${label} needs a name.`);
+    else if (seen.has(name)) errors.push(`${label} is defined twice — names must be unique.`);
+    seen.add(name);
+
+    const anyMasked =
+      row.urlMasked || row.signingMasked || row.headerRows.some((h) => h.masked);
+    if (anyMasked && row.origName && name && name !== row.origName) {
+      errors.push(
+        `Endpoint "${row.origName}" was renamed to "${name}" while its secrets are masked. Re-enter them, or rename it back.`
+      );
+    }
+    const url = row.url.trim();
+    if (!row.urlMasked && !URL_RE.test(url)) {
+      errors.push(`${label} needs a valid http(s) URL.`);
+    }
+
+    const headers: Record<string, string> = {};
+    for (const h of row.headerRows) {
+      const hName = h.name.trim();
+      if (!hName) continue;
+      if (!HEADER_NAME_RE.test(hName)) {
+        errors.push(`${label}: header name "${hName}" contains invalid characters.`);
+      }
+      if (h.value === '') errors.push(`${label}: header "${hName}" has an empty value.`);
+      if (h.masked && h.origName && hName !== h.origName) {
+        errors.push(
+          `${label}: header "${h.origName}" was renamed without re-entering its value.`
+        );
+      }
+      headers[hName] = h.value;
+    }
+
+    for (const [field, v] of [
+      ['retry base', row.retryBase],
+      ['retry max', row.retryMax],
+    ] as const) {
+      if (v.trim() && !DURATION_RE.test(v)) {
+        errors.push(`${label}: ${field} "${v}" is not a duration like 30s, 5m, or 24h.`);
+      }
+    }
+    if (row.maxAttempts !== null && (!Number.isInteger(row.maxAttempts) || row.maxAttempts < 1)) {
+      errors.push(`${label}: max attempts must be an integer ≥ 1.`);
+    }
+    if (row.minSize !== null && row.maxSize !== null && row.minSize > row.maxSize) {
+      errors.push(`${label}: minimum size exceeds maximum size.`);
+    }
+
+    const metadata: Record<string, string> = {};
+    for (const line of parseLines(row.metadataText)) {
+      const eq = line.indexOf('=');
+      if (eq <= 0) {
+        errors.push(`${label}: metadata "${line}" is not key=value.`);
+        continue;
+      }
+      metadata[line.slice(0, eq).trim()] = line.slice(eq + 1).trim();
+    }
+
+    const filter: EventFilterWire = {};
+    if (row.kinds.length > 0) filter.kinds = [...row.kinds];
+    const buckets = parseLines(row.bucketsText);
+    if (buckets.length > 0) filter.buckets = buckets;
+    const include = parseLines(row.includeText);
+    if (include.length > 0) filter.include_globs = include;
+    const exclude = parseLines(row.excludeText);
+    if (exclude.length > 0) filter.exclude_globs = exclude;
+    if (row.minSize !== null) filter.min_size = row.minSize;
+    if (row.maxSize !== null) filter.max_size = row.maxSize;
+    if (Object.keys(metadata).length > 0) filter.metadata = metadata;
+
+    const endpoint: EventEndpointWire = {
+      name,
+      enabled: row.enabled,
+      url: row.urlMasked ? WEBHOOK_REDACTED_SENTINEL : url,
+      format: row.format,
+    };
+    if (Object.keys(headers).length > 0) endpoint.headers = headers;
+    const secret = row.signingMasked ? WEBHOOK_REDACTED_SENTINEL : row.signingSecret.trim();
+    if (secret) {
+      endpoint.signing = { secret };
+      if (row.signingPrevious) endpoint.signing.previous_secret = row.signingPrevious;
+    }
+    if (Object.keys(filter).length > 0) endpoint.filter = filter;
+    if (row.maxAttempts !== null) endpoint.max_attempts = row.maxAttempts;
+    if (row.retryBase.trim()) endpoint.retry_base = row.retryBase.trim();
+    if (row.retryMax.trim()) endpoint.retry_max = row.retryMax.trim();
+    endpoints.push(endpoint);
+  });
+  return { errors, endpoints };
+}
+
 /** Flatten form rows back into the validated `EventDeliveryConfig` shape that
  *  `buildEventDeliveryPayload` consumes. Empty (in-progress) rows are dropped;
  *  the baseline is reconstructed from each header's `origName` so removals
@@ -621,10 +928,20 @@ export function buildPayloadFromForm(form: WebhookFormState): ValidationResult {
     baseline.webhook_headers[name] = WEBHOOK_REDACTED_SENTINEL;
   }
 
-  const res = buildEventDeliveryPayload(local, baseline);
+  const endpoints = buildEndpoints(form.endpoints);
+  errors.push(...endpoints.errors);
+  const access = buildAccessEvents(form.accessEvents);
+  errors.push(...access.errors);
+  const enabledEndpoints = form.endpoints.filter((e) => e.enabled).length;
+  const res = buildEventDeliveryPayload(local, baseline, enabledEndpoints);
   if (errors.length > 0) {
     return { ok: false, errors: [...errors, ...res.errors] };
   }
+  if (res.ok && res.body?.event_delivery) {
+    // Always emitted: an empty list clears every endpoint.
+    res.body.event_delivery.endpoints = endpoints.endpoints;
+    res.body.event_delivery.access_events = access.accessEvents;
+  }
   return res;
 }
 
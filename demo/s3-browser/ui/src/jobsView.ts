@@ -94,6 +94,101 @@ export function jobWalkProgress(
   return { scanning, dirs_completed, dirs_pending };
 }
 
+/**
+ * Open-conflict count of a bidirectional replication row, or `null` for any
+ * other job (one-way rules have no conflicts tab).
+ */
+export function jobConflictCount(row: Pick<JobRow, 'detail'> | null): number | null {
+  if (!row || row.detail?.direction !== 'bidirectional') return null;
+  return typeof row.detail.conflicts === 'number' ? row.detail.conflicts : 0;
+}
+
+export type JobExternalSide = {
+  endpoint: string | null;
+  region: string;
+  hydrated: boolean;
+};
+
+/**
+ * Where a replication row's external sides point (`null` per proxy-routed
+ * side), or `null` when neither side is external.
+ */
+export function jobExternal(
+  row: Pick<JobRow, 'detail'> | null,
+): { source: JobExternalSide | null; destination: JobExternalSide | null } | null {
+  const raw = row?.detail?.external as Record<string, unknown> | null | undefined;
+  if (!raw || typeof raw !== 'object') return null;
+  const side = (v: unknown): JobExternalSide | null => {
+    if (!v || typeof v !== 'object') return null;
+    const s = v as Record<string, unknown>;
+    return {
+      endpoint: typeof s.endpoint === 'string' ? s.endpoint : null,
+      region: typeof s.region === 'string' ? s.region : '',
+      hydrated: s.hydrated !== false,
+    };
+  };
+  return { source: side(raw.source), destination: side(raw.destination) };
+}
+
+export type JobThrottle = {
+  window_open: boolean;
+  opens_at: number | null;
+  bandwidth_limit_mbps: number | null;
+  throttled: boolean;
+  throttled_seconds: number;
+};
+
+/**
+ * Bandwidth-cap / transfer-window state of a replication row, or `null` when
+ * the rule has neither (absent on old servers and non-replication kinds).
+ */
+export function jobThrottle(row: Pick<JobRow, 'detail'> | null): JobThrottle | null {
+  const raw = row?.detail?.throttle as Record<string, unknown> | null | undefined;
+  if (!raw || typeof raw !== 'object') return null;
+  return {
+    window_open: raw.window_open !== false,
+    opens_at: typeof raw.opens_at === 'number' ? raw.opens_at : null,
+    bandwidth_limit_mbps:
+      typeof raw.bandwidth_limit_mbps === 'number' ? raw.bandwidth_limit_mbps : null,
+    throttled: raw.throttled === true,
+    throttled_seconds: typeof raw.throttled_seconds === 'number' ? raw.throttled_seconds : 0,
+  };
+}
+
+/**
+ * The chip next to a throttled row's status: outside its transfer window
+ * (changes queue until it opens) beats "held back by the bandwidth cap".
+ * `null` when replication is flowing freely. `opensLabel` formats the
+ * reopening time (kept injectable so the mapping stays pure).
+ */
+export function throttleChip(
+  t: JobThrottle | null,
+  opensLabel: (unix: number) => string = (u) => new Date(u * 1000).toLocaleString(),
+): { label: string; color: string; hint: string } | null {
+  if (!t) return null;
+  if (!t.window_open) {
+    return {
+      label: 'outside window',
+      color: 'gold',
+      hint:
+        t.opens_at != null
+          ? `Changes are queued until the transfer window opens (${opensLabel(t.opens_at)}).`
+          : 'Changes are queued until the transfer window opens.',
+    };
+  }
+  if (t.throttled) {
+    return {
+      label: 'throttled',
+      color: 'blue',
+      hint:
+        t.bandwidth_limit_mbps != null
+          ? `Copies are held to the ${t.bandwidth_limit_mbps} Mbit/s bandwidth cap.`
+          : 'Copies are held to the bandwidth cap.',
+    };
+  }
+  return null;
+}
+
 export type StrategySegment = {
   key: 'verbatim' | 'reconstructed' | 'straight';
   count: number;
@@ -5,14 +5,16 @@
 import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
 import {
   cancelVerifyParity,
+  getJobConflicts,
   getJobFailures,
   getJobRuns,
   getJobs,
   getVerifyStatus,
+  resolveJobConflict,
   runJobAction,
   startVerifyParity,
 } from '../adminApi';
-import type { ParityStatus } from '../adminApi';
+import type { ConflictSide, ParityStatus } from '../adminApi';
 import { isActiveJobStatus } from '../jobsView';
 import { qk } from './keys';
 
@@ -54,6 +56,27 @@ export function useJobFailures(id: string | null) {
   });
 }
 
+/** Keys a bidirectional replication rule holds for manual resolution. */
+export function useJobConflicts(id: string | null) {
+  return useQuery({
+    queryKey: qk.jobs.conflicts(id ?? ''),
+    queryFn: () => getJobConflicts(id as string),
+    enabled: !!id,
+  });
+}
+
+/** Resolve one conflict, then refetch the conflicts and the jobs list (its count). */
+export function useResolveConflict(id: string) {
+  const qc = useQueryClient();
+  return useMutation<unknown, Error, { key: string; keep: ConflictSide }>({
+    mutationFn: ({ key, keep }) => resolveJobConflict(id, key, keep),
+    onSuccess: () => {
+      qc.invalidateQueries({ queryKey: qk.jobs.conflicts(id) });
+      qc.invalidateQueries({ queryKey: qk.jobs.list() });
+    },
+  });
+}
+
 /**
  * Server-side parity verification status for a replication rule. The audit is a
  * BACKGROUND job: the result is persisted server-side, so this query gives an
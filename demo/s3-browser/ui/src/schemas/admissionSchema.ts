@@ -21,6 +21,9 @@
  *     server's compile check to catch invalid globs).
  *   * `action.reject.status`: 400-599.
  *   * `action.reject.message`: optional, up to 4096 chars.
+ *   * `action.throttle`: at least one of `requests_per_sec` /
+ *     `bytes_per_sec`; `burst` requires `requests_per_sec`.
+ *   * `match.access_key` / `match.group`: throttle blocks only.
  *
  * The schema type is what `react-hook-form` binds to. The submit
  * path converts this back to the server's `AdmissionBlock` shape
@@ -73,6 +76,12 @@ const admissionMatchSchema = z
       .min(1, 'config_flag must not be empty')
       .max(128)
       .optional(),
+    access_key: z
+      .array(z.string().trim().min(1, 'access key must not be empty').max(128))
+      .optional(),
+    group: z
+      .array(z.string().trim().min(1, 'group must not be empty').max(128))
+      .optional(),
   })
   .refine(
     (m) => !(m.source_ip && m.source_ip_list && m.source_ip_list.length > 0),
@@ -93,16 +102,42 @@ const admissionRejectSchema = z.object({
   message: z.string().max(4096).optional(),
 });
 
+export const THROTTLE_KEYS = [
+  'global',
+  'access-key',
+  'group',
+  'bucket',
+  'source-ip',
+] as const;
+
+const admissionThrottleSchema = z
+  .object({
+    type: z.literal('throttle'),
+    per: z.enum(THROTTLE_KEYS).optional(),
+    requests_per_sec: z.number().int().min(1, 'must be at least 1').optional(),
+    burst: z.number().int().min(1, 'must be at least 1').optional(),
+    bytes_per_sec: z.number().int().min(1, 'must be at least 1').optional(),
+  })
+  .refine((t) => t.requests_per_sec !== undefined || t.bytes_per_sec !== undefined, {
+    message: 'set requests_per_sec, bytes_per_sec, or both',
+    path: ['requests_per_sec'],
+  })
+  .refine((t) => t.burst === undefined || t.requests_per_sec !== undefined, {
+    message: 'burst requires requests_per_sec',
+    path: ['burst'],
+  });
+
 /**
  * Matching the server's `Action` enum. Simple-string actions
- * (`allow-anonymous`, `deny`, `continue`) stay strings; reject is
- * the only structured variant.
+ * (`allow-anonymous`, `deny`, `continue`) stay strings; reject and
+ * throttle are the structured variants.
  */
 const admissionActionSchema = z.union([
   z.literal('allow-anonymous'),
   z.literal('deny'),
   z.literal('continue'),
   admissionRejectSchema,
+  admissionThrottleSchema,
 ]);
 
 export const admissionBlockSchema = z.object({
@@ -121,7 +156,15 @@ export const admissionBlockSchema = z.object({
     }),
   match: admissionMatchSchema,
   action: admissionActionSchema,
-});
+}).refine(
+  (b) =>
+    actionKind(b.action) === 'throttle' ||
+    (!b.match.access_key?.length && !b.match.group?.length),
+  {
+    message: 'access key and group predicates are only valid on throttle blocks',
+    path: ['match', 'group'],
+  }
+);
 
 export type AdmissionBlockForm = z.infer<typeof admissionBlockSchema>;
 
@@ -129,19 +172,26 @@ export type AdmissionBlockForm = z.infer<typeof admissionBlockSchema>;
  * Narrow an `AdmissionBlock['action']` to its discriminant kind.
  *
  * Simple-string actions (`allow-anonymous`, `deny`, `continue`)
- * stay strings; the only structured variant is Reject. Centralised
+ * stay strings; the structured variants are Reject and Throttle. Centralised
  * here (rather than in each component that renders an action badge
  * / radio) so UIs and schemas agree on the kind set. Accepts
  * `unknown` to tolerate round-tripped payloads whose shape is
  * assumed but not proved — returns the narrow kind when the input
- * matches, falls through to `reject` otherwise (the only object-
- * shaped variant).
+ * matches, falls through to `reject` for any other object that is
+ * not tagged `throttle`.
  */
 export function actionKind(
   action: unknown
-): 'allow-anonymous' | 'deny' | 'reject' | 'continue' {
+): 'allow-anonymous' | 'deny' | 'reject' | 'continue' | 'throttle' {
   if (action === 'allow-anonymous' || action === 'deny' || action === 'continue') {
     return action;
   }
+  if (
+    typeof action === 'object' &&
+    action !== null &&
+    (action as { type?: unknown }).type === 'throttle'
+  ) {
+    return 'throttle';
+  }
   return 'reject';
 }
@@ -3,7 +3,7 @@
  *
  * React-free so it can be unit-tested in Node (see
  * scripts/trace-request-regression-test.mjs). The wire contract is
- * load-bearing: `query` / `source_ip` are only emitted when non-empty
+ * load-bearing: `query` / `source_ip` / `access_key` are only emitted when non-empty
  * after trimming, matching what `POST /_/api/admin/config/trace`
  * expects. Keep this byte-identical to the prior inline builder.
  */
@@ -14,6 +14,8 @@ export interface TraceRequestInput {
   query: string;
   sourceIp: string;
   authenticated: boolean;
+  /** Optional so callers predating throttle tracing stay valid. */
+  accessKey?: string;
 }
 
 export interface TraceRequestBody {
@@ -22,6 +24,7 @@ export interface TraceRequestBody {
   authenticated: boolean;
   query?: string;
   source_ip?: string;
+  access_key?: string;
 }
 
 export function buildTraceBody(input: TraceRequestInput): TraceRequestBody {
@@ -32,5 +35,6 @@ export function buildTraceBody(input: TraceRequestInput): TraceRequestBody {
   };
   if (input.query.trim()) body.query = input.query.trim();
   if (input.sourceIp.trim()) body.source_ip = input.sourceIp.trim();
+  if (input.accessKey?.trim()) body.access_key = input.accessKey.trim();
   return body;
 }
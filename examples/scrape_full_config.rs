@@ -314,7 +314,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     out.push_str("  backends:\n");
     out.push_str("    - id: primary\n");
     match &cfg.backend {
-        BackendConfig::Filesystem { path } => {
+        BackendConfig::Filesystem { path, .. } => {
             out.push_str(&format!(
                 "      type: filesystem\n\
                  \x20     path: {}\n",
@@ -346,11 +346,64 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 secrets.record("DGP_BE_AWS_SECRET_ACCESS_KEY", s);
             }
         }
+        BackendConfig::AzureBlob {
+            account,
+            account_key,
+            endpoint,
+            ..
+        } => {
+            out.push_str("      type: azure\n");
+            out.push_str(&format!("      account: {}\n", account));
+            if let Some(ep) = endpoint {
+                out.push_str(&format!("      endpoint: {}\n", ep));
+            }
+            if let Some(ref k) = account_key {
+                out.push_str("      account_key: !secret DGP_BE_AZURE_ACCOUNT_KEY\n");
+                secrets.record("DGP_BE_AZURE_ACCOUNT_KEY", k);
+            }
+        }
+        BackendConfig::Gcs {
+            project,
+            service_account_key,
+            endpoint,
+            ..
+        } => {
+            out.push_str("      type: gcs\n");
+            if let Some(p) = project {
+                out.push_str(&format!("      project: {}\n", p));
+            }
+            if let Some(ep) = endpoint {
+                out.push_str(&format!("      endpoint: {}\n", ep));
+            }
+            if let Some(ref k) = service_account_key {
+                out.push_str("      service_account_key: !secret DGP_BE_GCS_SERVICE_ACCOUNT_KEY\n");
+                secrets.record("DGP_BE_GCS_SERVICE_ACCOUNT_KEY", k);
+            }
+        }
+        BackendConfig::Erasure {
+            data_shards,
+            parity_shards,
+            shards,
+            write_quorum,
+        } => {
+            out.push_str(&format!(
+                "      type: erasure\n\
+                 \x20     data_shards: {}\n\
+                 \x20     parity_shards: {}\n\
+                 \x20     shards: [{}]\n",
+                data_shards,
+                parity_shards,
+                shards.join(", "),
+            ));
+            if let Some(wq) = write_quorum {
+                out.push_str(&format!("      write_quorum: {}\n", wq));
+            }
+        }
     }
     for named in &cfg.backends {
         out.push_str(&format!("    - id: {}\n", named.name));
         match &named.backend {
-            BackendConfig::Filesystem { path } => {
+            BackendConfig::Filesystem { path, .. } => {
                 out.push_str(&format!(
                     "      type: filesystem\n\
                      \x20     path: {}\n",
@@ -387,6 +440,64 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     secrets.record(key, s);
                 }
             }
+            BackendConfig::AzureBlob {
+                account,
+                account_key,
+                endpoint,
+                ..
+            } => {
+                out.push_str("      type: azure\n");
+                out.push_str(&format!("      account: {}\n", account));
+                if let Some(ep) = endpoint {
+                    out.push_str(&format!("      endpoint: {}\n", ep));
+                }
+                if let Some(ref k) = account_key {
+                    let key = format!("BACKEND_{}_ACCOUNT_KEY", sanitize_env_name(&named.name));
+                    out.push_str(&format!("      account_key: !secret {}\n", key));
+                    secrets.record(key, k);
+                }
+            }
+            BackendConfig::Gcs {
+                project,
+                service_account_key,
+                endpoint,
+                ..
+            } => {
+                out.push_str("      type: gcs\n");
+                if let Some(p) = project {
+                    out.push_str(&format!("      project: {}\n", p));
+                }
+                if let Some(ep) = endpoint {
+                    out.push_str(&format!("      endpoint: {}\n", ep));
+                }
+                if let Some(ref k) = service_account_key {
+                    let key = format!(
+                        "BACKEND_{}_SERVICE_ACCOUNT_KEY",
+                        sanitize_env_name(&named.name)
+                    );
+                    out.push_str(&format!("      service_account_key: !secret {}\n", key));
+                    secrets.record(key, k);
+                }
+            }
+            BackendConfig::Erasure {
+                data_shards,
+                parity_shards,
+                shards,
+                write_quorum,
+            } => {
+                out.push_str(&format!(
+                    "      type: erasure\n\
+                     \x20     data_shards: {}\n\
+                     \x20     parity_shards: {}\n\
+                     \x20     shards: [{}]\n",
+                    data_shards,
+                    parity_shards,
+                    shards.join(", "),
+                ));
+                if let Some(wq) = write_quorum {
+                    out.push_str(&format!("      write_quorum: {}\n", wq));
+                }
+            }
         }
     }
     if let Some(ref default) = cfg.default_backend {
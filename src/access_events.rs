@@ -0,0 +1,357 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Read and security events (`event_delivery.access_events`).
+//!
+//! Successful GetObject / HeadObject requests become `ObjectAccessed` outbox
+//! rows, IAM denials `AccessDenied` and rejected credentials `AuthFailed`.
+//! Request paths hand them to the [`AccessEventRecorder`], which never makes
+//! a request wait on the config DB: events go through a bounded channel to
+//! one writer task that inserts them in batches, and are dropped (counted in
+//! `deltaglider_access_events_total{outcome="dropped"}`) when it falls behind.
+//!
+//! The rows are stored already delivered (see [`EventKind::is_access`]), so
+//! only named endpoints whose filter lists the kind send them anywhere, and
+//! they are pruned by their own retention.
+
+use crate::api::auth::RequestClientIp;
+use crate::background::spawn_batch_writer;
+use crate::config::SharedConfig;
+use crate::config_db::ConfigDb;
+use crate::config_sections::AccessEventsConfig;
+use crate::event_outbox::{
+    attach_user_metadata, current_unix_seconds, EventKind, EventSource, NewEvent,
+};
+use crate::iam::AuthenticatedUser;
+use crate::metrics::Metrics;
+use crate::replication::event_consumer::is_user_object_key;
+use crate::types::FileMetadata;
+use axum::http::{Extensions, HeaderMap};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tracing::warn;
+
+/// Who made a request, as far as an event payload is concerned.
+#[derive(Debug, Clone, Default)]
+pub struct Requester {
+    pub principal: Option<String>,
+    pub access_key_id: Option<String>,
+    pub source_ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl Requester {
+    /// The requester of an authenticated (or anonymous) S3 request, from the
+    /// extensions the SigV4 middleware left on it.
+    pub fn from_request(extensions: &Extensions, headers: &HeaderMap) -> Self {
+        let user = extensions.get::<AuthenticatedUser>();
+        let (header_ip, user_agent) = crate::audit::extract_client_info(headers);
+        let source_ip = extensions
+            .get::<RequestClientIp>()
+            .map(|ip| ip.0.to_string())
+            .unwrap_or(header_ip);
+        Self {
+            principal: user.map(|u| u.name.clone()),
+            access_key_id: user
+                .map(|u| u.access_key_id.clone())
+                .filter(|k| !k.is_empty()),
+            source_ip: Some(source_ip).filter(|ip| ip != "unknown"),
+            user_agent: Some(user_agent).filter(|ua| !ua.is_empty()),
+        }
+    }
+
+    fn with_fields(&self, mut payload: Value) -> Value {
+        if let Some(obj) = payload.as_object_mut() {
+            for (field, value) in [
+                ("principal", &self.principal),
+                ("access_key_id", &self.access_key_id),
+                ("source_ip", &self.source_ip),
+                ("user_agent", &self.user_agent),
+            ] {
+                if let Some(value) = value {
+                    obj.insert(field.to_string(), Value::String(value.clone()));
+                }
+            }
+        }
+        payload
+    }
+}
+
+/// Bucket and (percent-decoded) key of a path-style request path, for the
+/// security events raised before s3s has parsed the request.
+pub(crate) fn request_bucket_key(path: &str) -> (String, String) {
+    let (bucket, key) = crate::iam::middleware::parse_bucket_key(path);
+    let decode = |s: &str| {
+        urlencoding::decode(s)
+            .map(|d| d.into_owned())
+            .unwrap_or_else(|_| s.to_string())
+    };
+    (decode(bucket), decode(key))
+}
+
+/// Compile what parses; an invalid glob is reported by config validation.
+fn build_globset(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for glob in patterns.iter().filter_map(|p| Glob::new(p).ok()) {
+        builder.add(glob);
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+/// One `access_events` config with its globs compiled.
+struct ReadFilter {
+    config: AccessEventsConfig,
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl ReadFilter {
+    fn new(config: &AccessEventsConfig) -> Self {
+        Self {
+            config: config.clone(),
+            include: build_globset(&config.include_globs),
+            exclude: build_globset(&config.exclude_globs),
+        }
+    }
+
+    /// Whether a read of `bucket`/`key` is recorded, sampling included.
+    fn records(&self, head: bool, bucket: &str, key: &str) -> bool {
+        let c = &self.config;
+        c.enabled
+            && (!head || c.include_head)
+            && (c.buckets.is_empty() || c.buckets.iter().any(|b| b == bucket))
+            && !self.exclude.is_match(key)
+            && (c.include_globs.is_empty() || self.include.is_match(key))
+            && sampled(c.sample_rate)
+    }
+}
+
+fn sampled(rate: f64) -> bool {
+    rate >= 1.0 || (rate > 0.0 && rand::random::<f64>() < rate)
+}
+
+/// Hands read and security events to the outbox writer. Cheap to clone; a
+/// [`Self::disabled`] recorder (no config DB) records nothing.
+#[derive(Clone)]
+pub struct AccessEventRecorder {
+    inner: Option<Arc<Inner>>,
+}
+
+struct Inner {
+    config: SharedConfig,
+    tx: mpsc::Sender<NewEvent>,
+    metrics: Arc<Metrics>,
+    /// Compiled filter of the last config seen; rebuilt when it changes.
+    filter: parking_lot::Mutex<Option<Arc<ReadFilter>>>,
+}
+
+impl AccessEventRecorder {
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    /// Start the writer task and return a recorder feeding it. Must be
+    /// called inside a Tokio runtime.
+    pub fn spawn(config: SharedConfig, db: Arc<Mutex<ConfigDb>>, metrics: Arc<Metrics>) -> Self {
+        let tx = spawn_batch_writer(move |batch| write_events(db.clone(), batch));
+        Self {
+            inner: Some(Arc::new(Inner {
+                config,
+                tx,
+                metrics,
+                filter: parking_lot::Mutex::new(None),
+            })),
+        }
+    }
+
+    /// Record a successful GetObject (`head == false`) or HeadObject of a
+    /// user object, subject to the configured filters and sampling.
+    pub async fn object_read(
+        &self,
+        head: bool,
+        bucket: &str,
+        key: &str,
+        meta: &FileMetadata,
+        range: Option<String>,
+        requester: &Requester,
+    ) {
+        let Some(inner) = &self.inner else { return };
+        if !is_user_object_key(key) || !inner.read_filter().await.records(head, bucket, key) {
+            return;
+        }
+        let mut payload = json!({
+            "method": if head { "HEAD" } else { "GET" },
+            "content_length": meta.file_size,
+            "etag": meta.etag(),
+        });
+        if let Some(range) = range {
+            payload["range"] = Value::String(range);
+        }
+        attach_user_metadata(&mut payload, &meta.user_metadata);
+        inner.send(
+            EventKind::ObjectAccessed,
+            bucket,
+            key,
+            requester.with_fields(payload),
+        );
+    }
+
+    /// Record an IAM denial of `action` on `bucket`/`key`.
+    pub async fn access_denied(
+        &self,
+        action: &str,
+        bucket: &str,
+        key: &str,
+        requester: &Requester,
+    ) {
+        let Some(inner) = &self.inner else { return };
+        if inner.security_events().await {
+            let payload = requester.with_fields(json!({ "action": action }));
+            inner.send(EventKind::AccessDenied, bucket, key, payload);
+        }
+    }
+
+    /// Record a request whose credentials were rejected for `reason` (the
+    /// `deltaglider_auth_failures_total` reason label).
+    pub async fn auth_failed(&self, reason: &str, bucket: &str, key: &str, requester: &Requester) {
+        let Some(inner) = &self.inner else { return };
+        if inner.security_events().await {
+            let payload = requester.with_fields(json!({ "reason": reason }));
+            inner.send(EventKind::AuthFailed, bucket, key, payload);
+        }
+    }
+}
+
+impl Inner {
+    async fn read_filter(&self) -> Arc<ReadFilter> {
+        let config = self.config.read().await;
+        let current = &config.event_delivery.access_events;
+        let mut cached = self.filter.lock();
+        match cached.as_ref() {
+            Some(filter) if filter.config == *current => filter.clone(),
+            _ => {
+                let filter = Arc::new(ReadFilter::new(current));
+                *cached = Some(filter.clone());
+                filter
+            }
+        }
+    }
+
+    async fn security_events(&self) -> bool {
+        self.config
+            .read()
+            .await
+            .event_delivery
+            .access_events
+            .security_events
+    }
+
+    fn send(&self, kind: EventKind, bucket: &str, key: &str, payload: Value) {
+        let event = NewEvent::new(
+            kind,
+            bucket,
+            key,
+            EventSource::S3Api,
+            current_unix_seconds(),
+            payload,
+        );
+        let outcome = match self.tx.try_send(event) {
+            Ok(()) => "recorded",
+            Err(_) => "dropped",
+        };
+        self.metrics
+            .access_events_total
+            .with_label_values(&[kind.as_str(), outcome])
+            .inc();
+    }
+}
+
+async fn write_events(db: Arc<Mutex<ConfigDb>>, batch: Vec<NewEvent>) {
+    if let Err(err) = db.lock().await.event_outbox_insert_many(&batch) {
+        warn!("failed to record {} access event(s): {}", batch.len(), err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn filter(config: AccessEventsConfig) -> ReadFilter {
+        ReadFilter::new(&config)
+    }
+
+    #[test]
+    fn read_filter_honours_buckets_globs_and_head() {
+        let f = filter(AccessEventsConfig {
+            enabled: true,
+            buckets: vec!["releases".into()],
+            include_globs: vec!["v*/**".into()],
+            exclude_globs: vec!["**/*.sha256".into()],
+            ..Default::default()
+        });
+        assert!(f.records(false, "releases", "v1/app.zip"));
+        assert!(!f.records(true, "releases", "v1/app.zip"), "HEAD is opt-in");
+        assert!(!f.records(false, "media", "v1/app.zip"));
+        assert!(!f.records(false, "releases", "notes.txt"));
+        assert!(!f.records(false, "releases", "v1/app.zip.sha256"));
+
+        let off = filter(AccessEventsConfig::default());
+        assert!(!off.records(false, "releases", "v1/app.zip"));
+    }
+
+    #[test]
+    fn sampling_extremes_are_exact() {
+        assert!((0..100).all(|_| sampled(1.0)));
+        assert!((0..100).all(|_| !sampled(0.0)));
+    }
+
+    #[tokio::test]
+    async fn recorded_reads_land_in_the_outbox_as_delivered_rows() {
+        let mut config = Config::default();
+        config.event_delivery.access_events.enabled = true;
+        config.event_delivery.access_events.security_events = true;
+        let db = Arc::new(Mutex::new(ConfigDb::in_memory("test-pass").unwrap()));
+        let recorder =
+            AccessEventRecorder::spawn(config.into_shared(), db.clone(), Arc::new(Metrics::new()));
+        let requester = Requester {
+            principal: Some("alice".into()),
+            source_ip: Some("10.0.0.7".into()),
+            ..Default::default()
+        };
+        let meta =
+            FileMetadata::new_passthrough("app.zip".into(), "sha".into(), "md5".into(), 42, None);
+        recorder
+            .object_read(false, "releases", "app.zip", &meta, None, &requester)
+            .await;
+        recorder
+            .object_read(
+                false,
+                "releases",
+                ".dg/reference.bin",
+                &meta,
+                None,
+                &requester,
+            )
+            .await;
+        recorder
+            .auth_failed("invalid_access_key", "releases", "", &requester)
+            .await;
+
+        let rows = loop {
+            let rows = db.lock().await.event_outbox_recent(10).unwrap();
+            if rows.len() >= 2 {
+                break rows;
+            }
+            tokio::task::yield_now().await;
+        };
+        let read = rows.iter().find(|r| r.kind == "ObjectAccessed").unwrap();
+        assert_eq!(read.status, "delivered");
+        assert_eq!(read.key, "app.zip");
+        assert_eq!(read.payload["principal"], "alice");
+        assert_eq!(read.payload["content_length"], 42);
+        assert!(rows.iter().any(|r| r.kind == "AuthFailed"));
+        assert_eq!(rows.len(), 2, "internal keys are never recorded");
+    }
+}
@@ -48,6 +48,15 @@ impl<'a> RequestInfo<'a> {
     }
 }
 
+/// Who a request authenticated as — the extra input of the post-SigV4
+/// throttle pass. `access_key_id` is `None` for anonymous requests;
+/// `groups` are IAM group names (a service account's are its parent's).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Principal<'a> {
+    pub access_key_id: Option<&'a str>,
+    pub groups: &'a [String],
+}
+
 /// Walk the chain, return the first matched decision. If no block fires,
 /// the default terminal is `Continue { matched: None }`.
 ///
@@ -56,6 +65,10 @@ impl<'a> RequestInfo<'a> {
 /// sites are responsible for the ordering they want to express.
 pub fn evaluate(chain: &AdmissionChain, req: &RequestInfo<'_>) -> Decision {
     for block in chain.blocks() {
+        // Throttles are not decisions; `matching_throttles` handles them.
+        if matches!(block.action, Action::Throttle { .. }) {
+            continue;
+        }
         if matches(chain, &block.match_, &block.action, req) {
             return match &block.action {
                 Action::AllowAnonymous => Decision::AllowAnonymous {
@@ -72,12 +85,51 @@ pub fn evaluate(chain: &AdmissionChain, req: &RequestInfo<'_>) -> Decision {
                     status: *status,
                     message: message.clone(),
                 },
+                Action::Throttle { .. } => unreachable!("throttle blocks are skipped above"),
             };
         }
     }
     Decision::Continue { matched: None }
 }
 
+/// Every `throttle` block that applies to an authenticated request, in
+/// chain order. Unlike [`evaluate`] this does not stop at the first
+/// match: a request is charged against each matching limiter.
+pub fn matching_throttles<'c>(
+    chain: &'c AdmissionChain,
+    req: &RequestInfo<'_>,
+    principal: &Principal<'_>,
+) -> Vec<&'c super::AdmissionBlock> {
+    chain
+        .blocks()
+        .iter()
+        .filter(|block| matches!(block.action, Action::Throttle { .. }))
+        .filter(|block| match &block.match_ {
+            Match::Predicates(p) => {
+                match_predicates(p, &block.action, req) && match_principal(p, principal)
+            }
+            Match::PublicPrefixGrant { .. } => false,
+        })
+        .collect()
+}
+
+/// Identity predicates (`access_key`, `group`). Unset = don't care; set
+/// on an anonymous request = no match.
+fn match_principal(p: &Predicates, principal: &Principal<'_>) -> bool {
+    if let Some(keys) = &p.access_keys {
+        match principal.access_key_id {
+            Some(key) if keys.iter().any(|k| k == key) => {}
+            _ => return false,
+        }
+    }
+    if let Some(groups) = &p.groups {
+        if !principal.groups.iter().any(|g| groups.contains(g)) {
+            return false;
+        }
+    }
+    true
+}
+
 /// Predicate dispatch. New `Match` variants must add a branch here; the
 /// wildcard is omitted intentionally so the compiler forces an update
 /// when variants grow. The block's `action` is threaded through so
@@ -153,7 +153,7 @@ pub async fn admission_middleware(mut request: Request<Body>, next: Next) -> Res
 /// for X-Forwarded-For / X-Real-IP, falls back to `ConnectInfo` when
 /// wired through. Admission's policy on missing IP is documented on
 /// [`RequestInfo::source_ip`]: fail-closed.
-fn extract_request_info(request: &Request<Body>) -> OwnedRequestInfo {
+pub(super) fn extract_request_info(request: &Request<Body>) -> OwnedRequestInfo {
     let query_string = request.uri().query().unwrap_or("");
     let authenticated =
         request.headers().contains_key("authorization") || has_presigned_query_params(query_string);
@@ -19,10 +19,14 @@
 //!   for Deny and the operator's status+body for Reject — both
 //!   short-circuiting SigV4.
 //!
+//! - `throttle` blocks compile to [`Action::Throttle`] plus a
+//!   [`throttle::Limiter`] owned by the chain. They are not part of the
+//!   first-match pass: [`throttle::throttle_middleware`] applies every
+//!   matching one after SigV4, when the caller's access key and groups
+//!   are known.
+//!
 //! # Still deferred
 //!
-//! - `RateLimit` action variant — Phase 3b.2.c, hooking the existing
-//!   `RateLimiter`.
 //! - `config_flag` predicate dispatch — today always evaluates false
 //!   with a compile-time warn; Phase 3b.2.c adds a flag registry
 //!   starting with `maintenance_mode`.
@@ -49,10 +53,12 @@ use serde::{Deserialize, Serialize};
 pub mod evaluator;
 pub mod middleware;
 pub mod spec;
+pub mod throttle;
 
-pub use evaluator::{evaluate, RequestInfo};
+pub use evaluator::{evaluate, matching_throttles, Principal, RequestInfo};
 pub use middleware::{admission_middleware, AdmissionAllowAnonymous};
-pub use spec::{AdmissionBlockSpec, AdmissionSpec, MatchSpec};
+pub use spec::{AdmissionBlockSpec, AdmissionSpec, MatchSpec, ThrottleKey};
+pub use throttle::throttle_middleware;
 
 /// Ordered list of admission blocks plus the snapshot needed to evaluate
 /// public-prefix matches. The order matters — the evaluator returns on the
@@ -68,6 +74,9 @@ pub struct AdmissionChain {
     /// one place — useful for unit tests that want to construct a chain
     /// without going through live config.
     public_prefixes: std::sync::Arc<PublicPrefixSnapshot>,
+    /// Token-bucket state for each `throttle` block, by block name. Built
+    /// with the chain, so a config change starts every limiter afresh.
+    limiters: std::collections::HashMap<String, std::sync::Arc<throttle::Limiter>>,
 }
 
 /// One admission rule. Stable shape across phases.
@@ -106,7 +115,7 @@ pub enum Match {
     /// Compound predicate authored by the operator. All populated
     /// fields are AND'd together — the block fires only when every
     /// predicate matches the request.
-    Predicates(Predicates),
+    Predicates(Box<Predicates>),
 }
 
 /// Compiled form of [`crate::admission::MatchSpec`]. Immutable after
@@ -133,6 +142,11 @@ pub struct Predicates {
     /// rate-limit work). A `tracing::warn!` fires per block at
     /// build time so operators see the gap.
     pub config_flag: Option<String>,
+    /// Access keys the caller must hold. Throttle blocks only (checked
+    /// in the post-SigV4 pass).
+    pub access_keys: Option<Vec<String>>,
+    /// IAM groups the caller must belong to (any of). Throttle blocks only.
+    pub groups: Option<Vec<String>>,
 }
 
 /// Decision side of an admission block.
@@ -143,6 +157,8 @@ pub struct Predicates {
 /// - [`Action::Reject`] — short-circuit with a custom 4xx/5xx status
 ///   and optional body. Intended for maintenance pages and rate-
 ///   exceed responses.
+/// - [`Action::Throttle`] — token-bucket limits applied after SigV4;
+///   never a first-match decision.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case", tag = "action")]
 pub enum Action {
@@ -164,6 +180,18 @@ pub enum Action {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         message: Option<String>,
     },
+    /// Rate-limit matching requests. Skipped by [`evaluate`]; applied by
+    /// [`throttle::throttle_middleware`] through the chain's limiter for
+    /// this block.
+    Throttle {
+        per: ThrottleKey,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        requests_per_sec: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        burst: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bytes_per_sec: Option<u64>,
+    },
 }
 
 /// Result of evaluating the chain against a request. The evaluator always
@@ -282,9 +310,17 @@ impl AdmissionChain {
         synthesised.sort_by(|a, b| a.name.cmp(&b.name));
         blocks.extend(synthesised);
 
+        let limiters = blocks
+            .iter()
+            .filter_map(|b| {
+                throttle::Limiter::for_block(b).map(|l| (b.name.clone(), std::sync::Arc::new(l)))
+            })
+            .collect();
+
         Self {
             blocks,
             public_prefixes: std::sync::Arc::new(snapshot),
+            limiters,
         }
     }
 
@@ -300,6 +336,11 @@ impl AdmissionChain {
     pub fn public_prefixes(&self) -> &std::sync::Arc<PublicPrefixSnapshot> {
         &self.public_prefixes
     }
+
+    /// The limiter of a `throttle` block, by block name.
+    pub fn limiter(&self, block: &str) -> Option<&std::sync::Arc<throttle::Limiter>> {
+        self.limiters.get(block)
+    }
 }
 
 /// Compile an [`crate::admission::AdmissionBlockSpec`] into its runtime
@@ -366,6 +407,8 @@ fn compile_block(spec: &crate::admission::AdmissionBlockSpec) -> Result<Admissio
         },
         authenticated: spec.match_.authenticated,
         config_flag: spec.match_.config_flag.clone(),
+        access_keys: spec.match_.access_key.clone(),
+        groups: spec.match_.group.clone(),
     };
 
     let action = match &spec.action {
@@ -380,6 +423,17 @@ fn compile_block(spec: &crate::admission::AdmissionBlockSpec) -> Result<Admissio
                 message: message.clone(),
             }
         }
+        specmod::ActionSpec::Tagged(specmod::TaggedAction::Throttle {
+            per,
+            requests_per_sec,
+            burst,
+            bytes_per_sec,
+        }) => Action::Throttle {
+            per: *per,
+            requests_per_sec: *requests_per_sec,
+            burst: *burst,
+            bytes_per_sec: *bytes_per_sec,
+        },
     };
 
     // Warn on unknown config_flag names. The `maintenance_mode` flag
@@ -404,7 +458,7 @@ fn compile_block(spec: &crate::admission::AdmissionBlockSpec) -> Result<Admissio
 
     Ok(AdmissionBlock {
         name: spec.name.clone(),
-        match_: Match::Predicates(predicates),
+        match_: Match::Predicates(Box::new(predicates)),
         action,
     })
 }
@@ -43,6 +43,15 @@
 //!         bucket: "releases"
 //!         path_glob: "*.zip"
 //!       action: allow-anonymous
+//!
+//!     - name: "throttle-ci"
+//!       match:
+//!         group: ["ci"]
+//!       action:
+//!         type: throttle
+//!         per: access-key
+//!         requests_per_sec: 50
+//!         bytes_per_sec: 20971520
 //! ```
 //!
 //! # Invariants
@@ -54,6 +63,9 @@
 //!   blocks are almost certainly operator error.
 //! * Block names are unique across the chain — order still matters,
 //!   but identical names in trace output would be useless.
+//! * `access_key` / `group` predicates need the authenticated identity,
+//!   so they are only accepted on `throttle` blocks (which run after
+//!   SigV4 — see [`crate::admission::throttle`]).
 
 use ipnet::IpNet;
 use schemars::JsonSchema;
@@ -136,6 +148,18 @@ pub struct MatchSpec {
     /// Full dispatch lands with the Phase 3b.2.c rate-limit work.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub config_flag: Option<String>,
+
+    /// Fire only for these access keys (a service account key counts as
+    /// itself). Identity predicates are evaluated after SigV4, so they
+    /// are only valid on `throttle` blocks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_key: Option<Vec<String>>,
+
+    /// Fire only when the caller belongs to one of these IAM groups (a
+    /// service account inherits its parent's groups). `throttle` blocks
+    /// only, like `access_key`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<Vec<String>>,
 }
 
 /// Source-IP entry accepting bare IPs and CIDRs. Parsed via a serde
@@ -278,6 +302,44 @@ pub enum TaggedAction {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         message: Option<String>,
     },
+    /// Token-bucket limits. Unlike the other actions this is not a
+    /// terminal decision: every matching throttle block applies, after
+    /// SigV4, and the request then carries on. Over the request rate →
+    /// `503 SlowDown`; the byte rate paces request and response bodies.
+    Throttle {
+        /// What one token bucket is shared by.
+        #[serde(default)]
+        per: ThrottleKey,
+        /// Sustained requests per second. `None` = no request limit.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        requests_per_sec: Option<u32>,
+        /// Requests allowed in a burst. Defaults to `requests_per_sec`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        burst: Option<u32>,
+        /// Sustained body bytes per second, uploads and downloads
+        /// combined. `None` = no bandwidth limit.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bytes_per_sec: Option<u64>,
+    },
+}
+
+/// How a `throttle` block partitions traffic into token buckets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThrottleKey {
+    /// One bucket for everything the block matches.
+    #[default]
+    Global,
+    /// One bucket per access key. Unsigned requests share one bucket.
+    AccessKey,
+    /// One bucket per IAM group; a request is charged to each of the
+    /// caller's groups (narrowed by `match.group` when set). Callers
+    /// without a group are not limited by the block.
+    Group,
+    /// One bucket per target S3 bucket.
+    Bucket,
+    /// One bucket per client IP.
+    SourceIp,
 }
 
 /// Top-level admission section shape: an ordered list of blocks.
@@ -355,6 +417,16 @@ impl AdmissionSpec {
             }
             block.match_.validate()?;
             block.action.validate()?;
+            let identity_predicate =
+                block.match_.access_key.is_some() || block.match_.group.is_some();
+            if identity_predicate && !block.action.is_throttle() {
+                return Err(format!(
+                    "admission block `{}`: `access_key` / `group` predicates are only \
+                     supported on `throttle` blocks — other actions run before \
+                     authentication, when the caller's identity is not yet known",
+                    block.name
+                ));
+            }
         }
         Ok(())
     }
@@ -422,14 +494,57 @@ impl MatchSpec {
                 return Err(format!("match: invalid path_glob `{}`: {}", glob, e));
             }
         }
+        for (field, list) in [("access_key", &self.access_key), ("group", &self.group)] {
+            if list.as_ref().is_some_and(|l| l.is_empty()) {
+                return Err(format!(
+                    "match: `{field}: []` matches nobody — omit the field or list at least one"
+                ));
+            }
+        }
         Ok(())
     }
 }
 
 impl ActionSpec {
+    fn is_throttle(&self) -> bool {
+        matches!(self, ActionSpec::Tagged(TaggedAction::Throttle { .. }))
+    }
+
     fn validate(&self) -> Result<(), String> {
         match self {
             ActionSpec::Simple(_) => Ok(()),
+            ActionSpec::Tagged(TaggedAction::Throttle {
+                per: _,
+                requests_per_sec,
+                burst,
+                bytes_per_sec,
+            }) => {
+                if requests_per_sec.is_none() && bytes_per_sec.is_none() {
+                    return Err(
+                        "action: throttle needs `requests_per_sec`, `bytes_per_sec`, \
+                         or both"
+                            .to_string(),
+                    );
+                }
+                if *requests_per_sec == Some(0) || *bytes_per_sec == Some(0) {
+                    return Err(
+                        "action: throttle rates must be positive — use `deny` to block \
+                         traffic outright"
+                            .to_string(),
+                    );
+                }
+                if burst.is_some() && requests_per_sec.is_none() {
+                    return Err(
+                        "action: throttle `burst` applies to `requests_per_sec`, which \
+                         is not set"
+                            .to_string(),
+                    );
+                }
+                if *burst == Some(0) {
+                    return Err("action: throttle `burst` must be at least 1".to_string());
+                }
+                Ok(())
+            }
             ActionSpec::Tagged(TaggedAction::Reject { status, message: _ }) => {
                 if !(400..600).contains(status) {
                     return Err(format!(
@@ -813,4 +928,64 @@ action: deny
         let b: SourceIpEntry = serde_yaml::from_str("\"203.0.113.5/32\"").unwrap();
         assert_eq!(a, b);
     }
+
+    #[test]
+    fn deserialize_throttle_tagged_action() {
+        let yaml = r#"
+name: throttle-ci
+match:
+  group: ["ci"]
+action:
+  type: throttle
+  per: access-key
+  requests_per_sec: 50
+  bytes_per_sec: 1048576
+"#;
+        let block: AdmissionBlockSpec = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            block.action,
+            ActionSpec::Tagged(TaggedAction::Throttle {
+                per: ThrottleKey::AccessKey,
+                requests_per_sec: Some(50),
+                burst: None,
+                bytes_per_sec: Some(1_048_576),
+            })
+        );
+        AdmissionSpec {
+            blocks: vec![block],
+        }
+        .validate()
+        .unwrap();
+    }
+
+    #[test]
+    fn throttle_validation_rejects_empty_and_zero_limits() {
+        for action in [
+            "{type: throttle}",
+            "{type: throttle, requests_per_sec: 0}",
+            "{type: throttle, bytes_per_sec: 100, burst: 5}",
+            "{type: throttle, requests_per_sec: 5, burst: 0}",
+        ] {
+            let yaml = format!("name: t\naction: {action}\n");
+            let block: AdmissionBlockSpec = serde_yaml::from_str(&yaml).unwrap();
+            assert!(
+                block.action.validate().is_err(),
+                "{action} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn identity_predicates_only_on_throttle_blocks() {
+        let yaml = r#"
+blocks:
+  - name: deny-ci
+    match:
+      access_key: ["AKCI"]
+    action: deny
+"#;
+        let spec: AdmissionSpec = serde_yaml::from_str(yaml).unwrap();
+        let err = spec.validate().unwrap_err();
+        assert!(err.contains("throttle"), "got: {err}");
+    }
 }
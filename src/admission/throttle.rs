@@ -0,0 +1,623 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Token-bucket throttling for `throttle` admission blocks.
+//!
+//! The admission chain runs before SigV4, but "per access key" and "per
+//! group" limits need the verified identity. So `throttle` blocks are
+//! skipped by the first-match pass ([`super::evaluate`]) and applied here,
+//! in a middleware layered just inside SigV4:
+//!
+//! - every matching throttle block charges one request to its
+//!   [`Limiter`]; any limiter out of request tokens → `503 SlowDown`
+//!   (the status S3 clients already back off on);
+//! - limiters with a byte rate wrap the request and response bodies in
+//!   [`MeteredBody`], which delays frames instead of failing them, so a
+//!   bulk transfer slows down rather than erroring out.
+//!
+//! Limiter state lives on the [`AdmissionChain`](super::AdmissionChain)
+//! and is node-local: with N instances behind a load balancer the
+//! effective cluster-wide limit is up to N times the configured rate.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+
+use axum::body::Body;
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use bytes::Bytes;
+use http_body::{Frame, SizeHint};
+
+use super::evaluator::{matching_throttles, Principal};
+use super::{Action, AdmissionBlock, Match, SharedAdmissionChain, ThrottleKey};
+use crate::iam::{AuthenticatedUser, IamState, SharedIamState};
+use crate::metrics::Metrics;
+
+/// Partition key for requests without an access key under `per: access-key`.
+const ANONYMOUS_PARTITION: &str = "$anonymous";
+
+/// Partitions a limiter tracks, overflow included. Idle (full) buckets
+/// are pruned to make room; `per: source-ip` on an open endpoint is the
+/// case this bounds.
+const MAX_PARTITIONS: usize = 10_000;
+
+/// Shared partition for new keys arriving while every tracked partition
+/// is still active, so a flood of fresh keys shares one budget instead of
+/// growing the map past [`MAX_PARTITIONS`].
+const OVERFLOW_PARTITION: &str = "$overflow";
+
+#[derive(Debug, Clone, Copy)]
+struct Rate {
+    per_sec: f64,
+    burst: f64,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn full(rate: Rate, now: Instant) -> Self {
+        Self {
+            tokens: rate.burst,
+            last: now,
+        }
+    }
+
+    fn refill(&mut self, rate: Rate, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate.per_sec).min(rate.burst);
+        self.last = now;
+    }
+}
+
+#[derive(Debug)]
+struct Partition {
+    requests: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+    touched: Instant,
+}
+
+/// Token buckets of one `throttle` block, one set per partition key.
+#[derive(Debug)]
+pub struct Limiter {
+    block: String,
+    per: ThrottleKey,
+    /// `match.group`, so `per: group` only charges the groups the block
+    /// names rather than every group of a matched caller.
+    group_filter: Option<Vec<String>>,
+    requests: Option<Rate>,
+    bytes: Option<Rate>,
+    partitions: parking_lot::Mutex<HashMap<String, Partition>>,
+}
+
+impl Limiter {
+    /// Build the limiter for a block, or `None` when it isn't a throttle.
+    pub(crate) fn for_block(block: &AdmissionBlock) -> Option<Self> {
+        let Action::Throttle {
+            per,
+            requests_per_sec,
+            burst,
+            bytes_per_sec,
+        } = &block.action
+        else {
+            return None;
+        };
+        let group_filter = match &block.match_ {
+            Match::Predicates(p) => p.groups.clone(),
+            Match::PublicPrefixGrant { .. } => None,
+        };
+        Some(Self {
+            block: block.name.clone(),
+            per: *per,
+            group_filter,
+            requests: requests_per_sec.map(|rps| Rate {
+                per_sec: f64::from(rps),
+                burst: f64::from(burst.unwrap_or(rps).max(1)),
+            }),
+            // One second of traffic is the byte burst.
+            bytes: bytes_per_sec.map(|bps| Rate {
+                per_sec: bps as f64,
+                burst: bps as f64,
+            }),
+            partitions: parking_lot::Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Name of the block this limiter belongs to.
+    pub fn block(&self) -> &str {
+        &self.block
+    }
+
+    /// True when the block limits bandwidth.
+    pub fn limits_bytes(&self) -> bool {
+        self.bytes.is_some()
+    }
+
+    /// The token buckets a request is charged to. Empty = the block does
+    /// not limit this request (`per: group` for a caller in no group).
+    pub fn partitions_for(
+        &self,
+        bucket: &str,
+        source_ip: Option<std::net::IpAddr>,
+        principal: &Principal<'_>,
+    ) -> Vec<String> {
+        match self.per {
+            ThrottleKey::Global => vec![String::new()],
+            ThrottleKey::AccessKey => vec![principal
+                .access_key_id
+                .unwrap_or(ANONYMOUS_PARTITION)
+                .to_string()],
+            ThrottleKey::Group => principal
+                .groups
+                .iter()
+                .filter(|g| self.group_filter.as_ref().is_none_or(|f| f.contains(g)))
+                .cloned()
+                .collect(),
+            ThrottleKey::Bucket => vec![bucket.to_string()],
+            ThrottleKey::SourceIp => vec![source_ip
+                .map(|ip| ip.to_string())
+                .unwrap_or_else(|| "unknown".to_string())],
+        }
+    }
+
+    /// The partition `key` is charged to, created full if missing. A new
+    /// key that finds the map at capacity after pruning lands in
+    /// [`OVERFLOW_PARTITION`]; one slot stays reserved for it.
+    fn slot<'k>(
+        &self,
+        map: &mut HashMap<String, Partition>,
+        key: &'k str,
+        now: Instant,
+    ) -> &'k str {
+        let fits = |map: &HashMap<String, Partition>| {
+            let reserved = usize::from(!map.contains_key(OVERFLOW_PARTITION));
+            map.len() + reserved < MAX_PARTITIONS
+        };
+        let mut room = map.contains_key(key) || fits(map);
+        if !room {
+            self.prune(map, now);
+            room = fits(map);
+        }
+        let key = if room { key } else { OVERFLOW_PARTITION };
+        let partition = map.entry(key.to_string()).or_insert_with(|| Partition {
+            requests: self.requests.map(|r| TokenBucket::full(r, now)),
+            bytes: self.bytes.map(|r| TokenBucket::full(r, now)),
+            touched: now,
+        });
+        partition.touched = now;
+        key
+    }
+
+    /// Resolve `partitions` to the distinct map keys they are charged to.
+    fn slots<'k>(
+        &self,
+        map: &mut HashMap<String, Partition>,
+        partitions: &'k [String],
+        now: Instant,
+    ) -> Vec<&'k str> {
+        let mut keys: Vec<&str> = Vec::with_capacity(partitions.len());
+        for key in partitions {
+            let slot = self.slot(map, key, now);
+            if !keys.contains(&slot) {
+                keys.push(slot);
+            }
+        }
+        keys
+    }
+
+    /// Drop partitions idle long enough to have refilled completely —
+    /// recreating them full later is indistinguishable.
+    fn prune(&self, map: &mut HashMap<String, Partition>, now: Instant) {
+        let refill_secs = [self.requests, self.bytes]
+            .into_iter()
+            .flatten()
+            .map(|r| r.burst / r.per_sec)
+            .fold(0.0, f64::max);
+        let idle = Duration::from_secs_f64(refill_secs.max(1.0));
+        map.retain(|_, p| now.saturating_duration_since(p.touched) < idle);
+    }
+
+    /// Take one request token from every partition, or none when any of
+    /// them is empty. Always true for a block without a request rate.
+    pub fn admit(&self, partitions: &[String], now: Instant) -> bool {
+        let Some(rate) = self.requests else {
+            return true;
+        };
+        let mut map = self.partitions.lock();
+        let keys = self.slots(&mut map, partitions, now);
+        let mut allowed = true;
+        for key in &keys {
+            let bucket = map
+                .get_mut(*key)
+                .and_then(|p| p.requests.as_mut())
+                .expect("request bucket exists when a request rate is set");
+            bucket.refill(rate, now);
+            allowed &= bucket.tokens >= 1.0;
+        }
+        if allowed {
+            for key in &keys {
+                if let Some(bucket) = map.get_mut(*key).and_then(|p| p.requests.as_mut()) {
+                    bucket.tokens -= 1.0;
+                }
+            }
+        }
+        allowed
+    }
+
+    /// Charge `n` body bytes to every partition and return how long to
+    /// hold them back. Buckets may go into debt; the delay is what it
+    /// takes to pay the largest debt off.
+    pub fn charge_bytes(&self, partitions: &[String], n: usize, now: Instant) -> Duration {
+        let Some(rate) = self.bytes else {
+            return Duration::ZERO;
+        };
+        let mut map = self.partitions.lock();
+        let keys = self.slots(&mut map, partitions, now);
+        let mut wait = Duration::ZERO;
+        for key in keys {
+            let bucket = map
+                .get_mut(key)
+                .and_then(|p| p.bytes.as_mut())
+                .expect("byte bucket exists when a byte rate is set");
+            bucket.refill(rate, now);
+            bucket.tokens -= n as f64;
+            if bucket.tokens < 0.0 {
+                wait = wait.max(Duration::from_secs_f64(-bucket.tokens / rate.per_sec));
+            }
+        }
+        wait
+    }
+}
+
+/// The byte limiters one request is charged to.
+struct ByteMeter {
+    limiters: Vec<(Arc<Limiter>, Vec<String>)>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl ByteMeter {
+    fn charge(&self, n: usize) -> Duration {
+        if n == 0 {
+            return Duration::ZERO;
+        }
+        let now = Instant::now();
+        let mut wait = Duration::ZERO;
+        for (limiter, partitions) in &self.limiters {
+            let delay = limiter.charge_bytes(partitions, n, now);
+            if let Some(metrics) = &self.metrics {
+                metrics
+                    .admission_throttle_bytes_total
+                    .with_label_values(&[limiter.block()])
+                    .inc_by(n as u64);
+                if !delay.is_zero() {
+                    metrics
+                        .admission_throttle_wait_seconds_total
+                        .with_label_values(&[limiter.block()])
+                        .inc_by(delay.as_secs_f64());
+                }
+            }
+            wait = wait.max(delay);
+        }
+        wait
+    }
+}
+
+/// A body whose data frames are held back until the byte limiters allow
+/// them. Size hints and end-of-stream pass through, so S3 framing
+/// (Content-Length, aws-chunked decoding) is unaffected.
+struct MeteredBody<B> {
+    inner: B,
+    meter: Arc<ByteMeter>,
+    delay: Option<Pin<Box<tokio::time::Sleep>>>,
+    pending: Option<Frame<Bytes>>,
+}
+
+impl<B> MeteredBody<B> {
+    fn new(inner: B, meter: Arc<ByteMeter>) -> Self {
+        Self {
+            inner,
+            meter,
+            delay: None,
+            pending: None,
+        }
+    }
+}
+
+impl<B> http_body::Body for MeteredBody<B>
+where
+    B: http_body::Body<Data = Bytes> + Unpin,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, B::Error>>> {
+        let this = &mut *self;
+        if let Some(delay) = this.delay.as_mut() {
+            ready!(delay.as_mut().poll(cx));
+            this.delay = None;
+            return Poll::Ready(this.pending.take().map(Ok));
+        }
+        match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+            Some(Ok(frame)) => {
+                let n = frame.data_ref().map_or(0, Bytes::len);
+                let wait = this.meter.charge(n);
+                if wait.is_zero() {
+                    return Poll::Ready(Some(Ok(frame)));
+                }
+                let mut delay = Box::pin(tokio::time::sleep(wait));
+                if delay.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(Some(Ok(frame)));
+                }
+                this.pending = Some(frame);
+                this.delay = Some(delay);
+                Poll::Pending
+            }
+            other => Poll::Ready(other),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.pending.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let inner = self.inner.size_hint();
+        let held = self
+            .pending
+            .as_ref()
+            .and_then(Frame::data_ref)
+            .map_or(0, |d| d.len() as u64);
+        let mut hint = SizeHint::new();
+        hint.set_lower(inner.lower() + held);
+        if let Some(upper) = inner.upper() {
+            hint.set_upper(upper + held);
+        }
+        hint
+    }
+}
+
+/// Middleware: apply every matching `throttle` block. Layered inside
+/// SigV4 so the caller's access key (and through IAM, its groups) is
+/// known. A chain without throttle blocks costs one `ArcSwap` load.
+pub async fn throttle_middleware(request: Request<Body>, next: Next) -> Response {
+    let Some(chain) = request
+        .extensions()
+        .get::<SharedAdmissionChain>()
+        .map(|h| h.load_full())
+    else {
+        return next.run(request).await;
+    };
+    if chain.limiters.is_empty() {
+        return next.run(request).await;
+    }
+
+    let access_key_id = request
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .map(|u| u.access_key_id.clone())
+        .filter(|k| !k.is_empty());
+    let groups = match (&access_key_id, request.extensions().get::<SharedIamState>()) {
+        (Some(key), Some(iam)) => match &**iam.load() {
+            IamState::Iam(index) => index.group_names(key),
+            IamState::Disabled | IamState::Legacy(_) => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    let principal = Principal {
+        access_key_id: access_key_id.as_deref(),
+        groups: &groups,
+    };
+    let owned = super::middleware::extract_request_info(&request);
+    let req_info = owned.as_ref();
+    let metrics = request.extensions().get::<Arc<Metrics>>().cloned();
+
+    let now = Instant::now();
+    let mut byte_limiters = Vec::new();
+    for block in matching_throttles(&chain, &req_info, &principal) {
+        let Some(limiter) = chain.limiter(&block.name) else {
+            continue;
+        };
+        let partitions = limiter.partitions_for(&owned.bucket, owned.source_ip, &principal);
+        if partitions.is_empty() {
+            continue;
+        }
+        if !limiter.admit(&partitions, now) {
+            if let Some(metrics) = &metrics {
+                metrics
+                    .admission_throttle_rejections_total
+                    .with_label_values(&[&block.name])
+                    .inc();
+            }
+            tracing::warn!(
+                target: "deltaglider_proxy::admission",
+                block = %block.name,
+                method = %owned.method,
+                bucket = %owned.bucket,
+                access_key = ?principal.access_key_id,
+                source_ip = ?owned.source_ip,
+                "[admission] THROTTLE block `{}` over its request rate",
+                block.name
+            );
+            return crate::api::errors::S3Error::SlowDown(format!(
+                "request rate limit '{}' exceeded — please retry",
+                block.name
+            ))
+            .into_response();
+        }
+        if limiter.limits_bytes() {
+            byte_limiters.push((limiter.clone(), partitions));
+        }
+    }
+
+    if byte_limiters.is_empty() {
+        return next.run(request).await;
+    }
+    let meter = Arc::new(ByteMeter {
+        limiters: byte_limiters,
+        metrics,
+    });
+    let request = request.map(|body| Body::new(MeteredBody::new(body, meter.clone())));
+    next.run(request)
+        .await
+        .map(|body| Body::new(MeteredBody::new(body, meter)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admission::spec::{ActionSpec, AdmissionBlockSpec, MatchSpec, TaggedAction};
+    use crate::admission::AdmissionChain;
+
+    fn throttle_chain(
+        per: ThrottleKey,
+        requests_per_sec: Option<u32>,
+        bytes_per_sec: Option<u64>,
+        match_: MatchSpec,
+    ) -> AdmissionChain {
+        AdmissionChain::from_config_parts(
+            &Default::default(),
+            &[AdmissionBlockSpec {
+                name: "t".into(),
+                match_,
+                action: ActionSpec::Tagged(TaggedAction::Throttle {
+                    per,
+                    requests_per_sec,
+                    burst: None,
+                    bytes_per_sec,
+                }),
+            }],
+        )
+    }
+
+    #[test]
+    fn request_bucket_allows_burst_then_refills() {
+        let chain = throttle_chain(ThrottleKey::Global, Some(2), None, MatchSpec::default());
+        let limiter = chain.limiter("t").unwrap();
+        let keys = vec![String::new()];
+        let t0 = Instant::now();
+        assert!(limiter.admit(&keys, t0));
+        assert!(limiter.admit(&keys, t0));
+        assert!(!limiter.admit(&keys, t0), "burst of 2 is spent");
+        assert!(limiter.admit(&keys, t0 + Duration::from_millis(500)));
+        assert!(!limiter.admit(&keys, t0 + Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn partitions_follow_the_throttle_key() {
+        let groups = vec!["ci".to_string(), "ops".to_string()];
+        let principal = Principal {
+            access_key_id: Some("AKCI"),
+            groups: &groups,
+        };
+        let ip = Some("10.0.0.7".parse().unwrap());
+        let parts = |per, match_| {
+            throttle_chain(per, Some(1), None, match_)
+                .limiter("t")
+                .unwrap()
+                .partitions_for("builds", ip, &principal)
+        };
+        assert_eq!(
+            parts(ThrottleKey::AccessKey, MatchSpec::default()),
+            ["AKCI"]
+        );
+        assert_eq!(parts(ThrottleKey::Bucket, MatchSpec::default()), ["builds"]);
+        assert_eq!(
+            parts(ThrottleKey::SourceIp, MatchSpec::default()),
+            ["10.0.0.7"]
+        );
+        assert_eq!(
+            parts(ThrottleKey::Group, MatchSpec::default()),
+            ["ci", "ops"]
+        );
+        let only_ci = MatchSpec {
+            group: Some(vec!["ci".into()]),
+            ..Default::default()
+        };
+        assert_eq!(parts(ThrottleKey::Group, only_ci), ["ci"]);
+
+        // One access key running dry leaves another untouched.
+        let chain = throttle_chain(ThrottleKey::AccessKey, Some(1), None, MatchSpec::default());
+        let limiter = chain.limiter("t").unwrap();
+        let now = Instant::now();
+        assert!(limiter.admit(&["AK1".into()], now));
+        assert!(!limiter.admit(&["AK1".into()], now));
+        assert!(limiter.admit(&["AK2".into()], now));
+    }
+
+    #[test]
+    fn a_full_partition_map_sends_new_keys_to_the_overflow_bucket() {
+        let chain = throttle_chain(ThrottleKey::SourceIp, Some(1), None, MatchSpec::default());
+        let limiter = chain.limiter("t").unwrap();
+        let t0 = Instant::now();
+        for i in 0..MAX_PARTITIONS - 1 {
+            assert!(limiter.admit(&[format!("ip-{i}")], t0));
+        }
+
+        // Every tracked bucket is active: new keys share one budget.
+        assert!(limiter.admit(&["late-1".into()], t0));
+        assert!(!limiter.admit(&["late-2".into()], t0));
+        assert_eq!(limiter.partitions.lock().len(), MAX_PARTITIONS);
+
+        // Once the active buckets have refilled they are evicted first.
+        let later = t0 + Duration::from_secs(2);
+        assert!(limiter.admit(&["late-2".into()], later));
+        assert!(!limiter.admit(&["late-2".into()], later));
+        assert!(limiter.partitions.lock().contains_key("late-2"));
+    }
+
+    #[test]
+    fn byte_debt_turns_into_delay() {
+        let chain = throttle_chain(ThrottleKey::Global, None, Some(1000), MatchSpec::default());
+        let limiter = chain.limiter("t").unwrap();
+        let keys = vec![String::new()];
+        let t0 = Instant::now();
+        assert_eq!(limiter.charge_bytes(&keys, 1000, t0), Duration::ZERO);
+        assert_eq!(
+            limiter.charge_bytes(&keys, 500, t0),
+            Duration::from_millis(500)
+        );
+        assert!(limiter.admit(&keys, t0), "no request rate set");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn metered_body_paces_frames_and_keeps_size_hint() {
+        let chain = throttle_chain(ThrottleKey::Global, None, Some(1000), MatchSpec::default());
+        let meter = Arc::new(ByteMeter {
+            limiters: vec![(chain.limiter("t").unwrap().clone(), vec![String::new()])],
+            metrics: None,
+        });
+        let body = MeteredBody::new(Body::from(vec![0u8; 3000]), meter);
+        assert_eq!(http_body::Body::size_hint(&body).exact(), Some(3000));
+
+        let start = tokio::time::Instant::now();
+        let bytes = collect(body).await;
+        assert_eq!(bytes.len(), 3000);
+        // 1000 bytes of burst, the remaining 2000 at 1000 B/s.
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+    }
+
+    async fn collect<B>(mut body: B) -> Vec<u8>
+    where
+        B: http_body::Body<Data = Bytes> + Unpin,
+        B::Error: std::fmt::Debug,
+    {
+        let mut out = Vec::new();
+        while let Some(frame) = std::future::poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await
+        {
+            if let Ok(data) = frame.unwrap().into_data() {
+                out.extend_from_slice(&data);
+            }
+        }
+        out
+    }
+}
@@ -1,43 +1,280 @@
 // SPDX-License-Identifier: BUSL-1.1
 
-//! Audit log viewer handler (Wave 11 of the admin UI revamp).
+//! Audit log viewer handlers (Wave 11 of the admin UI revamp).
 //!
-//! GET `/_/api/admin/audit?limit=N` returns the last N entries from
-//! the in-memory audit ring (`crate::audit::recent_audit`). Ordering
-//! is newest-first so the GUI can render the list without having to
-//! re-sort.
+//! GET `/_/api/admin/audit` returns entries newest-first so the GUI can
+//! render the list without having to re-sort. They come from the durable
+//! audit store (`crate::audit_store`) when `advanced.audit` is enabled,
+//! else from the in-memory ring (`crate::audit::recent_audit`), which only
+//! holds the last `DGP_AUDIT_RING_SIZE` entries. `?source=ring|store`
+//! overrides the choice; both take the same filters.
 //!
-//! The ring is supplementary — admin-compliance operators should
-//! still read stdout / their log pipeline for authoritative audit
-//! state. This endpoint is a GUI convenience for incident
-//! debugging, not a substitute for durable log shipping.
+//! GET `/_/api/admin/audit/export` streams the matching entries oldest-first
+//! as CSV or JSON Lines, and GET `/_/api/admin/audit/verify` recomputes the
+//! store's hash chain.
 
-use axum::{extract::Query, http::StatusCode, response::IntoResponse, Json};
-use serde::Deserialize;
+use super::{audit_log, AdminState};
+use crate::audit_store::{
+    csv_line, jsonl_line, verify_chain, AuditFilter, AuditVerification, CSV_HEADER,
+};
+use axum::body::{Body, Bytes};
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// Ceiling on `?limit` — keeps one burst request from serialising
-/// the whole ring into a single response. The ring itself is
-/// bounded upstream (`DGP_AUDIT_RING_SIZE`, default 500), so this
-/// is really a defensive max rather than a new knob.
+/// a huge page into a single response. The ring itself is bounded
+/// upstream (`DGP_AUDIT_RING_SIZE`, default 500); the store is paged
+/// with `?before`.
 const MAX_LIMIT: usize = 500;
 const DEFAULT_LIMIT: usize = 100;
+/// Entries read from the store per export chunk.
+const EXPORT_PAGE: usize = 1_000;
 
 #[derive(Deserialize)]
 pub struct AuditQuery {
     /// How many of the most-recent entries to return. Clamped to
     /// `[1, MAX_LIMIT]`. Defaults to 100 when absent.
     limit: Option<usize>,
+    /// Store entries only: return entries older than this id (the
+    /// previous page's `next_before`).
+    before: Option<i64>,
+    /// `ring` or `store`. Defaults to `store` when the store is enabled.
+    source: Option<String>,
+    /// `csv` (default) or `jsonl`; export only.
+    format: Option<String>,
+    user: Option<String>,
+    bucket: Option<String>,
+    /// Key prefix.
+    key: Option<String>,
+    action: Option<String>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
 }
 
-/// GET /_/api/admin/audit — recent audit entries, newest first.
-pub async fn get_audit(Query(q): Query<AuditQuery>) -> impl IntoResponse {
+impl AuditQuery {
+    fn filter(&self) -> AuditFilter {
+        AuditFilter {
+            user: self.user.clone(),
+            bucket: self.bucket.clone(),
+            key: self.key.clone(),
+            action: self.action.clone(),
+            from: self.from,
+            to: self.to,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Source {
+    Ring,
+    Store,
+}
+
+impl Source {
+    fn as_str(self) -> &'static str {
+        match self {
+            Source::Ring => "ring",
+            Source::Store => "store",
+        }
+    }
+}
+
+/// Resolve `?source` against what this node has.
+async fn source(
+    state: &AdminState,
+    requested: Option<&str>,
+) -> Result<Source, (StatusCode, String)> {
+    let store_enabled = state.config.read().await.audit.enabled && state.config_db.is_some();
+    match requested.map(str::trim).filter(|s| !s.is_empty()) {
+        None if store_enabled => Ok(Source::Store),
+        None | Some("ring") => Ok(Source::Ring),
+        Some("store") if state.config_db.is_some() => Ok(Source::Store),
+        Some("store") => Err((
+            StatusCode::CONFLICT,
+            "the audit store needs the config DB".to_string(),
+        )),
+        Some(other) => Err((
+            StatusCode::BAD_REQUEST,
+            format!("unknown audit source: {other}"),
+        )),
+    }
+}
+
+#[derive(Serialize)]
+pub struct AuditResponse {
+    entries: serde_json::Value,
+    limit: usize,
+    source: &'static str,
+    /// Whether `advanced.audit` is enabled.
+    store_enabled: bool,
+    /// Pass as `?before` for the next (older) page; store only, absent on
+    /// the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_before: Option<i64>,
+}
+
+/// GET /_/api/admin/audit — audit entries, newest first.
+pub async fn get_audit(
+    State(state): State<Arc<AdminState>>,
+    Query(q): Query<AuditQuery>,
+) -> Result<Json<AuditResponse>, (StatusCode, String)> {
     let limit = q.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
-    let entries = crate::audit::recent_audit(limit);
-    (
-        StatusCode::OK,
-        Json(serde_json::json!({
-            "entries": entries,
-            "limit": limit,
-        })),
-    )
+    let source = source(&state, q.source.as_deref()).await?;
+    let store_enabled = state.config.read().await.audit.enabled;
+    let filter = q.filter();
+    let (entries, next_before) = match (source, &state.config_db) {
+        (Source::Store, Some(db)) => {
+            let rows = db
+                .lock()
+                .await
+                .audit_query(&filter, q.before, limit)
+                .map_err(internal)?;
+            let next = (rows.len() == limit)
+                .then(|| rows.last().map(|e| e.id))
+                .flatten();
+            (serde_json::to_value(rows).map_err(internal)?, next)
+        }
+        _ => {
+            let rows: Vec<_> = crate::audit::recent_audit(usize::MAX)
+                .into_iter()
+                .filter(|e| filter.matches(e))
+                .take(limit)
+                .collect();
+            (serde_json::to_value(rows).map_err(internal)?, None)
+        }
+    };
+    Ok(Json(AuditResponse {
+        entries,
+        limit,
+        source: source.as_str(),
+        store_enabled,
+        next_before,
+    }))
+}
+
+/// GET /_/api/admin/audit/export — matching entries, oldest first, as a CSV
+/// (`?format=csv`, the default) or JSON Lines (`?format=jsonl`) download.
+/// The export itself is audited.
+pub async fn export_audit(
+    State(state): State<Arc<AdminState>>,
+    headers: HeaderMap,
+    Query(q): Query<AuditQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let jsonl = match q.format.as_deref().unwrap_or("csv") {
+        "csv" => false,
+        "jsonl" => true,
+        other => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("unknown export format: {other}"),
+            ))
+        }
+    };
+    let source = source(&state, q.source.as_deref()).await?;
+    let filter = q.filter();
+    audit_log(
+        "audit_export",
+        "admin",
+        &format!(
+            "{}:{}",
+            source.as_str(),
+            if jsonl { "jsonl" } else { "csv" }
+        ),
+        &headers,
+    );
+
+    let render = move |e: &crate::audit_store::StoredAuditEntry| {
+        if jsonl {
+            jsonl_line(e)
+        } else {
+            csv_line(e)
+        }
+    };
+    let header_line = if jsonl { "" } else { CSV_HEADER };
+    let body = match (source, state.config_db.clone()) {
+        (Source::Store, Some(db)) => {
+            let pages = futures::stream::unfold(Some(0i64), move |after| {
+                let db = db.clone();
+                let filter = filter.clone();
+                async move {
+                    let after = after?;
+                    let page = match db.lock().await.audit_scan(&filter, after, EXPORT_PAGE) {
+                        Ok(page) => page,
+                        Err(e) => {
+                            tracing::warn!("audit export stopped: {e}");
+                            return None;
+                        }
+                    };
+                    let last = page.last()?.id;
+                    let next = (page.len() == EXPORT_PAGE).then_some(last);
+                    let chunk: String = page.iter().map(render).collect();
+                    Some((Ok::<_, std::convert::Infallible>(Bytes::from(chunk)), next))
+                }
+            });
+            let head = futures::stream::once(async move {
+                Ok::<_, std::convert::Infallible>(Bytes::from_static(header_line.as_bytes()))
+            });
+            Body::from_stream(futures::StreamExt::chain(head, pages))
+        }
+        _ => {
+            // The ring has no chain; export it with empty ids and hashes.
+            let mut rows = crate::audit::recent_audit(usize::MAX);
+            rows.reverse();
+            let mut out = String::from(header_line);
+            for e in rows.iter().filter(|e| filter.matches(e)) {
+                out.push_str(&render(&crate::audit_store::StoredAuditEntry {
+                    id: 0,
+                    timestamp: e.timestamp,
+                    action: e.action.clone(),
+                    user: e.user.clone(),
+                    target: e.target.clone(),
+                    ip: e.ip.clone(),
+                    ua: e.ua.clone(),
+                    bucket: e.bucket.clone(),
+                    path: e.path.clone(),
+                    prev_hash: String::new(),
+                    hash: String::new(),
+                }));
+            }
+            Body::from(out)
+        }
+    };
+
+    let (content_type, ext) = if jsonl {
+        ("application/x-ndjson", "jsonl")
+    } else {
+        ("text/csv; charset=utf-8", "csv")
+    };
+    let filename = format!("dgp-audit-{}.{ext}", Utc::now().format("%Y%m%dT%H%M%SZ"));
+    let mut resp = body.into_response();
+    resp.headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    resp.headers_mut().insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!("attachment; filename=\"{filename}\""))
+            .unwrap_or_else(|_| HeaderValue::from_static("attachment")),
+    );
+    Ok(resp)
+}
+
+/// GET /_/api/admin/audit/verify — recompute the store's hash chain.
+pub async fn verify_audit(
+    State(state): State<Arc<AdminState>>,
+) -> Result<Json<AuditVerification>, (StatusCode, String)> {
+    let Some(db) = state.config_db.as_ref() else {
+        return Err((
+            StatusCode::CONFLICT,
+            "the audit store needs the config DB".to_string(),
+        ));
+    };
+    verify_chain(db).await.map(Json).map_err(internal)
+}
+
+fn internal(e: impl std::fmt::Display) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
 }
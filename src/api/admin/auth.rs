@@ -181,9 +181,9 @@ pub(super) fn secure_cookies_with(headers: Option<&HeaderMap>) -> bool {
 /// Remove the caller's previous session, if any. Called at every
 /// session-minting boundary so an XSS-leaked cookie from before
 /// "log out + log in" can't outlive the rotation.
-pub(super) fn drop_prior_session(state: &AdminState, headers: &HeaderMap) {
+pub(super) async fn drop_prior_session(state: &AdminState, headers: &HeaderMap) {
     if let Some(prior) = extract_session_token(headers) {
-        state.sessions.remove(&prior);
+        state.sessions.remove(&prior).await;
     }
 }
 
@@ -191,17 +191,18 @@ pub(super) fn drop_prior_session(state: &AdminState, headers: &HeaderMap) {
 /// prior cookie BEFORE creating the new one (XSS-rotation defense) — so a new
 /// mint path physically cannot forget it. All session-minting routes go through
 /// here; setting S3 creds stays at the call site (it varies per path).
-pub(super) fn mint_session(
+pub(super) async fn mint_session(
     state: &AdminState,
     headers: &HeaderMap,
     connect_info: Option<&ConnectInfo<SocketAddr>>,
     auth_method: AuthMethod,
     kind: SessionKind,
 ) -> String {
-    drop_prior_session(state, headers);
+    drop_prior_session(state, headers).await;
     state
         .sessions
         .create_session(request_client_ip(headers, connect_info), auth_method, kind)
+        .await
 }
 
 /// Test-only shim: equivalent to [`session_cookie_with_headers`] with
@@ -267,16 +268,19 @@ pub(super) async fn auto_populate_s3_creds(
         crate::config::BackendConfig::S3 { region, .. } => region.clone(),
         _ => "us-east-1".to_string(),
     };
-    state.sessions.set_s3_creds(
-        token,
-        S3SessionCredentials {
-            endpoint: String::new(),
-            region,
-            bucket: String::new(),
-            access_key_id,
-            secret_access_key,
-        },
-    );
+    state
+        .sessions
+        .set_s3_creds(
+            token,
+            S3SessionCredentials {
+                endpoint: String::new(),
+                region,
+                bucket: String::new(),
+                access_key_id,
+                secret_access_key,
+            },
+        )
+        .await;
 }
 
 /// Format a session cookie that clears the login token.
@@ -302,7 +306,7 @@ pub(super) fn extract_session_token(headers: &HeaderMap) -> Option<String> {
         })
 }
 
-fn request_client_ip(
+pub(super) fn request_client_ip(
     headers: &HeaderMap,
     connect_info: Option<&ConnectInfo<SocketAddr>>,
 ) -> Option<IpAddr> {
@@ -376,7 +380,8 @@ pub async fn login(
         connect_info.as_ref(),
         AuthMethod::Bootstrap,
         SessionKind::AdminGui,
-    );
+    )
+    .await;
 
     // Auto-populate S3 credentials from config so "login IS connect".
     // The legacy access_key_id/secret_access_key are the proxy's own auth credentials.
@@ -398,10 +403,13 @@ pub async fn login(
             // Open-access deployments: no proxy SigV4 keys. Without session S3 creds,
             // a hard refresh clears the in-memory SDK and the file browser stops listing
             // (PUT/GET would fail). Mirror `open_browser_connect` anonymous pair.
-            state.sessions.set_s3_creds(
-                &token,
-                S3SessionCredentials::anonymous(String::new(), region, String::new()),
-            );
+            state
+                .sessions
+                .set_s3_creds(
+                    &token,
+                    S3SessionCredentials::anonymous(String::new(), region, String::new()),
+                )
+                .await;
         }
     }
 
@@ -419,7 +427,7 @@ pub async fn login(
 /// POST /api/admin/logout — clear session.
 pub async fn logout(State(state): State<Arc<AdminState>>, headers: HeaderMap) -> impl IntoResponse {
     if let Some(token) = extract_session_token(&headers) {
-        state.sessions.remove(&token);
+        state.sessions.remove(&token).await;
     }
 
     let mut resp_headers = HeaderMap::new();
@@ -440,15 +448,14 @@ pub async fn check_session(
 ) -> impl IntoResponse {
     let client_ip = request_client_ip(&headers, connect_info.as_ref());
     let token = extract_session_token(&headers);
-    let valid = token
-        .as_ref()
-        .map(|t| state.sessions.validate(t, client_ip))
-        .unwrap_or(false);
-    let admin_gui = token
-        .as_ref()
-        .filter(|_| valid)
-        .map(|t| state.sessions.allows_admin_gui(t, client_ip))
-        .unwrap_or(false);
+    let valid = match &token {
+        Some(t) => state.sessions.validate(t, client_ip).await,
+        None => false,
+    };
+    let admin_gui = match &token {
+        Some(t) if valid => state.sessions.allows_admin_gui(t, client_ip).await,
+        _ => false,
+    };
 
     Json(SessionResponse { valid, admin_gui })
 }
@@ -567,7 +574,7 @@ async fn resolve_session_user(
     client_ip: Option<IpAddr>,
 ) -> Option<WhoamiUserInfo> {
     let token = extract_session_token(headers)?;
-    let auth_method = state.sessions.auth_method(&token, client_ip)?;
+    let auth_method = state.sessions.auth_method(&token, client_ip).await?;
     match auth_method {
         crate::session::AuthMethod::OpenLift => None,
         crate::session::AuthMethod::Bootstrap => Some(WhoamiUserInfo {
@@ -711,7 +718,8 @@ pub async fn login_as(
             access_key_id: body.access_key_id.clone(),
         },
         SessionKind::AdminGui,
-    );
+    )
+    .await;
 
     // Auto-populate S3 credentials from the IAM login so "login IS connect"
     auto_populate_s3_creds(
@@ -823,18 +831,22 @@ pub async fn browser_session_connect(
             access_key_id: ak.clone(),
         },
         SessionKind::S3BrowserLift,
-    );
+    )
+    .await;
 
-    state.sessions.set_s3_creds(
-        &token,
-        S3SessionCredentials {
-            endpoint: body.endpoint,
-            region,
-            bucket: body.bucket,
-            access_key_id: ak,
-            secret_access_key: body.secret_access_key,
-        },
-    );
+    state
+        .sessions
+        .set_s3_creds(
+            &token,
+            S3SessionCredentials {
+                endpoint: body.endpoint,
+                region,
+                bucket: body.bucket,
+                access_key_id: ak,
+                secret_access_key: body.secret_access_key,
+            },
+        )
+        .await;
 
     tracing::info!(
         "S3 browser-lift session created for access key '{}' from {}",
@@ -911,12 +923,16 @@ pub async fn open_browser_connect(
         connect_info.as_ref(),
         AuthMethod::OpenLift,
         SessionKind::S3BrowserLift,
-    );
+    )
+    .await;
 
-    state.sessions.set_s3_creds(
-        &token,
-        S3SessionCredentials::anonymous(body.endpoint, region, body.bucket),
-    );
+    state
+        .sessions
+        .set_s3_creds(
+            &token,
+            S3SessionCredentials::anonymous(body.endpoint, region, body.bucket),
+        )
+        .await;
 
     audit_log("open_browser_connect", "open", "anonymous", &req_headers);
 
@@ -943,9 +959,10 @@ pub async fn require_session(
         .get::<ConnectInfo<SocketAddr>>()
         .map(|ci| ci.0.ip());
     let client_ip = rate_limiter::extract_client_ip_with_peer(&headers, peer_ip);
-    let valid = extract_session_token(&headers)
-        .map(|t| state.sessions.validate(&t, client_ip))
-        .unwrap_or(false);
+    let valid = match extract_session_token(&headers) {
+        Some(t) => state.sessions.validate(&t, client_ip).await,
+        None => false,
+    };
 
     if !valid {
         return (
@@ -979,14 +996,14 @@ pub async fn require_admin_gui_session(
     };
     // No LIVE session (unknown / expired / REVOKED / wrong IP) is 401 — the UI
     // must re-login. 403 is reserved for a live session of the wrong KIND.
-    if !state.sessions.validate(&token, client_ip) {
+    if !state.sessions.validate(&token, client_ip).await {
         return (
             StatusCode::UNAUTHORIZED,
             Json(serde_json::json!({"error": "unauthorized"})),
         )
             .into_response();
     }
-    if !state.sessions.allows_admin_gui(&token, client_ip) {
+    if !state.sessions.allows_admin_gui(&token, client_ip).await {
         return (
             StatusCode::FORBIDDEN,
             Json(serde_json::json!({"error": "admin_session_required"})),
@@ -1068,7 +1085,7 @@ pub async fn get_s3_session_creds(
         None => return StatusCode::UNAUTHORIZED.into_response(),
     };
     let client_ip = request_client_ip(&headers, connect_info.as_ref());
-    match state.sessions.get_s3_creds(&token, client_ip) {
+    match state.sessions.get_s3_creds(&token, client_ip).await {
         Some(creds) => (
             StatusCode::OK,
             [
@@ -1096,7 +1113,7 @@ pub async fn set_s3_session_creds(
         Some(t) => t,
         None => return StatusCode::UNAUTHORIZED.into_response(),
     };
-    state.sessions.set_s3_creds(&token, creds);
+    state.sessions.set_s3_creds(&token, creds).await;
     StatusCode::OK.into_response()
 }
 
@@ -1109,7 +1126,7 @@ pub async fn clear_s3_session_creds(
         Some(t) => t,
         None => return StatusCode::UNAUTHORIZED.into_response(),
     };
-    state.sessions.clear_s3_creds(&token);
+    state.sessions.clear_s3_creds(&token).await;
     StatusCode::OK.into_response()
 }
 
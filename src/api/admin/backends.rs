@@ -61,11 +61,22 @@ pub struct CreateBackendRequest {
     #[serde(rename = "type")]
     pub backend_type: String,
     pub path: Option<String>,
+    /// Filesystem metadata mode (`type: filesystem`); defaults to `xattr`.
+    pub metadata: Option<crate::config::FilesystemMetadataMode>,
     pub endpoint: Option<String>,
     pub region: Option<String>,
     pub force_path_style: Option<bool>,
     pub access_key_id: Option<String>,
     pub secret_access_key: Option<String>,
+    /// Azure storage account name (`type: azure`).
+    pub account: Option<String>,
+    /// Azure storage account key (`type: azure`).
+    pub account_key: Option<String>,
+    /// GCS project (`type: gcs`).
+    pub project: Option<String>,
+    /// GCS service-account JSON key (`type: gcs`). Optional: without it the
+    /// backend uses the metadata server (or no auth against an emulator).
+    pub service_account_key: Option<String>,
     /// Set this backend as the default.
     pub set_default: Option<bool>,
 }
@@ -84,6 +95,7 @@ fn build_backend_config(req: &CreateBackendRequest) -> Result<BackendConfig, Str
             let path = req.path.as_deref().unwrap_or("./data").to_string();
             Ok(BackendConfig::Filesystem {
                 path: std::path::PathBuf::from(path),
+                metadata: req.metadata.unwrap_or_default(),
             })
         }
         "s3" => {
@@ -106,8 +118,27 @@ fn build_backend_config(req: &CreateBackendRequest) -> Result<BackendConfig, Str
                 allow_local: false,
             })
         }
+        "azure" => {
+            let account = req.account.clone().filter(|s| !s.is_empty());
+            let account_key = req.account_key.clone().filter(|s| !s.is_empty());
+            let (Some(account), Some(account_key)) = (account, account_key) else {
+                return Err("Azure backend requires both account and account_key".into());
+            };
+            Ok(BackendConfig::AzureBlob {
+                account,
+                account_key: Some(account_key),
+                endpoint: req.endpoint.clone().filter(|s| !s.is_empty()),
+                allow_local: false,
+            })
+        }
+        "gcs" => Ok(BackendConfig::Gcs {
+            project: req.project.clone().filter(|s| !s.is_empty()),
+            service_account_key: req.service_account_key.clone().filter(|s| !s.is_empty()),
+            endpoint: req.endpoint.clone().filter(|s| !s.is_empty()),
+            allow_local: false,
+        }),
         other => Err(format!(
-            "Unknown backend type: '{other}'. Must be 'filesystem' or 's3'."
+            "Unknown backend type: '{other}'. Must be 'filesystem', 's3', 'azure' or 'gcs'."
         )),
     }
 }
@@ -146,6 +177,11 @@ pub async fn list_backends(State(state): State<Arc<AdminState>>) -> impl IntoRes
     for b in &mut backends {
         b.capability = verdicts.get(&b.name).cloned();
         b.health = health.get(&b.name).cloned();
+        if let Some(erasure) = &mut b.erasure {
+            for shard in &mut erasure.shards {
+                shard.health = health.get(&shard.backend).cloned();
+            }
+        }
     }
 
     Json(BackendListResponse {
@@ -369,8 +369,9 @@ struct BackupSecrets {
     /// secrets without replacing users / groups).
     #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
     oauth_client_secrets: std::collections::BTreeMap<String, String>,
-    /// Event-delivery secrets (`advanced.event_delivery.*`): the Slack bot token
-    /// and webhook header values. These are masked in the backup's `config.yaml`
+    /// Event-delivery secrets (`advanced.event_delivery.*`): the Slack bot token,
+    /// webhook header values, webhook signing secrets, credentialed sink URLs and
+    /// named-endpoint secrets. These are masked in the backup's `config.yaml`
     /// (redact_all_secrets), so without capturing them here a cross-instance /
     /// DR restore — where the running instance has no token to preserve from —
     /// would silently lose them.
@@ -384,6 +385,25 @@ struct SecretsEventDelivery {
     slack_bot_token: Option<String>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     webhook_headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    webhook_signing: BTreeMap<String, crate::config_sections::WebhookSigningSecret>,
+    /// Sink URLs that carry credentials, by sink name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    sink_urls: BTreeMap<String, String>,
+    /// Secrets of named endpoints, by endpoint name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    endpoints: BTreeMap<String, SecretsEventEndpoint>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct SecretsEventEndpoint {
+    /// Only for Slack-format endpoints, whose URL is the credential.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signing: Option<crate::config_sections::WebhookSigningSecret>,
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -400,6 +420,45 @@ struct SecretsStorage {
     access_key_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     secret_access_key: Option<String>,
+    /// Azure Blob account key (Azure backends only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    account_key: Option<String>,
+    /// GCS service-account JSON key (GCS backends only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    service_account_key: Option<String>,
+}
+
+impl SecretsStorage {
+    /// The credentials a backend carries, or `None` when it has none
+    /// (filesystem backends, S3 on instance/env credentials).
+    fn from_backend(backend: &BackendConfig) -> Option<Self> {
+        match backend {
+            BackendConfig::S3 {
+                access_key_id,
+                secret_access_key,
+                ..
+            } if access_key_id.is_some() || secret_access_key.is_some() => Some(Self {
+                access_key_id: access_key_id.clone(),
+                secret_access_key: secret_access_key.clone(),
+                ..Self::default()
+            }),
+            BackendConfig::AzureBlob {
+                account_key: Some(key),
+                ..
+            } => Some(Self {
+                account_key: Some(key.clone()),
+                ..Self::default()
+            }),
+            BackendConfig::Gcs {
+                service_account_key: Some(key),
+                ..
+            } => Some(Self {
+                service_account_key: Some(key.clone()),
+                ..Self::default()
+            }),
+            _ => None,
+        }
+    }
 }
 
 /// Build the IamBackup struct from current DB state. Used by both
@@ -530,37 +589,12 @@ async fn export_zip(
                 secret_access_key: cfg.secret_access_key.clone(),
             });
         }
-        // Storage-section backend credentials (S3 only — filesystem
-        // backends have no secrets to round-trip).
-        if let crate::config::BackendConfig::S3 {
-            access_key_id,
-            secret_access_key,
-            ..
-        } = &cfg.backend
-        {
-            if access_key_id.is_some() || secret_access_key.is_some() {
-                s.storage = Some(SecretsStorage {
-                    access_key_id: access_key_id.clone(),
-                    secret_access_key: secret_access_key.clone(),
-                });
-            }
-        }
+        // Storage-section backend credentials (S3 key pair / Azure account
+        // key — filesystem backends have no secrets to round-trip).
+        s.storage = SecretsStorage::from_backend(&cfg.backend);
         for named in &cfg.backends {
-            if let crate::config::BackendConfig::S3 {
-                access_key_id,
-                secret_access_key,
-                ..
-            } = &named.backend
-            {
-                if access_key_id.is_some() || secret_access_key.is_some() {
-                    s.storage_backends.insert(
-                        named.name.clone(),
-                        SecretsStorage {
-                            access_key_id: access_key_id.clone(),
-                            secret_access_key: secret_access_key.clone(),
-                        },
-                    );
-                }
+            if let Some(secrets) = SecretsStorage::from_backend(&named.backend) {
+                s.storage_backends.insert(named.name.clone(), secrets);
             }
         }
         // OAuth client secrets (indexed by provider name, not id, so
@@ -570,18 +604,48 @@ async fn export_zip(
                 s.oauth_client_secrets.insert(p.name.clone(), cs.clone());
             }
         }
-        // Event-delivery secrets (Slack bot token + webhook header values) —
-        // masked in config.yaml, so captured here for cross-instance restore.
+        // Event-delivery secrets (Slack bot token, webhook header values,
+        // signing secrets, credentialed sink URLs and named-endpoint secrets)
+        // — masked in config.yaml,
+        // so captured here for cross-instance restore.
         let ed = &cfg.event_delivery;
         let has_token = ed
             .slack_bot_token
             .as_deref()
             .map(|t| !t.trim().is_empty())
             .unwrap_or(false);
-        if has_token || !ed.webhook_headers.is_empty() {
+        let sink_urls: BTreeMap<String, String> = ed
+            .sinks
+            .iter()
+            .filter(|sink| crate::event_sinks::url_has_credentials(&sink.url))
+            .map(|sink| (sink.name.clone(), sink.url.clone()))
+            .collect();
+        let endpoints: BTreeMap<String, SecretsEventEndpoint> = ed
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                let secrets = SecretsEventEndpoint {
+                    url: (endpoint.format == crate::config_sections::EventDeliveryFormat::Slack)
+                        .then(|| endpoint.url.clone()),
+                    headers: endpoint.headers.clone(),
+                    signing: endpoint.signing.clone(),
+                };
+                (endpoint.name.clone(), secrets)
+            })
+            .filter(|(_, e)| e.url.is_some() || !e.headers.is_empty() || e.signing.is_some())
+            .collect();
+        if has_token
+            || !ed.webhook_headers.is_empty()
+            || !ed.webhook_signing.is_empty()
+            || !sink_urls.is_empty()
+            || !endpoints.is_empty()
+        {
             s.event_delivery = Some(SecretsEventDelivery {
                 slack_bot_token: ed.slack_bot_token.clone(),
                 webhook_headers: ed.webhook_headers.clone(),
+                webhook_signing: ed.webhook_signing.clone(),
+                sink_urls,
+                endpoints,
             });
         }
         s
@@ -863,19 +927,34 @@ fn unzip_bounded(
     Ok(files)
 }
 
-fn hydrate_s3_backend_credentials(backend: &mut BackendConfig, secrets: &SecretsStorage) {
-    if let BackendConfig::S3 {
-        access_key_id,
-        secret_access_key,
-        ..
-    } = backend
-    {
-        if let Some(ak) = &secrets.access_key_id {
-            *access_key_id = Some(ak.clone());
+fn hydrate_backend_credentials(backend: &mut BackendConfig, secrets: &SecretsStorage) {
+    match backend {
+        BackendConfig::S3 {
+            access_key_id,
+            secret_access_key,
+            ..
+        } => {
+            if let Some(ak) = &secrets.access_key_id {
+                *access_key_id = Some(ak.clone());
+            }
+            if let Some(sk) = &secrets.secret_access_key {
+                *secret_access_key = Some(sk.clone());
+            }
         }
-        if let Some(sk) = &secrets.secret_access_key {
-            *secret_access_key = Some(sk.clone());
+        BackendConfig::AzureBlob { account_key, .. } => {
+            if let Some(key) = &secrets.account_key {
+                *account_key = Some(key.clone());
+            }
         }
+        BackendConfig::Gcs {
+            service_account_key,
+            ..
+        } => {
+            if let Some(key) = &secrets.service_account_key {
+                *service_account_key = Some(key.clone());
+            }
+        }
+        BackendConfig::Filesystem { .. } | BackendConfig::Erasure { .. } => {}
     }
 }
 
@@ -890,12 +969,12 @@ fn hydrate_config_with_backup_secrets(cfg: &mut Config, secrets: &BackupSecrets)
     }
 
     if let Some(s) = &secrets.storage {
-        hydrate_s3_backend_credentials(&mut cfg.backend, s);
+        hydrate_backend_credentials(&mut cfg.backend, s);
     }
 
     for named in &mut cfg.backends {
         if let Some(s) = secrets.storage_backends.get(&named.name) {
-            hydrate_s3_backend_credentials(&mut named.backend, s);
+            hydrate_backend_credentials(&mut named.backend, s);
         }
     }
 
@@ -912,7 +991,7 @@ fn hydrate_config_with_backup_secrets(cfg: &mut Config, secrets: &BackupSecrets)
                 .filter_map(|(idx, b)| matches!(b.backend, BackendConfig::S3 { .. }).then_some(idx))
                 .collect();
             if let [idx] = s3_named.as_slice() {
-                hydrate_s3_backend_credentials(&mut cfg.backends[*idx].backend, s);
+                hydrate_backend_credentials(&mut cfg.backends[*idx].backend, s);
             }
         }
     }
@@ -930,6 +1009,30 @@ fn hydrate_config_with_backup_secrets(cfg: &mut Config, secrets: &BackupSecrets)
                 .webhook_headers
                 .insert(k.clone(), v.clone());
         }
+        for (k, v) in &ed.webhook_signing {
+            cfg.event_delivery
+                .webhook_signing
+                .insert(k.clone(), v.clone());
+        }
+        for sink in cfg.event_delivery.sinks.iter_mut() {
+            if let Some(url) = ed.sink_urls.get(&sink.name) {
+                sink.url = url.clone();
+            }
+        }
+        for endpoint in cfg.event_delivery.endpoints.iter_mut() {
+            let Some(secrets) = ed.endpoints.get(&endpoint.name) else {
+                continue;
+            };
+            if let Some(url) = &secrets.url {
+                endpoint.url = url.clone();
+            }
+            for (k, v) in &secrets.headers {
+                endpoint.headers.insert(k.clone(), v.clone());
+            }
+            if secrets.signing.is_some() {
+                endpoint.signing = secrets.signing.clone();
+            }
+        }
     }
 }
 
@@ -1384,11 +1487,11 @@ async fn apply_secrets(
             }
         }
         if let Some(s) = &secrets.storage {
-            hydrate_s3_backend_credentials(&mut cfg.backend, s);
+            hydrate_backend_credentials(&mut cfg.backend, s);
         }
         for named in &mut cfg.backends {
             if let Some(s) = secrets.storage_backends.get(&named.name) {
-                hydrate_s3_backend_credentials(&mut named.backend, s);
+                hydrate_backend_credentials(&mut named.backend, s);
             }
         }
         cfg.clone()
@@ -1925,6 +2028,8 @@ storage:
             storage: Some(SecretsStorage {
                 access_key_id: Some("AKIA_BACKUP".into()),
                 secret_access_key: Some("SECRET_BACKUP".into()),
+                account_key: None,
+                service_account_key: None,
             }),
             ..BackupSecrets::default()
         };
@@ -1970,12 +2075,16 @@ storage:
             storage: Some(SecretsStorage {
                 access_key_id: Some("LEGACY_SHOULD_NOT_GUESS".into()),
                 secret_access_key: Some("LEGACY_SHOULD_NOT_GUESS".into()),
+                account_key: None,
+                service_account_key: None,
             }),
             storage_backends: BTreeMap::from([(
                 "b".into(),
                 SecretsStorage {
                     access_key_id: Some("B_KEY".into()),
                     secret_access_key: Some("B_SECRET".into()),
+                    account_key: None,
+                    service_account_key: None,
                 },
             )]),
             ..Default::default()
@@ -2009,6 +2118,40 @@ storage:
         }
     }
 
+    #[test]
+    fn azure_account_key_round_trips_through_backup_secrets() {
+        let backend = BackendConfig::AzureBlob {
+            account: "acct".into(),
+            account_key: Some("QUJD".into()),
+            endpoint: None,
+            allow_local: false,
+        };
+        let secrets = SecretsStorage::from_backend(&backend).expect("azure key is a secret");
+        assert_eq!(secrets.account_key.as_deref(), Some("QUJD"));
+        assert!(secrets.access_key_id.is_none());
+
+        let yaml = r#"
+storage:
+  backends:
+    - name: blob
+      type: azure
+      account: acct
+  default_backend: blob
+"#;
+        let backup = BackupSecrets {
+            storage_backends: BTreeMap::from([("blob".into(), secrets)]),
+            ..Default::default()
+        };
+        let hydrated = config_yaml_hydrated_for_restore(yaml, Some(&backup)).unwrap();
+        let cfg = Config::from_yaml_str(&hydrated).unwrap();
+        match &cfg.backends[0].backend {
+            BackendConfig::AzureBlob { account_key, .. } => {
+                assert_eq!(account_key.as_deref(), Some("QUJD"));
+            }
+            other => panic!("expected Azure backend, got {other:?}"),
+        }
+    }
+
     #[test]
     fn backup_secret_conflict_names_bootstrap_hash_without_leaking_values() {
         let current = Config {
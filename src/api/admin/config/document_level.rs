@@ -412,6 +412,7 @@ fn preserve_runtime_secrets(
     // identical logic; see the doc-block at the helpers' definition.
     super::preserve_primary_backend_creds(incoming, current, &mut warnings);
     super::preserve_named_backends_creds(incoming, current, &mut warnings);
+    super::preserve_replication_endpoint_creds(incoming, current, &mut warnings);
 
     // Webhook header values are masked to REDACTED_SENTINEL on export
     // (`redact_all_secrets`). A full-document export → edit → apply
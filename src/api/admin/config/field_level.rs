@@ -196,6 +196,12 @@ pub struct BackendInfoResponse {
     pub endpoint: Option<String>,
     pub region: Option<String>,
     pub force_path_style: Option<bool>,
+    /// Azure storage account name (Azure backends only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// GCS project (GCS backends only, when configured).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
     pub has_credentials: bool,
     /// Per-backend encryption status. Step 6.
     pub encryption: BackendEncryptionSummary,
@@ -221,24 +227,48 @@ pub struct BackendInfoResponse {
     /// `GET /backends` only — drives the BackendsPanel health column.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub health: Option<crate::coordination::health::HealthEntry>,
+    /// Shard layout of an erasure-coded backend. Shard health is stamped
+    /// by `GET /backends` from each shard backend's own health entry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub erasure: Option<ErasureSummary>,
+}
+
+/// Shard layout of a `type: erasure` backend.
+#[derive(Serialize, Clone)]
+pub struct ErasureSummary {
+    pub data_shards: u8,
+    pub parity_shards: u8,
+    pub write_quorum: u8,
+    pub shards: Vec<ErasureShardInfo>,
+}
+
+/// One shard backend of an erasure-coded backend.
+#[derive(Serialize, Clone)]
+pub struct ErasureShardInfo {
+    pub backend: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<crate::coordination::health::HealthEntry>,
 }
 
 impl From<&crate::config::NamedBackendConfig> for BackendInfoResponse {
     fn from(named: &crate::config::NamedBackendConfig) -> Self {
         let encryption = BackendEncryptionSummary::from_config(&named.name, &named.encryption);
         match &named.backend {
-            crate::config::BackendConfig::Filesystem { path } => Self {
+            crate::config::BackendConfig::Filesystem { path, .. } => Self {
                 name: named.name.clone(),
                 backend_type: "filesystem".into(),
                 path: Some(path.display().to_string()),
                 endpoint: None,
                 region: None,
                 force_path_style: None,
+                account: None,
+                project: None,
                 has_credentials: false,
                 encryption,
                 is_synthesized: false,
                 capability: None,
                 health: None,
+                erasure: None,
             },
             crate::config::BackendConfig::S3 {
                 endpoint,
@@ -253,11 +283,89 @@ impl From<&crate::config::NamedBackendConfig> for BackendInfoResponse {
                 endpoint: endpoint.clone(),
                 region: Some(region.clone()),
                 force_path_style: Some(*force_path_style),
+                account: None,
+                project: None,
                 has_credentials: access_key_id.is_some(),
                 encryption,
                 is_synthesized: false,
                 capability: None,
                 health: None,
+                erasure: None,
+            },
+            crate::config::BackendConfig::AzureBlob {
+                account,
+                account_key,
+                endpoint,
+                ..
+            } => Self {
+                name: named.name.clone(),
+                backend_type: "azure".into(),
+                path: None,
+                endpoint: endpoint.clone(),
+                region: None,
+                force_path_style: None,
+                account: Some(account.clone()),
+                project: None,
+                has_credentials: account_key.is_some(),
+                encryption,
+                is_synthesized: false,
+                capability: None,
+                health: None,
+                erasure: None,
+            },
+            crate::config::BackendConfig::Gcs {
+                project,
+                service_account_key,
+                endpoint,
+                ..
+            } => Self {
+                name: named.name.clone(),
+                backend_type: "gcs".into(),
+                path: None,
+                endpoint: endpoint.clone(),
+                region: None,
+                force_path_style: None,
+                account: None,
+                project: project.clone(),
+                has_credentials: service_account_key.is_some(),
+                encryption,
+                is_synthesized: false,
+                capability: None,
+                health: None,
+                erasure: None,
+            },
+            crate::config::BackendConfig::Erasure {
+                data_shards,
+                parity_shards,
+                shards,
+                write_quorum,
+            } => Self {
+                name: named.name.clone(),
+                backend_type: "erasure".into(),
+                path: None,
+                endpoint: None,
+                region: None,
+                force_path_style: None,
+                account: None,
+                project: None,
+                has_credentials: false,
+                encryption,
+                is_synthesized: false,
+                capability: None,
+                health: None,
+                erasure: Some(ErasureSummary {
+                    data_shards: *data_shards,
+                    parity_shards: *parity_shards,
+                    write_quorum: write_quorum
+                        .unwrap_or_else(|| data_shards.saturating_add(*parity_shards)),
+                    shards: shards
+                        .iter()
+                        .map(|backend| ErasureShardInfo {
+                            backend: backend.clone(),
+                            health: None,
+                        })
+                        .collect(),
+                }),
             },
         }
     }
@@ -355,10 +463,16 @@ fn compute_tainted_fields(runtime: &crate::config::Config) -> Vec<String> {
     let runtime_type = match &runtime.backend {
         crate::config::BackendConfig::Filesystem { .. } => "filesystem",
         crate::config::BackendConfig::S3 { .. } => "s3",
+        crate::config::BackendConfig::AzureBlob { .. } => "azure",
+        crate::config::BackendConfig::Gcs { .. } => "gcs",
+        crate::config::BackendConfig::Erasure { .. } => "erasure",
     };
     let disk_type = match &disk.backend {
         crate::config::BackendConfig::Filesystem { .. } => "filesystem",
         crate::config::BackendConfig::S3 { .. } => "s3",
+        crate::config::BackendConfig::AzureBlob { .. } => "azure",
+        crate::config::BackendConfig::Gcs { .. } => "gcs",
+        crate::config::BackendConfig::Erasure { .. } => "erasure",
     };
     if runtime_type != disk_type {
         tainted.push("backend_type".to_string());
@@ -367,8 +481,8 @@ fn compute_tainted_fields(runtime: &crate::config::Config) -> Vec<String> {
     // Backend details (only compare within same type)
     match (&runtime.backend, &disk.backend) {
         (
-            crate::config::BackendConfig::Filesystem { path: rp },
-            crate::config::BackendConfig::Filesystem { path: dp },
+            crate::config::BackendConfig::Filesystem { path: rp, .. },
+            crate::config::BackendConfig::Filesystem { path: dp, .. },
         ) if rp != dp => {
             tainted.push("backend_path".to_string());
         }
@@ -453,7 +567,7 @@ pub async fn get_config(State(state): State<Arc<AdminState>>) -> impl IntoRespon
         backend_force_path_style,
         backend_has_credentials,
     ) = match &cfg.backend {
-        crate::config::BackendConfig::Filesystem { path } => (
+        crate::config::BackendConfig::Filesystem { path, .. } => (
             "filesystem",
             Some(path.display().to_string()),
             None,
@@ -475,6 +589,32 @@ pub async fn get_config(State(state): State<Arc<AdminState>>) -> impl IntoRespon
             Some(*force_path_style),
             access_key_id.is_some(),
         ),
+        crate::config::BackendConfig::AzureBlob {
+            account_key,
+            endpoint,
+            ..
+        } => (
+            "azure",
+            None,
+            endpoint.clone(),
+            None,
+            None,
+            account_key.is_some(),
+        ),
+        crate::config::BackendConfig::Gcs {
+            service_account_key,
+            endpoint,
+            ..
+        } => (
+            "gcs",
+            None,
+            endpoint.clone(),
+            None,
+            None,
+            service_account_key.is_some(),
+        ),
+        // Only valid as a named backend (check_fatal); listed for totality.
+        crate::config::BackendConfig::Erasure { .. } => ("erasure", None, None, None, None, false),
     };
 
     // Read the current log filter from the reload handle
@@ -769,6 +909,9 @@ fn apply_backend_patch(
     let current_type = match backend {
         crate::config::BackendConfig::Filesystem { .. } => "filesystem",
         crate::config::BackendConfig::S3 { .. } => "s3",
+        crate::config::BackendConfig::AzureBlob { .. } => "azure",
+        crate::config::BackendConfig::Gcs { .. } => "gcs",
+        crate::config::BackendConfig::Erasure { .. } => "erasure",
     };
     // Case-insensitive: accept "S3" / "FileSystem" / "s3" equivalently. The
     // canonical on-the-wire value (and the one `ConfigResponse` echoes back)
@@ -787,6 +930,7 @@ fn apply_backend_patch(
                     .unwrap_or_else(|| "./data".to_string());
                 *backend = crate::config::BackendConfig::Filesystem {
                     path: std::path::PathBuf::from(path),
+                    metadata: Default::default(),
                 };
                 warnings.push(
                     "Backend type changed. Data in the previous backend is not migrated."
@@ -815,9 +959,33 @@ fn apply_backend_patch(
                 );
                 return Ok(());
             }
+            "azure" => {
+                return Err(
+                    "Switching to an Azure backend needs account credentials this \
+                     patch cannot carry — configure it in the YAML document or via \
+                     POST /api/admin/backends."
+                        .to_string(),
+                );
+            }
+            // Without a key a GCS backend authenticates through the
+            // metadata server (workload identity), so the switch can be
+            // made here; a service-account key goes in via the YAML.
+            "gcs" => {
+                *backend = crate::config::BackendConfig::Gcs {
+                    project: None,
+                    service_account_key: None,
+                    endpoint: body.backend_endpoint.clone().filter(|e| !e.is_empty()),
+                    allow_local: false,
+                };
+                warnings.push(
+                    "Backend type changed. Data in the previous backend is not migrated."
+                        .to_string(),
+                );
+                return Ok(());
+            }
             other => {
                 return Err(format!(
-                    "Unknown backend type: '{}'. Must be 'filesystem' or 's3'.",
+                    "Unknown backend type: '{}'. Must be 'filesystem', 's3' or 'gcs'.",
                     other
                 ));
             }
@@ -826,7 +994,7 @@ fn apply_backend_patch(
 
     // Same type — update fields in-place.
     match backend {
-        crate::config::BackendConfig::Filesystem { path } => {
+        crate::config::BackendConfig::Filesystem { path, .. } => {
             if let Some(ref p) = body.backend_path {
                 *path = std::path::PathBuf::from(p);
             }
@@ -863,6 +1031,21 @@ fn apply_backend_patch(
                 }
             }
         }
+        // The flattened patch has no account / key fields; Azure and GCS
+        // backends are edited through the YAML document or the
+        // named-backends API. Only the endpoint maps onto this shape.
+        crate::config::BackendConfig::AzureBlob { endpoint, .. }
+        | crate::config::BackendConfig::Gcs { endpoint, .. } => {
+            if let Some(ref ep) = body.backend_endpoint {
+                *endpoint = if ep.is_empty() {
+                    None
+                } else {
+                    Some(ep.clone())
+                };
+            }
+        }
+        // Shard layout is edited through the YAML document only.
+        crate::config::BackendConfig::Erasure { .. } => {}
     }
     Ok(())
 }
@@ -377,6 +377,7 @@ async fn apply_section(
     );
     super::preserve_primary_backend_creds(&mut new_cfg, &old_cfg, &mut cred_warnings);
     super::preserve_named_backends_creds(&mut new_cfg, &old_cfg, &mut cred_warnings);
+    super::preserve_replication_endpoint_creds(&mut new_cfg, &old_cfg, &mut cred_warnings);
 
     // Step 6: per-backend encryption key preservation. Three-state
     // semantics PER FIELD PER ENTRY:
@@ -911,22 +912,24 @@ fn fingerprint_secrets_for_diff(cfg: &crate::config::Config) -> crate::config::C
     let mut out = cfg.clone();
     out.bootstrap_password_hash = None; // never diffed (dedicated endpoint)
     fp_opt(&mut out.secret_access_key);
-    if let crate::config::BackendConfig::S3 {
-        ref mut secret_access_key,
-        ..
-    } = out.backend
-    {
-        fp_opt(secret_access_key);
+    fn fp_backend(b: &mut crate::config::BackendConfig) {
+        match b {
+            crate::config::BackendConfig::S3 {
+                secret_access_key, ..
+            } => fp_opt(secret_access_key),
+            crate::config::BackendConfig::AzureBlob { account_key, .. } => fp_opt(account_key),
+            crate::config::BackendConfig::Gcs {
+                service_account_key,
+                ..
+            } => fp_opt(service_account_key),
+            crate::config::BackendConfig::Filesystem { .. }
+            | crate::config::BackendConfig::Erasure { .. } => {}
+        }
     }
+    fp_backend(&mut out.backend);
     fp_enc(&mut out.backend_encryption);
     for nb in &mut out.backends {
-        if let crate::config::BackendConfig::S3 {
-            ref mut secret_access_key,
-            ..
-        } = nb.backend
-        {
-            fp_opt(secret_access_key);
-        }
+        fp_backend(&mut nb.backend);
         fp_enc(&mut nb.encryption);
     }
     for u in &mut out.iam_users {
@@ -940,6 +943,13 @@ fn fingerprint_secrets_for_diff(cfg: &crate::config::Config) -> crate::config::C
     for v in out.event_delivery.webhook_headers.values_mut() {
         *v = fp_str(v);
     }
+    for signing in out.event_delivery.webhook_signing.values_mut() {
+        signing.secret = fp_str(&signing.secret);
+        fp_opt(&mut signing.previous_secret);
+    }
+    for sink in out.event_delivery.sinks.iter_mut() {
+        sink.url = fp_str(&sink.url);
+    }
     fp_opt(&mut out.event_delivery.slack_bot_token);
     if out.event_delivery.format == crate::config_sections::EventDeliveryFormat::Slack
         && !out.event_delivery.uses_slack_bot_token()
@@ -949,6 +959,18 @@ fn fingerprint_secrets_for_diff(cfg: &crate::config::Config) -> crate::config::C
             *url = fp_str(url);
         }
     }
+    for endpoint in out.event_delivery.endpoints.iter_mut() {
+        for v in endpoint.headers.values_mut() {
+            *v = fp_str(v);
+        }
+        if let Some(signing) = endpoint.signing.as_mut() {
+            signing.secret = fp_str(&signing.secret);
+            fp_opt(&mut signing.previous_secret);
+        }
+        if endpoint.format == crate::config_sections::EventDeliveryFormat::Slack {
+            endpoint.url = fp_str(&endpoint.url);
+        }
+    }
     out
 }
 
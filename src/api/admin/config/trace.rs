@@ -41,6 +41,11 @@ pub struct TraceRequest {
     /// string parseable as an `IpAddr` (`"203.0.113.5"`, `"2001:db8::1"`).
     #[serde(default)]
     pub source_ip: Option<std::net::IpAddr>,
+    /// Access key the synthetic request is signed with. Selects the
+    /// `throttle` blocks that apply (their groups come from IAM); the
+    /// admission decision itself never depends on it.
+    #[serde(default)]
+    pub access_key: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -52,6 +57,9 @@ pub struct TraceResponse {
     /// Admission-layer decision. Phase 2.5+ will add sibling fields for
     /// identity, iam, parameters, and routing.
     pub admission: crate::admission::Decision,
+    /// `throttle` blocks the request would be charged to after SigV4, in
+    /// chain order. Empty when none match.
+    pub throttles: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -90,9 +98,24 @@ pub async fn trace_config(
     );
     // `RequestInfo` borrows from `owned`; confining it to a block
     // ends the borrow before we move out of `owned` below.
-    let decision = {
+    let access_key = body.access_key.as_deref().filter(|k| !k.is_empty());
+    let groups = match (access_key, &**state.iam_state.load()) {
+        (Some(key), crate::iam::IamState::Iam(index)) => index.group_names(key),
+        _ => Vec::new(),
+    };
+    let (decision, throttles) = {
         let req_info = owned.as_ref();
-        crate::admission::evaluate(&chain, &req_info)
+        let principal = crate::admission::Principal {
+            access_key_id: access_key,
+            groups: &groups,
+        };
+        (
+            crate::admission::evaluate(&chain, &req_info),
+            crate::admission::matching_throttles(&chain, &req_info, &principal)
+                .into_iter()
+                .map(|b| b.name.clone())
+                .collect(),
+        )
     };
 
     // `TraceResolved` echoes the parsed inputs back so operators can
@@ -116,6 +139,7 @@ pub async fn trace_config(
                 authenticated: body.authenticated,
             },
             admission: decision,
+            throttles,
         }),
     )
 }
@@ -164,6 +188,8 @@ pub struct TraceQuery {
     authenticated: bool,
     #[serde(default)]
     source_ip: Option<std::net::IpAddr>,
+    #[serde(default)]
+    access_key: Option<String>,
 }
 
 fn default_method() -> String {
@@ -178,8 +204,8 @@ fn default_path() -> String {
 /// trace URLs. Reuses the POST handler's evaluator path exactly —
 /// there is no "GET semantics vs POST semantics" split.
 ///
-/// Accepts the same five fields as POST, URL-encoded:
-/// `?method=&path=&query=&authenticated=&source_ip=`. Every field has
+/// Accepts the same six fields as POST, URL-encoded:
+/// `?method=&path=&query=&authenticated=&source_ip=&access_key=`. Every field has
 /// a sensible default so partial URLs still produce a response (the
 /// UI's Trace page can deep-link to just `?source_ip=203.0.113.5` to
 /// test one IP against the default GET `/`).
@@ -193,6 +219,7 @@ pub async fn trace_config_get(
         query: query.query,
         authenticated: query.authenticated,
         source_ip: query.source_ip,
+        access_key: query.access_key,
     };
     trace_config(State(state), Json(body)).await.into_response()
 }
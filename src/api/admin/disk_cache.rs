@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Admin endpoints for the read-through disk cache (`advanced.disk_cache`).
+//!
+//! - `GET  /_/api/admin/disk-cache` — occupancy + limits.
+//! - `POST /_/api/admin/disk-cache/prefetch` — warm the cache ahead of
+//!   demand (e.g. right after a release lands on a remote backend), by
+//!   reading the selected objects through the engine and discarding the
+//!   bytes. Reads go through the normal retrieve path, so deltas warm both
+//!   the delta file and its reference.
+//!
+//! Both sit behind `require_admin_gui_session`, like the bulk object ops.
+
+use axum::extract::{Extension, State};
+use axum::http::StatusCode;
+use axum::Json;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::info;
+
+use super::auth::AdminGuiGate;
+use super::objects::MAX_BULK_OBJECTS;
+use crate::deltaglider::{DynEngine, RetrieveResponse};
+use crate::storage::DiskCacheStats;
+
+/// Objects read concurrently by one prefetch request.
+const PREFETCH_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Serialize)]
+pub struct DiskCacheStatusResponse {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_object_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<DiskCacheStats>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PrefetchRequest {
+    pub bucket: String,
+    /// Prefetch every object under this prefix (empty = whole bucket).
+    /// Ignored when `keys` is non-empty.
+    #[serde(default)]
+    pub prefix: String,
+    /// Explicit keys to prefetch.
+    #[serde(default)]
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrefetchResponse {
+    pub requested: usize,
+    pub prefetched: usize,
+    pub failed: Vec<PrefetchFailure>,
+    /// Logical bytes read through the engine.
+    pub bytes: u64,
+    /// The prefix expanded past the per-request cap; re-run with a
+    /// narrower prefix for the rest.
+    pub truncated: bool,
+    pub stats: DiskCacheStats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrefetchFailure {
+    pub key: String,
+    pub error: String,
+}
+
+fn disabled() -> (StatusCode, String) {
+    (
+        StatusCode::CONFLICT,
+        "disk cache is disabled (advanced.disk_cache.enabled)".into(),
+    )
+}
+
+pub async fn get_disk_cache(
+    Extension(_gate): Extension<AdminGuiGate>,
+    State(state): State<Arc<crate::api::admin::AdminState>>,
+) -> Json<DiskCacheStatusResponse> {
+    let engine = state.s3_state.engine.load();
+    Json(match engine.disk_cache() {
+        Some(cache) => {
+            let limits = cache.limits();
+            DiskCacheStatusResponse {
+                enabled: true,
+                path: Some(cache.root().display().to_string()),
+                max_object_bytes: Some(limits.max_object_bytes),
+                ttl_secs: limits.ttl.map(|t| t.as_secs()),
+                stats: Some(cache.stats()),
+            }
+        }
+        None => DiskCacheStatusResponse {
+            enabled: false,
+            path: None,
+            max_object_bytes: None,
+            ttl_secs: None,
+            stats: None,
+        },
+    })
+}
+
+pub async fn prefetch(
+    Extension(_gate): Extension<AdminGuiGate>,
+    State(state): State<Arc<crate::api::admin::AdminState>>,
+    Json(req): Json<PrefetchRequest>,
+) -> Result<Json<PrefetchResponse>, (StatusCode, String)> {
+    let engine = state.s3_state.engine.load_full();
+    let Some(cache) = engine.disk_cache().cloned() else {
+        return Err(disabled());
+    };
+    if req.keys.len() > MAX_BULK_OBJECTS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "too many keys ({} > limit {})",
+                req.keys.len(),
+                MAX_BULK_OBJECTS
+            ),
+        ));
+    }
+
+    let (keys, truncated) = if req.keys.is_empty() {
+        expand_prefix(&engine, &req.bucket, &req.prefix).await?
+    } else {
+        (req.keys.clone(), false)
+    };
+
+    let results: Vec<(String, Result<u64, String>)> = futures::stream::iter(keys.iter().cloned())
+        .map(|key| {
+            let engine = Arc::clone(&engine);
+            let bucket = req.bucket.clone();
+            async move {
+                let result = read_through(&engine, &bucket, &key).await;
+                (key, result)
+            }
+        })
+        .buffer_unordered(PREFETCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut prefetched = 0;
+    let mut bytes = 0;
+    let mut failed = Vec::new();
+    for (key, result) in results {
+        match result {
+            Ok(n) => {
+                prefetched += 1;
+                bytes += n;
+            }
+            Err(error) => failed.push(PrefetchFailure { key, error }),
+        }
+    }
+    info!(
+        "disk cache prefetch: bucket={} prefix={:?} requested={} prefetched={} failed={}",
+        req.bucket,
+        req.prefix,
+        keys.len(),
+        prefetched,
+        failed.len()
+    );
+    Ok(Json(PrefetchResponse {
+        requested: keys.len(),
+        prefetched,
+        failed,
+        bytes,
+        truncated,
+        stats: cache.stats(),
+    }))
+}
+
+/// All keys under `prefix`, capped at `MAX_BULK_OBJECTS`.
+async fn expand_prefix(
+    engine: &DynEngine,
+    bucket: &str,
+    prefix: &str,
+) -> Result<(Vec<String>, bool), (StatusCode, String)> {
+    let mut keys = Vec::new();
+    let mut continuation: Option<String> = None;
+    loop {
+        let page = engine
+            .list_objects(bucket, prefix, None, 1000, continuation.as_deref(), false)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("{}", e)))?;
+        for (k, _) in page.objects {
+            if k.ends_with('/') {
+                continue; // directory marker
+            }
+            if keys.len() >= MAX_BULK_OBJECTS {
+                return Ok((keys, true));
+            }
+            keys.push(k);
+        }
+        if !page.is_truncated || page.next_continuation_token.is_none() {
+            return Ok((keys, false));
+        }
+        continuation = page.next_continuation_token;
+    }
+}
+
+/// Read one object to the end, discarding the bytes. Returns bytes read.
+async fn read_through(engine: &DynEngine, bucket: &str, key: &str) -> Result<u64, String> {
+    match engine
+        .retrieve_stream(bucket, key)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        RetrieveResponse::Buffered { data, .. } => Ok(data.len() as u64),
+        RetrieveResponse::Streamed { mut stream, .. } => {
+            let mut n = 0u64;
+            while let Some(chunk) = stream.next().await {
+                n += chunk.map_err(|e| e.to_string())?.len() as u64;
+            }
+            Ok(n)
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Admin endpoints for erasure-coded backends (`type: erasure`).
+//!
+//! - `GET  /_/api/admin/erasure` — erasure backends and their shard layout.
+//! - `POST /_/api/admin/erasure/:backend/repair` — rewrite missing and stale
+//!   shards, for every bucket or just `?bucket=`. Blobs whose shards all
+//!   agree are not read; the rest are rebuilt from any `k` shards.
+
+use axum::extract::{Extension, Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::info;
+
+use super::auth::AdminGuiGate;
+use crate::storage::ErasureRepairReport;
+
+#[derive(Debug, Serialize)]
+pub struct ErasureInfo {
+    pub backend: String,
+    pub shards: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ErasureRepairQuery {
+    pub bucket: Option<String>,
+}
+
+pub async fn list_erasure(
+    Extension(_gate): Extension<AdminGuiGate>,
+    State(state): State<Arc<crate::api::admin::AdminState>>,
+) -> Json<Vec<ErasureInfo>> {
+    let engine = state.s3_state.engine.load();
+    let mut out: Vec<ErasureInfo> = engine
+        .erasure_backends()
+        .values()
+        .map(|ec| ErasureInfo {
+            backend: ec.name().to_string(),
+            shards: ec.shard_backends().into_iter().map(String::from).collect(),
+        })
+        .collect();
+    out.sort_by(|a, b| a.backend.cmp(&b.backend));
+    Json(out)
+}
+
+pub async fn repair_erasure(
+    Extension(_gate): Extension<AdminGuiGate>,
+    State(state): State<Arc<crate::api::admin::AdminState>>,
+    Path(backend): Path<String>,
+    Query(query): Query<ErasureRepairQuery>,
+) -> Result<Json<ErasureRepairReport>, (StatusCode, String)> {
+    let engine = state.s3_state.engine.load_full();
+    let Some(ec) = engine.erasure_backends().get(&backend).cloned() else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("no erasure backend named '{backend}'"),
+        ));
+    };
+    let result = match &query.bucket {
+        Some(bucket) => {
+            let mut report = ErasureRepairReport::default();
+            ec.repair_bucket(bucket, &mut report).await.map(|()| report)
+        }
+        None => ec.repair().await,
+    };
+    let report = result.map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+    info!(
+        "erasure repair: backend={} buckets={} scanned={} repaired={} unrecoverable={} \
+         orphans_removed={} failed={}",
+        backend,
+        report.buckets,
+        report.blobs_scanned,
+        report.shards_repaired,
+        report.unrecoverable,
+        report.orphans_removed,
+        report.failed
+    );
+    crate::audit::audit_log(
+        "erasure_repair",
+        "admin",
+        &backend,
+        &axum::http::HeaderMap::new(),
+        query.bucket.as_deref().unwrap_or_default(),
+        "",
+    );
+    Ok(Json(report))
+}
@@ -4,6 +4,7 @@
 
 use super::AdminState;
 use crate::event_delivery::known_status;
+use crate::event_endpoints::{endpoint_statuses, EventEndpointStatus};
 use crate::event_outbox::{
     current_unix_seconds, EventOutboxListQuery as DbEventOutboxListQuery, EventOutboxRecord,
     EventOutboxSort, EventOutboxSortOrder, EventOutboxStatusCounts,
@@ -35,6 +36,10 @@ pub struct EventOutboxResponse {
     pub order: String,
     pub delivery_enabled: bool,
     pub delivery_active: bool,
+    /// Progress of each named endpoint (`event_delivery.endpoints`).
+    pub endpoints: Vec<EventEndpointStatus>,
+    /// Newest outbox id, so an endpoint's lag is `latest_event_id - cursor`.
+    pub latest_event_id: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -112,6 +117,11 @@ pub async fn list(
             order,
         })
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let latest_event_id = db
+        .event_outbox_recent(1)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .first()
+        .map(|row| row.id);
 
     Ok(Json(EventOutboxResponse {
         rows: page.rows,
@@ -123,7 +133,9 @@ pub async fn list(
         sort: sort_raw.to_string(),
         order: order_raw,
         delivery_enabled: delivery.enabled,
-        delivery_active: delivery.is_active(),
+        delivery_active: delivery.is_active() || delivery.endpoints_active(),
+        endpoints: endpoint_statuses(&db, &delivery),
+        latest_event_id,
     }))
 }
 
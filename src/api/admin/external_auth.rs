@@ -575,7 +575,8 @@ pub async fn oauth_callback(
             user_id: user.id,
         },
         crate::session::SessionKind::AdminGui,
-    );
+    )
+    .await;
 
     // Auto-populate S3 credentials
     super::auth::auto_populate_s3_creds(
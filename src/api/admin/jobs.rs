@@ -12,6 +12,8 @@
 //! Routes (admin tier unless noted):
 //! - `GET  /_/api/admin/jobs` — every job, one row shape.
 //! - `GET  /_/api/admin/jobs/:id/runs` · `GET …/failures`
+//! - `GET  /_/api/admin/jobs/:id/conflicts` · `POST …/conflicts/resolve`
+//!   (bidirectional replication rules)
 //! - `POST /_/api/admin/jobs/:id/{pause,resume,run-now,preview,cancel}`
 //! - `POST /_/api/admin/jobs/reencrypt` — create re-encrypt jobs.
 //! - `GET  /_/api/admin/jobs/bucket/:bucket` — SESSION-LIGHT (browser
@@ -267,6 +269,25 @@ pub struct JobFailureEntry {
     pub error: String,
 }
 
+/// One key held for manual resolution on a bidirectional replication rule.
+/// Stamps are the versions' HLC stamps; a `None` side is deleted.
+#[derive(Debug, Serialize)]
+pub struct JobConflictEntry {
+    pub key: String,
+    pub kind: String,
+    pub source_key: String,
+    pub destination_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_stamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_stamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_size: Option<i64>,
+    pub detected_at: i64,
+}
+
 fn maintenance_job_view(j: &MaintenanceJob) -> JobView {
     let percent = crate::maintenance::display_percent(j);
     let (target, detail) = match (j.kind.as_str(), j.params.as_deref()) {
@@ -398,6 +419,11 @@ pub async fn list_jobs(
                 detail: serde_json::json!({
                     "interval": rule.interval,
                     "destination_prefix": rule.destination.prefix,
+                    "direction": rule.direction,
+                    // Keys held for manual resolution (bidirectional only).
+                    "conflicts": (rule.direction
+                        == crate::config_sections::ReplicationDirection::Bidirectional)
+                        .then(|| db.replication_conflict_count(&rule.name).unwrap_or(0)),
                     // Live "currently copying" objects (largest first, top 3)
                     // so a slow-moving counter is explained in the UI — a
                     // 4 GB tarball at 10 MB/s is work, not a hang.
@@ -416,6 +442,12 @@ pub async fn list_jobs(
                             "dirs_pending": w.dirs_pending,
                             "scanning": w.scanning,
                         })),
+                    // Bandwidth cap / transfer-window state; absent when the
+                    // rule (and the global config) sets neither.
+                    "throttle": crate::replication::throttle::view(&repl_cfg, rule),
+                    // Endpoint URLs of external sides; absent when both
+                    // sides are proxy-routed buckets.
+                    "external": crate::replication::external::view(rule),
                 }),
             });
         }
@@ -732,6 +764,149 @@ pub async fn job_failures(
     Ok(Json(serde_json::json!({ "failures": failures })))
 }
 
+/// The bidirectional replication rule behind a `replication:<rule>` job id.
+async fn bidirectional_rule(
+    state: &Arc<AdminState>,
+    id: &str,
+) -> Result<crate::config_sections::ReplicationRule, (StatusCode, String)> {
+    let (sub, key) = parse_job_id(id).ok_or(not_found())?;
+    if sub != JobSubsystem::Replication {
+        return Err(not_found());
+    }
+    let (_, rule) = super::replication::snapshot_and_find_rule(state, key).await?;
+    if rule.direction != crate::config_sections::ReplicationDirection::Bidirectional {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "conflicts only exist on bidirectional rules".to_string(),
+        ));
+    }
+    Ok(rule)
+}
+
+/// GET /_/api/admin/jobs/:id/conflicts — keys a bidirectional rule is
+/// holding for manual resolution, oldest first.
+pub async fn job_conflicts(
+    Path(id): Path<String>,
+    Query(q): Query<LimitQuery>,
+    State(state): State<Arc<AdminState>>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let rule = bidirectional_rule(&state, &id).await?;
+    let limit = q.limit.unwrap_or(50).clamp(1, 500);
+    let db = state
+        .config_db
+        .as_ref()
+        .ok_or(db_unavailable())?
+        .lock()
+        .await;
+    let total = db
+        .replication_conflict_count(&rule.name)
+        .map_err(internal)?;
+    let src_prefix = crate::replication::normalize_prefix(&rule.source.prefix);
+    let dest_prefix = crate::replication::normalize_prefix(&rule.destination.prefix);
+    let conflicts: Vec<JobConflictEntry> = db
+        .replication_conflicts(&rule.name, limit)
+        .map_err(internal)?
+        .into_iter()
+        .map(|c| JobConflictEntry {
+            source_key: format!("{src_prefix}{}", c.rel_key),
+            destination_key: format!("{dest_prefix}{}", c.rel_key),
+            key: c.rel_key,
+            kind: c.kind,
+            source_stamp: c.src_stamp,
+            destination_stamp: c.dest_stamp,
+            source_size: c.src_size,
+            destination_size: c.dest_size,
+            detected_at: c.detected_at,
+        })
+        .collect();
+    Ok(Json(
+        serde_json::json!({ "conflicts": conflicts, "total": total }),
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveConflictRequest {
+    /// The conflict's key, relative to the rule's prefixes.
+    pub key: String,
+    pub keep: crate::replication::bidi::Side,
+}
+
+/// POST /_/api/admin/jobs/:id/conflicts/resolve — keep one side's version
+/// (or its deletion) on both sides. Runs under the rule's lease, so it waits
+/// for nothing: a running sweep → 409.
+pub async fn job_resolve_conflict(
+    Path(id): Path<String>,
+    State(state): State<Arc<AdminState>>,
+    headers: HeaderMap,
+    Json(req): Json<ResolveConflictRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let rule = bidirectional_rule(&state, &id).await?;
+    let ttl = crate::replication::scheduler::lease_ttl_secs(&state.config.read().await.replication);
+    let db = state.config_db.as_ref().ok_or_else(db_unavailable)?.clone();
+    let owner = format!("resolve:{}", uuid::Uuid::new_v4());
+    let busy = || {
+        (
+            StatusCode::CONFLICT,
+            "rule is running; resolve the conflict when the run finishes".to_string(),
+        )
+    };
+    // Same cross-backend gate as run-now: a scheduled run may hold only the
+    // coordination lease.
+    if let Some(lease) = state.coordination_lease.as_ref() {
+        let now = crate::replication::current_unix_seconds();
+        if lease
+            .is_held(
+                crate::coordination::LeaseSubsystem::Replication,
+                &rule.name,
+                now,
+            )
+            .await
+            .unwrap_or(false)
+        {
+            return Err(busy());
+        }
+    }
+    {
+        let db = db.lock().await;
+        if db
+            .replication_conflict_get(&rule.name, &req.key)
+            .map_err(internal)?
+            .is_none()
+        {
+            return Err((StatusCode::NOT_FOUND, "no such conflict".to_string()));
+        }
+        let now = crate::replication::current_unix_seconds();
+        let _ = db.replication_ensure_state(&rule.name, now);
+        if !db
+            .replication_try_acquire_lease(&rule.name, &owner, now, ttl)
+            .map_err(internal)?
+        {
+            return Err(busy());
+        }
+    }
+    let engine = state.s3_state.engine.load().clone();
+    let outcome =
+        crate::replication::bidi::resolve_conflict(&engine, &db, &rule, &req.key, req.keep).await;
+    {
+        let db = db.lock().await;
+        let _ = db.replication_release_lease(&rule.name, &owner);
+    }
+    let resolution = outcome.map_err(|e| (StatusCode::BAD_GATEWAY, e))?;
+    crate::audit::audit_log(
+        "replication_conflict_resolve",
+        "admin",
+        &format!("{}:keep-{}", rule.name, req.keep.as_str()),
+        &headers,
+        &rule.source.bucket,
+        &req.key,
+    );
+    Ok(Json(serde_json::json!({
+        "key": req.key,
+        "kept": req.keep,
+        "resolution": resolution,
+    })))
+}
+
 /// POST /_/api/admin/jobs/:id/:action — uniform action dispatch.
 pub async fn job_action(
     Path((id, action)): Path<(String, String)>,
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Admin endpoints for write-through mirrored buckets (`buckets.<name>.mirror`).
+//!
+//! - `GET /_/api/admin/mirrors` — mirrored buckets and their two copies.
+//! - `GET /_/api/admin/mirrors/:bucket/parity` — full listing of both copies
+//!   diffed with the replication parity kernel. Metadata only: no object
+//!   bytes are read.
+
+use axum::extract::{Extension, Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
+use std::sync::Arc;
+
+use super::auth::AdminGuiGate;
+use crate::replication::parity::{diff_mirror, ParityFinding};
+
+#[derive(Debug, Serialize)]
+pub struct MirrorInfo {
+    pub bucket: String,
+    pub primary: String,
+    pub primary_bucket: String,
+    pub mirror: String,
+    pub mirror_bucket: String,
+    pub write_quorum: u8,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MirrorParityResponse {
+    pub bucket: String,
+    pub primary_objects: u64,
+    pub mirror_objects: u64,
+    pub matched: u64,
+    /// On the primary, absent from the mirror.
+    pub missing_on_mirror: u64,
+    /// On the mirror, absent from the primary.
+    pub missing_on_primary: u64,
+    pub checksum_mismatch: u64,
+    pub unverifiable: u64,
+    pub in_sync: bool,
+    pub missing_on_mirror_samples: Vec<ParityFinding>,
+    pub missing_on_primary_samples: Vec<ParityFinding>,
+    pub mismatch_samples: Vec<ParityFinding>,
+}
+
+pub async fn list_mirrors(
+    Extension(_gate): Extension<AdminGuiGate>,
+    State(state): State<Arc<crate::api::admin::AdminState>>,
+) -> Json<Vec<MirrorInfo>> {
+    let engine = state.s3_state.engine.load();
+    let Some(mirrors) = engine.mirror_set() else {
+        return Json(Vec::new());
+    };
+    Json(
+        mirrors
+            .buckets()
+            .into_iter()
+            .filter_map(|bucket| {
+                let route = mirrors.route(bucket)?;
+                Some(MirrorInfo {
+                    bucket: bucket.to_string(),
+                    primary: route.primary.clone(),
+                    primary_bucket: route.primary_bucket.clone(),
+                    mirror: route.mirror.clone(),
+                    mirror_bucket: route.mirror_bucket.clone(),
+                    write_quorum: route.write_quorum,
+                })
+            })
+            .collect(),
+    )
+}
+
+pub async fn mirror_parity(
+    Extension(_gate): Extension<AdminGuiGate>,
+    State(state): State<Arc<crate::api::admin::AdminState>>,
+    Path(bucket): Path<String>,
+) -> Result<Json<MirrorParityResponse>, (StatusCode, String)> {
+    let engine = state.s3_state.engine.load_full();
+    let Some(mirrors) = engine.mirror_set().filter(|m| m.route(&bucket).is_some()) else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("bucket '{bucket}' is not mirrored"),
+        ));
+    };
+    let (primary, mirror) = mirrors
+        .list_copies(&bucket)
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+    let diff = diff_mirror(&primary, &mirror);
+    Ok(Json(MirrorParityResponse {
+        bucket,
+        primary_objects: primary.len() as u64,
+        mirror_objects: mirror.len() as u64,
+        matched: diff.matched,
+        missing_on_mirror: diff.missing_on_dest,
+        missing_on_primary: diff.orphan_on_dest,
+        checksum_mismatch: diff.checksum_mismatch,
+        unverifiable: diff.unverifiable,
+        in_sync: diff.missing_on_dest == 0
+            && diff.orphan_on_dest == 0
+            && diff.checksum_mismatch == 0,
+        missing_on_mirror_samples: diff.missing_samples,
+        missing_on_primary_samples: diff.orphan_samples,
+        mismatch_samples: diff.mismatch_samples,
+    }))
+}
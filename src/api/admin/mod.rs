@@ -9,6 +9,8 @@ mod backup;
 mod bucket_scan;
 mod config;
 mod delta_efficiency;
+mod disk_cache;
+mod erasure;
 mod event_outbox;
 pub mod external_auth;
 mod groups;
@@ -16,11 +18,15 @@ pub(crate) mod jobs;
 mod lifecycle;
 mod logs;
 pub(crate) mod maintenance;
+mod mirrors;
 pub(crate) mod objects;
 pub(crate) mod replication;
 mod savings;
 mod scanner;
+mod service_accounts;
 mod sessions;
+mod simulate;
+mod spans;
 pub(crate) mod users;
 
 use parking_lot::RwLock;
@@ -38,7 +44,7 @@ use crate::session::SessionStore;
 use crate::usage_scanner::UsageScanner;
 
 // Re-export everything so external code doesn't need import changes.
-pub use audit::get_audit;
+pub use audit::{export_audit, get_audit, verify_audit};
 pub use auth::{
     browser_session_connect, check_session, clear_s3_session_creds, get_s3_session_creds, login,
     login_as, logout, open_browser_connect, require_admin_gui_session, require_not_declarative,
@@ -68,6 +74,11 @@ pub use delta_efficiency::{
     classify_deltaspace, get_delta_efficiency, post_delta_efficiency_scan, verify_delta_efficiency,
     DeltaEfficiencyScanner, Efficiency,
 };
+pub use disk_cache::{
+    get_disk_cache, prefetch as prefetch_disk_cache, DiskCacheStatusResponse, PrefetchRequest,
+    PrefetchResponse,
+};
+pub use erasure::{list_erasure, repair_erasure, ErasureInfo, ErasureRepairQuery};
 pub use event_outbox::{
     list as event_outbox_list, purge_failed as event_outbox_purge_failed,
     requeue_many as event_outbox_requeue_many, requeue_one as event_outbox_requeue_one,
@@ -78,8 +89,9 @@ pub use groups::{
     update_group, AddGroupMemberRequest, CloneGroupRequest, CreateGroupRequest, UpdateGroupRequest,
 };
 pub use jobs::{
-    job_action as jobs_action, job_failures as jobs_failures, job_parity_version,
-    job_replication_event_version, job_replication_run_version, job_runs as jobs_runs,
+    job_action as jobs_action, job_conflicts as jobs_conflicts, job_failures as jobs_failures,
+    job_parity_version, job_replication_event_version, job_replication_run_version,
+    job_resolve_conflict as jobs_resolve_conflict, job_runs as jobs_runs,
     job_verify_cancel as jobs_verify_cancel, job_verify_start as jobs_verify_start,
     job_verify_status as jobs_verify_status, list_jobs as jobs_list,
 };
@@ -88,6 +100,7 @@ pub use maintenance::{
     bucket_status as maintenance_bucket_status, start_backfill as maintenance_start_backfill,
     start_migrate as maintenance_start_migrate, start_reencrypt as maintenance_start_reencrypt,
 };
+pub use mirrors::{list_mirrors, mirror_parity, MirrorInfo, MirrorParityResponse};
 pub use objects::{
     bulk_delete as bulk_delete_objects, copy_objects, download_zip, list_all as list_all_objects,
     move_objects,
@@ -97,7 +110,15 @@ pub use scanner::{
     get_bucket_usage, get_usage, migrate_legacy, refresh_bucket_usage, scan_usage,
     ScanUsageRequest, UsageQuery,
 };
+pub use service_accounts::{
+    create_own_service_account, create_user_service_account, delete_own_service_account,
+    delete_service_account, list_own_service_accounts, list_user_service_accounts,
+    update_own_service_account, update_service_account, CreateServiceAccountRequest,
+    UpdateServiceAccountRequest, MAX_SERVICE_ACCOUNTS_PER_USER,
+};
 pub use sessions::{list_sessions, revoke_session, revoke_user_sessions};
+pub use simulate::simulate_iam;
+pub use spans::{list_spans, SpanInfo, SpanMemberInfo};
 pub use users::{
     clone_user, create_user, delete_user, get_canned_policies, iam_version, list_users,
     rotate_user_keys, update_user, usage_scan_version, CloneUserRequest, CreateUserRequest,
@@ -128,7 +128,7 @@ pub struct ZipQuery {
 // Helpers
 // ---------------------------------------------------------------------------
 
-const MAX_BULK_OBJECTS: usize = 10_000;
+pub(super) const MAX_BULK_OBJECTS: usize = 10_000;
 const MAX_FAILURE_ENTRIES: usize = 100;
 const MAX_ZIP_BYTES: u64 = 500 * 1024 * 1024;
 
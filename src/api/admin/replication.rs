@@ -29,7 +29,7 @@ pub struct RunNowResponse {
 
 /// Snapshot the replication config (lock released immediately) and find the
 /// named rule, or 404. Returns the whole `repl` too — callers need its flags.
-async fn snapshot_and_find_rule(
+pub(super) async fn snapshot_and_find_rule(
     state: &Arc<AdminState>,
     name: &str,
 ) -> Result<(ReplicationConfig, ReplicationRule), (StatusCode, String)> {
@@ -65,10 +65,7 @@ pub async fn run_now(
 
     // Same deferral the scheduler and event consumer apply: run-now must
     // not write into a destination a maintenance job is rewriting.
-    if state
-        .s3_state
-        .maintenance_gate
-        .is_busy(&rule.destination.bucket)
+    if replication::external::under_maintenance(&state.s3_state.maintenance_gate, &rule.destination)
     {
         return Err((
             StatusCode::CONFLICT,
@@ -189,6 +186,10 @@ pub async fn run_now(
     let object_skip_after_failures = repl.object_skip_after_failures;
     let lease_ttl_secs = replication::scheduler::lease_ttl_secs(&repl);
     let heartbeat_secs = replication::scheduler::heartbeat_secs(&repl);
+    // Run-now ignores transfer windows but still honours the bandwidth caps;
+    // pick up a cap edited since the scheduler's last tick.
+    replication::throttle::configure(&repl);
+    replication::external::configure(&repl);
     let concurrency = replication::RunConcurrency {
         transfers: repl.transfers,
         upload_concurrency: repl.upload_concurrency,
@@ -310,6 +311,12 @@ pub async fn verify(
     State(state): State<Arc<AdminState>>,
 ) -> Result<(StatusCode, Json<ParityStatusResponse>), (StatusCode, String)> {
     let (_repl, rule) = snapshot_and_find_rule(&state, &name).await?;
+    if replication::external::is_external(&rule) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            replication::external::PARITY_UNSUPPORTED.to_string(),
+        ));
+    }
     let Some(db_arc) = state.config_db.clone() else {
         // No config DB → fall back to a synchronous in-request audit (dev/no-DB).
         let engine = state.s3_state.engine.load().clone();
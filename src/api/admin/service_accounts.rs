@@ -0,0 +1,408 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Service account handlers: long-lived access keys owned by an IAM user,
+//! optionally narrowed by an inline session policy and an expiry.
+//!
+//! Two route families share the same core:
+//! - `/session/service-accounts` — self-service for the logged-in IAM user
+//!   (login-as, browser-lift or external login). Bootstrap and open-access
+//!   sessions have no IAM identity to own a key and get 403.
+//! - `/users/:id/service-accounts`, `/service-accounts/:id` — admin management
+//!   of any user's service accounts.
+
+use axum::extract::{ConnectInfo, Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use serde::{Deserialize, Deserializer};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::config_db::{ConfigDb, ConfigDbError, ServiceAccountUpdate};
+use crate::iam::{self, normalize_permissions, validate_permissions, Permission, ServiceAccount};
+use crate::session::AuthMethod;
+
+use super::auth::{extract_session_token, request_client_ip};
+use super::users::rebuild_iam_index;
+use super::{audit_log, trigger_config_sync, AdminState};
+
+/// Upper bound on service accounts per user. Each one is an independent
+/// credential, so an unbounded number is an unbounded revocation problem.
+pub const MAX_SERVICE_ACCOUNTS_PER_USER: usize = 50;
+
+#[derive(Deserialize)]
+pub struct CreateServiceAccountRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    /// Inline session policy. Omitted = inherit the parent's permissions.
+    pub policy: Option<Vec<Permission>>,
+    /// Unix seconds; must be in the future. Omitted = never expires.
+    pub expires_at: Option<i64>,
+    #[serde(default = "crate::types::default_true")]
+    pub enabled: bool,
+}
+
+/// Partial update. For `policy` and `expires_at`, an explicit `null` clears
+/// the field while omitting it leaves it unchanged.
+#[derive(Deserialize)]
+pub struct UpdateServiceAccountRequest {
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    pub policy: Option<Option<Vec<Permission>>>,
+    #[serde(default, deserialize_with = "present")]
+    pub expires_at: Option<Option<i64>>,
+    pub enabled: Option<bool>,
+}
+
+/// Distinguish `"field": null` (`Some(None)`) from an absent field (`None`).
+fn present<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+fn mask(account: ServiceAccount) -> ServiceAccount {
+    ServiceAccount {
+        secret_access_key: "****".to_string(),
+        ..account
+    }
+}
+
+fn now_unix() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// Normalize and validate an inline policy; `None` passes through.
+fn checked_policy(policy: Option<Vec<Permission>>) -> Result<Option<Vec<Permission>>, StatusCode> {
+    let Some(mut perms) = policy else {
+        return Ok(None);
+    };
+    normalize_permissions(&mut perms);
+    if let Err(msg) = validate_permissions(&perms) {
+        tracing::warn!("Invalid service account policy: {}", msg);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(Some(perms))
+}
+
+fn check_expiry(expires_at: Option<i64>) -> Result<(), StatusCode> {
+    match expires_at {
+        Some(at) if at <= now_unix() => {
+            tracing::warn!("Service account expiry {} is not in the future", at);
+            Err(StatusCode::BAD_REQUEST)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// How the caller's session was created, for self-service routes. Resolved
+/// before taking the config DB lock: a shared session store may hit the network.
+async fn session_auth_method(
+    state: &AdminState,
+    headers: &HeaderMap,
+    connect_info: Option<&ConnectInfo<SocketAddr>>,
+) -> Result<AuthMethod, StatusCode> {
+    let token = extract_session_token(headers).ok_or(StatusCode::UNAUTHORIZED)?;
+    state
+        .sessions
+        .auth_method(&token, request_client_ip(headers, connect_info))
+        .await
+        .ok_or(StatusCode::UNAUTHORIZED)
+}
+
+/// The IAM user id behind the caller's session.
+fn session_user_id(db: &ConfigDb, method: AuthMethod) -> Result<i64, StatusCode> {
+    match method {
+        AuthMethod::IamLoginAs { access_key_id } | AuthMethod::IamBrowserLift { access_key_id } => {
+            db.get_user_by_access_key(&access_key_id)
+                .ok()
+                .flatten()
+                .map(|user| user.id)
+                .ok_or(StatusCode::FORBIDDEN)
+        }
+        AuthMethod::External { user_id, .. } => Ok(user_id),
+        AuthMethod::Bootstrap | AuthMethod::OpenLift => Err(StatusCode::FORBIDDEN),
+    }
+}
+
+fn list_for(db: &ConfigDb, user_id: i64) -> Result<Json<Vec<ServiceAccount>>, StatusCode> {
+    let accounts = db.list_service_accounts_for_user(user_id).map_err(|e| {
+        tracing::error!(
+            "Failed to load service accounts for user {}: {}",
+            user_id,
+            e
+        );
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(accounts.into_iter().map(mask).collect()))
+}
+
+fn create_for(
+    state: &Arc<AdminState>,
+    db: &ConfigDb,
+    headers: &HeaderMap,
+    actor: &str,
+    parent_user_id: i64,
+    body: CreateServiceAccountRequest,
+) -> Result<(StatusCode, Json<ServiceAccount>), StatusCode> {
+    db.get_user_by_id(parent_user_id)
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    if body.name.is_empty() || body.name.starts_with('$') {
+        tracing::warn!("Invalid service account name: {:?}", body.name);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let access_key_id = body
+        .access_key_id
+        .unwrap_or_else(iam::generate_access_key_id);
+    if access_key_id.is_empty()
+        || !access_key_id.is_ascii()
+        || access_key_id.contains(char::is_whitespace)
+    {
+        tracing::warn!("Invalid access key format: {:?}", access_key_id);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let policy = checked_policy(body.policy)?;
+    check_expiry(body.expires_at)?;
+
+    let existing = db
+        .list_service_accounts_for_user(parent_user_id)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if existing.len() >= MAX_SERVICE_ACCOUNTS_PER_USER {
+        tracing::warn!(
+            "User {} already has {} service accounts (max {})",
+            parent_user_id,
+            existing.len(),
+            MAX_SERVICE_ACCOUNTS_PER_USER
+        );
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let account = db
+        .create_service_account(&ServiceAccount {
+            id: 0,
+            parent_user_id,
+            name: body.name,
+            description: body.description,
+            access_key_id,
+            secret_access_key: body
+                .secret_access_key
+                .unwrap_or_else(iam::generate_secret_access_key),
+            policy,
+            expires_at: body.expires_at,
+            enabled: body.enabled,
+            created_at: String::new(),
+        })
+        .map_err(|e| {
+            tracing::warn!("Failed to create service account: {}", e);
+            StatusCode::CONFLICT
+        })?;
+
+    rebuild_iam_index(db, &state.iam_state)?;
+    trigger_config_sync(state);
+
+    tracing::info!(
+        "Service account '{}' created for user {} ({})",
+        account.name,
+        parent_user_id,
+        account.access_key_id
+    );
+    audit_log(
+        "create_service_account",
+        actor,
+        &account.access_key_id,
+        headers,
+    );
+    // Return the full secret (shown only once)
+    Ok((StatusCode::CREATED, Json(account)))
+}
+
+fn update_one(
+    state: &Arc<AdminState>,
+    db: &ConfigDb,
+    headers: &HeaderMap,
+    actor: &str,
+    id: i64,
+    body: UpdateServiceAccountRequest,
+) -> Result<Json<ServiceAccount>, StatusCode> {
+    let policy = match body.policy {
+        Some(policy) => Some(checked_policy(policy)?),
+        None => None,
+    };
+    if let Some(expires_at) = body.expires_at {
+        check_expiry(expires_at)?;
+    }
+    let account = db
+        .update_service_account(
+            id,
+            &ServiceAccountUpdate {
+                description: body.description,
+                policy,
+                expires_at: body.expires_at,
+                enabled: body.enabled,
+            },
+        )
+        .map_err(|e| match e {
+            ConfigDbError::NotFound(_) => StatusCode::NOT_FOUND,
+            e => {
+                tracing::error!("Failed to update service account {}: {}", id, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })?;
+
+    rebuild_iam_index(db, &state.iam_state)?;
+    trigger_config_sync(state);
+
+    audit_log(
+        "update_service_account",
+        actor,
+        &account.access_key_id,
+        headers,
+    );
+    Ok(Json(mask(account)))
+}
+
+fn delete_one(
+    state: &Arc<AdminState>,
+    db: &ConfigDb,
+    headers: &HeaderMap,
+    actor: &str,
+    id: i64,
+) -> Result<StatusCode, StatusCode> {
+    let account = db
+        .get_service_account(id)
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    db.delete_service_account(id)
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    rebuild_iam_index(db, &state.iam_state)?;
+    trigger_config_sync(state);
+
+    tracing::info!("Service account {} deleted ({})", id, account.access_key_id);
+    audit_log(
+        "delete_service_account",
+        actor,
+        &account.access_key_id,
+        headers,
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Self-service routes may only touch the caller's own service accounts.
+/// A foreign id answers 404, not 403, so ids of other users don't leak.
+fn ensure_owned(db: &ConfigDb, id: i64, user_id: i64) -> Result<(), StatusCode> {
+    match db.get_service_account(id) {
+        Ok(account) if account.parent_user_id == user_id => Ok(()),
+        _ => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+/// GET /api/admin/session/service-accounts — the caller's service accounts.
+pub async fn list_own_service_accounts(
+    State(state): State<Arc<AdminState>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<ServiceAccount>>, StatusCode> {
+    let method = session_auth_method(&state, &headers, connect_info.as_ref()).await?;
+    let db = state.config_db.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let db = db.lock().await;
+    let user_id = session_user_id(&db, method)?;
+    list_for(&db, user_id)
+}
+
+/// POST /api/admin/session/service-accounts — mint a key for the caller
+/// (returns the full secret once).
+pub async fn create_own_service_account(
+    State(state): State<Arc<AdminState>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(body): Json<CreateServiceAccountRequest>,
+) -> Result<(StatusCode, Json<ServiceAccount>), StatusCode> {
+    let method = session_auth_method(&state, &headers, connect_info.as_ref()).await?;
+    let db = state.config_db.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let db = db.lock().await;
+    let user_id = session_user_id(&db, method)?;
+    create_for(&state, &db, &headers, "self", user_id, body)
+}
+
+/// PUT /api/admin/session/service-accounts/:id — update one of the caller's.
+pub async fn update_own_service_account(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<i64>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(body): Json<UpdateServiceAccountRequest>,
+) -> Result<Json<ServiceAccount>, StatusCode> {
+    let method = session_auth_method(&state, &headers, connect_info.as_ref()).await?;
+    let db = state.config_db.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let db = db.lock().await;
+    let user_id = session_user_id(&db, method)?;
+    ensure_owned(&db, id, user_id)?;
+    update_one(&state, &db, &headers, "self", id, body)
+}
+
+/// DELETE /api/admin/session/service-accounts/:id — delete one of the caller's.
+pub async fn delete_own_service_account(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<i64>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    let method = session_auth_method(&state, &headers, connect_info.as_ref()).await?;
+    let db = state.config_db.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let db = db.lock().await;
+    let user_id = session_user_id(&db, method)?;
+    ensure_owned(&db, id, user_id)?;
+    delete_one(&state, &db, &headers, "self", id)
+}
+
+/// GET /api/admin/users/:id/service-accounts — list a user's service accounts.
+pub async fn list_user_service_accounts(
+    State(state): State<Arc<AdminState>>,
+    Path(user_id): Path<i64>,
+) -> Result<Json<Vec<ServiceAccount>>, StatusCode> {
+    let db = state.config_db.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let db = db.lock().await;
+    db.get_user_by_id(user_id)
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    list_for(&db, user_id)
+}
+
+/// POST /api/admin/users/:id/service-accounts — mint a key for a user.
+pub async fn create_user_service_account(
+    State(state): State<Arc<AdminState>>,
+    Path(user_id): Path<i64>,
+    headers: HeaderMap,
+    Json(body): Json<CreateServiceAccountRequest>,
+) -> Result<(StatusCode, Json<ServiceAccount>), StatusCode> {
+    let db = state.config_db.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let db = db.lock().await;
+    create_for(&state, &db, &headers, "admin", user_id, body)
+}
+
+/// PUT /api/admin/service-accounts/:id — update any service account.
+pub async fn update_service_account(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+    Json(body): Json<UpdateServiceAccountRequest>,
+) -> Result<Json<ServiceAccount>, StatusCode> {
+    let db = state.config_db.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let db = db.lock().await;
+    update_one(&state, &db, &headers, "admin", id, body)
+}
+
+/// DELETE /api/admin/service-accounts/:id — delete any service account.
+pub async fn delete_service_account(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    let db = state.config_db.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    let db = db.lock().await;
+    delete_one(&state, &db, &headers, "admin", id)
+}
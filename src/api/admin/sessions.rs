@@ -19,7 +19,7 @@ use super::{audit_log, AdminState};
 
 /// GET /api/admin/sessions — list live (non-expired) sessions, redacted.
 pub async fn list_sessions(State(state): State<Arc<AdminState>>) -> impl IntoResponse {
-    let sessions = state.sessions.list();
+    let sessions = state.sessions.list().await;
     (
         StatusCode::OK,
         Json(serde_json::json!({ "sessions": sessions })),
@@ -42,7 +42,7 @@ pub async fn revoke_session(
             );
         }
     }
-    let revoked = state.sessions.revoke_by_id(&id);
+    let revoked = state.sessions.revoke_by_id(&id).await;
     if !revoked {
         return (
             StatusCode::NOT_FOUND,
@@ -82,7 +82,7 @@ pub(crate) async fn revoke_identities_everywhere(
     let now = crate::event_outbox::current_unix_seconds();
     let mut revoked_local = 0;
     for identity in identities {
-        revoked_local += state.sessions.revoke_by_identity(identity);
+        revoked_local += state.sessions.revoke_by_identity(identity).await;
         state.sessions.note_revocation(identity, now);
     }
 
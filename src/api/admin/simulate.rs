@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! IAM policy simulator admin handler.
+//!
+//! `POST /_/api/admin/iam/simulate` — the IAM counterpart of
+//! `config/trace`: evaluate a synthetic request for a user, group or
+//! hypothetical rule set and return the decision plus the rules behind it.
+//! All evaluation lives in [`crate::iam::simulate`]; this handler only hands
+//! it the live IAM index.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::iam::simulate::{simulate, SimulationError, SimulationRequest};
+use crate::iam::{IamIndex, IamState};
+
+use super::AdminState;
+
+/// `POST /_/api/admin/iam/simulate` — explain the IAM decision for a
+/// synthetic request. Read-only, so it also works in declarative IAM mode.
+pub async fn simulate_iam(
+    State(state): State<Arc<AdminState>>,
+    Json(body): Json<SimulationRequest>,
+) -> impl IntoResponse {
+    // Simulate against the index live requests authenticate with, so users,
+    // service accounts and their session policies resolve identically.
+    // Without multi-user IAM only inline rule sets have anything to resolve.
+    let iam_state = state.iam_state.load();
+    let empty;
+    let index = match iam_state.as_ref() {
+        IamState::Iam(index) => index,
+        _ => {
+            empty = IamIndex::from_users(Vec::new());
+            &empty
+        }
+    };
+
+    match simulate(&body, index, chrono::Utc::now().timestamp()) {
+        Ok(result) => Json(result).into_response(),
+        Err(e) => {
+            let status = match e {
+                SimulationError::Invalid(_) => StatusCode::BAD_REQUEST,
+                SimulationError::NotFound(_) => StatusCode::NOT_FOUND,
+            };
+            (status, Json(serde_json::json!({ "error": e.to_string() }))).into_response()
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Admin endpoint for capacity-spanned buckets (`buckets.<name>.span`).
+//!
+//! - `GET /_/api/admin/spans` — each spanned bucket's members in fill order,
+//!   with capacity and freshly measured usage.
+
+use axum::extract::{Extension, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
+use std::sync::Arc;
+
+use super::auth::AdminGuiGate;
+
+#[derive(Debug, Serialize)]
+pub struct SpanInfo {
+    pub bucket: String,
+    pub members: Vec<SpanMemberInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SpanMemberInfo {
+    pub backend: String,
+    pub real_bucket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity_bytes: Option<u64>,
+    pub used_bytes: u64,
+    /// Usage reached capacity: new deltaspaces go to a later member.
+    pub full: bool,
+}
+
+pub async fn list_spans(
+    Extension(_gate): Extension<AdminGuiGate>,
+    State(state): State<Arc<crate::api::admin::AdminState>>,
+) -> Result<Json<Vec<SpanInfo>>, (StatusCode, String)> {
+    let engine = state.s3_state.engine.load_full();
+    let Some(spans) = engine.span_set() else {
+        return Ok(Json(Vec::new()));
+    };
+    let mut out = Vec::new();
+    for bucket in spans.buckets() {
+        let Some(route) = spans.route(bucket) else {
+            continue;
+        };
+        let usage = spans
+            .usage(bucket)
+            .await
+            .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+        let members = route
+            .members
+            .iter()
+            .zip(usage)
+            .map(|(member, (_, used_bytes))| SpanMemberInfo {
+                backend: member.backend.clone(),
+                real_bucket: member.real_bucket.clone(),
+                capacity_bytes: member.capacity_bytes,
+                used_bytes,
+                full: member.capacity_bytes.is_some_and(|c| used_bytes >= c),
+            })
+            .collect();
+        out.push(SpanInfo {
+            bucket: bucket.to_string(),
+            members,
+        });
+    }
+    Ok(Json(out))
+}
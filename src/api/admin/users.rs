@@ -150,9 +150,14 @@ fn rebuild_iam_index_inner(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    let service_accounts = db.load_service_accounts().map_err(|e| {
+        tracing::error!("Failed to load service accounts from config DB: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
     let count = users.len();
     let group_count = groups.len();
-    let state = IamIndex::build_iam_state(users, groups);
+    let state = IamIndex::build_iam_state(users, groups, service_accounts);
     iam_state.store(Arc::new(state));
     // Bump AFTER the store so observers see the new state when they
     // see a new version — lets integration tests poll `iam/version`
@@ -16,7 +16,7 @@
 //! canonical-request + HMAC machinery was removed.
 
 use super::S3Error;
-use crate::iam::{AuthenticatedUser, IamState, Permission, SharedIamState};
+use crate::iam::{AuthenticatedUser, CredentialRejection, IamState, Permission, SharedIamState};
 use crate::metrics::Metrics;
 use crate::rate_limiter::{self, RateLimiter};
 use axum::body::Body;
@@ -282,6 +282,7 @@ fn build_anonymous_user(bucket: &str, public_prefixes: &[String]) -> Authenticat
         access_key_id: String::new(),
         permissions,
         iam_policies,
+        session_policy: None,
     }
 }
 
@@ -517,12 +518,34 @@ pub async fn sigv4_auth_middleware(
     let (audit_ip, audit_ua) =
         crate::audit::extract_client_info_with_peer(request.headers(), peer_ip);
 
+    // `AuthFailed` security event (opt-in): recorded off the request path.
+    let access_events = request
+        .extensions()
+        .get::<crate::access_events::AccessEventRecorder>()
+        .cloned();
+    let request_path = request.uri().path().to_string();
+
     let record_auth_failure = {
         let metrics = metrics.clone();
         let rate_limiter = rate_limiter.clone();
         let audit_ip = audit_ip.clone();
         let audit_ua = audit_ua.clone();
-        move |reason: &str| {
+        move |reason: &str, access_key: Option<&str>| {
+            if let Some(recorder) = access_events.clone() {
+                let (bucket, key) = crate::access_events::request_bucket_key(&request_path);
+                let requester = crate::access_events::Requester {
+                    principal: None,
+                    access_key_id: access_key.map(str::to_string),
+                    source_ip: Some(audit_ip.clone()).filter(|ip| ip != "unknown"),
+                    user_agent: Some(audit_ua.clone()).filter(|ua| !ua.is_empty()),
+                };
+                let reason = reason.to_string();
+                tokio::spawn(async move {
+                    recorder
+                        .auth_failed(&reason, &bucket, &key, &requester)
+                        .await;
+                });
+            }
             if let Some(m) = &metrics {
                 m.auth_attempts_total.with_label_values(&["failure"]).inc();
                 m.auth_failures_total.with_label_values(&[reason]).inc();
@@ -693,11 +716,11 @@ pub async fn sigv4_auth_middleware(
     let is_presigned = has_presigned_query_params(query_string);
     let params = if is_presigned {
         SigV4Params::from_query(&request).inspect_err(|_| {
-            record_auth_failure("invalid_presigned");
+            record_auth_failure("invalid_presigned", None);
         })?
     } else {
         SigV4Params::from_headers(&request).inspect_err(|_| {
-            record_auth_failure("missing_header");
+            record_auth_failure("missing_header", None);
         })?
     };
 
@@ -732,7 +755,7 @@ pub async fn sigv4_auth_middleware(
             let matches: bool = provided_hash.ct_eq(&configured_hash).into();
             if !matches {
                 debug!("SigV4: access key mismatch (legacy mode)");
-                record_auth_failure("invalid_access_key");
+                record_auth_failure("invalid_access_key", Some(&params.access_key));
                 return Err(S3Error::AccessDenied.into_response());
             }
             // Legacy user gets full access via wildcard permissions
@@ -752,30 +775,29 @@ pub async fn sigv4_auth_middleware(
                 access_key_id: auth.access_key_id.clone(),
                 permissions: bootstrap_perms,
                 iam_policies: bootstrap_policies,
+                session_policy: None,
             };
             Some(auth_user)
         }
         AuthGateDecision::Iam(index) => {
-            let user = match index.get(&params.access_key) {
-                Some(u) => u,
-                None => {
+            match index.authenticate(&params.access_key, chrono::Utc::now().timestamp()) {
+                Ok((_, user)) => Some(user),
+                Err(CredentialRejection::UnknownKey) => {
                     debug!("SigV4: unknown access key '{}'", &params.access_key);
-                    record_auth_failure("invalid_access_key");
+                    record_auth_failure("invalid_access_key", Some(&params.access_key));
+                    return Err(S3Error::AccessDenied.into_response());
+                }
+                Err(CredentialRejection::Disabled) => {
+                    debug!("SigV4: access key '{}' is disabled", &params.access_key);
+                    record_auth_failure("user_disabled", Some(&params.access_key));
+                    return Err(S3Error::AccessDenied.into_response());
+                }
+                Err(CredentialRejection::Expired) => {
+                    debug!("SigV4: access key '{}' has expired", &params.access_key);
+                    record_auth_failure("key_expired", Some(&params.access_key));
                     return Err(S3Error::AccessDenied.into_response());
                 }
-            };
-            if !user.enabled {
-                debug!("SigV4: user '{}' is disabled", user.name);
-                record_auth_failure("user_disabled");
-                return Err(S3Error::AccessDenied.into_response());
             }
-            let auth_user = AuthenticatedUser {
-                name: user.name.clone(),
-                access_key_id: user.access_key_id.clone(),
-                permissions: user.permissions.clone(),
-                iam_policies: user.iam_policies.clone(),
-            };
-            Some(auth_user)
         }
     };
 
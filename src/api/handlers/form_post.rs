@@ -39,7 +39,9 @@
 use super::object_helpers::{check_client_write_allowed, check_quota, enqueue_object_event};
 use super::{audit_log_s3, ensure_bucket_exists, AppState};
 use crate::api::errors::S3Error;
-use crate::event_outbox::{current_unix_seconds, EventKind, EventSource, NewEvent};
+use crate::event_outbox::{
+    attach_user_metadata, current_unix_seconds, EventKind, EventSource, NewEvent,
+};
 use crate::iam::{AuthenticatedUser, IamState, Permission, S3Action, SharedIamState};
 use axum::body::Bytes;
 use axum::http::{HeaderMap, StatusCode};
@@ -619,23 +621,15 @@ fn authenticate_form_post(
                     access_key_id: auth.access_key_id.clone(),
                     permissions: bootstrap_perms,
                     iam_policies: bootstrap_policies,
+                    session_policy: None,
                 },
             )
         }
         IamState::Iam(index) => {
-            let user = index
-                .get(access_key)
-                .filter(|u| u.enabled)
-                .ok_or(S3Error::AccessDenied)?;
-            (
-                user.secret_access_key.clone(),
-                AuthenticatedUser {
-                    name: user.name.clone(),
-                    access_key_id: user.access_key_id.clone(),
-                    permissions: user.permissions.clone(),
-                    iam_policies: user.iam_policies.clone(),
-                },
-            )
+            let (secret, user) = index
+                .authenticate(access_key, chrono::Utc::now().timestamp())
+                .map_err(|_| S3Error::AccessDenied)?;
+            (secret.to_string(), user)
         }
     };
 
@@ -797,6 +791,12 @@ pub async fn handle_form_post_upload(
             .await?
     };
     let storage_type = result.metadata.storage_info.label();
+    let mut payload = serde_json::json!({
+        "content_length": parsed.file_data.len(),
+        "storage_type": storage_type,
+        "etag": result.metadata.etag(),
+    });
+    attach_user_metadata(&mut payload, &result.metadata.user_metadata);
     enqueue_object_event(
         state,
         NewEvent::new(
@@ -805,11 +805,7 @@ pub async fn handle_form_post_upload(
             &parsed.resolved_key,
             EventSource::S3Api,
             current_unix_seconds(),
-            serde_json::json!({
-                "content_length": parsed.file_data.len(),
-                "storage_type": storage_type,
-                "etag": result.metadata.etag(),
-            }),
+            payload,
         ),
     )
     .await;
@@ -868,6 +864,7 @@ mod tests {
                 permission_to_iam_policy(&allow),
                 permission_to_iam_policy(&deny),
             ],
+            session_policy: None,
         };
 
         // Context-free (the OLD, buggy path): the conditioned Deny doesn't fire
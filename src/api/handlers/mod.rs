@@ -69,6 +69,9 @@ pub struct AppState {
     /// single-instance). Re-attached to the engine on every rebuild, mirroring
     /// `bucket_usage`, so a config reload never drops the cross-node protection.
     pub reference_lock: Option<Arc<dyn crate::coordination::ReferenceLock>>,
+    /// Durable multipart upload registry (`None` = uploads live only in
+    /// `multipart`). Re-attached to the engine on every rebuild.
+    pub upload_registry: Option<Arc<dyn crate::multipart_registry::UploadRegistry>>,
     pub config_db: Option<Arc<tokio::sync::Mutex<ConfigDb>>>,
     /// Per-bucket WRITE gate for maintenance jobs (re-encryption). Layered
     /// into the S3 router as middleware; admin handlers and background
@@ -84,6 +87,10 @@ pub struct AppState {
     /// loop. Gates S3 requests to buckets on unhealthy backends (503) and
     /// feeds the admin backends API. See `src/coordination/health.rs`.
     pub backend_health: Arc<crate::coordination::BackendHealthCache>,
+    /// Opt-in read and security events (`event_delivery.access_events`);
+    /// records nothing without a config DB. Also layered into the S3 router
+    /// so the auth middlewares can record denials.
+    pub access_events: crate::access_events::AccessEventRecorder,
 }
 
 // ---------------------------------------------------------------------------
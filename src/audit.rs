@@ -17,6 +17,12 @@
 //! still fires so operators grepping stdout / JSON logs in production
 //! see nothing change. The ring is strictly a UX convenience for the
 //! admin panel, not a compliance substitute.
+//!
+//! ## Durable store
+//!
+//! With `advanced.audit.enabled`, every entry is also appended to the
+//! hash-chained, archived store in [`crate::audit_store`] — that one is
+//! meant for compliance.
 
 use axum::http::HeaderMap;
 use chrono::{DateTime, Utc};
@@ -78,7 +84,7 @@ pub fn extract_client_info_with_peer(
 /// through to the GUI without further shaping. Fields are all
 /// already-sanitised copies of what went into the tracing line, so
 /// the GUI doesn't have to worry about control-chars / pipe glyphs.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct AuditEntry {
     /// ISO-8601 UTC timestamp with millis — human-readable and
     /// trivial to sort client-side.
@@ -155,9 +161,10 @@ pub fn recent_audit(limit: usize) -> Vec<AuditEntry> {
 ///
 /// Also pushes a copy of the (sanitised) fields onto the in-memory
 /// audit ring so the admin GUI's Diagnostics → Audit panel has
-/// something to show. Tracing + ring share the exact same sanitised
-/// payload — nothing in the GUI comes from a different source than
-/// what hits stdout.
+/// something to show, and offers it to the durable audit store.
+/// Tracing, ring and store share the exact same sanitised payload —
+/// nothing in the GUI comes from a different source than what hits
+/// stdout.
 pub fn audit_log(
     action: &str,
     user: &str,
@@ -184,7 +191,7 @@ pub fn audit_log(
         &s_bucket,
         &s_path
     );
-    push_ring(AuditEntry {
+    let entry = AuditEntry {
         timestamp: Utc::now(),
         action: s_action,
         user: s_user,
@@ -193,5 +200,7 @@ pub fn audit_log(
         ua: s_ua,
         bucket: s_bucket,
         path: s_path,
-    });
+    };
+    push_ring(entry.clone());
+    crate::audit_store::offer(entry);
 }
@@ -0,0 +1,1191 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Durable audit store (`advanced.audit`).
+//!
+//! Every [`crate::audit::audit_log`] entry is offered to this store as well
+//! as to the in-memory ring. One writer task appends them to the node's
+//! config DB in batches; a request never waits on it. An entry that
+//! doesn't fit in the channel is dropped and counted in
+//! `deltaglider_audit_entries_total{outcome="dropped"}`, and the writer
+//! then appends an [`DROPPED_ACTION`] entry with the count, so the gap is
+//! in the chain itself and [`verify_chain`] reports it.
+//!
+//! ## Tamper evidence
+//!
+//! `audit_log` rows form a hash chain. Each row stores the previous row's
+//! hash, and `hash = sha256(prev_hash|id|ts_ms|action|user|target|ip|ua|bucket|path)`
+//! (lowercase hex, over the sanitised fields, so `|` never appears
+//! unescaped). The first row of a chain links to [`GENESIS_HASH`]. A
+//! trigger refuses UPDATEs, the chain head is kept in `audit_chain`, and
+//! [`ChainVerifier`] recomputes every link — so editing, inserting or
+//! deleting a row, the newest included, shows up as a broken chain.
+//! Retention only deletes from the old end (and never the newest row);
+//! verification then starts from the oldest remaining row's `prev_hash`.
+//!
+//! ## Archive
+//!
+//! With an archive bucket (`archive_bucket`, else `config_sync_bucket`),
+//! [`spawn_archiver`] seals new rows into JSONL segments every
+//! `rotate_interval`, keyed `<archive_prefix><chain_id>/<first>-<last>.jsonl`
+//! so the segments of one chain list in order and verify end to end.
+//! Archived rows leave the config DB after `local_retention`; segments are
+//! deleted after `retention`.
+
+use crate::audit::AuditEntry;
+use crate::background::spawn_batch_writer;
+use crate::config::SharedConfig;
+use crate::config_db::{ConfigDb, ConfigDbError};
+use crate::config_db_sync::ConfigDbSync;
+use crate::metrics::Metrics;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use chrono::{DateTime, TimeZone, Utc};
+use prometheus::IntCounter;
+use rusqlite::{params, OptionalExtension, ToSql};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Mutex};
+use tracing::{debug, info, warn};
+
+/// `prev_hash` of the first entry of a chain.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Action of the entry recording how many entries the writer dropped; its
+/// `target` is the count.
+pub const DROPPED_ACTION: &str = "audit_entries_dropped";
+/// How often the archiver wakes up to seal, sweep and prune.
+const ARCHIVE_TICK: Duration = Duration::from_secs(60);
+/// Most entries in one archive segment; a backlog is sealed in several.
+const SEGMENT_MAX_ENTRIES: usize = 50_000;
+/// How often expired segments are swept from the archive bucket.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(3600);
+/// Rows deleted per prune statement; the DB lock is released in between.
+const PRUNE_BATCH: usize = 5_000;
+/// Rows read per verification page.
+const VERIFY_PAGE: usize = 2_000;
+
+/// One persisted audit entry: the ring's fields plus its place in the chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredAuditEntry {
+    pub id: i64,
+    pub timestamp: DateTime<Utc>,
+    pub action: String,
+    pub user: String,
+    pub target: String,
+    pub ip: String,
+    pub ua: String,
+    pub bucket: String,
+    pub path: String,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl StoredAuditEntry {
+    /// The hash this entry should carry, given its `prev_hash`.
+    pub fn expected_hash(&self) -> String {
+        chain_hash(
+            &self.prev_hash,
+            self.id,
+            self.timestamp.timestamp_millis(),
+            &self.fields(),
+        )
+    }
+
+    fn fields(&self) -> [&str; 7] {
+        [
+            &self.action,
+            &self.user,
+            &self.target,
+            &self.ip,
+            &self.ua,
+            &self.bucket,
+            &self.path,
+        ]
+    }
+}
+
+fn entry_fields(e: &AuditEntry) -> [&str; 7] {
+    [
+        &e.action, &e.user, &e.target, &e.ip, &e.ua, &e.bucket, &e.path,
+    ]
+}
+
+/// `sha256(prev_hash|id|ts_ms|action|user|target|ip|ua|bucket|path)`, hex.
+pub fn chain_hash(prev_hash: &str, id: i64, ts_ms: i64, fields: &[&str; 7]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.as_bytes());
+    hasher.update(format!("|{id}|{ts_ms}").as_bytes());
+    for field in fields {
+        hasher.update(b"|");
+        hasher.update(field.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Query over the audit log. Every set criterion must match; empty strings
+/// count as unset.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuditFilter {
+    pub user: Option<String>,
+    pub bucket: Option<String>,
+    /// Key prefix, matched against the entry's `path`.
+    pub key: Option<String>,
+    pub action: Option<String>,
+    /// Inclusive lower bound.
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound.
+    pub to: Option<DateTime<Utc>>,
+}
+
+fn set(value: &Option<String>) -> Option<&str> {
+    value.as_deref().filter(|v| !v.is_empty())
+}
+
+impl AuditFilter {
+    /// Whether a ring entry matches (the store applies the same criteria in
+    /// SQL).
+    pub fn matches(&self, e: &AuditEntry) -> bool {
+        set(&self.user).is_none_or(|u| e.user == u)
+            && set(&self.bucket).is_none_or(|b| e.bucket == b)
+            && set(&self.key).is_none_or(|k| e.path.starts_with(k))
+            && set(&self.action).is_none_or(|a| e.action == a)
+            && self.from.is_none_or(|from| e.timestamp >= from)
+            && self.to.is_none_or(|to| e.timestamp < to)
+    }
+
+    /// SQL conditions (joined with AND) and their bind values.
+    fn conditions(&self) -> (Vec<&'static str>, Vec<Box<dyn ToSql>>) {
+        let mut clauses = Vec::new();
+        let mut binds: Vec<Box<dyn ToSql>> = Vec::new();
+        if let Some(user) = set(&self.user) {
+            clauses.push("user_name = ?");
+            binds.push(Box::new(user.to_string()));
+        }
+        if let Some(bucket) = set(&self.bucket) {
+            clauses.push("bucket = ?");
+            binds.push(Box::new(bucket.to_string()));
+        }
+        if let Some(key) = set(&self.key) {
+            clauses.push("substr(path, 1, ?) = ?");
+            binds.push(Box::new(key.chars().count() as i64));
+            binds.push(Box::new(key.to_string()));
+        }
+        if let Some(action) = set(&self.action) {
+            clauses.push("action = ?");
+            binds.push(Box::new(action.to_string()));
+        }
+        if let Some(from) = self.from {
+            clauses.push("ts >= ?");
+            binds.push(Box::new(from.timestamp_millis()));
+        }
+        if let Some(to) = self.to {
+            clauses.push("ts < ?");
+            binds.push(Box::new(to.timestamp_millis()));
+        }
+        (clauses, binds)
+    }
+}
+
+/// The chain's identity and progress (`audit_chain`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditChainHead {
+    pub chain_id: String,
+    pub head_id: i64,
+    pub head_hash: String,
+    /// Newest id sealed into an archive segment.
+    pub archived_id: i64,
+}
+
+const ENTRY_COLUMNS: &str =
+    "id, ts, action, user_name, target, ip, ua, bucket, path, prev_hash, hash";
+
+fn entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<StoredAuditEntry> {
+    let ts: i64 = row.get(1)?;
+    Ok(StoredAuditEntry {
+        id: row.get(0)?,
+        timestamp: Utc.timestamp_millis_opt(ts).single().unwrap_or_default(),
+        action: row.get(2)?,
+        user: row.get(3)?,
+        target: row.get(4)?,
+        ip: row.get(5)?,
+        ua: row.get(6)?,
+        bucket: row.get(7)?,
+        path: row.get(8)?,
+        prev_hash: row.get(9)?,
+        hash: row.get(10)?,
+    })
+}
+
+impl ConfigDb {
+    /// Append `entries` to the chain in one transaction, starting the chain
+    /// on first use. Returns the id of the last appended entry.
+    pub fn audit_append(&self, entries: &[AuditEntry]) -> Result<i64, ConfigDbError> {
+        let tx = self.conn.unchecked_transaction()?;
+        let head = tx
+            .query_row(
+                "SELECT head_id, head_hash FROM audit_chain WHERE singleton = 1",
+                [],
+                |r| Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        let (mut id, mut prev_hash) = match head {
+            Some(head) => head,
+            None => {
+                let chain_id = uuid::Uuid::new_v4().simple().to_string();
+                tx.execute(
+                    "INSERT INTO audit_chain (singleton, chain_id, head_id, head_hash)
+                     VALUES (1, ?, 0, ?)",
+                    params![chain_id, GENESIS_HASH],
+                )?;
+                (0, GENESIS_HASH.to_string())
+            }
+        };
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO audit_log
+                    (id, ts, action, user_name, target, ip, ua, bucket, path, prev_hash, hash)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )?;
+            for entry in entries {
+                id += 1;
+                let ts = entry.timestamp.timestamp_millis();
+                let hash = chain_hash(&prev_hash, id, ts, &entry_fields(entry));
+                stmt.execute(params![
+                    id,
+                    ts,
+                    entry.action,
+                    entry.user,
+                    entry.target,
+                    entry.ip,
+                    entry.ua,
+                    entry.bucket,
+                    entry.path,
+                    prev_hash,
+                    hash
+                ])?;
+                prev_hash = hash;
+            }
+        }
+        tx.execute(
+            "UPDATE audit_chain SET head_id = ?, head_hash = ? WHERE singleton = 1",
+            params![id, prev_hash],
+        )?;
+        tx.commit()?;
+        Ok(id)
+    }
+
+    /// The chain head, `None` before the first entry.
+    pub fn audit_chain_head(&self) -> Result<Option<AuditChainHead>, ConfigDbError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT chain_id, head_id, head_hash, archived_id
+                 FROM audit_chain WHERE singleton = 1",
+                [],
+                |r| {
+                    Ok(AuditChainHead {
+                        chain_id: r.get(0)?,
+                        head_id: r.get(1)?,
+                        head_hash: r.get(2)?,
+                        archived_id: r.get(3)?,
+                    })
+                },
+            )
+            .optional()?)
+    }
+
+    /// Up to `limit` matching entries, newest first, older than `before`
+    /// (an id) when set.
+    pub fn audit_query(
+        &self,
+        filter: &AuditFilter,
+        before: Option<i64>,
+        limit: usize,
+    ) -> Result<Vec<StoredAuditEntry>, ConfigDbError> {
+        let (mut clauses, mut binds) = filter.conditions();
+        if let Some(before) = before {
+            clauses.push("id < ?");
+            binds.push(Box::new(before));
+        }
+        self.audit_select(&clauses, binds, "DESC", limit)
+    }
+
+    /// Up to `limit` matching entries, oldest first, newer than `after` (an
+    /// id). Pages through an export or a verification.
+    pub fn audit_scan(
+        &self,
+        filter: &AuditFilter,
+        after: i64,
+        limit: usize,
+    ) -> Result<Vec<StoredAuditEntry>, ConfigDbError> {
+        let (mut clauses, mut binds) = filter.conditions();
+        clauses.push("id > ?");
+        binds.push(Box::new(after));
+        self.audit_select(&clauses, binds, "ASC", limit)
+    }
+
+    fn audit_select(
+        &self,
+        clauses: &[&str],
+        mut binds: Vec<Box<dyn ToSql>>,
+        order: &str,
+        limit: usize,
+    ) -> Result<Vec<StoredAuditEntry>, ConfigDbError> {
+        let where_sql = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT {ENTRY_COLUMNS} FROM audit_log {where_sql} ORDER BY id {order} LIMIT ?"
+        );
+        binds.push(Box::new(limit as i64));
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(binds.iter().map(|b| b.as_ref())),
+            entry_from_row,
+        )?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Up to `limit` entries not yet sealed into an archive segment, oldest
+    /// first.
+    pub fn audit_unsealed(&self, limit: usize) -> Result<Vec<StoredAuditEntry>, ConfigDbError> {
+        let sealed = self.audit_chain_head()?.map_or(0, |h| h.archived_id);
+        self.audit_scan(&AuditFilter::default(), sealed, limit)
+    }
+
+    /// Record that entries up to `id` are in an archive segment.
+    pub fn audit_mark_sealed(&self, id: i64) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "UPDATE audit_chain SET archived_id = max(archived_id, ?) WHERE singleton = 1",
+            params![id],
+        )?;
+        Ok(())
+    }
+
+    /// Delete up to `limit` of the oldest entries that are either older than
+    /// `expire_before` (unix millis) or sealed and older than
+    /// `sealed_before`. Only a prefix of the chain is deleted, and never the
+    /// newest entry. Returns the number of rows deleted.
+    pub fn audit_prune(
+        &self,
+        expire_before: i64,
+        sealed_before: Option<i64>,
+        limit: usize,
+    ) -> Result<usize, ConfigDbError> {
+        let Some(head) = self.audit_chain_head()? else {
+            return Ok(0);
+        };
+        let expired: Option<i64> = self.conn.query_row(
+            "SELECT max(id) FROM audit_log WHERE ts < ?",
+            params![expire_before],
+            |r| r.get(0),
+        )?;
+        let sealed: Option<i64> = match sealed_before {
+            Some(before) => self.conn.query_row(
+                "SELECT max(id) FROM audit_log WHERE ts < ? AND id <= ?",
+                params![before, head.archived_id],
+                |r| r.get(0),
+            )?,
+            None => None,
+        };
+        let cutoff = expired.max(sealed).unwrap_or(0).min(head.head_id - 1);
+        if cutoff <= 0 {
+            return Ok(0);
+        }
+        Ok(self.conn.execute(
+            "DELETE FROM audit_log WHERE id IN
+                (SELECT id FROM audit_log WHERE id <= ? ORDER BY id LIMIT ?)",
+            params![cutoff, limit as i64],
+        )?)
+    }
+}
+
+/// Outcome of [`verify_chain`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AuditVerification {
+    pub valid: bool,
+    pub chain_id: Option<String>,
+    /// Entries checked.
+    pub entries: u64,
+    pub first_id: Option<i64>,
+    pub last_id: Option<i64>,
+    /// `prev_hash` of the oldest remaining entry: [`GENESIS_HASH`] unless
+    /// retention has pruned the start of the chain.
+    pub anchor_hash: Option<String>,
+    pub head_id: i64,
+    pub head_hash: Option<String>,
+    pub archived_id: i64,
+    /// First entry whose link or hash doesn't check out.
+    pub broken_at: Option<i64>,
+    pub problem: Option<String>,
+    /// Entries the writer dropped, per the [`DROPPED_ACTION`] entries checked.
+    pub dropped: u64,
+}
+
+/// Recomputes a chain fed to it oldest first.
+#[derive(Debug, Default)]
+pub struct ChainVerifier {
+    previous: Option<(i64, String)>,
+    report: AuditVerification,
+}
+
+impl ChainVerifier {
+    /// Check the next entry. Returns `false` once the chain is broken.
+    pub fn feed(&mut self, entry: &StoredAuditEntry) -> bool {
+        if self.report.problem.is_some() {
+            return false;
+        }
+        let problem = match &self.previous {
+            None => {
+                self.report.first_id = Some(entry.id);
+                self.report.anchor_hash = Some(entry.prev_hash.clone());
+                None
+            }
+            Some((id, _)) if entry.id != id + 1 => {
+                Some(format!("entry {} follows entry {id}", entry.id))
+            }
+            Some((_, hash)) if entry.prev_hash != *hash => Some(format!(
+                "entry {} does not link to its predecessor",
+                entry.id
+            )),
+            Some(_) => None,
+        }
+        .or_else(|| {
+            (entry.expected_hash() != entry.hash)
+                .then(|| format!("entry {} does not match its hash", entry.id))
+        });
+        if let Some(problem) = problem {
+            self.report.broken_at = Some(entry.id);
+            self.report.problem = Some(problem);
+            return false;
+        }
+        self.report.entries += 1;
+        if entry.action == DROPPED_ACTION {
+            self.report.dropped += entry.target.parse::<u64>().unwrap_or(0);
+        }
+        self.report.last_id = Some(entry.id);
+        self.previous = Some((entry.id, entry.hash.clone()));
+        true
+    }
+
+    /// The report, checking the newest entry against the recorded head.
+    pub fn finish(mut self, head: Option<AuditChainHead>) -> AuditVerification {
+        if let Some(head) = head {
+            if self.report.problem.is_none() {
+                match &self.previous {
+                    Some((id, hash)) if *id == head.head_id && *hash == head.head_hash => {}
+                    Some((id, _)) => {
+                        self.report.problem = Some(format!(
+                            "newest entry is {id} but the chain head is {}",
+                            head.head_id
+                        ));
+                    }
+                    None if head.head_id > 0 => {
+                        self.report.problem = Some(format!(
+                            "no entries, but the chain head is {}",
+                            head.head_id
+                        ));
+                    }
+                    None => {}
+                }
+            }
+            self.report.chain_id = Some(head.chain_id);
+            self.report.head_id = head.head_id;
+            self.report.head_hash = Some(head.head_hash);
+            self.report.archived_id = head.archived_id;
+        }
+        self.report.valid = self.report.problem.is_none();
+        self.report
+    }
+}
+
+/// Walk the whole stored chain, a page at a time.
+pub async fn verify_chain(db: &Mutex<ConfigDb>) -> Result<AuditVerification, ConfigDbError> {
+    let mut verifier = ChainVerifier::default();
+    let mut after = 0;
+    loop {
+        let page = db
+            .lock()
+            .await
+            .audit_scan(&AuditFilter::default(), after, VERIFY_PAGE)?;
+        for entry in &page {
+            if !verifier.feed(entry) {
+                break;
+            }
+        }
+        match page.last() {
+            Some(last) if page.len() == VERIFY_PAGE && verifier.report.problem.is_none() => {
+                after = last.id
+            }
+            _ => break,
+        }
+    }
+    let head = db.lock().await.audit_chain_head()?;
+    Ok(verifier.finish(head))
+}
+
+// ── Export formats ──────────────────────────────────────────────────────
+
+/// Header line of a CSV export.
+pub const CSV_HEADER: &str = "id,timestamp,action,user,target,ip,ua,bucket,path,prev_hash,hash\n";
+
+/// One CSV line (with trailing newline). Cells a spreadsheet would run as a
+/// formula are prefixed with `'`.
+pub fn csv_line(e: &StoredAuditEntry) -> String {
+    let timestamp = e
+        .timestamp
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let cells = [
+        e.id.to_string(),
+        timestamp,
+        csv_cell(&e.action),
+        csv_cell(&e.user),
+        csv_cell(&e.target),
+        csv_cell(&e.ip),
+        csv_cell(&e.ua),
+        csv_cell(&e.bucket),
+        csv_cell(&e.path),
+        e.prev_hash.clone(),
+        e.hash.clone(),
+    ];
+    let mut line = cells.join(",");
+    line.push('\n');
+    line
+}
+
+fn csv_cell(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// One JSON Lines line (with trailing newline) — the archive segment format.
+pub fn jsonl_line(e: &StoredAuditEntry) -> String {
+    let mut line = serde_json::to_string(e).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+// ── Writer ──────────────────────────────────────────────────────────────
+
+struct Sink {
+    tx: mpsc::Sender<AuditEntry>,
+    /// Dropped since the writer last recorded a [`DROPPED_ACTION`] entry.
+    unrecorded_drops: Arc<AtomicU64>,
+    dropped: IntCounter,
+}
+
+static SINK: OnceLock<Sink> = OnceLock::new();
+
+/// Hand an entry to the writer, if one runs. Never blocks.
+pub(crate) fn offer(entry: AuditEntry) {
+    if let Some(sink) = SINK.get() {
+        sink.offer(entry);
+    }
+}
+
+impl Sink {
+    fn offer(&self, entry: AuditEntry) {
+        if self.tx.try_send(entry).is_err() {
+            self.unrecorded_drops.fetch_add(1, Ordering::Relaxed);
+            self.dropped.inc();
+        }
+    }
+}
+
+/// Start the writer and route [`crate::audit::audit_log`] entries to it.
+/// Entries are stored only while `advanced.audit.enabled` is set. Once per
+/// process; later calls are no-ops.
+pub fn spawn_writer(config: SharedConfig, db: Arc<Mutex<ConfigDb>>, metrics: Arc<Metrics>) {
+    if SINK.get().is_some() {
+        return;
+    }
+    let _ = SINK.set(writer(config, db, metrics));
+}
+
+fn writer(config: SharedConfig, db: Arc<Mutex<ConfigDb>>, metrics: Arc<Metrics>) -> Sink {
+    let unrecorded_drops = Arc::new(AtomicU64::new(0));
+    let dropped = metrics.audit_entries_total.with_label_values(&["dropped"]);
+    let drops = unrecorded_drops.clone();
+    let tx = spawn_batch_writer(move |batch| {
+        write_entries(
+            config.clone(),
+            db.clone(),
+            metrics.clone(),
+            drops.clone(),
+            batch,
+        )
+    });
+    Sink {
+        tx,
+        unrecorded_drops,
+        dropped,
+    }
+}
+
+/// Append one batch, followed by a [`DROPPED_ACTION`] entry when entries
+/// were dropped since the last one.
+async fn write_entries(
+    config: SharedConfig,
+    db: Arc<Mutex<ConfigDb>>,
+    metrics: Arc<Metrics>,
+    unrecorded_drops: Arc<AtomicU64>,
+    mut batch: Vec<AuditEntry>,
+) {
+    let drops = unrecorded_drops.swap(0, Ordering::Relaxed);
+    if !config.read().await.audit.enabled {
+        return;
+    }
+    let offered = batch.len() as u64;
+    if drops > 0 {
+        warn!("audit writer fell behind: {drops} entries dropped");
+        batch.push(dropped_entry(drops));
+    }
+    let outcome = match db.lock().await.audit_append(&batch) {
+        Ok(_) => "stored",
+        Err(err) => {
+            warn!("failed to store {} audit entries: {}", batch.len(), err);
+            // Record the drops with the next batch instead.
+            unrecorded_drops.fetch_add(drops, Ordering::Relaxed);
+            "failed"
+        }
+    };
+    metrics
+        .audit_entries_total
+        .with_label_values(&[outcome])
+        .inc_by(offered);
+}
+
+fn dropped_entry(count: u64) -> AuditEntry {
+    AuditEntry {
+        timestamp: Utc::now(),
+        action: DROPPED_ACTION.to_string(),
+        user: String::new(),
+        target: count.to_string(),
+        ip: String::new(),
+        ua: String::new(),
+        bucket: String::new(),
+        path: String::new(),
+    }
+}
+
+// ── Archive and retention ───────────────────────────────────────────────
+
+/// Start the task that seals entries into archive segments, sweeps expired
+/// segments and prunes the local table.
+pub fn spawn_archiver(
+    config: SharedConfig,
+    db: Arc<Mutex<ConfigDb>>,
+    metrics: Arc<Metrics>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_seal = Instant::now();
+        let mut last_sweep: Option<Instant> = None;
+        loop {
+            tokio::time::sleep(ARCHIVE_TICK).await;
+            let (audit, sync_bucket, backend) = {
+                let cfg = config.read().await;
+                (
+                    cfg.audit.clone(),
+                    cfg.config_sync_bucket.clone(),
+                    cfg.backend.clone(),
+                )
+            };
+            if !audit.enabled {
+                continue;
+            }
+            let target = audit
+                .archive_target(sync_bucket.as_deref())
+                .map(str::to_string);
+            if let Some(bucket) = &target {
+                let due = last_seal.elapsed() >= audit.rotate_interval_duration();
+                let sweep_due = last_sweep.is_none_or(|t| t.elapsed() >= SWEEP_INTERVAL);
+                if due || sweep_due {
+                    match ConfigDbSync::build_client(&backend).await {
+                        Ok(client) => {
+                            let archive = Archive {
+                                client,
+                                bucket: bucket.clone(),
+                                prefix: audit.archive_prefix.clone(),
+                            };
+                            if due {
+                                seal_segments(&db, &archive, &metrics).await;
+                                last_seal = Instant::now();
+                            }
+                            if sweep_due {
+                                archive.sweep(audit.retention_duration()).await;
+                                last_sweep = Some(Instant::now());
+                            }
+                        }
+                        Err(e) => warn!("audit archive: client build failed: {e}"),
+                    }
+                }
+            }
+            let sealed_retention = target.as_ref().map(|_| audit.local_retention_duration());
+            prune(&db, audit.retention_duration(), sealed_retention).await;
+        }
+    })
+}
+
+/// Where archive segments go.
+struct Archive {
+    client: Client,
+    bucket: String,
+    prefix: String,
+}
+
+impl Archive {
+    fn segment_key(&self, chain_id: &str, first: i64, last: i64) -> String {
+        format!("{}{chain_id}/{first:012}-{last:012}.jsonl", self.prefix)
+    }
+
+    async fn put(&self, key: &str, body: String) -> Result<(), String> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from(body.into_bytes()))
+            .content_type("application/x-ndjson")
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    /// Delete segments last written more than `retention` ago.
+    async fn sweep(&self, retention: Duration) {
+        let cutoff = Utc::now() - chrono::Duration::from_std(retention).unwrap_or_default();
+        let mut continuation: Option<String> = None;
+        let mut deleted = 0usize;
+        loop {
+            let out = match self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(&self.prefix)
+                .set_continuation_token(continuation.take())
+                .send()
+                .await
+            {
+                Ok(out) => out,
+                Err(e) => {
+                    warn!("audit archive: listing {} failed: {e:?}", self.bucket);
+                    return;
+                }
+            };
+            for object in out.contents() {
+                let Some(key) = object.key().filter(|k| k.ends_with(".jsonl")) else {
+                    continue;
+                };
+                let expired = object
+                    .last_modified()
+                    .and_then(|t| Utc.timestamp_millis_opt(t.to_millis().ok()?).single())
+                    .is_some_and(|modified| modified < cutoff);
+                if !expired {
+                    continue;
+                }
+                match self
+                    .client
+                    .delete_object()
+                    .bucket(&self.bucket)
+                    .key(key)
+                    .send()
+                    .await
+                {
+                    Ok(_) => deleted += 1,
+                    Err(e) => warn!("audit archive: deleting {key} failed: {e:?}"),
+                }
+            }
+            match out.next_continuation_token() {
+                Some(token) if out.is_truncated() == Some(true) => {
+                    continuation = Some(token.to_string())
+                }
+                _ => break,
+            }
+        }
+        if deleted > 0 {
+            info!("audit archive: deleted {deleted} expired segment(s)");
+        }
+    }
+}
+
+/// Seal every unsealed entry, [`SEGMENT_MAX_ENTRIES`] per segment.
+async fn seal_segments(db: &Mutex<ConfigDb>, archive: &Archive, metrics: &Metrics) {
+    loop {
+        let (head, entries) = {
+            let db = db.lock().await;
+            match (
+                db.audit_chain_head(),
+                db.audit_unsealed(SEGMENT_MAX_ENTRIES),
+            ) {
+                (Ok(Some(head)), Ok(entries)) => (head, entries),
+                (Ok(None), _) => return,
+                (Err(e), _) | (_, Err(e)) => {
+                    warn!("audit archive: reading unsealed entries failed: {e}");
+                    return;
+                }
+            }
+        };
+        let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+            return;
+        };
+        let key = archive.segment_key(&head.chain_id, first.id, last.id);
+        let body: String = entries.iter().map(jsonl_line).collect();
+        if let Err(e) = archive.put(&key, body).await {
+            warn!("audit archive: uploading {key} failed: {e}");
+            metrics
+                .audit_segments_total
+                .with_label_values(&["failed"])
+                .inc();
+            return;
+        }
+        metrics
+            .audit_segments_total
+            .with_label_values(&["uploaded"])
+            .inc();
+        debug!("audit archive: sealed {} entries into {key}", entries.len());
+        if let Err(e) = db.lock().await.audit_mark_sealed(last.id) {
+            warn!("audit archive: recording {key} failed: {e}");
+            return;
+        }
+        if entries.len() < SEGMENT_MAX_ENTRIES {
+            return;
+        }
+    }
+}
+
+/// Apply retention to the local table, [`PRUNE_BATCH`] rows per lock.
+async fn prune(db: &Mutex<ConfigDb>, retention: Duration, sealed_retention: Option<Duration>) {
+    let now = Utc::now().timestamp_millis();
+    let before = |d: Duration| now.saturating_sub(d.as_millis().min(i64::MAX as u128) as i64);
+    let expire_before = before(retention);
+    let sealed_before = sealed_retention.map(before);
+    loop {
+        match db
+            .lock()
+            .await
+            .audit_prune(expire_before, sealed_before, PRUNE_BATCH)
+        {
+            Ok(n) if n == PRUNE_BATCH => continue,
+            Ok(_) => return,
+            Err(e) => {
+                warn!("audit store prune failed: {e}");
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn entry(action: &str, user: &str, bucket: &str, path: &str, ts_ms: i64) -> AuditEntry {
+        AuditEntry {
+            timestamp: Utc.timestamp_millis_opt(ts_ms).unwrap(),
+            action: action.into(),
+            user: user.into(),
+            target: String::new(),
+            ip: "10.0.0.7".into(),
+            ua: "aws-cli".into(),
+            bucket: bucket.into(),
+            path: path.into(),
+        }
+    }
+
+    fn all(db: &ConfigDb) -> Vec<StoredAuditEntry> {
+        db.audit_scan(&AuditFilter::default(), 0, 1000).unwrap()
+    }
+
+    fn verify(db: &ConfigDb) -> AuditVerification {
+        let mut verifier = ChainVerifier::default();
+        for e in all(db) {
+            verifier.feed(&e);
+        }
+        verifier.finish(db.audit_chain_head().unwrap())
+    }
+
+    #[test]
+    fn appended_entries_form_a_verifiable_chain() {
+        let db = ConfigDb::in_memory("test-pass").unwrap();
+        db.audit_append(&[
+            entry("put_object", "alice", "media", "a.mp4", 1_000),
+            entry("delete_object", "bob", "media", "b.mp4", 2_000),
+        ])
+        .unwrap();
+        assert_eq!(
+            db.audit_append(&[entry("login", "admin", "", "", 3_000)])
+                .unwrap(),
+            3
+        );
+
+        let rows = all(&db);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].prev_hash, GENESIS_HASH);
+        assert_eq!(rows[1].prev_hash, rows[0].hash);
+        assert_eq!(rows[2].prev_hash, rows[1].hash);
+        let report = verify(&db);
+        assert!(report.valid, "{report:?}");
+        assert_eq!((report.entries, report.head_id), (3, 3));
+        assert_eq!(report.anchor_hash.as_deref(), Some(GENESIS_HASH));
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let db = ConfigDb::in_memory("test-pass").unwrap();
+        let batch: Vec<_> = (0..4)
+            .map(|i| entry("put_object", "alice", "media", &format!("k{i}"), i * 1000))
+            .collect();
+        db.audit_append(&batch).unwrap();
+
+        // Updates are refused outright.
+        assert!(db
+            .conn
+            .execute(
+                "UPDATE audit_log SET user_name = 'mallory' WHERE id = 2",
+                []
+            )
+            .is_err());
+
+        // A deleted middle row breaks the link of its successor.
+        db.conn
+            .execute("DELETE FROM audit_log WHERE id = 2", [])
+            .unwrap();
+        let report = verify(&db);
+        assert!(!report.valid);
+        assert_eq!(report.broken_at, Some(3));
+
+        // Rewriting a row (delete + re-insert) breaks its own hash.
+        let db = ConfigDb::in_memory("test-pass").unwrap();
+        db.audit_append(&batch).unwrap();
+        let mut row = all(&db).remove(1);
+        row.user = "mallory".into();
+        db.conn
+            .execute("DELETE FROM audit_log WHERE id = 2", [])
+            .unwrap();
+        db.conn
+            .execute(
+                "INSERT INTO audit_log
+                    (id, ts, action, user_name, target, ip, ua, bucket, path, prev_hash, hash)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    row.id,
+                    row.timestamp.timestamp_millis(),
+                    row.action,
+                    row.user,
+                    row.target,
+                    row.ip,
+                    row.ua,
+                    row.bucket,
+                    row.path,
+                    row.prev_hash,
+                    row.hash
+                ],
+            )
+            .unwrap();
+        assert_eq!(verify(&db).broken_at, Some(2));
+
+        // Dropping the newest row disagrees with the recorded head.
+        let db = ConfigDb::in_memory("test-pass").unwrap();
+        db.audit_append(&batch).unwrap();
+        db.conn
+            .execute("DELETE FROM audit_log WHERE id = 4", [])
+            .unwrap();
+        let report = verify(&db);
+        assert!(!report.valid);
+        assert!(report.problem.unwrap().contains("chain head"));
+    }
+
+    #[test]
+    fn queries_filter_and_page_newest_first() {
+        let db = ConfigDb::in_memory("test-pass").unwrap();
+        db.audit_append(&[
+            entry("put_object", "alice", "media", "videos/a.mp4", 1_000),
+            entry("put_object", "bob", "media", "videos/b.mp4", 2_000),
+            entry("delete_object", "alice", "media", "images/c.png", 3_000),
+            entry("put_object", "alice", "docs", "videos/d.mp4", 4_000),
+        ])
+        .unwrap();
+
+        let by = |filter: AuditFilter, before| -> Vec<i64> {
+            db.audit_query(&filter, before, 10)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.id)
+                .collect()
+        };
+        let alice = AuditFilter {
+            user: Some("alice".into()),
+            ..Default::default()
+        };
+        assert_eq!(by(alice.clone(), None), vec![4, 3, 1]);
+        assert_eq!(by(alice, Some(3)), vec![1]);
+        assert_eq!(
+            by(
+                AuditFilter {
+                    bucket: Some("media".into()),
+                    key: Some("videos/".into()),
+                    ..Default::default()
+                },
+                None
+            ),
+            vec![2, 1]
+        );
+        let window = AuditFilter {
+            action: Some("put_object".into()),
+            from: Some(Utc.timestamp_millis_opt(2_000).unwrap()),
+            to: Some(Utc.timestamp_millis_opt(4_000).unwrap()),
+            user: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(by(window.clone(), None), vec![2]);
+        assert!(window.matches(&entry("put_object", "bob", "media", "x", 2_000)));
+        assert!(!window.matches(&entry("put_object", "bob", "media", "x", 4_000)));
+    }
+
+    #[test]
+    fn prune_keeps_unsealed_entries_and_the_chain_head() {
+        let db = ConfigDb::in_memory("test-pass").unwrap();
+        let batch: Vec<_> = (1..=6)
+            .map(|i| entry("put_object", "alice", "media", "k", i * 1000))
+            .collect();
+        db.audit_append(&batch).unwrap();
+        db.audit_mark_sealed(2).unwrap();
+
+        // Local retention only reaches sealed rows.
+        assert_eq!(db.audit_prune(0, Some(5_500), 100).unwrap(), 2);
+        assert_eq!(all(&db).first().map(|e| e.id), Some(3));
+        assert_eq!(db.audit_unsealed(10).unwrap().len(), 4);
+
+        // Full retention expires anything, except the newest row.
+        assert_eq!(db.audit_prune(i64::MAX, None, 100).unwrap(), 3);
+        let report = verify(&db);
+        assert!(report.valid, "{report:?}");
+        assert_eq!((report.first_id, report.head_id), (Some(6), 6));
+        assert_ne!(report.anchor_hash.as_deref(), Some(GENESIS_HASH));
+
+        // The chain continues across pruning.
+        db.audit_append(&[entry("login", "admin", "", "", 7_000)])
+            .unwrap();
+        assert!(verify(&db).valid);
+    }
+
+    #[test]
+    fn csv_cells_are_quoted_and_defused() {
+        let mut e = StoredAuditEntry {
+            id: 7,
+            timestamp: Utc.timestamp_millis_opt(1_500).unwrap(),
+            action: "put_object".into(),
+            user: "=HYPERLINK(\"x\")".into(),
+            target: String::new(),
+            ip: "10.0.0.7".into(),
+            ua: "curl/8, like that".into(),
+            bucket: "media".into(),
+            path: "a.txt".into(),
+            prev_hash: GENESIS_HASH.into(),
+            hash: String::new(),
+        };
+        e.hash = e.expected_hash();
+        let line = csv_line(&e);
+        assert!(line.starts_with("7,1970-01-01T00:00:01.500Z,put_object,"));
+        assert!(line.contains(",\"'=HYPERLINK(\"\"x\"\")\","));
+        assert!(line.contains(",\"curl/8, like that\","));
+        assert!(line.ends_with(&format!("{}\n", e.hash)));
+        let back: StoredAuditEntry = serde_json::from_str(jsonl_line(&e).trim_end()).unwrap();
+        assert_eq!(back, e);
+    }
+
+    #[tokio::test]
+    async fn writer_stores_entries_only_while_enabled() {
+        let config = Config::default().into_shared();
+        let db = Arc::new(Mutex::new(ConfigDb::in_memory("test-pass").unwrap()));
+        let metrics = Arc::new(Metrics::new());
+        let run = |e: AuditEntry| {
+            write_entries(
+                config.clone(),
+                db.clone(),
+                metrics.clone(),
+                Arc::default(),
+                vec![e],
+            )
+        };
+
+        run(entry("login", "admin", "", "", 1_000)).await;
+        assert!(all(&*db.lock().await).is_empty());
+
+        config.write().await.audit.enabled = true;
+        run(entry("put_object", "alice", "media", "a", 2_000)).await;
+        let rows = all(&*db.lock().await);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].user, "alice");
+        assert_eq!(
+            metrics
+                .audit_entries_total
+                .with_label_values(&["stored"])
+                .get(),
+            1
+        );
+        assert!(verify_chain(&db).await.unwrap().valid);
+    }
+
+    #[tokio::test]
+    async fn dropped_entries_are_recorded_in_the_chain() {
+        let config = Config::default().into_shared();
+        config.write().await.audit.enabled = true;
+        let db = Arc::new(Mutex::new(ConfigDb::in_memory("test-pass").unwrap()));
+        let metrics = Arc::new(Metrics::new());
+        let (tx, rx) = mpsc::channel(1);
+        let sink = Sink {
+            tx,
+            unrecorded_drops: Arc::default(),
+            dropped: metrics.audit_entries_total.with_label_values(&["dropped"]),
+        };
+        for ts in 1..=3 {
+            sink.offer(entry("put_object", "alice", "media", "a", ts * 1_000));
+        }
+        let Sink {
+            tx,
+            unrecorded_drops,
+            ..
+        } = sink;
+        drop(tx);
+        crate::background::drain_batches(rx, |batch| {
+            write_entries(
+                config.clone(),
+                db.clone(),
+                metrics.clone(),
+                unrecorded_drops.clone(),
+                batch,
+            )
+        })
+        .await;
+
+        let rows = all(&*db.lock().await);
+        let actions: Vec<_> = rows.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, ["put_object", DROPPED_ACTION]);
+        assert_eq!(rows[1].target, "2");
+        let report = verify_chain(&db).await.unwrap();
+        assert!(report.valid);
+        assert_eq!(report.dropped, 2);
+        assert_eq!(
+            metrics
+                .audit_entries_total
+                .with_label_values(&["dropped"])
+                .get(),
+            2
+        );
+    }
+}
@@ -7,10 +7,49 @@
 //! - [`RunLease`] — per-run leader-lease knobs, shared by the replication +
 //!   lifecycle workers (their heartbeat loops still differ — replication has a
 //!   lock-acquire retry lifecycle doesn't — but the struct is identical).
+//! - [`spawn_batch_writer`] — bounded channel plus one task writing what it
+//!   receives in batches (used by the audit store and access events).
 
+use std::future::Future;
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tracing::warn;
 
+/// Records buffered between request paths and a batch writer.
+pub(crate) const WRITER_CHANNEL_CAPACITY: usize = 4096;
+/// Most records a batch writer hands to `write` at once.
+pub(crate) const WRITE_BATCH: usize = 256;
+
+/// Start a task that calls `write` with up to [`WRITE_BATCH`] records at a
+/// time, and return the sending side of its channel. The task ends once
+/// every sender is gone. Must be called inside a Tokio runtime.
+pub(crate) fn spawn_batch_writer<T, F, Fut>(write: F) -> mpsc::Sender<T>
+where
+    T: Send + 'static,
+    F: FnMut(Vec<T>) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(WRITER_CHANNEL_CAPACITY);
+    tokio::spawn(drain_batches(rx, write));
+    tx
+}
+
+/// The loop behind [`spawn_batch_writer`].
+pub(crate) async fn drain_batches<T, F, Fut>(mut rx: mpsc::Receiver<T>, mut write: F)
+where
+    F: FnMut(Vec<T>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut batch = Vec::with_capacity(WRITE_BATCH);
+    while rx.recv_many(&mut batch, WRITE_BATCH).await > 0 {
+        write(std::mem::replace(
+            &mut batch,
+            Vec::with_capacity(WRITE_BATCH),
+        ))
+        .await;
+    }
+}
+
 /// Leader-lease knobs threaded through a leased background run.
 #[derive(Debug, Clone)]
 pub struct RunLease {
@@ -72,6 +72,131 @@ pub struct BucketPolicyConfig {
     /// Reads are unaffected.
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub replication_target_only: bool,
+
+    /// Serve reads from a replica bucket while this bucket's backend is
+    /// down. See [`ReadFallbackConfig`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_fallback: Option<ReadFallbackConfig>,
+
+    /// Keep a synchronous second copy of this bucket on another backend.
+    /// See [`MirrorConfig`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<MirrorConfig>,
+
+    /// Spread this bucket over an ordered set of backends, filling each up
+    /// to its capacity before spilling onto the next. See [`SpanConfig`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SpanConfig>,
+
+    /// Race slow passthrough GETs against the bucket's second copy. See
+    /// [`HedgeConfig`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hedge: Option<HedgeConfig>,
+}
+
+/// Capacity-based spanning: one virtual bucket stored across several
+/// backends. Each deltaspace (key prefix) lives on exactly one member, so
+/// references and their deltas stay co-located; a NEW deltaspace is placed
+/// on the first member whose usage is below its `capacity_bytes`. Existing
+/// deltaspaces keep receiving writes where they are.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct SpanConfig {
+    /// Members in fill order. Replaces the bucket's `backend`.
+    pub backends: Vec<SpanMember>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct SpanMember {
+    pub backend: String,
+
+    /// Real bucket name on this backend. When `None`, the bucket's `alias`,
+    /// else its name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+
+    /// Bytes this member may hold before new deltaspaces spill onto the
+    /// next one. `None` = unlimited (only sensible on the last member).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity_bytes: Option<u64>,
+}
+
+/// Write-through mirror: every write to the bucket goes to its own backend
+/// AND to `backend` concurrently, and is acknowledged once `write_quorum`
+/// copies accepted it. A copy that missed a write is queued for repair
+/// through the event outbox; reads are served from the healthier copy.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct MirrorConfig {
+    /// Backend holding the second copy. Must differ from the bucket's own.
+    pub backend: String,
+
+    /// Real bucket name on the mirror backend. When `None`, the same real
+    /// name as the primary copy (the bucket's `alias`, else its name).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+
+    /// Copies that must accept a write before it is acknowledged: `2`
+    /// (both, default) or `1` (either — the other is repaired later).
+    #[serde(default = "default_mirror_write_quorum")]
+    pub write_quorum: u8,
+}
+
+fn default_mirror_write_quorum() -> u8 {
+    2
+}
+
+/// Hedged reads: when the bucket's backend has not produced the first byte
+/// of a passthrough GET within the threshold, the same read is sent to the
+/// bucket's second copy — its `mirror`, else its `read_fallback` replica —
+/// and whichever answers first is served. The second copy is only served
+/// when its stored SHA-256 and size match the object being read, so an
+/// asynchronously replicated replica that is behind never answers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct HedgeConfig {
+    /// Fixed threshold in milliseconds. When `None`, the p95 of the
+    /// backend's recent time-to-first-byte (bounded below by
+    /// `DGP_HEDGE_MIN_DELAY_MS`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_ms: Option<u64>,
+}
+
+/// Disaster-recovery read path for a bucket whose copy is kept on another
+/// backend by a replication rule. When the health gate marks the bucket's
+/// backend unhealthy — or, with `on_error`, when a read fails with a backend
+/// error — GET/HEAD/LIST are answered from `bucket` and flagged with the
+/// `x-deltaglider-stale-read` response header.
+///
+/// Keys are read verbatim from the replica, so the replicating rule must not
+/// rewrite prefixes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct ReadFallbackConfig {
+    /// Replica bucket (a virtual bucket name on this proxy).
+    pub bucket: String,
+
+    /// Also fall back when a read against a backend the gate still considers
+    /// healthy fails with a backend (transport / 5xx) error. Default `true`.
+    #[serde(default = "default_true")]
+    pub on_error: bool,
+
+    /// What happens to client writes while the primary is down.
+    #[serde(default)]
+    pub writes: FallbackWrites,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Write policy for a bucket whose reads are served from its replica.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum FallbackWrites {
+    /// Writes keep answering the health gate's 503 (default).
+    #[default]
+    Block,
+    /// PutObject and DeleteObject land on the replica and the key is queued;
+    /// once the primary recovers the queue is replayed onto it. Multipart,
+    /// copy and batch delete stay blocked.
+    Queue,
 }
 
 /// Why a `public_prefix` was rejected during validation. Carries enough
@@ -384,6 +509,28 @@ impl BucketPolicyRegistry {
             .is_some_and(|p| p.replication_target_only)
     }
 
+    /// The bucket's read-fallback policy, if any.
+    pub fn read_fallback(&self, bucket: &str) -> Option<&ReadFallbackConfig> {
+        self.policies
+            .get(bucket)
+            .and_then(|p| p.read_fallback.as_ref())
+    }
+
+    /// The bucket's write-through mirror, if any.
+    pub fn mirror(&self, bucket: &str) -> Option<&MirrorConfig> {
+        self.policies.get(bucket).and_then(|p| p.mirror.as_ref())
+    }
+
+    /// The bucket's capacity span, if any.
+    pub fn span(&self, bucket: &str) -> Option<&SpanConfig> {
+        self.policies.get(bucket).and_then(|p| p.span.as_ref())
+    }
+
+    /// The bucket's hedged-read policy, if any.
+    pub fn hedge(&self, bucket: &str) -> Option<&HedgeConfig> {
+        self.policies.get(bucket).and_then(|p| p.hedge.as_ref())
+    }
+
     /// Reason (if any) a client write to `bucket` must be blocked to preserve
     /// the `replication_target_only` single-writer guarantee. `None` = allowed.
     ///
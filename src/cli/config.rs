@@ -519,7 +519,7 @@ async fn apply_async(input: &str, opts: AdminClientOpts) -> Result<i32, CliError
     Ok(EXIT_OK)
 }
 
-/// `admission trace --method M --path P [--authenticated] [--query Q] [--server URL]`
+/// `admission trace --method M --path P [--authenticated] [--query Q] [--access-key K] [--server URL]`
 ///
 /// Dry-run a synthetic request through the server's admission chain via
 /// `POST /_/api/admin/config/trace`. Emits the decision as JSON on stdout
@@ -529,6 +529,7 @@ pub struct TraceArgs {
     pub path: String,
     pub authenticated: bool,
     pub query: Option<String>,
+    pub access_key: Option<String>,
 }
 
 pub fn admission_trace(args: TraceArgs, opts: AdminClientOpts) -> i32 {
@@ -564,6 +565,11 @@ async fn admission_trace_async(args: TraceArgs, opts: AdminClientOpts) -> Result
             .expect("just constructed as an object")
             .insert("query".to_string(), serde_json::Value::String(q));
     }
+    if let Some(k) = args.access_key {
+        body.as_object_mut()
+            .expect("just constructed as an object")
+            .insert("access_key".to_string(), serde_json::Value::String(k));
+    }
 
     let resp = client
         .post(&url)
@@ -588,6 +594,102 @@ async fn admission_trace_async(args: TraceArgs, opts: AdminClientOpts) -> Result
     Ok(())
 }
 
+/// `iam simulate (--user U | --group G | --permissions-file F) --action A --bucket B [--key K] ...`
+///
+/// Ask the server's policy simulator via `POST /_/api/admin/iam/simulate`
+/// whether a request would be allowed and which rules decided it. Emits
+/// the result as JSON on stdout (`| jq -e .allowed` to branch on it).
+pub struct SimulateArgs {
+    pub user: Option<String>,
+    pub group: Option<String>,
+    /// JSON or YAML file holding a list of permission rules.
+    pub permissions_file: Option<String>,
+    pub action: String,
+    pub bucket: String,
+    pub key: Option<String>,
+    pub source_ip: Option<String>,
+    pub prefix: Option<String>,
+    pub secure_transport: bool,
+    pub governance_bypass: bool,
+}
+
+pub fn iam_simulate(args: SimulateArgs, opts: AdminClientOpts) -> i32 {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("error: failed to start tokio runtime: {e}");
+            return EXIT_IO;
+        }
+    };
+    match runtime.block_on(iam_simulate_async(args, opts)) {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("{}", e.user_message());
+            e.exit_code()
+        }
+    }
+}
+
+async fn iam_simulate_async(args: SimulateArgs, opts: AdminClientOpts) -> Result<(), CliError> {
+    // Read the rule file before logging in so a typo fails fast.
+    let permissions = match &args.permissions_file {
+        Some(path) => {
+            let raw = std::fs::read_to_string(path)
+                .map_err(|e| CliError::Io(format!("read {path}: {e}")))?;
+            // YAML is a superset of JSON, so one parser takes both.
+            let rules: serde_json::Value = serde_yaml::from_str(&raw)
+                .map_err(|e| CliError::Rejected(format!("{path}: {e}")))?;
+            Some(rules)
+        }
+        None => None,
+    };
+
+    let client = admin_login(&opts).await?;
+    let url = format!(
+        "{}/_/api/admin/iam/simulate",
+        opts.server.trim_end_matches('/')
+    );
+    let mut body = serde_json::json!({
+        "action": args.action,
+        "bucket": args.bucket,
+        "key": args.key.unwrap_or_default(),
+        "secure_transport": args.secure_transport,
+        "governance_bypass": args.governance_bypass,
+    });
+    let fields = body.as_object_mut().expect("just constructed as an object");
+    for (name, value) in [
+        ("user", args.user.map(serde_json::Value::String)),
+        ("group", args.group.map(serde_json::Value::String)),
+        ("permissions", permissions),
+        ("source_ip", args.source_ip.map(serde_json::Value::String)),
+        ("prefix", args.prefix.map(serde_json::Value::String)),
+    ] {
+        if let Some(value) = value {
+            fields.insert(name.to_string(), value);
+        }
+    }
+
+    let resp = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| CliError::Http(format!("POST {url}: {e}")))?;
+    let status = resp.status();
+    let body: serde_json::Value = resp.json().await.map_err(|e| {
+        CliError::Http(format!(
+            "response from {url} was not JSON (status {status}): {e}"
+        ))
+    })?;
+    if !status.is_success() {
+        return Err(CliError::Rejected(format!("{status}: {body}")));
+    }
+
+    let pretty = serde_json::to_string_pretty(&body).unwrap_or_else(|_| body.to_string());
+    println!("{pretty}");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
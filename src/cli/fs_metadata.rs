@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `deltaglider_proxy storage migrate-metadata <PATH> --to xattr|sidecar
+//! [--dry-run] [--json]`
+//!
+//! Move a filesystem backend's object metadata between the `user.dg.metadata`
+//! xattr and `.dgmeta.*` sidecar files, e.g. before relocating the data
+//! directory onto a filesystem without xattr support. Works on the directory
+//! directly — stop the proxy first, then switch the backend's `metadata:`
+//! setting to match. Safe to re-run after an interruption.
+
+use crate::cli::config as cli_exit;
+use crate::config::FilesystemMetadataMode;
+use crate::storage::{migrate_filesystem_metadata, MetadataMigrationReport};
+use std::path::PathBuf;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct MigrateMetadataArgs {
+    /// The filesystem backend's data directory (its `path:`).
+    #[arg(value_name = "PATH")]
+    pub path: PathBuf,
+
+    /// Metadata mode to migrate to.
+    #[arg(long, value_name = "MODE", value_parser = ["xattr", "sidecar"])]
+    pub to: String,
+
+    /// Count what would be migrated without changing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Emit the result as a single JSON object on stdout.
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub async fn run(args: MigrateMetadataArgs) -> i32 {
+    let to = match args.to.as_str() {
+        "sidecar" => FilesystemMetadataMode::Sidecar,
+        _ => FilesystemMetadataMode::Xattr,
+    };
+    let report = match migrate_filesystem_metadata(&args.path, to, args.dry_run).await {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}: {e}", args.path.display());
+            return cli_exit::EXIT_IO;
+        }
+    };
+    emit(&report, args.json);
+    if report.errors.is_empty() {
+        cli_exit::EXIT_OK
+    } else if report.migrated > 0 {
+        cli_exit::EXIT_PARTIAL
+    } else {
+        cli_exit::EXIT_IO
+    }
+}
+
+fn emit(report: &MetadataMigrationReport, json: bool) {
+    if json {
+        match serde_json::to_string(report) {
+            Ok(s) => println!("{s}"),
+            Err(e) => eprintln!("error: serialise migration report: {e}"),
+        }
+        return;
+    }
+    println!("Target mode:     {}", report.to);
+    println!("Migrated:        {}", report.migrated);
+    println!("Already there:   {}", report.already_migrated);
+    println!("Unmanaged:       {}", report.unmanaged);
+    println!("Orphans removed: {}", report.orphans_removed);
+    println!("Errors:          {}", report.errors.len());
+    if report.dry_run {
+        println!("(dry run — nothing changed)");
+    }
+    if !report.errors.is_empty() {
+        eprintln!("\nFirst {} errors:", report.errors.len().min(10));
+        for line in report.errors.iter().take(10) {
+            eprintln!("  - {line}");
+        }
+    }
+}
@@ -7,7 +7,8 @@
 //! Each subcommand is a small dispatcher that borrows logic from the library
 //! crate. The `config` and `admission` families live in `config.rs`; the
 //! AWS-CLI-shaped S3 commands (`cp`, `ls`, `rm`, `stats`, `verify`) each get
-//! their own module so help-text and argument shapes don't collide.
+//! their own module so help-text and argument shapes don't collide. The
+//! offline `storage` tooling works on a backend's data directory directly.
 
 pub mod aws_creds;
 pub mod bucket_acl;
@@ -15,6 +16,7 @@ pub mod config;
 pub mod cp;
 pub mod engine_factory;
 pub mod filter;
+pub mod fs_metadata;
 pub mod ls;
 pub mod migrate;
 pub mod purge;
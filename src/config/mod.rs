@@ -328,6 +328,24 @@ pub const ENV_VAR_REGISTRY: &[EnvVarEntry] = &[
         example: "4",
         category: "Security",
     },
+    EnvVarEntry {
+        name: "DGP_MAX_SESSIONS",
+        description: "Max concurrent admin sessions, across all instances with a shared store (default: 10)",
+        example: "10",
+        category: "Security",
+    },
+    EnvVarEntry {
+        name: "DGP_SESSION_STORE",
+        description: "Admin session store: shared (coordination bucket, default when config_sync_bucket is set) or memory (node-local)",
+        example: "shared",
+        category: "Security",
+    },
+    EnvVarEntry {
+        name: "DGP_SESSION_RECHECK_SECS",
+        description: "Seconds a cached shared session is trusted before re-reading the store (default: 30)",
+        example: "30",
+        category: "Security",
+    },
     EnvVarEntry {
         name: "DGP_MAX_MULTIPART_UPLOADS",
         description: "Max concurrent multipart uploads (default: 1000)",
@@ -364,6 +382,18 @@ pub const ENV_VAR_REGISTRY: &[EnvVarEntry] = &[
         example: "24",
         category: "Server",
     },
+    EnvVarEntry {
+        name: "DGP_MULTIPART_STAGED_TTL_HOURS",
+        description: "Idle TTL in hours for multipart uploads staged on a filesystem backend or tracked by the durable registry (default: 24)",
+        example: "24",
+        category: "Server",
+    },
+    EnvVarEntry {
+        name: "DGP_MULTIPART_STORE",
+        description: "Multipart upload state: durable (registry in the coordination bucket, or the config DB single-instance; default when config_sync_bucket is set) or memory (node-local, lost on restart)",
+        example: "durable",
+        category: "Server",
+    },
     EnvVarEntry {
         name: "DGP_AUDIT_RING_SIZE",
         description: "In-memory audit-log ring buffer capacity (default: 500)",
@@ -717,6 +747,17 @@ pub struct Config {
     #[serde(default)]
     pub event_delivery: crate::config_sections::EventDeliveryConfig,
 
+    /// Read-through local-disk object cache for slow backends. Disabled by
+    /// default. See [`crate::config_sections::DiskCacheConfig`].
+    #[serde(default)]
+    pub disk_cache: crate::config_sections::DiskCacheConfig,
+
+    /// Durable, hash-chained audit store. Disabled by default; audit entries
+    /// then reach only the log and the in-memory ring. See
+    /// [`crate::config_sections::AuditConfig`].
+    #[serde(default)]
+    pub audit: crate::config_sections::AuditConfig,
+
     /// Operator-authored admission blocks.
     ///
     /// Parsed from `admission.blocks:` in the sectioned YAML OR from
@@ -1102,6 +1143,13 @@ pub enum BackendConfig {
     Filesystem {
         /// Directory for data storage
         path: PathBuf,
+
+        /// Where object metadata is kept: `xattr` (default) or `sidecar`
+        /// files for filesystems without extended attributes. Switch an
+        /// existing directory with `deltaglider_proxy storage
+        /// migrate-metadata`.
+        #[serde(default, skip_serializing_if = "FilesystemMetadataMode::is_default")]
+        metadata: FilesystemMetadataMode,
     },
 
     /// S3 backend for production use
@@ -1139,10 +1187,106 @@ pub enum BackendConfig {
         #[serde(default, skip_serializing_if = "is_false")]
         allow_local: bool,
     },
+
+    /// Azure Blob Storage backend (Shared Key auth). Buckets map 1:1 to
+    /// blob containers.
+    #[serde(rename = "azure")]
+    AzureBlob {
+        /// Storage account name
+        account: String,
+
+        /// Base64 storage account key (Shared Key auth)
+        #[serde(default)]
+        account_key: Option<String>,
+
+        /// Blob service endpoint. Defaults to
+        /// `https://<account>.blob.core.windows.net`; set it for Azurite
+        /// (`http://127.0.0.1:10000/devstoreaccount1`) or sovereign clouds.
+        #[serde(default)]
+        endpoint: Option<String>,
+
+        /// Permit `http://` and private-IP / localhost endpoints (Azurite).
+        /// Same semantics and env fallback as the S3 backend's `allow_local`.
+        #[serde(default, skip_serializing_if = "is_false")]
+        allow_local: bool,
+    },
+
+    /// Google Cloud Storage backend (JSON API). Buckets map 1:1 to GCS
+    /// buckets.
+    Gcs {
+        /// Project that owns the buckets (used to create and list them).
+        /// Defaults to the service-account key's `project_id`.
+        #[serde(default)]
+        project: Option<String>,
+
+        /// Service-account JSON key (the key file's contents). When omitted,
+        /// tokens come from the GCE/GKE metadata server — or, with a custom
+        /// `endpoint`, requests go unauthenticated (fake-gcs-server).
+        #[serde(default)]
+        service_account_key: Option<String>,
+
+        /// JSON API endpoint. Defaults to `https://storage.googleapis.com`;
+        /// set it for fake-gcs-server (`http://127.0.0.1:4443`).
+        #[serde(default)]
+        endpoint: Option<String>,
+
+        /// Permit `http://` and private-IP / localhost endpoints
+        /// (fake-gcs-server). Same semantics and env fallback as the S3
+        /// backend's `allow_local`.
+        #[serde(default, skip_serializing_if = "is_false")]
+        allow_local: bool,
+    },
+
+    /// Reed-Solomon erasure coding across other named backends. Every
+    /// stored blob is split into `data_shards` + `parity_shards` shards, one
+    /// per entry of `shards`; any `data_shards` of them rebuild it. Only
+    /// valid under `storage.backends`.
+    Erasure {
+        /// Shards a blob is split into (k). Any k shards rebuild it.
+        data_shards: u8,
+
+        /// Extra parity shards (m): how many shard backends may be lost.
+        parity_shards: u8,
+
+        /// Named backends holding shards 0..k+m, in shard order. Each must
+        /// be a plain backend used by nothing else.
+        shards: Vec<String>,
+
+        /// Shards that must be written before a PUT is acknowledged.
+        /// Defaults to every shard; any value from k+1 keeps the write
+        /// readable while still tolerating a down shard backend.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        write_quorum: Option<u8>,
+    },
+}
+
+/// How a filesystem backend stores object metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FilesystemMetadataMode {
+    /// `user.dg.metadata` extended attribute on each file.
+    #[default]
+    Xattr,
+    /// Hidden `.dgmeta.<name>` file next to each file, for filesystems
+    /// without xattr support.
+    Sidecar,
+}
+
+impl FilesystemMetadataMode {
+    pub fn is_default(&self) -> bool {
+        matches!(self, FilesystemMetadataMode::Xattr)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilesystemMetadataMode::Xattr => "xattr",
+            FilesystemMetadataMode::Sidecar => "sidecar",
+        }
+    }
 }
 
 #[inline]
-fn is_false(b: &bool) -> bool {
+pub(crate) fn is_false(b: &bool) -> bool {
     !*b
 }
 
@@ -1178,11 +1322,11 @@ pub(crate) fn default_metadata_cache_mb() -> usize {
     50
 }
 
-fn default_region() -> String {
+pub(crate) fn default_region() -> String {
     "us-east-1".to_string()
 }
 
-fn default_force_path_style() -> bool {
+pub(crate) fn default_force_path_style() -> bool {
     true
 }
 
@@ -1194,6 +1338,7 @@ impl Default for BackendConfig {
     fn default() -> Self {
         BackendConfig::Filesystem {
             path: PathBuf::from("./data"),
+            metadata: FilesystemMetadataMode::default(),
         }
     }
 }
@@ -1226,6 +1371,8 @@ impl Default for Config {
             replication: crate::config_sections::ReplicationConfig::default(),
             lifecycle: crate::config_sections::LifecycleConfig::default(),
             event_delivery: crate::config_sections::EventDeliveryConfig::default(),
+            disk_cache: crate::config_sections::DiskCacheConfig::default(),
+            audit: crate::config_sections::AuditConfig::default(),
             admission_blocks: Vec::new(),
             iam_mode: crate::config_sections::IamMode::default(),
             iam_users: Vec::new(),
@@ -1502,6 +1649,7 @@ impl Config {
         } else if let Ok(dir) = std::env::var("DGP_DATA_DIR") {
             self.backend = BackendConfig::Filesystem {
                 path: PathBuf::from(dir),
+                metadata: FilesystemMetadataMode::default(),
             };
         }
 
@@ -1813,6 +1961,87 @@ impl Config {
                 }
             }
         }
+        // read_fallback coherence: the replica only helps if it lives on a
+        // DIFFERENT backend and something actually keeps it current.
+        for (bucket, policy) in &self.buckets {
+            let Some(fallback) = &policy.read_fallback else {
+                continue;
+            };
+            let replica = &fallback.bucket;
+            let backend_of = |name: &str| {
+                self.buckets
+                    .iter()
+                    .find(|(b, _)| eq_bucket(b, name))
+                    .and_then(|(_, p)| p.backend.clone())
+                    .unwrap_or_else(|| "default".to_string())
+            };
+            if backend_of(bucket) == backend_of(replica) {
+                warnings.push(format!(
+                    "bucket '{bucket}' falls back to '{replica}' on the same backend — an outage \
+                     takes both down. Route '{replica}' to another backend."
+                ));
+            }
+            let replicated = self.replication.rules.iter().any(|r| {
+                eq_bucket(&r.source.bucket, bucket)
+                    && eq_bucket(&r.destination.bucket, replica)
+                    && crate::replication::normalize_prefix(&r.source.prefix)
+                        == crate::replication::normalize_prefix(&r.destination.prefix)
+            });
+            if !replicated {
+                warnings.push(format!(
+                    "bucket '{bucket}' falls back to '{replica}' but no replication rule copies \
+                     '{bucket}' into '{replica}' under the same keys — fallback reads would serve \
+                     whatever happens to be there."
+                ));
+            }
+            if self
+                .buckets
+                .iter()
+                .any(|(b, p)| eq_bucket(b, replica) && p.read_fallback.is_some())
+            {
+                warnings.push(format!(
+                    "bucket '{bucket}' falls back to '{replica}', which has its own read_fallback — \
+                     fallbacks do not chain."
+                ));
+            }
+        }
+        // Erasure write quorum of exactly k: a write acknowledged by k shard
+        // backends has no redundancy until repair runs.
+        for named in &self.backends {
+            if let BackendConfig::Erasure {
+                data_shards,
+                write_quorum: Some(wq),
+                ..
+            } = &named.backend
+            {
+                if wq == data_shards {
+                    warnings.push(format!(
+                        "erasure backend '{}' acknowledges writes on {wq} shards — a write \
+                         that reaches only data_shards backends has no redundancy until the \
+                         next repair. Raise write_quorum above data_shards.",
+                        named.name
+                    ));
+                }
+            }
+        }
+        // Span fill order: a member without a capacity never fills, so the
+        // members after it would never receive a deltaspace.
+        for (bucket, policy) in &self.buckets {
+            let Some(span) = &policy.span else {
+                continue;
+            };
+            let members = &span.backends;
+            if let Some(pos) = members.iter().position(|m| m.capacity_bytes.is_none()) {
+                if pos + 1 < members.len() {
+                    warnings.push(format!(
+                        "bucket '{bucket}' spans '{}' without a capacity_bytes ahead of other \
+                         members — new deltaspaces never spill past it. Set a capacity or move \
+                         it last.",
+                        members[pos].backend
+                    ));
+                }
+            }
+        }
         // Aliasing hole: a marked bucket's single-writer guarantee applies to
         // its REAL (backend, bucket) storage; an unmarked second virtual name
         // resolving to the same real location reopens client writes to it.
@@ -1886,18 +2115,26 @@ impl Config {
                                   backend: &BackendConfig,
                                   enc: &BackendEncryptionConfig,
                                   warnings: &mut Vec<String>| {
-            if matches!(backend, BackendConfig::Filesystem { .. })
-                && matches!(
+            let non_s3_kind = match backend {
+                BackendConfig::Filesystem { .. } => Some("filesystem"),
+                BackendConfig::AzureBlob { .. } => Some("azure"),
+                BackendConfig::Gcs { .. } => Some("gcs"),
+                BackendConfig::Erasure { .. } => Some("erasure"),
+                BackendConfig::S3 { .. } => None,
+            };
+            if let Some(kind) = non_s3_kind.filter(|_| {
+                matches!(
                     enc,
                     BackendEncryptionConfig::SseKms { .. } | BackendEncryptionConfig::SseS3 { .. }
                 )
-            {
+            }) {
                 warnings.push(format!(
-                    "backend '{}' uses a native S3 encryption mode ({}) on a filesystem \
+                    "backend '{}' uses a native S3 encryption mode ({}) on a {} \
                      backend — native modes require S3. Change mode to 'aes256-gcm-proxy' \
                      or 'none'.",
                     label,
-                    enc.mode_tag()
+                    enc.mode_tag(),
+                    kind
                 ));
             }
             if let BackendEncryptionConfig::Aes256GcmProxy {
@@ -2018,6 +2255,13 @@ impl Config {
         warnings.extend(crate::config_sections::validate_event_delivery(
             &self.event_delivery,
         ));
+        warnings.extend(crate::config_sections::validate_disk_cache(
+            &self.disk_cache,
+        ));
+        warnings.extend(crate::config_sections::validate_audit(
+            &self.audit,
+            self.config_sync_bucket.as_deref(),
+        ));
 
         // Cross-field advisories — "this combination is suspicious" checks that a
         // single field can't reveal (rate-limit/trust-proxy collapse, stale IAM
@@ -2047,7 +2291,17 @@ impl Config {
     ///     silently fall through to the default backend and 404/misroute
     ///     (the beshu-b2 incident);
     ///   * duplicate backend names — ambiguous routing, "first entry wins"
-    ///     is a silent coin-flip after a config edit.
+    ///     is a silent coin-flip after a config edit;
+    ///   * a `read_fallback` naming the bucket itself — the fallback would
+    ///     re-enter the dead backend;
+    ///   * a `mirror` on an undefined backend, on the bucket's own backend,
+    ///     or with a `write_quorum` other than 1 or 2;
+    ///   * a `span` with fewer than two members, an undefined or repeated
+    ///     member backend, or combined with `backend` / `mirror`;
+    ///   * an `erasure` backend whose shard list doesn't match its layout,
+    ///     or whose shard backends are undefined, repeated, erasure-coded,
+    ///     encrypted, or reachable some other way (routes, mirrors, spans,
+    ///     the default backend, another erasure backend).
     ///
     /// Enforced at boot (refuse to start) and at every apply / section-PUT
     /// (reject the transition). Pure read — never mutates.
@@ -2062,6 +2316,7 @@ impl Config {
                 ));
             }
         }
+        errors.extend(self.check_erasure_layouts());
         for (bucket, policy) in &self.buckets {
             if let Some(ref backend) = policy.backend {
                 // "default" is the SYNTHESIZED name of the singleton
@@ -2076,6 +2331,201 @@ impl Config {
                     ));
                 }
             }
+            if let Some(fallback) = &policy.read_fallback {
+                if fallback.bucket.eq_ignore_ascii_case(bucket) {
+                    errors.push(format!(
+                        "bucket '{bucket}' names itself as its read_fallback bucket — \
+                         point it at the replica bucket"
+                    ));
+                }
+            }
+            if let Some(mirror) = &policy.mirror {
+                let primary = policy
+                    .backend
+                    .clone()
+                    .or_else(|| self.default_backend.clone())
+                    .or_else(|| self.backends.first().map(|b| b.name.clone()))
+                    .unwrap_or_else(|| "default".to_string());
+                if !self.backends.iter().any(|b| b.name == mirror.backend) {
+                    errors.push(format!(
+                        "bucket '{bucket}' mirrors to undefined backend '{}' — mirrors need \
+                         a named backend under storage.backends (available: {:?})",
+                        mirror.backend,
+                        self.backends.iter().map(|b| &b.name).collect::<Vec<_>>()
+                    ));
+                } else if mirror.backend == primary {
+                    errors.push(format!(
+                        "bucket '{bucket}' mirrors to its own backend '{primary}' — \
+                         the mirror must live on a different backend"
+                    ));
+                }
+                if !(1..=2).contains(&mirror.write_quorum) {
+                    errors.push(format!(
+                        "bucket '{bucket}' has mirror write_quorum {} — use 2 (both copies) \
+                         or 1 (either copy)",
+                        mirror.write_quorum
+                    ));
+                }
+            }
+            if let Some(span) = &policy.span {
+                if span.backends.len() < 2 {
+                    errors.push(format!(
+                        "bucket '{bucket}' spans {} backend(s) — a span needs at least two; \
+                         use `backend` to route a bucket to a single backend",
+                        span.backends.len()
+                    ));
+                }
+                if policy.backend.is_some() || policy.mirror.is_some() {
+                    errors.push(format!(
+                        "bucket '{bucket}' combines `span` with `backend`/`mirror` — the span \
+                         members are the bucket's backends; remove the other field"
+                    ));
+                }
+                let mut members = std::collections::HashSet::new();
+                for member in &span.backends {
+                    if !self.backends.iter().any(|b| b.name == member.backend) {
+                        errors.push(format!(
+                            "bucket '{bucket}' spans undefined backend '{}' — span members \
+                             need a named backend under storage.backends (available: {:?})",
+                            member.backend,
+                            self.backends.iter().map(|b| &b.name).collect::<Vec<_>>()
+                        ));
+                    } else if !members.insert(member.backend.as_str()) {
+                        errors.push(format!(
+                            "bucket '{bucket}' spans backend '{}' twice",
+                            member.backend
+                        ));
+                    }
+                }
+            }
+            if policy.hedge.is_some() {
+                if policy.mirror.is_none() && policy.read_fallback.is_none() {
+                    errors.push(format!(
+                        "bucket '{bucket}' hedges reads but has no second copy — hedging \
+                         needs a `mirror` or a `read_fallback` replica to race against"
+                    ));
+                } else if policy.span.is_some() {
+                    errors.push(format!(
+                        "bucket '{bucket}' combines `hedge` with `span` — spanned buckets \
+                         can't be hedged"
+                    ));
+                } else if self.backends.is_empty() {
+                    errors.push(format!(
+                        "bucket '{bucket}' hedges reads on a single-backend setup — hedging \
+                         needs the copies on named backends under storage.backends"
+                    ));
+                }
+            }
+        }
+        errors
+    }
+
+    /// The `erasure` part of [`Self::check_fatal`]. Shard backends belong to
+    /// their erasure backend alone: anything else writing to them would
+    /// store bytes that aren't shards.
+    fn check_erasure_layouts(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if matches!(self.backend, BackendConfig::Erasure { .. }) && self.backends.is_empty() {
+            errors.push(
+                "storage.backend is an erasure backend — erasure shards are named backends; \
+                 define them and the erasure backend under storage.backends"
+                    .to_string(),
+            );
+        }
+        let mut owners: std::collections::HashMap<&str, &str> = std::collections::HashMap::new();
+        for named in &self.backends {
+            let BackendConfig::Erasure {
+                data_shards,
+                parity_shards,
+                shards,
+                write_quorum,
+            } = &named.backend
+            else {
+                continue;
+            };
+            let name = named.name.as_str();
+            let (k, m) = (usize::from(*data_shards), usize::from(*parity_shards));
+            if k == 0 || m == 0 || k + m > 255 {
+                errors.push(format!(
+                    "erasure backend '{name}' has layout {k}+{m} — data_shards and \
+                     parity_shards must be at least 1, and at most 255 shards in total"
+                ));
+            }
+            if shards.len() != k + m {
+                errors.push(format!(
+                    "erasure backend '{name}' lists {} shard backends for {k}+{m} shards — \
+                     list exactly data_shards + parity_shards backends",
+                    shards.len()
+                ));
+            }
+            if let Some(wq) = write_quorum {
+                if !(k..=k + m).contains(&usize::from(*wq)) {
+                    errors.push(format!(
+                        "erasure backend '{name}' has write_quorum {wq} — use {k}..={}",
+                        k + m
+                    ));
+                }
+            }
+            for shard in shards {
+                let Some(member) = self.backends.iter().find(|b| &b.name == shard) else {
+                    errors.push(format!(
+                        "erasure backend '{name}' uses undefined shard backend '{shard}' \
+                         (available: {:?})",
+                        self.backends.iter().map(|b| &b.name).collect::<Vec<_>>()
+                    ));
+                    continue;
+                };
+                if let Some(owner) = owners.insert(shard, name) {
+                    errors.push(if owner == name {
+                        format!("erasure backend '{name}' uses shard backend '{shard}' twice")
+                    } else {
+                        format!(
+                            "shard backend '{shard}' is used by erasure backends '{owner}' \
+                             and '{name}' — each shard backend belongs to one"
+                        )
+                    });
+                }
+                if matches!(member.backend, BackendConfig::Erasure { .. }) {
+                    errors.push(format!(
+                        "erasure backend '{name}' uses erasure backend '{shard}' as a shard — \
+                         erasure backends don't nest"
+                    ));
+                }
+                if !matches!(member.encryption, BackendEncryptionConfig::None { .. }) {
+                    errors.push(format!(
+                        "shard backend '{shard}' of erasure backend '{name}' is encrypted — \
+                         set encryption on '{name}' instead; shard backends must use mode none"
+                    ));
+                }
+            }
+        }
+        if owners.is_empty() {
+            return errors;
+        }
+        let mut shard_use = |what: String, backend: &str| {
+            if let Some(owner) = owners.get(backend) {
+                errors.push(format!(
+                    "{what} uses '{backend}', a shard backend of erasure backend '{owner}' — \
+                     use '{owner}' instead"
+                ));
+            }
+        };
+        let default = self
+            .default_backend
+            .as_deref()
+            .or_else(|| self.backends.first().map(|b| b.name.as_str()))
+            .unwrap_or_default();
+        shard_use("the default backend".to_string(), default);
+        for (bucket, policy) in &self.buckets {
+            if let Some(backend) = &policy.backend {
+                shard_use(format!("bucket '{bucket}'"), backend);
+            }
+            if let Some(mirror) = &policy.mirror {
+                shard_use(format!("bucket '{bucket}' mirror"), &mirror.backend);
+            }
+            for member in policy.span.iter().flat_map(|s| &s.backends) {
+                shard_use(format!("bucket '{bucket}' span"), &member.backend);
+            }
         }
         errors
     }
@@ -2325,11 +2775,11 @@ impl Config {
             counts: &std::collections::HashMap<String, u32>,
         ) {
             match v {
-                serde_yaml::Value::String(s) => {
-                    if counts.get(s.as_str()).copied().unwrap_or(0) == 1 {
-                        if let Some(reference) = inverse.get(s.as_str()) {
-                            *s = reference.clone();
-                        }
+                serde_yaml::Value::String(s)
+                    if counts.get(s.as_str()).copied().unwrap_or(0) == 1 =>
+                {
+                    if let Some(reference) = inverse.get(s.as_str()) {
+                        *s = reference.clone();
                     }
                 }
                 serde_yaml::Value::Sequence(seq) => {
@@ -2545,19 +2995,52 @@ impl Config {
             clear_unless_ref(access_key_id);
             clear_unless_ref(secret_access_key);
         }
+        if let BackendConfig::AzureBlob {
+            ref mut account_key,
+            ..
+        } = export.backend
+        {
+            clear_unless_ref(account_key);
+        }
+        if let BackendConfig::Gcs {
+            ref mut service_account_key,
+            ..
+        } = export.backend
+        {
+            clear_unless_ref(service_account_key);
+        }
         for named in &mut export.backends {
-            if let BackendConfig::S3 {
-                ref mut access_key_id,
-                ref mut secret_access_key,
-                ..
-            } = named.backend
-            {
-                clear_unless_ref(access_key_id);
-                clear_unless_ref(secret_access_key);
+            match named.backend {
+                BackendConfig::S3 {
+                    ref mut access_key_id,
+                    ref mut secret_access_key,
+                    ..
+                } => {
+                    clear_unless_ref(access_key_id);
+                    clear_unless_ref(secret_access_key);
+                }
+                BackendConfig::AzureBlob {
+                    ref mut account_key,
+                    ..
+                } => clear_unless_ref(account_key),
+                BackendConfig::Gcs {
+                    ref mut service_account_key,
+                    ..
+                } => clear_unless_ref(service_account_key),
+                BackendConfig::Filesystem { .. } | BackendConfig::Erasure { .. } => {}
             }
         }
         clear_unless_ref(&mut export.access_key_id);
         clear_unless_ref(&mut export.secret_access_key);
+        // External replication endpoints carry SigV4 pairs like backends do.
+        for rule in &mut export.replication.rules {
+            for side in [&mut rule.source, &mut rule.destination] {
+                if let Some(ext) = side.external.as_mut() {
+                    clear_unless_ref(&mut ext.access_key_id);
+                    clear_unless_ref(&mut ext.secret_access_key);
+                }
+            }
+        }
         // Webhook header values may carry bearer tokens. Mask the VALUE but keep
         // the KEY so the GUI shows which headers exist; the section-PUT preserve
         // path restores any value left as this sentinel. Endpoint URLs are left
@@ -2568,6 +3051,24 @@ impl Config {
                 *value = REDACTED_SENTINEL.to_string();
             }
         }
+        // Webhook signing secrets: mask per endpoint, keeping the key.
+        for signing in export.event_delivery.webhook_signing.values_mut() {
+            if !is_env_ref(&signing.secret) {
+                signing.secret = REDACTED_SENTINEL.to_string();
+            }
+            if let Some(prev) = signing.previous_secret.as_mut() {
+                if !is_env_ref(prev) {
+                    *prev = REDACTED_SENTINEL.to_string();
+                }
+            }
+        }
+        // Broker sink URLs with user-info carry the broker password: mask the
+        // whole URL, preserved (by sink name) on an untouched round-trip.
+        for sink in export.event_delivery.sinks.iter_mut() {
+            if !is_env_ref(&sink.url) && crate::event_sinks::url_has_credentials(&sink.url) {
+                sink.url = REDACTED_SENTINEL.to_string();
+            }
+        }
         // Slack bot token is a secret too — mask it (keep Some so the GUI shows a
         // token IS configured), preserved on an untouched round-trip.
         if export
@@ -2600,6 +3101,30 @@ impl Config {
                 }
             }
         }
+        // Named endpoints: the same rules per endpoint — header values and
+        // signing secrets are masked, and so is a Slack-format URL.
+        for endpoint in export.event_delivery.endpoints.iter_mut() {
+            for value in endpoint.headers.values_mut() {
+                if !is_env_ref(value) {
+                    *value = REDACTED_SENTINEL.to_string();
+                }
+            }
+            if let Some(signing) = endpoint.signing.as_mut() {
+                if !is_env_ref(&signing.secret) {
+                    signing.secret = REDACTED_SENTINEL.to_string();
+                }
+                if let Some(prev) = signing.previous_secret.as_mut() {
+                    if !is_env_ref(prev) {
+                        *prev = REDACTED_SENTINEL.to_string();
+                    }
+                }
+            }
+            if endpoint.format == crate::config_sections::EventDeliveryFormat::Slack
+                && !is_env_ref(&endpoint.url)
+            {
+                endpoint.url = REDACTED_SENTINEL.to_string();
+            }
+        }
         export
     }
 
@@ -2695,10 +3220,8 @@ fn is_whole_env_ref(s: &str) -> bool {
 /// [`Config::to_canonical_yaml_for_persist`].
 fn escape_dollar_for_persist(v: &mut serde_yaml::Value) {
     match v {
-        serde_yaml::Value::String(s) => {
-            if !is_whole_env_ref(s) && s.contains('$') {
-                *s = s.replace('$', "$$");
-            }
+        serde_yaml::Value::String(s) if !is_whole_env_ref(s) && s.contains('$') => {
+            *s = s.replace('$', "$$");
         }
         serde_yaml::Value::Sequence(seq) => seq.iter_mut().for_each(escape_dollar_for_persist),
         serde_yaml::Value::Mapping(map) => {
@@ -2992,12 +3515,17 @@ mod tests {
             "DGP_LOG_RING_SIZE",                     // logs::ring_capacity()
             "DGP_LOG_RING_LEVEL",                    // logs::ring_min_level()
             "DGP_SESSION_TTL_HOURS",                 // session::default_session_ttl()
+            "DGP_MAX_SESSIONS",                      // session::default_max_sessions()
+            "DGP_SESSION_STORE",                     // startup::build_session_store()
+            "DGP_SESSION_RECHECK_SECS",              // session::default_recheck()
             "DGP_MAX_MULTIPART_UPLOADS",             // multipart::default_max_uploads()
             "DGP_MULTIPART_SWEEP_INTERVAL_SECS",     // main multipart sweeper cadence
             "DGP_MULTIPART_SWEEP_MAX_AGE_SECS",      // main multipart sweeper max-age cutoff
             "DGP_MULTIPART_COMPLETING_TIMEOUT_SECS", // main multipart Completing timeout
             "DGP_MAX_TOTAL_MULTIPART_BYTES",         // multipart::max_total_multipart_bytes()
             "DGP_MULTIPART_IDLE_TTL_HOURS",          // multipart::idle_ttl_hours()
+            "DGP_MULTIPART_STAGED_TTL_HOURS",        // main staged multipart janitor
+            "DGP_MULTIPART_STORE",                   // startup::build_upload_registry()
             "DGP_AUDIT_RING_SIZE",                   // audit::ring capacity
             "DGP_CLOCK_SKEW_SECONDS",                // api::auth + startup replay cache
             "DGP_MAX_CONCURRENT_REQUESTS",           // startup::build_s3_router()
@@ -3176,7 +3704,7 @@ backend:
         assert_eq!(config.listen_addr.port(), 8080);
         assert_eq!(config.max_delta_ratio, 0.3);
         match config.backend {
-            BackendConfig::Filesystem { path } => {
+            BackendConfig::Filesystem { path, .. } => {
                 assert_eq!(path, PathBuf::from("/var/lib/deltaglider_proxy"));
             }
             _ => panic!("Expected filesystem backend"),
@@ -3609,6 +4137,7 @@ storage:
                 name: "local".into(),
                 backend: BackendConfig::Filesystem {
                     path: "/tmp/x".into(),
+                    metadata: Default::default(),
                 },
                 encryption: BackendEncryptionConfig::SseKms {
                     kms_key_id: "arn:aws:kms:...".into(),
@@ -3639,6 +4168,7 @@ storage:
                 name: "unconfigured-xyz-42".into(),
                 backend: BackendConfig::Filesystem {
                     path: "/tmp/x".into(),
+                    metadata: Default::default(),
                 },
                 encryption: BackendEncryptionConfig::Aes256GcmProxy {
                     key: None,
@@ -3716,6 +4246,287 @@ storage:
         );
     }
 
+    #[test]
+    fn test_span_unlimited_member_must_be_last() {
+        let warnings = check_yaml(
+            r#"
+storage:
+  backends:
+    - { name: nas, type: filesystem, path: /tmp/nas }
+    - { name: cloud, type: filesystem, path: /tmp/cloud }
+  buckets:
+    archive:
+      span:
+        backends: [{ backend: nas }, { backend: cloud }]
+"#,
+        );
+        assert!(
+            warnings.iter().any(|w| w.contains("never spill past it")),
+            "{warnings:?}"
+        );
+
+        let warnings = check_yaml(
+            r#"
+storage:
+  backends:
+    - { name: nas, type: filesystem, path: /tmp/nas }
+    - { name: cloud, type: filesystem, path: /tmp/cloud }
+  buckets:
+    archive:
+      span:
+        backends: [{ backend: nas, capacity_bytes: 1000 }, { backend: cloud }]
+"#,
+        );
+        assert!(
+            !warnings.iter().any(|w| w.contains("spill")),
+            "{warnings:?}"
+        );
+    }
+
+    #[test]
+    fn test_read_fallback_coherence() {
+        // Same backend + no rule feeding the replica → both advisories.
+        let warnings = check_yaml(
+            r#"
+storage:
+  buckets:
+    releases:
+      read_fallback: { bucket: releases-dr }
+"#,
+        );
+        assert!(
+            warnings.iter().any(|w| w.contains("same backend")),
+            "{warnings:?}"
+        );
+        assert!(
+            warnings.iter().any(|w| w.contains("no replication rule")),
+            "{warnings:?}"
+        );
+
+        // Replica on another backend, fed by a rule → silent.
+        let warnings = check_yaml(
+            r#"
+storage:
+  backends:
+    - name: dr
+      type: filesystem
+      path: /tmp/dr
+  buckets:
+    releases:
+      read_fallback: { bucket: releases-dr, writes: queue }
+    releases-dr: { backend: dr, replication_target_only: true }
+  replication:
+    rules:
+      - name: dr-copy
+        source: { bucket: releases }
+        destination: { bucket: releases-dr }
+"#,
+        );
+        assert!(
+            !warnings.iter().any(|w| w.contains("falls back")),
+            "{warnings:?}"
+        );
+
+        // Self-fallback is fatal.
+        let cfg = Config::from_yaml_str(
+            r#"
+storage:
+  buckets:
+    releases:
+      read_fallback: { bucket: Releases }
+"#,
+        )
+        .expect("parses");
+        let errors = cfg.check_fatal();
+        assert!(
+            errors.iter().any(|e| e.contains("names itself")),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_check_fatal_mirror_targets() {
+        let fatal = |yaml: &str| Config::from_yaml_str(yaml).expect("parses").check_fatal();
+        let backends = r#"
+storage:
+  default_backend: a
+  backends:
+    - { name: a, type: filesystem, path: /tmp/a }
+    - { name: b, type: filesystem, path: /tmp/b }
+  buckets:
+"#;
+        let ok = fatal(&format!(
+            "{backends}    critical: {{ mirror: {{ backend: b }} }}\n"
+        ));
+        assert!(ok.is_empty(), "{ok:?}");
+
+        let own = fatal(&format!(
+            "{backends}    critical: {{ mirror: {{ backend: a }} }}\n"
+        ));
+        assert!(own.iter().any(|e| e.contains("its own backend")), "{own:?}");
+
+        let undefined = fatal(&format!(
+            "{backends}    critical: {{ backend: b, mirror: {{ backend: c }} }}\n"
+        ));
+        assert!(
+            undefined
+                .iter()
+                .any(|e| e.contains("undefined backend 'c'")),
+            "{undefined:?}"
+        );
+
+        let quorum = fatal(&format!(
+            "{backends}    critical: {{ mirror: {{ backend: b, write_quorum: 3 }} }}\n"
+        ));
+        assert!(
+            quorum.iter().any(|e| e.contains("write_quorum 3")),
+            "{quorum:?}"
+        );
+    }
+
+    #[test]
+    fn test_check_fatal_span_members() {
+        let fatal = |yaml: &str| Config::from_yaml_str(yaml).expect("parses").check_fatal();
+        let backends = r#"
+storage:
+  backends:
+    - { name: nas, type: filesystem, path: /tmp/nas }
+    - { name: cloud, type: filesystem, path: /tmp/cloud }
+  buckets:
+"#;
+        let ok = fatal(&format!(
+            "{backends}    archive: {{ span: {{ backends: [{{ backend: nas, capacity_bytes: 100 }}, {{ backend: cloud }}] }} }}\n"
+        ));
+        assert!(ok.is_empty(), "{ok:?}");
+
+        let single = fatal(&format!(
+            "{backends}    archive: {{ span: {{ backends: [{{ backend: nas }}] }} }}\n"
+        ));
+        assert!(
+            single.iter().any(|e| e.contains("at least two")),
+            "{single:?}"
+        );
+
+        let twice = fatal(&format!(
+            "{backends}    archive: {{ span: {{ backends: [{{ backend: nas }}, {{ backend: nas }}] }} }}\n"
+        ));
+        assert!(twice.iter().any(|e| e.contains("twice")), "{twice:?}");
+
+        let mixed = fatal(&format!(
+            "{backends}    archive: {{ backend: nas, span: {{ backends: [{{ backend: nas }}, {{ backend: ghost }}] }} }}\n"
+        ));
+        assert!(mixed.iter().any(|e| e.contains("combines")), "{mixed:?}");
+        assert!(
+            mixed
+                .iter()
+                .any(|e| e.contains("undefined backend 'ghost'")),
+            "{mixed:?}"
+        );
+    }
+
+    #[test]
+    fn test_check_fatal_hedge_needs_a_second_copy() {
+        let fatal = |yaml: &str| Config::from_yaml_str(yaml).expect("parses").check_fatal();
+        let backends = r#"
+storage:
+  default_backend: a
+  backends:
+    - { name: a, type: filesystem, path: /tmp/a }
+    - { name: b, type: filesystem, path: /tmp/b }
+  buckets:
+"#;
+        let mirrored = fatal(&format!(
+            "{backends}    ci: {{ mirror: {{ backend: b }}, hedge: {{}} }}\n"
+        ));
+        assert!(mirrored.is_empty(), "{mirrored:?}");
+
+        let replica = fatal(&format!(
+            "{backends}    ci: {{ read_fallback: {{ bucket: ci-dr }}, hedge: {{ after_ms: 200 }} }}\n"
+        ));
+        assert!(replica.is_empty(), "{replica:?}");
+
+        let alone = fatal(&format!("{backends}    ci: {{ hedge: {{}} }}\n"));
+        assert!(
+            alone.iter().any(|e| e.contains("no second copy")),
+            "{alone:?}"
+        );
+
+        let spanned = fatal(&format!(
+            "{backends}    ci: {{ read_fallback: {{ bucket: ci-dr }}, hedge: {{}}, span: {{ backends: [{{ backend: a }}, {{ backend: b }}] }} }}\n"
+        ));
+        assert!(
+            spanned.iter().any(|e| e.contains("`hedge` with `span`")),
+            "{spanned:?}"
+        );
+    }
+
+    #[test]
+    fn test_check_fatal_erasure_layouts() {
+        let parsed = |yaml: &str| Config::from_yaml_str(yaml).expect("parses");
+        let shards = r#"
+storage:
+  default_backend: ec
+  backends:
+    - { name: d1, type: filesystem, path: /tmp/d1 }
+    - { name: d2, type: filesystem, path: /tmp/d2 }
+    - { name: p1, type: filesystem, path: /tmp/p1 }
+"#;
+        let ok = parsed(&format!(
+            "{shards}    - {{ name: ec, type: erasure, data_shards: 2, parity_shards: 1, shards: [d1, d2, p1] }}\n"
+        ))
+        .check_fatal();
+        assert!(ok.is_empty(), "{ok:?}");
+
+        let short = parsed(&format!(
+            "{shards}    - {{ name: ec, type: erasure, data_shards: 2, parity_shards: 2, shards: [d1, d2, p1], write_quorum: 1 }}\n"
+        ))
+        .check_fatal();
+        assert!(
+            short.iter().any(|e| e.contains("3 shard backends")),
+            "{short:?}"
+        );
+        assert!(
+            short.iter().any(|e| e.contains("write_quorum 1")),
+            "{short:?}"
+        );
+
+        let reused = parsed(&format!(
+            "{shards}    - {{ name: ec, type: erasure, data_shards: 2, parity_shards: 1, shards: [d1, d1, ghost] }}\n  buckets:\n    raw: {{ backend: p1 }}\n"
+        ))
+        .check_fatal();
+        assert!(reused.iter().any(|e| e.contains("twice")), "{reused:?}");
+        assert!(
+            reused
+                .iter()
+                .any(|e| e.contains("undefined shard backend 'ghost'")),
+            "{reused:?}"
+        );
+        assert!(
+            !reused.iter().any(|e| e.contains("bucket 'raw'")),
+            "p1 isn't a shard here: {reused:?}"
+        );
+
+        let routed = parsed(&format!(
+            "{shards}    - {{ name: ec, type: erasure, data_shards: 2, parity_shards: 1, shards: [d1, d2, p1] }}\n  buckets:\n    raw: {{ backend: p1 }}\n"
+        ))
+        .check_fatal();
+        assert!(
+            routed
+                .iter()
+                .any(|e| e.contains("bucket 'raw'") && e.contains("shard backend")),
+            "{routed:?}"
+        );
+
+        let mut quorum_k = parsed(&format!(
+            "{shards}    - {{ name: ec, type: erasure, data_shards: 2, parity_shards: 1, shards: [d1, d2, p1], write_quorum: 2 }}\n"
+        ));
+        assert!(quorum_k.check_fatal().is_empty());
+        assert!(
+            quorum_k.check().iter().any(|w| w.contains("no redundancy")),
+            "warns on write_quorum == data_shards"
+        );
+    }
+
     #[test]
     fn test_check_accepts_marker_with_rule_and_public_prefixes() {
         // marker + public_prefixes is COHERENT (read-only published mirror);
@@ -3870,6 +4681,7 @@ storage:
                     name: "a".into(),
                     backend: BackendConfig::Filesystem {
                         path: "/tmp/a".into(),
+                        metadata: Default::default(),
                     },
                     encryption: BackendEncryptionConfig::Aes256GcmProxy {
                         key: Some("K1".into()),
@@ -3882,6 +4694,7 @@ storage:
                     name: "b".into(),
                     backend: BackendConfig::Filesystem {
                         path: "/tmp/b".into(),
+                        metadata: Default::default(),
                     },
                     encryption: BackendEncryptionConfig::Aes256GcmProxy {
                         key: Some("K2".into()),
@@ -3913,6 +4726,7 @@ storage:
                     name: "primary".into(),
                     backend: BackendConfig::Filesystem {
                         path: "/tmp/a".into(),
+                        metadata: Default::default(),
                     },
                     encryption: BackendEncryptionConfig::Aes256GcmProxy {
                         key: Some("SAME".into()),
@@ -3925,6 +4739,7 @@ storage:
                     name: "replica".into(),
                     backend: BackendConfig::Filesystem {
                         path: "/tmp/b".into(),
+                        metadata: Default::default(),
                     },
                     encryption: BackendEncryptionConfig::Aes256GcmProxy {
                         key: Some("SAME".into()),
@@ -3953,6 +4768,7 @@ storage:
                 name: "bad".into(),
                 backend: BackendConfig::Filesystem {
                     path: "/tmp/x".into(),
+                    metadata: Default::default(),
                 },
                 encryption: BackendEncryptionConfig::Aes256GcmProxy {
                     key: Some("K".into()),
@@ -4217,17 +5033,26 @@ storage:
             backends: vec![
                 NamedBackendConfig {
                     name: "shared".into(),
-                    backend: BackendConfig::Filesystem { path: "/a".into() },
+                    backend: BackendConfig::Filesystem {
+                        path: "/a".into(),
+                        metadata: Default::default(),
+                    },
                     encryption: BackendEncryptionConfig::default(),
                 },
                 NamedBackendConfig {
                     name: "unique".into(),
-                    backend: BackendConfig::Filesystem { path: "/b".into() },
+                    backend: BackendConfig::Filesystem {
+                        path: "/b".into(),
+                        metadata: Default::default(),
+                    },
                     encryption: BackendEncryptionConfig::default(),
                 },
                 NamedBackendConfig {
                     name: "shared".into(),
-                    backend: BackendConfig::Filesystem { path: "/c".into() },
+                    backend: BackendConfig::Filesystem {
+                        path: "/c".into(),
+                        metadata: Default::default(),
+                    },
                     encryption: BackendEncryptionConfig::default(),
                 },
             ],
@@ -4248,12 +5073,18 @@ storage:
             backends: vec![
                 NamedBackendConfig {
                     name: "a".into(),
-                    backend: BackendConfig::Filesystem { path: "/a".into() },
+                    backend: BackendConfig::Filesystem {
+                        path: "/a".into(),
+                        metadata: Default::default(),
+                    },
                     encryption: BackendEncryptionConfig::default(),
                 },
                 NamedBackendConfig {
                     name: "b".into(),
-                    backend: BackendConfig::Filesystem { path: "/b".into() },
+                    backend: BackendConfig::Filesystem {
+                        path: "/b".into(),
+                        metadata: Default::default(),
+                    },
                     encryption: BackendEncryptionConfig::default(),
                 },
             ],
@@ -4663,7 +5494,7 @@ storage:
 "#;
         let cfg = Config::from_yaml_str(yaml).unwrap();
         match &cfg.backend {
-            BackendConfig::Filesystem { path } => {
+            BackendConfig::Filesystem { path, .. } => {
                 assert_eq!(path.to_str(), Some("/var/lib/dgp"));
             }
             other => panic!("filesystem shorthand must yield Filesystem backend, got {other:?}"),
@@ -4922,12 +5753,18 @@ admission_blocks:
             backends: vec![
                 NamedBackendConfig {
                     name: "eu-archive".into(),
-                    backend: BackendConfig::Filesystem { path: "/a".into() },
+                    backend: BackendConfig::Filesystem {
+                        path: "/a".into(),
+                        metadata: Default::default(),
+                    },
                     encryption: BackendEncryptionConfig::default(),
                 },
                 NamedBackendConfig {
                     name: "eu.archive".into(),
-                    backend: BackendConfig::Filesystem { path: "/b".into() },
+                    backend: BackendConfig::Filesystem {
+                        path: "/b".into(),
+                        metadata: Default::default(),
+                    },
                     encryption: BackendEncryptionConfig::default(),
                 },
             ],
@@ -4949,12 +5786,18 @@ admission_blocks:
             backends: vec![
                 NamedBackendConfig {
                     name: "one".into(),
-                    backend: BackendConfig::Filesystem { path: "/a".into() },
+                    backend: BackendConfig::Filesystem {
+                        path: "/a".into(),
+                        metadata: Default::default(),
+                    },
                     encryption: BackendEncryptionConfig::default(),
                 },
                 NamedBackendConfig {
                     name: "two".into(),
-                    backend: BackendConfig::Filesystem { path: "/b".into() },
+                    backend: BackendConfig::Filesystem {
+                        path: "/b".into(),
+                        metadata: Default::default(),
+                    },
                     encryption: BackendEncryptionConfig::default(),
                 },
             ],
@@ -4989,6 +5832,7 @@ admission_blocks:
                 name: "b".into(),
                 backend: BackendConfig::Filesystem {
                     path: "/tmp/x".into(),
+                    metadata: Default::default(),
                 },
                 encryption: BackendEncryptionConfig::Aes256GcmProxy {
                     // 64-char hex key — realistic shape.
@@ -34,11 +34,12 @@ pub async fn rebuild_engine_only(
 ) -> Result<(), String> {
     match DynEngine::new(cfg, Some(app.metrics.clone())).await {
         Ok(new_engine) => {
-            // Re-attach the usage counter and cross-instance reference lock —
-            // a rebuild must not drop either.
+            // Re-attach the usage counter, cross-instance reference lock and
+            // multipart registry — a rebuild must not drop any of them.
             let new_engine = new_engine
                 .with_bucket_usage(app.bucket_usage.clone())
-                .with_reference_lock(app.reference_lock.clone());
+                .with_reference_lock(app.reference_lock.clone())
+                .with_upload_registry(app.upload_registry.clone());
             app.engine.store(Arc::new(new_engine));
             tracing::info!("{}", context);
             Ok(())
@@ -25,12 +25,14 @@ pub struct ConfigDb {
 }
 
 /// Schema version — bump when adding migrations.
-const SCHEMA_VERSION: i32 = 24;
+const SCHEMA_VERSION: i32 = 32;
 
 pub(crate) mod auth_providers;
 mod declarative;
 mod groups;
 pub(crate) mod job_store;
+mod service_accounts;
+pub use service_accounts::ServiceAccountUpdate;
 mod users;
 
 /// Compute the path to the IAM config database file.
@@ -810,6 +812,214 @@ impl ConfigDb {
             );
         }
 
+        if version < 25 {
+            // v25: service accounts — child keys of an IAM user, scoped by an
+            // optional inline policy (JSON `Vec<Permission>`; NULL inherits the
+            // parent in full) and an optional expiry (unix seconds). Synced
+            // (IAM_SYNC_TABLES); cascades with the parent user.
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS service_accounts (
+                    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+                    parent_user_id    INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                    name              TEXT NOT NULL,
+                    description       TEXT NOT NULL DEFAULT '',
+                    access_key_id     TEXT NOT NULL UNIQUE,
+                    secret_access_key TEXT NOT NULL,
+                    policy_json       TEXT,
+                    expires_at        INTEGER,
+                    enabled           INTEGER NOT NULL DEFAULT 1,
+                    created_at        TEXT NOT NULL DEFAULT (datetime('now')),
+                    UNIQUE(parent_user_id, name)
+                );
+                CREATE INDEX IF NOT EXISTS idx_service_accounts_parent
+                    ON service_accounts(parent_user_id);",
+            )?;
+            info!(
+                "Migrated config DB schema from v{} to v25 (service_accounts)",
+                version
+            );
+        }
+
+        if version < 26 {
+            // v26: read-fallback write-back queue. One row per key written to a
+            // replica while its primary bucket's backend was down; `generation`
+            // bumps on every re-queue so a drain never drops a newer write.
+            // Node-local (not in IAM_SYNC_TABLES): each instance replays what
+            // it accepted.
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS read_fallback_queue (
+                    bucket      TEXT NOT NULL,
+                    object_key  TEXT NOT NULL,
+                    generation  INTEGER NOT NULL DEFAULT 1,
+                    queued_at   INTEGER NOT NULL,
+                    attempts    INTEGER NOT NULL DEFAULT 0,
+                    last_error  TEXT,
+                    PRIMARY KEY (bucket, object_key)
+                );",
+            )?;
+            info!(
+                "Migrated config DB schema from v{} to v26 (read_fallback_queue)",
+                version
+            );
+        }
+
+        if version < 27 {
+            // v27: durable multipart uploads (`multipart_registry`). One row
+            // per upload the node accepted, one per staged part; the bytes
+            // live on the bucket's backend. Node-local (not in
+            // IAM_SYNC_TABLES): multi-instance deployments keep the registry
+            // in the coordination bucket instead.
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS multipart_uploads (
+                    upload_id     TEXT PRIMARY KEY,
+                    bucket        TEXT NOT NULL,
+                    object_key    TEXT NOT NULL,
+                    content_type  TEXT,
+                    user_metadata TEXT NOT NULL DEFAULT '{}',
+                    initiated     INTEGER NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS multipart_parts (
+                    upload_id     TEXT NOT NULL,
+                    part_number   INTEGER NOT NULL,
+                    md5           TEXT NOT NULL,
+                    size          INTEGER NOT NULL,
+                    last_modified INTEGER NOT NULL,
+                    PRIMARY KEY (upload_id, part_number)
+                );",
+            )?;
+            info!(
+                "Migrated config DB schema from v{} to v27 (multipart_uploads)",
+                version
+            );
+        }
+
+        if version < 28 {
+            // v28: retry state of named event-delivery endpoints
+            // (`event_endpoint_state`). Each endpoint's position in the
+            // outbox is an ordinary listener cursor; this holds the attempt
+            // count and backoff of the event at its head. Per-node, like the
+            // outbox itself.
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS event_endpoint_state (
+                    endpoint        TEXT PRIMARY KEY,
+                    event_id        INTEGER NOT NULL DEFAULT 0,
+                    attempts        INTEGER NOT NULL DEFAULT 0,
+                    next_attempt_at INTEGER,
+                    last_error      TEXT,
+                    skipped         INTEGER NOT NULL DEFAULT 0,
+                    updated_at      INTEGER NOT NULL
+                );",
+            )?;
+            info!(
+                "Migrated config DB schema from v{} to v28 (event_endpoint_state)",
+                version
+            );
+        }
+
+        if version < 29 {
+            // v29: read and security events (`ObjectAccessed`, `AccessDenied`,
+            // `AuthFailed`) share the outbox under their own retention; the
+            // access-event prune walks them by kind.
+            conn.execute_batch(
+                "CREATE INDEX IF NOT EXISTS idx_event_outbox_kind
+                    ON event_outbox(kind, id);",
+            )?;
+            info!(
+                "Migrated config DB schema from v{} to v29 (idx_event_outbox_kind)",
+                version
+            );
+        }
+
+        if version < 30 {
+            // v30: durable audit store (`audit_store`). `audit_log` is an
+            // append-only hash chain: each row's `hash` covers its fields and
+            // the previous row's hash. `audit_chain` holds the chain head and
+            // how far the archive has sealed it. Per-node, like the outbox.
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS audit_log (
+                    id        INTEGER PRIMARY KEY,
+                    ts        INTEGER NOT NULL,
+                    action    TEXT NOT NULL,
+                    user_name TEXT NOT NULL,
+                    target    TEXT NOT NULL,
+                    ip        TEXT NOT NULL,
+                    ua        TEXT NOT NULL,
+                    bucket    TEXT NOT NULL,
+                    path      TEXT NOT NULL,
+                    prev_hash TEXT NOT NULL,
+                    hash      TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_audit_log_ts ON audit_log(ts);
+                CREATE INDEX IF NOT EXISTS idx_audit_log_user ON audit_log(user_name, id);
+                CREATE INDEX IF NOT EXISTS idx_audit_log_bucket ON audit_log(bucket, id);
+                CREATE TRIGGER IF NOT EXISTS audit_log_append_only
+                    BEFORE UPDATE ON audit_log
+                    BEGIN SELECT RAISE(ABORT, 'audit_log is append-only'); END;
+                CREATE TABLE IF NOT EXISTS audit_chain (
+                    singleton   INTEGER PRIMARY KEY CHECK (singleton = 1),
+                    chain_id    TEXT NOT NULL,
+                    head_id     INTEGER NOT NULL,
+                    head_hash   TEXT NOT NULL,
+                    archived_id INTEGER NOT NULL DEFAULT 0
+                );",
+            )?;
+            info!(
+                "Migrated config DB schema from v{} to v30 (audit_log)",
+                version
+            );
+        }
+
+        if version < 31 {
+            // v31: bidirectional replication (`replication::bidi`).
+            // `replication_sync_state` is each key's last-synced version on
+            // both sides (or a delete tombstone); `replication_conflicts`
+            // holds keys changed on both sides, awaiting manual resolution.
+            // Both keyed by the key relative to the rule's prefixes.
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS replication_sync_state (
+                    rule_name  TEXT NOT NULL,
+                    rel_key    TEXT NOT NULL,
+                    stamp      TEXT NOT NULL,
+                    src_fp     TEXT,
+                    dest_fp    TEXT,
+                    tombstone  INTEGER NOT NULL DEFAULT 0,
+                    updated_at INTEGER NOT NULL,
+                    PRIMARY KEY (rule_name, rel_key)
+                );
+                CREATE TABLE IF NOT EXISTS replication_conflicts (
+                    rule_name   TEXT NOT NULL,
+                    rel_key     TEXT NOT NULL,
+                    kind        TEXT NOT NULL,
+                    src_stamp   TEXT,
+                    dest_stamp  TEXT,
+                    src_size    INTEGER,
+                    dest_size   INTEGER,
+                    detected_at INTEGER NOT NULL,
+                    PRIMARY KEY (rule_name, rel_key)
+                );",
+            )?;
+            info!(
+                "Migrated config DB schema from v{} to v31 (replication_sync_state)",
+                version
+            );
+        }
+
+        if version < 32 {
+            // v32: `multipart_uploads.completing` — the claim a
+            // CompleteMultipartUpload takes on its upload, so exactly one
+            // request assembles it (`UploadRegistry::claim_completion`).
+            add_column_if_missing(
+                conn,
+                "multipart_uploads",
+                "completing",
+                "INTEGER NOT NULL DEFAULT 0",
+            )?;
+            info!(
+                "Migrated config DB schema from v{} to v32 (multipart_uploads.completing)",
+                version
+            );
+        }
+
         conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
         debug!("Config DB schema at version {}", SCHEMA_VERSION);
         Ok(())
@@ -963,6 +1173,7 @@ impl ConfigDb {
         "group_members",       // FK → groups, users
         "group_permissions",   // FK → groups
         "external_identities", // FK → users
+        "service_accounts",    // FK → users
     ];
 
     /// Replace ONLY the IAM-truth tables from a downloaded peer DB, leaving this
@@ -972,7 +1183,7 @@ impl ConfigDb {
     /// `fs::rename`d the whole SQLCipher file, wholesale-clobbering coordination
     /// state that is correct per-node and lease-shared — silently desyncing
     /// `event_outbox` cursors and replication/maintenance leases across instances.
-    /// We now ATTACH the peer file and copy across only the 8 IAM tables in one
+    /// We now ATTACH the peer file and copy across only the IAM tables in one
     /// transaction, so the synced plane (IAM) converges while the coordination
     /// plane stays node-local. No `reopen` is needed — the live connection keeps
     /// its coordination rows.
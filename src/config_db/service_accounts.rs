@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+use rusqlite::{params, OptionalExtension};
+
+use crate::iam::{Permission, ServiceAccount};
+
+use super::{ConfigDb, ConfigDbError};
+
+const SERVICE_ACCOUNT_COLUMNS: &str = "id, parent_user_id, name, description, access_key_id, \
+     secret_access_key, policy_json, expires_at, enabled, created_at";
+
+/// Fields of a service account an update may change. `None` leaves the
+/// field as is; `Some(None)` clears a nullable one.
+#[derive(Debug, Default)]
+pub struct ServiceAccountUpdate {
+    pub description: Option<String>,
+    pub policy: Option<Option<Vec<Permission>>>,
+    pub expires_at: Option<Option<i64>>,
+    pub enabled: Option<bool>,
+}
+
+impl ConfigDb {
+    fn service_account_from_row(row: &rusqlite::Row) -> rusqlite::Result<ServiceAccount> {
+        Ok(ServiceAccount {
+            id: row.get(0)?,
+            parent_user_id: row.get(1)?,
+            name: row.get(2)?,
+            description: row.get::<_, String>(3).unwrap_or_default(),
+            access_key_id: row.get(4)?,
+            secret_access_key: row.get(5)?,
+            // An unparseable stored policy must not widen to "inherit the
+            // parent": fall back to an empty policy, which grants nothing.
+            policy: row
+                .get::<_, Option<String>>(6)?
+                .map(|json| serde_json::from_str(&json).unwrap_or_default()),
+            expires_at: row.get(7)?,
+            enabled: row.get::<_, i32>(8)? != 0,
+            created_at: row.get(9)?,
+        })
+    }
+
+    fn policy_json(policy: Option<&Vec<Permission>>) -> Result<Option<String>, ConfigDbError> {
+        policy
+            .map(|p| serde_json::to_string(p).map_err(|e| ConfigDbError::Other(e.to_string())))
+            .transpose()
+    }
+
+    /// Load every service account (all parents).
+    pub fn load_service_accounts(&self) -> Result<Vec<ServiceAccount>, ConfigDbError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {SERVICE_ACCOUNT_COLUMNS} FROM service_accounts ORDER BY id"
+        ))?;
+        let accounts = stmt
+            .query_map([], Self::service_account_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(accounts)
+    }
+
+    /// Service accounts owned by one user.
+    pub fn list_service_accounts_for_user(
+        &self,
+        parent_user_id: i64,
+    ) -> Result<Vec<ServiceAccount>, ConfigDbError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {SERVICE_ACCOUNT_COLUMNS} FROM service_accounts \
+             WHERE parent_user_id = ?1 ORDER BY id"
+        ))?;
+        let accounts = stmt
+            .query_map(params![parent_user_id], Self::service_account_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(accounts)
+    }
+
+    pub fn get_service_account(&self, id: i64) -> Result<ServiceAccount, ConfigDbError> {
+        self.conn
+            .query_row(
+                &format!("SELECT {SERVICE_ACCOUNT_COLUMNS} FROM service_accounts WHERE id = ?1"),
+                params![id],
+                Self::service_account_from_row,
+            )
+            .optional()?
+            .ok_or_else(|| ConfigDbError::NotFound(format!("service account {id}")))
+    }
+
+    /// Create a service account. The access key must not collide with a
+    /// user's: both live in one SigV4 key space.
+    pub fn create_service_account(
+        &self,
+        account: &ServiceAccount,
+    ) -> Result<ServiceAccount, ConfigDbError> {
+        let tx = self.conn.unchecked_transaction()?;
+        let taken: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE access_key_id = ?1)",
+            params![account.access_key_id],
+            |r| r.get(0),
+        )?;
+        if taken {
+            return Err(ConfigDbError::Other(format!(
+                "access key '{}' is already in use",
+                account.access_key_id
+            )));
+        }
+        tx.execute(
+            "INSERT INTO service_accounts (parent_user_id, name, description, access_key_id, \
+             secret_access_key, policy_json, expires_at, enabled) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                account.parent_user_id,
+                account.name,
+                account.description,
+                account.access_key_id,
+                account.secret_access_key,
+                Self::policy_json(account.policy.as_ref())?,
+                account.expires_at,
+                account.enabled as i32,
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+        tx.commit()?;
+        self.get_service_account(id)
+    }
+
+    pub fn update_service_account(
+        &self,
+        id: i64,
+        update: &ServiceAccountUpdate,
+    ) -> Result<ServiceAccount, ConfigDbError> {
+        let mut account = self.get_service_account(id)?;
+        if let Some(description) = &update.description {
+            account.description = description.clone();
+        }
+        if let Some(policy) = &update.policy {
+            account.policy = policy.clone();
+        }
+        if let Some(expires_at) = update.expires_at {
+            account.expires_at = expires_at;
+        }
+        if let Some(enabled) = update.enabled {
+            account.enabled = enabled;
+        }
+        self.conn.execute(
+            "UPDATE service_accounts SET description = ?1, policy_json = ?2, expires_at = ?3, \
+             enabled = ?4 WHERE id = ?5",
+            params![
+                account.description,
+                Self::policy_json(account.policy.as_ref())?,
+                account.expires_at,
+                account.enabled as i32,
+                id,
+            ],
+        )?;
+        self.get_service_account(id)
+    }
+
+    /// Users and service accounts share one access-key space; refuse a user
+    /// key that a service account already holds.
+    pub(crate) fn ensure_key_not_held_by_service_account(
+        &self,
+        access_key_id: &str,
+    ) -> Result<(), ConfigDbError> {
+        let taken: bool = self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM service_accounts WHERE access_key_id = ?1)",
+            params![access_key_id],
+            |r| r.get(0),
+        )?;
+        if taken {
+            return Err(ConfigDbError::Other(format!(
+                "access key '{access_key_id}' is held by a service account"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn delete_service_account(&self, id: i64) -> Result<(), ConfigDbError> {
+        let deleted = self
+            .conn
+            .execute("DELETE FROM service_accounts WHERE id = ?1", params![id])?;
+        if deleted == 0 {
+            return Err(ConfigDbError::NotFound(format!("service account {id}")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(parent_user_id: i64, name: &str, key: &str) -> ServiceAccount {
+        ServiceAccount {
+            id: 0,
+            parent_user_id,
+            name: name.into(),
+            description: String::new(),
+            access_key_id: key.into(),
+            secret_access_key: "secret".into(),
+            policy: Some(vec![Permission {
+                id: 0,
+                effect: "Allow".into(),
+                actions: vec!["read".into()],
+                resources: vec!["ci/*".into()],
+                conditions: None,
+            }]),
+            expires_at: Some(4_000_000_000),
+            enabled: true,
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_service_account_crud_and_cascade() {
+        let db = ConfigDb::in_memory("test").unwrap();
+        let user = db.create_user("dana", "AKDANA", "s", true, &[]).unwrap();
+
+        let created = db
+            .create_service_account(&account(user.id, "ci", "AKCI"))
+            .unwrap();
+        assert_eq!(created.policy.as_ref().unwrap()[0].resources, vec!["ci/*"]);
+        assert_eq!(created.expires_at, Some(4_000_000_000));
+
+        // Same name under the same parent, or a user's key, is rejected.
+        assert!(db
+            .create_service_account(&account(user.id, "ci", "AKCI2"))
+            .is_err());
+        assert!(db
+            .create_service_account(&account(user.id, "other", "AKDANA"))
+            .is_err());
+
+        let updated = db
+            .update_service_account(
+                created.id,
+                &ServiceAccountUpdate {
+                    policy: Some(None),
+                    expires_at: Some(None),
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert!(updated.policy.is_none());
+        assert!(updated.expires_at.is_none());
+        assert!(!updated.enabled);
+
+        // A user can't take a service account's key either.
+        assert!(db.create_user("eve", "AKCI", "s", true, &[]).is_err());
+        assert!(db.rotate_keys(user.id, "AKCI", "s2").is_err());
+
+        db.delete_user(user.id).unwrap();
+        assert!(db.load_service_accounts().unwrap().is_empty());
+        assert!(matches!(
+            db.delete_service_account(created.id),
+            Err(ConfigDbError::NotFound(_))
+        ));
+    }
+}
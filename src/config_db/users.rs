@@ -95,6 +95,7 @@ impl ConfigDb {
         enabled: bool,
         permissions: &[Permission],
     ) -> Result<IamUser, ConfigDbError> {
+        self.ensure_key_not_held_by_service_account(access_key_id)?;
         let tx = self.conn.unchecked_transaction()?;
         tx.execute(
             "INSERT INTO users (name, access_key_id, secret_access_key, enabled) VALUES (?1, ?2, ?3, ?4)",
@@ -199,6 +200,7 @@ impl ConfigDb {
         new_access_key_id: &str,
         new_secret_access_key: &str,
     ) -> Result<IamUser, ConfigDbError> {
+        self.ensure_key_not_held_by_service_account(new_access_key_id)?;
         let rows = self.conn.execute(
             "UPDATE users SET access_key_id = ?1, secret_access_key = ?2 WHERE id = ?3",
             params![new_access_key_id, new_secret_access_key, user_id],
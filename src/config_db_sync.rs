@@ -150,7 +150,10 @@ impl ConfigDbSync {
                 access_key_id.clone(),
                 secret_access_key.clone(),
             ),
-            BackendConfig::Filesystem { .. } => {
+            BackendConfig::Filesystem { .. }
+            | BackendConfig::AzureBlob { .. }
+            | BackendConfig::Gcs { .. }
+            | BackendConfig::Erasure { .. } => {
                 return Err("Config DB S3 sync requires an S3 backend. \
                      Set DGP_CONFIG_SYNC_BUCKET only when using the S3 backend."
                     .to_string());
@@ -854,9 +857,10 @@ pub async fn reopen_and_rebuild_iam(
     // Rebuild IAM index from the new DB
     let users = db.load_users().unwrap_or_default();
     let groups = db.load_groups().unwrap_or_default();
+    let service_accounts = db.load_service_accounts().unwrap_or_default();
     let count = users.len();
     let group_count = groups.len();
-    let state = IamIndex::build_iam_state(users, groups);
+    let state = IamIndex::build_iam_state(users, groups, service_accounts);
     if matches!(&state, IamState::Iam(_)) {
         info!(
             "IAM index rebuilt from S3-synced DB ({} users, {} groups) [{}]",
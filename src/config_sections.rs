@@ -380,6 +380,19 @@ pub struct ReplicationConfig {
     #[serde(default = "default_dir_concurrency")]
     pub dir_concurrency: u32,
 
+    /// Ceiling on the combined copy throughput of every rule, in megabits per
+    /// second (1 Mbit/s = 125 000 bytes/s). Each rule's own
+    /// `bandwidth_limit_mbps` applies on top. Unset = uncapped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bandwidth_limit_mbps: Option<u32>,
+
+    /// Allowed-hours windows for every rule: copies (reconcile sweeps AND
+    /// event-driven) only run inside them; outside, changes queue until a
+    /// window opens. A rule's own `windows` must ALSO be open. Empty = any
+    /// time.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<ReplicationWindow>,
+
     /// Replication rules. Each rule describes a source → destination
     /// copy with its own interval and filters. Empty by default.
     #[serde(default)]
@@ -399,6 +412,8 @@ impl Default for ReplicationConfig {
             transfers: default_transfers(),
             upload_concurrency: default_upload_concurrency(),
             dir_concurrency: default_dir_concurrency(),
+            bandwidth_limit_mbps: None,
+            windows: Vec::new(),
             rules: Vec::new(),
         }
     }
@@ -761,7 +776,7 @@ fn default_lifecycle_batch_size() -> u32 {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct EventDeliveryConfig {
     /// Master switch. Delivery is inactive unless this is true AND at least one
-    /// webhook endpoint is present.
+    /// webhook endpoint (or sink) is present.
     #[serde(default)]
     pub enabled: bool,
 
@@ -779,6 +794,13 @@ pub struct EventDeliveryConfig {
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub webhook_headers: BTreeMap<String, String>,
 
+    /// HMAC signing secrets, keyed by webhook endpoint URL; the `*` entry
+    /// covers every endpoint without its own. A signed delivery carries an
+    /// `X-DGP-Signature` header (see `crate::webhook_signature`). SECRET —
+    /// masked on export, preserved on an untouched round-trip.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub webhook_signing: BTreeMap<String, WebhookSigningSecret>,
+
     /// Dispatcher wake interval. Defaults to `10s`.
     #[serde(default = "default_event_delivery_tick")]
     pub tick_interval: String,
@@ -879,6 +901,267 @@ pub struct EventDeliveryConfig {
     /// requires a bot token (`chat.postMessage`).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub slack_routes: Vec<SlackRoute>,
+
+    /// Message brokers that receive the raw `{schema,event}` envelope, in
+    /// addition to any webhook endpoints. An event is delivered once every
+    /// webhook endpoint and every sink has accepted it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sinks: Vec<EventSinkConfig>,
+
+    /// Named delivery endpoints, each with its own filter, format, headers,
+    /// retry policy and listener cursor. Unlike `webhook_url(s)`, an
+    /// endpoint only receives the events its filter matches, and one that is
+    /// down only holds back itself.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<EventEndpoint>,
+
+    /// Opt-in `ObjectAccessed` (GET/HEAD) and `AccessDenied` / `AuthFailed`
+    /// security events. They share the outbox but have their own retention,
+    /// and only named [`Self::endpoints`] that list their kinds receive them.
+    #[serde(default, skip_serializing_if = "AccessEventsConfig::is_default")]
+    pub access_events: AccessEventsConfig,
+}
+
+/// Read and security events. See [`EventDeliveryConfig::access_events`].
+///
+/// These events are journal entries: they are stored already delivered, so
+/// `webhook_url(s)`, sinks and Slack never send them and they cannot crowd
+/// out object-mutation deliveries. Their rows are pruned by `retention` and
+/// `max_rows` only, and never count against `delivered_max_rows`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AccessEventsConfig {
+    /// Record an `ObjectAccessed` event for every successful GetObject (and
+    /// HeadObject with `include_head`) that passes the filters below.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Buckets whose reads are recorded. Empty = every bucket.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buckets: Vec<String>,
+
+    /// Only keys matching at least one glob. Empty = any key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_globs: Vec<String>,
+
+    /// Keys matching any of these globs are never recorded (exclude wins).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_globs: Vec<String>,
+
+    /// Also record HeadObject. Off by default: SDKs HEAD before most reads.
+    #[serde(default)]
+    pub include_head: bool,
+
+    /// Fraction of matching reads recorded, `0.0`–`1.0`. Default `1.0`.
+    #[serde(default = "default_access_events_sample_rate")]
+    pub sample_rate: f64,
+
+    /// Record `AccessDenied` (IAM denial) and `AuthFailed` (rejected
+    /// credentials) events for every S3 request, unsampled.
+    #[serde(default)]
+    pub security_events: bool,
+
+    /// Access and security rows older than this are pruned. Default `24h`.
+    #[serde(default = "default_access_events_retention")]
+    pub retention: String,
+
+    /// Maximum access and security rows kept (newest kept). Default 100000.
+    #[serde(default = "default_access_events_max_rows")]
+    pub max_rows: u32,
+}
+
+impl Default for AccessEventsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            buckets: Vec::new(),
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            include_head: false,
+            sample_rate: default_access_events_sample_rate(),
+            security_events: false,
+            retention: default_access_events_retention(),
+            max_rows: default_access_events_max_rows(),
+        }
+    }
+}
+
+impl AccessEventsConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_access_events_sample_rate() -> f64 {
+    1.0
+}
+
+fn default_access_events_retention() -> String {
+    "24h".to_string()
+}
+
+fn default_access_events_max_rows() -> u32 {
+    100_000
+}
+
+/// One named delivery endpoint. See [`EventDeliveryConfig::endpoints`].
+///
+/// The endpoint walks the outbox with its own listener cursor
+/// (`event-endpoint:<name>`), starting at the newest event when it is first
+/// seen. The event at its head is retried with the endpoint's backoff until
+/// it is delivered or `max_attempts` runs out, after which it is skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EventEndpoint {
+    /// Unique label. Renaming an endpoint starts it over at the newest event.
+    pub name: String,
+
+    /// Per-endpoint toggle. A disabled endpoint keeps its cursor and resumes
+    /// where it stopped, minus any events pruned in the meantime.
+    #[serde(default = "crate::types::default_true")]
+    pub enabled: bool,
+
+    /// HTTP(S) URL that receives the events. With `format = slack` this is a
+    /// Slack Incoming Webhook URL — SECRET, masked on export and preserved on
+    /// an untouched round-trip.
+    pub url: String,
+
+    /// `raw` posts the `{schema,event}` envelope; `slack` a Slack message.
+    #[serde(default)]
+    pub format: EventDeliveryFormat,
+
+    /// Static HTTP headers. Values are SECRET, like `webhook_headers`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+
+    /// HMAC signing secret (`X-DGP-Signature`). SECRET.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing: Option<WebhookSigningSecret>,
+
+    /// Which events this endpoint receives. Empty = every object event;
+    /// access and security events only go to endpoints that list their kind.
+    #[serde(default, skip_serializing_if = "EventFilter::is_empty")]
+    pub filter: EventFilter,
+
+    /// Attempts per event before it is skipped. Default: the top-level
+    /// `max_attempts`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+
+    /// Initial retry delay. Default: the top-level `retry_base`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_base: Option<String>,
+
+    /// Maximum retry delay. Default: the top-level `retry_max`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_max: Option<String>,
+}
+
+/// Event selection of an [`EventEndpoint`]. Every non-empty criterion must
+/// match. Size and metadata criteria only match events that carry
+/// `content_length` / `user_metadata` in their payload (object writes), so
+/// setting one of them narrows an endpoint to writes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EventFilter {
+    /// Event kinds, e.g. `ObjectCreated`. Empty = every object-mutation
+    /// kind; `ObjectAccessed`, `AccessDenied` and `AuthFailed` must be listed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<String>,
+
+    /// Bucket names. Empty = all buckets.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buckets: Vec<String>,
+
+    /// Only keys matching at least one glob, e.g. `reports/**` or `**/*.zip`.
+    /// Empty = any key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_globs: Vec<String>,
+
+    /// Keys matching any of these globs never match (exclude wins).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_globs: Vec<String>,
+
+    /// Minimum object size in bytes (inclusive).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_size: Option<u64>,
+
+    /// Maximum object size in bytes (inclusive).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<u64>,
+
+    /// User metadata (`x-amz-meta-*` without the prefix) the object must
+    /// carry, with these exact values.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl EventFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// One message-broker sink. See [`EventDeliveryConfig::sinks`] and
+/// `crate::event_sinks`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EventSinkConfig {
+    /// Unique label, used in metrics and delivery errors.
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: EventSinkKind,
+    /// Where the broker listens: Kafka bootstrap brokers
+    /// (`host:port[,host:port…]`, or `kafka://` / `kafkas://` with SASL
+    /// credentials), `nats://` / `tls://`, `amqp://` / `amqps://` or
+    /// `redis://` / `rediss://` URL. The second scheme of each pair
+    /// connects over TLS. Credentials go in the URL's user-info. SECRET
+    /// when it carries credentials — masked on export, preserved on an
+    /// untouched round-trip.
+    pub url: String,
+    /// Kafka topic, NATS subject, AMQP exchange (`""` = default exchange)
+    /// or Redis stream key.
+    pub topic: String,
+    /// AMQP routing key. Default: the event's bucket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing_key: Option<String>,
+    /// PEM file of the CAs a TLS broker's certificate is checked against.
+    /// Default: the public web roots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<String>,
+}
+
+/// Broker protocol of an [`EventSinkConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSinkKind {
+    /// Kafka, produced with `acks=all`.
+    Kafka,
+    /// NATS JetStream, waiting for the stream's publish ack.
+    Nats,
+    /// RabbitMQ / AMQP 0-9-1, with publisher confirms.
+    Amqp,
+    /// Redis Streams (`XADD`).
+    Redis,
+}
+
+impl EventSinkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Kafka => "kafka",
+            Self::Nats => "nats",
+            Self::Amqp => "amqp",
+            Self::Redis => "redis",
+        }
+    }
+}
+
+/// Signing secret(s) of one webhook endpoint. See
+/// [`EventDeliveryConfig::webhook_signing`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookSigningSecret {
+    /// Current secret; every delivery is signed with it.
+    pub secret: String,
+    /// The secret being rotated out. While set, deliveries carry a second
+    /// signature made with it, so receivers can switch over at their pace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_secret: Option<String>,
 }
 
 /// One bucket/prefix → channel routing rule. See [`EventDeliveryConfig::slack_routes`].
@@ -918,7 +1201,18 @@ fn default_slack_notify_kinds() -> Vec<String> {
 
 impl EventDeliveryConfig {
     pub fn is_active(&self) -> bool {
-        self.enabled && (!self.webhook_endpoints().is_empty() || self.uses_slack_bot_token())
+        self.enabled && (self.has_http_targets() || !self.sinks.is_empty())
+    }
+
+    /// `true` when the dispatcher should walk the outbox for named endpoints.
+    pub fn endpoints_active(&self) -> bool {
+        self.enabled && self.endpoints.iter().any(|e| e.enabled)
+    }
+
+    /// `true` when events go out over HTTP: webhook endpoints, or Slack's
+    /// Web API.
+    pub fn has_http_targets(&self) -> bool {
+        !self.webhook_endpoints().is_empty() || self.uses_slack_bot_token()
     }
 
     /// `true` when Slack delivery is configured to use the Web API (bot token)
@@ -932,6 +1226,14 @@ impl EventDeliveryConfig {
                 .unwrap_or(false)
     }
 
+    /// Signing secrets for deliveries to `endpoint`: its own entry in
+    /// [`Self::webhook_signing`], else the `*` entry.
+    pub fn signing_secret_for(&self, endpoint: &str) -> Option<&WebhookSigningSecret> {
+        self.webhook_signing
+            .get(endpoint)
+            .or_else(|| self.webhook_signing.get("*"))
+    }
+
     pub fn webhook_endpoints(&self) -> Vec<&str> {
         self.webhook_url
             .as_deref()
@@ -950,6 +1252,7 @@ impl Default for EventDeliveryConfig {
             webhook_url: None,
             webhook_urls: Vec::new(),
             webhook_headers: BTreeMap::new(),
+            webhook_signing: BTreeMap::new(),
             tick_interval: default_event_delivery_tick(),
             batch_size: default_event_delivery_batch_size(),
             request_timeout: default_event_delivery_timeout(),
@@ -969,6 +1272,9 @@ impl Default for EventDeliveryConfig {
             slack_exclude_globs: Vec::new(),
             slack_notify_kinds: default_slack_notify_kinds(),
             slack_routes: Vec::new(),
+            sinks: Vec::new(),
+            endpoints: Vec::new(),
+            access_events: AccessEventsConfig::default(),
         }
     }
 }
@@ -1017,6 +1323,214 @@ fn is_default_event_delivery(e: &EventDeliveryConfig) -> bool {
     e == &EventDeliveryConfig::default()
 }
 
+/// Optional read-through object cache on local disk, in front of slow or
+/// metered backends (WAN-attached S3, B2, Hetzner, …). Disabled by default.
+///
+/// Repeat downloads of the same object are served from `path` instead of
+/// the backend; partial (range) reads cache only the 1 MiB blocks they
+/// touched. Writes and deletes through the proxy invalidate the affected
+/// entries. Bytes are cached in their at-rest form, so an encrypted
+/// backend's cache holds ciphertext.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DiskCacheConfig {
+    /// Master switch. Requires `path`.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Cache directory. Created on startup; reused (and re-indexed) across
+    /// restarts. Must not be shared with a filesystem backend root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<std::path::PathBuf>,
+
+    /// Total on-disk budget. Least-recently-used blocks are evicted past it.
+    /// Defaults to 10240 (10 GiB).
+    #[serde(default = "default_disk_cache_max_size_mb")]
+    pub max_size_mb: u64,
+
+    /// Entries older than this are refetched (humantime, e.g. `24h`). Absent
+    /// = no expiry; only invalidation and LRU eviction remove entries.
+    /// Needed when the backend is also written by something other than
+    /// this proxy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<String>,
+
+    /// Objects larger than this are never cached (their first blocks may
+    /// still be cached by range reads). Defaults to a quarter of
+    /// `max_size_mb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_object_size_mb: Option<u64>,
+
+    /// Backend names to cache (`default` = the singleton `backend:`). Empty =
+    /// every non-filesystem backend.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backends: Vec<String>,
+}
+
+impl DiskCacheConfig {
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.path.is_some()
+    }
+
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_mb.saturating_mul(1024 * 1024)
+    }
+
+    pub fn max_object_size_bytes(&self) -> u64 {
+        match self.max_object_size_mb {
+            Some(mb) => mb.saturating_mul(1024 * 1024),
+            None => self.max_size_bytes() / 4,
+        }
+    }
+
+    /// Parsed `ttl`; `None` when absent or unparseable (validation warns).
+    pub fn ttl_duration(&self) -> Option<std::time::Duration> {
+        self.ttl
+            .as_deref()
+            .and_then(|t| humantime::parse_duration(t).ok())
+            .filter(|d| !d.is_zero())
+    }
+
+    /// Whether the backend named `name` should be wrapped. `is_filesystem`
+    /// backends are local already and only cached when listed explicitly.
+    pub fn covers_backend(&self, name: &str, is_filesystem: bool) -> bool {
+        if self.backends.is_empty() {
+            !is_filesystem
+        } else {
+            self.backends.iter().any(|b| b == name)
+        }
+    }
+}
+
+impl Default for DiskCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            max_size_mb: default_disk_cache_max_size_mb(),
+            ttl: None,
+            max_object_size_mb: None,
+            backends: Vec::new(),
+        }
+    }
+}
+
+fn default_disk_cache_max_size_mb() -> u64 {
+    10_240
+}
+
+fn is_default_disk_cache(c: &DiskCacheConfig) -> bool {
+    c == &DiskCacheConfig::default()
+}
+
+/// Durable audit store (`advanced.audit`). Disabled by default, in which
+/// case audit entries only reach the log and the in-memory ring.
+///
+/// When enabled, every audit entry is appended to a hash-chained table in
+/// the node's config DB, queryable and exportable through the admin API.
+/// Entries are sealed into JSONL segments in the archive bucket every
+/// `rotate_interval`, and both the table and the archive are pruned by
+/// `retention`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AuditConfig {
+    /// Master switch.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Entries older than this are deleted from the config DB and from the
+    /// archive (humantime, e.g. `365d`). Defaults to `365d`.
+    #[serde(default = "default_audit_retention")]
+    pub retention: String,
+
+    /// How long archived entries stay in the config DB, where the admin API
+    /// can query them. Entries not yet archived are kept until `retention`.
+    /// Defaults to `30d`.
+    #[serde(default = "default_audit_local_retention")]
+    pub local_retention: String,
+
+    /// Bucket on the default backend that receives archive segments.
+    /// Defaults to `config_sync_bucket`; with neither, entries live only in
+    /// the config DB. Use a bucket the proxy does not serve.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_bucket: Option<String>,
+
+    /// Key prefix of archive segments. Defaults to `.deltaglider/audit/`.
+    #[serde(default = "default_audit_archive_prefix")]
+    pub archive_prefix: String,
+
+    /// How often new entries are sealed into an archive segment. Defaults
+    /// to `1h`.
+    #[serde(default = "default_audit_rotate_interval")]
+    pub rotate_interval: String,
+}
+
+impl AuditConfig {
+    /// Where archive segments go: `archive_bucket`, else the config-sync
+    /// bucket. `None` = no archive.
+    pub fn archive_target<'a>(&'a self, config_sync_bucket: Option<&'a str>) -> Option<&'a str> {
+        self.archive_bucket
+            .as_deref()
+            .or(config_sync_bucket)
+            .filter(|b| !b.is_empty())
+    }
+
+    /// Parsed `retention`; the default when unparseable (validation warns).
+    pub fn retention_duration(&self) -> std::time::Duration {
+        parse_duration_or(&self.retention, &default_audit_retention())
+    }
+
+    /// Parsed `local_retention`, never longer than `retention`.
+    pub fn local_retention_duration(&self) -> std::time::Duration {
+        parse_duration_or(&self.local_retention, &default_audit_local_retention())
+            .min(self.retention_duration())
+    }
+
+    /// Parsed `rotate_interval`; the default when unparseable or zero.
+    pub fn rotate_interval_duration(&self) -> std::time::Duration {
+        parse_duration_or(&self.rotate_interval, &default_audit_rotate_interval())
+    }
+}
+
+fn parse_duration_or(value: &str, default: &str) -> std::time::Duration {
+    humantime::parse_duration(value)
+        .ok()
+        .filter(|d| !d.is_zero())
+        .or_else(|| humantime::parse_duration(default).ok())
+        .unwrap_or_default()
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention: default_audit_retention(),
+            local_retention: default_audit_local_retention(),
+            archive_bucket: None,
+            archive_prefix: default_audit_archive_prefix(),
+            rotate_interval: default_audit_rotate_interval(),
+        }
+    }
+}
+
+fn default_audit_retention() -> String {
+    "365d".to_string()
+}
+
+fn default_audit_local_retention() -> String {
+    "30d".to_string()
+}
+
+fn default_audit_archive_prefix() -> String {
+    ".deltaglider/audit/".to_string()
+}
+
+fn default_audit_rotate_interval() -> String {
+    "1h".to_string()
+}
+
+fn is_default_audit(c: &AuditConfig) -> bool {
+    c == &AuditConfig::default()
+}
+
 /// A single replication rule: copy objects from `source` to `destination`
 /// on `interval`. The cross-encryption/cross-backend/cross-compression
 /// transparency comes from routing the copy through
@@ -1057,7 +1571,26 @@ pub struct ReplicationRule {
     #[serde(default)]
     pub replicate_deletes: bool,
 
-    /// Policy for handling objects that exist on both sides.
+    /// `one-way` (default) copies source → destination. `bidirectional`
+    /// keeps both sides in sync for two sites that each take writes: changes
+    /// on either side are copied to the other, deletes propagate as
+    /// tombstones (with `replicate_deletes`), and keys changed on both sides
+    /// since the last sync are held as conflicts for manual resolution.
+    #[serde(default, skip_serializing_if = "is_default_direction")]
+    pub direction: ReplicationDirection,
+
+    /// Bidirectional only: how long a propagated delete is remembered
+    /// (humantime). A copy that resurfaces on one side within this window is
+    /// deleted again; after it the tombstone is forgotten and a resurfaced
+    /// copy is treated as a new object. Default `30d`.
+    #[serde(
+        default = "default_tombstone_retention",
+        skip_serializing_if = "is_default_tombstone_retention"
+    )]
+    pub tombstone_retention: String,
+
+    /// Policy for handling objects that exist on both sides. One-way rules
+    /// only: bidirectional rules hold true conflicts for manual resolution.
     #[serde(default)]
     pub conflict: ConflictPolicy,
 
@@ -1069,6 +1602,17 @@ pub struct ReplicationRule {
     #[serde(default)]
     pub strict_content_diff: bool,
 
+    /// Ceiling on this rule's copy throughput in megabits per second, shared
+    /// by its reconcile sweeps and event-driven copies. The global
+    /// `replication.bandwidth_limit_mbps` still applies. Unset = uncapped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bandwidth_limit_mbps: Option<u32>,
+
+    /// Allowed-hours windows for this rule (in addition to the global ones).
+    /// Empty = any time.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<ReplicationWindow>,
+
     /// Optional globset: if non-empty, ONLY keys matching at least one
     /// of these patterns are replicated. Applied in addition to
     /// `exclude_globs`.
@@ -1100,11 +1644,121 @@ fn default_exclude_globs() -> Vec<String> {
     vec![".deltaglider/**".to_string()]
 }
 
+fn default_tombstone_retention() -> String {
+    "30d".to_string()
+}
+
+fn is_default_tombstone_retention(s: &str) -> bool {
+    s == default_tombstone_retention()
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ReplicationEndpoint {
     pub bucket: String,
     #[serde(default)]
     pub prefix: String,
+    /// Set when this side is not a proxy-routed bucket but a bucket on an
+    /// arbitrary S3 endpoint reached with its own credentials (a partner's
+    /// mirror, a vendor's release bucket). `bucket` then names the bucket on
+    /// that endpoint. See `replication::external`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external: Option<ExternalS3Endpoint>,
+}
+
+/// Connection settings for an external replication endpoint — the knobs of
+/// an S3 backend (`BackendConfig::S3`, same SSRF guard) plus `hydrated`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExternalS3Endpoint {
+    /// S3 endpoint URL. Unset = AWS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(default = "crate::config::default_region")]
+    pub region: String,
+    #[serde(default = "crate::config::default_force_path_style")]
+    pub force_path_style: bool,
+    /// Cleared on export with `secret_access_key`, like backend creds; an
+    /// apply that omits both keeps the current pair (matched by rule name,
+    /// side and endpoint URL).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_key_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_access_key: Option<String>,
+    /// Permit `http://` and private-IP / localhost endpoints (MinIO, CI).
+    #[serde(default, skip_serializing_if = "crate::config::is_false")]
+    pub allow_local: bool,
+    /// `true` (default): the bucket holds plain objects — a destination
+    /// receives fully reconstructed objects any S3 client can read. `false`:
+    /// the bucket is DeltaGlider storage layout (another proxy's backing
+    /// bucket); reads reconstruct deltas and writes delta-compress.
+    #[serde(
+        default = "crate::types::default_true",
+        skip_serializing_if = "is_true"
+    )]
+    pub hydrated: bool,
+}
+
+fn is_true(b: &bool) -> bool {
+    *b
+}
+
+impl ReplicationEndpoint {
+    /// Where this side's bucket lives: the bucket name for a proxy-routed
+    /// side, `<endpoint>/<bucket>` for an external one — so a partner bucket
+    /// never aliases a same-named proxy bucket in the self-loop and cycle
+    /// checks.
+    pub fn location(&self) -> String {
+        match &self.external {
+            None => self.bucket.clone(),
+            Some(ext) => match &ext.endpoint {
+                Some(url) => format!("{}/{}", url.trim_end_matches('/'), self.bucket),
+                None => format!("aws:{}/{}", ext.region, self.bucket),
+            },
+        }
+    }
+}
+
+/// An allowed-hours window, e.g. `{days: mon-fri, start: "22:00", end:
+/// "06:00", timezone: Europe/Berlin}`. A window whose `end` is not after its
+/// `start` runs past midnight and belongs to the day it OPENS on (the example
+/// covers Friday 22:00 → Saturday 06:00, not Sunday night). `start == end`
+/// means the whole day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ReplicationWindow {
+    /// Days the window opens on: `daily`, a range (`mon-fri`, `fri-mon`), a
+    /// list (`sat,sun`), or a mix (`mon-wed,fri`). Defaults to `daily`.
+    #[serde(default = "default_window_days")]
+    pub days: String,
+    /// Opening time, `HH:MM` (24h) in `timezone`.
+    pub start: String,
+    /// Closing time, `HH:MM` (24h) in `timezone`.
+    pub end: String,
+    /// IANA time zone name (`Europe/Berlin`, `America/New_York`). DST is
+    /// honoured. Defaults to `UTC`.
+    #[serde(default = "default_window_timezone")]
+    pub timezone: String,
+}
+
+fn default_window_days() -> String {
+    "daily".to_string()
+}
+
+fn default_window_timezone() -> String {
+    "UTC".to_string()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReplicationDirection {
+    /// Source → destination only (default).
+    #[default]
+    OneWay,
+    /// Active-active: both endpoints take writes and each side's changes are
+    /// copied to the other (`replication::bidi`).
+    Bidirectional,
+}
+
+fn is_default_direction(d: &ReplicationDirection) -> bool {
+    *d == ReplicationDirection::OneWay
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -1173,6 +1827,7 @@ impl StorageSection {
                 validate_filesystem_path(self.filesystem.as_ref().expect("has_fs asserted above"))?;
                 self.backend = BackendConfig::Filesystem {
                     path: self.filesystem.take().expect("has_fs asserted above"),
+                    metadata: Default::default(),
                 };
             }
             (true, true, _) => {
@@ -1320,6 +1975,14 @@ pub struct AdvancedSection {
     /// blocking S3 operations.
     #[serde(default, skip_serializing_if = "is_default_event_delivery")]
     pub event_delivery: EventDeliveryConfig,
+
+    /// Read-through object cache on local disk. Disabled by default.
+    #[serde(default, skip_serializing_if = "is_default_disk_cache")]
+    pub disk_cache: DiskCacheConfig,
+
+    /// Durable, hash-chained audit store. Disabled by default.
+    #[serde(default, skip_serializing_if = "is_default_audit")]
+    pub audit: AuditConfig,
 }
 
 // ══ skip_serializing_if helpers — any non-default value surfaces. ══════
@@ -1446,6 +2109,8 @@ impl SectionedConfig {
                 tls: flat.tls.clone(),
                 bootstrap_password_hash: flat.bootstrap_password_hash.clone(),
                 event_delivery: flat.event_delivery.clone(),
+                disk_cache: flat.disk_cache.clone(),
+                audit: flat.audit.clone(),
             },
         }
     }
@@ -1522,6 +2187,8 @@ impl SectionedConfig {
             config_sync_object_key: self.advanced.config_sync_object_key,
             tls: self.advanced.tls,
             event_delivery: self.advanced.event_delivery,
+            disk_cache: self.advanced.disk_cache,
+            audit: self.advanced.audit,
             buckets: self.storage.buckets,
             backend_encryption: self.storage.backend_encryption,
             backends: self.storage.backends,
@@ -1708,6 +2375,19 @@ pub fn validate_replication(cfg: &ReplicationConfig) -> Vec<String> {
             cfg.dir_concurrency
         ));
     }
+    if cfg.bandwidth_limit_mbps == Some(0) {
+        warnings.push(
+            "replication.bandwidth_limit_mbps=0 is treated as uncapped; omit it instead"
+                .to_string(),
+        );
+    }
+    for (i, window) in cfg.windows.iter().enumerate() {
+        if let Err(e) = crate::replication::throttle::parse_window(window) {
+            warnings.push(format!(
+                "replication.windows[{i}] is invalid and will be ignored: {e}"
+            ));
+        }
+    }
 
     // Per-rule checks.
     let mut seen_names = std::collections::HashSet::new();
@@ -1771,7 +2451,7 @@ pub fn validate_replication(cfg: &ReplicationConfig) -> Vec<String> {
         // Self-loop: source and destination reference the same
         // bucket+prefix. Degenerate — the rule would copy objects
         // back to themselves, pointlessly re-incrementing counters.
-        if rule.source.bucket == rule.destination.bucket && source_norm == dest_norm {
+        if rule.source.location() == rule.destination.location() && source_norm == dest_norm {
             warnings.push(format!(
                 "replication rule '{}' is a self-loop \
                  (source == destination); it will be skipped at runtime",
@@ -1788,6 +2468,74 @@ pub fn validate_replication(cfg: &ReplicationConfig) -> Vec<String> {
                 ));
             }
         }
+
+        if rule.bandwidth_limit_mbps == Some(0) {
+            warnings.push(format!(
+                "replication rule '{}' bandwidth_limit_mbps=0 is treated as uncapped; \
+                 omit it instead",
+                rule.name
+            ));
+        }
+        for (i, window) in rule.windows.iter().enumerate() {
+            if let Err(e) = crate::replication::throttle::parse_window(window) {
+                warnings.push(format!(
+                    "replication rule '{}' windows[{i}] is invalid and will be ignored: {e}",
+                    rule.name
+                ));
+            }
+        }
+
+        if let Err(e) = humantime::parse_duration(&rule.tombstone_retention) {
+            warnings.push(format!(
+                "replication rule '{}' tombstone_retention={} invalid: {}",
+                rule.name, rule.tombstone_retention, e
+            ));
+        }
+        // Bidirectional rules never overwrite a side changed since the last
+        // sync — true conflicts are held for manual resolution — so the
+        // one-way conflict knobs have no effect.
+        if rule.direction == ReplicationDirection::Bidirectional
+            && (rule.conflict != ConflictPolicy::default() || rule.strict_content_diff)
+        {
+            warnings.push(format!(
+                "replication rule '{}' is bidirectional; conflict and strict_content_diff \
+                 are ignored (conflicts are resolved manually)",
+                rule.name
+            ));
+        }
+
+        for (side, endpoint) in [("source", &rule.source), ("destination", &rule.destination)] {
+            let Some(ext) = &endpoint.external else {
+                continue;
+            };
+            if rule.direction == ReplicationDirection::Bidirectional {
+                warnings.push(format!(
+                    "replication rule '{}' is bidirectional with an external {side}; \
+                     it will be refused at runtime (external endpoints are one-way only)",
+                    rule.name
+                ));
+            }
+            if let Some(url) = &ext.endpoint {
+                let kind = if ext.allow_local {
+                    crate::security::UrlKind::BackendDev
+                } else {
+                    crate::security::UrlKind::Backend
+                };
+                if let Err(e) = crate::security::validate_outbound_url(url, kind) {
+                    warnings.push(format!(
+                        "replication rule '{}' {side}.external.endpoint {url:?} is refused: {e}",
+                        rule.name
+                    ));
+                }
+            }
+            if ext.access_key_id.is_some() != ext.secret_access_key.is_some() {
+                warnings.push(format!(
+                    "replication rule '{}' {side}.external needs both access_key_id and \
+                     secret_access_key (or neither, for ambient AWS credentials)",
+                    rule.name
+                ));
+            }
+        }
     }
 
     // Cycle detection across rules. A 2-hop cycle (A→B, B→A with
@@ -1958,13 +2706,86 @@ pub fn validate_lifecycle(cfg: &LifecycleConfig) -> Vec<String> {
     warnings
 }
 
+fn validate_event_endpoint(endpoint: &EventEndpoint) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let label = format!("event_delivery.endpoints[{:?}]", endpoint.name);
+    match reqwest::Url::parse(&endpoint.url) {
+        Ok(parsed) if parsed.scheme() == "http" || parsed.scheme() == "https" => {}
+        Ok(parsed) => warnings.push(format!(
+            "{label}.url uses unsupported scheme '{}'; expected http or https",
+            parsed.scheme(),
+        )),
+        Err(e) => warnings.push(format!("{label}.url is invalid: {e}")),
+    }
+    for (name, value) in &endpoint.headers {
+        if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+            warnings.push(format!("{label}.headers has invalid header name {name:?}"));
+        }
+        if reqwest::header::HeaderValue::from_str(value).is_err() {
+            warnings.push(format!(
+                "{label}.headers[{name:?}] is not a valid HTTP header value"
+            ));
+        }
+    }
+    if endpoint
+        .signing
+        .as_ref()
+        .is_some_and(|s| s.secret.trim().is_empty())
+    {
+        warnings.push(format!("{label}.signing.secret is empty"));
+    }
+    for (field, value) in [
+        ("retry_base", &endpoint.retry_base),
+        ("retry_max", &endpoint.retry_max),
+    ] {
+        if let Some(value) = value {
+            if let Err(e) = humantime::parse_duration(value) {
+                warnings.push(format!(
+                    "{label}.{field}={value:?} is not a valid humantime duration: {e}"
+                ));
+            }
+        }
+    }
+    if endpoint.max_attempts == Some(0) {
+        warnings.push(format!(
+            "{label}.max_attempts=0; dispatcher will treat it as 1"
+        ));
+    }
+    let filter = &endpoint.filter;
+    for kind in &filter.kinds {
+        use crate::event_outbox::EventKind;
+        use crate::replication::event_consumer::parse_event_kind;
+        if parse_event_kind(kind).is_none_or(|k| k == EventKind::MirrorRepair) {
+            warnings.push(format!("{label}.filter.kinds has unknown kind {kind:?}"));
+        }
+    }
+    for (field, patterns) in [
+        ("include_globs", &filter.include_globs),
+        ("exclude_globs", &filter.exclude_globs),
+    ] {
+        for p in patterns {
+            if globset::Glob::new(p).is_err() {
+                warnings.push(format!("{label}.filter.{field} has invalid glob {p:?}"));
+            }
+        }
+    }
+    if let (Some(min), Some(max)) = (filter.min_size, filter.max_size) {
+        if min > max {
+            warnings.push(format!(
+                "{label}.filter.min_size={min} exceeds max_size={max}; nothing will match"
+            ));
+        }
+    }
+    warnings
+}
+
 /// Validate event-delivery config. Warning-only to match `Config::check`;
 /// the dispatcher still treats invalid/missing webhook config as inactive.
 pub fn validate_event_delivery(cfg: &EventDeliveryConfig) -> Vec<String> {
     let mut warnings = Vec::new();
-    if cfg.enabled && cfg.webhook_endpoints().is_empty() && !cfg.uses_slack_bot_token() {
+    if cfg.enabled && !cfg.has_http_targets() && cfg.sinks.is_empty() && !cfg.endpoints_active() {
         warnings.push(
-            "event_delivery.enabled=true but no webhook endpoint (or Slack bot token) is configured; dispatcher will stay inactive"
+            "event_delivery.enabled=true but no webhook endpoint, Slack bot token, sink or enabled endpoint is configured; dispatcher will stay inactive"
                 .to_string(),
         );
     }
@@ -2006,6 +2827,44 @@ pub fn validate_event_delivery(cfg: &EventDeliveryConfig) -> Vec<String> {
         }
     }
 
+    let mut sink_names = std::collections::HashSet::new();
+    for sink in &cfg.sinks {
+        if sink.name.trim().is_empty() || !sink_names.insert(sink.name.as_str()) {
+            warnings.push(format!(
+                "event_delivery.sinks: name {:?} is empty or not unique",
+                sink.name
+            ));
+        }
+        if let Err(e) = crate::event_sinks::validate_sink(sink) {
+            warnings.push(format!("event_delivery.sinks[{:?}]: {e}", sink.name));
+        }
+    }
+
+    let mut endpoint_names = std::collections::HashSet::new();
+    for endpoint in &cfg.endpoints {
+        warnings.extend(validate_event_endpoint(endpoint));
+        if endpoint.name.trim().is_empty() || !endpoint_names.insert(endpoint.name.as_str()) {
+            warnings.push(format!(
+                "event_delivery.endpoints: name {:?} is empty or not unique",
+                endpoint.name
+            ));
+        }
+    }
+
+    let endpoints = cfg.webhook_endpoints();
+    for (endpoint, signing) in &cfg.webhook_signing {
+        if endpoint != "*" && !endpoints.contains(&endpoint.as_str()) {
+            warnings.push(format!(
+                "event_delivery.webhook_signing[{endpoint:?}] matches no webhook endpoint"
+            ));
+        }
+        if signing.secret.trim().is_empty() {
+            warnings.push(format!(
+                "event_delivery.webhook_signing[{endpoint:?}].secret is empty"
+            ));
+        }
+    }
+
     for (label, value) in [
         ("event_delivery.tick_interval", &cfg.tick_interval),
         ("event_delivery.request_timeout", &cfg.request_timeout),
@@ -2043,6 +2902,20 @@ pub fn validate_event_delivery(cfg: &EventDeliveryConfig) -> Vec<String> {
         );
     }
 
+    warnings.extend(validate_access_events(&cfg.access_events));
+    let access = &cfg.access_events;
+    if (access.enabled || access.security_events)
+        && !cfg
+            .endpoints
+            .iter()
+            .any(|e| e.filter.kinds.iter().any(|k| is_access_kind(k)))
+    {
+        warnings.push(
+            "event_delivery.access_events records events, but no endpoint lists ObjectAccessed, AccessDenied or AuthFailed in filter.kinds; they are only kept in the outbox"
+                .to_string(),
+        );
+    }
+
     // Slack-format validation.
     if cfg.format == EventDeliveryFormat::Slack {
         // A bot-token config needs SOME destination: either per-route channels
@@ -2108,6 +2981,110 @@ pub fn validate_event_delivery(cfg: &EventDeliveryConfig) -> Vec<String> {
     warnings
 }
 
+fn is_access_kind(kind: &str) -> bool {
+    crate::replication::event_consumer::parse_event_kind(kind).is_some_and(|k| k.is_access())
+}
+
+fn validate_access_events(cfg: &AccessEventsConfig) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if !(0.0..=1.0).contains(&cfg.sample_rate) {
+        warnings.push(format!(
+            "event_delivery.access_events.sample_rate={} outside [0, 1]; it will be clamped",
+            cfg.sample_rate
+        ));
+    }
+    for (field, patterns) in [
+        ("include_globs", &cfg.include_globs),
+        ("exclude_globs", &cfg.exclude_globs),
+    ] {
+        for p in patterns {
+            if globset::Glob::new(p).is_err() {
+                warnings.push(format!(
+                    "event_delivery.access_events.{field} has invalid glob {p:?}"
+                ));
+            }
+        }
+    }
+    if let Err(e) = humantime::parse_duration(&cfg.retention) {
+        warnings.push(format!(
+            "event_delivery.access_events.retention={:?} is not a valid humantime duration: {e}",
+            cfg.retention
+        ));
+    }
+    warnings
+}
+
+pub fn validate_disk_cache(cfg: &DiskCacheConfig) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if !cfg.enabled {
+        return warnings;
+    }
+    match cfg.path.as_deref() {
+        None => warnings.push(
+            "disk_cache.enabled=true but disk_cache.path is not set; the cache stays disabled"
+                .to_string(),
+        ),
+        Some(p) if !p.is_absolute() => warnings.push(format!(
+            "disk_cache.path={} is relative; it resolves against the process working directory",
+            p.display()
+        )),
+        Some(_) => {}
+    }
+    if cfg.max_size_mb == 0 {
+        warnings.push("disk_cache.max_size_mb=0; nothing will be cached".to_string());
+    }
+    if let Some(ttl) = cfg.ttl.as_deref() {
+        if let Err(e) = humantime::parse_duration(ttl) {
+            warnings.push(format!(
+                "disk_cache.ttl={ttl:?} is not a valid humantime duration: {e}; entries will not expire"
+            ));
+        }
+    }
+    if let Some(mb) = cfg.max_object_size_mb {
+        if mb > cfg.max_size_mb {
+            warnings.push(format!(
+                "disk_cache.max_object_size_mb={mb} exceeds max_size_mb={}; large objects will evict everything else",
+                cfg.max_size_mb
+            ));
+        }
+    }
+    warnings
+}
+
+pub fn validate_audit(cfg: &AuditConfig, config_sync_bucket: Option<&str>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if !cfg.enabled {
+        return warnings;
+    }
+    for (field, value) in [
+        ("retention", &cfg.retention),
+        ("local_retention", &cfg.local_retention),
+        ("rotate_interval", &cfg.rotate_interval),
+    ] {
+        match humantime::parse_duration(value) {
+            Ok(d) if d.is_zero() => {
+                warnings.push(format!("audit.{field}=0; the default is used instead"));
+            }
+            Ok(_) => {}
+            Err(e) => warnings.push(format!(
+                "audit.{field}={value:?} is not a valid humantime duration: {e}; the default is used instead"
+            )),
+        }
+    }
+    if cfg.archive_target(config_sync_bucket).is_none() {
+        warnings.push(
+            "audit.enabled=true without archive_bucket or config_sync_bucket; entries are kept only in this node's config DB, for `retention`"
+                .to_string(),
+        );
+    } else if !cfg.archive_prefix.ends_with('/') {
+        warnings.push(format!(
+            "audit.archive_prefix={:?} should end with '/'; segment keys are appended to it",
+            cfg.archive_prefix
+        ));
+    }
+    warnings
+}
+
 /// Pure cycle detection: report all rules that form a cycle with another.
 /// A cycle exists when there's a chain of rules whose source+prefix
 /// graph returns to the starting bucket+prefix.
@@ -2116,20 +3093,29 @@ pub fn validate_event_delivery(cfg: &EventDeliveryConfig) -> Vec<String> {
 pub(crate) fn detect_replication_cycles(rules: &[ReplicationRule]) -> Vec<String> {
     use std::collections::{HashMap, HashSet};
 
-    /// `(bucket, prefix)` identifies a node in the replication graph.
+    /// `(location, prefix)` identifies a node in the replication graph
+    /// (`ReplicationEndpoint::location` keeps external buckets apart).
     type Node = (String, String);
 
-    // Edge list: source node -> list of (dest node, rule_name).
+    // Edge list: source node -> list of (dest node, rule_name). A
+    // bidirectional rule is an edge each way; its own A -> B -> A loop is
+    // intended (and loop-safe), so only cycles through another rule count.
     let mut edges: HashMap<Node, Vec<(Node, String)>> = HashMap::new();
     for rule in rules {
         let src: Node = (
-            rule.source.bucket.clone(),
+            rule.source.location(),
             crate::replication::normalize_prefix(&rule.source.prefix),
         );
         let dst: Node = (
-            rule.destination.bucket.clone(),
+            rule.destination.location(),
             crate::replication::normalize_prefix(&rule.destination.prefix),
         );
+        if rule.direction == ReplicationDirection::Bidirectional {
+            edges
+                .entry(dst.clone())
+                .or_default()
+                .push((src.clone(), rule.name.clone()));
+        }
         edges.entry(src).or_default().push((dst, rule.name.clone()));
     }
 
@@ -2153,6 +3139,10 @@ pub(crate) fn detect_replication_cycles(rules: &[ReplicationRule]) -> Vec<String
                         // set so the warnings list stays clean.
                         let mut sorted = next_path.clone();
                         sorted.sort();
+                        sorted.dedup();
+                        if sorted.len() == 1 && next_path.len() == 2 {
+                            continue; // one bidirectional rule's own round trip
+                        }
                         let key = sorted.join("|");
                         if seen_cycles.insert(key) {
                             warnings.push(format!(
@@ -2294,7 +3284,7 @@ mod tests {
         };
         storage.normalize().unwrap();
         match &storage.backend {
-            BackendConfig::Filesystem { path } => {
+            BackendConfig::Filesystem { path, .. } => {
                 assert_eq!(path.to_str(), Some("/var/dgp"));
             }
             other => panic!("expected Filesystem backend, got {other:?}"),
@@ -2324,6 +3314,7 @@ mod tests {
             s3: Some("https://example.com".into()),
             backend: BackendConfig::Filesystem {
                 path: "/explicit".into(),
+                metadata: Default::default(),
             },
             ..Default::default()
         };
@@ -2362,6 +3353,7 @@ mod tests {
         let original = StorageSection {
             backend: BackendConfig::Filesystem {
                 path: "/data".into(),
+                metadata: Default::default(),
             },
             ..Default::default()
         };
@@ -2482,16 +3474,22 @@ mod tests {
             source: ReplicationEndpoint {
                 bucket: src.0.to_string(),
                 prefix: src.1.to_string(),
+                external: None,
             },
             destination: ReplicationEndpoint {
                 bucket: dst.0.to_string(),
                 prefix: dst.1.to_string(),
+                external: None,
             },
             interval: interval.to_string(),
             batch_size: 100,
             replicate_deletes: false,
+            direction: ReplicationDirection::OneWay,
+            tombstone_retention: "30d".into(),
             conflict: ConflictPolicy::NewerWins,
             strict_content_diff: false,
+            bandwidth_limit_mbps: None,
+            windows: Vec::new(),
             include_globs: Vec::new(),
             exclude_globs: default_exclude_globs(),
         }
@@ -2563,6 +3561,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn replication_validation_checks_external_endpoints() {
+        let ext: ExternalS3Endpoint =
+            serde_yaml::from_str("{endpoint: 'http://10.0.0.5:9000', access_key_id: AK}").unwrap();
+        assert!(ext.hydrated, "hydrated defaults on");
+        let mut push = rule("push", ("releases", ""), ("releases", ""), "1h");
+        push.destination.external = Some(ext);
+        push.direction = ReplicationDirection::Bidirectional;
+        let warnings = validate_replication(&ReplicationConfig {
+            rules: vec![push.clone()],
+            ..Default::default()
+        });
+        let has = |needle: &str| warnings.iter().any(|w| w.contains(needle));
+        assert!(has("refused at runtime"), "{warnings:?}");
+        assert!(has("destination.external.endpoint"), "{warnings:?}");
+        assert!(has("needs both access_key_id"), "{warnings:?}");
+        // A same-named bucket on another endpoint is not a self-loop.
+        assert!(!has("self-loop"), "{warnings:?}");
+
+        let ext = push.destination.external.as_mut().unwrap();
+        ext.allow_local = true;
+        ext.secret_access_key = Some("SK".into());
+        push.direction = ReplicationDirection::OneWay;
+        let warnings = validate_replication(&ReplicationConfig {
+            rules: vec![push],
+            ..Default::default()
+        });
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn replication_endpoint_external_round_trips() {
+        let yaml = "bucket: partner-mirror\nexternal:\n  endpoint: https://s3.partner.example\n  \
+                    region: eu-central-1\n  access_key_id: AK\n  secret_access_key: SK\n  \
+                    hydrated: false\n";
+        let endpoint: ReplicationEndpoint = serde_yaml::from_str(yaml).unwrap();
+        let ext = endpoint.external.as_ref().unwrap();
+        assert!(!ext.hydrated && !ext.allow_local);
+        assert_eq!(
+            endpoint.location(),
+            "https://s3.partner.example/partner-mirror"
+        );
+        let back: ReplicationEndpoint =
+            serde_yaml::from_str(&serde_yaml::to_string(&endpoint).unwrap()).unwrap();
+        assert_eq!(back, endpoint);
+        // Proxy-routed sides serialize without the key.
+        let plain: ReplicationEndpoint = serde_yaml::from_str("bucket: b").unwrap();
+        assert!(!serde_yaml::to_string(&plain).unwrap().contains("external"));
+        assert_eq!(plain.location(), "b");
+    }
+
     #[test]
     fn replication_validation_rejects_too_short_interval() {
         let cfg = ReplicationConfig {
@@ -2672,6 +3721,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn replication_bidirectional_rule_alone_is_not_a_cycle() {
+        let mut cfg = ReplicationConfig {
+            rules: vec![rule("offices", ("berlin", ""), ("lisbon", ""), "1h")],
+            ..Default::default()
+        };
+        cfg.rules[0].direction = ReplicationDirection::Bidirectional;
+        let warnings = validate_replication(&cfg);
+        assert!(
+            warnings.iter().all(|w| !w.contains("cycle")),
+            "a bidirectional rule's own round trip is not a cycle: {:?}",
+            warnings
+        );
+        // ...but a one-way rule closing the loop with it is.
+        cfg.rules
+            .push(rule("back", ("lisbon", ""), ("berlin", ""), "1h"));
+        let warnings = validate_replication(&cfg);
+        assert!(
+            warnings.iter().any(|w| w.contains("cycle")),
+            "expected cycle warning: {:?}",
+            warnings
+        );
+    }
+
+    #[test]
+    fn replication_bidirectional_flags_ignored_fields() {
+        let mut cfg = ReplicationConfig {
+            rules: vec![rule("offices", ("berlin", ""), ("lisbon", ""), "1h")],
+            ..Default::default()
+        };
+        cfg.rules[0].direction = ReplicationDirection::Bidirectional;
+        assert!(validate_replication(&cfg)
+            .iter()
+            .all(|w| !w.contains("ignored")));
+        cfg.rules[0].conflict = ConflictPolicy::SkipIfDestExists;
+        cfg.rules[0].tombstone_retention = "forever".into();
+        let warnings = validate_replication(&cfg);
+        assert!(warnings.iter().any(|w| w.contains("ignored")));
+        assert!(warnings.iter().any(|w| w.contains("tombstone_retention")));
+    }
+
+    #[test]
+    fn replication_direction_defaults_and_round_trips() {
+        let r: ReplicationRule =
+            serde_yaml::from_str("{name: r, source: {bucket: a}, destination: {bucket: b}}")
+                .unwrap();
+        assert_eq!(r.direction, ReplicationDirection::OneWay);
+        assert_eq!(r.tombstone_retention, "30d");
+        // Defaults stay out of the serialized YAML.
+        let yaml = serde_yaml::to_string(&r).unwrap();
+        assert!(!yaml.contains("direction") && !yaml.contains("tombstone_retention"));
+        let r: ReplicationRule = serde_yaml::from_str(
+            "{name: r, source: {bucket: a}, destination: {bucket: b}, direction: bidirectional}",
+        )
+        .unwrap();
+        assert_eq!(r.direction, ReplicationDirection::Bidirectional);
+        assert!(serde_yaml::to_string(&r)
+            .unwrap()
+            .contains("direction: bidirectional"));
+    }
+
     #[test]
     fn replication_validation_rejects_bad_glob() {
         let mut cfg = ReplicationConfig {
@@ -2707,6 +3817,13 @@ mod tests {
             transfers: 4,
             upload_concurrency: 4,
             dir_concurrency: 4,
+            bandwidth_limit_mbps: Some(200),
+            windows: vec![ReplicationWindow {
+                days: "mon-fri".to_string(),
+                start: "22:00".to_string(),
+                end: "06:00".to_string(),
+                timezone: "Europe/Berlin".to_string(),
+            }],
             rules: vec![rule("r", ("a", ""), ("b", ""), "1h")],
         };
         let yaml = serde_yaml::to_string(&cfg).unwrap();
@@ -2885,4 +4002,79 @@ mod tests {
             "expected no warnings, got: {warnings:?}"
         );
     }
+
+    #[test]
+    fn validate_access_events_flags_bad_values_and_no_subscriber() {
+        let mut cfg = EventDeliveryConfig {
+            access_events: AccessEventsConfig {
+                enabled: true,
+                sample_rate: 1.5,
+                retention: "soon".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let warnings = validate_event_delivery(&cfg);
+        for needle in ["sample_rate", "retention", "no endpoint lists"] {
+            assert!(
+                warnings.iter().any(|w| w.contains(needle)),
+                "missing {needle:?} in {warnings:?}"
+            );
+        }
+
+        cfg.access_events.sample_rate = 0.25;
+        cfg.access_events.retention = "7d".to_string();
+        cfg.endpoints = vec![EventEndpoint {
+            name: "dlp".to_string(),
+            enabled: true,
+            url: "https://dlp.example.com/ingest".to_string(),
+            format: EventDeliveryFormat::Raw,
+            headers: Default::default(),
+            signing: None,
+            filter: EventFilter {
+                kinds: vec!["ObjectAccessed".to_string()],
+                ..Default::default()
+            },
+            max_attempts: None,
+            retry_base: None,
+            retry_max: None,
+        }];
+        let warnings = validate_event_delivery(&cfg);
+        assert!(
+            !warnings.iter().any(|w| w.contains("access_events")),
+            "warnings: {warnings:?}"
+        );
+        assert!(!warnings.iter().any(|w| w.contains("unknown kind")));
+    }
+
+    #[test]
+    fn validate_audit_flags_bad_durations_and_missing_archive() {
+        let mut cfg = AuditConfig {
+            enabled: true,
+            retention: "a year".to_string(),
+            ..Default::default()
+        };
+        let warnings = validate_audit(&cfg, None);
+        assert!(warnings.iter().any(|w| w.contains("audit.retention")));
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("without archive_bucket")));
+        assert_eq!(
+            cfg.retention_duration(),
+            std::time::Duration::from_secs(365 * 86_400)
+        );
+
+        cfg.retention = "90d".to_string();
+        cfg.local_retention = "180d".to_string();
+        assert!(validate_audit(&cfg, Some("dgp-sync")).is_empty());
+        assert_eq!(cfg.archive_target(Some("dgp-sync")), Some("dgp-sync"));
+        assert_eq!(cfg.local_retention_duration(), cfg.retention_duration());
+
+        cfg.archive_bucket = Some("audit-archive".to_string());
+        cfg.archive_prefix = "audit".to_string();
+        assert_eq!(cfg.archive_target(Some("dgp-sync")), Some("audit-archive"));
+        let warnings = validate_audit(&cfg, None);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("archive_prefix"));
+    }
 }
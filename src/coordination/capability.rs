@@ -22,6 +22,9 @@ pub const CAPABILITY_DOC_URL: &str =
 pub enum VerifiedVia {
     /// Live two-step `If-None-Match:*` probe ran this boot.
     Probe,
+    /// Live two-step GCS generation-precondition probe
+    /// (`ifGenerationMatch=0` re-write refused with 412) ran this boot.
+    GenerationPrecondition,
 }
 
 /// The capability verdict for one backend.
@@ -104,11 +107,16 @@ pub struct ClientWritableGroup {
     pub probe_bucket: String,
 }
 
-/// Pure projection: which NAMED S3 backends host at least one client-writable
-/// routed bucket, and therefore need a CAS verdict under multi-instance.
+/// Pure projection: which NAMED S3 (or GCS) backends host at least one
+/// client-writable routed bucket, and therefore need a CAS verdict under
+/// multi-instance. GCS backends map the verdict onto generation
+/// preconditions (see [`establish_backend_verdict`]).
 ///
 /// Skipped by design: `replication_target_only` buckets (no client writers),
-/// filesystem backends (per-node local, single-writer by nature), and the
+/// filesystem backends (per-node local, single-writer by nature), Azure
+/// backends (conditional writes are native and not probed), erasure
+/// backends (a write spans several stores, so there is no single
+/// conditional write to probe), and the
 /// DEFAULT backend — it hosts the coordination bucket (`ConfigDbSync` builds
 /// its client from `config.backend`), so the coordination gate already
 /// crash-validates it. Compression policy is deliberately IGNORED: it is
@@ -127,9 +135,12 @@ pub fn client_writable_s3_backends(
         let Some(named) = config.backends.iter().find(|b| b.name == backend_name) else {
             continue; // unknown backend: already warned by Config::check()
         };
+        // The probe speaks S3; Azure Blob honours conditional writes natively.
         if matches!(
             named.backend,
             crate::config::BackendConfig::Filesystem { .. }
+                | crate::config::BackendConfig::AzureBlob { .. }
+                | crate::config::BackendConfig::Erasure { .. }
         ) {
             continue;
         }
@@ -169,6 +180,10 @@ const PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
 /// Shared by the startup gate and the hot-apply pre-commit gate; the CALLER
 /// decides what each verdict means (exit(1) vs reject-apply vs warn).
 ///
+/// S3 backends are probed with `If-None-Match:*`; GCS backends with the
+/// JSON API's `ifGenerationMatch=0` create-only precondition, which is what
+/// a conditional write means there.
+///
 /// NO witness object in the data bucket: a witness there is client-visible,
 /// blocks DeleteBucket with a ghost key, and lands unencrypted on encrypting
 /// backends. The probe is 3 requests once per (backend definition, boot) —
@@ -181,6 +196,9 @@ pub async fn establish_backend_verdict(
     if forced_noncas.contains(name) {
         return CapabilityVerdict::NonCas;
     }
+    if matches!(group.backend, crate::config::BackendConfig::Gcs { .. }) {
+        return establish_gcs_verdict(group).await;
+    }
     let client = match crate::config_db_sync::ConfigDbSync::build_client(&group.backend).await {
         Ok(c) => c,
         Err(e) => {
@@ -221,6 +239,42 @@ pub async fn establish_backend_verdict(
     }
 }
 
+/// GCS flavour of [`establish_backend_verdict`]: same probe key, timeout and
+/// cancel-safe cleanup, speaking the JSON API.
+async fn establish_gcs_verdict(group: &ClientWritableGroup) -> CapabilityVerdict {
+    let backend = match crate::storage::GcsBackend::new(&group.backend).await {
+        Ok(b) => b,
+        Err(e) => {
+            return CapabilityVerdict::Unknown {
+                reason: format!("client build failed: {e}"),
+            }
+        }
+    };
+    let probe_key = format!(".deltaglider/_cwprobe/{}", uuid::Uuid::new_v4());
+    match tokio::time::timeout(
+        PROBE_TIMEOUT,
+        backend.probe_generation_precondition(&group.probe_bucket, &probe_key),
+    )
+    .await
+    {
+        Err(_) => {
+            let _ = tokio::time::timeout(
+                std::time::Duration::from_secs(5),
+                backend.delete_object(&group.probe_bucket, &probe_key),
+            )
+            .await;
+            CapabilityVerdict::Unknown {
+                reason: format!("capability probe timed out ({}s)", PROBE_TIMEOUT.as_secs()),
+            }
+        }
+        Ok(Ok(true)) => CapabilityVerdict::CasVerified {
+            via: VerifiedVia::GenerationPrecondition,
+        },
+        Ok(Ok(false)) => CapabilityVerdict::NonCas,
+        Ok(Err(reason)) => CapabilityVerdict::Unknown { reason },
+    }
+}
+
 /// The single source of the non-CAS enforcement message — used verbatim by the
 /// boot FATAL and the hot-apply refusal so the two can never drift.
 pub fn noncas_enforcement_message(name: &str, buckets: &[String]) -> String {
@@ -311,6 +365,7 @@ pub async fn migrate_target_capability_gate(
     if matches!(
         named.backend,
         crate::config::BackendConfig::Filesystem { .. }
+            | crate::config::BackendConfig::AzureBlob { .. }
     ) {
         return Ok(());
     }
@@ -451,5 +506,34 @@ storage:
         .unwrap();
         assert_eq!(v["verdict"], "cas-verified");
         assert_eq!(v["via"], "probe");
+        let v = serde_json::to_value(CapabilityVerdict::CasVerified {
+            via: VerifiedVia::GenerationPrecondition,
+        })
+        .unwrap();
+        assert_eq!(v["via"], "generation-precondition");
+    }
+
+    #[test]
+    fn projection_includes_gcs_backends_but_not_azure() {
+        let cfg = crate::config::Config::from_yaml_str(
+            r#"
+storage:
+  backends:
+    - name: gcs
+      type: gcs
+      endpoint: "http://127.0.0.1:1"
+    - name: blob
+      type: azure
+      account: acct
+      account_key: "a2V5"
+  buckets:
+    on-gcs: { backend: gcs }
+    on-blob: { backend: blob }
+"#,
+        )
+        .expect("fixture parses");
+        let groups = client_writable_s3_backends(&cfg);
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec!["gcs"]);
+        assert_eq!(groups["gcs"].buckets, vec!["on-gcs"]);
     }
 }
@@ -257,12 +257,36 @@ impl BackendHealthCache {
     }
 }
 
+/// Run a native (non-S3) backend's single-request status probe under
+/// [`HEALTH_PROBE_TIMEOUT`] and map the HTTP status onto a verdict.
+async fn verdict_from_status_probe(
+    probe: impl std::future::Future<Output = Result<u16, String>>,
+) -> HealthVerdict {
+    match tokio::time::timeout(HEALTH_PROBE_TIMEOUT, probe).await {
+        Err(_) => HealthVerdict::Unreachable {
+            detail: format!("probe timed out ({}s)", HEALTH_PROBE_TIMEOUT.as_secs()),
+        },
+        Ok(Err(detail)) => HealthVerdict::Unreachable { detail },
+        Ok(Ok(status)) => match status {
+            200..=299 => HealthVerdict::Healthy,
+            401 | 403 => HealthVerdict::AuthRejected {
+                detail: format!("status={status}"),
+            },
+            _ => HealthVerdict::Erroring {
+                detail: format!("status={status}"),
+            },
+        },
+    }
+}
+
 /// Probe one backend definition's connectivity + auth.
 ///
 /// S3: an authenticated `ListBuckets` under [`HEALTH_PROBE_TIMEOUT`]. If it is
 /// DENIED, fall back to `HeadBucket` on `fallback_bucket` — bucket-scoped
 /// application keys (Backblaze B2) legitimately cannot ListBuckets, and a 404
 /// there still proves the credentials work (authenticated + bucket absent).
+/// Azure / GCS: one authenticated List Containers / buckets.list call;
+/// 401/403 is an auth rejection, 429/5xx is erroring.
 /// Filesystem: the root path must exist and be a directory.
 /// ponytail: fs probe is exists+is_dir; add a write test if silent read-only
 /// mounts ever bite.
@@ -308,6 +332,32 @@ pub async fn probe_backend_health(
             }
             last
         }
+        BackendConfig::AzureBlob { .. } => {
+            let backend = match crate::storage::AzureBlobBackend::new(config).await {
+                Ok(b) => b,
+                Err(e) => {
+                    return HealthVerdict::Unreachable {
+                        detail: format!("client build failed: {e}"),
+                    }
+                }
+            };
+            verdict_from_status_probe(backend.probe_status()).await
+        }
+        BackendConfig::Gcs { .. } => {
+            let backend = match crate::storage::GcsBackend::new(config).await {
+                Ok(b) => b,
+                Err(e) => {
+                    return HealthVerdict::Unreachable {
+                        detail: format!("client build failed: {e}"),
+                    }
+                }
+            };
+            verdict_from_status_probe(backend.probe_status()).await
+        }
+        // Nothing of its own to reach: each shard backend is probed under
+        // its own name, and the erasure backend reads and writes around the
+        // down ones itself.
+        BackendConfig::Erasure { .. } => HealthVerdict::Healthy,
     }
 }
 
@@ -474,6 +524,10 @@ pub struct BackendHealthGate {
 /// per-request timeout storm with an actionable error). Recovery lag is
 /// bounded by the re-probe loop (~30s). Buckets on healthy or never-probed
 /// backends always pass (fail-open — only a definitive verdict gates).
+/// Buckets with a `read_fallback` keep serving reads from their replica
+/// (see [`crate::read_fallback`]); mirrored buckets whose other copy is
+/// healthy pass through to the routing layer, which fails over itself
+/// (see [`crate::storage::mirror`]).
 pub async fn backend_health_gate_middleware(request: Request<Body>, next: Next) -> Response {
     let Some(gate) = request.extensions().get::<BackendHealthGate>().cloned() else {
         return next.run(request).await;
@@ -496,20 +550,41 @@ pub async fn backend_health_gate_middleware(request: Request<Body>, next: Next)
     // operator fixing it). Queueing every S3 request behind that writer
     // would be a self-inflicted global stall; failing open for the apply's
     // duration just restores pre-gate behavior for a few seconds.
-    let resolved = match gate.config.try_read() {
+    let (resolved, fallback, mirror) = match gate.config.try_read() {
         Err(_) => return next.run(request).await,
-        Ok(cfg) => match cfg.buckets.get(&bucket).and_then(|p| p.backend.clone()) {
-            // The literal "default" is the synthesized singleton name
-            // (accepted by check_fatal + the admin API) — same target as an
-            // unrouted bucket.
-            Some(name) if name == "default" => Some(("default".to_string(), cfg.backend.clone())),
-            Some(name) => cfg
-                .backends
-                .iter()
-                .find(|b| b.name == name)
-                .map(|b| (name.clone(), b.backend.clone())),
-            None => Some(("default".to_string(), cfg.backend.clone())),
-        },
+        // A spanned bucket has no single backend to gate on; its members'
+        // errors surface per request.
+        Ok(cfg) if cfg.buckets.get(&bucket).is_some_and(|p| p.span.is_some()) => {
+            return next.run(request).await;
+        }
+        Ok(cfg) => {
+            let policy = cfg.buckets.get(&bucket);
+            let resolved = match policy.and_then(|p| p.backend.clone()) {
+                // The literal "default" is the synthesized singleton name
+                // (accepted by check_fatal + the admin API) — same target as an
+                // unrouted bucket.
+                Some(name) if name == "default" => {
+                    Some(("default".to_string(), cfg.backend.clone()))
+                }
+                Some(name) => cfg
+                    .backends
+                    .iter()
+                    .find(|b| b.name == name)
+                    .map(|b| (name.clone(), b.backend.clone())),
+                None => Some(("default".to_string(), cfg.backend.clone())),
+            };
+            let mirror = policy.and_then(|p| p.mirror.as_ref()).and_then(|m| {
+                cfg.backends
+                    .iter()
+                    .find(|b| b.name == m.backend)
+                    .map(|b| (m.backend.clone(), b.backend.clone(), m.write_quorum))
+            });
+            (
+                resolved,
+                policy.and_then(|p| p.read_fallback.clone()),
+                mirror,
+            )
+        }
     };
     let Some((backend_name, backend_cfg)) = resolved else {
         // Route to an undefined backend: unreachable in practice (check_fatal
@@ -518,6 +593,45 @@ pub async fn backend_health_gate_middleware(request: Request<Body>, next: Next)
     };
     if let Some(verdict) = gate.health.get(&backend_name, &backend_cfg) {
         if verdict.is_gating() {
+            // Mirrored bucket: the other copy serves reads, and takes writes
+            // alone when the quorum allows it.
+            if let Some((mirror_name, mirror_cfg, write_quorum)) = mirror {
+                let mirror_up = !gate
+                    .health
+                    .get(&mirror_name, &mirror_cfg)
+                    .is_some_and(|v| v.is_gating());
+                if mirror_up
+                    && (write_quorum <= 1 || crate::read_fallback::is_read_method(request.method()))
+                {
+                    return next.run(request).await;
+                }
+            }
+            // DR path: reads (and, with `writes: queue`, plain single-object
+            // writes) are served from the replica instead of the 503.
+            if let Some(fallback) = fallback {
+                use crate::bucket_policy::FallbackWrites;
+                use crate::read_fallback::{is_queueable_write, is_read_method};
+                let divert = is_read_method(request.method())
+                    || (fallback.writes == FallbackWrites::Queue
+                        && is_queueable_write(request.method(), request.uri(), request.headers()));
+                if divert {
+                    let mut request = request;
+                    request
+                        .extensions_mut()
+                        .insert(crate::read_fallback::ReadFallbackRoute {
+                            replica: fallback.bucket,
+                        });
+                    return next.run(request).await;
+                }
+                return crate::api::errors::S3Error::ServiceUnavailable(format!(
+                    "bucket '{bucket}' is on backend '{backend_name}', which is currently \
+                     unavailable: {}. Reads are being served from replica bucket '{}'; \
+                     this write is blocked until the backend recovers (re-checked every 30s)",
+                    verdict.cause(),
+                    fallback.bucket
+                ))
+                .into_response();
+            }
             return crate::api::errors::S3Error::ServiceUnavailable(format!(
                 "bucket '{bucket}' is on backend '{backend_name}', which is currently \
                  unavailable: {}. Requests are blocked until the backend recovers \
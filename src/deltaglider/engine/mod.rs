@@ -10,7 +10,9 @@ use super::file_router::FileRouter;
 use crate::config::{BackendConfig, Config};
 use crate::metadata_cache::MetadataCache;
 use crate::metrics::Metrics;
-use crate::storage::{FilesystemBackend, S3Backend, StorageBackend, StorageError};
+use crate::storage::{
+    AzureBlobBackend, FilesystemBackend, GcsBackend, S3Backend, StorageBackend, StorageError,
+};
 use crate::types::{FileMetadata, ObjectKey, StorageInfo, StoreResult};
 use bytes::Bytes;
 use dashmap::DashMap;
@@ -269,6 +271,10 @@ pub struct DeltaGliderEngine<S: StorageBackend> {
     /// the reference read-modify-write so two nodes cannot both create a
     /// `reference.bin` baseline for the same deltaspace and corrupt it.
     reference_lock: Option<Arc<dyn crate::coordination::ReferenceLock>>,
+    /// Durable registry of client multipart uploads (`None` = uploads live
+    /// in the proxy's in-memory `MultipartStore`). See
+    /// [`crate::multipart_registry`].
+    upload_registry: Option<Arc<dyn crate::multipart_registry::UploadRegistry>>,
     /// Optional Prometheus metrics (None in tests).
     metrics: Option<Arc<Metrics>>,
     /// In-memory cache for object metadata (eliminates HEAD requests).
@@ -282,6 +288,18 @@ pub struct DeltaGliderEngine<S: StorageBackend> {
     /// delta GETs decode to a spool file here, then stream the file to the
     /// client — bounded memory regardless of object size.
     spool: Arc<crate::deltaglider::spool::SpoolDir>,
+    /// Read-through disk cache shared by the wrapped backends (None when
+    /// `advanced.disk_cache` is off). Kept here for stats + prefetch.
+    disk_cache: Option<Arc<crate::storage::DiskCache>>,
+    /// Write-through mirrored buckets, shared with the routing backend (None
+    /// when no bucket has a `mirror` policy). Kept here for repair + parity.
+    mirrors: Option<Arc<crate::storage::MirrorSet>>,
+    /// Capacity-spanned buckets, shared with the routing backend (None when
+    /// no bucket has a `span` policy). Kept here for the admin usage view.
+    spans: Option<Arc<crate::storage::SpanSet>>,
+    /// Erasure-coded backends by name (empty when none is configured).
+    /// Kept here for shard repair.
+    erasure: std::collections::HashMap<String, crate::storage::ErasureBackend>,
 }
 
 /// RAII guard for the optional cross-instance reference lock. Held for the
@@ -342,10 +360,47 @@ impl DynEngine {
         // The two layers are mutually exclusive on a given backend: you
         // get ONE of {proxy AES-GCM, SSE-KMS, SSE-S3, none}. The
         // encryption config enum enforces this by construction.
+        //
+        // The optional read-through disk cache slots in BETWEEN the raw
+        // backend and the encryption wrapper, so it holds at-rest bytes
+        // (ciphertext on an encrypted backend) and never plaintext.
+        let disk_cache = if config.disk_cache.is_active() {
+            let path = config.disk_cache.path.clone().unwrap_or_default();
+            Some(
+                crate::storage::DiskCache::open(
+                    path,
+                    crate::storage::DiskCacheLimits::from_config(&config.disk_cache),
+                    metrics.clone(),
+                )
+                .await?,
+            )
+        } else {
+            None
+        };
+        let with_cache =
+            |name: &str, cfg: &BackendConfig, raw: Box<dyn StorageBackend>| match &disk_cache {
+                Some(cache)
+                    if config
+                        .disk_cache
+                        .covers_backend(name, matches!(cfg, BackendConfig::Filesystem { .. })) =>
+                {
+                    tracing::info!("backend '{}': read-through disk cache enabled", name);
+                    Box::new(crate::storage::CachingBackend::new(
+                        name,
+                        raw,
+                        Arc::clone(cache),
+                    )) as Box<dyn StorageBackend>
+                }
+                _ => raw,
+            };
+        let mut mirrors = None;
+        let mut spans = None;
+        let mut erasure = std::collections::HashMap::new();
         let storage: Box<dyn StorageBackend> = if config.backends.is_empty() {
             // Singleton backend path. Synthetic name "default" matches
             // what `apply_backend_encryption_env` uses for this entry.
             let raw = build_raw_backend(&config.backend, &config.backend_encryption).await?;
+            let raw = with_cache("default", &config.backend, raw);
             wrap_backend_with_encryption(
                 "default",
                 raw,
@@ -357,10 +412,77 @@ impl DynEngine {
             // raw (with native-SSE config already baked in), wrapped
             // with its own proxy-AES config if any, then handed to
             // the router.
+            //
+            // Shard members of a `type: erasure` backend are built raw
+            // (no cache, no encryption) and are not routable themselves:
+            // the erasure backend owns them, and is cached and encrypted
+            // like any other entry.
             let mut backends = std::collections::HashMap::new();
             let mut kid_collisions = KeyIdCollisionCheck::new();
+            let shard_names: std::collections::HashSet<&str> = config
+                .backends
+                .iter()
+                .filter_map(|named| match &named.backend {
+                    BackendConfig::Erasure { shards, .. } => Some(shards),
+                    _ => None,
+                })
+                .flatten()
+                .map(String::as_str)
+                .collect();
+            let mut shard_backends: std::collections::HashMap<&str, Arc<dyn StorageBackend>> =
+                std::collections::HashMap::new();
             for named in &config.backends {
-                let raw = build_raw_backend(&named.backend, &named.encryption).await?;
+                if shard_names.contains(named.name.as_str()) {
+                    let raw = build_raw_backend(&named.backend, &named.encryption).await?;
+                    shard_backends.insert(&named.name, Arc::from(raw));
+                }
+            }
+            for named in &config.backends {
+                if shard_names.contains(named.name.as_str()) {
+                    continue;
+                }
+                let raw = match &named.backend {
+                    BackendConfig::Erasure {
+                        data_shards,
+                        parity_shards,
+                        shards,
+                        write_quorum,
+                    } => {
+                        let members = shards
+                            .iter()
+                            .map(|shard| {
+                                shard_backends
+                                    .get(shard.as_str())
+                                    .map(|b| (shard.clone(), Arc::clone(b)))
+                                    .ok_or_else(|| {
+                                        StorageError::Other(format!(
+                                            "erasure backend '{}': unknown shard backend '{shard}'",
+                                            named.name
+                                        ))
+                                    })
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+                        let ec = crate::storage::ErasureBackend::new(
+                            &named.name,
+                            members,
+                            *data_shards,
+                            *parity_shards,
+                            *write_quorum,
+                            metrics.clone(),
+                        )?;
+                        tracing::info!(
+                            "backend '{}': erasure coded {}+{} across {}",
+                            named.name,
+                            data_shards,
+                            parity_shards,
+                            shards.join(", ")
+                        );
+                        erasure.insert(named.name.clone(), ec.clone());
+                        Box::new(ec) as Box<dyn StorageBackend>
+                    }
+                    _ => build_raw_backend(&named.backend, &named.encryption).await?,
+                };
+                let raw = with_cache(&named.name, &named.backend, raw);
                 let wrapped = wrap_backend_with_encryption(
                     &named.name,
                     raw,
@@ -380,14 +502,101 @@ impl DynEngine {
             );
             let routes = registry.routing_table();
 
-            Box::new(crate::storage::RoutingBackend::new(
-                backends,
-                routes,
-                default_name,
-            )?)
+            let mirror_routes: std::collections::HashMap<_, _> = config
+                .buckets
+                .iter()
+                .filter_map(|(bucket, policy)| {
+                    let mirror = policy.mirror.as_ref()?;
+                    let primary_bucket = policy.alias.clone().unwrap_or_else(|| bucket.clone());
+                    let route = crate::storage::MirrorRoute {
+                        primary: policy
+                            .backend
+                            .clone()
+                            .unwrap_or_else(|| default_name.clone()),
+                        mirror_bucket: mirror
+                            .alias
+                            .clone()
+                            .unwrap_or_else(|| primary_bucket.clone()),
+                        primary_bucket,
+                        mirror: mirror.backend.clone(),
+                        write_quorum: mirror.write_quorum,
+                    };
+                    Some((bucket.clone(), route))
+                })
+                .collect();
+            let span_routes: std::collections::HashMap<_, _> = config
+                .buckets
+                .iter()
+                .filter_map(|(bucket, policy)| {
+                    let span = policy.span.as_ref()?;
+                    let real = policy.alias.clone().unwrap_or_else(|| bucket.clone());
+                    let members = span
+                        .backends
+                        .iter()
+                        .map(|m| crate::storage::SpanMemberRoute {
+                            backend: m.backend.clone(),
+                            real_bucket: m.alias.clone().unwrap_or_else(|| real.clone()),
+                            capacity_bytes: m.capacity_bytes,
+                        })
+                        .collect();
+                    Some((bucket.clone(), crate::storage::SpanRoute { members }))
+                })
+                .collect();
+            let hedge_routes: std::collections::HashMap<_, _> = config
+                .buckets
+                .iter()
+                .filter_map(|(bucket, policy)| {
+                    let hedge = policy.hedge.as_ref()?;
+                    let copy = if policy.mirror.is_some() {
+                        crate::storage::HedgeCopy::Mirror
+                    } else {
+                        crate::storage::HedgeCopy::Replica(
+                            policy.read_fallback.as_ref()?.bucket.clone(),
+                        )
+                    };
+                    let route = crate::storage::HedgeRoute {
+                        copy,
+                        after: hedge.after_ms.map(std::time::Duration::from_millis),
+                    };
+                    Some((bucket.clone(), route))
+                })
+                .collect();
+            let mut routing =
+                crate::storage::RoutingBackend::new(backends.clone(), routes, default_name)?;
+            if !hedge_routes.is_empty() {
+                let set = crate::storage::HedgeSet::new(hedge_routes, metrics.clone());
+                tracing::info!("hedged reads: {}", set.buckets().join(", "));
+                routing = routing.with_hedges(Arc::new(set));
+            }
+            if !span_routes.is_empty() {
+                let set = Arc::new(crate::storage::SpanSet::new(
+                    backends.clone(),
+                    span_routes,
+                    metrics.clone(),
+                )?);
+                tracing::info!("capacity-spanned buckets: {}", set.buckets().join(", "));
+                routing = routing.with_spans(Arc::clone(&set));
+                spans = Some(set);
+            }
+            if !mirror_routes.is_empty() {
+                let set = Arc::new(crate::storage::MirrorSet::new(
+                    backends,
+                    mirror_routes,
+                    metrics.clone(),
+                )?);
+                tracing::info!("write-through mirrors: {}", set.buckets().join(", "));
+                routing = routing.with_mirrors(Arc::clone(&set));
+                mirrors = Some(set);
+            }
+            Box::new(routing)
         };
 
-        Ok(Self::new_with_backend(Arc::new(storage), config, metrics))
+        let mut engine = Self::new_with_backend(Arc::new(storage), config, metrics);
+        engine.disk_cache = disk_cache;
+        engine.mirrors = mirrors;
+        engine.spans = spans;
+        engine.erasure = erasure;
+        Ok(engine)
     }
 }
 
@@ -418,20 +627,26 @@ fn native_encryption_for(
 /// Build ONE storage backend from a `BackendConfig` variant + its
 /// encryption config. Native SSE modes are baked into the S3 client
 /// here; proxy-AES encryption is layered on top by
-/// `wrap_backend_with_encryption`. Filesystem backends ignore native
-/// modes (rejected at `Config::check` time).
+/// `wrap_backend_with_encryption`. Filesystem, Azure and GCS backends ignore
+/// native modes (rejected at `Config::check` time).
 async fn build_raw_backend(
     cfg: &BackendConfig,
     enc: &crate::config::BackendEncryptionConfig,
 ) -> Result<Box<dyn StorageBackend>, StorageError> {
     match cfg {
-        BackendConfig::Filesystem { path } => {
-            Ok(Box::new(FilesystemBackend::new(path.clone()).await?))
-        }
+        BackendConfig::Filesystem { path, metadata } => Ok(Box::new(
+            FilesystemBackend::with_metadata_mode(path.clone(), *metadata).await?,
+        )),
         BackendConfig::S3 { .. } => {
             let native = native_encryption_for(enc);
             Ok(Box::new(S3Backend::new(cfg, native).await?))
         }
+        BackendConfig::AzureBlob { .. } => Ok(Box::new(AzureBlobBackend::new(cfg).await?)),
+        BackendConfig::Gcs { .. } => Ok(Box::new(GcsBackend::new(cfg).await?)),
+        BackendConfig::Erasure { .. } => Err(StorageError::Other(
+            "an erasure backend is built from its shard backends and needs a storage.backends list"
+                .to_string(),
+        )),
     }
 }
 
@@ -727,6 +942,7 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
             codec_semaphore: Arc::new(Semaphore::new(codec_concurrency)),
             prefix_locks: DashMap::new(),
             reference_lock: None,
+            upload_registry: None,
             metrics,
             metadata_cache: MetadataCache::new((config.metadata_cache_mb as u64) * 1024 * 1024),
             bucket_policies: crate::bucket_policy::BucketPolicyRegistry::new(
@@ -738,6 +954,10 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
                 crate::deltaglider::spool::SpoolDir::from_env()
                     .unwrap_or_else(|e| panic!("failed to init spool dir: {e}")),
             ),
+            disk_cache: None,
+            mirrors: None,
+            spans: None,
+            erasure: std::collections::HashMap::new(),
         }
     }
 
@@ -764,6 +984,18 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         self
     }
 
+    /// Attach the durable multipart registry (builder; re-attached on engine
+    /// rebuild, mirroring `with_reference_lock`). `None` keeps client
+    /// multipart uploads in the proxy's in-memory `MultipartStore`, except
+    /// on backends that stage them themselves.
+    pub fn with_upload_registry(
+        mut self,
+        registry: Option<Arc<dyn crate::multipart_registry::UploadRegistry>>,
+    ) -> Self {
+        self.upload_registry = registry;
+        self
+    }
+
     /// Best-effort: fold a stored object into the bucket counter. Never fails
     /// the S3 path. Applies the NET delta the store path captured:
     /// - new object: +1 / +logical / +stored
@@ -931,6 +1163,25 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         ) && self.storage.supports_native_multipart(bucket)
     }
 
+    /// True when client multipart uploads to `bucket` are staged on the
+    /// storage backend (surviving a restart) rather than buffered in the
+    /// proxy's `MultipartStore`: the backend persists them itself, or the
+    /// upload registry tracks them.
+    pub fn stages_client_multipart(&self, bucket: &str) -> bool {
+        self.storage.persists_multipart_uploads(bucket) || self.upload_registry.is_some()
+    }
+
+    /// The upload registry, when it (not the backend) tracks `bucket`'s
+    /// staged uploads.
+    fn registry_for(
+        &self,
+        bucket: &str,
+    ) -> Option<&Arc<dyn crate::multipart_registry::UploadRegistry>> {
+        self.upload_registry
+            .as_ref()
+            .filter(|_| !self.storage.persists_multipart_uploads(bucket))
+    }
+
     /// True when a lite LIST of `bucket` carries trustworthy logical facts
     /// (real user_metadata + plaintext size/etag). False → parity must HEAD
     /// every key for ownership + logical size (S3, or an encrypting backend).
@@ -938,6 +1189,12 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         self.storage.lite_list_carries_logical_facts(bucket)
     }
 
+    /// True when writes to `bucket` encrypt at rest and stamp the key id, so
+    /// a raw rewrite keeps the blob encrypted.
+    pub fn stamps_encryption_key_id(&self, bucket: &str) -> bool {
+        self.storage.stamps_encryption_key_id(bucket)
+    }
+
     /// Return the number of entries in the reference cache (O(1) atomic read).
     pub fn cache_entry_count(&self) -> u64 {
         self.cache.entry_count()
@@ -953,6 +1210,28 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         self.cache.max_capacity_bytes()
     }
 
+    /// The read-through disk cache, when `advanced.disk_cache` is enabled.
+    pub fn disk_cache(&self) -> Option<&Arc<crate::storage::DiskCache>> {
+        self.disk_cache.as_ref()
+    }
+
+    /// The write-through mirrored buckets, when any bucket has a `mirror`.
+    pub fn mirror_set(&self) -> Option<&Arc<crate::storage::MirrorSet>> {
+        self.mirrors.as_ref()
+    }
+
+    /// The capacity-spanned buckets, when any bucket has a `span`.
+    pub fn span_set(&self) -> Option<&Arc<crate::storage::SpanSet>> {
+        self.spans.as_ref()
+    }
+
+    /// The erasure-coded backends, by name.
+    pub fn erasure_backends(
+        &self,
+    ) -> &std::collections::HashMap<String, crate::storage::ErasureBackend> {
+        &self.erasure
+    }
+
     /// Return available codec semaphore permits.
     pub fn codec_available_permits(&self) -> usize {
         self.codec_semaphore.available_permits()
@@ -1220,11 +1499,22 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
     /// Like `validated_key` but stricter — the INGEST (PUT) gate. Rejects `//`
     /// so a malformed key can't be STORED; reads/deletes keep using
     /// `validated_key` so pre-existing `//` objects stay reachable for cleanup.
-    fn validated_key_ingest(bucket: &str, key: &str) -> Result<(ObjectKey, String), EngineError> {
+    /// Also rejects names the bucket's backend keeps its own files under.
+    fn validated_key_ingest(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<(ObjectKey, String), EngineError> {
         let obj_key = ObjectKey::parse(bucket, key);
         obj_key
             .validate_ingest()
             .map_err(|e| EngineError::InvalidArgument(e.to_string()))?;
+        if self.storage.reserves_filename(bucket, &obj_key.filename) {
+            return Err(EngineError::InvalidArgument(format!(
+                "Key name '{}' is reserved by the bucket's storage backend",
+                obj_key.filename
+            )));
+        }
         let deltaspace_id = obj_key.deltaspace_id();
         Ok((obj_key, deltaspace_id))
     }
@@ -1456,6 +1746,7 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
             self.list_objects_bulk(bucket, prefix, delimiter, max_keys, continuation_token)
                 .await?
         };
+        self.hide_staged_parts(bucket, delimiter, &mut page).await?;
 
         // Even without metadata=true, use the metadata cache to correct
         // file_size for delta objects. The lite LIST returns delta (stored) size,
@@ -1527,6 +1818,54 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         Ok(page)
     }
 
+    /// Drop the staged parts of registry multipart uploads
+    /// (`multipart_registry::STAGING_PREFIX`) from a listing page: they live
+    /// in the bucket but are not objects. A CommonPrefix above the staging
+    /// area (`.deltaglider/` in a root listing) stays only if something else
+    /// lives under it. The page may come out short; pagination is unchanged.
+    async fn hide_staged_parts(
+        &self,
+        bucket: &str,
+        delimiter: Option<&str>,
+        page: &mut ListObjectsPage,
+    ) -> Result<(), EngineError> {
+        let staging = format!("{}/", crate::multipart_registry::STAGING_PREFIX);
+        page.objects.retain(|(key, _)| !key.starts_with(&staging));
+        let mut common_prefixes = Vec::with_capacity(page.common_prefixes.len());
+        for cp in std::mem::take(&mut page.common_prefixes) {
+            let hidden = cp.starts_with(&staging)
+                || (staging.starts_with(&cp)
+                    && !self.lists_anything_under(bucket, &cp, delimiter).await?);
+            if !hidden {
+                common_prefixes.push(cp);
+            }
+        }
+        page.common_prefixes = common_prefixes;
+        Ok(())
+    }
+
+    /// Does a listing of `prefix` show anything once staged parts are hidden?
+    async fn lists_anything_under(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        delimiter: Option<&str>,
+    ) -> Result<bool, EngineError> {
+        let mut token: Option<String> = None;
+        loop {
+            let page =
+                Box::pin(self.list_objects(bucket, prefix, delimiter, 2, token.as_deref(), false))
+                    .await?;
+            if !page.objects.is_empty() || !page.common_prefixes.is_empty() {
+                return Ok(true);
+            }
+            match page.next_continuation_token {
+                Some(next) if page.is_truncated => token = Some(next),
+                _ => return Ok(false),
+            }
+        }
+    }
+
     /// Return the `reference.bin` metadata for every deltaspace whose
     /// prefix begins with `scope_prefix` in the given bucket, plus a
     /// `truncated` flag set when the scan hit `limit` matching
@@ -146,7 +146,7 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
                 let stored_name = &metadata.original_name;
                 let stream = self
                     .storage
-                    .get_passthrough_stream(bucket, deltaspace_id, stored_name)
+                    .get_passthrough_stream_hedged(bucket, deltaspace_id, stored_name, &metadata)
                     .await?;
                 debug!("Streaming passthrough file for {}", obj_key.full_key());
                 Ok(RetrieveResponse::Streamed {
@@ -499,7 +499,14 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
                 let stored_name = &metadata.original_name;
                 let range_result = self
                     .storage
-                    .get_passthrough_stream_range(bucket, &deltaspace_id, stored_name, start, end)
+                    .get_passthrough_stream_range_hedged(
+                        bucket,
+                        &deltaspace_id,
+                        stored_name,
+                        start,
+                        end,
+                        &metadata,
+                    )
                     .await;
                 let (stream, content_length) = match range_result {
                     Ok(v) => v,
@@ -520,12 +527,13 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
                             StorageInfo::Passthrough => {
                                 let (stream, content_length) = self
                                     .storage
-                                    .get_passthrough_stream_range(
+                                    .get_passthrough_stream_range_hedged(
                                         bucket,
                                         &deltaspace_id,
                                         &fresh_meta.original_name,
                                         start,
                                         end,
+                                        &fresh_meta,
                                     )
                                     .await?;
                                 return if content_length == 0 {
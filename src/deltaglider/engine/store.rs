@@ -3,7 +3,10 @@
 //! Store pipeline — delta encoding, passthrough, and baseline management.
 
 use super::*;
-use crate::storage::{MultipartUpload, StorageBackend, UploadedPart};
+use crate::storage::{
+    MultipartUpload, StagedMultipartUpload, StagedPart, StagedSweepReport, StorageBackend,
+    UploadedPart,
+};
 use md5::{Digest, Md5};
 use sha2::Sha256;
 use std::path::{Path, PathBuf};
@@ -104,7 +107,7 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
             });
         }
 
-        let (obj_key, deltaspace_id) = Self::validated_key_ingest(bucket, key)?;
+        let (obj_key, deltaspace_id) = self.validated_key_ingest(bucket, key)?;
 
         // Usage-counter accounting: capture the PRIOR object metadata (S3 PUT is
         // an upsert) so the counter nets an overwrite to +0 instead of double-
@@ -340,7 +343,7 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         use tokio::io::AsyncReadExt;
 
         self.metadata_cache.invalidate(bucket, key);
-        let (obj_key, deltaspace_id) = Self::validated_key_ingest(bucket, key)?;
+        let (obj_key, deltaspace_id) = self.validated_key_ingest(bucket, key)?;
         // Size ceiling depends on the STRATEGY: a delta-eligible object is
         // bounded by max_object_size (it will be xdelta3-encoded in RAM); a
         // passthrough object streams from the spool and is bounded by the far
@@ -996,7 +999,7 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         // Invalidate stale metadata on overwrite
         self.metadata_cache.invalidate(bucket, key);
 
-        let (obj_key, deltaspace_id) = Self::validated_key_ingest(bucket, key)?;
+        let (obj_key, deltaspace_id) = self.validated_key_ingest(bucket, key)?;
 
         // Compute SHA256 + MD5 incrementally across chunks
         let mut sha256_hasher = Sha256::new();
@@ -1067,7 +1070,7 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         self.ensure_within_passthrough_ceiling(total_size)?;
 
         self.metadata_cache.invalidate(bucket, key);
-        let (obj_key, deltaspace_id) = Self::validated_key_ingest(bucket, key)?;
+        let (obj_key, deltaspace_id) = self.validated_key_ingest(bucket, key)?;
 
         let mut sha256_hasher = Sha256::new();
         let mut md5_hasher = Md5::new();
@@ -1150,7 +1153,7 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         self.ensure_within_passthrough_ceiling(total_size)?;
 
         self.metadata_cache.invalidate(bucket, key);
-        let (obj_key, deltaspace_id) = Self::validated_key_ingest(bucket, key)?;
+        let (obj_key, deltaspace_id) = self.validated_key_ingest(bucket, key)?;
 
         let mut file = tokio::fs::File::open(source_path)
             .await
@@ -1232,7 +1235,7 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         self.ensure_within_passthrough_ceiling(total_size)?;
 
         self.metadata_cache.invalidate(bucket, key);
-        let (obj_key, deltaspace_id) = Self::validated_key_ingest(bucket, key)?;
+        let (obj_key, deltaspace_id) = self.validated_key_ingest(bucket, key)?;
         let guard = self.acquire_prefix_lock(&deltaspace_id).await;
 
         // The create call needs metadata headers (content-type, user
@@ -1376,6 +1379,560 @@ impl<S: StorageBackend> DeltaGliderEngine<S> {
         }
     }
 
+    // === Client multipart uploads staged on the backend ===
+    //
+    // Used instead of the proxy's in-memory `MultipartStore` when
+    // [`Self::stages_client_multipart`] holds for the bucket. Either the
+    // backend persists the upload itself (`persists_multipart_uploads`), or
+    // the upload registry records it and each part is stored as a
+    // passthrough object under `multipart_registry::staging_prefix`. Both
+    // survive a restart; only the registry's are visible to other nodes.
+
+    /// Is `staged` tracked by the upload registry (rather than held by a
+    /// backend upload)? Registry uploads carry `native: false` and the
+    /// visible bucket name.
+    fn is_registry_upload(staged: &StagedMultipartUpload) -> bool {
+        !staged.upload.native
+    }
+
+    fn registry(&self) -> Result<&Arc<dyn crate::multipart_registry::UploadRegistry>, EngineError> {
+        self.upload_registry.as_ref().ok_or_else(|| {
+            EngineError::Storage(StorageError::Other(
+                "multipart upload registry is not attached".to_string(),
+            ))
+        })
+    }
+
+    fn registry_error(e: String) -> EngineError {
+        EngineError::Storage(StorageError::Other(format!("multipart registry: {e}")))
+    }
+
+    /// The staged-upload view of a registry manifest. `None` for a key that
+    /// no longer validates.
+    fn staged_from_manifest(
+        manifest: crate::multipart_registry::UploadManifest,
+    ) -> Option<StagedMultipartUpload> {
+        let (obj_key, deltaspace_id) = Self::validated_key(&manifest.bucket, &manifest.key).ok()?;
+        let mut metadata = FileMetadata::new_passthrough(
+            obj_key.filename.clone(),
+            String::new(),
+            String::new(),
+            0,
+            manifest.content_type,
+        );
+        metadata.user_metadata = manifest.user_metadata;
+        Some(StagedMultipartUpload {
+            upload: MultipartUpload {
+                bucket: manifest.bucket,
+                upload_id: manifest.upload_id,
+                native: false,
+                backend: None,
+            },
+            prefix: deltaspace_id,
+            filename: obj_key.filename,
+            metadata,
+            initiated: manifest.initiated,
+        })
+    }
+
+    /// Start a staged multipart upload to `bucket/key`; returns its id.
+    pub async fn create_staged_multipart(
+        &self,
+        bucket: &str,
+        key: &str,
+        content_type: Option<String>,
+        user_metadata: HashMap<String, String>,
+    ) -> Result<String, EngineError> {
+        let (obj_key, deltaspace_id) = self.validated_key_ingest(bucket, key)?;
+        if let Some(registry) = self.registry_for(bucket) {
+            let manifest = crate::multipart_registry::UploadManifest {
+                upload_id: crate::multipart_registry::new_upload_id(),
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+                content_type,
+                user_metadata,
+                initiated: chrono::Utc::now(),
+            };
+            registry
+                .create(&manifest)
+                .await
+                .map_err(Self::registry_error)?;
+            return Ok(manifest.upload_id);
+        }
+        let mut create_meta = FileMetadata::new_passthrough(
+            obj_key.filename.clone(),
+            String::new(),
+            String::new(),
+            0,
+            content_type,
+        );
+        create_meta.user_metadata = user_metadata;
+        let upload = self
+            .storage
+            .create_multipart_upload(bucket, &deltaspace_id, &obj_key.filename, &create_meta)
+            .await?;
+        Ok(upload.upload_id)
+    }
+
+    /// Re-open staged upload `upload_id` of `bucket/key`. `None` when
+    /// neither the backend nor the registry holds such an upload for that
+    /// key.
+    pub async fn open_staged_multipart(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+    ) -> Result<Option<StagedMultipartUpload>, EngineError> {
+        let (obj_key, deltaspace_id) = Self::validated_key(bucket, key)?;
+        if let Some(registry) = self.registry_for(bucket) {
+            if !crate::multipart_registry::is_valid_upload_id(upload_id) {
+                return Ok(None);
+            }
+            let manifest = registry
+                .get(upload_id)
+                .await
+                .map_err(Self::registry_error)?;
+            return Ok(manifest
+                .filter(|m| m.bucket == bucket && m.key == key)
+                .and_then(Self::staged_from_manifest));
+        }
+        match self
+            .storage
+            .resume_multipart_upload(bucket, &deltaspace_id, &obj_key.filename, upload_id)
+            .await
+        {
+            Ok(staged) => Ok(Some(staged)),
+            Err(StorageError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn upload_staged_part(
+        &self,
+        staged: &StagedMultipartUpload,
+        part_number: i32,
+        data: Bytes,
+    ) -> Result<UploadedPart, EngineError> {
+        if !Self::is_registry_upload(staged) {
+            return Ok(self
+                .storage
+                .upload_part(
+                    &staged.upload,
+                    &staged.prefix,
+                    &staged.filename,
+                    part_number,
+                    data,
+                )
+                .await?);
+        }
+        let filename = crate::multipart_registry::part_filename(part_number as u32);
+        let md5 = hex::encode(Md5::digest(&data));
+        let metadata = FileMetadata::new_passthrough(
+            filename.clone(),
+            hex::encode(Sha256::digest(&data)),
+            md5.clone(),
+            data.len() as u64,
+            None,
+        );
+        self.storage
+            .put_passthrough(
+                &staged.upload.bucket,
+                &crate::multipart_registry::staging_prefix(&staged.upload.upload_id),
+                &filename,
+                &data,
+                &metadata,
+            )
+            .await?;
+        self.registry()?
+            .put_part(
+                &staged.upload.upload_id,
+                &crate::multipart_registry::ManifestPart {
+                    part_number: part_number as u32,
+                    md5: md5.clone(),
+                    size: data.len() as u64,
+                    last_modified: chrono::Utc::now(),
+                },
+            )
+            .await
+            .map_err(Self::registry_error)?;
+        Ok(UploadedPart {
+            part_number,
+            etag: format!("\"{md5}\""),
+        })
+    }
+
+    pub async fn list_staged_parts(
+        &self,
+        staged: &StagedMultipartUpload,
+    ) -> Result<Vec<StagedPart>, EngineError> {
+        if !Self::is_registry_upload(staged) {
+            return Ok(self
+                .storage
+                .list_multipart_parts(&staged.upload, &staged.prefix, &staged.filename)
+                .await?);
+        }
+        Ok(self
+            .registry()?
+            .parts(&staged.upload.upload_id)
+            .await
+            .map_err(Self::registry_error)?
+            .into_iter()
+            .map(|p| StagedPart {
+                part_number: p.part_number as i32,
+                etag: format!("\"{}\"", p.md5),
+                size: p.size,
+                last_modified: p.last_modified,
+            })
+            .collect())
+    }
+
+    /// Read back one registry-staged part, checked against the MD5 recorded
+    /// when it was uploaded.
+    async fn read_registry_part(
+        &self,
+        staged: &StagedMultipartUpload,
+        part_number: i32,
+        expected_etag: &str,
+    ) -> Result<Bytes, EngineError> {
+        let data = self
+            .storage
+            .get_passthrough(
+                &staged.upload.bucket,
+                &crate::multipart_registry::staging_prefix(&staged.upload.upload_id),
+                &crate::multipart_registry::part_filename(part_number as u32),
+            )
+            .await?;
+        let actual = hex::encode(Md5::digest(&data));
+        if actual != expected_etag.trim_matches('"') {
+            return Err(EngineError::Storage(StorageError::Other(format!(
+                "staged part {} of upload {} failed its integrity check",
+                part_number, staged.upload.upload_id
+            ))));
+        }
+        Ok(Bytes::from(data))
+    }
+
+    pub async fn read_staged_part(
+        &self,
+        staged: &StagedMultipartUpload,
+        part_number: i32,
+    ) -> Result<Bytes, EngineError> {
+        if !Self::is_registry_upload(staged) {
+            return Ok(self
+                .storage
+                .read_multipart_part(
+                    &staged.upload,
+                    &staged.prefix,
+                    &staged.filename,
+                    part_number,
+                )
+                .await?);
+        }
+        let parts = self.list_staged_parts(staged).await?;
+        let part = parts
+            .iter()
+            .find(|p| p.part_number == part_number)
+            .ok_or_else(|| {
+                EngineError::NotFound(format!(
+                    "part {} of upload {}",
+                    part_number, staged.upload.upload_id
+                ))
+            })?;
+        self.read_registry_part(staged, part_number, &part.etag)
+            .await
+    }
+
+    pub async fn abort_staged_multipart(
+        &self,
+        staged: &StagedMultipartUpload,
+    ) -> Result<(), EngineError> {
+        if !Self::is_registry_upload(staged) {
+            return Ok(self
+                .storage
+                .abort_multipart_upload(&staged.upload, &staged.prefix, &staged.filename)
+                .await?);
+        }
+        let registry = self.registry()?;
+        let upload_id = &staged.upload.upload_id;
+        let prefix = crate::multipart_registry::staging_prefix(upload_id);
+        // Parts first, the registry entry last: an interrupted abort leaves
+        // an upload the janitor still finds.
+        for part in registry
+            .parts(upload_id)
+            .await
+            .map_err(Self::registry_error)?
+        {
+            self.delete_passthrough_idempotent(
+                &staged.upload.bucket,
+                &prefix,
+                &crate::multipart_registry::part_filename(part.part_number),
+            )
+            .await?;
+        }
+        registry
+            .remove(upload_id)
+            .await
+            .map_err(Self::registry_error)
+    }
+
+    /// Claim a registry upload for completion (see
+    /// `UploadRegistry::claim_completion`), so only one request — on any
+    /// node sharing the registry — assembles it. `false` when another
+    /// holds the claim or the upload is gone. Backend-held uploads are
+    /// guarded by the backend and always claim.
+    pub async fn claim_staged_completion(
+        &self,
+        staged: &StagedMultipartUpload,
+    ) -> Result<bool, EngineError> {
+        if !Self::is_registry_upload(staged) {
+            return Ok(true);
+        }
+        self.registry()?
+            .claim_completion(&staged.upload.upload_id)
+            .await
+            .map_err(Self::registry_error)
+    }
+
+    /// Drop the claim of a completion that failed, leaving the upload staged
+    /// for a retry or an abort.
+    pub async fn release_staged_completion(&self, staged: &StagedMultipartUpload) {
+        if !Self::is_registry_upload(staged) {
+            return;
+        }
+        let released = match self.registry() {
+            Ok(registry) => registry
+                .release_completion(&staged.upload.upload_id)
+                .await
+                .map_err(Self::registry_error),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = released {
+            warn!(
+                "Failed to release the completion claim of multipart upload {}: {}",
+                staged.upload.upload_id, e
+            );
+        }
+    }
+
+    /// Is a completion of this registry upload in progress on any node?
+    pub async fn staged_completion_claimed(
+        &self,
+        staged: &StagedMultipartUpload,
+    ) -> Result<bool, EngineError> {
+        if !Self::is_registry_upload(staged) {
+            return Ok(false);
+        }
+        self.registry()?
+            .completion_claimed(&staged.upload.upload_id)
+            .await
+            .map_err(Self::registry_error)
+    }
+
+    /// Complete a staged upload as a passthrough object (`parts` in
+    /// part-number order, already validated). A backend-held upload is
+    /// assembled by the backend, which computes the whole-object hashes as
+    /// it goes; a registry upload is spooled locally (each part verified
+    /// against its MD5) and stored like a relayed upload, after which its
+    /// staging is dropped. On failure the upload is left staged so the
+    /// client can retry or abort.
+    pub async fn complete_staged_multipart(
+        &self,
+        bucket: &str,
+        key: &str,
+        staged: &StagedMultipartUpload,
+        parts: &[UploadedPart],
+        total_size: u64,
+        multipart_etag: String,
+    ) -> Result<StoreResult, EngineError> {
+        self.ensure_within_passthrough_ceiling(total_size)?;
+        if Self::is_registry_upload(staged) {
+            return self
+                .complete_registry_multipart(bucket, key, staged, parts, total_size, multipart_etag)
+                .await;
+        }
+
+        self.metadata_cache.invalidate(bucket, key);
+        let (obj_key, deltaspace_id) = self.validated_key_ingest(bucket, key)?;
+        let _guard = self.acquire_prefix_lock(&deltaspace_id).await;
+        let prior_for_counter = self.prior_for_counter(bucket, key).await;
+
+        let mut metadata = staged.metadata.clone();
+        metadata.file_size = total_size;
+        metadata.created_at = chrono::Utc::now();
+        metadata.multipart_etag = Some(multipart_etag);
+        self.storage
+            .complete_multipart_upload(
+                &staged.upload,
+                &deltaspace_id,
+                &obj_key.filename,
+                parts,
+                &[],
+                &metadata,
+            )
+            .await?;
+        // Re-read to pick up the hashes computed during assembly.
+        let metadata = self
+            .storage
+            .get_passthrough_metadata(bucket, &deltaspace_id, &obj_key.filename)
+            .await?;
+
+        if let Err(e) = self
+            .delete_delta_idempotent(bucket, &deltaspace_id, &obj_key.filename)
+            .await
+        {
+            warn!(
+                "Failed to clean up old delta after staged multipart write: {}",
+                e
+            );
+        }
+
+        let result = StoreResult::new(metadata, total_size).with_accounting(prior_for_counter, 0);
+        self.metadata_cache
+            .insert(bucket, key, result.metadata.clone());
+        self.record_store(bucket, &result);
+        Ok(result)
+    }
+
+    async fn complete_registry_multipart(
+        &self,
+        bucket: &str,
+        key: &str,
+        staged: &StagedMultipartUpload,
+        parts: &[UploadedPart],
+        total_size: u64,
+        multipart_etag: String,
+    ) -> Result<StoreResult, EngineError> {
+        use tokio::io::AsyncWriteExt;
+
+        let spool = self
+            .spool
+            .acquire(total_size)
+            .await
+            .map_err(StorageError::from)?;
+        let mut file = tokio::fs::File::create(spool.path())
+            .await
+            .map_err(StorageError::from)?;
+        for part in parts {
+            let data = self
+                .read_registry_part(staged, part.part_number, &part.etag)
+                .await?;
+            file.write_all(&data).await.map_err(StorageError::from)?;
+        }
+        file.flush().await.map_err(StorageError::from)?;
+        drop(file);
+
+        let result = self
+            .store_passthrough_file_with_multipart_etag(
+                bucket,
+                key,
+                spool.path(),
+                total_size,
+                staged.metadata.content_type.clone(),
+                staged.metadata.user_metadata.clone(),
+                multipart_etag,
+            )
+            .await?;
+        if let Err(e) = self.abort_staged_multipart(staged).await {
+            warn!(
+                "Failed to drop staging of completed multipart upload {}: {}",
+                staged.upload.upload_id, e
+            );
+        }
+        Ok(result)
+    }
+
+    /// Staged uploads of `bucket` that were neither completed nor aborted.
+    pub async fn list_staged_multipart_uploads(
+        &self,
+        bucket: &str,
+    ) -> Result<Vec<StagedMultipartUpload>, EngineError> {
+        if let Some(registry) = self.registry_for(bucket) {
+            return Ok(registry
+                .list()
+                .await
+                .map_err(Self::registry_error)?
+                .into_iter()
+                .filter(|m| m.bucket == bucket)
+                .filter_map(Self::staged_from_manifest)
+                .collect());
+        }
+        Ok(self.storage.list_multipart_uploads(bucket).await?)
+    }
+
+    /// Abort staged uploads idle for longer than `idle`: those of every
+    /// backend, and the registry's (idle = since the last part, else since
+    /// the upload started).
+    pub async fn sweep_staged_multipart_uploads(
+        &self,
+        idle: std::time::Duration,
+    ) -> Result<StagedSweepReport, EngineError> {
+        let mut report = self.storage.sweep_multipart_uploads(idle).await?;
+        let Some(registry) = self.upload_registry.as_ref() else {
+            return Ok(report);
+        };
+        let cutoff =
+            chrono::Utc::now() - chrono::Duration::from_std(idle).unwrap_or(chrono::Duration::MAX);
+        for manifest in registry.list().await.map_err(Self::registry_error)? {
+            if manifest.initiated >= cutoff {
+                continue;
+            }
+            let parts = registry
+                .parts(&manifest.upload_id)
+                .await
+                .map_err(Self::registry_error)?;
+            if parts.iter().any(|p| p.last_modified >= cutoff) {
+                continue;
+            }
+            let upload_id = manifest.upload_id.clone();
+            let Some(staged) = Self::staged_from_manifest(manifest) else {
+                registry
+                    .remove(&upload_id)
+                    .await
+                    .map_err(Self::registry_error)?;
+                continue;
+            };
+            match self.abort_staged_multipart(&staged).await {
+                Ok(()) => {
+                    report.uploads += 1;
+                    report.bytes += parts.iter().map(|p| p.size).sum::<u64>();
+                }
+                Err(e) => warn!("Failed to sweep multipart upload {}: {}", upload_id, e),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Number of part objects the registry has staged in `bucket` (0 when
+    /// its backend holds uploads itself). They are visible to listings.
+    pub async fn registry_staged_part_count(&self, bucket: &str) -> Result<usize, EngineError> {
+        let Some(registry) = self.registry_for(bucket) else {
+            return Ok(0);
+        };
+        let mut count = 0;
+        for manifest in registry.list().await.map_err(Self::registry_error)? {
+            if manifest.bucket == bucket {
+                count += registry
+                    .parts(&manifest.upload_id)
+                    .await
+                    .map_err(Self::registry_error)?
+                    .len();
+            }
+        }
+        Ok(count)
+    }
+
+    /// Abort every registry-staged upload of `bucket` (ahead of deleting
+    /// it). Backend-held uploads go with the bucket's storage.
+    pub async fn purge_staged_multipart_uploads(&self, bucket: &str) -> Result<(), EngineError> {
+        if self.registry_for(bucket).is_none() {
+            return Ok(());
+        }
+        for staged in self.list_staged_multipart_uploads(bucket).await? {
+            self.abort_staged_multipart(&staged).await?;
+        }
+        Ok(())
+    }
+
     /// Store as passthrough without delta compression
     async fn store_passthrough(&self, ctx: StoreContext<'_>) -> Result<StoreResult, EngineError> {
         let mut metadata = FileMetadata::new_passthrough(
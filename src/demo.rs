@@ -143,6 +143,17 @@ pub fn ui_router(admin_state: Arc<AdminState>) -> Router {
                 .put(admin::set_s3_session_creds)
                 .delete(admin::clear_s3_session_creds),
         )
+        // Self-service service accounts: any IAM-backed session (including
+        // browser-lift users) manages its own keys. Not declarative-gated —
+        // service accounts are runtime credentials, not part of the YAML.
+        .route(
+            "/_/api/admin/session/service-accounts",
+            get(admin::list_own_service_accounts).post(admin::create_own_service_account),
+        )
+        .route(
+            "/_/api/admin/session/service-accounts/:id",
+            put(admin::update_own_service_account).delete(admin::delete_own_service_account),
+        )
         .layer(middleware::from_fn_with_state(
             admin_state.clone(),
             admin::require_session,
@@ -184,6 +195,9 @@ pub fn ui_router(admin_state: Arc<AdminState>) -> Router {
             "/_/api/admin/config/trace",
             post(admin::trace_config).get(admin::trace_config_get),
         )
+        // IAM counterpart of trace: which rule allowed or denied a
+        // synthetic request. Read-only, so not declarative-gated.
+        .route("/_/api/admin/iam/simulate", post(admin::simulate_iam))
         // Section-level config operations (Wave 1 of the admin UI revamp).
         // Gives the UI a per-section scope between the field-level PATCH
         // (too granular) and the document-level APPLY (too coarse).
@@ -290,14 +304,26 @@ pub fn ui_router(admin_state: Arc<AdminState>) -> Router {
             "/_/api/admin/diagnostics/scan/stream",
             get(admin::get_scan_stream),
         )
-        // Audit log viewer — recent ring of structured audit entries.
-        // Read-only; no corresponding mutation route. Session-gated
-        // via the surrounding `require_session` layer. Not IAM-gated
-        // (all admins see the same log so there's no per-identity
-        // filtering to do at this layer).
+        // Audit log viewer — the durable audit store when enabled, else
+        // the recent ring of structured audit entries. Read-only; no
+        // corresponding mutation route. Session-gated via the
+        // surrounding `require_session` layer. Not IAM-gated (all admins
+        // see the same log so there's no per-identity filtering to do at
+        // this layer).
         .route("/_/api/admin/audit", get(admin::get_audit))
+        .route("/_/api/admin/audit/export", get(admin::export_audit))
+        .route("/_/api/admin/audit/verify", get(admin::verify_audit))
         // Live admin session list + force-logout (revoke a stolen cookie / all
         // sessions of a compromised IAM key) without restarting the proxy.
+        // Service accounts of any user (admin view).
+        .route(
+            "/_/api/admin/users/:id/service-accounts",
+            get(admin::list_user_service_accounts).post(admin::create_user_service_account),
+        )
+        .route(
+            "/_/api/admin/service-accounts/:id",
+            put(admin::update_service_account).delete(admin::delete_service_account),
+        )
         .route("/_/api/admin/sessions", get(admin::list_sessions))
         .route("/_/api/admin/sessions/:id", delete(admin::revoke_session))
         .route(
@@ -335,6 +361,14 @@ pub fn ui_router(admin_state: Arc<AdminState>) -> Router {
         )
         .route("/_/api/admin/jobs/:id/runs", get(admin::jobs_runs))
         .route("/_/api/admin/jobs/:id/failures", get(admin::jobs_failures))
+        .route(
+            "/_/api/admin/jobs/:id/conflicts",
+            get(admin::jobs_conflicts),
+        )
+        .route(
+            "/_/api/admin/jobs/:id/conflicts/resolve",
+            post(admin::jobs_resolve_conflict),
+        )
         // `verify` is a LITERAL segment handling BOTH GET (poll status) and POST
         // (kick off the background audit). It must carry the POST too: a literal
         // path segment shadows the `:action` param at this position, so routing
@@ -362,6 +396,28 @@ pub fn ui_router(admin_state: Arc<AdminState>) -> Router {
         )
         .route("/_/api/admin/objects/zip", get(admin::download_zip))
         .route("/_/api/admin/objects/list", get(admin::list_all_objects))
+        // Read-through disk cache: stats + warm-up (reads objects through
+        // the engine, so same GUI-session trust boundary as bulk ops).
+        .route("/_/api/admin/disk-cache", get(admin::get_disk_cache))
+        .route(
+            "/_/api/admin/disk-cache/prefetch",
+            post(admin::prefetch_disk_cache),
+        )
+        // Write-through mirrors: copies + parity (metadata-only listing of
+        // both copies).
+        .route("/_/api/admin/mirrors", get(admin::list_mirrors))
+        .route(
+            "/_/api/admin/mirrors/:bucket/parity",
+            get(admin::mirror_parity),
+        )
+        // Capacity spans: per-member usage (one total_size per member).
+        .route("/_/api/admin/spans", get(admin::list_spans))
+        // Erasure-coded backends: shard layout + on-demand shard repair.
+        .route("/_/api/admin/erasure", get(admin::list_erasure))
+        .route(
+            "/_/api/admin/erasure/:backend/repair",
+            post(admin::repair_erasure),
+        )
         // Merge the IAM-gated subrouter in; it already carries its own
         // `require_not_declarative` layer.
         .merge(iam_gated)
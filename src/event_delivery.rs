@@ -4,8 +4,11 @@
 //!
 //! The dispatcher is intentionally conservative: it is disabled unless
 //! `advanced.event_delivery.enabled=true` and a delivery target is set (a
-//! webhook URL, or — in `format = slack` bot-token mode — a Slack bot token).
-//! Request handlers never call this module; they only append to `event_outbox`.
+//! webhook URL, a Slack bot token in `format = slack` bot-token mode, or a
+//! message-broker sink). Named endpoints (`event_delivery.endpoints`) are
+//! walked from their own cursors by `crate::event_endpoints` in the same
+//! loop. Request handlers never call this module; they only append to
+//! `event_outbox`.
 
 use crate::background::parse_duration_or;
 use crate::config::SharedConfig;
@@ -15,7 +18,11 @@ use crate::event_outbox::{
     current_unix_seconds, EventOutboxRecord, STATUS_DELIVERED, STATUS_FAILED, STATUS_IN_PROGRESS,
     STATUS_PENDING,
 };
+use crate::event_sinks::SinkSet;
+use crate::metrics::Metrics;
+use crate::secret::{Secret, WebhookSecret};
 use crate::security::{validate_outbound_url, UrlKind};
+use crate::webhook_signature;
 use async_trait::async_trait;
 use reqwest::header::{HeaderName, HeaderValue};
 use reqwest::Url;
@@ -50,6 +57,7 @@ const DEFAULT_RETRY_BASE: Duration = Duration::from_secs(5);
 const DEFAULT_RETRY_MAX: Duration = Duration::from_secs(300);
 const DEFAULT_STALE_CLAIM_AFTER: Duration = Duration::from_secs(60);
 const DEFAULT_DELIVERED_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+const DEFAULT_ACCESS_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
 
 /// A listener cursor older than this (no advance in 1h) is treated as inactive
 /// and no longer pins the prune floor. A healthy consumer advances every tick
@@ -74,6 +82,16 @@ pub trait EventDeliveryClient: Send + Sync + 'static {
         config: &EventDeliveryConfig,
         event: &EventOutboxRecord,
     ) -> Result<(), String>;
+
+    /// Deliver to one named endpoint; `config` is the endpoint's own
+    /// delivery config (see `event_endpoints::delivery_config`).
+    async fn deliver_to_endpoint(
+        &self,
+        config: &EventDeliveryConfig,
+        event: &EventOutboxRecord,
+    ) -> Result<(), String> {
+        self.deliver(config, event).await
+    }
 }
 
 #[derive(Clone)]
@@ -177,7 +195,8 @@ impl EventDeliveryClient for HttpWebhookDeliveryClient {
 
 impl HttpWebhookDeliveryClient {
     /// Existing behavior: POST the `{schema,event}` envelope to every webhook
-    /// endpoint with the configured static headers.
+    /// endpoint with the configured static headers, signed for endpoints
+    /// that have a signing secret.
     async fn deliver_raw(
         &self,
         config: &EventDeliveryConfig,
@@ -192,6 +211,8 @@ impl HttpWebhookDeliveryClient {
             schema: "deltaglider.event.v1",
             event,
         };
+        // Serialize once: a signature covers these exact bytes.
+        let body = serde_json::to_vec(&payload).map_err(|e| format!("serialize event: {e}"))?;
         for endpoint in endpoints {
             // SSRF guard: reject private/loopback/metadata targets before any
             // outbound request (the client also refuses to follow redirects).
@@ -209,8 +230,19 @@ impl HttpWebhookDeliveryClient {
                     .map_err(|e| format!("invalid webhook header value for {name}: {e}"))?;
                 request = request.header(name, value);
             }
+            if let Some(signing) = config.signing_secret_for(endpoint) {
+                let current = WebhookSecret::new(&signing.secret);
+                let previous = signing.previous_secret.as_deref().map(WebhookSecret::new);
+                let mut secrets: Vec<&dyn Secret> = vec![&current];
+                secrets.extend(previous.as_ref().map(|p| p as &dyn Secret));
+                request = request.header(
+                    webhook_signature::SIGNATURE_HEADER,
+                    webhook_signature::sign(current_unix_seconds(), &body, &secrets),
+                );
+            }
             let response = request
-                .json(&payload)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone())
                 .send()
                 .await
                 .map_err(|e| format!("{}: {e}", redact_url_for_error(endpoint)))?;
@@ -375,11 +407,139 @@ impl HttpWebhookDeliveryClient {
     }
 }
 
+/// Publishes the raw `{schema,event}` envelope to every `sinks` entry (see
+/// `crate::event_sinks`). Every sink is attempted; the event fails if any
+/// of them did not acknowledge it.
+#[derive(Default)]
+pub struct BrokerDeliveryClient {
+    sinks: SinkSet,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl BrokerDeliveryClient {
+    pub fn new(metrics: Option<Arc<Metrics>>) -> Self {
+        Self {
+            sinks: SinkSet::default(),
+            metrics,
+        }
+    }
+}
+
+#[async_trait]
+impl EventDeliveryClient for BrokerDeliveryClient {
+    async fn deliver(
+        &self,
+        config: &EventDeliveryConfig,
+        event: &EventOutboxRecord,
+    ) -> Result<(), String> {
+        self.sinks.retain(&config.sinks).await;
+        if config.sinks.is_empty() {
+            return Ok(());
+        }
+        let timeout = parse_duration_or(
+            &config.request_timeout,
+            DEFAULT_TIMEOUT,
+            MIN_TIMEOUT,
+            "event_delivery.request_timeout",
+        );
+        let body = serde_json::to_vec(&EventWebhookPayload {
+            schema: "deltaglider.event.v1",
+            event,
+        })
+        .map_err(|e| format!("serialize event: {e}"))?;
+
+        let mut errors = Vec::new();
+        for sink_config in &config.sinks {
+            let started = std::time::Instant::now();
+            let outcome = match self.sinks.get(sink_config).await {
+                Ok(sink) => sink.publish(event, &body, timeout).await,
+                Err(e) => Err(e),
+            };
+            if let Some(metrics) = &self.metrics {
+                metrics
+                    .event_sink_publish_total
+                    .with_label_values(&[
+                        sink_config.name.as_str(),
+                        if outcome.is_ok() { "ok" } else { "error" },
+                    ])
+                    .inc();
+                metrics
+                    .event_sink_publish_duration_seconds
+                    .with_label_values(&[&sink_config.name])
+                    .observe(started.elapsed().as_secs_f64());
+            }
+            if let Err(e) = outcome {
+                errors.push(format!(
+                    "sink {} ({}): {e}",
+                    sink_config.name,
+                    sink_config.kind.as_str()
+                ));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// The production client: the HTTP targets (webhooks or Slack) when any are
+/// configured, then the message-broker sinks. Both are attempted even when
+/// the first fails, so a retry re-sends to targets that already accepted
+/// the event — consumers dedupe (webhooks on the event `id`, brokers on the
+/// sink message id).
+pub struct OutboxDeliveryClient {
+    http: HttpWebhookDeliveryClient,
+    brokers: BrokerDeliveryClient,
+}
+
+impl OutboxDeliveryClient {
+    pub fn new(metrics: Option<Arc<Metrics>>) -> Self {
+        Self {
+            http: HttpWebhookDeliveryClient::default(),
+            brokers: BrokerDeliveryClient::new(metrics),
+        }
+    }
+}
+
+#[async_trait]
+impl EventDeliveryClient for OutboxDeliveryClient {
+    async fn deliver(
+        &self,
+        config: &EventDeliveryConfig,
+        event: &EventOutboxRecord,
+    ) -> Result<(), String> {
+        let http = if config.has_http_targets() {
+            self.http.deliver(config, event).await
+        } else {
+            Ok(())
+        };
+        let brokers = self.brokers.deliver(config, event).await;
+        match (http, brokers) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Err(e), Ok(())) | (Ok(()), Err(e)) => Err(e),
+            (Err(http), Err(brokers)) => Err(format!("{http}; {brokers}")),
+        }
+    }
+
+    /// Endpoints are HTTP only; skipping the broker client also keeps its
+    /// sink connections, which it drops for sinks missing from `config`.
+    async fn deliver_to_endpoint(
+        &self,
+        config: &EventDeliveryConfig,
+        event: &EventOutboxRecord,
+    ) -> Result<(), String> {
+        self.http.deliver(config, event).await
+    }
+}
+
 pub fn spawn_dispatcher(
     config: SharedConfig,
     db: Arc<Mutex<ConfigDb>>,
+    metrics: Option<Arc<Metrics>>,
 ) -> tokio::task::JoinHandle<()> {
-    spawn_dispatcher_with_client(config, db, Arc::new(HttpWebhookDeliveryClient::default()))
+    spawn_dispatcher_with_client(config, db, Arc::new(OutboxDeliveryClient::new(metrics)))
 }
 
 pub fn spawn_dispatcher_with_client(
@@ -393,6 +553,14 @@ pub fn spawn_dispatcher_with_client(
         loop {
             let cfg = { config.read().await.event_delivery.clone() };
             tokio::time::sleep(dispatcher_tick(&cfg)).await;
+            crate::event_endpoints::dispatch_endpoints_once(
+                &db,
+                client.as_ref(),
+                &cfg,
+                current_unix_seconds(),
+            )
+            .await;
+            prune_access_events(&db, &cfg, current_unix_seconds()).await;
             if !cfg.is_active() {
                 // Delivery disabled — but STILL prune the outbox below the
                 // active-listener floor, or pending rows consumed by
@@ -428,6 +596,38 @@ pub async fn prune_below_floor(db: &Arc<Mutex<ConfigDb>>, config: &EventDelivery
     }
 }
 
+/// Age out read and security events under their own retention and row cap,
+/// every tick whether or not delivery is active. They never count against
+/// the delivered-row budgets, so a busy bucket's downloads cannot push
+/// mutation events out.
+pub async fn prune_access_events(
+    db: &Arc<Mutex<ConfigDb>>,
+    config: &EventDeliveryConfig,
+    now: i64,
+) {
+    let access = &config.access_events;
+    let retention = parse_duration_or(
+        &access.retention,
+        DEFAULT_ACCESS_RETENTION,
+        Duration::from_secs(0),
+        "event_delivery.access_events.retention",
+    )
+    .as_secs() as i64;
+    let db = db.lock().await;
+    let min_keep_id = db
+        .event_outbox_min_active_listener_cursor(now, LISTENER_CURSOR_STALE_SECS)
+        .unwrap_or(None)
+        .unwrap_or(i64::MAX);
+    if let Err(err) = db.event_outbox_prune_access_events(
+        now.saturating_sub(retention),
+        access.max_rows,
+        config.prune_batch,
+        min_keep_id,
+    ) {
+        warn!("Event outbox access-event prune failed: {}", err);
+    }
+}
+
 pub async fn dispatch_once(
     db: &Arc<Mutex<ConfigDb>>,
     client: &dyn EventDeliveryClient,
@@ -598,7 +798,7 @@ pub(crate) fn delivered_retention_secs(config: &EventDeliveryConfig) -> i64 {
     .as_secs() as i64
 }
 
-fn truncate_error(error: &str) -> String {
+pub(crate) fn truncate_error(error: &str) -> String {
     const MAX_ERROR_LEN: usize = 1000;
     if error.len() <= MAX_ERROR_LEN {
         return error.to_string();
@@ -958,6 +1158,85 @@ mod tests {
         server.abort();
     }
 
+    #[tokio::test]
+    async fn http_webhook_client_signs_per_endpoint() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(String, Option<String>)>();
+        let app = Router::new().route(
+            "/:hook",
+            post(
+                move |axum::extract::Path(hook): axum::extract::Path<String>,
+                      headers: HeaderMap,
+                      body: axum::body::Bytes| {
+                    let tx = tx.clone();
+                    async move {
+                        let signature = headers
+                            .get(webhook_signature::SIGNATURE_HEADER)
+                            .and_then(|v| v.to_str().ok())
+                            .map(str::to_string);
+                        let verified = signature.map(|header| {
+                            let now = current_unix_seconds();
+                            let tolerance = webhook_signature::DEFAULT_TOLERANCE;
+                            let with = |secret: &[u8]| {
+                                webhook_signature::verify(&header, &body, &[secret], tolerance, now)
+                                    .is_ok()
+                            };
+                            format!("new={} old={}", with(b"new"), with(b"old"))
+                        });
+                        tx.send((hook, verified)).unwrap();
+                        StatusCode::NO_CONTENT
+                    }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let db = Arc::new(Mutex::new(ConfigDb::in_memory("test-pass").unwrap()));
+        db.lock()
+            .await
+            .event_outbox_insert(&event("signed"))
+            .unwrap();
+        let mut config = cfg();
+        config.webhook_url = Some(format!("{base_url}/rotating"));
+        config.webhook_urls = vec![format!("{base_url}/plain")];
+        config.webhook_signing.insert(
+            format!("{base_url}/rotating"),
+            crate::config_sections::WebhookSigningSecret {
+                secret: "new".to_string(),
+                previous_secret: Some("old".to_string()),
+            },
+        );
+        let client = HttpWebhookDeliveryClient::for_tests();
+
+        dispatch_once(&db, &client, &config, "test-worker", 200).await;
+
+        let mut seen = Vec::new();
+        for _ in 0..2 {
+            seen.push(
+                timeout(Duration::from_secs(2), rx.recv())
+                    .await
+                    .unwrap()
+                    .expect("webhook request"),
+            );
+        }
+        seen.sort();
+        assert_eq!(
+            seen,
+            vec![
+                ("plain".to_string(), None),
+                (
+                    "rotating".to_string(),
+                    Some("new=true old=true".to_string())
+                ),
+            ]
+        );
+
+        server.abort();
+    }
+
     #[tokio::test]
     async fn slack_webhook_delivery_formats_block_kit_and_filters() {
         // Mock Slack Incoming Webhook: capture the posted body, return 200.
@@ -1043,6 +1322,55 @@ mod tests {
         server.abort();
     }
 
+    #[tokio::test]
+    async fn broker_client_attempts_every_sink_and_counts_outcomes() {
+        use crate::config_sections::{EventSinkConfig, EventSinkKind};
+        // A Redis that accepts any command with +OK, and a closed port.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let up = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = tokio::io::BufReader::new(socket);
+            let mut line = String::new();
+            while conn.read_line(&mut line).await.unwrap_or(0) > 0 {
+                if line.starts_with("event") {
+                    conn.get_mut().write_all(b"+1-0\r\n").await.unwrap();
+                }
+                line.clear();
+            }
+        });
+        let down = {
+            let l = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            l.local_addr().unwrap()
+        };
+        let sink = |name: &str, addr: std::net::SocketAddr| EventSinkConfig {
+            name: name.to_string(),
+            kind: EventSinkKind::Redis,
+            url: format!("redis://{addr}"),
+            topic: "events".to_string(),
+            routing_key: None,
+            ca_file: None,
+        };
+        let mut config = cfg();
+        config.webhook_url = None;
+        config.sinks = vec![sink("down", down), sink("up", up)];
+        assert!(config.is_active());
+
+        let metrics = Arc::new(Metrics::new());
+        let client = BrokerDeliveryClient::new(Some(metrics.clone()));
+        let record = crate::event_sinks::test_event(1, "b", "k");
+        let err = client.deliver(&config, &record).await.unwrap_err();
+        assert!(err.starts_with("sink down (redis): "), "got: {err}");
+        let count = |sink: &str, outcome: &str| {
+            metrics
+                .event_sink_publish_total
+                .with_label_values(&[sink, outcome])
+                .get()
+        };
+        assert_eq!((count("down", "error"), count("up", "ok")), (1, 1));
+    }
+
     #[test]
     fn slack_web_api_ok_false_is_failure() {
         // The Slack Web API returns HTTP 200 even on error; the real status is
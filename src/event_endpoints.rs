@@ -0,0 +1,666 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Named event-delivery endpoints (`event_delivery.endpoints`).
+//!
+//! Each endpoint is an independent outbox listener: it walks `event_outbox`
+//! from its own cursor (`event-endpoint:<name>`), steps over events its
+//! filter rejects and POSTs the rest in id order through the dispatcher's
+//! [`EventDeliveryClient`]. The event at an endpoint's head is retried with
+//! the endpoint's backoff until it is delivered or its attempt budget runs
+//! out (then it is skipped); that retry state lives in `event_endpoint_state`
+//! so it survives a restart. A dead endpoint only holds back its own cursor.
+//!
+//! Endpoints never touch an outbox row's `status` — that belongs to the
+//! `webhook_url(s)` / sink delivery. Their cursors are ordinary listener
+//! cursors, so they pin the prune floor like event-driven replication does.
+
+use crate::config_db::ConfigDb;
+use crate::config_sections::{EventDeliveryConfig, EventEndpoint, EventFilter};
+use crate::event_delivery::{next_attempt_after, truncate_error, EventDeliveryClient};
+use crate::event_outbox::{EventEndpointState, EventKind, EventOutboxRecord};
+use crate::replication::event_consumer::{
+    is_non_object_kind, is_user_object_key, parse_event_kind,
+};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// Listener-cursor name prefix of named endpoints.
+pub const ENDPOINT_LISTENER_PREFIX: &str = "event-endpoint:";
+
+/// The event kinds an endpoint can receive.
+const ENDPOINT_EVENT_KINDS: [EventKind; 9] = [
+    EventKind::ObjectCreated,
+    EventKind::ObjectDeleted,
+    EventKind::ObjectCopied,
+    EventKind::ReplicationObjectCopied,
+    EventKind::LifecycleExpired,
+    EventKind::LifecycleTransitioned,
+    EventKind::ObjectAccessed,
+    EventKind::AccessDenied,
+    EventKind::AuthFailed,
+];
+
+/// Outbox pages one endpoint tick may scan looking for a batch of matches.
+const MAX_SCAN_PAGES: u32 = 20;
+
+pub fn listener_name(endpoint: &str) -> String {
+    format!("{ENDPOINT_LISTENER_PREFIX}{endpoint}")
+}
+
+fn build_globset(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for p in patterns {
+        builder.add(Glob::new(p).map_err(|e| format!("invalid glob {p:?}: {e}"))?);
+    }
+    builder
+        .build()
+        .map_err(|e| format!("globset build failed: {e}"))
+}
+
+/// An [`EventFilter`] with its globs compiled.
+pub struct CompiledFilter<'a> {
+    filter: &'a EventFilter,
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl<'a> CompiledFilter<'a> {
+    pub fn new(filter: &'a EventFilter) -> Result<Self, String> {
+        Ok(Self {
+            filter,
+            include: build_globset(&filter.include_globs)?,
+            exclude: build_globset(&filter.exclude_globs)?,
+        })
+    }
+
+    /// Whether `event` goes to the endpoint. Internal work items and
+    /// DeltaGlider-internal keys never do; read and security events only
+    /// when the filter lists their kind.
+    pub fn matches(&self, event: &EventOutboxRecord) -> bool {
+        let f = self.filter;
+        let listed = f.kinds.iter().any(|k| k == &event.kind);
+        if parse_event_kind(&event.kind).is_some_and(EventKind::is_access) {
+            if !listed {
+                return false;
+            }
+        } else if is_non_object_kind(&event.kind)
+            || !is_user_object_key(&event.key)
+            || (!f.kinds.is_empty() && !listed)
+        {
+            return false;
+        }
+        if !f.buckets.is_empty() && !f.buckets.iter().any(|b| b == &event.bucket) {
+            return false;
+        }
+        if self.exclude.is_match(&event.key) {
+            return false;
+        }
+        if !f.include_globs.is_empty() && !self.include.is_match(&event.key) {
+            return false;
+        }
+        if f.min_size.is_some() || f.max_size.is_some() {
+            let Some(size) = event.payload.get("content_length").and_then(Value::as_u64) else {
+                return false;
+            };
+            if f.min_size.is_some_and(|min| size < min) || f.max_size.is_some_and(|max| size > max)
+            {
+                return false;
+            }
+        }
+        // S3 lower-cases user-metadata keys, so the filter's keys match
+        // case-insensitively; values match exactly.
+        let metadata = event.payload.get("user_metadata");
+        f.metadata.iter().all(|(key, value)| {
+            metadata
+                .and_then(|m| m.get(key.to_ascii_lowercase()))
+                .and_then(Value::as_str)
+                == Some(value.as_str())
+        })
+    }
+}
+
+/// The delivery config an endpoint's events go out with: the top-level
+/// timeouts and Slack cosmetics, with the endpoint's URL, format, headers,
+/// signing secret and retry policy. Slack filtering is left to the endpoint
+/// filter, and Slack formatting always uses the Incoming Webhook URL.
+pub fn delivery_config(
+    base: &EventDeliveryConfig,
+    endpoint: &EventEndpoint,
+) -> EventDeliveryConfig {
+    EventDeliveryConfig {
+        enabled: true,
+        webhook_url: Some(endpoint.url.clone()),
+        webhook_urls: Vec::new(),
+        webhook_headers: endpoint.headers.clone(),
+        webhook_signing: endpoint
+            .signing
+            .iter()
+            .map(|s| ("*".to_string(), s.clone()))
+            .collect(),
+        max_attempts: endpoint.max_attempts.unwrap_or(base.max_attempts),
+        retry_base: endpoint
+            .retry_base
+            .clone()
+            .unwrap_or_else(|| base.retry_base.clone()),
+        retry_max: endpoint
+            .retry_max
+            .clone()
+            .unwrap_or_else(|| base.retry_max.clone()),
+        format: endpoint.format,
+        slack_bot_token: None,
+        slack_channel: None,
+        slack_include_globs: Vec::new(),
+        slack_exclude_globs: Vec::new(),
+        slack_notify_kinds: ENDPOINT_EVENT_KINDS
+            .iter()
+            .map(|k| k.as_str().to_string())
+            .collect(),
+        slack_routes: Vec::new(),
+        sinks: Vec::new(),
+        endpoints: Vec::new(),
+        ..base.clone()
+    }
+}
+
+/// One dispatcher pass over the named endpoints: forget cursors of removed
+/// endpoints, then advance every enabled endpoint concurrently.
+pub async fn dispatch_endpoints_once(
+    db: &Arc<Mutex<ConfigDb>>,
+    client: &dyn EventDeliveryClient,
+    config: &EventDeliveryConfig,
+    now: i64,
+) {
+    forget_removed_endpoints(db, config).await;
+    if !config.endpoints_active() {
+        return;
+    }
+    let batch = config.batch_size.clamp(1, 500);
+    futures::future::join_all(
+        config
+            .endpoints
+            .iter()
+            .filter(|e| e.enabled)
+            .map(|endpoint| dispatch_endpoint(db, client, config, endpoint, batch, now)),
+    )
+    .await;
+}
+
+async fn forget_removed_endpoints(db: &Arc<Mutex<ConfigDb>>, config: &EventDeliveryConfig) {
+    let configured = |name: &str| config.endpoints.iter().any(|e| e.name == name);
+    let db = db.lock().await;
+    let cursors = db
+        .listener_cursors_with_prefix(ENDPOINT_LISTENER_PREFIX)
+        .unwrap_or_default();
+    for cursor in cursors {
+        let name = &cursor.listener_name[ENDPOINT_LISTENER_PREFIX.len()..];
+        if !configured(name) {
+            if let Err(err) = db.listener_cursor_delete(&cursor.listener_name) {
+                warn!("Event endpoint {}: cursor delete failed: {}", name, err);
+            }
+        }
+    }
+    for state in db.event_endpoint_states().unwrap_or_default() {
+        if !configured(&state.endpoint) {
+            if let Err(err) = db.event_endpoint_state_delete(&state.endpoint) {
+                warn!(
+                    "Event endpoint {}: state delete failed: {}",
+                    state.endpoint, err
+                );
+            }
+        }
+    }
+}
+
+async fn dispatch_endpoint(
+    db: &Arc<Mutex<ConfigDb>>,
+    client: &dyn EventDeliveryClient,
+    base: &EventDeliveryConfig,
+    endpoint: &EventEndpoint,
+    batch: u32,
+    now: i64,
+) {
+    let filter = match CompiledFilter::new(&endpoint.filter) {
+        Ok(filter) => filter,
+        Err(err) => {
+            warn!("Event endpoint {}: {}", endpoint.name, err);
+            return;
+        }
+    };
+    let listener = listener_name(&endpoint.name);
+    let (matched, scanned_to, mut state) = {
+        let db = db.lock().await;
+        let cursor = match db.listener_cursor_load_full(&listener) {
+            Ok(Some(cursor)) => cursor.last_event_id,
+            Ok(None) => {
+                // First sight of this endpoint: start at the newest event
+                // rather than replaying the whole outbox at it.
+                let newest = db
+                    .event_outbox_recent(1)
+                    .ok()
+                    .and_then(|rows| rows.first().map(|r| r.id))
+                    .unwrap_or(0);
+                if let Err(err) = db.listener_cursor_advance(&listener, newest, now) {
+                    warn!(
+                        "Event endpoint {}: cursor seed failed: {}",
+                        endpoint.name, err
+                    );
+                }
+                return;
+            }
+            Err(err) => {
+                warn!(
+                    "Event endpoint {}: cursor load failed: {}",
+                    endpoint.name, err
+                );
+                return;
+            }
+        };
+        let state = db
+            .event_endpoint_state_load(&endpoint.name)
+            .unwrap_or(None)
+            .unwrap_or_else(|| EventEndpointState {
+                endpoint: endpoint.name.clone(),
+                ..Default::default()
+            });
+        // Backing off, or caught up: still alive, so keep pinning the floor.
+        let backing_off = state.next_attempt_at.is_some_and(|at| at > now);
+        let mut matched = Vec::new();
+        let mut scanned_to = cursor;
+        // Step over rows the filter rejects a few pages at a time, so a
+        // stream of (say) read events it does not want cannot starve it.
+        for _ in 0..if backing_off { 0 } else { MAX_SCAN_PAGES } {
+            let page = match db.event_outbox_since(scanned_to, batch) {
+                Ok(page) => page,
+                Err(err) => {
+                    warn!(
+                        "Event endpoint {}: outbox read failed: {}",
+                        endpoint.name, err
+                    );
+                    return;
+                }
+            };
+            let Some(last) = page.last() else { break };
+            scanned_to = last.id;
+            let full = page.len() >= batch as usize;
+            matched.extend(page.into_iter().filter(|e| filter.matches(e)));
+            if !full || matched.len() >= batch as usize {
+                break;
+            }
+        }
+        if scanned_to == cursor {
+            let _ = db.listener_cursor_touch(&listener, now);
+            return;
+        }
+        (matched, scanned_to, state)
+    };
+
+    let delivery = delivery_config(base, endpoint);
+    let before = state.clone();
+    // Everything scanned is passed unless a delivery stops the walk; rows
+    // between matches were rejected by the filter.
+    let mut watermark = scanned_to;
+    for event in &matched {
+        match client.deliver_to_endpoint(&delivery, event).await {
+            Ok(()) => {
+                state.event_id = 0;
+                state.attempts = 0;
+                state.next_attempt_at = None;
+            }
+            Err(err) => {
+                let attempts = if state.event_id == event.id {
+                    state.attempts + 1
+                } else {
+                    1
+                };
+                state.last_error = Some(truncate_error(&err));
+                match next_attempt_after(&delivery, attempts, now) {
+                    Some(at) => {
+                        state.event_id = event.id;
+                        state.attempts = attempts;
+                        state.next_attempt_at = Some(at);
+                        watermark = event.id - 1;
+                        break;
+                    }
+                    None => {
+                        warn!(
+                            "Event endpoint {}: giving up on event {} after {} attempts: {}",
+                            endpoint.name, event.id, attempts, err
+                        );
+                        state.event_id = 0;
+                        state.attempts = 0;
+                        state.next_attempt_at = None;
+                        state.skipped += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let db = db.lock().await;
+    if let Err(err) = db.listener_cursor_advance(&listener, watermark, now) {
+        warn!(
+            "Event endpoint {}: cursor advance failed: {}",
+            endpoint.name, err
+        );
+    }
+    let _ = db.listener_cursor_touch(&listener, now);
+    if state != before {
+        state.updated_at = now;
+        if let Err(err) = db.event_endpoint_state_save(&state) {
+            warn!(
+                "Event endpoint {}: state save failed: {}",
+                endpoint.name, err
+            );
+        }
+    }
+}
+
+/// Delivery progress of one named endpoint, for the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventEndpointStatus {
+    pub name: String,
+    pub enabled: bool,
+    /// Last outbox id the endpoint is past; `None` before its first tick.
+    pub cursor: Option<i64>,
+    pub cursor_updated_at: Option<i64>,
+    /// Attempts so far on the event at the endpoint's head.
+    pub attempts: i64,
+    pub next_attempt_at: Option<i64>,
+    pub last_error: Option<String>,
+    /// Events given up on after the attempt budget ran out.
+    pub skipped: i64,
+}
+
+pub fn endpoint_statuses(db: &ConfigDb, config: &EventDeliveryConfig) -> Vec<EventEndpointStatus> {
+    let states: BTreeMap<String, EventEndpointState> = db
+        .event_endpoint_states()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| (s.endpoint.clone(), s))
+        .collect();
+    config
+        .endpoints
+        .iter()
+        .map(|endpoint| {
+            let cursor = db
+                .listener_cursor_load_full(&listener_name(&endpoint.name))
+                .unwrap_or(None);
+            let state = states.get(&endpoint.name);
+            EventEndpointStatus {
+                name: endpoint.name.clone(),
+                enabled: endpoint.enabled,
+                cursor: cursor.as_ref().map(|c| c.last_event_id),
+                cursor_updated_at: cursor.as_ref().map(|c| c.updated_at),
+                attempts: state.map_or(0, |s| s.attempts),
+                next_attempt_at: state.and_then(|s| s.next_attempt_at),
+                last_error: state.and_then(|s| s.last_error.clone()),
+                skipped: state.map_or(0, |s| s.skipped),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_outbox::{EventSource, NewEvent};
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    /// Fails every delivery to a URL containing "down"; records the rest.
+    #[derive(Default)]
+    struct FakeClient {
+        delivered: std::sync::Mutex<Vec<(String, i64)>>,
+    }
+
+    #[async_trait]
+    impl EventDeliveryClient for FakeClient {
+        async fn deliver(
+            &self,
+            config: &EventDeliveryConfig,
+            event: &EventOutboxRecord,
+        ) -> Result<(), String> {
+            let url = config.webhook_url.clone().unwrap_or_default();
+            if url.contains("down") {
+                return Err("connection refused".to_string());
+            }
+            self.delivered.lock().unwrap().push((url, event.id));
+            Ok(())
+        }
+    }
+
+    fn endpoint(name: &str, url: &str, filter: EventFilter) -> EventEndpoint {
+        EventEndpoint {
+            name: name.to_string(),
+            enabled: true,
+            url: url.to_string(),
+            format: Default::default(),
+            headers: BTreeMap::new(),
+            signing: None,
+            filter,
+            max_attempts: Some(2),
+            retry_base: Some("5s".to_string()),
+            retry_max: None,
+        }
+    }
+
+    fn record(kind: &str, bucket: &str, key: &str, payload: Value) -> EventOutboxRecord {
+        EventOutboxRecord {
+            id: 1,
+            kind: kind.to_string(),
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+            source: "s3_api".to_string(),
+            occurred_at: 0,
+            payload,
+            status: "pending".to_string(),
+            attempts: 0,
+            next_attempt_at: None,
+            claimed_by: None,
+            claimed_at: None,
+            delivered_at: None,
+            last_error: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn filters_match_every_criterion() {
+        let filter = EventFilter {
+            kinds: vec!["ObjectCreated".to_string()],
+            buckets: vec!["media".to_string()],
+            include_globs: vec!["videos/**".to_string()],
+            exclude_globs: vec!["**/*.tmp".to_string()],
+            min_size: Some(10),
+            max_size: Some(100),
+            metadata: [("Team".to_string(), "video".to_string())].into(),
+        };
+        let compiled = CompiledFilter::new(&filter).unwrap();
+        let payload = json!({"content_length": 50, "user_metadata": {"team": "video"}});
+        let matching = record("ObjectCreated", "media", "videos/a.mp4", payload.clone());
+        assert!(compiled.matches(&matching));
+
+        for (kind, bucket, key) in [
+            ("ObjectDeleted", "media", "videos/a.mp4"),
+            ("ObjectCreated", "docs", "videos/a.mp4"),
+            ("ObjectCreated", "media", "images/a.png"),
+            ("ObjectCreated", "media", "videos/a.tmp"),
+        ] {
+            assert!(!compiled.matches(&record(kind, bucket, key, payload.clone())));
+        }
+        for payload in [
+            json!({"content_length": 500, "user_metadata": {"team": "video"}}),
+            json!({"content_length": 50, "user_metadata": {"team": "docs"}}),
+            json!({"user_metadata": {"team": "video"}}),
+        ] {
+            assert!(!compiled.matches(&record("ObjectCreated", "media", "videos/a.mp4", payload)));
+        }
+
+        // The empty filter takes every object event, but never internal ones.
+        let all = EventFilter::default();
+        let all = CompiledFilter::new(&all).unwrap();
+        assert!(all.matches(&record("ObjectDeleted", "b", "k", json!({}))));
+        assert!(!all.matches(&record("MirrorRepair", "b", "k", json!({}))));
+        assert!(!all.matches(&record("ObjectCreated", "b", ".deltaglider/x", json!({}))));
+
+        // Read and security events go only where their kind is listed.
+        assert!(!all.matches(&record("ObjectAccessed", "b", "k", json!({}))));
+        let reads = EventFilter {
+            kinds: vec!["ObjectAccessed".to_string(), "AuthFailed".to_string()],
+            ..Default::default()
+        };
+        let reads = CompiledFilter::new(&reads).unwrap();
+        assert!(reads.matches(&record("ObjectAccessed", "b", "k", json!({}))));
+        assert!(reads.matches(&record("AuthFailed", "b", "", json!({}))));
+        assert!(!reads.matches(&record("ObjectCreated", "b", "k", json!({}))));
+    }
+
+    #[tokio::test]
+    async fn unmatched_rows_are_stepped_over_a_page_at_a_time() {
+        let db = Arc::new(Mutex::new(ConfigDb::in_memory("test-pass").unwrap()));
+        let config = EventDeliveryConfig {
+            enabled: true,
+            batch_size: 2,
+            endpoints: vec![endpoint(
+                "up",
+                "http://up.example/hook",
+                EventFilter::default(),
+            )],
+            ..Default::default()
+        };
+        let client = FakeClient::default();
+        dispatch_endpoints_once(&db, &client, &config, 100).await;
+        let created = {
+            let db = db.lock().await;
+            let event = |kind| NewEvent::new(kind, "b", "k", EventSource::S3Api, 100, json!({}));
+            for _ in 0..7 {
+                db.event_outbox_insert(&event(EventKind::ObjectAccessed))
+                    .unwrap();
+            }
+            db.event_outbox_insert(&event(EventKind::ObjectCreated))
+                .unwrap()
+        };
+
+        // Seven reads the endpoint does not want fill four pages of two; the
+        // write behind them still goes out on the same tick.
+        dispatch_endpoints_once(&db, &client, &config, 200).await;
+        assert_eq!(
+            *client.delivered.lock().unwrap(),
+            [("http://up.example/hook".to_string(), created)]
+        );
+        assert_eq!(
+            db.lock()
+                .await
+                .listener_cursor_load("event-endpoint:up")
+                .unwrap(),
+            created
+        );
+    }
+
+    #[tokio::test]
+    async fn a_dead_endpoint_only_holds_back_itself() {
+        let db = Arc::new(Mutex::new(ConfigDb::in_memory("test-pass").unwrap()));
+        let media_only = EventFilter {
+            buckets: vec!["media".to_string()],
+            ..Default::default()
+        };
+        let config = EventDeliveryConfig {
+            enabled: true,
+            endpoints: vec![
+                endpoint("up", "http://up.example/hook", media_only),
+                endpoint("down", "http://down.example/hook", EventFilter::default()),
+            ],
+            ..Default::default()
+        };
+        let client = FakeClient::default();
+        // The first tick seeds both cursors at the (empty) outbox's end.
+        dispatch_endpoints_once(&db, &client, &config, 100).await;
+        let ids: Vec<i64> = {
+            let db = db.lock().await;
+            ["media", "docs", "media"]
+                .iter()
+                .enumerate()
+                .map(|(i, bucket)| {
+                    db.event_outbox_insert(&NewEvent::new(
+                        EventKind::ObjectCreated,
+                        *bucket,
+                        format!("k{i}"),
+                        EventSource::S3Api,
+                        100,
+                        json!({}),
+                    ))
+                    .unwrap()
+                })
+                .collect()
+        };
+
+        dispatch_endpoints_once(&db, &client, &config, 200).await;
+        assert_eq!(
+            *client.delivered.lock().unwrap(),
+            [
+                ("http://up.example/hook".to_string(), ids[0]),
+                ("http://up.example/hook".to_string(), ids[2]),
+            ]
+        );
+        {
+            let db = db.lock().await;
+            assert_eq!(
+                db.listener_cursor_load("event-endpoint:up").unwrap(),
+                ids[2]
+            );
+            assert_eq!(db.listener_cursor_load("event-endpoint:down").unwrap(), 0);
+            let down = db.event_endpoint_state_load("down").unwrap().unwrap();
+            assert_eq!((down.event_id, down.attempts), (ids[0], 1));
+            assert_eq!(down.next_attempt_at, Some(205));
+            let statuses = endpoint_statuses(&db, &config);
+            assert_eq!(
+                statuses[1].last_error.as_deref(),
+                Some("connection refused")
+            );
+        }
+
+        // Still backing off: nothing is attempted.
+        dispatch_endpoints_once(&db, &client, &config, 204).await;
+        assert_eq!(
+            db.lock()
+                .await
+                .event_endpoint_state_load("down")
+                .unwrap()
+                .unwrap()
+                .attempts,
+            1
+        );
+
+        // The second attempt exhausts `max_attempts = 2`: the event is skipped
+        // and the next one tried.
+        dispatch_endpoints_once(&db, &client, &config, 205).await;
+        {
+            let db = db.lock().await;
+            assert_eq!(
+                db.listener_cursor_load("event-endpoint:down").unwrap(),
+                ids[0]
+            );
+            let down = db.event_endpoint_state_load("down").unwrap().unwrap();
+            assert_eq!(down.skipped, 1);
+            assert_eq!((down.event_id, down.attempts), (ids[1], 1));
+        }
+
+        // Removing an endpoint forgets its cursor and retry state.
+        let config = EventDeliveryConfig {
+            endpoints: config.endpoints[..1].to_vec(),
+            ..config
+        };
+        dispatch_endpoints_once(&db, &client, &config, 300).await;
+        let db = db.lock().await;
+        assert!(db
+            .listener_cursor_load_full("event-endpoint:down")
+            .unwrap()
+            .is_none());
+        assert!(db.event_endpoint_state_load("down").unwrap().is_none());
+    }
+}
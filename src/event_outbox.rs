@@ -109,6 +109,16 @@ pub enum EventKind {
     ReplicationObjectCopied,
     LifecycleExpired,
     LifecycleTransitioned,
+    /// A mirrored bucket's copy missed a write and awaits repair (internal
+    /// work item, not an object-state change).
+    MirrorRepair,
+    /// An object was read (GetObject / HeadObject). Opt-in, see
+    /// `EventDeliveryConfig::access_events`.
+    ObjectAccessed,
+    /// An authenticated request was denied by IAM.
+    AccessDenied,
+    /// A request presented credentials that were rejected.
+    AuthFailed,
 }
 
 impl EventKind {
@@ -120,15 +130,35 @@ impl EventKind {
             Self::ReplicationObjectCopied => "ReplicationObjectCopied",
             Self::LifecycleExpired => "LifecycleExpired",
             Self::LifecycleTransitioned => "LifecycleTransitioned",
+            Self::MirrorRepair => "MirrorRepair",
+            Self::ObjectAccessed => "ObjectAccessed",
+            Self::AccessDenied => "AccessDenied",
+            Self::AuthFailed => "AuthFailed",
         }
     }
+
+    /// Read and security events: journal rows with their own retention that
+    /// only named endpoints deliver (see [`ACCESS_KINDS_SQL`]).
+    pub fn is_access(self) -> bool {
+        matches!(
+            self,
+            Self::ObjectAccessed | Self::AccessDenied | Self::AuthFailed
+        )
+    }
 }
 
+/// SQL list of the [`EventKind::is_access`] kinds. Access rows are inserted
+/// already `delivered` (the shared webhook / sink / Slack delivery never
+/// claims them) and are pruned only by the access-event retention, never by
+/// the delivered-row prunes.
+const ACCESS_KINDS_SQL: &str = "('ObjectAccessed', 'AccessDenied', 'AuthFailed')";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EventSource {
     S3Api,
     Replication,
     Lifecycle,
+    Mirror,
 }
 
 impl EventSource {
@@ -137,6 +167,7 @@ impl EventSource {
             Self::S3Api => "s3_api",
             Self::Replication => "replication",
             Self::Lifecycle => "lifecycle",
+            Self::Mirror => "mirror",
         }
     }
 }
@@ -228,19 +259,27 @@ impl ConfigDb {
         {
             let mut stmt = tx.prepare(
                 "INSERT INTO event_outbox
-                    (kind, bucket, object_key, source, occurred_at, payload_json, status)
-                 VALUES (?, ?, ?, ?, ?, ?, 'pending')",
+                    (kind, bucket, object_key, source, occurred_at, payload_json,
+                     status, delivered_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
             )?;
             for event in events {
                 let payload_json = serde_json::to_string(&event.payload)
                     .map_err(|e| ConfigDbError::Other(e.to_string()))?;
+                let (status, delivered_at) = if event.kind.is_access() {
+                    (STATUS_DELIVERED, Some(event.occurred_at))
+                } else {
+                    (STATUS_PENDING, None)
+                };
                 let id = stmt.insert(params![
                     event.kind.as_str(),
                     event.bucket,
                     event.key,
                     event.source.as_str(),
                     event.occurred_at,
-                    payload_json
+                    payload_json,
+                    status,
+                    delivered_at
                 ])?;
                 ids.push(id);
             }
@@ -551,17 +590,20 @@ impl ConfigDb {
             return Ok(0);
         }
         let deleted = self.conn.execute(
-            "DELETE FROM event_outbox
-              WHERE id IN (
-                    SELECT id
-                      FROM event_outbox
-                     WHERE status = 'delivered'
-                       AND delivered_at IS NOT NULL
-                       AND delivered_at < ?
-                       AND id <= ?
-                     ORDER BY delivered_at ASC, id ASC
-                     LIMIT ?
-              )",
+            &format!(
+                "DELETE FROM event_outbox
+                  WHERE id IN (
+                        SELECT id
+                          FROM event_outbox
+                         WHERE status = 'delivered'
+                           AND delivered_at IS NOT NULL
+                           AND delivered_at < ?
+                           AND id <= ?
+                           AND kind NOT IN {ACCESS_KINDS_SQL}
+                         ORDER BY delivered_at ASC, id ASC
+                         LIMIT ?
+                  )"
+            ),
             params![before, min_keep_id, limit as i64],
         )?;
         Ok(deleted)
@@ -581,25 +623,79 @@ impl ConfigDb {
             return Ok(0);
         }
         let deleted = self.conn.execute(
-            "DELETE FROM event_outbox
-              WHERE id IN (
-                    SELECT id
-                      FROM (
-                            SELECT id
-                              FROM event_outbox
-                             WHERE status = 'delivered'
-                               AND id <= ?
-                             ORDER BY COALESCE(delivered_at, occurred_at) DESC, id DESC
-                             LIMIT -1 OFFSET ?
-                      )
-                     ORDER BY id ASC
-                     LIMIT ?
-              )",
+            &format!(
+                "DELETE FROM event_outbox
+                  WHERE id IN (
+                        SELECT id
+                          FROM (
+                                SELECT id
+                                  FROM event_outbox
+                                 WHERE status = 'delivered'
+                                   AND id <= ?
+                                   AND kind NOT IN {ACCESS_KINDS_SQL}
+                                 ORDER BY COALESCE(delivered_at, occurred_at) DESC, id DESC
+                                 LIMIT -1 OFFSET ?
+                          )
+                         ORDER BY id ASC
+                         LIMIT ?
+                  )"
+            ),
             params![min_keep_id, max_delivered_rows as i64, limit as i64],
         )?;
         Ok(deleted)
     }
 
+    /// Delete up to `limit` access/security rows ([`EventKind::is_access`])
+    /// that occurred before `before`, or that exceed the newest `max_rows`.
+    /// `min_keep_id` is the listener-cursor floor, as for the delivered
+    /// prunes, so an endpoint still reading them keeps its rows.
+    pub fn event_outbox_prune_access_events(
+        &self,
+        before: i64,
+        max_rows: u32,
+        limit: u32,
+        min_keep_id: i64,
+    ) -> Result<usize, ConfigDbError> {
+        if limit == 0 {
+            return Ok(0);
+        }
+        let aged = self.conn.execute(
+            &format!(
+                "DELETE FROM event_outbox
+                  WHERE id IN (
+                        SELECT id
+                          FROM event_outbox
+                         WHERE kind IN {ACCESS_KINDS_SQL}
+                           AND occurred_at < ?
+                           AND id <= ?
+                         ORDER BY id ASC
+                         LIMIT ?
+                  )"
+            ),
+            params![before, min_keep_id, limit as i64],
+        )?;
+        let over = self.conn.execute(
+            &format!(
+                "DELETE FROM event_outbox
+                  WHERE id IN (
+                        SELECT id
+                          FROM (
+                                SELECT id
+                                  FROM event_outbox
+                                 WHERE kind IN {ACCESS_KINDS_SQL}
+                                   AND id <= ?
+                                 ORDER BY id DESC
+                                 LIMIT -1 OFFSET ?
+                          )
+                         ORDER BY id ASC
+                         LIMIT ?
+                  )"
+            ),
+            params![min_keep_id, max_rows as i64, limit as i64],
+        )?;
+        Ok(aged + over)
+    }
+
     /// Age out terminal `failed` rows older than `before` (occurred_at), bounded
     /// by `limit`. Failed rows (retries exhausted) are otherwise never pruned —
     /// a dead webhook/Slack target would grow the DB unbounded.
@@ -785,6 +881,124 @@ impl ConfigDb {
         Ok(())
     }
 
+    /// Mark the listener active without moving its cursor. For a listener
+    /// that is caught up (or retrying within its policy) and should keep
+    /// pinning the prune floor; a wedged listener must not call this (H65).
+    pub fn listener_cursor_touch(&self, listener: &str, now: i64) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "UPDATE listener_cursors SET updated_at = MAX(updated_at, ?)
+              WHERE listener_name = ?",
+            params![now, listener],
+        )?;
+        Ok(())
+    }
+
+    /// Every cursor whose listener name starts with `prefix`.
+    pub fn listener_cursors_with_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<ListenerCursor>, ConfigDbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT listener_name, last_event_id, updated_at
+               FROM listener_cursors
+              WHERE substr(listener_name, 1, length(?1)) = ?1
+              ORDER BY listener_name",
+        )?;
+        let rows = stmt
+            .query_map(params![prefix], |row| {
+                Ok(ListenerCursor {
+                    listener_name: row.get(0)?,
+                    last_event_id: row.get(1)?,
+                    updated_at: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Forget a listener's cursor, so it no longer pins the prune floor.
+    pub fn listener_cursor_delete(&self, listener: &str) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "DELETE FROM listener_cursors WHERE listener_name = ?",
+            params![listener],
+        )?;
+        Ok(())
+    }
+
+    // === Delivery-endpoint retry state (schema v28) ===
+    //
+    // A named delivery endpoint walks the outbox with its own listener cursor
+    // and retries the event at its head until it succeeds or the endpoint's
+    // attempt budget runs out. The head event's attempt count and backoff live
+    // here so they survive a restart.
+
+    pub fn event_endpoint_state_load(
+        &self,
+        endpoint: &str,
+    ) -> Result<Option<EventEndpointState>, ConfigDbError> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT endpoint, event_id, attempts, next_attempt_at, last_error,
+                        skipped, updated_at
+                   FROM event_endpoint_state WHERE endpoint = ?",
+                params![endpoint],
+                endpoint_state_from_row,
+            )
+            .optional()?;
+        Ok(row)
+    }
+
+    pub fn event_endpoint_states(&self) -> Result<Vec<EventEndpointState>, ConfigDbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT endpoint, event_id, attempts, next_attempt_at, last_error,
+                    skipped, updated_at
+               FROM event_endpoint_state
+              ORDER BY endpoint",
+        )?;
+        let rows = stmt
+            .query_map([], endpoint_state_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    pub fn event_endpoint_state_save(
+        &self,
+        state: &EventEndpointState,
+    ) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "INSERT INTO event_endpoint_state
+                    (endpoint, event_id, attempts, next_attempt_at, last_error,
+                     skipped, updated_at)
+                  VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(endpoint) DO UPDATE SET
+                  event_id        = excluded.event_id,
+                  attempts        = excluded.attempts,
+                  next_attempt_at = excluded.next_attempt_at,
+                  last_error      = excluded.last_error,
+                  skipped         = excluded.skipped,
+                  updated_at      = excluded.updated_at",
+            params![
+                state.endpoint,
+                state.event_id,
+                state.attempts,
+                state.next_attempt_at,
+                state.last_error,
+                state.skipped,
+                state.updated_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn event_endpoint_state_delete(&self, endpoint: &str) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "DELETE FROM event_endpoint_state WHERE endpoint = ?",
+            params![endpoint],
+        )?;
+        Ok(())
+    }
+
     /// Load the full cursor row (for surfacing "last event applied at" in the
     /// admin UI). `None` if the listener has no cursor yet.
     pub fn listener_cursor_load_full(
@@ -867,6 +1081,52 @@ pub struct ListenerCursor {
     pub updated_at: i64,
 }
 
+/// Retry state of one named delivery endpoint. `event_id` is the event at the
+/// endpoint's head that is failing (0 when none is); `skipped` counts events
+/// given up on after the endpoint's attempt budget ran out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EventEndpointState {
+    pub endpoint: String,
+    pub event_id: i64,
+    pub attempts: i64,
+    pub next_attempt_at: Option<i64>,
+    pub last_error: Option<String>,
+    pub skipped: i64,
+    pub updated_at: i64,
+}
+
+fn endpoint_state_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<EventEndpointState> {
+    Ok(EventEndpointState {
+        endpoint: row.get(0)?,
+        event_id: row.get(1)?,
+        attempts: row.get(2)?,
+        next_attempt_at: row.get(3)?,
+        last_error: row.get(4)?,
+        skipped: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+/// Attach an object's user metadata (`x-amz-meta-*`, without DeltaGlider's
+/// own `dg-*` markers) to an event payload, so delivery endpoints can filter
+/// on it. Leaves the payload alone when there is none.
+pub fn attach_user_metadata(
+    payload: &mut Value,
+    user_metadata: &std::collections::HashMap<String, String>,
+) {
+    let visible: serde_json::Map<String, Value> = user_metadata
+        .iter()
+        .filter(|(k, _)| !k.starts_with("dg-"))
+        .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+        .collect();
+    if visible.is_empty() {
+        return;
+    }
+    if let Some(obj) = payload.as_object_mut() {
+        obj.insert("user_metadata".to_string(), Value::Object(visible));
+    }
+}
+
 fn event_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<EventOutboxRecord> {
     let payload_json: String = row.get(6)?;
     let payload = serde_json::from_str(&payload_json)
@@ -1091,6 +1351,70 @@ mod tests {
         assert!(ids.contains(&failed));
     }
 
+    #[test]
+    fn access_events_are_journal_rows_with_their_own_retention() {
+        let db = ConfigDb::in_memory("test-pass").unwrap();
+        let access = |ts: i64, key: &str| {
+            NewEvent::new(
+                EventKind::ObjectAccessed,
+                "bucket",
+                key,
+                EventSource::S3Api,
+                ts,
+                json!({}),
+            )
+        };
+        let mutation = db.event_outbox_insert(&event_at(10, "written")).unwrap();
+        db.event_outbox_mark_delivered(mutation, 10).unwrap();
+        let old = db.event_outbox_insert(&access(10, "old")).unwrap();
+        let mid = db.event_outbox_insert(&access(20, "mid")).unwrap();
+        let new = db.event_outbox_insert(&access(30, "new")).unwrap();
+
+        // Stored delivered, so the shared delivery never claims them.
+        assert_eq!(
+            db.event_outbox_load(old).unwrap().unwrap().status,
+            STATUS_DELIVERED
+        );
+        assert!(db
+            .event_outbox_claim_due("worker", 100, 30, 10)
+            .unwrap()
+            .is_empty());
+
+        // The delivered-row prunes leave them alone...
+        assert_eq!(
+            db.event_outbox_prune_delivered_over_count(0, 100, i64::MAX)
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            db.event_outbox_prune_delivered_before(100, 100, i64::MAX)
+                .unwrap(),
+            0
+        );
+        // ...and their own prune respects the cursor floor, age and count.
+        assert_eq!(
+            db.event_outbox_prune_access_events(15, 100, 100, mid)
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            db.event_outbox_prune_access_events(0, 1, 100, mid).unwrap(),
+            0
+        );
+        assert_eq!(
+            db.event_outbox_prune_access_events(0, 1, 100, i64::MAX)
+                .unwrap(),
+            1
+        );
+        let ids: Vec<i64> = db
+            .event_outbox_recent(10)
+            .unwrap()
+            .iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(ids, vec![new]);
+    }
+
     #[test]
     fn requeue_failed_preserves_attempt_count_and_clears_failure_state() {
         let db = ConfigDb::in_memory("test-pass").unwrap();
@@ -1349,4 +1673,24 @@ mod tests {
         // Floor 0 (no active listener has consumed anything) prunes nothing.
         assert_eq!(db.event_outbox_prune_below_floor(0, 100).unwrap(), 0);
     }
+
+    #[test]
+    fn user_metadata_is_attached_without_internal_markers() {
+        let mut payload = json!({"etag": "x"});
+        let meta = [
+            ("team".to_string(), "video".to_string()),
+            ("dg-encrypted".to_string(), "aes-256-gcm-v1".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        attach_user_metadata(&mut payload, &meta);
+        assert_eq!(
+            payload,
+            json!({"etag": "x", "user_metadata": {"team": "video"}})
+        );
+
+        let mut payload = json!({"etag": "x"});
+        attach_user_metadata(&mut payload, &Default::default());
+        assert_eq!(payload, json!({"etag": "x"}));
+    }
 }
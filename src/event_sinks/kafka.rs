@@ -0,0 +1,823 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Kafka sink. `url` is the bootstrap list, either bare
+//! (`host:port[,host:port…]`, plaintext) or as
+//! `kafka://[user:pass@]host:port[,…][?sasl=<mechanism>]`, where `kafkas://`
+//! connects over TLS. With credentials, every connection authenticates
+//! (SaslHandshake v1 + SaslAuthenticate) with `PLAIN` (the default),
+//! `SCRAM-SHA-256` or `SCRAM-SHA-512`.
+//!
+//! Looks up partition leaders with Metadata v1, then produces one-record
+//! v2 batches with Produce v3 and `acks=all`. The record key is
+//! `<bucket>/<key>` and picks the partition the way the Java client's
+//! default partitioner does (murmur2), so consumers keyed on the object see
+//! its events in order. The record carries a `dgp-message-id` header.
+
+use super::scram::{Scram, ScramHash};
+use super::{reply_len, with_timeout, BrokerStream, EventSink, Tls};
+use crate::event_outbox::EventOutboxRecord;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+const API_PRODUCE: i16 = 0;
+const API_METADATA: i16 = 3;
+const API_SASL_HANDSHAKE: i16 = 17;
+const API_SASL_AUTHENTICATE: i16 = 36;
+const CLIENT_ID: &str = "deltaglider-proxy";
+
+/// Broker error codes after which the cached partition leaders are stale.
+const UNKNOWN_TOPIC_OR_PARTITION: i16 = 3;
+const LEADER_NOT_AVAILABLE: i16 = 5;
+const NOT_LEADER_FOR_PARTITION: i16 = 6;
+
+pub struct KafkaSink {
+    bootstrap: Vec<String>,
+    topic: String,
+    tls: Option<Tls>,
+    sasl: Option<Sasl>,
+    state: Mutex<State>,
+}
+
+/// How each connection authenticates.
+#[derive(Debug, PartialEq)]
+struct Sasl {
+    /// `None` = PLAIN.
+    scram: Option<ScramHash>,
+    username: String,
+    password: String,
+}
+
+impl Sasl {
+    fn mechanism(&self) -> &'static str {
+        self.scram.map_or("PLAIN", ScramHash::mechanism)
+    }
+}
+
+/// What a sink URL says: brokers, TLS, credentials.
+#[derive(Debug, PartialEq)]
+struct KafkaUrl {
+    bootstrap: Vec<String>,
+    tls: bool,
+    sasl: Option<Sasl>,
+}
+
+fn parse_url(url: &str) -> Result<KafkaUrl, String> {
+    let url = url.trim();
+    let (tls, rest) = match url.split_once("://") {
+        None => (false, url),
+        Some(("kafka", rest)) => (false, rest),
+        Some(("kafkas", rest)) => (true, rest),
+        Some(_) => return Err("expected host:port[,…] or a kafka:// or kafkas:// URL".to_string()),
+    };
+    let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (user_info, hosts) = match rest.trim_end_matches('/').rsplit_once('@') {
+        Some((user_info, hosts)) => (Some(user_info), hosts),
+        None => (None, rest.trim_end_matches('/')),
+    };
+    let bootstrap: Vec<String> = hosts
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+    if bootstrap.is_empty() {
+        return Err("no bootstrap servers".to_string());
+    }
+    for server in &bootstrap {
+        match server.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {}
+            _ => return Err(format!("bootstrap server {server:?} must be host:port")),
+        }
+    }
+
+    let mut scram = None;
+    let mut mechanism_set = false;
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        match pair.split_once('=') {
+            Some(("sasl", mechanism)) => {
+                mechanism_set = true;
+                scram = match mechanism.to_ascii_uppercase().as_str() {
+                    "PLAIN" => None,
+                    "SCRAM-SHA-256" => Some(ScramHash::Sha256),
+                    "SCRAM-SHA-512" => Some(ScramHash::Sha512),
+                    _ => return Err(format!("unsupported SASL mechanism {mechanism:?}")),
+                };
+            }
+            _ => return Err(format!("unknown URL parameter {pair:?}")),
+        }
+    }
+    let decode = |s: &str| {
+        urlencoding::decode(s)
+            .map(|d| d.into_owned())
+            .map_err(|e| format!("invalid URL user-info: {e}"))
+    };
+    let sasl = match user_info.map(|u| u.split_once(':')) {
+        None if mechanism_set => return Err("sasl needs user:pass@ in the URL".to_string()),
+        None => None,
+        Some(Some((username, password))) if !username.is_empty() => Some(Sasl {
+            scram,
+            username: decode(username)?,
+            password: decode(password)?,
+        }),
+        Some(_) => return Err("SASL credentials must be user:pass".to_string()),
+    };
+    Ok(KafkaUrl {
+        bootstrap,
+        tls,
+        sasl,
+    })
+}
+
+#[derive(Default)]
+struct State {
+    /// Leader address per partition, indexed by partition id.
+    leaders: Option<Vec<Option<String>>>,
+    conns: HashMap<String, BrokerStream>,
+    correlation_id: i32,
+}
+
+impl State {
+    fn next_correlation_id(&mut self) -> i32 {
+        self.correlation_id = self.correlation_id.wrapping_add(1);
+        self.correlation_id
+    }
+}
+
+impl KafkaSink {
+    pub fn new(url: &str, topic: &str, ca_file: Option<&str>) -> Result<Self, String> {
+        let url = parse_url(url)?;
+        if topic.len() > 249
+            || !topic
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        {
+            return Err(format!("invalid Kafka topic {topic:?}"));
+        }
+        Ok(Self {
+            bootstrap: url.bootstrap,
+            topic: topic.to_string(),
+            tls: Tls::for_url(url.tls, ca_file)?,
+            sasl: url.sasl,
+            state: Mutex::new(State::default()),
+        })
+    }
+
+    /// Send one request on the connection to `addr` (opened and
+    /// authenticated if needed) and return the response body after the
+    /// correlation id.
+    async fn request(
+        &self,
+        state: &mut State,
+        addr: &str,
+        api_key: i16,
+        api_version: i16,
+        body: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        if !state.conns.contains_key(addr) {
+            let conn = self.open(state, addr).await?;
+            state.conns.insert(addr.to_string(), conn);
+        }
+        let correlation_id = state.next_correlation_id();
+        let conn = state.conns.get_mut(addr).expect("inserted above");
+        let result = exchange(conn, correlation_id, api_key, api_version, body).await;
+        if result.is_err() {
+            state.conns.remove(addr);
+        }
+        result
+    }
+
+    async fn open(&self, state: &mut State, addr: &str) -> Result<BrokerStream, String> {
+        let mut conn = super::connect(addr, self.tls.as_ref()).await?;
+        if let Some(sasl) = &self.sasl {
+            authenticate(&mut conn, state, sasl)
+                .await
+                .map_err(|e| format!("{addr}: SASL {}: {e}", sasl.mechanism()))?;
+        }
+        Ok(conn)
+    }
+
+    async fn refresh_leaders(&self, state: &mut State) -> Result<(), String> {
+        let mut last_err = String::new();
+        for server in &self.bootstrap {
+            match self.fetch_leaders(state, server).await {
+                Ok(leaders) => {
+                    state.leaders = Some(leaders);
+                    return Ok(());
+                }
+                Err(e) => {
+                    state.conns.remove(server);
+                    last_err = format!("{server}: {e}");
+                }
+            }
+        }
+        Err(format!("metadata: {last_err}"))
+    }
+
+    async fn fetch_leaders(
+        &self,
+        state: &mut State,
+        server: &str,
+    ) -> Result<Vec<Option<String>>, String> {
+        let mut body = Vec::new();
+        body.extend_from_slice(&1i32.to_be_bytes());
+        put_str(&mut body, &self.topic);
+        let resp = self.request(state, server, API_METADATA, 1, &body).await?;
+        let mut r = Reader(&resp);
+
+        let mut brokers = HashMap::new();
+        for _ in 0..r.i32()? {
+            let node_id = r.i32()?;
+            let host = r.string()?;
+            let port = r.i32()?;
+            r.nullable_string()?; // rack
+            brokers.insert(node_id, format!("{host}:{port}"));
+        }
+        r.i32()?; // controller id
+        let mut leaders = Vec::new();
+        for _ in 0..r.i32()? {
+            let error = r.i16()?;
+            let name = r.string()?;
+            r.i8()?; // is_internal
+            let mut partitions = Vec::new();
+            for _ in 0..r.i32()? {
+                r.i16()?; // partition error
+                let id = r.i32()?;
+                let leader = r.i32()?;
+                for _ in 0..2 {
+                    // replicas, isr
+                    for _ in 0..r.i32()? {
+                        r.i32()?;
+                    }
+                }
+                partitions.push((id, leader));
+            }
+            if name != self.topic {
+                continue;
+            }
+            if error == UNKNOWN_TOPIC_OR_PARTITION {
+                return Err(format!("topic {:?} does not exist", self.topic));
+            }
+            if error != 0 || partitions.is_empty() {
+                return Err(format!(
+                    "topic {:?} unavailable (error {error})",
+                    self.topic
+                ));
+            }
+            partitions.sort_unstable();
+            leaders = partitions
+                .into_iter()
+                .map(|(_, leader)| brokers.get(&leader).cloned())
+                .collect();
+        }
+        if leaders.is_empty() {
+            return Err(format!("no metadata for topic {:?}", self.topic));
+        }
+        Ok(leaders)
+    }
+
+    async fn produce(
+        &self,
+        state: &mut State,
+        event: &EventOutboxRecord,
+        body: &[u8],
+    ) -> Result<(), String> {
+        if state.leaders.is_none() {
+            self.refresh_leaders(state).await?;
+        }
+        let leaders = state.leaders.as_ref().expect("refreshed above");
+        let key = super::ordering_key(event);
+        let partition = partition_for(key.as_bytes(), leaders.len());
+        let leader = leaders[partition]
+            .clone()
+            .ok_or_else(|| format!("partition {partition} has no leader"))?;
+
+        let batch = record_batch(
+            key.as_bytes(),
+            body,
+            &[("dgp-message-id", super::message_id(event).as_bytes())],
+            event.occurred_at.saturating_mul(1000),
+        );
+        let mut req = Vec::new();
+        req.extend_from_slice(&(-1i16).to_be_bytes()); // transactional id
+        req.extend_from_slice(&(-1i16).to_be_bytes()); // acks = all
+        req.extend_from_slice(&30_000i32.to_be_bytes());
+        req.extend_from_slice(&1i32.to_be_bytes());
+        put_str(&mut req, &self.topic);
+        req.extend_from_slice(&1i32.to_be_bytes());
+        req.extend_from_slice(&(partition as i32).to_be_bytes());
+        req.extend_from_slice(&(batch.len() as i32).to_be_bytes());
+        req.extend_from_slice(&batch);
+
+        let resp = self.request(state, &leader, API_PRODUCE, 3, &req).await?;
+        let mut r = Reader(&resp);
+        for _ in 0..r.i32()? {
+            r.string()?;
+            for _ in 0..r.i32()? {
+                let id = r.i32()?;
+                let error = r.i16()?;
+                r.i64()?; // base offset
+                r.i64()?; // log append time
+                if id as usize != partition {
+                    continue;
+                }
+                return match error {
+                    0 => Ok(()),
+                    UNKNOWN_TOPIC_OR_PARTITION
+                    | LEADER_NOT_AVAILABLE
+                    | NOT_LEADER_FOR_PARTITION => {
+                        state.leaders = None;
+                        Err(format!(
+                            "partition {partition}: leader moved (error {error})"
+                        ))
+                    }
+                    _ => Err(format!(
+                        "partition {partition}: produce failed (error {error})"
+                    )),
+                };
+            }
+        }
+        Err("produce response has no result for the partition".to_string())
+    }
+}
+
+/// Log in on a fresh connection: SaslHandshake v1 names the mechanism,
+/// then its messages go in SaslAuthenticate v0 requests.
+async fn authenticate(
+    conn: &mut BrokerStream,
+    state: &mut State,
+    sasl: &Sasl,
+) -> Result<(), String> {
+    let mut body = Vec::new();
+    put_str(&mut body, sasl.mechanism());
+    let resp = exchange(
+        conn,
+        state.next_correlation_id(),
+        API_SASL_HANDSHAKE,
+        1,
+        &body,
+    )
+    .await?;
+    let mut r = Reader(&resp);
+    let error = r.i16()?;
+    if error != 0 {
+        let mut offered = Vec::new();
+        for _ in 0..r.i32()? {
+            offered.push(r.string()?);
+        }
+        return Err(format!(
+            "not enabled on the broker (error {error}; offers {offered:?})"
+        ));
+    }
+
+    match sasl.scram {
+        None => {
+            let plain = format!("\0{}\0{}", sasl.username, sasl.password);
+            sasl_round(conn, state, plain.as_bytes()).await?;
+        }
+        Some(hash) => {
+            let mut scram = Scram::new(hash, &sasl.username, &sasl.password);
+            let server_first = sasl_round(conn, state, scram.client_first().as_bytes()).await?;
+            let client_final = scram.client_final(&server_first).await?;
+            let server_final = sasl_round(conn, state, client_final.as_bytes()).await?;
+            scram.verify_server_final(&server_final)?;
+        }
+    }
+    Ok(())
+}
+
+/// One SaslAuthenticate exchange: our message out, the broker's back.
+async fn sasl_round(
+    conn: &mut BrokerStream,
+    state: &mut State,
+    message: &[u8],
+) -> Result<String, String> {
+    let mut body = Vec::with_capacity(message.len() + 4);
+    body.extend_from_slice(&(message.len() as i32).to_be_bytes());
+    body.extend_from_slice(message);
+    let resp = exchange(
+        conn,
+        state.next_correlation_id(),
+        API_SASL_AUTHENTICATE,
+        0,
+        &body,
+    )
+    .await?;
+    let mut r = Reader(&resp);
+    let error = r.i16()?;
+    let reason = r.nullable_string()?;
+    if error != 0 {
+        return Err(format!(
+            "rejected (error {error}): {}",
+            reason.unwrap_or_default()
+        ));
+    }
+    let len = r.i32()?.max(0) as usize;
+    Ok(String::from_utf8_lossy(r.take(len)?).into_owned())
+}
+
+/// Send one request and read its response; the body after the
+/// correlation id is returned.
+async fn exchange(
+    conn: &mut BrokerStream,
+    correlation_id: i32,
+    api_key: i16,
+    api_version: i16,
+    body: &[u8],
+) -> Result<Vec<u8>, String> {
+    let mut frame = Vec::with_capacity(body.len() + 32);
+    frame.extend_from_slice(&0i32.to_be_bytes());
+    frame.extend_from_slice(&api_key.to_be_bytes());
+    frame.extend_from_slice(&api_version.to_be_bytes());
+    frame.extend_from_slice(&correlation_id.to_be_bytes());
+    put_str(&mut frame, CLIENT_ID);
+    frame.extend_from_slice(body);
+    let len = (frame.len() - 4) as i32;
+    frame[..4].copy_from_slice(&len.to_be_bytes());
+
+    conn.write_all(&frame).await.map_err(|e| e.to_string())?;
+    let len = conn.read_i32().await.map_err(|e| e.to_string())?;
+    if len < 4 {
+        return Err(format!("bad response length {len}"));
+    }
+    let mut resp = vec![0; reply_len(len as usize, "response")?];
+    conn.read_exact(&mut resp)
+        .await
+        .map_err(|e| e.to_string())?;
+    if resp[..4] != correlation_id.to_be_bytes() {
+        return Err("response correlation id mismatch".to_string());
+    }
+    resp.drain(..4);
+    Ok(resp)
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as i16).to_be_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Kafka's zigzag varint, as used inside v2 records.
+fn put_varint(out: &mut Vec<u8>, value: i64) {
+    let mut v = ((value << 1) ^ (value >> 63)) as u64;
+    while v >= 0x80 {
+        out.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+fn put_bytes_varint(out: &mut Vec<u8>, data: &[u8]) {
+    put_varint(out, data.len() as i64);
+    out.extend_from_slice(data);
+}
+
+/// A v2 record batch holding a single record.
+fn record_batch(key: &[u8], value: &[u8], headers: &[(&str, &[u8])], timestamp_ms: i64) -> Vec<u8> {
+    let mut record = vec![0]; // attributes
+    put_varint(&mut record, 0); // timestamp delta
+    put_varint(&mut record, 0); // offset delta
+    put_bytes_varint(&mut record, key);
+    put_bytes_varint(&mut record, value);
+    put_varint(&mut record, headers.len() as i64);
+    for (name, value) in headers {
+        put_bytes_varint(&mut record, name.as_bytes());
+        put_bytes_varint(&mut record, value);
+    }
+
+    // Everything the CRC covers: attributes through the records.
+    let mut tail = Vec::with_capacity(record.len() + 48);
+    tail.extend_from_slice(&0i16.to_be_bytes()); // attributes
+    tail.extend_from_slice(&0i32.to_be_bytes()); // last offset delta
+    tail.extend_from_slice(&timestamp_ms.to_be_bytes());
+    tail.extend_from_slice(&timestamp_ms.to_be_bytes());
+    tail.extend_from_slice(&(-1i64).to_be_bytes()); // producer id
+    tail.extend_from_slice(&(-1i16).to_be_bytes()); // producer epoch
+    tail.extend_from_slice(&(-1i32).to_be_bytes()); // base sequence
+    tail.extend_from_slice(&1i32.to_be_bytes());
+    put_varint(&mut tail, record.len() as i64);
+    tail.extend_from_slice(&record);
+
+    let mut batch = Vec::with_capacity(tail.len() + 21);
+    batch.extend_from_slice(&0i64.to_be_bytes()); // base offset
+    batch.extend_from_slice(&((tail.len() + 9) as i32).to_be_bytes());
+    batch.extend_from_slice(&(-1i32).to_be_bytes()); // partition leader epoch
+    batch.push(2); // magic
+    batch.extend_from_slice(&crc32c(&tail).to_be_bytes());
+    batch.extend_from_slice(&tail);
+    batch
+}
+
+fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0x82F6_3B78
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// The Java client's `Utils.murmur2`.
+fn murmur2(data: &[u8]) -> i32 {
+    const M: u32 = 0x5bd1_e995;
+    let mut h = 0x9747_b28c_u32 ^ data.len() as u32;
+    let chunks = data.chunks_exact(4);
+    let rest = chunks.remainder();
+    for chunk in chunks {
+        let mut k = u32::from_le_bytes(chunk.try_into().expect("4-byte chunk"));
+        k = k.wrapping_mul(M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M) ^ k;
+    }
+    if !rest.is_empty() {
+        for (i, &b) in rest.iter().enumerate().rev() {
+            h ^= (b as u32) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h as i32
+}
+
+/// The default partitioner's choice for a keyed record.
+fn partition_for(key: &[u8], partitions: usize) -> usize {
+    (murmur2(key) & 0x7fff_ffff) as usize % partitions
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8], String> {
+        if self.0.len() < n {
+            return Err("truncated response".to_string());
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+    fn i8(&mut self) -> Result<i8, String> {
+        Ok(self.take(1)?[0] as i8)
+    }
+    fn i16(&mut self) -> Result<i16, String> {
+        Ok(i16::from_be_bytes(
+            self.take(2)?.try_into().expect("2 bytes"),
+        ))
+    }
+    fn i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_be_bytes(
+            self.take(4)?.try_into().expect("4 bytes"),
+        ))
+    }
+    fn i64(&mut self) -> Result<i64, String> {
+        Ok(i64::from_be_bytes(
+            self.take(8)?.try_into().expect("8 bytes"),
+        ))
+    }
+    fn nullable_string(&mut self) -> Result<Option<String>, String> {
+        let len = self.i16()?;
+        if len < 0 {
+            return Ok(None);
+        }
+        Ok(Some(
+            String::from_utf8_lossy(self.take(len as usize)?).into_owned(),
+        ))
+    }
+    fn string(&mut self) -> Result<String, String> {
+        self.nullable_string()?
+            .ok_or_else(|| "unexpected null string".to_string())
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    async fn publish(
+        &self,
+        event: &EventOutboxRecord,
+        body: &[u8],
+        timeout: Duration,
+    ) -> Result<(), String> {
+        let mut state = self.state.lock().await;
+        let result = with_timeout(
+            timeout,
+            "kafka produce",
+            self.produce(&mut state, event, body),
+        )
+        .await;
+        if result.is_err() {
+            // A timeout may leave a response in flight; start clean.
+            state.conns.clear();
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[test]
+    fn murmur2_matches_the_java_client() {
+        assert_eq!(murmur2(b"21"), -973_932_308);
+        assert_eq!(murmur2(b"foobar"), -790_332_482);
+        assert_eq!(murmur2(b"abc"), 479_470_107);
+        assert_eq!(murmur2(b"a-little-bit-long-string"), -985_981_536);
+    }
+
+    #[test]
+    fn crc32c_check_value() {
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+    }
+
+    #[test]
+    fn varints_are_zigzag() {
+        let mut out = Vec::new();
+        for v in [0, -1, 1, 300] {
+            put_varint(&mut out, v);
+        }
+        assert_eq!(out, [0x00, 0x01, 0x02, 0xd8, 0x04]);
+    }
+
+    #[test]
+    fn urls_carry_tls_and_sasl() {
+        let bare = parse_url("k1:9092, k2:9092").unwrap();
+        assert_eq!(bare.bootstrap, ["k1:9092", "k2:9092"]);
+        assert!(!bare.tls && bare.sasl.is_none());
+
+        let url = parse_url("kafkas://dgp:p%40ss@k1:9093,k2:9093?sasl=scram-sha-512").unwrap();
+        assert_eq!(url.bootstrap, ["k1:9093", "k2:9093"]);
+        assert!(url.tls);
+        assert_eq!(
+            url.sasl,
+            Some(Sasl {
+                scram: Some(ScramHash::Sha512),
+                username: "dgp".to_string(),
+                password: "p@ss".to_string(),
+            })
+        );
+        let plain = parse_url("kafka://u:p@k1:9092/").unwrap();
+        assert_eq!(plain.sasl.unwrap().mechanism(), "PLAIN");
+
+        for bad in [
+            "kafka://k1:9092?sasl=plain",
+            "kafka://u@k1:9092",
+            "kafka://u:p@k1:9092?sasl=gssapi",
+            "kafka://k1:9092?acks=1",
+            "http://k1:9092",
+            "kafka://k1",
+        ] {
+            assert!(parse_url(bad).is_err(), "{bad}");
+        }
+    }
+
+    async fn read_request(conn: &mut TcpStream) -> (i16, i32, Vec<u8>) {
+        let len = conn.read_i32().await.unwrap();
+        let mut req = vec![0; len as usize];
+        conn.read_exact(&mut req).await.unwrap();
+        let mut r = Reader(&req);
+        let api_key = r.i16().unwrap();
+        r.i16().unwrap();
+        let correlation_id = r.i32().unwrap();
+        r.string().unwrap();
+        let body = r.0.to_vec();
+        (api_key, correlation_id, body)
+    }
+
+    async fn respond(conn: &mut TcpStream, correlation_id: i32, body: &[u8]) {
+        let mut frame = ((body.len() + 4) as i32).to_be_bytes().to_vec();
+        frame.extend_from_slice(&correlation_id.to_be_bytes());
+        frame.extend_from_slice(body);
+        conn.write_all(&frame).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn produce_goes_to_the_key_partition_leader() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut conn, _) = listener.accept().await.unwrap();
+            let (api_key, cid, _) = read_request(&mut conn).await;
+            assert_eq!(api_key, API_METADATA);
+            // One broker (this one), topic "events" with 3 partitions.
+            let mut meta = Vec::new();
+            meta.extend_from_slice(&1i32.to_be_bytes());
+            meta.extend_from_slice(&7i32.to_be_bytes());
+            put_str(&mut meta, &addr.ip().to_string());
+            meta.extend_from_slice(&(addr.port() as i32).to_be_bytes());
+            meta.extend_from_slice(&(-1i16).to_be_bytes());
+            meta.extend_from_slice(&7i32.to_be_bytes());
+            meta.extend_from_slice(&1i32.to_be_bytes());
+            meta.extend_from_slice(&0i16.to_be_bytes());
+            put_str(&mut meta, "events");
+            meta.push(0);
+            meta.extend_from_slice(&3i32.to_be_bytes());
+            for p in 0..3i32 {
+                meta.extend_from_slice(&0i16.to_be_bytes());
+                meta.extend_from_slice(&p.to_be_bytes());
+                meta.extend_from_slice(&7i32.to_be_bytes());
+                meta.extend_from_slice(&0i32.to_be_bytes());
+                meta.extend_from_slice(&0i32.to_be_bytes());
+            }
+            respond(&mut conn, cid, &meta).await;
+
+            let (api_key, cid, body) = read_request(&mut conn).await;
+            assert_eq!(api_key, API_PRODUCE);
+            let mut r = Reader(&body);
+            assert_eq!(r.nullable_string().unwrap(), None);
+            assert_eq!(r.i16().unwrap(), -1);
+            r.i32().unwrap();
+            r.i32().unwrap();
+            assert_eq!(r.string().unwrap(), "events");
+            r.i32().unwrap();
+            let partition = r.i32().unwrap();
+            let batch_len = r.i32().unwrap() as usize;
+            let batch = r.take(batch_len).unwrap().to_vec();
+
+            let mut resp = Vec::new();
+            resp.extend_from_slice(&1i32.to_be_bytes());
+            put_str(&mut resp, "events");
+            resp.extend_from_slice(&1i32.to_be_bytes());
+            resp.extend_from_slice(&partition.to_be_bytes());
+            resp.extend_from_slice(&0i16.to_be_bytes());
+            resp.extend_from_slice(&42i64.to_be_bytes());
+            resp.extend_from_slice(&(-1i64).to_be_bytes());
+            resp.extend_from_slice(&0i32.to_be_bytes());
+            respond(&mut conn, cid, &resp).await;
+            (partition, batch)
+        });
+
+        let sink = KafkaSink::new(&addr.to_string(), "events", None).unwrap();
+        let event = super::super::test_event(5, "media", "a.mp4");
+        sink.publish(&event, b"{\"e\":1}", Duration::from_secs(2))
+            .await
+            .unwrap();
+        let (partition, batch) = server.await.unwrap();
+        assert_eq!(partition as usize, partition_for(b"media/a.mp4", 3));
+        assert_eq!(batch[16], 2, "magic");
+        assert_eq!(
+            u32::from_be_bytes(batch[17..21].try_into().unwrap()),
+            crc32c(&batch[21..])
+        );
+        let tail = String::from_utf8_lossy(&batch);
+        assert!(tail.contains("media/a.mp4"));
+        assert!(tail.contains("{\"e\":1}"));
+        assert!(tail.contains(&super::super::message_id(&event)));
+    }
+
+    #[tokio::test]
+    async fn connections_log_in_before_the_first_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut conn, _) = listener.accept().await.unwrap();
+            let (api_key, cid, body) = read_request(&mut conn).await;
+            assert_eq!(api_key, API_SASL_HANDSHAKE);
+            assert_eq!(Reader(&body).string().unwrap(), "PLAIN");
+            let mut resp = 0i16.to_be_bytes().to_vec();
+            resp.extend_from_slice(&1i32.to_be_bytes());
+            put_str(&mut resp, "PLAIN");
+            respond(&mut conn, cid, &resp).await;
+
+            let (api_key, cid, body) = read_request(&mut conn).await;
+            assert_eq!(api_key, API_SASL_AUTHENTICATE);
+            let mut resp = 0i16.to_be_bytes().to_vec();
+            resp.extend_from_slice(&(-1i16).to_be_bytes());
+            resp.extend_from_slice(&0i32.to_be_bytes());
+            respond(&mut conn, cid, &resp).await;
+
+            // Logged in: the sink asks for metadata of a missing topic.
+            let (api_key, cid, _) = read_request(&mut conn).await;
+            assert_eq!(api_key, API_METADATA);
+            let mut meta = 0i32.to_be_bytes().to_vec();
+            meta.extend_from_slice(&(-1i32).to_be_bytes());
+            meta.extend_from_slice(&1i32.to_be_bytes());
+            meta.extend_from_slice(&UNKNOWN_TOPIC_OR_PARTITION.to_be_bytes());
+            put_str(&mut meta, "events");
+            meta.push(0);
+            meta.extend_from_slice(&0i32.to_be_bytes());
+            respond(&mut conn, cid, &meta).await;
+            body
+        });
+
+        let sink = KafkaSink::new(&format!("kafka://dgp:s3cret@{addr}"), "events", None).unwrap();
+        let event = super::super::test_event(5, "media", "a.mp4");
+        let err = sink
+            .publish(&event, b"{}", Duration::from_secs(2))
+            .await
+            .unwrap_err();
+        assert!(err.contains("does not exist"), "{err}");
+        let auth = server.await.unwrap();
+        assert_eq!(&auth[..4], 11i32.to_be_bytes());
+        assert_eq!(&auth[4..], b"\0dgp\0s3cret");
+    }
+}
@@ -0,0 +1,259 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Redis Streams sink: each event is one `XADD <stream> * …` entry with the
+//! fields `id` (the [`super::message_id`]), `kind`, `bucket`, `key` and
+//! `event` (the JSON envelope). Speaks just enough RESP2 for `AUTH`,
+//! `SELECT` and `XADD`.
+//!
+//! `redis://[[user]:pass@]host[:port][/db]`; `rediss://` connects over TLS.
+//! A password (and user, for an ACL user) is sent with `AUTH`.
+
+use super::{
+    parse_broker_url, read_line, reply_len, with_timeout, BrokerIo, BrokerStream, EventSink, Tls,
+    MAX_REPLY,
+};
+use crate::event_outbox::EventOutboxRecord;
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+
+pub struct RedisSink {
+    addr: String,
+    username: Option<String>,
+    password: Option<String>,
+    db: u32,
+    stream: String,
+    tls: Option<Tls>,
+    conn: Mutex<Option<BufReader<BrokerStream>>>,
+}
+
+/// One RESP reply, as far as the sink cares.
+#[derive(Debug, PartialEq)]
+enum Reply {
+    Ok(String),
+    Nil,
+    Array(Vec<Reply>),
+}
+
+impl RedisSink {
+    pub fn new(url: &str, stream: &str, ca_file: Option<&str>) -> Result<Self, String> {
+        let url = parse_broker_url(url, &[("redis", 6379, false), ("rediss", 6379, true)])?;
+        let db = match url.path.trim_matches('/') {
+            "" => 0,
+            db => db
+                .parse()
+                .map_err(|_| format!("invalid database number {db:?}"))?,
+        };
+        Ok(Self {
+            addr: url.addr,
+            username: url.username,
+            password: url.password,
+            db,
+            stream: stream.to_string(),
+            tls: Tls::for_url(url.tls, ca_file)?,
+            conn: Mutex::new(None),
+        })
+    }
+
+    async fn connect(&self) -> Result<BufReader<BrokerStream>, String> {
+        let mut conn = BufReader::new(super::connect(&self.addr, self.tls.as_ref()).await?);
+        if let Some(password) = &self.password {
+            let mut auth = vec!["AUTH".as_bytes()];
+            auth.extend(self.username.as_deref().map(str::as_bytes));
+            auth.push(password.as_bytes());
+            command(&mut conn, &auth).await?;
+        }
+        if self.db != 0 {
+            command(&mut conn, &[b"SELECT", self.db.to_string().as_bytes()]).await?;
+        }
+        Ok(conn)
+    }
+}
+
+fn encode_command(args: &[&[u8]]) -> Vec<u8> {
+    let mut out = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        out.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        out.extend_from_slice(arg);
+        out.extend_from_slice(b"\r\n");
+    }
+    out
+}
+
+async fn read_reply<S: BrokerIo>(conn: &mut BufReader<S>) -> Result<Reply, String> {
+    let line = read_line(conn).await?;
+    let (kind, rest) = line.split_at(line.len().min(1));
+    match kind {
+        "+" | ":" => Ok(Reply::Ok(rest.to_string())),
+        "-" => Err(format!("redis error: {rest}")),
+        "$" => {
+            let len: i64 = rest.parse().map_err(|_| "malformed bulk length")?;
+            if len < 0 {
+                return Ok(Reply::Nil);
+            }
+            let len = reply_len(len as usize, "bulk reply")?;
+            let mut data = vec![0; len + 2];
+            conn.read_exact(&mut data)
+                .await
+                .map_err(|e| e.to_string())?;
+            data.truncate(len);
+            Ok(Reply::Ok(String::from_utf8_lossy(&data).into_owned()))
+        }
+        "*" => {
+            let len: i64 = rest.parse().map_err(|_| "malformed array length")?;
+            // Each item takes at least three bytes on the wire.
+            if len > (MAX_REPLY / 3) as i64 {
+                return Err(format!("array reply of {len} items is too large"));
+            }
+            let mut items = Vec::new();
+            for _ in 0..len.max(0) {
+                items.push(Box::pin(read_reply(conn)).await?);
+            }
+            Ok(Reply::Array(items))
+        }
+        _ => Err(format!("unexpected redis reply {line:?}")),
+    }
+}
+
+async fn command(conn: &mut BufReader<impl BrokerIo>, args: &[&[u8]]) -> Result<Reply, String> {
+    conn.get_mut()
+        .write_all(&encode_command(args))
+        .await
+        .map_err(|e| e.to_string())?;
+    read_reply(conn).await
+}
+
+#[async_trait]
+impl EventSink for RedisSink {
+    async fn publish(
+        &self,
+        event: &EventOutboxRecord,
+        body: &[u8],
+        timeout: Duration,
+    ) -> Result<(), String> {
+        let mut guard = self.conn.lock().await;
+        let result = with_timeout(timeout, "redis XADD", async {
+            if guard.is_none() {
+                *guard = Some(self.connect().await?);
+            }
+            let conn = guard.as_mut().expect("connected above");
+            let id = super::message_id(event);
+            let reply = command(
+                conn,
+                &[
+                    b"XADD",
+                    self.stream.as_bytes(),
+                    b"*",
+                    b"id",
+                    id.as_bytes(),
+                    b"kind",
+                    event.kind.as_bytes(),
+                    b"bucket",
+                    event.bucket.as_bytes(),
+                    b"key",
+                    event.key.as_bytes(),
+                    b"event",
+                    body,
+                ],
+            )
+            .await?;
+            match reply {
+                Reply::Ok(_) => Ok(()),
+                other => Err(format!("unexpected XADD reply {other:?}")),
+            }
+        })
+        .await;
+        if result.is_err() {
+            *guard = None;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn oversized_replies_are_refused() {
+        for reply in ["$4294967296\r\n", "*999999999\r\n"] {
+            let (client, mut server) = tokio::io::duplex(64);
+            server.write_all(reply.as_bytes()).await.unwrap();
+            let err = read_reply(&mut BufReader::new(client)).await.err().unwrap();
+            assert!(err.contains("too large"), "{reply:?}: {err}");
+        }
+    }
+
+    #[test]
+    fn commands_are_resp_arrays_of_bulk_strings() {
+        assert_eq!(
+            encode_command(&[b"XADD", b"s", b"*"]),
+            b"*3\r\n$4\r\nXADD\r\n$1\r\ns\r\n$1\r\n*\r\n".to_vec()
+        );
+    }
+
+    /// A one-connection fake Redis: answers AUTH / SELECT with +OK and XADD
+    /// with an entry id, handing each command's arguments to the test.
+    async fn fake_redis() -> (String, tokio::sync::mpsc::UnboundedReceiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = BufReader::new(socket);
+            while let Ok(Reply::Array(args)) = read_reply(&mut conn).await {
+                let args: Vec<String> = args
+                    .into_iter()
+                    .map(|a| match a {
+                        Reply::Ok(s) => s,
+                        other => panic!("{other:?}"),
+                    })
+                    .collect();
+                let reply: &[u8] = if args[0] == "XADD" {
+                    b"$15\r\n1700000000000-0\r\n"
+                } else {
+                    b"+OK\r\n"
+                };
+                tx.send(args).unwrap();
+                conn.get_mut().write_all(reply).await.unwrap();
+            }
+        });
+        (addr, rx)
+    }
+
+    #[tokio::test]
+    async fn publish_authenticates_selects_and_appends() {
+        let (addr, mut rx) = fake_redis().await;
+        let sink = RedisSink::new(&format!("redis://:pw@{addr}/3"), "dgp:events", None).unwrap();
+        let event = super::super::test_event(1, "media", "a.mp4");
+        sink.publish(&event, b"{}", Duration::from_secs(2))
+            .await
+            .unwrap();
+        sink.publish(&event, b"{}", Duration::from_secs(2))
+            .await
+            .unwrap();
+
+        assert_eq!(rx.recv().await.unwrap(), ["AUTH", "pw"]);
+        assert_eq!(rx.recv().await.unwrap(), ["SELECT", "3"]);
+        for _ in 0..2 {
+            let xadd = rx.recv().await.unwrap();
+            assert_eq!(&xadd[..4], ["XADD", "dgp:events", "*", "id"]);
+            assert_eq!(xadd[4], super::super::message_id(&event));
+            assert_eq!(
+                &xadd[5..],
+                [
+                    "kind",
+                    "ObjectCreated",
+                    "bucket",
+                    "media",
+                    "key",
+                    "a.mp4",
+                    "event",
+                    "{}"
+                ]
+            );
+        }
+    }
+}
@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Client side of SCRAM-SHA-256 / SCRAM-SHA-512 (RFC 5802, RFC 7677), as
+//! Kafka's SASL layer runs it: no channel binding, three messages.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha512};
+use std::ops::RangeInclusive;
+
+/// Iteration counts accepted from the server. RFC 7677 asks for at least
+/// 4096; the cap keeps a hostile broker from pinning a thread on PBKDF2.
+const ITERATIONS: RangeInclusive<u32> = 4096..=1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScramHash {
+    Sha256,
+    Sha512,
+}
+
+impl ScramHash {
+    /// The SASL mechanism name.
+    pub(crate) fn mechanism(self) -> &'static str {
+        match self {
+            Self::Sha256 => "SCRAM-SHA-256",
+            Self::Sha512 => "SCRAM-SHA-512",
+        }
+    }
+
+    fn hmac(self, key: &[u8], data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes any key");
+                mac.update(data);
+                mac.finalize().into_bytes().to_vec()
+            }
+            Self::Sha512 => {
+                let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes any key");
+                mac.update(data);
+                mac.finalize().into_bytes().to_vec()
+            }
+        }
+    }
+
+    fn hash(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => Sha256::digest(data).to_vec(),
+            Self::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+
+    /// `Hi()`: PBKDF2 with this hash's HMAC, one output block.
+    fn salted_password(self, password: &[u8], salt: &[u8], iterations: u32) -> Vec<u8> {
+        let mut block = self.hmac(password, &[salt, &1u32.to_be_bytes()].concat());
+        let mut out = block.clone();
+        for _ in 1..iterations {
+            block = self.hmac(password, &block);
+            for (o, b) in out.iter_mut().zip(&block) {
+                *o ^= b;
+            }
+        }
+        out
+    }
+}
+
+/// One authentication exchange.
+pub(crate) struct Scram {
+    hash: ScramHash,
+    password: String,
+    client_first_bare: String,
+    nonce: String,
+    /// Set by [`Self::client_final`]; checked by [`Self::verify_server_final`].
+    server_signature: Option<Vec<u8>>,
+}
+
+impl Scram {
+    pub(crate) fn new(hash: ScramHash, username: &str, password: &str) -> Self {
+        use rand::Rng;
+        let mut nonce = [0u8; 24];
+        rand::rngs::OsRng.fill(&mut nonce);
+        Self::with_nonce(hash, username, password, &BASE64.encode(nonce))
+    }
+
+    fn with_nonce(hash: ScramHash, username: &str, password: &str, nonce: &str) -> Self {
+        let username = username.replace('=', "=3D").replace(',', "=2C");
+        Self {
+            hash,
+            password: password.to_string(),
+            client_first_bare: format!("n={username},r={nonce}"),
+            nonce: nonce.to_string(),
+            server_signature: None,
+        }
+    }
+
+    pub(crate) fn client_first(&self) -> String {
+        format!("n,,{}", self.client_first_bare)
+    }
+
+    /// Answer the server-first message with the client proof. The key
+    /// derivation runs on the blocking pool.
+    pub(crate) async fn client_final(&mut self, server_first: &str) -> Result<String, String> {
+        let field = |name: char| {
+            server_first
+                .split(',')
+                .find_map(|kv| kv.strip_prefix(name)?.strip_prefix('='))
+                .ok_or_else(|| format!("SCRAM server-first message lacks {name}="))
+        };
+        let nonce = field('r')?;
+        if !nonce.starts_with(&self.nonce) || nonce.len() == self.nonce.len() {
+            return Err("SCRAM server nonce does not extend ours".to_string());
+        }
+        let salt = BASE64
+            .decode(field('s')?)
+            .map_err(|e| format!("SCRAM salt: {e}"))?;
+        let iterations: u32 = field('i')?
+            .parse()
+            .ok()
+            .filter(|i| ITERATIONS.contains(i))
+            .ok_or_else(|| {
+                format!(
+                    "SCRAM iteration count must be {}..={}",
+                    ITERATIONS.start(),
+                    ITERATIONS.end()
+                )
+            })?;
+
+        let (hash, password) = (self.hash, self.password.clone());
+        let salted = tokio::task::spawn_blocking(move || {
+            hash.salted_password(password.as_bytes(), &salt, iterations)
+        })
+        .await
+        .map_err(|e| format!("SCRAM key derivation: {e}"))?;
+        let client_key = self.hash.hmac(&salted, b"Client Key");
+        let stored_key = self.hash.hash(&client_key);
+        let without_proof = format!("c=biws,r={nonce}");
+        let auth_message = format!("{},{server_first},{without_proof}", self.client_first_bare);
+        let signature = self.hash.hmac(&stored_key, auth_message.as_bytes());
+        let proof: Vec<u8> = client_key
+            .iter()
+            .zip(&signature)
+            .map(|(k, s)| k ^ s)
+            .collect();
+        let server_key = self.hash.hmac(&salted, b"Server Key");
+        self.server_signature = Some(self.hash.hmac(&server_key, auth_message.as_bytes()));
+        Ok(format!("{without_proof},p={}", BASE64.encode(proof)))
+    }
+
+    /// Check the server's signature, proving it knows the password too.
+    pub(crate) fn verify_server_final(&self, server_final: &str) -> Result<(), String> {
+        if let Some(err) = server_final.strip_prefix("e=") {
+            return Err(format!("SCRAM authentication failed: {err}"));
+        }
+        let expected = self
+            .server_signature
+            .as_ref()
+            .ok_or("SCRAM server-final before client-final")?;
+        let got = server_final
+            .strip_prefix("v=")
+            .and_then(|v| BASE64.decode(v).ok())
+            .ok_or("malformed SCRAM server-final message")?;
+        if got != *expected {
+            return Err("SCRAM server signature mismatch".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The SCRAM-SHA-256 example exchange of RFC 7677 §3.
+    #[tokio::test]
+    async fn rfc7677_exchange() {
+        let mut scram =
+            Scram::with_nonce(ScramHash::Sha256, "user", "pencil", "rOprNGfwEbeRWgbNEkqO");
+        assert_eq!(scram.client_first(), "n,,n=user,r=rOprNGfwEbeRWgbNEkqO");
+        let client_final = scram
+            .client_final(
+                "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+                 s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096",
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            client_final,
+            "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+             p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ="
+        );
+        scram
+            .verify_server_final("v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=")
+            .unwrap();
+        assert!(scram
+            .verify_server_final("v=AAAATRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=")
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn server_must_extend_the_client_nonce() {
+        let mut scram = Scram::with_nonce(ScramHash::Sha512, "u", "p", "abc");
+        assert!(scram.client_final("r=xyz,s=c2FsdA==,i=4096").await.is_err());
+        assert!(scram.client_final("r=abc,s=c2FsdA==,i=4096").await.is_err());
+        assert!(scram
+            .client_final("r=abcdef,s=c2FsdA==,i=4096")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn iteration_count_is_bounded() {
+        let mut scram = Scram::with_nonce(ScramHash::Sha256, "u", "p", "abc");
+        for i in ["0", "4095", "1000001", "4000000000", "-1"] {
+            let err = scram
+                .client_final(&format!("r=abcdef,s=c2FsdA==,i={i}"))
+                .await
+                .unwrap_err();
+            assert!(err.contains("iteration count"), "{i}: {err}");
+        }
+    }
+}
@@ -179,7 +179,7 @@ fn matches_rule(rule: &GroupMappingRule, identity: &ExternalIdentityInfo) -> boo
 /// O(pattern.len() + text.len()) and avoids the exponential backtracking that
 /// a naive recursive matcher exhibits on patterns like `*a*b*c`. Operates on
 /// bytes since the glob path always passes ASCII-lowercased inputs.
-fn glob_match(pattern: &str, text: &str) -> bool {
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
     let pattern = pattern.as_bytes();
     let text = text.as_bytes();
 
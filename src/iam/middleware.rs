@@ -9,46 +9,152 @@ use axum::response::{IntoResponse, Response};
 use iam_rs::Context;
 use tracing::debug;
 
-use super::types::{AuthenticatedUser, ListScope, S3Action};
+use super::types::{AuthenticatedUser, ListScope, S3Action, S3Operation};
 use crate::metrics::{record_http_request_total, Metrics};
 
-/// Map an HTTP method + path to an S3 action.
-fn classify_action(method: &axum::http::Method, path: &str) -> S3Action {
-    let is_bucket_level = path.trim_matches('/').split('/').count() <= 1;
+/// Map an HTTP method + path + query string to the S3 operation it performs.
+///
+/// Sub-resource query parameters (`?tagging`, `?uploadId=`, `?acl`, ...) pick
+/// the operation-level action, so policies can grant e.g. tagging or multipart
+/// housekeeping without plain overwrites. Every operation's
+/// [`S3Operation::category`] equals what the method/path alone classified to
+/// before operation-level actions existed, keeping the verb aliases stable.
+fn classify_action(method: &axum::http::Method, path: &str, query: &str) -> S3Operation {
+    let trimmed = path.trim_matches('/');
+    let is_bucket_level = trimmed.split('/').count() <= 1;
+    let has = |name: &str| {
+        query
+            .split('&')
+            .any(|p| p == name || p.strip_prefix(name).is_some_and(|r| r.starts_with('=')))
+    };
 
     match *method {
         axum::http::Method::GET | axum::http::Method::HEAD => {
-            if is_bucket_level {
-                S3Action::List
+            if trimmed.is_empty() {
+                S3Operation::ListAllMyBuckets
+            } else if is_bucket_level {
+                if has("uploads") {
+                    S3Operation::ListBucketMultipartUploads
+                } else if has("versions") {
+                    S3Operation::ListBucketVersions
+                } else if has("location") {
+                    S3Operation::GetBucketLocation
+                } else if has("versioning") {
+                    S3Operation::GetBucketVersioning
+                } else if has("tagging") {
+                    S3Operation::GetBucketTagging
+                } else if has("acl") {
+                    S3Operation::GetBucketAcl
+                } else if has("cors") {
+                    S3Operation::GetBucketCors
+                } else if has("policy") {
+                    S3Operation::GetBucketPolicy
+                } else if has("lifecycle") {
+                    S3Operation::GetLifecycleConfiguration
+                } else {
+                    S3Operation::ListBucket
+                }
+            } else if has("uploadId") {
+                S3Operation::ListMultipartUploadParts
+            } else if has("tagging") {
+                S3Operation::GetObjectTagging
+            } else if has("acl") {
+                S3Operation::GetObjectAcl
+            } else if has("attributes") {
+                S3Operation::GetObjectAttributes
+            } else if has("retention") {
+                S3Operation::GetObjectRetention
+            } else if has("legal-hold") {
+                S3Operation::GetObjectLegalHold
             } else {
-                S3Action::Read
+                S3Operation::GetObject
             }
         }
         axum::http::Method::PUT => {
             if is_bucket_level {
-                S3Action::Admin
+                if has("versioning") {
+                    S3Operation::PutBucketVersioning
+                } else if has("tagging") {
+                    S3Operation::PutBucketTagging
+                } else if has("acl") {
+                    S3Operation::PutBucketAcl
+                } else if has("cors") {
+                    S3Operation::PutBucketCors
+                } else if has("policy") {
+                    S3Operation::PutBucketPolicy
+                } else if has("lifecycle") {
+                    S3Operation::PutLifecycleConfiguration
+                } else {
+                    S3Operation::CreateBucket
+                }
+            } else if has("tagging") {
+                S3Operation::PutObjectTagging
+            } else if has("acl") {
+                S3Operation::PutObjectAcl
+            } else if has("retention") {
+                S3Operation::PutObjectRetention
+            } else if has("legal-hold") {
+                S3Operation::PutObjectLegalHold
             } else {
-                S3Action::Write
+                // Plain PUT, UploadPart, CopyObject, UploadPartCopy.
+                S3Operation::PutObject
             }
         }
         axum::http::Method::DELETE => {
             if is_bucket_level {
-                S3Action::Admin
+                // AWS authorizes the DeleteBucket* sub-resources with the
+                // matching Put* action.
+                if has("tagging") {
+                    S3Operation::PutBucketTagging
+                } else if has("cors") {
+                    S3Operation::PutBucketCors
+                } else if has("policy") {
+                    S3Operation::DeleteBucketPolicy
+                } else if has("lifecycle") {
+                    S3Operation::PutLifecycleConfiguration
+                } else {
+                    S3Operation::DeleteBucket
+                }
+            } else if has("uploadId") {
+                S3Operation::AbortMultipartUpload
+            } else if has("tagging") {
+                S3Operation::DeleteObjectTagging
             } else {
-                S3Action::Delete
+                S3Operation::DeleteObject
             }
         }
         axum::http::Method::POST => {
-            // POST is used for multipart uploads, batch delete, etc.
-            // Check query string for ?delete (batch delete)
-            S3Action::Write
+            // POST /{bucket}?delete is a batch DELETE, not a write. Must check
+            // for the exact "delete" query parameter, not a substring
+            // (otherwise ?delimiter= would also match).
+            if has("delete") {
+                S3Operation::DeleteObject
+            } else if !is_bucket_level && has("restore") {
+                S3Operation::RestoreObject
+            } else {
+                // CreateMultipartUpload, CompleteMultipartUpload, form POST.
+                S3Operation::PutObject
+            }
         }
-        _ => S3Action::Admin, // Unknown methods require admin permissions
+        _ => S3Operation::CreateBucket, // Unknown methods require admin permissions
     }
 }
 
+/// True if the request asks to bypass object-lock governance retention, which
+/// AWS authorizes as a separate `s3:BypassGovernanceRetention` action on top of
+/// the delete / retention change itself.
+fn requests_governance_bypass(headers: &axum::http::HeaderMap, action: S3Operation) -> bool {
+    matches!(
+        action,
+        S3Operation::DeleteObject | S3Operation::PutObjectRetention
+    ) && headers
+        .get("x-amz-bypass-governance-retention")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.trim().eq_ignore_ascii_case("true"))
+}
+
 /// Extract bucket and key from the URI path (path-style: /{bucket}/{key...}).
-fn parse_bucket_key(path: &str) -> (&str, &str) {
+pub(crate) fn parse_bucket_key(path: &str) -> (&str, &str) {
     let trimmed = path.trim_start_matches('/');
     match trimmed.split_once('/') {
         Some((bucket, key)) => (bucket, key),
@@ -56,60 +162,39 @@ fn parse_bucket_key(path: &str) -> (&str, &str) {
     }
 }
 
-/// Axum middleware that checks IAM permissions after SigV4 authentication.
-///
-/// If an `AuthenticatedUser` is present in request extensions (inserted by
-/// the SigV4 middleware in IAM mode), evaluates their permissions against
-/// the requested action and resource. Denies with 403 if not permitted.
-///
-/// In legacy mode or open access, no `AuthenticatedUser` is present and
-/// the request passes through unchecked.
-pub async fn authorization_middleware(
-    mut request: Request<Body>,
-    next: Next,
-) -> Result<Response, Response> {
-    // OPTIONS (CORS preflight) always passes through without auth
-    if request.method() == axum::http::Method::OPTIONS {
-        return Ok(next.run(request).await);
-    }
-
-    // Only enforce if an AuthenticatedUser was inserted by SigV4 middleware
-    let user = match request.extensions().get::<AuthenticatedUser>() {
-        Some(u) => u.clone(),
-        None => return Ok(next.run(request).await),
-    };
-
-    let method = request.method().clone();
-    let path = request.uri().path().to_string();
-    let query = request.uri().query().unwrap_or("");
-
-    // Determine the S3 action
-    let mut action = classify_action(&method, &path);
-
-    // POST /{bucket}?delete is a batch DELETE, not a write.
-    // Must check for exact "delete" query parameter, not substring
-    // (otherwise ?delimiter= would also match).
-    if method == axum::http::Method::POST
-        && query
-            .split('&')
-            .any(|p| p == "delete" || p.starts_with("delete="))
-    {
-        action = S3Action::Delete;
-    }
-
-    let (bucket, key) = parse_bucket_key(&path);
-
-    // ListBuckets (GET /) is filtered at the handler level, not denied outright.
-    // This lets IAM users see only the buckets they have permissions on.
-    if bucket.is_empty() && action == S3Action::List {
-        return Ok(next.run(request).await);
+/// Whether the proxy's own listener terminates TLS. The S3 router inserts it
+/// as a request extension so `aws:SecureTransport` reflects the listener even
+/// when no front proxy sets `X-Forwarded-Proto`.
+#[derive(Debug, Clone, Copy)]
+pub struct ListenerTls(pub bool);
+
+/// `aws:SecureTransport` for a live request: TLS at our listener, or a
+/// trusted front proxy reporting `X-Forwarded-Proto: https`.
+fn is_secure_transport(headers: &axum::http::HeaderMap, listener_tls: bool) -> bool {
+    if listener_tls {
+        return true;
     }
+    crate::rate_limiter::trust_proxy_headers()
+        && headers
+            .get("x-forwarded-proto")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("https"))
+}
 
-    // Build IAM evaluation context from request
+/// Build the iam-rs condition context for one request.
+///
+/// Shared by the live middleware and the policy simulator so a simulated
+/// request sees exactly the condition keys a real one would.
+pub(crate) fn request_context(
+    action: S3Operation,
+    query: &str,
+    source_ip: Option<std::net::IpAddr>,
+    secure_transport: bool,
+) -> Context {
     let mut context = Context::new();
 
     // s3:prefix — from query parameter on LIST requests
-    if action == S3Action::List {
+    if action.category() == S3Action::List {
         // AWS IAM evaluates root bucket LIST as `s3:prefix == ""` even when
         // the client omits the `prefix` query parameter. Without this default,
         // a condition like `StringLike: { "s3:prefix": "" }` can never match
@@ -118,46 +203,93 @@ pub async fn authorization_middleware(
             "s3:prefix".to_string(),
             iam_rs::ContextValue::String(String::new()),
         );
-        if let Some(query_str) = request.uri().query() {
-            for param in query_str.split('&') {
-                if let Some(value) = param.strip_prefix("prefix=") {
-                    let decoded = urlencoding::decode(value).unwrap_or_default();
-                    context.insert(
-                        "s3:prefix".to_string(),
-                        iam_rs::ContextValue::String(decoded.into_owned()),
-                    );
-                } else if let Some(value) = param.strip_prefix("delimiter=") {
-                    let decoded = urlencoding::decode(value).unwrap_or_default();
-                    context.insert(
-                        "s3:delimiter".to_string(),
-                        iam_rs::ContextValue::String(decoded.into_owned()),
-                    );
-                } else if let Some(value) = param.strip_prefix("max-keys=") {
-                    if let Ok(n) = value.parse::<f64>() {
-                        context.insert("s3:max-keys".to_string(), iam_rs::ContextValue::Number(n));
-                    }
+        for param in query.split('&') {
+            if let Some(value) = param.strip_prefix("prefix=") {
+                let decoded = urlencoding::decode(value).unwrap_or_default();
+                context.insert(
+                    "s3:prefix".to_string(),
+                    iam_rs::ContextValue::String(decoded.into_owned()),
+                );
+            } else if let Some(value) = param.strip_prefix("delimiter=") {
+                let decoded = urlencoding::decode(value).unwrap_or_default();
+                context.insert(
+                    "s3:delimiter".to_string(),
+                    iam_rs::ContextValue::String(decoded.into_owned()),
+                );
+            } else if let Some(value) = param.strip_prefix("max-keys=") {
+                if let Ok(n) = value.parse::<f64>() {
+                    context.insert("s3:max-keys".to_string(), iam_rs::ContextValue::Number(n));
                 }
             }
         }
     }
 
-    // aws:SourceIp — combine `X-Forwarded-For` (when
-    // `DGP_TRUST_PROXY_HEADERS=true`) with the direct TCP peer IP so
-    // policies like `Deny { aws:SourceIp NotIpAddress 10.0.0.0/8 }`
-    // actually fire on a direct-internet deployment where no reverse
-    // proxy is setting XFF. Without the peer fallback, the context
-    // value is `null` and `iam-rs` skips the condition silently.
-    let peer_ip = request
-        .extensions()
-        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
-        .map(|ci| ci.0.ip());
-    if let Some(ip) = crate::rate_limiter::extract_client_ip_with_peer(request.headers(), peer_ip) {
+    // aws:SourceIp — when absent the context value is `null` and `iam-rs`
+    // skips the condition silently, so callers resolve the peer IP fallback
+    // before getting here.
+    if let Some(ip) = source_ip {
         context.insert(
             "aws:SourceIp".to_string(),
             iam_rs::ContextValue::String(ip.to_string()),
         );
     }
 
+    context.insert(
+        "aws:SecureTransport".to_string(),
+        iam_rs::ContextValue::Boolean(secure_transport),
+    );
+
+    context
+}
+
+/// Which branch of [`authorize`] produced the decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthzBasis {
+    /// A policy Allow matched and no Deny did.
+    PolicyAllow,
+    /// An explicit Deny matched (possibly via a condition).
+    ExplicitDeny,
+    /// Nothing matched.
+    ImplicitDeny,
+    /// Bucket-level LIST admitted because the user holds some permission on
+    /// the bucket; the handler filters the returned keys.
+    BucketVisibilityFallback,
+    /// The operation was allowed but `x-amz-bypass-governance-retention`
+    /// also needs `s3:BypassGovernanceRetention`, which was not granted.
+    GovernanceBypassDenied,
+}
+
+/// Result of authorizing one request.
+pub(crate) struct Authorization {
+    pub allowed: bool,
+    pub basis: AuthzBasis,
+    /// Set on admitted bucket-level LIST requests; tells the handler whether
+    /// to filter keys.
+    pub list_scope: Option<ListScope>,
+}
+
+/// The authorization decision for one classified request.
+///
+/// This is the whole decision the middleware makes, factored out so the
+/// policy simulator runs the identical logic instead of a reimplementation.
+pub(crate) fn authorize(
+    user: &AuthenticatedUser,
+    action: S3Operation,
+    bucket: &str,
+    key: &str,
+    requested_prefix: &str,
+    governance_bypass: bool,
+    context: &Context,
+) -> Authorization {
+    let visibility_fallback = matches!(
+        action,
+        S3Operation::ListBucket
+            | S3Operation::ListBucketVersions
+            | S3Operation::ListBucketMultipartUploads
+            | S3Operation::GetBucketLocation
+    );
+
     // ListObjects (GET /bucket) — four-way evaluation with post-auth scope marker:
     //
     // 1. If an Allow covers the full requested bucket/prefix AND policies grant
@@ -180,54 +312,155 @@ pub async fn authorization_middleware(
     // AWS: a user with s3:GetObject on bucket/* can still ListBucket even
     // without an explicit s3:ListBucket statement. What's NEW in this fix is
     // that the handler must FILTER, not return everything wholesale.
-    let (allowed, list_scope) = if action == S3Action::List && key.is_empty() {
-        // Extract the requested prefix (may be empty).
-        let requested_prefix = extract_prefix_from_query(request.uri().query());
-
-        if user.can_with_context(action, bucket, key, &context) {
+    //
+    // Only the key listings (ListObjects, `?versions`, `?uploads`) take this
+    // path, because their handlers filter what they return by the scope —
+    // plus `?location`, which reveals nothing but the region and which SDKs
+    // probe before their first request. Other bucket sub-resource reads
+    // (`?policy`, `?acl`, ...) expose bucket-wide configuration and must be
+    // granted by their own action.
+    let (allowed, basis, list_scope) = if visibility_fallback && key.is_empty() {
+        if user.can_with_context(action, bucket, key, context) {
             // Policies matched with the prefix-aware context. Decide whether
             // the coverage is unrestricted (user can see every key in the
             // prefix space) or prefix-scoped (handler must filter).
-            let unrestricted = super::permissions::has_unrestricted_allow_for_bucket_prefix(
-                &user.permissions,
-                bucket,
-                &requested_prefix,
-            );
+            let unrestricted = user.rule_sets().all(|rules| {
+                super::permissions::has_unrestricted_allow_for_bucket_prefix(
+                    rules,
+                    bucket,
+                    requested_prefix,
+                )
+            });
             let scope = if unrestricted {
-                Some(ListScope::Unrestricted)
+                ListScope::Unrestricted
             } else {
                 // iam-rs said yes but the policy is narrower than the
                 // requested prefix (e.g. condition-based) → filter anyway.
                 // Defence in depth: if we can't prove coverage is
                 // unrestricted, assume it isn't.
-                Some(ListScope::Filtered {
+                ListScope::Filtered {
                     user: Box::new(user.clone()),
-                })
+                }
             };
-            (true, scope)
-        } else if user.is_explicitly_denied(action, bucket, key, &context) {
+            (true, AuthzBasis::PolicyAllow, Some(scope))
+        } else if user.is_explicitly_denied(action, bucket, key, context) {
             // An explicit Deny matched (possibly via condition) — blocked
-            (false, None)
+            (false, AuthzBasis::ExplicitDeny, None)
         } else if user.name == "$anonymous" {
             // Anonymous users must NOT use the can_see_bucket fallback —
             // it would allow unscoped LIST, leaking keys outside public prefixes.
-            (false, None)
+            (false, AuthzBasis::ImplicitDeny, None)
         } else if user.can_see_bucket(bucket) {
             // No explicit Allow on the prefix, but the user has SOME
             // permission on this bucket. Admit with filtering enforced.
             (
                 true,
+                AuthzBasis::BucketVisibilityFallback,
                 Some(ListScope::Filtered {
                     user: Box::new(user.clone()),
                 }),
             )
         } else {
-            (false, None)
+            (false, AuthzBasis::ImplicitDeny, None)
         }
+    } else if user.can_with_context(action, bucket, key, context) {
+        (true, AuthzBasis::PolicyAllow, None)
+    } else if user.is_explicitly_denied(action, bucket, key, context) {
+        (false, AuthzBasis::ExplicitDeny, None)
     } else {
-        (user.can_with_context(action, bucket, key, &context), None)
+        (false, AuthzBasis::ImplicitDeny, None)
     };
 
+    if allowed
+        && governance_bypass
+        && !user.can_with_context(S3Operation::BypassGovernanceRetention, bucket, key, context)
+    {
+        return Authorization {
+            allowed: false,
+            basis: AuthzBasis::GovernanceBypassDenied,
+            list_scope: None,
+        };
+    }
+
+    Authorization {
+        allowed,
+        basis,
+        list_scope,
+    }
+}
+
+/// Axum middleware that checks IAM permissions after SigV4 authentication.
+///
+/// If an `AuthenticatedUser` is present in request extensions (inserted by
+/// the SigV4 middleware in IAM mode), evaluates their permissions against
+/// the requested action and resource. Denies with 403 if not permitted.
+///
+/// In legacy mode or open access, no `AuthenticatedUser` is present and
+/// the request passes through unchecked.
+pub async fn authorization_middleware(
+    mut request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    // OPTIONS (CORS preflight) always passes through without auth
+    if request.method() == axum::http::Method::OPTIONS {
+        return Ok(next.run(request).await);
+    }
+
+    // Only enforce if an AuthenticatedUser was inserted by SigV4 middleware
+    let user = match request.extensions().get::<AuthenticatedUser>() {
+        Some(u) => u.clone(),
+        None => return Ok(next.run(request).await),
+    };
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().unwrap_or("");
+
+    // Determine the S3 operation
+    let action = classify_action(&method, &path, query);
+
+    let (bucket, key) = parse_bucket_key(&path);
+
+    // ListBuckets (GET /) is filtered at the handler level, not denied outright.
+    // This lets IAM users see only the buckets they have permissions on.
+    if bucket.is_empty() && action.category() == S3Action::List {
+        return Ok(next.run(request).await);
+    }
+
+    // aws:SourceIp — combine `X-Forwarded-For` (when
+    // `DGP_TRUST_PROXY_HEADERS=true`) with the direct TCP peer IP so
+    // policies like `Deny { aws:SourceIp NotIpAddress 10.0.0.0/8 }`
+    // actually fire on a direct-internet deployment where no reverse
+    // proxy is setting XFF. Without the peer fallback, the context
+    // value is `null` and `iam-rs` skips the condition silently.
+    let peer_ip = request
+        .extensions()
+        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+        .map(|ci| ci.0.ip());
+    let source_ip = crate::rate_limiter::extract_client_ip_with_peer(request.headers(), peer_ip);
+    let listener_tls = request
+        .extensions()
+        .get::<ListenerTls>()
+        .is_some_and(|t| t.0);
+    let secure_transport = is_secure_transport(request.headers(), listener_tls);
+
+    // Build IAM evaluation context from request
+    let context = request_context(action, query, source_ip, secure_transport);
+
+    let Authorization {
+        allowed,
+        list_scope,
+        ..
+    } = authorize(
+        &user,
+        action,
+        bucket,
+        key,
+        &extract_prefix_from_query(request.uri().query()),
+        requests_governance_bypass(request.headers(), action),
+        &context,
+    );
+
     if !allowed {
         debug!(
             "IAM denied: user='{}' action={:?} bucket='{}' key='{}'",
@@ -255,11 +488,24 @@ pub async fn authorization_middleware(
         crate::audit::audit_log(
             "access_denied",
             &user.name,
-            &format!("{:?}", action),
+            action.iam_name(),
             request.headers(),
             bucket,
             key,
         );
+        if let Some(recorder) = request
+            .extensions()
+            .get::<crate::access_events::AccessEventRecorder>()
+        {
+            let (bucket, key) = crate::access_events::request_bucket_key(&path);
+            let requester = crate::access_events::Requester::from_request(
+                request.extensions(),
+                request.headers(),
+            );
+            recorder
+                .access_denied(action.iam_name(), &bucket, &key, &requester)
+                .await;
+        }
         // Drain up to 64KB of the request body before returning 403 so the client
         // receives a clean error response instead of "connection reset". Without this,
         // axum drops the unread body and closes the connection mid-upload, breaking
@@ -287,7 +533,7 @@ pub async fn authorization_middleware(
 
 /// Extract the URL-decoded `prefix` query parameter, if present.
 /// Returns an empty string when no prefix is given.
-fn extract_prefix_from_query(query: Option<&str>) -> String {
+pub(crate) fn extract_prefix_from_query(query: Option<&str>) -> String {
     let Some(q) = query else {
         return String::new();
     };
@@ -305,10 +551,10 @@ mod tests {
 
     #[test]
     fn test_classify_action_unknown_method_requires_admin() {
-        let action = classify_action(&axum::http::Method::PATCH, "/bucket/key");
-        assert_eq!(action, S3Action::Admin);
-        let action = classify_action(&axum::http::Method::TRACE, "/bucket/key");
-        assert_eq!(action, S3Action::Admin);
+        let action = classify_action(&axum::http::Method::PATCH, "/bucket/key", "");
+        assert_eq!(action.category(), S3Action::Admin);
+        let action = classify_action(&axum::http::Method::TRACE, "/bucket/key", "");
+        assert_eq!(action.category(), S3Action::Admin);
     }
 
     #[test]
@@ -324,38 +570,142 @@ mod tests {
 
     #[test]
     fn test_classify_action_mapping() {
-        assert_eq!(
-            classify_action(&axum::http::Method::GET, "/bucket/key"),
-            S3Action::Read
-        );
-        assert_eq!(
-            classify_action(&axum::http::Method::GET, "/bucket"),
-            S3Action::List
-        );
-        assert_eq!(
-            classify_action(&axum::http::Method::GET, "/"),
-            S3Action::List
-        );
-        assert_eq!(
-            classify_action(&axum::http::Method::PUT, "/bucket/key"),
-            S3Action::Write
-        );
-        assert_eq!(
-            classify_action(&axum::http::Method::PUT, "/bucket"),
-            S3Action::Admin
-        );
-        assert_eq!(
-            classify_action(&axum::http::Method::DELETE, "/bucket/key"),
-            S3Action::Delete
-        );
-        assert_eq!(
-            classify_action(&axum::http::Method::DELETE, "/bucket"),
-            S3Action::Admin
-        );
-        assert_eq!(
-            classify_action(&axum::http::Method::POST, "/bucket/key"),
-            S3Action::Write
+        use axum::http::Method;
+        let cases = [
+            (Method::GET, "/bucket/key", "", S3Operation::GetObject),
+            (Method::GET, "/bucket", "", S3Operation::ListBucket),
+            (Method::GET, "/", "", S3Operation::ListAllMyBuckets),
+            (Method::PUT, "/bucket/key", "", S3Operation::PutObject),
+            (Method::PUT, "/bucket", "", S3Operation::CreateBucket),
+            (Method::DELETE, "/bucket/key", "", S3Operation::DeleteObject),
+            (Method::DELETE, "/bucket", "", S3Operation::DeleteBucket),
+            (
+                Method::POST,
+                "/bucket/key",
+                "uploads",
+                S3Operation::PutObject,
+            ),
+            (Method::POST, "/bucket", "delete", S3Operation::DeleteObject),
+            (
+                Method::GET,
+                "/bucket",
+                "list-type=2&delimiter=%2F",
+                S3Operation::ListBucket,
+            ),
+        ];
+        for (method, path, query, expected) in cases {
+            assert_eq!(
+                classify_action(&method, path, query),
+                expected,
+                "{method} {path}?{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_classify_action_sub_resources() {
+        use axum::http::Method;
+        let cases = [
+            (
+                Method::PUT,
+                "/b/k",
+                "tagging",
+                S3Operation::PutObjectTagging,
+            ),
+            (
+                Method::GET,
+                "/b/k",
+                "tagging",
+                S3Operation::GetObjectTagging,
+            ),
+            (
+                Method::DELETE,
+                "/b/k",
+                "tagging",
+                S3Operation::DeleteObjectTagging,
+            ),
+            (
+                Method::DELETE,
+                "/b/k",
+                "uploadId=abc",
+                S3Operation::AbortMultipartUpload,
+            ),
+            (
+                Method::GET,
+                "/b/k",
+                "uploadId=abc&max-parts=10",
+                S3Operation::ListMultipartUploadParts,
+            ),
+            (
+                Method::PUT,
+                "/b/k",
+                "partNumber=1&uploadId=abc",
+                S3Operation::PutObject,
+            ),
+            (
+                Method::GET,
+                "/b/k",
+                "attributes",
+                S3Operation::GetObjectAttributes,
+            ),
+            (Method::POST, "/b/k", "restore", S3Operation::RestoreObject),
+            (
+                Method::GET,
+                "/b",
+                "uploads",
+                S3Operation::ListBucketMultipartUploads,
+            ),
+            (
+                Method::GET,
+                "/b",
+                "location",
+                S3Operation::GetBucketLocation,
+            ),
+            (
+                Method::PUT,
+                "/b",
+                "versioning",
+                S3Operation::PutBucketVersioning,
+            ),
+            (
+                Method::DELETE,
+                "/b",
+                "policy",
+                S3Operation::DeleteBucketPolicy,
+            ),
+            // `?delimiter=` must not be mistaken for `?delete`.
+            (Method::POST, "/b", "delimiter=x", S3Operation::PutObject),
+            // A key that merely contains a sub-resource name stays a plain GET.
+            (Method::GET, "/b/tagging", "", S3Operation::GetObject),
+        ];
+        for (method, path, query, expected) in cases {
+            assert_eq!(
+                classify_action(&method, path, query),
+                expected,
+                "{method} {path}?{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_governance_bypass_header() {
+        let mut headers = axum::http::HeaderMap::new();
+        assert!(!requests_governance_bypass(
+            &headers,
+            S3Operation::DeleteObject
+        ));
+        headers.insert(
+            "x-amz-bypass-governance-retention",
+            axum::http::HeaderValue::from_static("true"),
         );
+        assert!(requests_governance_bypass(
+            &headers,
+            S3Operation::DeleteObject
+        ));
+        assert!(!requests_governance_bypass(
+            &headers,
+            S3Operation::GetObject
+        ));
     }
 }
 
@@ -378,34 +728,34 @@ mod classify_action_proptests {
     }
 
     proptest! {
-        /// Never panics on arbitrary method+path.
+        /// Never panics on arbitrary method+path+query.
         #[test]
-        fn never_panics(mi in any::<u8>(), path in ".{0,120}") {
-            let _ = classify_action(&method(mi), &path);
+        fn never_panics(mi in any::<u8>(), path in ".{0,120}", query in ".{0,60}") {
+            let _ = classify_action(&method(mi), &path, &query);
         }
 
         /// SECURITY INVARIANT: a mutating HTTP method must NEVER classify as a
         /// read-only action (Read or List). A regression here would let a
         /// read-only IAM user perform writes/deletes.
         #[test]
-        fn mutating_methods_never_map_to_read(path in ".{0,120}") {
+        fn mutating_methods_never_map_to_read(path in ".{0,120}", query in ".{0,60}") {
             for m in [Method::PUT, Method::DELETE, Method::POST, Method::PATCH] {
-                let a = classify_action(&m, &path);
+                let a = classify_action(&m, &path, &query);
                 prop_assert!(
-                    !matches!(a, S3Action::Read | S3Action::List),
-                    "mutating method {m} on {path:?} mapped to read-only {a:?}"
+                    !matches!(a.category(), S3Action::Read | S3Action::List),
+                    "mutating method {m} on {path:?}?{query:?} mapped to read-only {a:?}"
                 );
             }
         }
 
         /// Read methods (GET/HEAD) never escalate to a write-class action.
         #[test]
-        fn read_methods_never_escalate(path in ".{0,120}") {
+        fn read_methods_never_escalate(path in ".{0,120}", query in ".{0,60}") {
             for m in [Method::GET, Method::HEAD] {
-                let a = classify_action(&m, &path);
+                let a = classify_action(&m, &path, &query);
                 prop_assert!(
-                    matches!(a, S3Action::Read | S3Action::List),
-                    "read method {m} on {path:?} escalated to {a:?}"
+                    matches!(a.category(), S3Action::Read | S3Action::List),
+                    "read method {m} on {path:?}?{query:?} escalated to {a:?}"
                 );
             }
         }
@@ -11,6 +11,7 @@
 //! - `types` — Data types: `IamUser`, `Group`, `Permission`, `S3Action`, `AuthenticatedUser`
 //! - `permissions` — Pure permission evaluation logic (no I/O, no framework)
 //! - `middleware` — Axum authorization middleware
+//! - `simulate` — Policy simulator: explain the decision for a synthetic request
 //! - `keygen` — Cryptographic key generation
 //! - `index` — `IamIndex` for O(1) user lookup and `IamState` enum
 
@@ -19,6 +20,7 @@ pub mod external_auth;
 pub mod keygen;
 pub mod middleware;
 pub mod permissions;
+pub mod simulate;
 pub mod types;
 
 use arc_swap::ArcSwap;
@@ -92,6 +94,28 @@ pub type SharedIamState = Arc<ArcSwap<IamState>>;
 pub struct IamIndex {
     users: HashMap<String, IamUser>,
     groups: Vec<Group>,
+    service_accounts: HashMap<String, IndexedServiceAccount>,
+}
+
+/// A service account resolved against its parent at index build time.
+struct IndexedServiceAccount {
+    account: ServiceAccount,
+    /// Access key of the parent user (looked up on every authentication so a
+    /// disabled parent disables its service accounts immediately).
+    parent_access_key_id: String,
+    /// Inline policy with templates expanded for the parent identity.
+    session_policy: Option<SessionPolicy>,
+}
+
+/// Why [`IamIndex::authenticate`] refused an access key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialRejection {
+    /// No user or service account owns this key.
+    UnknownKey,
+    /// The user, the service account, or its parent is disabled.
+    Disabled,
+    /// The service account is past its expiry.
+    Expired,
 }
 
 impl IamIndex {
@@ -104,6 +128,17 @@ impl IamIndex {
     /// effective permission set. The user's `permissions` field in the index will contain
     /// both direct and group-inherited permissions.
     pub fn from_users_and_groups(users: Vec<IamUser>, groups: Vec<Group>) -> Self {
+        Self::from_parts(users, groups, Vec::new())
+    }
+
+    /// Build the full index: users and groups as in [`Self::from_users_and_groups`],
+    /// plus service accounts keyed by their own access key. A service account
+    /// whose parent is missing, or whose key collides with a user's, is skipped.
+    pub fn from_parts(
+        users: Vec<IamUser>,
+        groups: Vec<Group>,
+        service_accounts: Vec<ServiceAccount>,
+    ) -> Self {
         let group_perms: HashMap<i64, &[Permission]> = groups
             .iter()
             .map(|g| (g.id, g.permissions.as_slice()))
@@ -147,7 +182,55 @@ impl IamIndex {
             }
             map.insert(user.access_key_id.clone(), user);
         }
-        Self { users: map, groups }
+
+        let parents: HashMap<i64, &IamUser> = map.values().map(|u| (u.id, u)).collect();
+        let mut accounts = HashMap::with_capacity(service_accounts.len());
+        for account in service_accounts {
+            let Some(parent) = parents.get(&account.parent_user_id) else {
+                warn!(
+                    "Service account '{}' ({}) has no parent user {} — skipped",
+                    account.name, account.access_key_id, account.parent_user_id
+                );
+                continue;
+            };
+            if map.contains_key(&account.access_key_id) {
+                warn!(
+                    "Service account '{}' reuses user access key {} — skipped",
+                    account.name, account.access_key_id
+                );
+                continue;
+            }
+            let session_policy = account.policy.as_ref().map(|policy| {
+                match permissions::expand_permission_templates(
+                    policy,
+                    &parent.name,
+                    &parent.access_key_id,
+                ) {
+                    Ok(perms) => SessionPolicy::new(perms),
+                    Err(e) => {
+                        warn!(
+                            "Service account '{}' ({}) has invalid policy templates: {} — denying all permissions",
+                            account.name, account.access_key_id, e
+                        );
+                        SessionPolicy::new(Vec::new())
+                    }
+                }
+            });
+            accounts.insert(
+                account.access_key_id.clone(),
+                IndexedServiceAccount {
+                    parent_access_key_id: parent.access_key_id.clone(),
+                    session_policy,
+                    account,
+                },
+            );
+        }
+
+        Self {
+            users: map,
+            groups,
+            service_accounts: accounts,
+        }
     }
 
     /// Look up a user by access_key_id. O(1).
@@ -155,6 +238,68 @@ impl IamIndex {
         self.users.get(access_key_id)
     }
 
+    /// Resolve an access key (user or service account) to its secret and the
+    /// identity it authenticates as. A service account acts as its parent
+    /// user, narrowed by its session policy; `now` is unix seconds.
+    pub fn authenticate(
+        &self,
+        access_key_id: &str,
+        now: i64,
+    ) -> Result<(&str, AuthenticatedUser), CredentialRejection> {
+        if let Some(user) = self.users.get(access_key_id) {
+            if !user.enabled {
+                return Err(CredentialRejection::Disabled);
+            }
+            return Ok((&user.secret_access_key, user.authenticated()));
+        }
+        let sa = self
+            .service_accounts
+            .get(access_key_id)
+            .ok_or(CredentialRejection::UnknownKey)?;
+        let parent = self
+            .users
+            .get(&sa.parent_access_key_id)
+            .ok_or(CredentialRejection::UnknownKey)?;
+        if !sa.account.enabled || !parent.enabled {
+            return Err(CredentialRejection::Disabled);
+        }
+        if sa.account.is_expired(now) {
+            return Err(CredentialRejection::Expired);
+        }
+        let mut identity = parent.authenticated();
+        identity.access_key_id = sa.account.access_key_id.clone();
+        identity.session_policy = sa.session_policy.clone().map(Box::new);
+        Ok((&sa.account.secret_access_key, identity))
+    }
+
+    /// The access key a principal reference names: a user name, a user's
+    /// access key, or a service account's access key.
+    pub fn principal_key(&self, wanted: &str) -> Option<&str> {
+        if let Some((key, _)) = self.users.get_key_value(wanted) {
+            return Some(key);
+        }
+        if let Some((key, _)) = self.service_accounts.get_key_value(wanted) {
+            return Some(key);
+        }
+        self.users
+            .values()
+            .find(|u| u.name == wanted)
+            .map(|u| u.access_key_id.as_str())
+    }
+
+    /// A service account and its parent user (with group rules merged), by
+    /// the account's access key.
+    pub fn service_account(&self, access_key_id: &str) -> Option<(&ServiceAccount, &IamUser)> {
+        let sa = self.service_accounts.get(access_key_id)?;
+        let parent = self.users.get(&sa.parent_access_key_id)?;
+        Some((&sa.account, parent))
+    }
+
+    /// Number of service accounts in the index.
+    pub fn service_account_count(&self) -> usize {
+        self.service_accounts.len()
+    }
+
     /// Number of users in the index.
     pub fn len(&self) -> usize {
         self.users.len()
@@ -169,13 +314,35 @@ impl IamIndex {
         &self.groups
     }
 
-    /// Build IAM state from users and groups.
+    /// Names of the groups an access key's identity belongs to. A service
+    /// account resolves to its parent's groups; an unknown key to none.
+    pub fn group_names(&self, access_key_id: &str) -> Vec<String> {
+        let user = self.users.get(access_key_id).or_else(|| {
+            self.service_accounts
+                .get(access_key_id)
+                .and_then(|sa| self.users.get(&sa.parent_access_key_id))
+        });
+        let Some(user) = user else {
+            return Vec::new();
+        };
+        self.groups
+            .iter()
+            .filter(|g| user.group_ids.contains(&g.id))
+            .map(|g| g.name.clone())
+            .collect()
+    }
+
+    /// Build IAM state from users, groups and service accounts.
     /// Returns `Iam(index)` if users exist, `Disabled` otherwise.
-    pub fn build_iam_state(users: Vec<IamUser>, groups: Vec<Group>) -> IamState {
+    pub fn build_iam_state(
+        users: Vec<IamUser>,
+        groups: Vec<Group>,
+        service_accounts: Vec<ServiceAccount>,
+    ) -> IamState {
         if users.is_empty() {
             return IamState::Disabled;
         }
-        IamState::Iam(Self::from_users_and_groups(users, groups))
+        IamState::Iam(Self::from_parts(users, groups, service_accounts))
     }
 }
 
@@ -333,6 +500,7 @@ mod tests {
             access_key_id: user.access_key_id.clone(),
             iam_policies: user.iam_policies.clone(),
             permissions: user.permissions.clone(),
+            session_policy: None,
         };
         assert!(auth.can(S3Action::Read, "bucket", "key"));
         assert!(auth.can(S3Action::Write, "bucket", "key"));
@@ -380,6 +548,7 @@ mod tests {
             access_key_id: user.access_key_id.clone(),
             iam_policies: user.iam_policies.clone(),
             permissions: user.permissions.clone(),
+            session_policy: None,
         };
         assert!(auth.can(S3Action::Read, "releases", "v1.zip"));
         assert!(!auth.can(S3Action::Delete, "releases", "v1.zip"));
@@ -437,6 +606,7 @@ mod tests {
             access_key_id: user.access_key_id.clone(),
             iam_policies: user.iam_policies.clone(),
             permissions: user.permissions.clone(),
+            session_policy: None,
         };
         assert!(auth.can(S3Action::Read, "bucket", "key"));
         assert!(auth.can(S3Action::List, "bucket", ""));
@@ -497,7 +667,7 @@ mod tests {
 
     #[test]
     fn test_build_iam_state_empty_users() {
-        let state = IamIndex::build_iam_state(vec![], vec![]);
+        let state = IamIndex::build_iam_state(vec![], vec![], vec![]);
         assert!(matches!(state, IamState::Disabled));
     }
 
@@ -515,7 +685,118 @@ mod tests {
             auth_source: "local".into(),
             iam_policies: vec![],
         }];
-        let state = IamIndex::build_iam_state(users, vec![]);
+        let state = IamIndex::build_iam_state(users, vec![], vec![]);
         assert!(matches!(state, IamState::Iam(_)));
     }
+
+    fn sa_parent() -> IamUser {
+        IamUser {
+            id: 7,
+            name: "alice".into(),
+            access_key_id: "AKALICE".into(),
+            secret_access_key: "parent-secret".into(),
+            enabled: true,
+            created_at: String::new(),
+            permissions: vec![Permission {
+                id: 1,
+                effect: "Allow".into(),
+                actions: vec!["*".into()],
+                resources: vec!["data/*".into(), "logs/*".into()],
+                conditions: None,
+            }],
+            group_ids: vec![],
+            auth_source: "local".into(),
+            iam_policies: vec![],
+        }
+    }
+
+    fn sa(key: &str, policy: Option<Vec<Permission>>, expires_at: Option<i64>) -> ServiceAccount {
+        ServiceAccount {
+            id: 1,
+            parent_user_id: 7,
+            name: key.to_lowercase(),
+            description: String::new(),
+            access_key_id: key.into(),
+            secret_access_key: format!("{key}-secret"),
+            policy,
+            expires_at,
+            enabled: true,
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_service_account_policy_intersects_parent() {
+        // The session policy asks for more than the parent has (`other/*`)
+        // and uses a template that must expand to the PARENT's name.
+        let policy = vec![Permission {
+            id: 0,
+            effect: "Allow".into(),
+            actions: vec!["read".into()],
+            resources: vec!["data/${iam:username}/*".into(), "other/*".into()],
+            conditions: None,
+        }];
+        let index = IamIndex::from_parts(
+            vec![sa_parent()],
+            vec![],
+            vec![sa("AKCI", Some(policy), None), sa("AKFULL", None, None)],
+        );
+        assert_eq!(index.service_account_count(), 2);
+
+        let (secret, ci) = index.authenticate("AKCI", 0).unwrap();
+        assert_eq!(secret, "AKCI-secret");
+        assert_eq!(ci.name, "alice");
+        assert_eq!(ci.access_key_id, "AKCI");
+        assert!(ci.can(S3Action::Read, "data", "alice/x"));
+        assert!(!ci.can(S3Action::Read, "data", "bob/x"));
+        assert!(!ci.can(S3Action::Write, "data", "alice/x"));
+        assert!(!ci.can(S3Action::Read, "logs", "x"));
+        assert!(!ci.can(S3Action::Read, "other", "x"));
+        assert!(!ci.is_admin());
+
+        // No inline policy: the parent's permissions apply unchanged.
+        let (_, full) = index.authenticate("AKFULL", 0).unwrap();
+        assert!(full.can(S3Action::Write, "logs", "x"));
+    }
+
+    #[test]
+    fn test_service_account_expiry_and_disabled_parent() {
+        let mut disabled = sa("AKOFF", None, None);
+        disabled.enabled = false;
+        let index = IamIndex::from_parts(
+            vec![sa_parent()],
+            vec![],
+            vec![sa("AKTEMP", None, Some(1_000)), disabled],
+        );
+        assert!(index.authenticate("AKTEMP", 999).is_ok());
+        assert_eq!(
+            index.authenticate("AKTEMP", 1_000).unwrap_err(),
+            CredentialRejection::Expired
+        );
+        assert_eq!(
+            index.authenticate("AKOFF", 0).unwrap_err(),
+            CredentialRejection::Disabled
+        );
+        assert_eq!(
+            index.authenticate("AKNONE", 0).unwrap_err(),
+            CredentialRejection::UnknownKey
+        );
+
+        let mut parent = sa_parent();
+        parent.enabled = false;
+        let index = IamIndex::from_parts(vec![parent], vec![], vec![sa("AKTEMP", None, None)]);
+        assert_eq!(
+            index.authenticate("AKTEMP", 0).unwrap_err(),
+            CredentialRejection::Disabled
+        );
+    }
+
+    #[test]
+    fn test_service_account_cannot_shadow_user_key() {
+        let index =
+            IamIndex::from_parts(vec![sa_parent()], vec![], vec![sa("AKALICE", None, None)]);
+        assert_eq!(index.service_account_count(), 0);
+        let (secret, _) = index.authenticate("AKALICE", 0).unwrap();
+        assert_eq!(secret, "parent-secret");
+    }
 }
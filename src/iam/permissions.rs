@@ -14,9 +14,10 @@ use iam_rs::{
     PolicyEvaluator, Principal, PrincipalId,
 };
 
-use super::types::{Permission, S3Action};
+use super::types::{Permission, S3Action, S3Operation};
 
-/// Valid action verbs for permissions.
+/// Valid action verbs for permissions. Operation-level `s3:<Name>` actions are
+/// accepted in addition (see [`resolve_s3_action`]).
 const VALID_ACTIONS: &[&str] = &["read", "write", "delete", "list", "admin", "*"];
 /// IAM identity template variables, namespaced under `iam:`. The `iam:` prefix
 /// is mandatory and disambiguates these REQUEST-TIME substitutions from the
@@ -142,19 +143,69 @@ pub fn expand_permission_templates(
         .collect()
 }
 
+// === Action resolution ===
+
+/// True if `action` is spelled as an IAM action (`s3:` prefix, any case).
+fn is_s3_action(action: &str) -> bool {
+    action
+        .get(..3)
+        .is_some_and(|p| p.eq_ignore_ascii_case("s3:"))
+}
+
+/// Resolve an `s3:` action or wildcard pattern (`s3:PutObject`, `s3:Get*`,
+/// `s3:*`) to the operations it names. Matching is case-insensitive, like AWS.
+/// Empty for anything that isn't an `s3:` action or names no known operation.
+pub(crate) fn resolve_s3_action(action: &str) -> Vec<S3Operation> {
+    if !is_s3_action(action) {
+        return Vec::new();
+    }
+    let pattern = action.to_ascii_lowercase();
+    S3Operation::ALL
+        .iter()
+        .copied()
+        .filter(|op| {
+            crate::iam::external_auth::mapping::glob_match(
+                &pattern,
+                &op.iam_name().to_ascii_lowercase(),
+            )
+        })
+        .collect()
+}
+
+/// Does one permission action entry (`*`, a verb alias, or an `s3:` action)
+/// grant `op`?
+pub(crate) fn action_grants(action: &str, op: S3Operation) -> bool {
+    if action == "*" {
+        return true;
+    }
+    if let Some(verb) = S3Action::from_verb(action) {
+        return op.category() == verb;
+    }
+    resolve_s3_action(action).contains(&op)
+}
+
 // === IAM-RS Integration ===
 
-/// Map a simple action verb to an AWS IAM S3 action string.
-fn action_to_iam(action: &str) -> String {
-    match action {
-        "read" => "s3:GetObject".to_string(),
-        "write" => "s3:PutObject".to_string(),
-        "delete" => "s3:DeleteObject".to_string(),
-        "list" => "s3:ListBucket".to_string(),
-        "admin" => "s3:CreateBucket".to_string(),
-        "*" => "s3:*".to_string(),
-        other => format!("s3:{}", other),
+/// Map a permission action entry to the AWS IAM S3 action strings it grants.
+///
+/// Verbs expand to every operation they alias and `s3:` patterns expand to
+/// their canonical operation names, so iam-rs (which matches actions
+/// case-sensitively) sees exactly the set the legacy evaluator would grant.
+/// `*` and `s3:*` stay a wildcard so they cover operations added later.
+fn action_to_iam(action: &str) -> Vec<String> {
+    if action == "*" || action.eq_ignore_ascii_case("s3:*") {
+        return vec!["s3:*".to_string()];
+    }
+    let ops: Vec<S3Operation> = match S3Action::from_verb(action) {
+        Some(verb) => verb.operations().collect(),
+        None => resolve_s3_action(action),
+    };
+    if ops.is_empty() {
+        // Rejected by `validate_permissions`; keep legacy data inert rather
+        // than guessing what it meant.
+        return vec![format!("s3:{}", action)];
     }
+    ops.iter().map(|op| op.iam_name().to_string()).collect()
 }
 
 /// Map a simple resource pattern to an S3 ARN string.
@@ -174,7 +225,7 @@ pub fn permission_to_iam_policy(perm: &Permission) -> IAMPolicy {
         IAMEffect::Allow
     };
 
-    let actions: Vec<String> = perm.actions.iter().map(|a| action_to_iam(a)).collect();
+    let actions: Vec<String> = perm.actions.iter().flat_map(|a| action_to_iam(a)).collect();
     let resources: Vec<String> = perm.resources.iter().map(|r| resource_to_arn(r)).collect();
 
     let mut stmt = IAMStatement::new(effect)
@@ -234,7 +285,7 @@ fn build_resource_arn(bucket: &str, key: &str) -> String {
 /// Returns `None` if the ARN cannot be parsed (caller decides fail-open vs fail-closed).
 fn build_iam_evaluator(
     policies: &[IAMPolicy],
-    action: S3Action,
+    action: S3Operation,
     bucket: &str,
     key: &str,
     context: &Context,
@@ -243,7 +294,7 @@ fn build_iam_evaluator(
     let resource = Arn::parse(&resource_str).ok()?;
     let request = IAMRequest::new_with_context(
         Principal::Aws(PrincipalId::String("000000000000".into())),
-        action.to_iam_action(),
+        action.iam_name(),
         resource,
         context.clone(),
     );
@@ -255,11 +306,12 @@ fn build_iam_evaluator(
 /// Supports conditions (s3:prefix, aws:SourceIp, etc.) via the context parameter.
 pub(crate) fn evaluate_iam(
     policies: &[IAMPolicy],
-    action: S3Action,
+    action: impl Into<S3Operation>,
     bucket: &str,
     key: &str,
     context: &Context,
 ) -> bool {
+    let action = action.into();
     let (request, evaluator, resource_str) =
         match build_iam_evaluator(policies, action, bucket, key, context) {
             Some(t) => t,
@@ -282,7 +334,7 @@ pub(crate) fn evaluate_iam(
                 if let Ok(alt_arn) = Arn::parse(&alt_str) {
                     let alt_request = IAMRequest::new_with_context(
                         Principal::Aws(PrincipalId::String("000000000000".into())),
-                        action.to_iam_action(),
+                        action.iam_name(),
                         alt_arn,
                         context.clone(),
                     );
@@ -297,7 +349,7 @@ pub(crate) fn evaluate_iam(
             tracing::warn!(
                 "IAM policy evaluation error: {} (action={}, resource={})",
                 e,
-                action.to_iam_action(),
+                action.iam_name(),
                 resource_str
             );
             false // fail closed
@@ -309,11 +361,12 @@ pub(crate) fn evaluate_iam(
 /// Returns true if the decision is Deny (not NotApplicable).
 pub(crate) fn is_explicitly_denied_iam(
     policies: &[IAMPolicy],
-    action: S3Action,
+    action: impl Into<S3Operation>,
     bucket: &str,
     key: &str,
     context: &Context,
 ) -> bool {
+    let action = action.into();
     let (request, evaluator, _) = match build_iam_evaluator(policies, action, bucket, key, context)
     {
         Some(t) => t,
@@ -329,14 +382,14 @@ pub(crate) fn is_explicitly_denied_iam(
 /// Check if any Deny rule matches in legacy permissions (no conditions).
 pub(crate) fn has_matching_deny(
     permissions: &[Permission],
-    action: S3Action,
+    action: impl Into<S3Operation>,
     bucket: &str,
     key: &str,
 ) -> bool {
-    let action_str = action.as_str();
-    permissions.iter().any(|perm| {
-        perm.effect == "Deny" && matches_action_and_resource(perm, action_str, bucket, key)
-    })
+    let action = action.into();
+    permissions
+        .iter()
+        .any(|perm| perm.effect == "Deny" && matches_action_and_resource(perm, action, bucket, key))
 }
 
 /// Maximum number of permission rules per user or group.
@@ -361,7 +414,8 @@ pub fn normalize_permissions(permissions: &mut [Permission]) {
 ///
 /// Checks:
 /// - Effect must be "Allow" or "Deny" (case-insensitive)
-/// - Actions must be valid verbs (read, write, delete, list, admin, *)
+/// - Actions must be valid verbs (read, write, delete, list, admin, *) or
+///   `s3:` actions naming at least one known operation (`s3:PutObjectTagging`, `s3:Get*`)
 /// - Actions list must not be empty
 /// - Resources must not be empty
 /// - Resource patterns: only trailing `*` is supported (no mid-pattern wildcards)
@@ -394,9 +448,16 @@ pub fn validate_permissions(permissions: &[Permission]) -> Result<(), String> {
             return Err(format!("{}: actions must not be empty", ctx));
         }
         for action in &perm.actions {
-            if !VALID_ACTIONS.contains(&action.as_str()) {
+            if is_s3_action(action) {
+                if resolve_s3_action(action).is_empty() {
+                    return Err(format!(
+                        "{}: unknown S3 action '{}' (e.g. s3:GetObject, s3:PutObjectTagging, s3:AbortMultipartUpload, s3:Get*)",
+                        ctx, action
+                    ));
+                }
+            } else if !VALID_ACTIONS.contains(&action.as_str()) {
                 return Err(format!(
-                    "{}: invalid action '{}' (valid: {})",
+                    "{}: invalid action '{}' (valid: {}, or an s3: action such as s3:PutObjectTagging)",
                     ctx,
                     action,
                     VALID_ACTIONS.join(", ")
@@ -450,22 +511,21 @@ pub fn validate_permissions(permissions: &[Permission]) -> Result<(), String> {
 /// Two-pass evaluation: explicit Deny overrides Allow. No match = implicit deny.
 pub(crate) fn evaluate(
     permissions: &[Permission],
-    action: S3Action,
+    action: impl Into<S3Operation>,
     bucket: &str,
     key: &str,
 ) -> bool {
-    let action_str = action.as_str();
-
+    let action = action.into();
     // Pass 1: Any explicit Deny? Reject immediately.
     for perm in permissions {
-        if perm.effect == "Deny" && matches_action_and_resource(perm, action_str, bucket, key) {
+        if perm.effect == "Deny" && matches_action_and_resource(perm, action, bucket, key) {
             return false;
         }
     }
 
     // Pass 2: Any Allow? Permit.
     for perm in permissions {
-        if perm.effect == "Allow" && matches_action_and_resource(perm, action_str, bucket, key) {
+        if perm.effect == "Allow" && matches_action_and_resource(perm, action, bucket, key) {
             return true;
         }
     }
@@ -579,7 +639,13 @@ fn allow_references_common_prefix(
     bucket: &str,
     prefix: &str,
 ) -> bool {
-    user.permissions.iter().any(|perm| {
+    // A service account's session policy must reference the prefix too.
+    user.rule_sets()
+        .all(|rules| rules_reference_common_prefix(rules, bucket, prefix))
+}
+
+fn rules_reference_common_prefix(rules: &[Permission], bucket: &str, prefix: &str) -> bool {
+    rules.iter().any(|perm| {
         if perm.effect != "Allow" {
             return false;
         }
@@ -589,10 +655,7 @@ fn allow_references_common_prefix(
             return false;
         }
         let can_discover = perm.actions.iter().any(|a| {
-            matches!(
-                a.as_str(),
-                "*" | "read" | "list" | "s3:GetObject" | "s3:ListBucket" | "s3:*"
-            )
+            action_grants(a, S3Operation::GetObject) || action_grants(a, S3Operation::ListBucket)
         });
         can_discover
             && perm
@@ -674,11 +737,11 @@ pub(crate) fn is_admin(permissions: &[Permission]) -> bool {
 /// Check whether a permission rule matches the given action and resource.
 fn matches_action_and_resource(
     perm: &Permission,
-    action_str: &str,
+    action: S3Operation,
     bucket: &str,
     key: &str,
 ) -> bool {
-    let action_matches = perm.actions.iter().any(|a| a == "*" || a == action_str);
+    let action_matches = perm.actions.iter().any(|a| action_grants(a, action));
     if !action_matches {
         return false;
     }
@@ -1305,6 +1368,7 @@ mod tests {
             access_key_id: "AKIA".into(),
             permissions,
             iam_policies,
+            session_policy: None,
         }
     }
 
@@ -1318,6 +1382,7 @@ mod tests {
             access_key_id: "AKIA".into(),
             permissions,
             iam_policies,
+            session_policy: None,
         }
     }
 
@@ -1922,4 +1987,172 @@ mod tests {
             }))
         );
     }
+
+    fn allow(actions: &[&str], resources: &[&str]) -> Permission {
+        Permission {
+            id: 0,
+            effect: "Allow".into(),
+            actions: actions.iter().map(|a| a.to_string()).collect(),
+            resources: resources.iter().map(|r| r.to_string()).collect(),
+            conditions: None,
+        }
+    }
+
+    /// Both evaluation paths must agree on every operation-level decision.
+    fn both_paths(perms: &[Permission], op: S3Operation, bucket: &str, key: &str) -> bool {
+        let policies: Vec<IAMPolicy> = perms.iter().map(permission_to_iam_policy).collect();
+        let legacy = evaluate(perms, op, bucket, key);
+        let iam = evaluate_iam(&policies, op, bucket, key, &Default::default());
+        assert_eq!(legacy, iam, "legacy and iam-rs paths disagree on {op}");
+        iam
+    }
+
+    #[test]
+    fn test_verb_aliases_cover_their_operations() {
+        let perms = vec![allow(&["write"], &["bucket/*"])];
+        assert!(both_paths(&perms, S3Operation::PutObject, "bucket", "k"));
+        assert!(both_paths(
+            &perms,
+            S3Operation::PutObjectTagging,
+            "bucket",
+            "k"
+        ));
+        assert!(!both_paths(
+            &perms,
+            S3Operation::DeleteObject,
+            "bucket",
+            "k"
+        ));
+        assert!(!both_paths(
+            &perms,
+            S3Operation::AbortMultipartUpload,
+            "bucket",
+            "k"
+        ));
+
+        let perms = vec![allow(&["delete"], &["bucket/*"])];
+        assert!(both_paths(
+            &perms,
+            S3Operation::AbortMultipartUpload,
+            "bucket",
+            "k"
+        ));
+        assert!(both_paths(
+            &perms,
+            S3Operation::DeleteObjectTagging,
+            "bucket",
+            "k"
+        ));
+        assert!(!both_paths(&perms, S3Operation::PutObject, "bucket", "k"));
+
+        // Every operation belongs to exactly the verb that aliases it.
+        for op in S3Operation::ALL {
+            assert!(op.category().operations().any(|o| o == *op));
+        }
+    }
+
+    #[test]
+    fn test_tagging_without_write() {
+        let perms = vec![allow(
+            &["s3:GetObjectTagging", "s3:PutObjectTagging"],
+            &["bucket/*"],
+        )];
+        assert!(both_paths(
+            &perms,
+            S3Operation::PutObjectTagging,
+            "bucket",
+            "k"
+        ));
+        assert!(both_paths(
+            &perms,
+            S3Operation::GetObjectTagging,
+            "bucket",
+            "k"
+        ));
+        assert!(!both_paths(&perms, S3Operation::PutObject, "bucket", "k"));
+        assert!(!both_paths(&perms, S3Operation::GetObject, "bucket", "k"));
+    }
+
+    #[test]
+    fn test_multipart_housekeeping_without_delete() {
+        // Multipart create/part/complete are `s3:PutObject`, as on AWS. Deny
+        // plain deletes but let the uploader clean up its own uploads.
+        let perms = vec![allow(
+            &[
+                "s3:PutObject",
+                "s3:AbortMultipartUpload",
+                "s3:ListMultipartUploadParts",
+            ],
+            &["bucket/*"],
+        )];
+        assert!(both_paths(
+            &perms,
+            S3Operation::AbortMultipartUpload,
+            "bucket",
+            "k"
+        ));
+        assert!(both_paths(
+            &perms,
+            S3Operation::ListMultipartUploadParts,
+            "bucket",
+            "k"
+        ));
+        assert!(!both_paths(
+            &perms,
+            S3Operation::DeleteObject,
+            "bucket",
+            "k"
+        ));
+        assert!(!both_paths(&perms, S3Operation::GetObject, "bucket", "k"));
+    }
+
+    #[test]
+    fn test_s3_actions_are_case_insensitive_and_support_wildcards() {
+        let perms = vec![allow(&["s3:get*"], &["bucket/*"])];
+        assert!(both_paths(&perms, S3Operation::GetObject, "bucket", "k"));
+        assert!(both_paths(
+            &perms,
+            S3Operation::GetObjectAttributes,
+            "bucket",
+            "k"
+        ));
+        assert!(!both_paths(&perms, S3Operation::PutObject, "bucket", "k"));
+
+        let perms = vec![allow(&["S3:PUTOBJECTTAGGING"], &["bucket/*"])];
+        assert!(both_paths(
+            &perms,
+            S3Operation::PutObjectTagging,
+            "bucket",
+            "k"
+        ));
+    }
+
+    #[test]
+    fn test_granular_deny_overrides_verb_allow() {
+        let perms = vec![
+            allow(&["read", "write"], &["bucket/*"]),
+            Permission {
+                effect: "Deny".into(),
+                ..allow(&["s3:PutObjectTagging"], &["bucket/*"])
+            },
+        ];
+        assert!(both_paths(&perms, S3Operation::PutObject, "bucket", "k"));
+        assert!(!both_paths(
+            &perms,
+            S3Operation::PutObjectTagging,
+            "bucket",
+            "k"
+        ));
+    }
+
+    #[test]
+    fn test_validate_s3_actions() {
+        assert!(validate_permissions(&[allow(&["s3:PutObjectTagging"], &["*"])]).is_ok());
+        assert!(validate_permissions(&[allow(&["s3:Get*", "read"], &["*"])]).is_ok());
+        assert!(validate_permissions(&[allow(&["s3:*"], &["*"])]).is_ok());
+        let err = validate_permissions(&[allow(&["s3:PutObjectTaggin"], &["*"])]).unwrap_err();
+        assert!(err.contains("unknown S3 action"), "{err}");
+        let err = validate_permissions(&[allow(&["s3:Frobnicate*"], &["*"])]).unwrap_err();
+        assert!(err.contains("unknown S3 action"), "{err}");
+    }
 }
@@ -0,0 +1,615 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! IAM policy simulator — "why is this a 403?"
+//!
+//! Evaluates a synthetic request for a user, a service account, a group or a
+//! hypothetical permission set and reports the decision together with the
+//! rules that produced it. The decision itself comes from
+//! [`middleware::authorize`](super::middleware::authorize) over the
+//! [`AuthenticatedUser`] that [`IamIndex::authenticate`] returns for the
+//! principal's access key (group merge, template expansion, session policy),
+//! so a simulated Allow is the proxy's Allow.
+//!
+//! Pure: callers pass the IAM index; nothing here touches the DB.
+
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+use super::middleware::{authorize, request_context, AuthzBasis};
+use super::permissions::{
+    evaluate_iam, expand_permission_templates, is_explicitly_denied_iam, permission_to_iam_policy,
+};
+use super::types::{
+    AuthenticatedUser, Group, IamUser, ListScope, Permission, S3Action, S3Operation,
+};
+use super::{CredentialRejection, IamIndex};
+
+/// Name `${iam:username}` expands to for group and inline principals, which
+/// have no user of their own.
+pub const SIMULATED_USER: &str = "$simulated";
+
+/// A synthetic request. Exactly one of `user`, `group` or `permissions`
+/// names the principal.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SimulationRequest {
+    /// User name, user access key ID, or service-account access key ID.
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Group name; simulates a member with no direct rules.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Hypothetical rule set, validated like a user's rules.
+    #[serde(default)]
+    pub permissions: Option<Vec<Permission>>,
+    /// Verb (`read`, ...) or operation (`s3:PutObjectTagging`). A verb is
+    /// simulated as its representative operation (`write` → `s3:PutObject`).
+    pub action: String,
+    pub bucket: String,
+    #[serde(default)]
+    pub key: String,
+    /// `aws:SourceIp`. When absent, IP conditions are skipped, as for a
+    /// live request without a resolvable client address.
+    #[serde(default)]
+    pub source_ip: Option<IpAddr>,
+    /// `s3:prefix` for bucket-level list operations.
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// `aws:SecureTransport`.
+    #[serde(default)]
+    pub secure_transport: bool,
+    /// Request carries `x-amz-bypass-governance-retention: true`.
+    #[serde(default)]
+    pub governance_bypass: bool,
+}
+
+/// Decision plus explanation.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationResult {
+    /// `user:<name>`, `service-account:<name>`, `group:<name>` or `inline`.
+    pub principal: String,
+    pub operation: &'static str,
+    pub resource: String,
+    pub allowed: bool,
+    pub basis: AuthzBasis,
+    /// `unrestricted` or `filtered` for admitted bucket-level lists.
+    pub list_scope: Option<&'static str>,
+    /// Rules that decided the request: the matching Denies when denied by
+    /// policy, the matching Allows when allowed. Empty for implicit deny.
+    pub deciding_rules: Vec<MatchedRule>,
+    /// Every rule whose actions, resources and conditions match.
+    pub matched_rules: Vec<MatchedRule>,
+    /// Number of rules evaluated after group merge.
+    pub rules_evaluated: usize,
+}
+
+/// One matching rule and where it came from.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchedRule {
+    /// Position in the merged rule list.
+    pub index: usize,
+    /// `user:<name>`, `group:<name>`, `service-account:<name>` (session
+    /// policy) or `inline`.
+    pub source: String,
+    /// The rule after template expansion.
+    pub rule: Permission,
+}
+
+/// Why a simulation could not run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationError {
+    /// Bad request shape: principal count, action, bucket, rules.
+    Invalid(String),
+    /// The named user or group does not exist.
+    NotFound(String),
+}
+
+impl std::fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(msg) | Self::NotFound(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+/// Resolve a verb or exact `s3:` operation name. Wildcards are rejected:
+/// a request is always one operation.
+pub fn parse_operation(action: &str) -> Result<S3Operation, SimulationError> {
+    let action = action.trim();
+    if let Some(verb) = S3Action::from_verb(action) {
+        return Ok(verb.into());
+    }
+    S3Operation::from_iam_name(action).ok_or_else(|| {
+        SimulationError::Invalid(format!(
+            "unknown action '{}' (expected a verb such as 'read' or an operation such as 's3:GetObject')",
+            action
+        ))
+    })
+}
+
+/// Run a simulation against the live IAM index. `now` (unix seconds) decides
+/// service-account expiry, as it does at authentication.
+pub fn simulate(
+    req: &SimulationRequest,
+    index: &IamIndex,
+    now: i64,
+) -> Result<SimulationResult, SimulationError> {
+    let operation = parse_operation(&req.action)?;
+    let bucket = req.bucket.trim();
+    if bucket.is_empty() {
+        return Err(SimulationError::Invalid("bucket is required".into()));
+    }
+    let key = req.key.trim_start_matches('/');
+
+    let Principal {
+        label: principal,
+        user,
+        sources,
+        session_source,
+    } = resolve_principal(req, index, now)?;
+
+    let prefix = req.prefix.clone().unwrap_or_default();
+    let query = match &req.prefix {
+        Some(p) => format!("prefix={}", urlencoding::encode(p)),
+        None => String::new(),
+    };
+    let context = request_context(operation, &query, req.source_ip, req.secure_transport);
+
+    let outcome = authorize(
+        &user,
+        operation,
+        bucket,
+        key,
+        &prefix,
+        req.governance_bypass,
+        &context,
+    );
+
+    // Identity rules first, then the session policy's, numbered as one list.
+    let session_rules = user
+        .session_policy
+        .as_ref()
+        .map(|sp| sp.permissions.as_slice())
+        .unwrap_or_default();
+    let labelled = user
+        .permissions
+        .iter()
+        .zip(sources)
+        .chain(session_rules.iter().map(|p| (p, session_source.clone())));
+    let mut matched_rules = Vec::new();
+    for (index, (perm, source)) in labelled.enumerate() {
+        let policy = [permission_to_iam_policy(perm)];
+        let matches = if perm.effect == "Deny" {
+            is_explicitly_denied_iam(&policy, operation, bucket, key, &context)
+        } else {
+            evaluate_iam(&policy, operation, bucket, key, &context)
+        };
+        if matches {
+            matched_rules.push(MatchedRule {
+                index,
+                source,
+                rule: perm.clone(),
+            });
+        }
+    }
+
+    let deciding_effect = match outcome.basis {
+        AuthzBasis::ExplicitDeny => Some("Deny"),
+        AuthzBasis::PolicyAllow => Some("Allow"),
+        _ => None,
+    };
+    let deciding_rules = matched_rules
+        .iter()
+        .filter(|m| deciding_effect == Some(m.rule.effect.as_str()))
+        .cloned()
+        .collect();
+
+    Ok(SimulationResult {
+        principal,
+        operation: operation.iam_name(),
+        resource: if key.is_empty() {
+            format!("arn:aws:s3:::{}", bucket)
+        } else {
+            format!("arn:aws:s3:::{}/{}", bucket, key)
+        },
+        allowed: outcome.allowed,
+        basis: outcome.basis,
+        list_scope: outcome.list_scope.map(|scope| match scope {
+            ListScope::Unrestricted => "unrestricted",
+            ListScope::Filtered { .. } => "filtered",
+        }),
+        deciding_rules,
+        matched_rules,
+        rules_evaluated: user.permissions.len() + session_rules.len(),
+    })
+}
+
+/// The identity a simulation runs as, with a source label per rule.
+struct Principal {
+    /// `user:<name>`, `service-account:<name>`, `group:<name>` or `inline`.
+    label: String,
+    user: AuthenticatedUser,
+    /// One label per entry of `user.permissions`.
+    sources: Vec<String>,
+    /// Label for the rules of `user.session_policy`.
+    session_source: String,
+}
+
+fn resolve_principal(
+    req: &SimulationRequest,
+    index: &IamIndex,
+    now: i64,
+) -> Result<Principal, SimulationError> {
+    let given = [
+        req.user.is_some(),
+        req.group.is_some(),
+        req.permissions.is_some(),
+    ];
+    if given.iter().filter(|g| **g).count() != 1 {
+        return Err(SimulationError::Invalid(
+            "exactly one of 'user', 'group' or 'permissions' is required".into(),
+        ));
+    }
+
+    if let Some(wanted) = &req.user {
+        let access_key = index
+            .principal_key(wanted)
+            .ok_or_else(|| SimulationError::NotFound(format!("user '{}' not found", wanted)))?;
+        let (label, parent) = match index.get(access_key) {
+            Some(user) => (format!("user:{}", user.name), user),
+            None => {
+                let (account, parent) = index.service_account(access_key).ok_or_else(|| {
+                    SimulationError::NotFound(format!("user '{}' not found", wanted))
+                })?;
+                (format!("service-account:{}", account.name), parent)
+            }
+        };
+        // Resolve through the same call SigV4 makes, so the simulated
+        // identity (group merge, templates, session policy) is the real one.
+        // A rejected key never reaches authorization; say so rather than
+        // report a policy decision the proxy would never make.
+        let user = match index.authenticate(access_key, now) {
+            Ok((_, user)) => user,
+            Err(CredentialRejection::Disabled) => {
+                return Err(SimulationError::Invalid(format!(
+                    "{} is disabled; every request is rejected at authentication",
+                    label
+                )))
+            }
+            Err(CredentialRejection::Expired) => {
+                return Err(SimulationError::Invalid(format!(
+                    "{} has expired; every request is rejected at authentication",
+                    label
+                )))
+            }
+            Err(CredentialRejection::UnknownKey) => {
+                return Err(SimulationError::NotFound(format!(
+                    "user '{}' not found",
+                    wanted
+                )))
+            }
+        };
+        return Ok(Principal {
+            sources: identity_sources(parent, index.groups()),
+            session_source: label.clone(),
+            label,
+            user,
+        });
+    }
+
+    if let Some(wanted) = &req.group {
+        let group = index
+            .groups()
+            .iter()
+            .find(|g| g.name == *wanted)
+            .ok_or_else(|| SimulationError::NotFound(format!("group '{}' not found", wanted)))?;
+        let label = format!("group:{}", group.name);
+        return synthetic_principal(label, &group.permissions);
+    }
+
+    let mut rules = req.permissions.clone().unwrap_or_default();
+    super::permissions::normalize_permissions(&mut rules);
+    super::permissions::validate_permissions(&rules).map_err(SimulationError::Invalid)?;
+    synthetic_principal("inline".to_string(), &rules)
+}
+
+/// Labels for an indexed user's merged rules, in the order
+/// [`IamIndex`] merges them: direct rules, then each group's.
+fn identity_sources(user: &IamUser, groups: &[Group]) -> Vec<String> {
+    let group_sources: Vec<String> = user
+        .group_ids
+        .iter()
+        .filter_map(|gid| groups.iter().find(|g| g.id == *gid))
+        .flat_map(group_rules)
+        .map(|(source, _)| source)
+        .collect();
+    let direct = user.permissions.len().saturating_sub(group_sources.len());
+    std::iter::repeat_n(format!("user:{}", user.name), direct)
+        .chain(group_sources)
+        .collect()
+}
+
+/// A group member with no direct rules, or an unsaved rule list, as a user
+/// named [`SIMULATED_USER`].
+fn synthetic_principal(label: String, rules: &[Permission]) -> Result<Principal, SimulationError> {
+    let permissions =
+        expand_permission_templates(rules, SIMULATED_USER, "").map_err(SimulationError::Invalid)?;
+    Ok(Principal {
+        sources: vec![label.clone(); permissions.len()],
+        session_source: label.clone(),
+        label,
+        user: AuthenticatedUser {
+            name: SIMULATED_USER.to_string(),
+            access_key_id: String::new(),
+            iam_policies: permissions.iter().map(permission_to_iam_policy).collect(),
+            permissions,
+            session_policy: None,
+        },
+    })
+}
+
+fn group_rules(group: &Group) -> Vec<(String, Permission)> {
+    let source = format!("group:{}", group.name);
+    group
+        .permissions
+        .iter()
+        .map(|p| (source.clone(), p.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iam::types::ServiceAccount;
+
+    fn perm(effect: &str, actions: &[&str], resources: &[&str]) -> Permission {
+        Permission {
+            id: 0,
+            effect: effect.into(),
+            actions: actions.iter().map(|s| s.to_string()).collect(),
+            resources: resources.iter().map(|s| s.to_string()).collect(),
+            conditions: None,
+        }
+    }
+
+    fn user(name: &str, permissions: Vec<Permission>, group_ids: Vec<i64>) -> IamUser {
+        IamUser {
+            id: 1,
+            name: name.into(),
+            access_key_id: format!("AK{}", name.to_uppercase()),
+            secret_access_key: String::new(),
+            enabled: true,
+            created_at: String::new(),
+            permissions,
+            group_ids,
+            auth_source: "local".into(),
+            iam_policies: vec![],
+        }
+    }
+
+    fn group(id: i64, name: &str, permissions: Vec<Permission>) -> Group {
+        Group {
+            id,
+            name: name.into(),
+            description: String::new(),
+            permissions,
+            member_ids: vec![],
+            created_at: String::new(),
+        }
+    }
+
+    fn run(
+        req: &SimulationRequest,
+        users: &[IamUser],
+        groups: &[Group],
+    ) -> Result<SimulationResult, SimulationError> {
+        let index = IamIndex::from_users_and_groups(users.to_vec(), groups.to_vec());
+        simulate(req, &index, 0)
+    }
+
+    fn request(action: &str, bucket: &str, key: &str) -> SimulationRequest {
+        SimulationRequest {
+            action: action.into(),
+            bucket: bucket.into(),
+            key: key.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_group_deny_is_reported_as_deciding_rule() {
+        let users = vec![user(
+            "dana",
+            vec![perm("Allow", &["*"], &["releases/*"])],
+            vec![7],
+        )];
+        let groups = vec![group(
+            7,
+            "Contractors",
+            vec![perm("Deny", &["s3:PutObjectTagging"], &["*"])],
+        )];
+        let mut req = request("s3:PutObjectTagging", "releases", "a.zip");
+        req.user = Some("dana".into());
+
+        let result = run(&req, &users, &groups).unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.basis, AuthzBasis::ExplicitDeny);
+        assert_eq!(result.deciding_rules.len(), 1);
+        assert_eq!(result.deciding_rules[0].source, "group:Contractors");
+        assert_eq!(result.deciding_rules[0].index, 1);
+        // The direct Allow matched too but did not decide.
+        assert_eq!(result.matched_rules.len(), 2);
+    }
+
+    #[test]
+    fn test_lookup_by_access_key_and_template_expansion() {
+        let users = vec![user(
+            "dana",
+            vec![perm("Allow", &["read"], &["home/${iam:username}/*"])],
+            vec![],
+        )];
+        let mut req = request("read", "home", "dana/notes.txt");
+        req.user = Some("AKDANA".into());
+        let result = run(&req, &users, &[]).unwrap();
+        assert!(result.allowed);
+        assert_eq!(result.operation, "s3:GetObject");
+        assert_eq!(result.deciding_rules[0].rule.resources, vec!["home/dana/*"]);
+
+        req.key = "erin/notes.txt".into();
+        let result = run(&req, &users, &[]).unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.basis, AuthzBasis::ImplicitDeny);
+        assert!(result.deciding_rules.is_empty());
+    }
+
+    #[test]
+    fn test_service_account_runs_under_its_session_policy() {
+        let users = vec![user(
+            "dana",
+            vec![perm("Allow", &["*"], &["home/${iam:username}/*"])],
+            vec![],
+        )];
+        let account = |enabled: bool, expires_at: Option<i64>| ServiceAccount {
+            id: 1,
+            parent_user_id: 1,
+            name: "ci".into(),
+            description: String::new(),
+            access_key_id: "AKCI".into(),
+            secret_access_key: String::new(),
+            policy: Some(vec![perm("Allow", &["read"], &["home/${iam:username}/*"])]),
+            expires_at,
+            enabled,
+            created_at: String::new(),
+        };
+        let index = IamIndex::from_parts(users.clone(), vec![], vec![account(true, None)]);
+
+        let mut req = request("read", "home", "dana/notes.txt");
+        req.user = Some("AKCI".into());
+        let result = simulate(&req, &index, 0).unwrap();
+        assert!(result.allowed);
+        assert_eq!(result.principal, "service-account:ci");
+        assert_eq!(result.rules_evaluated, 2);
+        let sources: Vec<_> = result.deciding_rules.iter().map(|m| &m.source).collect();
+        assert_eq!(sources, ["user:dana", "service-account:ci"]);
+
+        // The parent may write; the session policy may not.
+        req.action = "write".into();
+        let result = simulate(&req, &index, 0).unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.basis, AuthzBasis::ImplicitDeny);
+        assert_eq!(result.matched_rules.len(), 1);
+
+        let index = IamIndex::from_parts(users, vec![], vec![account(true, Some(100))]);
+        assert!(matches!(
+            simulate(&req, &index, 100),
+            Err(SimulationError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_conditions_use_request_context() {
+        let mut rule = perm("Allow", &["read"], &["ops/*"]);
+        rule.conditions = Some(serde_json::json!({
+            "IpAddress": { "aws:SourceIp": "10.0.0.0/8" },
+            "Bool": { "aws:SecureTransport": "true" }
+        }));
+        let mut req = request("read", "ops", "runbook.md");
+        req.permissions = Some(vec![rule]);
+        req.source_ip = Some("10.1.2.3".parse().unwrap());
+
+        assert!(!run(&req, &[], &[]).unwrap().allowed);
+        req.secure_transport = true;
+        assert!(run(&req, &[], &[]).unwrap().allowed);
+        req.source_ip = Some("203.0.113.9".parse().unwrap());
+        assert!(!run(&req, &[], &[]).unwrap().allowed);
+    }
+
+    #[test]
+    fn test_prefix_scoped_list_is_filtered() {
+        let mut req = request("list", "db-archive", "");
+        req.permissions = Some(vec![perm(
+            "Allow",
+            &["read", "list"],
+            &["db-archive/home/*"],
+        )]);
+        req.prefix = Some("home/".into());
+        let result = run(&req, &[], &[]).unwrap();
+        assert!(result.allowed);
+        assert_eq!(result.basis, AuthzBasis::BucketVisibilityFallback);
+        assert_eq!(result.list_scope, Some("filtered"));
+
+        req.permissions = Some(vec![perm("Allow", &["read", "list"], &["db-archive/*"])]);
+        let result = run(&req, &[], &[]).unwrap();
+        assert_eq!(result.basis, AuthzBasis::PolicyAllow);
+        assert_eq!(result.list_scope, Some("unrestricted"));
+    }
+
+    #[test]
+    fn test_bucket_sub_resources_need_their_own_action() {
+        let mut req = request("s3:GetBucketPolicy", "db-archive", "");
+        req.permissions = Some(vec![perm(
+            "Allow",
+            &["read", "list"],
+            &["db-archive/home/*"],
+        )]);
+        let result = run(&req, &[], &[]).unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.basis, AuthzBasis::ImplicitDeny);
+
+        req.permissions = Some(vec![perm(
+            "Allow",
+            &["s3:GetBucketPolicy"],
+            &["db-archive"],
+        )]);
+        assert!(run(&req, &[], &[]).unwrap().allowed);
+    }
+
+    #[test]
+    fn test_bucket_location_keeps_the_visibility_fallback() {
+        // SDKs probe the region of a bucket the user can only partly see.
+        let mut req = request("s3:GetBucketLocation", "db-archive", "");
+        req.permissions = Some(vec![perm(
+            "Allow",
+            &["read", "list"],
+            &["db-archive/home/*"],
+        )]);
+        let result = run(&req, &[], &[]).unwrap();
+        assert!(result.allowed);
+        assert_eq!(result.basis, AuthzBasis::BucketVisibilityFallback);
+    }
+
+    #[test]
+    fn test_governance_bypass_needs_its_own_grant() {
+        let mut req = request("s3:DeleteObject", "vault", "x");
+        req.permissions = Some(vec![perm("Allow", &["s3:DeleteObject"], &["vault/*"])]);
+        req.governance_bypass = true;
+        let result = run(&req, &[], &[]).unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.basis, AuthzBasis::GovernanceBypassDenied);
+    }
+
+    #[test]
+    fn test_rejects_bad_requests() {
+        let users = vec![user("dana", vec![], vec![])];
+        let req = request("read", "b", "k");
+        assert!(matches!(
+            run(&req, &users, &[]),
+            Err(SimulationError::Invalid(_))
+        ));
+
+        let mut req = request("s3:Get*", "b", "k");
+        req.user = Some("dana".into());
+        assert!(matches!(
+            run(&req, &users, &[]),
+            Err(SimulationError::Invalid(_))
+        ));
+
+        let mut req = request("read", "b", "k");
+        req.group = Some("nobody".into());
+        assert!(matches!(
+            run(&req, &users, &[]),
+            Err(SimulationError::NotFound(_))
+        ));
+    }
+}
@@ -59,6 +59,42 @@ pub struct Group {
     pub created_at: String,
 }
 
+/// A child credential minted by (or for) an IAM user.
+///
+/// Authenticates with its own key pair but acts as the parent: its effective
+/// permissions are the parent's (direct + groups) intersected with the
+/// optional inline `policy`. Expiry and revocation are per account; deleting
+/// or disabling the parent disables every child.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceAccount {
+    #[serde(default)]
+    pub id: i64,
+    pub parent_user_id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub access_key_id: String,
+    #[serde(skip_serializing_if = "is_masked")]
+    pub secret_access_key: String,
+    /// Inline session policy. `None` inherits the parent's permissions in full.
+    #[serde(default)]
+    pub policy: Option<Vec<Permission>>,
+    /// Unix seconds after which the key stops authenticating. `None` = never.
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    #[serde(default = "crate::types::default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub created_at: String,
+}
+
+impl ServiceAccount {
+    /// True once `now` (unix seconds) has reached the expiry.
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|at| now >= at)
+    }
+}
+
 fn is_masked(s: &str) -> bool {
     s == "****"
 }
@@ -80,7 +116,8 @@ pub struct Permission {
     /// "Allow" or "Deny" — Deny rules override Allow rules.
     #[serde(default = "default_allow")]
     pub effect: String,
-    /// Action verbs: "read", "write", "delete", "list", "admin", or "*"
+    /// Action verbs ("read", "write", "delete", "list", "admin", "*") or
+    /// operation-level IAM actions ("s3:PutObjectTagging", "s3:Get*")
     pub actions: Vec<String>,
     /// Resource patterns: "bucket/*", "bucket/prefix*", or "*"
     pub resources: Vec<String>,
@@ -90,6 +127,11 @@ pub struct Permission {
 }
 
 /// S3 action categories mapped from HTTP methods.
+///
+/// These are the coarse permission verbs (`read`, `write`, ...). Each verb is
+/// an alias for every [`S3Operation`] whose [`S3Operation::category`] it is,
+/// so existing policies keep granting exactly what they granted before
+/// operation-level actions existed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum S3Action {
     Read,   // GET object, HEAD object
@@ -111,18 +153,244 @@ impl S3Action {
         }
     }
 
+    /// Parse a permission action verb (`"read"`, `"write"`, ...).
+    pub fn from_verb(verb: &str) -> Option<Self> {
+        match verb {
+            "read" => Some(Self::Read),
+            "write" => Some(Self::Write),
+            "delete" => Some(Self::Delete),
+            "list" => Some(Self::List),
+            "admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+
     /// Map to standard AWS IAM S3 action string.
     pub fn to_iam_action(&self) -> &'static str {
+        S3Operation::from(*self).iam_name()
+    }
+
+    /// Every operation-level action this verb is an alias for.
+    pub fn operations(self) -> impl Iterator<Item = S3Operation> {
+        S3Operation::ALL
+            .iter()
+            .copied()
+            .filter(move |op| op.category() == self)
+    }
+}
+
+/// Operation-level S3 actions, named after the AWS IAM action they map to
+/// (`s3:PutObjectTagging`, `s3:AbortMultipartUpload`, ...).
+///
+/// The authorization middleware classifies every request into one of these
+/// so a policy can grant e.g. multipart uploads or tagging without granting
+/// plain overwrites. Permission rules may name them directly (`s3:GetObject`,
+/// `s3:Get*`) or through the [`S3Action`] verb aliases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum S3Operation {
+    // Object reads
+    GetObject,
+    GetObjectAttributes,
+    GetObjectTagging,
+    GetObjectAcl,
+    GetObjectRetention,
+    GetObjectLegalHold,
+    ListMultipartUploadParts,
+    // Object writes
+    PutObject,
+    PutObjectTagging,
+    PutObjectAcl,
+    PutObjectRetention,
+    PutObjectLegalHold,
+    RestoreObject,
+    // Object deletes
+    DeleteObject,
+    DeleteObjectTagging,
+    AbortMultipartUpload,
+    BypassGovernanceRetention,
+    // Bucket reads
+    ListAllMyBuckets,
+    ListBucket,
+    ListBucketVersions,
+    ListBucketMultipartUploads,
+    GetBucketLocation,
+    GetBucketVersioning,
+    GetBucketTagging,
+    GetBucketAcl,
+    GetBucketCors,
+    GetBucketPolicy,
+    GetLifecycleConfiguration,
+    // Bucket administration
+    CreateBucket,
+    DeleteBucket,
+    PutBucketVersioning,
+    PutBucketTagging,
+    PutBucketAcl,
+    PutBucketCors,
+    PutBucketPolicy,
+    DeleteBucketPolicy,
+    PutLifecycleConfiguration,
+}
+
+impl S3Operation {
+    /// Every operation, in declaration order.
+    pub const ALL: &'static [S3Operation] = &[
+        Self::GetObject,
+        Self::GetObjectAttributes,
+        Self::GetObjectTagging,
+        Self::GetObjectAcl,
+        Self::GetObjectRetention,
+        Self::GetObjectLegalHold,
+        Self::ListMultipartUploadParts,
+        Self::PutObject,
+        Self::PutObjectTagging,
+        Self::PutObjectAcl,
+        Self::PutObjectRetention,
+        Self::PutObjectLegalHold,
+        Self::RestoreObject,
+        Self::DeleteObject,
+        Self::DeleteObjectTagging,
+        Self::AbortMultipartUpload,
+        Self::BypassGovernanceRetention,
+        Self::ListAllMyBuckets,
+        Self::ListBucket,
+        Self::ListBucketVersions,
+        Self::ListBucketMultipartUploads,
+        Self::GetBucketLocation,
+        Self::GetBucketVersioning,
+        Self::GetBucketTagging,
+        Self::GetBucketAcl,
+        Self::GetBucketCors,
+        Self::GetBucketPolicy,
+        Self::GetLifecycleConfiguration,
+        Self::CreateBucket,
+        Self::DeleteBucket,
+        Self::PutBucketVersioning,
+        Self::PutBucketTagging,
+        Self::PutBucketAcl,
+        Self::PutBucketCors,
+        Self::PutBucketPolicy,
+        Self::DeleteBucketPolicy,
+        Self::PutLifecycleConfiguration,
+    ];
+
+    /// The AWS IAM action name, e.g. `"s3:PutObjectTagging"`.
+    pub fn iam_name(&self) -> &'static str {
+        match self {
+            Self::GetObject => "s3:GetObject",
+            Self::GetObjectAttributes => "s3:GetObjectAttributes",
+            Self::GetObjectTagging => "s3:GetObjectTagging",
+            Self::GetObjectAcl => "s3:GetObjectAcl",
+            Self::GetObjectRetention => "s3:GetObjectRetention",
+            Self::GetObjectLegalHold => "s3:GetObjectLegalHold",
+            Self::ListMultipartUploadParts => "s3:ListMultipartUploadParts",
+            Self::PutObject => "s3:PutObject",
+            Self::PutObjectTagging => "s3:PutObjectTagging",
+            Self::PutObjectAcl => "s3:PutObjectAcl",
+            Self::PutObjectRetention => "s3:PutObjectRetention",
+            Self::PutObjectLegalHold => "s3:PutObjectLegalHold",
+            Self::RestoreObject => "s3:RestoreObject",
+            Self::DeleteObject => "s3:DeleteObject",
+            Self::DeleteObjectTagging => "s3:DeleteObjectTagging",
+            Self::AbortMultipartUpload => "s3:AbortMultipartUpload",
+            Self::BypassGovernanceRetention => "s3:BypassGovernanceRetention",
+            Self::ListAllMyBuckets => "s3:ListAllMyBuckets",
+            Self::ListBucket => "s3:ListBucket",
+            Self::ListBucketVersions => "s3:ListBucketVersions",
+            Self::ListBucketMultipartUploads => "s3:ListBucketMultipartUploads",
+            Self::GetBucketLocation => "s3:GetBucketLocation",
+            Self::GetBucketVersioning => "s3:GetBucketVersioning",
+            Self::GetBucketTagging => "s3:GetBucketTagging",
+            Self::GetBucketAcl => "s3:GetBucketAcl",
+            Self::GetBucketCors => "s3:GetBucketCORS",
+            Self::GetBucketPolicy => "s3:GetBucketPolicy",
+            Self::GetLifecycleConfiguration => "s3:GetLifecycleConfiguration",
+            Self::CreateBucket => "s3:CreateBucket",
+            Self::DeleteBucket => "s3:DeleteBucket",
+            Self::PutBucketVersioning => "s3:PutBucketVersioning",
+            Self::PutBucketTagging => "s3:PutBucketTagging",
+            Self::PutBucketAcl => "s3:PutBucketAcl",
+            Self::PutBucketCors => "s3:PutBucketCORS",
+            Self::PutBucketPolicy => "s3:PutBucketPolicy",
+            Self::DeleteBucketPolicy => "s3:DeleteBucketPolicy",
+            Self::PutLifecycleConfiguration => "s3:PutLifecycleConfiguration",
+        }
+    }
+
+    /// The coarse verb this operation belongs to. Mirrors the pre-granular
+    /// HTTP-method classification, so `read`/`write`/... keep their meaning.
+    pub fn category(&self) -> S3Action {
         match self {
-            Self::Read => "s3:GetObject",
-            Self::Write => "s3:PutObject",
-            Self::Delete => "s3:DeleteObject",
-            Self::List => "s3:ListBucket",
-            Self::Admin => "s3:CreateBucket",
+            Self::GetObject
+            | Self::GetObjectAttributes
+            | Self::GetObjectTagging
+            | Self::GetObjectAcl
+            | Self::GetObjectRetention
+            | Self::GetObjectLegalHold
+            | Self::ListMultipartUploadParts => S3Action::Read,
+            Self::PutObject
+            | Self::PutObjectTagging
+            | Self::PutObjectAcl
+            | Self::PutObjectRetention
+            | Self::PutObjectLegalHold
+            | Self::RestoreObject => S3Action::Write,
+            Self::DeleteObject
+            | Self::DeleteObjectTagging
+            | Self::AbortMultipartUpload
+            | Self::BypassGovernanceRetention => S3Action::Delete,
+            Self::ListAllMyBuckets
+            | Self::ListBucket
+            | Self::ListBucketVersions
+            | Self::ListBucketMultipartUploads
+            | Self::GetBucketLocation
+            | Self::GetBucketVersioning
+            | Self::GetBucketTagging
+            | Self::GetBucketAcl
+            | Self::GetBucketCors
+            | Self::GetBucketPolicy
+            | Self::GetLifecycleConfiguration => S3Action::List,
+            Self::CreateBucket
+            | Self::DeleteBucket
+            | Self::PutBucketVersioning
+            | Self::PutBucketTagging
+            | Self::PutBucketAcl
+            | Self::PutBucketCors
+            | Self::PutBucketPolicy
+            | Self::DeleteBucketPolicy
+            | Self::PutLifecycleConfiguration => S3Action::Admin,
+        }
+    }
+
+    /// Look up an operation by its IAM action name. Case-insensitive, like
+    /// AWS: `s3:getobject` and `s3:GetObject` are the same action.
+    pub fn from_iam_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|op| op.iam_name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl From<S3Action> for S3Operation {
+    /// The representative operation for a coarse verb — what callers that
+    /// only know the verb (batch delete, copy-source checks) evaluate.
+    fn from(action: S3Action) -> Self {
+        match action {
+            S3Action::Read => Self::GetObject,
+            S3Action::Write => Self::PutObject,
+            S3Action::Delete => Self::DeleteObject,
+            S3Action::List => Self::ListBucket,
+            S3Action::Admin => Self::CreateBucket,
         }
     }
 }
 
+impl std::fmt::Display for S3Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.iam_name())
+    }
+}
+
 /// Resolved identity after SigV4 authentication.
 /// Inserted into request extensions by the SigV4 middleware.
 #[derive(Debug, Clone)]
@@ -132,52 +400,131 @@ pub struct AuthenticatedUser {
     pub permissions: Vec<Permission>,
     /// Precomputed IAM policies for iam-rs evaluation (includes conditions support).
     pub iam_policies: Vec<IAMPolicy>,
+    /// Inline policy of a service account. When set, a request needs an
+    /// Allow from both `permissions` and this policy, and a Deny in either
+    /// blocks it.
+    pub session_policy: Option<Box<SessionPolicy>>,
+}
+
+/// A rule set that can only narrow what an identity is allowed to do.
+#[derive(Debug, Clone)]
+pub struct SessionPolicy {
+    pub permissions: Vec<Permission>,
+    pub iam_policies: Vec<IAMPolicy>,
+}
+
+impl SessionPolicy {
+    pub fn new(permissions: Vec<Permission>) -> Self {
+        let iam_policies = permissions
+            .iter()
+            .map(permissions::permission_to_iam_policy)
+            .collect();
+        Self {
+            permissions,
+            iam_policies,
+        }
+    }
+}
+
+/// Allow check over one rule set (iam-rs when policies are precomputed,
+/// legacy evaluation otherwise).
+fn rules_allow(
+    rules: &[Permission],
+    policies: &[IAMPolicy],
+    action: S3Operation,
+    bucket: &str,
+    key: &str,
+    context: &iam_rs::Context,
+) -> bool {
+    if !policies.is_empty() {
+        permissions::evaluate_iam(policies, action, bucket, key, context)
+    } else {
+        // Legacy path — no conditions support, ignore context
+        permissions::evaluate(rules, action, bucket, key)
+    }
+}
+
+/// Explicit-Deny check over one rule set.
+fn rules_deny(
+    rules: &[Permission],
+    policies: &[IAMPolicy],
+    action: S3Operation,
+    bucket: &str,
+    key: &str,
+    context: &iam_rs::Context,
+) -> bool {
+    if !policies.is_empty() {
+        permissions::is_explicitly_denied_iam(policies, action, bucket, key, context)
+    } else {
+        // Legacy: check if any Deny rule matches
+        permissions::has_matching_deny(rules, action, bucket, key)
+    }
 }
 
 impl AuthenticatedUser {
     /// Check if this user is allowed to perform the given action on the given resource.
     /// Uses iam-rs for evaluation when policies are available (supports conditions),
     /// falls back to legacy evaluation otherwise.
-    pub fn can(&self, action: S3Action, bucket: &str, key: &str) -> bool {
-        if !self.iam_policies.is_empty() {
-            permissions::evaluate_iam(&self.iam_policies, action, bucket, key, &Default::default())
-        } else {
-            permissions::evaluate(&self.permissions, action, bucket, key)
-        }
+    pub fn can(&self, action: impl Into<S3Operation>, bucket: &str, key: &str) -> bool {
+        self.can_with_context(action, bucket, key, &Default::default())
     }
 
     /// Check with request context (s3:prefix, aws:SourceIp, etc.).
     /// Used by the authorization middleware to pass conditions from the HTTP request.
     pub fn can_with_context(
         &self,
-        action: S3Action,
+        action: impl Into<S3Operation>,
         bucket: &str,
         key: &str,
         context: &iam_rs::Context,
     ) -> bool {
-        if !self.iam_policies.is_empty() {
-            permissions::evaluate_iam(&self.iam_policies, action, bucket, key, context)
-        } else {
-            // Legacy path — no conditions support, ignore context
-            permissions::evaluate(&self.permissions, action, bucket, key)
-        }
+        let action = action.into();
+        rules_allow(
+            &self.permissions,
+            &self.iam_policies,
+            action,
+            bucket,
+            key,
+            context,
+        ) && self.session_policy.as_ref().is_none_or(|s| {
+            rules_allow(
+                &s.permissions,
+                &s.iam_policies,
+                action,
+                bucket,
+                key,
+                context,
+            )
+        })
     }
 
     /// Check if an explicit Deny rule matches (including conditions).
     /// Used to distinguish "no matching Allow" from "explicitly denied" for LIST fallback logic.
     pub fn is_explicitly_denied(
         &self,
-        action: S3Action,
+        action: impl Into<S3Operation>,
         bucket: &str,
         key: &str,
         context: &iam_rs::Context,
     ) -> bool {
-        if !self.iam_policies.is_empty() {
-            permissions::is_explicitly_denied_iam(&self.iam_policies, action, bucket, key, context)
-        } else {
-            // Legacy: check if any Deny rule matches
-            permissions::has_matching_deny(&self.permissions, action, bucket, key)
-        }
+        let action = action.into();
+        rules_deny(
+            &self.permissions,
+            &self.iam_policies,
+            action,
+            bucket,
+            key,
+            context,
+        ) || self.session_policy.as_ref().is_some_and(|s| {
+            rules_deny(
+                &s.permissions,
+                &s.iam_policies,
+                action,
+                bucket,
+                key,
+                context,
+            )
+        })
     }
 
     /// Check if this user should see the given bucket in ListBuckets.
@@ -185,16 +532,38 @@ impl AuthenticatedUser {
     /// Ignores Deny rules for visibility (deny only blocks actions, not bucket discovery).
     pub fn can_see_bucket(&self, bucket: &str) -> bool {
         permissions::has_any_on_bucket(&self.permissions, bucket)
+            && self
+                .session_policy
+                .as_ref()
+                .is_none_or(|s| permissions::has_any_on_bucket(&s.permissions, bucket))
     }
 
     /// Returns true if any of this user's permissions have conditions attached.
     pub fn has_any_conditions(&self) -> bool {
         self.permissions.iter().any(|p| p.conditions.is_some())
+            || self
+                .session_policy
+                .as_ref()
+                .is_some_and(|s| s.permissions.iter().any(|p| p.conditions.is_some()))
     }
 
     /// Returns true if this user has full admin permissions.
     pub fn is_admin(&self) -> bool {
         permissions::is_admin(&self.permissions)
+            && self
+                .session_policy
+                .as_ref()
+                .is_none_or(|s| permissions::is_admin(&s.permissions))
+    }
+
+    /// Every rule set a request must satisfy: the identity's own rules, then
+    /// the session policy if any.
+    pub(crate) fn rule_sets(&self) -> impl Iterator<Item = &[Permission]> {
+        std::iter::once(self.permissions.as_slice()).chain(
+            self.session_policy
+                .as_ref()
+                .map(|s| s.permissions.as_slice()),
+        )
     }
 }
 
@@ -234,6 +603,17 @@ pub enum ListScope {
 }
 
 impl IamUser {
+    /// The identity this user authenticates as (no session policy).
+    pub fn authenticated(&self) -> AuthenticatedUser {
+        AuthenticatedUser {
+            name: self.name.clone(),
+            access_key_id: self.access_key_id.clone(),
+            permissions: self.permissions.clone(),
+            iam_policies: self.iam_policies.clone(),
+            session_policy: None,
+        }
+    }
+
     /// Returns true if this user has full admin permissions:
     /// actions must contain "*" or "admin", AND resources must contain "*".
     /// A user with actions=["*"] on a specific bucket is NOT considered admin.
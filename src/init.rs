@@ -156,6 +156,7 @@ pub fn run_init_inner(
                 let path = prompt(reader, writer, "Data directory", "./data")?;
                 break BackendConfig::Filesystem {
                     path: PathBuf::from(path),
+                    metadata: Default::default(),
                 };
             }
             "s3" => {
@@ -322,6 +323,8 @@ pub fn run_init_inner(
         replication: crate::config_sections::ReplicationConfig::default(),
         lifecycle: crate::config_sections::LifecycleConfig::default(),
         event_delivery: crate::config_sections::EventDeliveryConfig::default(),
+        disk_cache: crate::config_sections::DiskCacheConfig::default(),
+        audit: crate::config_sections::AuditConfig::default(),
         admission_blocks: Vec::new(),
         iam_mode: crate::config_sections::IamMode::default(),
         iam_users: Vec::new(),
@@ -4,9 +4,11 @@
 //!
 //! This library provides the core functionality for the DeltaGlider Proxy S3 server.
 
+pub mod access_events;
 pub mod admission;
 pub mod api;
 pub mod audit;
+pub mod audit_store;
 pub(crate) mod background;
 pub mod bucket_policy;
 pub mod bucket_usage;
@@ -20,7 +22,9 @@ pub mod coordination;
 pub mod cors;
 pub mod deltaglider;
 pub mod event_delivery;
+pub mod event_endpoints;
 pub mod event_outbox;
+pub mod event_sinks;
 pub mod iam;
 pub mod init;
 pub mod job_loop;
@@ -30,7 +34,9 @@ pub mod maintenance;
 pub mod metadata_cache;
 pub mod metrics;
 pub mod multipart;
+pub mod multipart_registry;
 pub mod rate_limiter;
+pub mod read_fallback;
 pub mod replication;
 pub mod s3_adapter_s3s;
 pub mod secret;
@@ -43,3 +49,4 @@ pub(crate) mod transfer;
 pub mod transfer_plan;
 pub mod types;
 pub mod usage_scanner;
+pub mod webhook_signature;
@@ -785,9 +785,12 @@ async fn execute_action(
                         metadata_key: LIFECYCLE_RULE_METADATA_KEY,
                         metadata_value: &rule.name,
                     }),
+                    extra_user_metadata: &[],
                     strip_user_metadata_keys: &[],
                     operation: "lifecycle transition",
                     upload_concurrency: None,
+                    bandwidth: None,
+                    source_engine: None,
                 },
             )
             .await?;
@@ -15,7 +15,6 @@ use deltaglider_proxy::config::{env_parse_with_default, Config};
 use deltaglider_proxy::deltaglider::DynEngine;
 use deltaglider_proxy::multipart::MultipartStore;
 use deltaglider_proxy::rate_limiter::RateLimiter;
-use deltaglider_proxy::session::SessionStore;
 use deltaglider_proxy::usage_scanner::UsageScanner;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -97,11 +96,28 @@ enum Command {
         #[command(subcommand)]
         action: AdmissionCommand,
     },
+    /// IAM tooling (simulate, ...).
+    Iam {
+        #[command(subcommand)]
+        action: IamCommand,
+    },
     /// AWS-CLI-shaped S3 client commands. See `s3 --help`.
     S3 {
         #[command(subcommand)]
         action: S3Command,
     },
+    /// Offline tooling for a backend's on-disk data (migrate-metadata, ...).
+    Storage {
+        #[command(subcommand)]
+        action: StorageCommand,
+    },
+}
+
+/// Verbs that work on a stopped backend's data directly.
+#[derive(Subcommand, Debug)]
+enum StorageCommand {
+    /// Move a filesystem backend's metadata between xattrs and sidecar files.
+    MigrateMetadata(deltaglider_proxy::cli::fs_metadata::MigrateMetadataArgs),
 }
 
 /// AWS-CLI-shaped client verbs. Each runs in-process against a remote
@@ -203,6 +219,58 @@ enum AdmissionCommand {
         /// Optional query string (e.g. `prefix=releases/`).
         #[arg(long, value_name = "QUERY")]
         query: Option<String>,
+        /// Access key the request is signed with; reports matching
+        /// `throttle` blocks.
+        #[arg(long, value_name = "KEY")]
+        access_key: Option<String>,
+        /// Server URL. Defaults to http://127.0.0.1:9000.
+        #[arg(long, value_name = "URL")]
+        server: Option<String>,
+        /// Per-request timeout in seconds. Defaults to 30.
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum IamCommand {
+    /// Explain whether a principal may perform an action on a resource.
+    ///
+    /// Emits the decision and the deciding permission rules as JSON on
+    /// stdout. Requires the admin bootstrap password in
+    /// `DGP_BOOTSTRAP_PASSWORD`.
+    #[command(group(clap::ArgGroup::new("principal").required(true)))]
+    Simulate {
+        /// Simulate a user (name or access key ID) or a service account (access key ID).
+        #[arg(long, value_name = "USER", group = "principal")]
+        user: Option<String>,
+        /// Simulate a member of a stored group with no direct rules.
+        #[arg(long, value_name = "GROUP", group = "principal")]
+        group: Option<String>,
+        /// Simulate a hypothetical rule list (JSON or YAML file).
+        #[arg(long, value_name = "FILE", group = "principal")]
+        permissions_file: Option<String>,
+        /// Verb (`read`, `write`, ...) or operation (`s3:PutObjectTagging`).
+        #[arg(long, value_name = "ACTION")]
+        action: String,
+        /// Target bucket.
+        #[arg(long, value_name = "BUCKET")]
+        bucket: String,
+        /// Object key; omit for bucket-level operations.
+        #[arg(long, value_name = "KEY")]
+        key: Option<String>,
+        /// `aws:SourceIp` for the request.
+        #[arg(long, value_name = "IP")]
+        source_ip: Option<String>,
+        /// `s3:prefix` for list operations.
+        #[arg(long, value_name = "PREFIX")]
+        prefix: Option<String>,
+        /// Set `aws:SecureTransport` (request arrived over TLS).
+        #[arg(long)]
+        secure_transport: bool,
+        /// Request sends `x-amz-bypass-governance-retention: true`.
+        #[arg(long)]
+        governance_bypass: bool,
         /// Server URL. Defaults to http://127.0.0.1:9000.
         #[arg(long, value_name = "URL")]
         server: Option<String>,
@@ -218,7 +286,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Subcommand dispatch (runs synchronously, exits before tokio runtime).
     if let Some(ref cmd) = cli.command {
         use deltaglider_proxy::cli::config::{
-            admission_trace, apply, defaults, lint, schema, AdminClientOpts, TraceArgs,
+            admission_trace, apply, defaults, iam_simulate, lint, schema, AdminClientOpts,
+            SimulateArgs, TraceArgs,
         };
         let code = match cmd {
             Command::Config { action } => match action {
@@ -246,6 +315,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     path,
                     authenticated,
                     query,
+                    access_key,
                     server,
                     timeout,
                 } => {
@@ -262,6 +332,46 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             path: path.clone(),
                             authenticated: *authenticated,
                             query: query.clone(),
+                            access_key: access_key.clone(),
+                        },
+                        opts,
+                    )
+                }
+            },
+            Command::Iam { action } => match action {
+                IamCommand::Simulate {
+                    user,
+                    group,
+                    permissions_file,
+                    action,
+                    bucket,
+                    key,
+                    source_ip,
+                    prefix,
+                    secure_transport,
+                    governance_bypass,
+                    server,
+                    timeout,
+                } => {
+                    let mut opts = AdminClientOpts::default();
+                    if let Some(s) = server.as_deref() {
+                        opts.server = s.to_string();
+                    }
+                    if let Some(t) = timeout {
+                        opts.timeout_secs = *t;
+                    }
+                    iam_simulate(
+                        SimulateArgs {
+                            user: user.clone(),
+                            group: group.clone(),
+                            permissions_file: permissions_file.clone(),
+                            action: action.clone(),
+                            bucket: bucket.clone(),
+                            key: key.clone(),
+                            source_ip: source_ip.clone(),
+                            prefix: prefix.clone(),
+                            secure_transport: *secure_transport,
+                            governance_bypass: *governance_bypass,
                         },
                         opts,
                     )
@@ -296,6 +406,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     run_cli_async(deltaglider_proxy::cli::purge::run(args.clone()))
                 }
             },
+            Command::Storage { action } => match action {
+                StorageCommand::MigrateMetadata(args) => {
+                    run_cli_async(deltaglider_proxy::cli::fs_metadata::run(args.clone()))
+                }
+            },
         };
         std::process::exit(code);
     }
@@ -647,10 +762,18 @@ async fn async_main(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
 
     // --- Admin / sessions / config DB (must be before S3 router for mismatch guard) ---
     let admin_password_hash = config.ensure_bootstrap_password_hash();
-    let session_store = Arc::new(SessionStore::new());
-    spawn_periodic(Duration::from_secs(300), {
+    let session_store = Arc::new(build_session_store(&config, &admin_password_hash).await);
+    // Session sweep: drops expired sessions and, with a shared store, applies
+    // the cluster-wide session cap (kept off the login path).
+    tokio::spawn({
         let sessions = session_store.clone();
-        move || sessions.cleanup_expired()
+        async move {
+            let mut tick = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                tick.tick().await;
+                sessions.cleanup_expired().await;
+            }
+        }
     });
     let shared_config = config.clone().into_shared();
     let (config_db, config_db_mismatch) = init_config_db(&admin_password_hash, &iam_state, &config);
@@ -683,6 +806,29 @@ async fn async_main(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     let backend_health = Arc::new(deltaglider_proxy::coordination::BackendHealthCache::default());
     startup::boot_backend_health_gate(&config, &backend_health).await;
 
+    // Durable multipart registry: needs the config DB (single-instance), so
+    // it's attached here rather than at engine construction.
+    let upload_registry = startup::build_upload_registry(&config, config_db.as_ref()).await;
+    let engine = engine.with_upload_registry(upload_registry.clone());
+
+    if let Some(db) = config_db.as_ref() {
+        deltaglider_proxy::audit_store::spawn_writer(
+            shared_config.clone(),
+            db.clone(),
+            metrics.clone(),
+        );
+        deltaglider_proxy::replication::mirror_repair::install_journal(db.clone());
+    }
+
+    let access_events = match config_db.as_ref() {
+        Some(db) => deltaglider_proxy::access_events::AccessEventRecorder::spawn(
+            shared_config.clone(),
+            db.clone(),
+            metrics.clone(),
+        ),
+        None => deltaglider_proxy::access_events::AccessEventRecorder::disabled(),
+    };
+
     let state = Arc::new(AppState {
         engine: ArcSwap::from_pointee(engine),
         multipart,
@@ -690,11 +836,13 @@ async fn async_main(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         usage_scanner: usage_scanner.clone(),
         bucket_usage: bucket_usage.clone(),
         reference_lock: reference_lock.clone(),
+        upload_registry,
         config_db: config_db.clone(),
         maintenance_gate: maintenance_gate.clone(),
         maintenance_notify: maintenance_notify.clone(),
         backend_capabilities: backend_capabilities.clone(),
         backend_health: backend_health.clone(),
+        access_events,
     });
 
     // Re-arm the maintenance write-gate for jobs that survived a restart
@@ -809,7 +957,18 @@ async fn async_main(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
                 db.clone(),
                 state.clone(),
             );
-            deltaglider_proxy::event_delivery::spawn_dispatcher(shared_config.clone(), db.clone());
+            deltaglider_proxy::event_delivery::spawn_dispatcher(
+                shared_config.clone(),
+                db.clone(),
+                Some(metrics.clone()),
+            );
+            // Durable audit store: seals entries into archive segments and
+            // applies retention (the writer itself starts with AppState).
+            deltaglider_proxy::audit_store::spawn_archiver(
+                shared_config.clone(),
+                db.clone(),
+                metrics.clone(),
+            );
         }
     }
 
@@ -890,6 +1049,69 @@ async fn async_main(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
+    // Read-fallback replay: writes queued against a replica while its
+    // primary was down are copied back once the primary is healthy again.
+    spawn_periodic(Duration::from_secs(30), {
+        let state = state.clone();
+        move || {
+            let state = state.clone();
+            tokio::spawn(async move {
+                deltaglider_proxy::read_fallback::drain_once(&state).await;
+            });
+        }
+    });
+
+    // Mirror repair: copies of mirrored buckets that missed a write are
+    // queued through the outbox and brought back in line from the other copy.
+    spawn_periodic(Duration::from_secs(10), {
+        let state = state.clone();
+        move || {
+            let state = state.clone();
+            tokio::spawn(async move {
+                deltaglider_proxy::replication::mirror_repair::repair_once(&state).await;
+            });
+        }
+    });
+
+    // Staged multipart janitor: client uploads that survive restarts
+    // (filesystem staging, the durable registry) are aborted once idle for
+    // the TTL.
+    let multipart_staged_ttl = Duration::from_secs(
+        env_parse_with_default("DGP_MULTIPART_STAGED_TTL_HOURS", 24u64).max(1) * 3600,
+    );
+    spawn_periodic(multipart_sweep_interval, {
+        let state = state.clone();
+        move || {
+            let state = state.clone();
+            tokio::spawn(async move {
+                let engine = state.engine.load();
+                match engine
+                    .sweep_staged_multipart_uploads(multipart_staged_ttl)
+                    .await
+                {
+                    Ok(report) => {
+                        state
+                            .metrics
+                            .multipart_swept_uploads_total
+                            .with_label_values(&["staged"])
+                            .inc_by(report.uploads);
+                        state
+                            .metrics
+                            .multipart_sweep_reclaimed_bytes_total
+                            .inc_by(report.bytes);
+                        if report.uploads > 0 {
+                            info!(
+                                "multipart janitor aborted {} idle staged upload(s), {} bytes",
+                                report.uploads, report.bytes
+                            );
+                        }
+                    }
+                    Err(e) => tracing::warn!("multipart janitor failed: {e}"),
+                }
+            });
+        }
+    });
+
     // --- External auth (OAuth/OIDC) ---
     let external_auth = {
         use deltaglider_proxy::iam::external_auth::ExternalAuthManager;
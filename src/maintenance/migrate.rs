@@ -337,9 +337,12 @@ async fn run_phases(
                         metadata_key: "dg-migration",
                         metadata_value: &provenance_value,
                     }),
+                    extra_user_metadata: &[],
                     strip_user_metadata_keys: &[],
                     operation: "migrate",
                     upload_concurrency: None,
+                    bandwidth: None,
+                    source_engine: None,
                 };
                 match copy_object_with_retries(&engine, req).await {
                     Ok(outcome) => {
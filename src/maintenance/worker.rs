@@ -321,9 +321,12 @@ async fn execute_phases(
                     provenance: None,
                     // Shed stale markers; the encrypting wrapper re-stamps
                     // fresh ones when the destination mode encrypts.
+                    extra_user_metadata: &[],
                     strip_user_metadata_keys: &[ENCRYPTION_MARKER_KEY, ENCRYPTION_KEY_ID_KEY],
                     operation: "maintenance-reencrypt",
                     upload_concurrency: None,
+                    bandwidth: None,
+                    source_engine: None,
                 };
                 match copy_object_with_retries(&engine, req).await {
                     Ok(outcome) => {
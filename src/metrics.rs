@@ -11,8 +11,8 @@ use axum::http::{Request, StatusCode};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 use prometheus::{
-    Encoder, Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec,
-    IntGauge, Opts, Registry, TextEncoder, TEXT_FORMAT,
+    CounterVec, Encoder, Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounter,
+    IntCounterVec, IntGauge, Opts, Registry, TextEncoder, TEXT_FORMAT,
 };
 use std::sync::Arc;
 use std::time::Instant;
@@ -51,6 +51,39 @@ pub struct Metrics {
     pub cache_utilization_ratio: Gauge,
     pub cache_miss_rate_ratio: Gauge,
 
+    // -- Disk cache (read-through object cache) --
+    pub disk_cache_hits_total: IntCounterVec,
+    pub disk_cache_misses_total: IntCounterVec,
+    pub disk_cache_evictions_total: IntCounter,
+    pub disk_cache_fill_bytes_total: IntCounter,
+    pub disk_cache_size_bytes: Gauge,
+    pub disk_cache_max_bytes: Gauge,
+    pub disk_cache_hit_ratio: Gauge,
+
+    // -- Read fallback (replica reads while a backend is down) --
+    pub read_fallback_reads_total: IntCounterVec,
+    pub read_fallback_writes_queued_total: IntCounter,
+    pub read_fallback_writes_replayed_total: IntCounter,
+    pub read_fallback_queue_depth: Gauge,
+
+    // -- Write-through mirrors --
+    pub mirror_degraded_writes_total: IntCounter,
+    pub mirror_read_failovers_total: IntCounter,
+    pub mirror_repairs_total: IntCounterVec,
+
+    // -- Capacity spans --
+    pub span_placements_total: IntCounterVec,
+    pub span_member_usage_bytes: GaugeVec,
+
+    // -- Erasure coding --
+    pub erasure_degraded_reads_total: IntCounterVec,
+    pub erasure_degraded_writes_total: IntCounterVec,
+    pub erasure_shards_repaired_total: IntCounterVec,
+
+    // -- Hedged reads --
+    pub backend_first_byte_seconds: HistogramVec,
+    pub hedged_reads_total: IntCounterVec,
+
     // -- Codec Concurrency --
     pub codec_semaphore_available: Gauge,
 
@@ -58,6 +91,11 @@ pub struct Metrics {
     pub auth_attempts_total: IntCounterVec,
     pub auth_failures_total: IntCounterVec,
 
+    // -- Admission throttles --
+    pub admission_throttle_rejections_total: IntCounterVec,
+    pub admission_throttle_wait_seconds_total: CounterVec,
+    pub admission_throttle_bytes_total: IntCounterVec,
+
     // -- Multipart Sweep --
     pub multipart_sweep_runs_total: IntCounterVec,
     pub multipart_sweep_duration_seconds: HistogramVec,
@@ -91,6 +129,17 @@ pub struct Metrics {
     pub replication_list_calls_total: IntCounter,
     pub replication_head_calls_total: IntCounter,
     pub replication_dirs_completed_total: IntCounter,
+
+    // -- Event outbox message-broker sinks --
+    pub event_sink_publish_total: IntCounterVec,
+    pub event_sink_publish_duration_seconds: HistogramVec,
+
+    // -- Read and security events (event_delivery.access_events) --
+    pub access_events_total: IntCounterVec,
+
+    // -- Durable audit store (advanced.audit) --
+    pub audit_entries_total: IntCounterVec,
+    pub audit_segments_total: IntCounterVec,
 }
 
 /// Set `peak` to `live`'s current value when it has risen above the prior
@@ -316,6 +365,220 @@ impl Metrics {
             .unwrap()
         );
 
+        // -- Disk cache --
+        let disk_cache_hits_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_disk_cache_hits_total",
+                    "Backend reads served entirely from the disk cache, by object kind",
+                ),
+                &["kind"],
+            )
+            .unwrap()
+        );
+        let disk_cache_misses_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_disk_cache_misses_total",
+                    "Backend reads that had to fetch from the backend, by object kind",
+                ),
+                &["kind"],
+            )
+            .unwrap()
+        );
+        let disk_cache_evictions_total = register!(
+            registry,
+            IntCounter::new(
+                "deltaglider_disk_cache_evictions_total",
+                "Disk cache blocks evicted by the size budget",
+            )
+            .unwrap()
+        );
+        let disk_cache_fill_bytes_total = register!(
+            registry,
+            IntCounter::new(
+                "deltaglider_disk_cache_fill_bytes_total",
+                "Bytes written into the disk cache from backend reads",
+            )
+            .unwrap()
+        );
+        let disk_cache_size_bytes = register!(
+            registry,
+            Gauge::new(
+                "deltaglider_disk_cache_size_bytes",
+                "Bytes currently held by the disk cache (updated on scrape)",
+            )
+            .unwrap()
+        );
+        let disk_cache_max_bytes = register!(
+            registry,
+            Gauge::new(
+                "deltaglider_disk_cache_max_bytes",
+                "Configured disk cache budget in bytes (0 when disabled)",
+            )
+            .unwrap()
+        );
+        let disk_cache_hit_ratio = register!(
+            registry,
+            Gauge::new(
+                "deltaglider_disk_cache_hit_ratio",
+                "Disk cache hit ratio since startup (hits / total, 0.0-1.0)",
+            )
+            .unwrap()
+        );
+
+        // -- Read fallback --
+        let read_fallback_reads_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_read_fallback_reads_total",
+                    "Reads served from a replica bucket instead of the primary, by reason",
+                ),
+                &["reason"],
+            )
+            .unwrap()
+        );
+        let read_fallback_writes_queued_total = register!(
+            registry,
+            IntCounter::new(
+                "deltaglider_read_fallback_writes_queued_total",
+                "Writes accepted onto a replica while the primary backend was down",
+            )
+            .unwrap()
+        );
+        let read_fallback_writes_replayed_total = register!(
+            registry,
+            IntCounter::new(
+                "deltaglider_read_fallback_writes_replayed_total",
+                "Queued writes replayed onto the recovered primary",
+            )
+            .unwrap()
+        );
+        let read_fallback_queue_depth = register!(
+            registry,
+            Gauge::new(
+                "deltaglider_read_fallback_queue_depth",
+                "Keys waiting to be replayed onto a recovered primary",
+            )
+            .unwrap()
+        );
+
+        // -- Write-through mirrors --
+        let mirror_degraded_writes_total = register!(
+            registry,
+            IntCounter::new(
+                "deltaglider_mirror_degraded_writes_total",
+                "Mirrored-bucket writes that only one copy accepted",
+            )
+            .unwrap()
+        );
+        let mirror_read_failovers_total = register!(
+            registry,
+            IntCounter::new(
+                "deltaglider_mirror_read_failovers_total",
+                "Mirrored-bucket reads answered by the second copy after the first failed",
+            )
+            .unwrap()
+        );
+        let mirror_repairs_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_mirror_repairs_total",
+                    "Lagging mirror copies repaired from the outbox, by outcome",
+                ),
+                &["outcome"],
+            )
+            .unwrap()
+        );
+
+        // -- Capacity spans --
+        let span_placements_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_span_placements_total",
+                    "New deltaspaces of spanned buckets placed, by member backend",
+                ),
+                &["bucket", "backend"],
+            )
+            .unwrap()
+        );
+        let span_member_usage_bytes = register!(
+            registry,
+            GaugeVec::new(
+                Opts::new(
+                    "deltaglider_span_member_usage_bytes",
+                    "Bytes a spanned bucket holds on each member backend (last measured)",
+                ),
+                &["bucket", "backend"],
+            )
+            .unwrap()
+        );
+
+        // -- Erasure coding --
+        let erasure_degraded_reads_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_erasure_degraded_reads_total",
+                    "Erasure-coded reads rebuilt from parity shards, by erasure backend",
+                ),
+                &["backend"],
+            )
+            .unwrap()
+        );
+        let erasure_degraded_writes_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_erasure_degraded_writes_total",
+                    "Erasure-coded writes acknowledged without every shard, by erasure backend",
+                ),
+                &["backend"],
+            )
+            .unwrap()
+        );
+        let erasure_shards_repaired_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_erasure_shards_repaired_total",
+                    "Missing or stale shards rewritten by erasure repair, by erasure backend",
+                ),
+                &["backend"],
+            )
+            .unwrap()
+        );
+
+        // -- Hedged reads --
+        let backend_first_byte_seconds = register!(
+            registry,
+            HistogramVec::new(
+                HistogramOpts::new(
+                    "deltaglider_backend_first_byte_seconds",
+                    "Time to first byte of passthrough reads on hedged buckets, by backend",
+                )
+                .buckets(prometheus::exponential_buckets(0.005, 2.0, 12).unwrap()),
+                &["backend"],
+            )
+            .unwrap()
+        );
+        let hedged_reads_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_hedged_reads_total",
+                    "Hedged reads that went to the second copy, by which copy answered first",
+                ),
+                &["winner"],
+            )
+            .unwrap()
+        );
+
         // -- Codec Concurrency --
         let codec_semaphore_available = register!(
             registry,
@@ -343,6 +606,41 @@ impl Metrics {
             )
             .unwrap()
         );
+
+        // -- Admission throttles --
+        let admission_throttle_rejections_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_admission_throttle_rejections_total",
+                    "Requests rejected with SlowDown by a throttle block",
+                ),
+                &["block"],
+            )
+            .unwrap()
+        );
+        let admission_throttle_wait_seconds_total = register!(
+            registry,
+            CounterVec::new(
+                Opts::new(
+                    "deltaglider_admission_throttle_wait_seconds_total",
+                    "Time body transfers were held back by a throttle block's byte rate",
+                ),
+                &["block"],
+            )
+            .unwrap()
+        );
+        let admission_throttle_bytes_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_admission_throttle_bytes_total",
+                    "Body bytes metered by a throttle block's byte rate",
+                ),
+                &["block"],
+            )
+            .unwrap()
+        );
         // -- Multipart Sweep --
         let multipart_sweep_runs_total = register!(
             registry,
@@ -532,6 +830,67 @@ impl Metrics {
             .unwrap()
         );
 
+        // -- Event outbox message-broker sinks --
+        let event_sink_publish_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_event_sink_publish_total",
+                    "Outbox event publishes to message-broker sinks by sink and outcome",
+                ),
+                &["sink", "outcome"],
+            )
+            .unwrap()
+        );
+        let event_sink_publish_duration_seconds = register!(
+            registry,
+            HistogramVec::new(
+                HistogramOpts::new(
+                    "deltaglider_event_sink_publish_duration_seconds",
+                    "Time until a message-broker sink acknowledged (or failed) a publish",
+                ),
+                &["sink"],
+            )
+            .unwrap()
+        );
+
+        // -- Read and security events --
+        let access_events_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_access_events_total",
+                    "Read and security events by kind and outcome (recorded, or dropped when the writer fell behind)",
+                ),
+                &["kind", "outcome"],
+            )
+            .unwrap()
+        );
+
+        // -- Durable audit store --
+        let audit_entries_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_audit_entries_total",
+                    "Audit entries offered to the durable store, by outcome (stored, dropped when the writer fell behind, failed)",
+                ),
+                &["outcome"],
+            )
+            .unwrap()
+        );
+        let audit_segments_total = register!(
+            registry,
+            IntCounterVec::new(
+                Opts::new(
+                    "deltaglider_audit_segments_total",
+                    "Audit archive segments by outcome (uploaded, failed)",
+                ),
+                &["outcome"],
+            )
+            .unwrap()
+        );
+
         // Delegated-listing request-amplification counters (issue #82). These
         // live as statics in `storage::s3` (the backend has no Metrics handle);
         // registering the clones here puts them on the same /_/metrics scrape.
@@ -567,9 +926,33 @@ impl Metrics {
             cache_max_bytes,
             cache_utilization_ratio,
             cache_miss_rate_ratio,
+            disk_cache_hits_total,
+            disk_cache_misses_total,
+            disk_cache_evictions_total,
+            disk_cache_fill_bytes_total,
+            disk_cache_size_bytes,
+            disk_cache_max_bytes,
+            disk_cache_hit_ratio,
+            read_fallback_reads_total,
+            read_fallback_writes_queued_total,
+            read_fallback_writes_replayed_total,
+            read_fallback_queue_depth,
+            mirror_degraded_writes_total,
+            mirror_read_failovers_total,
+            mirror_repairs_total,
+            span_placements_total,
+            span_member_usage_bytes,
+            erasure_degraded_reads_total,
+            erasure_degraded_writes_total,
+            erasure_shards_repaired_total,
+            backend_first_byte_seconds,
+            hedged_reads_total,
             codec_semaphore_available,
             auth_attempts_total,
             auth_failures_total,
+            admission_throttle_rejections_total,
+            admission_throttle_wait_seconds_total,
+            admission_throttle_bytes_total,
             multipart_sweep_runs_total,
             multipart_sweep_duration_seconds,
             multipart_swept_uploads_total,
@@ -592,6 +975,11 @@ impl Metrics {
             replication_list_calls_total,
             replication_head_calls_total,
             replication_dirs_completed_total,
+            event_sink_publish_total,
+            event_sink_publish_duration_seconds,
+            access_events_total,
+            audit_entries_total,
+            audit_segments_total,
         }
     }
 }
@@ -693,6 +1081,16 @@ pub async fn http_metrics_middleware(
     response
 }
 
+/// Sum every label combination of a counter vec (for derived ratio gauges).
+fn sum_counter_vec(vec: &IntCounterVec) -> u64 {
+    use prometheus::core::Collector;
+    vec.collect()
+        .iter()
+        .flat_map(|family| family.get_metric())
+        .map(|m| m.get_counter().get_value() as u64)
+        .sum()
+}
+
 /// Handler for GET /metrics — returns Prometheus text format.
 pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     let metrics = &state.metrics;
@@ -719,6 +1117,24 @@ pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoRes
     if total > 0.0 {
         metrics.cache_miss_rate_ratio.set(misses / total);
     }
+    match engine.disk_cache() {
+        Some(cache) => {
+            let stats = cache.stats();
+            metrics.disk_cache_size_bytes.set(stats.used_bytes as f64);
+            metrics.disk_cache_max_bytes.set(stats.max_bytes as f64);
+        }
+        None => {
+            metrics.disk_cache_size_bytes.set(0.0);
+            metrics.disk_cache_max_bytes.set(0.0);
+        }
+    }
+    let disk_hits: u64 = sum_counter_vec(&metrics.disk_cache_hits_total);
+    let disk_misses: u64 = sum_counter_vec(&metrics.disk_cache_misses_total);
+    if disk_hits + disk_misses > 0 {
+        metrics
+            .disk_cache_hit_ratio
+            .set(disk_hits as f64 / (disk_hits + disk_misses) as f64);
+    }
     metrics
         .codec_semaphore_available
         .set(engine.codec_available_permits() as f64);
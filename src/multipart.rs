@@ -800,6 +800,16 @@ impl MultipartStore {
         });
     }
 
+    /// Is a CompleteMultipartUpload for `upload_id` running right now? The
+    /// registry is keyed by id alone, so this also covers uploads staged on
+    /// the storage backend, which this store otherwise doesn't track.
+    pub fn completion_in_flight(&self, upload_id: &str) -> bool {
+        matches!(
+            self.completions.lock().get(upload_id),
+            Some(CompletionSlot::InFlight { .. })
+        )
+    }
+
     /// Clear an upload's completion slot (publisher Drop path).
     fn clear_completion_slot(&self, upload_id: &str) {
         self.completions.lock().remove(upload_id);
@@ -1077,12 +1087,9 @@ impl MultipartStore {
             return Err(S3Error::NoSuchUpload(upload_id.to_string()));
         }
 
-        let cap = max_parts.clamp(1, 10_000) as usize;
-
-        let mut all: Vec<PartInfo> = upload
+        let all = upload
             .parts
             .iter()
-            .filter(|(&num, _)| num > part_number_marker)
             .map(|(&num, pd)| PartInfo {
                 part_number: num,
                 etag: format!("\"{}\"", pd.md5_hex),
@@ -1090,14 +1097,7 @@ impl MultipartStore {
                 last_modified: pd.uploaded_at,
             })
             .collect();
-        all.sort_by_key(|p| p.part_number);
-
-        let is_truncated = all.len() > cap;
-        if is_truncated {
-            all.truncate(cap);
-        }
-        let next_marker = all.last().map(|p| p.part_number).unwrap_or(0);
-        Ok((all, is_truncated, next_marker))
+        Ok(paginate_parts(all, part_number_marker, max_parts))
     }
 
     /// Paginated ListMultipartUploads (L1 correctness fix).
@@ -1114,17 +1114,41 @@ impl MultipartStore {
         key_marker: &str,
         upload_id_marker: &str,
         max_uploads: u32,
+    ) -> (Vec<UploadInfo>, bool, String, String) {
+        self.list_uploads_paginated_with(
+            bucket,
+            Vec::new(),
+            prefix,
+            key_marker,
+            upload_id_marker,
+            max_uploads,
+        )
+    }
+
+    /// [`Self::list_uploads_paginated`] over this store's uploads plus
+    /// `staged` — uploads of `bucket` persisted by its storage backend — so
+    /// both kinds page through one cursor.
+    pub fn list_uploads_paginated_with(
+        &self,
+        bucket: Option<&str>,
+        staged: Vec<UploadInfo>,
+        prefix: Option<&str>,
+        key_marker: &str,
+        upload_id_marker: &str,
+        max_uploads: u32,
     ) -> (Vec<UploadInfo>, bool, String, String) {
         let uploads = self.uploads.read();
         let cap = max_uploads.clamp(1, 1000) as usize;
         let mut filtered: Vec<UploadInfo> = uploads
             .values()
+            .filter(|u| bucket.is_none_or(|b| u.bucket == b))
+            .map(|u| UploadInfo {
+                key: u.key.clone(),
+                upload_id: u.upload_id.clone(),
+                initiated: u.created_at,
+            })
+            .chain(staged)
             .filter(|u| {
-                if let Some(b) = bucket {
-                    if u.bucket != b {
-                        return false;
-                    }
-                }
                 if let Some(p) = prefix {
                     if !u.key.starts_with(p) {
                         return false;
@@ -1140,11 +1164,6 @@ impl MultipartStore {
                 }
                 true
             })
-            .map(|u| UploadInfo {
-                key: u.key.clone(),
-                upload_id: u.upload_id.clone(),
-                initiated: u.created_at,
-            })
             .collect();
         filtered.sort_by(|a, b| a.key.cmp(&b.key).then(a.upload_id.cmp(&b.upload_id)));
 
@@ -1277,6 +1296,12 @@ impl MultipartStore {
         self.uploads.read().len()
     }
 
+    /// Is `upload_id` an upload of this store (Open or Completing)? Ids it
+    /// doesn't know may belong to an upload staged on the storage backend.
+    pub fn contains(&self, upload_id: &str) -> bool {
+        self.uploads.read().contains_key(upload_id)
+    }
+
     fn promote_upload_to_relay(upload: &mut MultipartUpload) -> Result<(), S3Error> {
         let relay_dir = relay_dir_for_upload(&upload.upload_id);
         fs::create_dir_all(&relay_dir).map_err(|e| {
@@ -1294,6 +1319,81 @@ impl MultipartStore {
     }
 }
 
+/// One page of `parts` after `part_number_marker`, in part-number order.
+/// Returns `(page, is_truncated, next_part_number_marker)`; `max_parts` is
+/// clamped to 1..=10_000.
+pub fn paginate_parts(
+    mut parts: Vec<PartInfo>,
+    part_number_marker: u32,
+    max_parts: u32,
+) -> (Vec<PartInfo>, bool, u32) {
+    let cap = max_parts.clamp(1, 10_000) as usize;
+    parts.retain(|p| p.part_number > part_number_marker);
+    parts.sort_by_key(|p| p.part_number);
+
+    let is_truncated = parts.len() > cap;
+    if is_truncated {
+        parts.truncate(cap);
+    }
+    let next_marker = parts.last().map(|p| p.part_number).unwrap_or(0);
+    (parts, is_truncated, next_marker)
+}
+
+/// A CompleteMultipartUpload part list checked against the parts staged on
+/// the storage backend — the rules [`MultipartStore`] applies to buffered
+/// uploads. `uploaded` is the backend's part listing as
+/// `(part_number, etag, size)`. Returns the total size and the quoted
+/// S3 multipart ETag.
+pub fn validate_staged_parts(
+    uploaded: &[(u32, String, u64)],
+    requested_parts: &[(u32, String)],
+    max_object_size: u64,
+) -> Result<(u64, String), S3Error> {
+    if requested_parts.is_empty() {
+        return Err(S3Error::InvalidPart(
+            "You must specify at least one part".to_string(),
+        ));
+    }
+    for window in requested_parts.windows(2) {
+        if window[0].0 >= window[1].0 {
+            return Err(S3Error::InvalidPartOrder);
+        }
+    }
+
+    let mut total_size: u64 = 0;
+    let mut md5_concat = Vec::new();
+    for (part_number, requested_etag) in requested_parts {
+        let (_, etag, size) = uploaded
+            .iter()
+            .find(|(n, _, _)| n == part_number)
+            .ok_or_else(|| {
+                S3Error::InvalidPart(format!("Part {} has not been uploaded", part_number))
+            })?;
+        let md5_hex = etag.trim_matches('"');
+        let requested_clean = requested_etag.trim_matches('"');
+        if requested_clean != md5_hex {
+            return Err(S3Error::InvalidPart(format!(
+                "ETag mismatch for part {}: expected \"{}\", got \"{}\"",
+                part_number, md5_hex, requested_clean
+            )));
+        }
+        total_size += size;
+        if total_size > max_object_size {
+            return Err(S3Error::InvalidArgument(format!(
+                "Assembled object size {} exceeds maximum {}",
+                total_size, max_object_size
+            )));
+        }
+        let raw = hex::decode(md5_hex)
+            .map_err(|_| S3Error::InvalidPart(format!("Part {} has no MD5 ETag", part_number)))?;
+        md5_concat.extend_from_slice(&raw);
+    }
+
+    let final_md5 = Md5::digest(&md5_concat);
+    let etag = format!("\"{}-{}\"", hex::encode(final_md5), requested_parts.len());
+    Ok((total_size, etag))
+}
+
 /// Parent of all per-process relay roots on this host.
 fn relay_parent_dir() -> PathBuf {
     std::env::temp_dir().join(RELAY_ROOT_DIR)
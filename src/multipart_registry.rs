@@ -0,0 +1,800 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Durable registry of client multipart uploads.
+//!
+//! Without it, an upload lives in the [`crate::multipart::MultipartStore`]
+//! of the node that created it and is lost on restart. With a registry
+//! attached to the engine (`DGP_MULTIPART_STORE=durable`, the default when a
+//! coordination bucket is configured), the upload's manifest (bucket, key,
+//! content type, user metadata) and part list live here, and each part's
+//! bytes are staged on the bucket's own backend under
+//! `.deltaglider/uploads/<upload-id>/`. Any node — or this one after a
+//! restart — can then list, complete or abort the upload. Two backing
+//! stores, selected once at startup:
+//!   - [`ConfigDbUploadRegistry`] — tables in the node's ConfigDb. Survives
+//!     restarts; single-instance.
+//!   - [`S3UploadRegistry`] — objects under `_dgp/multipart/` in the
+//!     coordination bucket, visible to every node.
+//!
+//! Buckets whose backend stages multipart uploads itself (filesystem, see
+//! `StorageBackend::persists_multipart_uploads`) never consult the registry.
+//!
+//! Errors are `String`s, like [`crate::coordination::CoordinationLease`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use chrono::{DateTime, TimeZone, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::config_db::{ConfigDb, ConfigDbError};
+
+/// Deltaspace (bucket-relative prefix) holding the staged parts of every
+/// registry-tracked upload of a bucket.
+pub const STAGING_PREFIX: &str = ".deltaglider/uploads";
+
+/// Coordination-bucket prefix of the [`S3UploadRegistry`].
+const REGISTRY_PREFIX: &str = "_dgp/multipart/";
+
+/// How long a completion claim holds before another request may take it
+/// over: a node that died mid-completion must not wedge the upload forever.
+const COMPLETION_CLAIM_TTL: chrono::Duration = chrono::Duration::hours(1);
+
+/// Where the parts of `upload_id` are staged on the bucket's backend.
+pub fn staging_prefix(upload_id: &str) -> String {
+    format!("{STAGING_PREFIX}/{upload_id}")
+}
+
+/// File name of one staged part under [`staging_prefix`].
+pub fn part_filename(part_number: u32) -> String {
+    format!("part-{part_number:05}")
+}
+
+/// A fresh upload id: 32 random hex chars, like the in-memory store's.
+pub fn new_upload_id() -> String {
+    use rand::Rng;
+    let mut random_bytes = [0u8; 16];
+    rand::rngs::OsRng.fill(&mut random_bytes);
+    hex::encode(random_bytes)
+}
+
+/// Could `upload_id` be one of [`new_upload_id`]'s (32 lowercase hex
+/// chars)? Client-supplied ids become storage and registry paths, so
+/// anything else is never looked up.
+pub fn is_valid_upload_id(upload_id: &str) -> bool {
+    upload_id.len() == 32
+        && upload_id
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// What CreateMultipartUpload recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadManifest {
+    pub upload_id: String,
+    pub bucket: String,
+    pub key: String,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub user_metadata: HashMap<String, String>,
+    pub initiated: DateTime<Utc>,
+}
+
+/// One staged part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestPart {
+    pub part_number: u32,
+    /// Bare MD5 hex of the part bytes (the part's ETag).
+    pub md5: String,
+    pub size: u64,
+    pub last_modified: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait UploadRegistry: Send + Sync {
+    /// Visible to other nodes (coordination bucket) rather than node-local.
+    fn is_shared(&self) -> bool;
+
+    async fn create(&self, manifest: &UploadManifest) -> Result<(), String>;
+
+    async fn get(&self, upload_id: &str) -> Result<Option<UploadManifest>, String>;
+
+    /// Every registered upload, all buckets.
+    async fn list(&self) -> Result<Vec<UploadManifest>, String>;
+
+    /// Record a staged part, replacing an earlier upload of the same number.
+    async fn put_part(&self, upload_id: &str, part: &ManifestPart) -> Result<(), String>;
+
+    /// Parts of `upload_id`, ascending part number.
+    async fn parts(&self, upload_id: &str) -> Result<Vec<ManifestPart>, String>;
+
+    /// Forget `upload_id` and its parts (idempotent). Drops any completion
+    /// claim with it.
+    async fn remove(&self, upload_id: &str) -> Result<(), String>;
+
+    /// Compare-and-set claim on completing `upload_id`: `true` for exactly
+    /// one caller across every node sharing the registry, until the claim is
+    /// released, the upload removed, or [`COMPLETION_CLAIM_TTL`] passes.
+    /// `false` when someone else holds it or the upload is gone.
+    async fn claim_completion(&self, upload_id: &str) -> Result<bool, String>;
+
+    /// Give up a claim after a failed completion, so the client can retry.
+    async fn release_completion(&self, upload_id: &str) -> Result<(), String>;
+
+    /// Is a completion of `upload_id` claimed (and not yet stale)?
+    async fn completion_claimed(&self, upload_id: &str) -> Result<bool, String>;
+}
+
+fn millis_to_utc(ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(ms).single().unwrap_or_default()
+}
+
+impl ConfigDb {
+    pub fn multipart_create(&self, m: &UploadManifest) -> Result<(), ConfigDbError> {
+        let user_metadata = serde_json::to_string(&m.user_metadata).unwrap_or_default();
+        self.conn.execute(
+            "INSERT INTO multipart_uploads
+                (upload_id, bucket, object_key, content_type, user_metadata, initiated)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            params![
+                m.upload_id,
+                m.bucket,
+                m.key,
+                m.content_type,
+                user_metadata,
+                m.initiated.timestamp_millis()
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn multipart_from_row(row: &rusqlite::Row) -> rusqlite::Result<UploadManifest> {
+        let user_metadata: String = row.get(4)?;
+        Ok(UploadManifest {
+            upload_id: row.get(0)?,
+            bucket: row.get(1)?,
+            key: row.get(2)?,
+            content_type: row.get(3)?,
+            user_metadata: serde_json::from_str(&user_metadata).unwrap_or_default(),
+            initiated: millis_to_utc(row.get(5)?),
+        })
+    }
+
+    pub fn multipart_get(&self, upload_id: &str) -> Result<Option<UploadManifest>, ConfigDbError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT upload_id, bucket, object_key, content_type, user_metadata, initiated
+                 FROM multipart_uploads WHERE upload_id = ?",
+                params![upload_id],
+                Self::multipart_from_row,
+            )
+            .optional()?)
+    }
+
+    pub fn multipart_list(&self) -> Result<Vec<UploadManifest>, ConfigDbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT upload_id, bucket, object_key, content_type, user_metadata, initiated
+             FROM multipart_uploads ORDER BY bucket, object_key, upload_id",
+        )?;
+        let rows = stmt.query_map([], Self::multipart_from_row)?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    pub fn multipart_put_part(
+        &self,
+        upload_id: &str,
+        part: &ManifestPart,
+    ) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "INSERT INTO multipart_parts (upload_id, part_number, md5, size, last_modified)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(upload_id, part_number) DO UPDATE SET
+                md5 = excluded.md5,
+                size = excluded.size,
+                last_modified = excluded.last_modified",
+            params![
+                upload_id,
+                part.part_number,
+                part.md5,
+                part.size as i64,
+                part.last_modified.timestamp_millis()
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn multipart_parts(&self, upload_id: &str) -> Result<Vec<ManifestPart>, ConfigDbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT part_number, md5, size, last_modified FROM multipart_parts
+             WHERE upload_id = ? ORDER BY part_number",
+        )?;
+        let rows = stmt.query_map(params![upload_id], |row| {
+            Ok(ManifestPart {
+                part_number: row.get(0)?,
+                md5: row.get(1)?,
+                size: row.get::<_, i64>(2)? as u64,
+                last_modified: millis_to_utc(row.get(3)?),
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Claim `upload_id` for completion at `now`, taking over a claim
+    /// older than `stale_before` (both millis). `false` when the row is
+    /// missing or claimed.
+    pub fn multipart_claim(
+        &self,
+        upload_id: &str,
+        now: i64,
+        stale_before: i64,
+    ) -> Result<bool, ConfigDbError> {
+        let claimed = self.conn.execute(
+            "UPDATE multipart_uploads SET completing = ?
+             WHERE upload_id = ? AND (completing = 0 OR completing < ?)",
+            params![now, upload_id, stale_before],
+        )?;
+        Ok(claimed == 1)
+    }
+
+    pub fn multipart_release(&self, upload_id: &str) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "UPDATE multipart_uploads SET completing = 0 WHERE upload_id = ?",
+            params![upload_id],
+        )?;
+        Ok(())
+    }
+
+    /// Is `upload_id` claimed at or after `stale_before` (millis)?
+    pub fn multipart_claimed(
+        &self,
+        upload_id: &str,
+        stale_before: i64,
+    ) -> Result<bool, ConfigDbError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT completing FROM multipart_uploads WHERE upload_id = ?",
+                params![upload_id],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()?
+            .is_some_and(|at| at != 0 && at >= stale_before))
+    }
+
+    pub fn multipart_remove(&self, upload_id: &str) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "DELETE FROM multipart_parts WHERE upload_id = ?",
+            params![upload_id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM multipart_uploads WHERE upload_id = ?",
+            params![upload_id],
+        )?;
+        Ok(())
+    }
+}
+
+/// Node-local registry in the ConfigDb (`multipart_uploads` /
+/// `multipart_parts`, not synced across instances).
+pub struct ConfigDbUploadRegistry {
+    db: Arc<Mutex<ConfigDb>>,
+}
+
+impl ConfigDbUploadRegistry {
+    pub fn new(db: Arc<Mutex<ConfigDb>>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UploadRegistry for ConfigDbUploadRegistry {
+    fn is_shared(&self) -> bool {
+        false
+    }
+
+    async fn create(&self, manifest: &UploadManifest) -> Result<(), String> {
+        self.db
+            .lock()
+            .await
+            .multipart_create(manifest)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn get(&self, upload_id: &str) -> Result<Option<UploadManifest>, String> {
+        self.db
+            .lock()
+            .await
+            .multipart_get(upload_id)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn list(&self) -> Result<Vec<UploadManifest>, String> {
+        self.db
+            .lock()
+            .await
+            .multipart_list()
+            .map_err(|e| e.to_string())
+    }
+
+    async fn put_part(&self, upload_id: &str, part: &ManifestPart) -> Result<(), String> {
+        self.db
+            .lock()
+            .await
+            .multipart_put_part(upload_id, part)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn parts(&self, upload_id: &str) -> Result<Vec<ManifestPart>, String> {
+        self.db
+            .lock()
+            .await
+            .multipart_parts(upload_id)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn remove(&self, upload_id: &str) -> Result<(), String> {
+        self.db
+            .lock()
+            .await
+            .multipart_remove(upload_id)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn claim_completion(&self, upload_id: &str) -> Result<bool, String> {
+        let now = Utc::now();
+        self.db
+            .lock()
+            .await
+            .multipart_claim(
+                upload_id,
+                now.timestamp_millis(),
+                (now - COMPLETION_CLAIM_TTL).timestamp_millis(),
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    async fn release_completion(&self, upload_id: &str) -> Result<(), String> {
+        self.db
+            .lock()
+            .await
+            .multipart_release(upload_id)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn completion_claimed(&self, upload_id: &str) -> Result<bool, String> {
+        self.db
+            .lock()
+            .await
+            .multipart_claimed(
+                upload_id,
+                (Utc::now() - COMPLETION_CLAIM_TTL).timestamp_millis(),
+            )
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Shared registry in the coordination bucket:
+///
+/// - `_dgp/multipart/<upload-id>/upload.json` — the [`UploadManifest`].
+/// - `_dgp/multipart/<upload-id>/parts/<nnnnn>-<md5>-<size>` — one empty
+///   object per staged part, so ListParts is a single LIST (the object's
+///   LastModified is the part's). Re-uploading a part number writes a new
+///   marker and deletes the older ones; should two survive a race, the
+///   newest wins.
+/// - `_dgp/multipart/<upload-id>/completing` — the completion claim, written
+///   with `If-None-Match: *` (a stale one is taken over with `If-Match`).
+pub struct S3UploadRegistry {
+    client: Client,
+    bucket: String,
+}
+
+impl S3UploadRegistry {
+    pub fn new(client: Client, bucket: String) -> Self {
+        Self { client, bucket }
+    }
+
+    fn manifest_key(upload_id: &str) -> String {
+        format!("{REGISTRY_PREFIX}{upload_id}/upload.json")
+    }
+
+    fn claim_key(upload_id: &str) -> String {
+        format!("{REGISTRY_PREFIX}{upload_id}/completing")
+    }
+
+    fn parts_prefix(upload_id: &str) -> String {
+        format!("{REGISTRY_PREFIX}{upload_id}/parts/")
+    }
+
+    fn part_marker(part: &ManifestPart) -> String {
+        format!("{:05}-{}-{}", part.part_number, part.md5, part.size)
+    }
+
+    /// Inverse of [`Self::part_marker`] (without the timestamp).
+    fn parse_part_marker(name: &str) -> Option<(u32, String, u64)> {
+        let mut it = name.splitn(3, '-');
+        let number = it.next()?.parse().ok()?;
+        let md5 = it.next()?.to_string();
+        let size = it.next()?.parse().ok()?;
+        Some((number, md5, size))
+    }
+
+    /// Every key under `prefix`, with its LastModified.
+    async fn list_keys(&self, prefix: &str) -> Result<Vec<(String, DateTime<Utc>)>, String> {
+        let mut keys = Vec::new();
+        let mut continuation: Option<String> = None;
+        loop {
+            let out = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation.take())
+                .send()
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            keys.extend(out.contents().iter().filter_map(|o| {
+                let modified = o
+                    .last_modified()
+                    .and_then(|t| Utc.timestamp_millis_opt(t.to_millis().ok()?).single())
+                    .unwrap_or_default();
+                Some((o.key()?.to_string(), modified))
+            }));
+            match out.next_continuation_token() {
+                Some(token) if out.is_truncated() == Some(true) => {
+                    continuation = Some(token.to_string())
+                }
+                _ => break,
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Conditional PUT of an empty object: `If-Match: etag` when given,
+    /// else `If-None-Match: *`. `false` when the precondition fails.
+    async fn put_if(&self, key: &str, etag: Option<&str>) -> Result<bool, String> {
+        let put = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(ByteStream::from_static(b""));
+        let put = match etag {
+            Some(etag) => put.if_match(etag),
+            None => put.if_none_match("*"),
+        };
+        match put.send().await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                if crate::config_db_sync::is_precondition_failed(
+                    &crate::config_db_sync::sdk_error_signal(&e),
+                ) {
+                    Ok(false)
+                } else {
+                    Err(format!("{e:?}"))
+                }
+            }
+        }
+    }
+
+    /// ETag and LastModified of `key`, `None` if absent.
+    async fn head(&self, key: &str) -> Result<Option<(String, DateTime<Utc>)>, String> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(out) => {
+                let modified = out
+                    .last_modified()
+                    .and_then(|t| Utc.timestamp_millis_opt(t.to_millis().ok()?).single())
+                    .unwrap_or_default();
+                Ok(Some((
+                    out.e_tag().unwrap_or_default().to_string(),
+                    modified,
+                )))
+            }
+            Err(e) => {
+                if crate::config_db_sync::is_object_absent(
+                    &crate::config_db_sync::sdk_error_signal(&e),
+                ) {
+                    Ok(None)
+                } else {
+                    Err(format!("{e:?}"))
+                }
+            }
+        }
+    }
+
+    async fn delete_key(&self, key: &str) -> Result<(), String> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("{e:?}"))
+    }
+}
+
+#[async_trait]
+impl UploadRegistry for S3UploadRegistry {
+    fn is_shared(&self) -> bool {
+        true
+    }
+
+    async fn create(&self, manifest: &UploadManifest) -> Result<(), String> {
+        let body = serde_json::to_vec(manifest).map_err(|e| format!("manifest encode: {e}"))?;
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(Self::manifest_key(&manifest.upload_id))
+            .body(ByteStream::from(body))
+            .content_type("application/json")
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    async fn get(&self, upload_id: &str) -> Result<Option<UploadManifest>, String> {
+        match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(Self::manifest_key(upload_id))
+            .send()
+            .await
+        {
+            Ok(out) => {
+                let bytes = out
+                    .body
+                    .collect()
+                    .await
+                    .map_err(|e| format!("manifest body read: {e}"))?
+                    .into_bytes();
+                serde_json::from_slice(&bytes)
+                    .map(Some)
+                    .map_err(|e| format!("manifest decode: {e}"))
+            }
+            Err(e) => {
+                if crate::config_db_sync::is_object_absent(
+                    &crate::config_db_sync::sdk_error_signal(&e),
+                ) {
+                    Ok(None)
+                } else {
+                    Err(format!("{e:?}"))
+                }
+            }
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<UploadManifest>, String> {
+        let ids: Vec<String> = self
+            .list_keys(REGISTRY_PREFIX)
+            .await?
+            .into_iter()
+            .filter_map(|(key, _)| {
+                key.strip_prefix(REGISTRY_PREFIX)?
+                    .strip_suffix("/upload.json")
+                    .map(str::to_string)
+            })
+            .collect();
+        let mut manifests = Vec::with_capacity(ids.len());
+        for id in ids {
+            match self.get(&id).await? {
+                Some(manifest) => manifests.push(manifest),
+                // Removed between LIST and GET.
+                None => tracing::debug!("multipart manifest {id} is gone; skipping"),
+            }
+        }
+        Ok(manifests)
+    }
+
+    async fn put_part(&self, upload_id: &str, part: &ManifestPart) -> Result<(), String> {
+        let prefix = Self::parts_prefix(upload_id);
+        let marker = Self::part_marker(part);
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(format!("{prefix}{marker}"))
+            .body(ByteStream::from_static(b""))
+            .send()
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let same_number = format!("{prefix}{:05}-", part.part_number);
+        for (key, _) in self.list_keys(&same_number).await? {
+            if !key.ends_with(&marker) {
+                self.delete_key(&key).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn parts(&self, upload_id: &str) -> Result<Vec<ManifestPart>, String> {
+        let prefix = Self::parts_prefix(upload_id);
+        let mut newest: HashMap<u32, ManifestPart> = HashMap::new();
+        for (key, last_modified) in self.list_keys(&prefix).await? {
+            let Some((part_number, md5, size)) =
+                key.strip_prefix(&prefix).and_then(Self::parse_part_marker)
+            else {
+                continue;
+            };
+            let part = ManifestPart {
+                part_number,
+                md5,
+                size,
+                last_modified,
+            };
+            match newest.get(&part_number) {
+                Some(seen) if seen.last_modified > part.last_modified => {}
+                _ => {
+                    newest.insert(part_number, part);
+                }
+            }
+        }
+        let mut parts: Vec<ManifestPart> = newest.into_values().collect();
+        parts.sort_by_key(|p| p.part_number);
+        Ok(parts)
+    }
+
+    async fn remove(&self, upload_id: &str) -> Result<(), String> {
+        // Part markers first: a manifest without parts is still a valid
+        // (empty) upload, never the other way round.
+        let manifest = Self::manifest_key(upload_id);
+        for (key, _) in self
+            .list_keys(&format!("{REGISTRY_PREFIX}{upload_id}/"))
+            .await?
+        {
+            if key != manifest {
+                self.delete_key(&key).await?;
+            }
+        }
+        self.delete_key(&manifest).await
+    }
+
+    async fn claim_completion(&self, upload_id: &str) -> Result<bool, String> {
+        let key = Self::claim_key(upload_id);
+        let claimed = if self.put_if(&key, None).await? {
+            true
+        } else {
+            match self.head(&key).await? {
+                Some((etag, at)) if at < Utc::now() - COMPLETION_CLAIM_TTL => {
+                    self.put_if(&key, Some(&etag)).await?
+                }
+                Some(_) => false,
+                // Released between the PUT and the HEAD; the caller retries.
+                None => false,
+            }
+        };
+        // A claim on an upload that was completed or aborted meanwhile
+        // claims nothing.
+        if claimed && self.get(upload_id).await?.is_none() {
+            self.delete_key(&key).await?;
+            return Ok(false);
+        }
+        Ok(claimed)
+    }
+
+    async fn release_completion(&self, upload_id: &str) -> Result<(), String> {
+        self.delete_key(&Self::claim_key(upload_id)).await
+    }
+
+    async fn completion_claimed(&self, upload_id: &str) -> Result<bool, String> {
+        Ok(self
+            .head(&Self::claim_key(upload_id))
+            .await?
+            .is_some_and(|(_, at)| at >= Utc::now() - COMPLETION_CLAIM_TTL))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(id: &str, bucket: &str) -> UploadManifest {
+        UploadManifest {
+            upload_id: id.to_string(),
+            bucket: bucket.to_string(),
+            key: "dir/file.bin".to_string(),
+            content_type: Some("video/mp4".to_string()),
+            user_metadata: HashMap::from([("owner".to_string(), "ci".to_string())]),
+            initiated: millis_to_utc(1_700_000_000_123),
+        }
+    }
+
+    fn part(number: u32, md5: &str, size: u64) -> ManifestPart {
+        ManifestPart {
+            part_number: number,
+            md5: md5.to_string(),
+            size,
+            last_modified: millis_to_utc(1_700_000_001_000),
+        }
+    }
+
+    #[test]
+    fn config_db_registry_round_trips_uploads_and_parts() {
+        let db = ConfigDb::in_memory("test-pass").unwrap();
+        db.multipart_create(&manifest("u1", "media")).unwrap();
+        db.multipart_create(&manifest("u2", "other")).unwrap();
+        assert_eq!(
+            db.multipart_get("u1").unwrap(),
+            Some(manifest("u1", "media"))
+        );
+        assert_eq!(db.multipart_list().unwrap().len(), 2);
+
+        db.multipart_put_part("u1", &part(2, "bb", 20)).unwrap();
+        db.multipart_put_part("u1", &part(1, "aa", 10)).unwrap();
+        db.multipart_put_part("u1", &part(1, "cc", 11)).unwrap();
+        assert_eq!(
+            db.multipart_parts("u1").unwrap(),
+            vec![part(1, "cc", 11), part(2, "bb", 20)]
+        );
+
+        db.multipart_remove("u1").unwrap();
+        assert_eq!(db.multipart_get("u1").unwrap(), None);
+        assert!(db.multipart_parts("u1").unwrap().is_empty());
+        db.multipart_remove("u1").unwrap();
+    }
+
+    #[test]
+    fn config_db_completion_claim_is_compare_and_set() {
+        let db = ConfigDb::in_memory("test-pass").unwrap();
+        db.multipart_create(&manifest("u1", "media")).unwrap();
+
+        assert!(db.multipart_claim("u1", 1_000, 0).unwrap());
+        assert!(!db.multipart_claim("u1", 2_000, 0).unwrap());
+        assert!(db.multipart_claimed("u1", 0).unwrap());
+
+        db.multipart_release("u1").unwrap();
+        assert!(!db.multipart_claimed("u1", 0).unwrap());
+        assert!(db.multipart_claim("u1", 3_000, 0).unwrap());
+
+        // A claim older than `stale_before` is taken over.
+        assert!(!db.multipart_claimed("u1", 3_001).unwrap());
+        assert!(db.multipart_claim("u1", 9_000, 3_001).unwrap());
+
+        // Nothing to claim once the upload is gone.
+        db.multipart_remove("u1").unwrap();
+        assert!(!db.multipart_claim("u1", 10_000, 0).unwrap());
+    }
+
+    #[test]
+    fn part_markers_round_trip() {
+        let p = part(42, "d41d8cd98f00b204e9800998ecf8427e", 5_242_880);
+        let marker = S3UploadRegistry::part_marker(&p);
+        assert_eq!(marker, "00042-d41d8cd98f00b204e9800998ecf8427e-5242880");
+        assert_eq!(
+            S3UploadRegistry::parse_part_marker(&marker),
+            Some((42, p.md5.clone(), p.size))
+        );
+        assert_eq!(S3UploadRegistry::parse_part_marker("junk"), None);
+    }
+
+    #[test]
+    fn only_generated_ids_are_valid() {
+        assert!(is_valid_upload_id(&new_upload_id()));
+        assert!(!is_valid_upload_id("../../etc/passwd"));
+        assert!(!is_valid_upload_id("0123456789abcdef0123456789abcde/"));
+        assert!(!is_valid_upload_id("0123456789ABCDEF0123456789ABCDEF"));
+    }
+
+    #[test]
+    fn staged_parts_sort_by_name() {
+        assert_eq!(
+            staging_prefix("abc"),
+            ".deltaglider/uploads/abc".to_string()
+        );
+        assert!(part_filename(9) < part_filename(10));
+    }
+}
@@ -0,0 +1,428 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Read fallback: serve a bucket from its replica while its backend is down.
+//!
+//! A bucket with `read_fallback: { bucket: <replica> }` keeps answering reads
+//! through an outage of its own backend, using the copy a replication rule
+//! maintains on another backend. Two triggers:
+//!
+//! - **Health gate.** When [`crate::coordination::health`] holds a gating
+//!   verdict for the primary backend, the gate lets GET/HEAD through with a
+//!   [`ReadFallbackRoute`] extension instead of answering 503. The S3 adapter
+//!   then reads the replica directly, without touching the dead backend.
+//! - **Backend error** (`on_error`, default on). A read against a backend the
+//!   gate still thinks is healthy that fails with a transport / 5xx error is
+//!   retried once against the replica. The primary's error wins if the
+//!   replica fails too.
+//!
+//! Either way the response carries [`STALE_READ_HEADER`] naming the replica,
+//! because replication is asynchronous and the copy may lag.
+//!
+//! ## Queued writes
+//!
+//! With `writes: queue`, single-object PutObject / DeleteObject requests are
+//! applied to the replica while the primary is down, and the key is recorded
+//! in the node-local `read_fallback_queue` table BEFORE the write (a spurious
+//! entry is harmless — replay reconciles from the replica's current state).
+//! [`drain_once`] replays the queue onto the primary once it is healthy
+//! again: copy when the replica holds the key, delete when it doesn't —
+//! unless the primary has meanwhile been written more recently, in which case
+//! the primary wins.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use axum::http::{HeaderMap, Method, Uri};
+use rusqlite::params;
+use tracing::{info, warn};
+
+use crate::api::handlers::AppState;
+use crate::config_db::{ConfigDb, ConfigDbError};
+use crate::deltaglider::{DynEngine, EngineError};
+use crate::storage::StorageError;
+use crate::transfer::{copy_object_with_retries, ObjectTransferRequest};
+
+/// Response header marking a read served from the replica. Value: the
+/// replica bucket name.
+pub const STALE_READ_HEADER: &str = "x-deltaglider-stale-read";
+
+/// Response header marking a write applied to the replica and queued for
+/// replay onto the primary. Value: the replica bucket name.
+pub const QUEUED_WRITE_HEADER: &str = "x-deltaglider-queued-write";
+
+/// Rows replayed per drain pass.
+const DRAIN_BATCH: u32 = 200;
+
+/// Request extension set by the health gate: this request's bucket is on an
+/// unhealthy backend and must be served from `replica` instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadFallbackRoute {
+    pub replica: String,
+}
+
+/// Why a read was diverted (metric label).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackReason {
+    PrimaryUnhealthy,
+    BackendError,
+}
+
+impl FallbackReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PrimaryUnhealthy => "primary_unhealthy",
+            Self::BackendError => "backend_error",
+        }
+    }
+}
+
+/// Does this engine error mean "the backend failed" (as opposed to a
+/// definitive answer such as NotFound, or a local condition)? Only these
+/// trigger an `on_error` fallback.
+pub fn is_backend_fault(err: &EngineError) -> bool {
+    matches!(
+        err,
+        EngineError::Storage(
+            StorageError::Io(_)
+                | StorageError::S3(_)
+                | StorageError::Throttled(_)
+                | StorageError::Other(_)
+        )
+    )
+}
+
+/// GET and HEAD are served from the replica; everything else is a write.
+pub fn is_read_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD)
+}
+
+/// Can this request be queued under `writes: queue`? Only a plain
+/// single-object PutObject or DeleteObject: an object key in the path, no
+/// subresource query (multipart, tagging, ACL, …) and no copy source. The
+/// SDKs' `x-id` operation hint is the one tolerated query parameter.
+pub fn is_queueable_write(method: &Method, uri: &Uri, headers: &HeaderMap) -> bool {
+    if !matches!(*method, Method::PUT | Method::DELETE) {
+        return false;
+    }
+    let has_key = uri
+        .path()
+        .trim_start_matches('/')
+        .split_once('/')
+        .is_some_and(|(_, key)| !key.is_empty());
+    if !has_key {
+        return false;
+    }
+    let plain_query = uri.query().is_none_or(|q| {
+        q.split('&')
+            .filter(|p| !p.is_empty())
+            .all(|p| p.starts_with("x-id="))
+    });
+    plain_query && !headers.contains_key("x-amz-copy-source")
+}
+
+/// Is `bucket`'s backend currently gated by the health cache?
+pub fn primary_is_down(state: &AppState, engine: &DynEngine, bucket: &str) -> bool {
+    let (backend, _) = engine.bucket_policy_registry().resolve_backend(bucket);
+    state
+        .backend_health
+        .unhealthy_verdict(backend.unwrap_or("default"))
+        .is_some_and(|v| v.is_gating())
+}
+
+/// One key waiting to be replayed onto its primary bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedWrite {
+    pub bucket: String,
+    pub key: String,
+    pub generation: i64,
+    pub queued_at: i64,
+    pub attempts: i64,
+}
+
+impl ConfigDb {
+    /// Mark `bucket/key` as written through the replica. Re-queuing a key
+    /// bumps its generation so an in-flight replay of the older write can't
+    /// drop the newer one.
+    pub fn read_fallback_enqueue(
+        &self,
+        bucket: &str,
+        key: &str,
+        queued_at: i64,
+    ) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "INSERT INTO read_fallback_queue (bucket, object_key, queued_at)
+             VALUES (?, ?, ?)
+             ON CONFLICT(bucket, object_key) DO UPDATE SET
+                generation = generation + 1,
+                queued_at = excluded.queued_at,
+                attempts = 0,
+                last_error = NULL",
+            params![bucket, key, queued_at],
+        )?;
+        Ok(())
+    }
+
+    /// Oldest queued keys first.
+    pub fn read_fallback_pending(&self, limit: u32) -> Result<Vec<QueuedWrite>, ConfigDbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT bucket, object_key, generation, queued_at, attempts
+             FROM read_fallback_queue ORDER BY queued_at, bucket, object_key LIMIT ?",
+        )?;
+        let rows = stmt.query_map(params![limit], |row| {
+            Ok(QueuedWrite {
+                bucket: row.get(0)?,
+                key: row.get(1)?,
+                generation: row.get(2)?,
+                queued_at: row.get(3)?,
+                attempts: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Remove a replayed key — only if it wasn't re-queued meanwhile.
+    /// Returns whether the row was removed.
+    pub fn read_fallback_complete(
+        &self,
+        bucket: &str,
+        key: &str,
+        generation: i64,
+    ) -> Result<bool, ConfigDbError> {
+        let n = self.conn.execute(
+            "DELETE FROM read_fallback_queue
+             WHERE bucket = ? AND object_key = ? AND generation = ?",
+            params![bucket, key, generation],
+        )?;
+        Ok(n > 0)
+    }
+
+    /// Record a failed replay attempt; the row stays queued.
+    pub fn read_fallback_record_failure(
+        &self,
+        bucket: &str,
+        key: &str,
+        error: &str,
+    ) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "UPDATE read_fallback_queue SET attempts = attempts + 1, last_error = ?
+             WHERE bucket = ? AND object_key = ?",
+            params![error, bucket, key],
+        )?;
+        Ok(())
+    }
+
+    pub fn read_fallback_queue_len(&self) -> Result<i64, ConfigDbError> {
+        Ok(self
+            .conn
+            .query_row("SELECT COUNT(*) FROM read_fallback_queue", [], |r| r.get(0))?)
+    }
+}
+
+/// Single-flight guard: the periodic tick must not overlap a slow drain.
+static DRAINING: AtomicBool = AtomicBool::new(false);
+
+/// Replay queued writes whose primary is healthy again. Returns the number
+/// of keys replayed. Rows for a still-down primary stay queued untouched.
+pub async fn drain_once(state: &Arc<AppState>) -> usize {
+    let Some(db) = state.config_db.as_ref() else {
+        return 0;
+    };
+    if DRAINING.swap(true, Ordering::AcqRel) {
+        return 0;
+    }
+    let replayed = drain_pass(state, db).await;
+    DRAINING.store(false, Ordering::Release);
+    replayed
+}
+
+async fn drain_pass(state: &Arc<AppState>, db: &Arc<tokio::sync::Mutex<ConfigDb>>) -> usize {
+    let pending = match db.lock().await.read_fallback_pending(DRAIN_BATCH) {
+        Ok(rows) => rows,
+        Err(e) => {
+            warn!("read fallback: cannot load write-back queue: {e}");
+            return 0;
+        }
+    };
+    let engine = state.engine.load_full();
+    let mut replayed = 0;
+    for row in pending {
+        let Some(replica) = engine
+            .bucket_policy_registry()
+            .read_fallback(&row.bucket)
+            .map(|f| f.bucket.clone())
+        else {
+            // Policy removed while writes were queued: nothing to replay from.
+            warn!(
+                "read fallback: dropping queued write {}/{} — the bucket no longer has a \
+                 read_fallback; the object stays on its former replica",
+                row.bucket, row.key
+            );
+            let _ = db
+                .lock()
+                .await
+                .read_fallback_complete(&row.bucket, &row.key, row.generation);
+            continue;
+        };
+        if primary_is_down(state, &engine, &row.bucket) {
+            continue;
+        }
+        match replay_key(&engine, &row, &replica).await {
+            Ok(()) => {
+                let _ =
+                    db.lock()
+                        .await
+                        .read_fallback_complete(&row.bucket, &row.key, row.generation);
+                state.metrics.read_fallback_writes_replayed_total.inc();
+                replayed += 1;
+            }
+            Err(e) => {
+                warn!(
+                    "read fallback: replay of {}/{} from '{}' failed (attempt {}): {}",
+                    row.bucket,
+                    row.key,
+                    replica,
+                    row.attempts + 1,
+                    e
+                );
+                let _ = db.lock().await.read_fallback_record_failure(
+                    &row.bucket,
+                    &row.key,
+                    &e.to_string(),
+                );
+            }
+        }
+    }
+    if let Ok(depth) = db.lock().await.read_fallback_queue_len() {
+        state.metrics.read_fallback_queue_depth.set(depth as f64);
+    }
+    if replayed > 0 {
+        info!("read fallback: replayed {replayed} queued write(s) onto recovered primaries");
+    }
+    replayed
+}
+
+/// Bring `row.bucket/row.key` on the primary in line with the replica's
+/// current state, unless the primary was written after the queued write.
+async fn replay_key(
+    engine: &Arc<DynEngine>,
+    row: &QueuedWrite,
+    replica: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let primary = match engine.head(&row.bucket, &row.key).await {
+        Ok(meta) => Some(meta),
+        Err(EngineError::NotFound(_)) => None,
+        Err(e) => return Err(Box::new(e)),
+    };
+    match engine.head(replica, &row.key).await {
+        Ok(replica_meta) => {
+            if primary
+                .as_ref()
+                .is_some_and(|p| p.created_at > replica_meta.created_at)
+            {
+                return Ok(());
+            }
+            copy_object_with_retries(
+                engine,
+                ObjectTransferRequest {
+                    source_bucket: replica,
+                    source_key: &row.key,
+                    destination_bucket: &row.bucket,
+                    destination_key: &row.key,
+                    provenance: None,
+                    extra_user_metadata: &[],
+                    strip_user_metadata_keys: &[],
+                    operation: "read-fallback-replay",
+                    upload_concurrency: None,
+                    bandwidth: None,
+                    source_engine: None,
+                },
+            )
+            .await?;
+            Ok(())
+        }
+        Err(EngineError::NotFound(_)) => {
+            let Some(primary) = primary else {
+                return Ok(());
+            };
+            if primary.created_at.timestamp() > row.queued_at {
+                return Ok(());
+            }
+            match engine.delete(&row.bucket, &row.key).await {
+                Ok(_) | Err(EngineError::NotFound(_)) => Ok(()),
+                Err(e) => Err(Box::new(e)),
+            }
+        }
+        Err(e) => Err(Box::new(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backend_faults_are_transport_class_only() {
+        assert!(is_backend_fault(&EngineError::Storage(StorageError::S3(
+            "dispatch failure".into()
+        ))));
+        assert!(is_backend_fault(&EngineError::Storage(
+            StorageError::Throttled("503".into())
+        )));
+        assert!(!is_backend_fault(&EngineError::NotFound("k".into())));
+        assert!(!is_backend_fault(&EngineError::Storage(
+            StorageError::NotFound("k".into())
+        )));
+        assert!(!is_backend_fault(&EngineError::Storage(
+            StorageError::BucketNotFound("b".into())
+        )));
+        assert!(!is_backend_fault(&EngineError::Overloaded("busy".into())));
+    }
+
+    #[test]
+    fn queueable_writes_are_plain_single_object_ops() {
+        let h = HeaderMap::new();
+        let uri = |s: &str| s.parse::<Uri>().unwrap();
+        assert!(is_queueable_write(&Method::PUT, &uri("/b/k.txt"), &h));
+        assert!(is_queueable_write(
+            &Method::PUT,
+            &uri("/b/dir/k.txt?x-id=PutObject"),
+            &h
+        ));
+        assert!(is_queueable_write(&Method::DELETE, &uri("/b/k.txt"), &h));
+
+        // Bucket-level, multipart, subresources, POST, copy.
+        assert!(!is_queueable_write(&Method::PUT, &uri("/b"), &h));
+        assert!(!is_queueable_write(&Method::DELETE, &uri("/b/"), &h));
+        assert!(!is_queueable_write(
+            &Method::PUT,
+            &uri("/b/k?partNumber=1&uploadId=x"),
+            &h
+        ));
+        assert!(!is_queueable_write(&Method::PUT, &uri("/b/k?tagging"), &h));
+        assert!(!is_queueable_write(&Method::POST, &uri("/b?delete"), &h));
+        let mut copy = HeaderMap::new();
+        copy.insert("x-amz-copy-source", "/src/k".parse().unwrap());
+        assert!(!is_queueable_write(&Method::PUT, &uri("/b/k"), &copy));
+    }
+
+    #[test]
+    fn queue_generation_protects_newer_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ConfigDb::open_or_create(&dir.path().join("c.db"), "pw").unwrap();
+        db.read_fallback_enqueue("b", "k", 100).unwrap();
+        let first = db.read_fallback_pending(10).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].generation, 1);
+
+        // Re-queued while the first replay was in flight.
+        db.read_fallback_enqueue("b", "k", 200).unwrap();
+        assert!(!db.read_fallback_complete("b", "k", 1).unwrap());
+        assert_eq!(db.read_fallback_queue_len().unwrap(), 1);
+
+        db.read_fallback_record_failure("b", "k", "boom").unwrap();
+        let row = &db.read_fallback_pending(10).unwrap()[0];
+        assert_eq!((row.generation, row.queued_at, row.attempts), (2, 200, 1));
+        assert!(db.read_fallback_complete("b", "k", 2).unwrap());
+        assert_eq!(db.read_fallback_queue_len().unwrap(), 0);
+    }
+}
@@ -0,0 +1,1663 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Bidirectional (active-active) replication.
+//!
+//! A rule with `direction: bidirectional` keeps its two endpoints in sync when
+//! both take writes. Each key's last-synced state lives in
+//! `replication_sync_state`: the listing fingerprint of both sides at the last
+//! sync, or a tombstone for a propagated delete. A sweep merge-joins the two
+//! listings with those rows (all keyed relative to the rule's prefixes) and
+//! [`plan_key`] decides per key:
+//!
+//! - changed on one side only → copy it to the other side;
+//! - deleted on one side, unchanged on the other → delete the survivor and
+//!   write a tombstone (`replicate_deletes` only — otherwise the copy is
+//!   restored);
+//! - changed on both sides, or deleted on one and modified on the other → a
+//!   **conflict**, held in `replication_conflicts` until an operator keeps one
+//!   side ([`resolve_conflict`]). Conflicted keys are left alone by the sweep
+//!   (a conflict clears itself once both sides agree again).
+//!
+//! When the sync state can't tell (no row yet, or a key that reappears after a
+//! tombstone), [`resolve_compare`] / [`resolve_revive`] decide from HEADs:
+//! identical content is adopted as synced; the `dg-replication-rule`
+//! provenance marker tells a replica we wrote from a client write; and the
+//! `dg-hlc` hybrid-logical-clock stamp (`hlc`) orders versions and tombstones.
+//!
+//! Loop prevention: a replica carries this rule's provenance marker and its
+//! origin's HLC stamp, and the sync row records it as synced, so it is never
+//! copied back. The event consumer skips events for objects carrying the
+//! marker, which covers replicas written through another proxy's S3 API.
+//!
+//! Every copy re-checks the target's fingerprint and every delete re-checks
+//! both sides just before acting, so a write landing mid-sweep is left for the
+//! next pass instead of being overwritten. Sync state is node-local (config
+//! DB) and a sweep is not resumable mid-pass: an interrupted run starts over
+//! and converges on the next.
+
+use super::event_consumer::{is_user_object_key, owned_by_rule};
+use super::hlc::{clock, HlcStamp};
+use super::planner::{compile_rule_globs, content_differs, normalize_prefix};
+use super::state_store::{current_unix_seconds, FailureInsert, RunTotals, SyncConflict, SyncRow};
+use super::worker::{
+    compute_next_due, flush_event_sink, settle_run, spawn_lease_heartbeat, ControlVerdict,
+    EventSink, InFlightGuard, RunConcurrency, RunControl, RunOutcome, SettleInput,
+};
+use crate::background::RunLease;
+use crate::config_db::{ConfigDb, ConfigDbError};
+use crate::config_sections::ReplicationRule;
+use crate::deltaglider::{DynEngine, EngineError};
+use crate::event_outbox::{EventKind, EventSource, NewEvent};
+use crate::job_loop::Pager;
+use crate::transfer::{
+    copy_object_with_retries, BandwidthBudget, CopyStrategy, ObjectTransferRequest,
+    TransferProvenance, HLC_METADATA_KEY, REPLICATION_RULE_METADATA_KEY,
+};
+use crate::types::FileMetadata;
+use futures::stream::StreamExt;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+/// Objects per listing page and sync rows per DB read.
+const PAGE_SIZE: u32 = 1_000;
+const LIST_MAX_ATTEMPTS: u32 = 3;
+/// Both sides of a bidirectional rule must be proxy-routed: an external
+/// endpoint emits no events, so the sync journal could never track it.
+const EXTERNAL_REFUSED: &str = "bidirectional rules cannot use external endpoints";
+
+/// One endpoint of a bidirectional rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Source,
+    Destination,
+}
+
+impl Side {
+    pub fn other(self) -> Self {
+        match self {
+            Side::Source => Side::Destination,
+            Side::Destination => Side::Source,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Side::Source => "source",
+            Side::Destination => "destination",
+        }
+    }
+}
+
+/// Why a key was held for manual resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Both sides created the key independently, with different content.
+    BothCreated,
+    /// Both sides changed the key since the last sync.
+    BothModified,
+    /// Deleted on the source, modified on the destination.
+    DeletedOnSource,
+    /// Deleted on the destination, modified on the source.
+    DeletedOnDestination,
+}
+
+impl ConflictKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConflictKind::BothCreated => "both-created",
+            ConflictKind::BothModified => "both-modified",
+            ConflictKind::DeletedOnSource => "deleted-on-source",
+            ConflictKind::DeletedOnDestination => "deleted-on-destination",
+        }
+    }
+}
+
+/// What to do with one key. Produced by the pure [`plan_key`];
+/// `Compare` and `Revive` need HEADs and are refined by [`resolve_compare`] /
+/// [`resolve_revive`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Plan {
+    /// Nothing to do.
+    InSync,
+    /// Copy the key from `from` to the other side.
+    Copy { from: Side },
+    /// Delete the key on `side` (its counterpart was deleted) and tombstone it.
+    Delete { side: Side },
+    /// Both sides deleted it: record a tombstone.
+    Tombstone,
+    /// Drop the sync row (expired tombstone, or a delete not replicated).
+    Forget,
+    /// Both sides present with no usable history: compare the objects.
+    Compare { kind: ConflictKind },
+    /// A tombstoned key reappeared on `side`: resurrected, or a stale copy?
+    Revive { side: Side },
+    /// Hold for manual resolution.
+    Conflict { kind: ConflictKind },
+    /// Both sides already hold the same content: record it as synced.
+    Adopt,
+}
+
+/// Listing fingerprint of an object: creation time and ETag as a lite
+/// listing reports them. Compared only against other listing fingerprints —
+/// it changes whenever the object is rewritten.
+pub fn fingerprint(meta: &FileMetadata) -> String {
+    format!("{}:{}", meta.created_at.timestamp_millis(), meta.etag())
+}
+
+/// PURE per-key decision from both sides' current fingerprints (`None` =
+/// absent) and the key's sync row. A row fingerprint that is unknown counts
+/// as changed, which routes to a HEAD comparison rather than an overwrite.
+pub fn plan_key(
+    src_fp: Option<&str>,
+    dest_fp: Option<&str>,
+    row: Option<&SyncRow>,
+    replicate_deletes: bool,
+    tombstone_expired: bool,
+) -> Plan {
+    let Some(row) = row else {
+        return match (src_fp, dest_fp) {
+            (None, None) => Plan::InSync,
+            (Some(_), None) => Plan::Copy { from: Side::Source },
+            (None, Some(_)) => Plan::Copy {
+                from: Side::Destination,
+            },
+            (Some(_), Some(_)) => Plan::Compare {
+                kind: ConflictKind::BothCreated,
+            },
+        };
+    };
+    if row.tombstone {
+        return match (src_fp, dest_fp) {
+            (None, None) if tombstone_expired => Plan::Forget,
+            (None, None) => Plan::InSync,
+            (Some(_), None) if !replicate_deletes => Plan::Copy { from: Side::Source },
+            (None, Some(_)) if !replicate_deletes => Plan::Copy {
+                from: Side::Destination,
+            },
+            (Some(_), None) => Plan::Revive { side: Side::Source },
+            (None, Some(_)) => Plan::Revive {
+                side: Side::Destination,
+            },
+            (Some(_), Some(_)) => Plan::Compare {
+                kind: ConflictKind::BothCreated,
+            },
+        };
+    }
+    let changed = |now: &str, synced: &Option<String>| synced.as_deref() != Some(now);
+    match (src_fp, dest_fp) {
+        (Some(s), Some(d)) => match (changed(s, &row.src_fp), changed(d, &row.dest_fp)) {
+            (false, false) => Plan::InSync,
+            (true, false) => Plan::Copy { from: Side::Source },
+            (false, true) => Plan::Copy {
+                from: Side::Destination,
+            },
+            (true, true) => Plan::Compare {
+                kind: ConflictKind::BothModified,
+            },
+        },
+        (Some(_), None) if !replicate_deletes => Plan::Copy { from: Side::Source },
+        (None, Some(_)) if !replicate_deletes => Plan::Copy {
+            from: Side::Destination,
+        },
+        (Some(s), None) if !changed(s, &row.src_fp) => Plan::Delete { side: Side::Source },
+        (None, Some(d)) if !changed(d, &row.dest_fp) => Plan::Delete {
+            side: Side::Destination,
+        },
+        (Some(_), None) => Plan::Conflict {
+            kind: ConflictKind::DeletedOnDestination,
+        },
+        (None, Some(_)) => Plan::Conflict {
+            kind: ConflictKind::DeletedOnSource,
+        },
+        (None, None) if replicate_deletes => Plan::Tombstone,
+        (None, None) => Plan::Forget,
+    }
+}
+
+/// PURE: both sides present without usable sync history. Identical content is
+/// adopted; a replica this rule wrote yields to the other side's write; two
+/// replicas are ordered by their HLC stamps; two client writes conflict.
+pub fn resolve_compare(
+    kind: ConflictKind,
+    src: &FileMetadata,
+    dest: &FileMetadata,
+    rule_name: &str,
+    src_site: &str,
+    dest_site: &str,
+) -> Plan {
+    if !content_differs(src, dest, true) {
+        return Plan::Adopt;
+    }
+    match (
+        owned_by_rule(src, rule_name),
+        owned_by_rule(dest, rule_name),
+    ) {
+        (true, false) => Plan::Copy {
+            from: Side::Destination,
+        },
+        (false, true) => Plan::Copy { from: Side::Source },
+        (true, true) => {
+            let (s, d) = (
+                HlcStamp::of_object(src, src_site),
+                HlcStamp::of_object(dest, dest_site),
+            );
+            match s.cmp(&d) {
+                std::cmp::Ordering::Greater => Plan::Copy { from: Side::Source },
+                std::cmp::Ordering::Less => Plan::Copy {
+                    from: Side::Destination,
+                },
+                std::cmp::Ordering::Equal => Plan::Conflict { kind },
+            }
+        }
+        (false, false) => Plan::Conflict { kind },
+    }
+}
+
+/// PURE: a tombstoned key is present again on `side`. Only a replica this
+/// rule wrote (its provenance marker) stamped no later than the tombstone is
+/// a stale in-flight copy and is deleted again. Anything else newer than the
+/// tombstone is a re-creation and is copied. A client write that doesn't
+/// order after the tombstone — unstamped, so versioned by the backend's
+/// clock — is held as a conflict rather than deleted on clock evidence. An
+/// unreadable tombstone stamp keeps the data.
+pub fn resolve_revive(
+    side: Side,
+    meta: &FileMetadata,
+    rule_name: &str,
+    site: &str,
+    tombstone: Option<&HlcStamp>,
+) -> Plan {
+    let Some(t) = tombstone else {
+        return Plan::Copy { from: side };
+    };
+    if HlcStamp::of_object(meta, site) > *t {
+        return Plan::Copy { from: side };
+    }
+    if owned_by_rule(meta, rule_name) {
+        return Plan::Delete { side };
+    }
+    Plan::Conflict {
+        kind: match side {
+            Side::Source => ConflictKind::DeletedOnDestination,
+            Side::Destination => ConflictKind::DeletedOnSource,
+        },
+    }
+}
+
+/// A rule's two endpoints, prefixes normalized.
+struct Endpoints<'a> {
+    rule: &'a ReplicationRule,
+    src_prefix: String,
+    dest_prefix: String,
+}
+
+impl<'a> Endpoints<'a> {
+    fn new(rule: &'a ReplicationRule) -> Self {
+        Self {
+            rule,
+            src_prefix: normalize_prefix(&rule.source.prefix),
+            dest_prefix: normalize_prefix(&rule.destination.prefix),
+        }
+    }
+
+    fn bucket(&self, side: Side) -> &str {
+        match side {
+            Side::Source => &self.rule.source.bucket,
+            Side::Destination => &self.rule.destination.bucket,
+        }
+    }
+
+    fn abs(&self, side: Side, rel: &str) -> String {
+        match side {
+            Side::Source => format!("{}{rel}", self.src_prefix),
+            Side::Destination => format!("{}{rel}", self.dest_prefix),
+        }
+    }
+
+    /// The relative key of an absolute `(bucket, key)` on either side.
+    fn rel_of(&self, bucket: &str, key: &str) -> Option<String> {
+        [Side::Source, Side::Destination]
+            .into_iter()
+            .filter(|&side| self.bucket(side) == bucket)
+            .find_map(|side| {
+                let prefix = match side {
+                    Side::Source => &self.src_prefix,
+                    Side::Destination => &self.dest_prefix,
+                };
+                key.strip_prefix(prefix.as_str()).map(str::to_string)
+            })
+    }
+}
+
+/// One side of a key as a listing reports it.
+#[derive(Debug, Clone)]
+struct Listed {
+    fp: String,
+}
+
+impl Listed {
+    fn of(meta: &FileMetadata) -> Self {
+        Self {
+            fp: fingerprint(meta),
+        }
+    }
+}
+
+/// One key to sync.
+struct KeyJob {
+    rel: String,
+    src: Option<Listed>,
+    dest: Option<Listed>,
+    row: Option<SyncRow>,
+    conflicted: bool,
+}
+
+impl KeyJob {
+    fn side(&self, side: Side) -> Option<&Listed> {
+        match side {
+            Side::Source => self.src.as_ref(),
+            Side::Destination => self.dest.as_ref(),
+        }
+    }
+}
+
+/// The outcome of syncing one key. DB-free: the driver applies the sync-state
+/// effects after the batch, under one lock (see the worker's fair-mutex notes).
+#[derive(Default)]
+struct KeyResult {
+    rel: String,
+    upsert: Option<SyncRow>,
+    forget: bool,
+    conflict: Option<SyncConflict>,
+    clear_conflict: bool,
+    copied: bool,
+    bytes_copied: i64,
+    delta_passthrough: i64,
+    bytes_egress_saved: i64,
+    reconstructed: i64,
+    deleted: bool,
+    skipped: bool,
+    error: Option<String>,
+}
+
+/// Per-run knobs shared by every key of a sync.
+struct SyncCtx<'a> {
+    engine: &'a Arc<DynEngine>,
+    ends: &'a Endpoints<'a>,
+    object_timeout: Option<std::time::Duration>,
+    upload_concurrency: Option<usize>,
+    bandwidth: Arc<BandwidthBudget>,
+    tombstone_retention_secs: i64,
+    events: &'a EventSink,
+}
+
+impl SyncCtx<'_> {
+    /// HEAD one side; `None` when the key is absent.
+    async fn head(&self, side: Side, rel: &str) -> Result<Option<FileMetadata>, String> {
+        let (bucket, key) = (self.ends.bucket(side), self.ends.abs(side, rel));
+        match self.engine.head(bucket, &key).await {
+            Ok(meta) => Ok(Some(meta)),
+            Err(EngineError::NotFound(_)) => Ok(None),
+            Err(e) => Err(format!("head {bucket}/{key} failed: {e}")),
+        }
+    }
+
+    /// One side's CURRENT listing entry (a single-key lite listing, so the
+    /// fingerprint compares with the sweep's). Delimited so a filesystem
+    /// backend reads the key's directory instead of walking the key as one.
+    async fn probe(&self, side: Side, rel: &str) -> Result<Option<Listed>, String> {
+        let (bucket, key) = (self.ends.bucket(side), self.ends.abs(side, rel));
+        let page = self
+            .engine
+            .list_objects(bucket, &key, Some("/"), 1, None, false)
+            .await
+            .map_err(|e| format!("list {bucket}/{key} failed: {e}"))?;
+        Ok(page
+            .objects
+            .first()
+            .filter(|(k, _)| *k == key)
+            .map(|(_, m)| Listed::of(m)))
+    }
+}
+
+fn live_row(
+    rel: &str,
+    stamp: &HlcStamp,
+    src_fp: Option<String>,
+    dest_fp: Option<String>,
+) -> SyncRow {
+    SyncRow {
+        rel_key: rel.to_string(),
+        stamp: stamp.to_string(),
+        src_fp,
+        dest_fp,
+        tombstone: false,
+        updated_at: current_unix_seconds(),
+    }
+}
+
+fn tombstone_row(rel: &str, stamp: &HlcStamp) -> SyncRow {
+    SyncRow {
+        rel_key: rel.to_string(),
+        stamp: stamp.to_string(),
+        src_fp: None,
+        dest_fp: None,
+        tombstone: true,
+        updated_at: current_unix_seconds(),
+    }
+}
+
+/// Sync one key. Never touches the DB; see [`KeyResult`].
+async fn sync_one(ctx: &SyncCtx<'_>, job: KeyJob) -> KeyResult {
+    let mut out = KeyResult {
+        rel: job.rel.clone(),
+        ..Default::default()
+    };
+    if job.conflicted {
+        settle_conflicted(ctx, &job, &mut out).await;
+        return out;
+    }
+    let ends = ctx.ends;
+    let rule = ends.rule;
+    let expired = job.row.as_ref().is_some_and(|r| {
+        r.tombstone && current_unix_seconds() - r.updated_at >= ctx.tombstone_retention_secs
+    });
+    let mut plan = plan_key(
+        job.src.as_ref().map(|l| l.fp.as_str()),
+        job.dest.as_ref().map(|l| l.fp.as_str()),
+        job.row.as_ref(),
+        rule.replicate_deletes,
+        expired,
+    );
+
+    // Refine the plans that need the objects themselves.
+    let mut heads: Option<(FileMetadata, FileMetadata)> = None;
+    match plan {
+        Plan::Compare { kind } => {
+            match (
+                ctx.head(Side::Source, &job.rel).await,
+                ctx.head(Side::Destination, &job.rel).await,
+            ) {
+                (Ok(Some(s)), Ok(Some(d))) => {
+                    plan = resolve_compare(
+                        kind,
+                        &s,
+                        &d,
+                        &rule.name,
+                        ends.bucket(Side::Source),
+                        ends.bucket(Side::Destination),
+                    );
+                    heads = Some((s, d));
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    out.error = Some(e);
+                    return out;
+                }
+                // Changed since the listing; the next pass sees it settled.
+                _ => {
+                    out.skipped = true;
+                    return out;
+                }
+            }
+        }
+        Plan::Revive { side } => match ctx.head(side, &job.rel).await {
+            Ok(Some(meta)) => {
+                let tombstone = job.row.as_ref().and_then(|r| r.stamp.parse().ok());
+                plan = resolve_revive(
+                    side,
+                    &meta,
+                    &rule.name,
+                    ends.bucket(side),
+                    tombstone.as_ref(),
+                );
+            }
+            Ok(None) => {
+                out.skipped = true;
+                return out;
+            }
+            Err(e) => {
+                out.error = Some(e);
+                return out;
+            }
+        },
+        _ => {}
+    }
+
+    match plan {
+        Plan::InSync | Plan::Compare { .. } | Plan::Revive { .. } => out.skipped = true,
+        Plan::Forget => out.forget = true,
+        Plan::Tombstone => {
+            out.upsert = Some(tombstone_row(
+                &job.rel,
+                &clock().now(ends.bucket(Side::Source)),
+            ))
+        }
+        Plan::Adopt => {
+            let stamp = heads
+                .as_ref()
+                .map(|(s, d)| {
+                    HlcStamp::of_object(s, ends.bucket(Side::Source))
+                        .max(HlcStamp::of_object(d, ends.bucket(Side::Destination)))
+                })
+                .unwrap_or_else(|| clock().now(ends.bucket(Side::Source)));
+            clock().observe(&stamp);
+            out.upsert = Some(live_row(
+                &job.rel,
+                &stamp,
+                job.src.as_ref().map(|l| l.fp.clone()),
+                job.dest.as_ref().map(|l| l.fp.clone()),
+            ));
+        }
+        Plan::Conflict { kind } => match conflict_record(ctx, &job, kind, heads).await {
+            Ok(c) => {
+                info!(
+                    "replication rule '{}': conflict on '{}' ({})",
+                    rule.name,
+                    job.rel,
+                    kind.as_str()
+                );
+                out.conflict = Some(c);
+            }
+            Err(e) => out.error = Some(e),
+        },
+        Plan::Copy { from } => copy_key(ctx, &job, from, &mut out).await,
+        Plan::Delete { side } => delete_key(ctx, &job, side, &mut out).await,
+    }
+    out
+}
+
+/// A conflicted key is left alone until both sides agree again: both gone,
+/// or both holding the same content (fixed by hand). Then it is synced.
+async fn settle_conflicted(ctx: &SyncCtx<'_>, job: &KeyJob, out: &mut KeyResult) {
+    match (&job.src, &job.dest) {
+        (None, None) => {
+            out.forget = true;
+            out.clear_conflict = true;
+        }
+        (Some(src), Some(dest)) => {
+            match (
+                ctx.head(Side::Source, &job.rel).await,
+                ctx.head(Side::Destination, &job.rel).await,
+            ) {
+                (Ok(Some(s)), Ok(Some(d))) if !content_differs(&s, &d, true) => {
+                    let stamp = HlcStamp::of_object(&s, ctx.ends.bucket(Side::Source))
+                        .max(HlcStamp::of_object(&d, ctx.ends.bucket(Side::Destination)));
+                    out.upsert = Some(live_row(
+                        &job.rel,
+                        &stamp,
+                        Some(src.fp.clone()),
+                        Some(dest.fp.clone()),
+                    ));
+                    out.clear_conflict = true;
+                }
+                (Err(e), _) | (_, Err(e)) => out.error = Some(e),
+                _ => out.skipped = true,
+            }
+        }
+        _ => out.skipped = true,
+    }
+}
+
+/// The conflict row for the operator: each side's version stamp and size
+/// (logical, from a HEAD), absent for a deleted side.
+async fn conflict_record(
+    ctx: &SyncCtx<'_>,
+    job: &KeyJob,
+    kind: ConflictKind,
+    heads: Option<(FileMetadata, FileMetadata)>,
+) -> Result<SyncConflict, String> {
+    let (src, dest) = match heads {
+        Some((s, d)) => (Some(s), Some(d)),
+        None => {
+            let src = match job.src {
+                Some(_) => ctx.head(Side::Source, &job.rel).await?,
+                None => None,
+            };
+            let dest = match job.dest {
+                Some(_) => ctx.head(Side::Destination, &job.rel).await?,
+                None => None,
+            };
+            (src, dest)
+        }
+    };
+    let stamp = |m: &Option<FileMetadata>, side: Side| {
+        m.as_ref()
+            .map(|m| HlcStamp::of_object(m, ctx.ends.bucket(side)).to_string())
+    };
+    Ok(SyncConflict {
+        rel_key: job.rel.clone(),
+        kind: kind.as_str().to_string(),
+        src_stamp: stamp(&src, Side::Source),
+        dest_stamp: stamp(&dest, Side::Destination),
+        src_size: src.as_ref().map(|m| m.file_size as i64),
+        dest_size: dest.as_ref().map(|m| m.file_size as i64),
+        detected_at: current_unix_seconds(),
+    })
+}
+
+/// Copy `job.rel` from `from` to the other side, unless the target changed
+/// since it was listed. The replica carries this rule's provenance marker
+/// and the origin's HLC stamp.
+async fn copy_key(ctx: &SyncCtx<'_>, job: &KeyJob, from: Side, out: &mut KeyResult) {
+    let to = from.other();
+    match ctx.probe(to, &job.rel).await {
+        Ok(current) if current.as_ref().map(|l| &l.fp) == job.side(to).map(|l| &l.fp) => {}
+        Ok(_) => {
+            out.skipped = true;
+            return;
+        }
+        Err(e) => {
+            out.error = Some(e);
+            return;
+        }
+    }
+    let meta = match ctx.head(from, &job.rel).await {
+        Ok(Some(meta)) => meta,
+        Ok(None) => {
+            out.skipped = true;
+            return;
+        }
+        Err(e) => {
+            out.error = Some(e);
+            return;
+        }
+    };
+    let stamp = HlcStamp::of_object(&meta, ctx.ends.bucket(from));
+    clock().observe(&stamp);
+    let outcome = copy_stamped(ctx, &job.rel, from, &meta, &stamp).await;
+    match outcome {
+        Ok(outcome) => {
+            out.copied = true;
+            out.bytes_copied = outcome.bytes_copied as i64;
+            out.bytes_egress_saved = outcome.bytes_egress_saved as i64;
+            match outcome.strategy {
+                CopyStrategy::DeltaPassthrough => out.delta_passthrough = 1,
+                CopyStrategy::Reconstructed => out.reconstructed = 1,
+                _ => {}
+            }
+            // The target's new fingerprint; unknown if the probe fails, which
+            // makes the next pass compare the objects instead of copying.
+            let target_fp = ctx.probe(to, &job.rel).await.ok().flatten().map(|l| l.fp);
+            let source_fp = job.side(from).map(|l| l.fp.clone());
+            let (src_fp, dest_fp) = match from {
+                Side::Source => (source_fp, target_fp),
+                Side::Destination => (target_fp, source_fp),
+            };
+            out.upsert = Some(live_row(&job.rel, &stamp, src_fp, dest_fp));
+        }
+        Err(e) => out.error = Some(e),
+    }
+}
+
+/// The copy itself, bounded by `object_timeout`, with its event pushed the
+/// moment it is durable.
+async fn copy_stamped(
+    ctx: &SyncCtx<'_>,
+    rel: &str,
+    from: Side,
+    meta: &FileMetadata,
+    stamp: &HlcStamp,
+) -> Result<crate::transfer::ObjectTransferOutcome, String> {
+    let ends = ctx.ends;
+    let to = from.other();
+    let (src_bucket, src_key) = (ends.bucket(from), ends.abs(from, rel));
+    let (dst_bucket, dst_key) = (ends.bucket(to), ends.abs(to, rel));
+    let _inflight = InFlightGuard::new(&ends.rule.name, &src_key, meta.file_size);
+    let stamp_text = stamp.to_string();
+    let extra = [(HLC_METADATA_KEY, stamp_text.as_str())];
+    let transfer = ObjectTransferRequest {
+        source_bucket: src_bucket,
+        source_key: &src_key,
+        destination_bucket: dst_bucket,
+        destination_key: &dst_key,
+        provenance: Some(TransferProvenance {
+            metadata_key: REPLICATION_RULE_METADATA_KEY,
+            metadata_value: &ends.rule.name,
+        }),
+        extra_user_metadata: &extra,
+        strip_user_metadata_keys: &[],
+        operation: "replication-bidi",
+        upload_concurrency: ctx.upload_concurrency,
+        bandwidth: Some(&ctx.bandwidth),
+        source_engine: None,
+    };
+    let copy_fut = copy_object_with_retries(ctx.engine, transfer);
+    let outcome = match ctx.object_timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, copy_fut).await {
+            Ok(r) => r,
+            Err(_elapsed) => {
+                return Err(format!(
+                    "object copy timed out after {}s",
+                    timeout.as_secs()
+                ))
+            }
+        },
+        None => copy_fut.await,
+    }
+    .map_err(|e| e.to_string())?;
+    ctx.events.lock().push(NewEvent::new(
+        EventKind::ReplicationObjectCopied,
+        dst_bucket,
+        &dst_key,
+        EventSource::Replication,
+        current_unix_seconds(),
+        serde_json::json!({
+            "rule_name": &ends.rule.name,
+            "source_bucket": src_bucket,
+            "source_key": &src_key,
+            "destination_bucket": dst_bucket,
+            "destination_key": &dst_key,
+            "content_length": outcome.bytes_copied,
+            "strategy": outcome.strategy.as_str(),
+            "source_storage_type": outcome.source_storage_label,
+            "copied_from": from.as_str(),
+        }),
+    ));
+    Ok(outcome)
+}
+
+/// Delete `job.rel` on `side` because it was deleted on the other side — only
+/// if the other side is still absent and the victim is still the version we
+/// last synced. Records a tombstone.
+async fn delete_key(ctx: &SyncCtx<'_>, job: &KeyJob, side: Side, out: &mut KeyResult) {
+    let other = side.other();
+    match ctx.probe(other, &job.rel).await {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            out.skipped = true;
+            return;
+        }
+        Err(e) => {
+            out.error = Some(e);
+            return;
+        }
+    }
+    match ctx.probe(side, &job.rel).await {
+        Ok(Some(current)) if job.side(side).is_some_and(|l| l.fp == current.fp) => {
+            let (bucket, key) = (ctx.ends.bucket(side), ctx.ends.abs(side, &job.rel));
+            match ctx.engine.delete(bucket, &key).await {
+                Ok(_) | Err(EngineError::NotFound(_)) => out.deleted = true,
+                Err(e) => {
+                    out.error = Some(format!("delete {bucket}/{key} failed: {e}"));
+                    return;
+                }
+            }
+        }
+        // Already gone: both sides agree, just record it.
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            out.skipped = true;
+            return;
+        }
+        Err(e) => {
+            out.error = Some(e);
+            return;
+        }
+    }
+    out.upsert = Some(tombstone_row(
+        &job.rel,
+        &clock().now(ctx.ends.bucket(other)),
+    ));
+}
+
+/// Apply one key's sync-state effects through an already-held DB guard.
+fn apply_effects(db: &ConfigDb, rule_name: &str, r: &KeyResult) -> Result<(), ConfigDbError> {
+    if let Some(row) = &r.upsert {
+        db.replication_sync_upsert(rule_name, row)?;
+    }
+    if r.forget {
+        db.replication_sync_delete(rule_name, &r.rel)?;
+    }
+    if let Some(c) = &r.conflict {
+        db.replication_conflict_upsert(rule_name, c)?;
+    }
+    if r.clear_conflict {
+        db.replication_conflict_clear(rule_name, &r.rel)?;
+    }
+    Ok(())
+}
+
+/// Flat lite listing of one side, relative keys in listing order.
+struct SideListing {
+    bucket: String,
+    prefix: String,
+    pager: Pager,
+    buf: VecDeque<(String, Listed)>,
+    exhausted: bool,
+}
+
+impl SideListing {
+    fn new(bucket: &str, prefix: &str) -> Self {
+        Self {
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+            pager: Pager::fresh().with_test_max_pages_env(),
+            buf: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+
+    /// Ensure the next entry is buffered. A listing that can't complete is an
+    /// error — treating the unlisted tail as absent would delete it.
+    async fn fill(&mut self, engine: &Arc<DynEngine>) -> Result<(), String> {
+        while self.buf.is_empty() && !self.exhausted {
+            if self.pager.begin_page().is_none() {
+                return Err(format!(
+                    "listing {}/{} exceeded the page budget",
+                    self.bucket, self.prefix
+                ));
+            }
+            let mut attempt = 0u32;
+            let page = loop {
+                if let Some(m) = engine.metrics() {
+                    m.replication_list_calls_total.inc();
+                }
+                match engine
+                    .list_objects(
+                        &self.bucket,
+                        &self.prefix,
+                        None,
+                        PAGE_SIZE,
+                        self.pager.token(),
+                        false,
+                    )
+                    .await
+                {
+                    Ok(p) => break p,
+                    Err(e) => {
+                        let msg = e.to_string();
+                        attempt += 1;
+                        if attempt >= LIST_MAX_ATTEMPTS
+                            || !crate::transfer::is_transient_copy_error(&msg)
+                        {
+                            return Err(format!(
+                                "list {}/{} failed: {msg}",
+                                self.bucket, self.prefix
+                            ));
+                        }
+                        tokio::time::sleep(std::time::Duration::from_millis(250 * attempt as u64))
+                            .await;
+                    }
+                }
+            };
+            for (key, meta) in &page.objects {
+                let rel = key.strip_prefix(self.prefix.as_str()).ok_or_else(|| {
+                    format!("listing entry {key:?} outside prefix {:?}", self.prefix)
+                })?;
+                self.buf.push_back((rel.to_string(), Listed::of(meta)));
+            }
+            if !self
+                .pager
+                .advance(page.is_truncated, page.next_continuation_token)
+            {
+                self.exhausted = true;
+            }
+        }
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.buf.front().map(|(k, _)| k.as_str())
+    }
+
+    fn take(&mut self, rel: &str) -> Option<Listed> {
+        if self.peek() == Some(rel) {
+            self.buf.pop_front().map(|(_, l)| l)
+        } else {
+            None
+        }
+    }
+}
+
+/// The rule's sync rows in key order.
+struct SyncRows {
+    after: String,
+    buf: VecDeque<SyncRow>,
+    exhausted: bool,
+}
+
+impl SyncRows {
+    async fn fill(&mut self, db: &Arc<Mutex<ConfigDb>>, rule: &str) -> Result<(), ConfigDbError> {
+        if self.buf.is_empty() && !self.exhausted {
+            let rows = db
+                .lock()
+                .await
+                .replication_sync_rows_after(rule, &self.after, PAGE_SIZE)?;
+            self.exhausted = rows.len() < PAGE_SIZE as usize;
+            if let Some(last) = rows.last() {
+                self.after = last.rel_key.clone();
+            }
+            self.buf.extend(rows);
+        }
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.buf.front().map(|r| r.rel_key.as_str())
+    }
+
+    fn take(&mut self, rel: &str) -> Option<SyncRow> {
+        if self.peek() == Some(rel) {
+            self.buf.pop_front()
+        } else {
+            None
+        }
+    }
+}
+
+fn tombstone_retention_secs(rule: &ReplicationRule) -> i64 {
+    humantime::parse_duration(&rule.tombstone_retention)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(30 * 86_400)
+}
+
+/// One full sweep of a bidirectional rule. Same contract as `run_rule` (which
+/// dispatches here): run-history row, lease heartbeat, kill/pause/lease
+/// checks at each batch boundary, settle.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn run_bidirectional(
+    db: Arc<Mutex<ConfigDb>>,
+    engine: &Arc<DynEngine>,
+    rule: &ReplicationRule,
+    max_failures_retained: u32,
+    object_timeout: Option<std::time::Duration>,
+    triggered_by: &str,
+    lease: Option<RunLease>,
+    concurrency: RunConcurrency,
+    maintenance_gate: Option<Arc<crate::maintenance::gate::MaintenanceGate>>,
+    coordination_lease: Option<Arc<dyn crate::coordination::CoordinationLease>>,
+) -> Result<(i64, RunOutcome), ConfigDbError> {
+    let transfers = concurrency.transfers.clamp(1, 64) as usize;
+    let upload_concurrency = concurrency.upload_concurrency.clamp(1, 16) as usize;
+    let started_at = current_unix_seconds();
+    let (run_id, conflicted) = {
+        let db = db.lock().await;
+        db.replication_ensure_state(&rule.name, started_at)?;
+        let id = db.replication_begin_run(&rule.name, started_at, triggered_by)?;
+        (id, db.replication_conflict_keys(&rule.name)?)
+    };
+    let ends = Endpoints::new(rule);
+    info!(
+        "Bidirectional replication run starting: rule='{}' {}/{} <-> {}/{}",
+        rule.name, rule.source.bucket, ends.src_prefix, rule.destination.bucket, ends.dest_prefix,
+    );
+
+    let lease_alive = Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let heartbeat_handle = spawn_lease_heartbeat(
+        db.clone(),
+        &rule.name,
+        lease.clone(),
+        coordination_lease.clone(),
+        lease_alive.clone(),
+    );
+    // RunControl watches the destination's maintenance gate; both sides take
+    // writes here, so the source's is checked alongside it.
+    let source_gate = maintenance_gate.clone();
+    let ctrl = RunControl {
+        db: db.clone(),
+        rule_name: rule.name.clone(),
+        run_id,
+        lease,
+        coordination_lease,
+        lease_alive,
+        one_off: triggered_by == "run-now",
+        max_failures_retained,
+        dest_bucket: rule.destination.bucket.clone(),
+        maintenance_gate,
+        windows: (triggered_by != "run-now").then(|| super::throttle::parse_windows(&rule.windows)),
+    };
+    let events: EventSink = Default::default();
+    let ctx = SyncCtx {
+        engine,
+        ends: &ends,
+        object_timeout,
+        upload_concurrency: Some(upload_concurrency),
+        bandwidth: super::throttle::budget(rule),
+        tombstone_retention_secs: tombstone_retention_secs(rule),
+        events: &events,
+    };
+
+    let mut totals = RunTotals::default();
+    let (mut killed, mut stopped_paused) = (false, false);
+    let (mut hit_fatal_error, mut had_any_error) = (false, false);
+    let mut window_reopens: Option<Option<i64>> = None;
+    let cap = rule.batch_size.clamp(1, 10_000) as usize;
+
+    let globs = match compile_rule_globs(rule) {
+        _ if super::external::is_external(rule) => {
+            warn!("replication rule '{}': {EXTERNAL_REFUSED}", rule.name);
+            totals.errors += 1;
+            hit_fatal_error = true;
+            None
+        }
+        Ok(globs) => Some(globs),
+        Err(e) => {
+            warn!("replication rule '{}' glob compile failed: {e}", rule.name);
+            totals.errors += 1;
+            hit_fatal_error = true;
+            None
+        }
+    };
+    let mut src = SideListing::new(&rule.source.bucket, &ends.src_prefix);
+    let mut dest = SideListing::new(&rule.destination.bucket, &ends.dest_prefix);
+    let mut rows = SyncRows {
+        after: String::new(),
+        buf: VecDeque::new(),
+        exhausted: false,
+    };
+
+    'sweep: while let Some((include, exclude)) = &globs {
+        match ctrl.check(true).await {
+            Err(e) => {
+                warn!(
+                    "replication rule '{}': control check failed: {e}",
+                    rule.name
+                );
+                totals.errors += 1;
+                hit_fatal_error = true;
+                break;
+            }
+            Ok(ControlVerdict::Continue) => {}
+            Ok(ControlVerdict::Killed) => {
+                info!("replication rule '{}' killed mid-sweep", rule.name);
+                killed = true;
+                break;
+            }
+            Ok(ControlVerdict::Paused) => {
+                stopped_paused = true;
+                break;
+            }
+            Ok(ControlVerdict::LeaseLost) => {
+                totals.errors += 1;
+                hit_fatal_error = true;
+                break;
+            }
+            Ok(ControlVerdict::OutsideWindow(opens_at)) => {
+                stopped_paused = true;
+                window_reopens = Some(opens_at);
+                break;
+            }
+        }
+        if source_gate
+            .as_ref()
+            .is_some_and(|g| g.is_busy(&rule.source.bucket))
+        {
+            stopped_paused = true;
+            break;
+        }
+
+        // Merge-join the next batch of keys across both listings and the
+        // sync rows.
+        let mut batch = Vec::with_capacity(cap);
+        let mut listing_error = None;
+        while batch.len() < cap {
+            if let Err(e) = src.fill(engine).await {
+                listing_error = Some(e);
+                break;
+            }
+            if let Err(e) = dest.fill(engine).await {
+                listing_error = Some(e);
+                break;
+            }
+            rows.fill(&db, &rule.name).await?;
+            let Some(rel) = [src.peek(), dest.peek(), rows.peek()]
+                .into_iter()
+                .flatten()
+                .min()
+                .map(str::to_string)
+            else {
+                break;
+            };
+            let job = KeyJob {
+                src: src.take(&rel),
+                dest: dest.take(&rel),
+                row: rows.take(&rel),
+                conflicted: conflicted.contains(&rel),
+                rel,
+            };
+            let abs_src = ends.abs(Side::Source, &job.rel);
+            if !is_user_object_key(&abs_src)
+                || exclude.is_match(&abs_src)
+                || (!include.is_empty() && !include.is_match(&abs_src))
+            {
+                continue;
+            }
+            totals.objects_scanned += 1;
+            batch.push(job);
+        }
+        if let Some(e) = listing_error {
+            warn!("replication rule '{}': {e}", rule.name);
+            totals.errors += 1;
+            hit_fatal_error = true;
+            let db = db.lock().await;
+            let _ = db.replication_record_failure(
+                &rule.name,
+                FailureInsert {
+                    run_id: Some(run_id),
+                    occurred_at: current_unix_seconds(),
+                    source_key: "",
+                    dest_key: "",
+                    error_message: &e,
+                },
+                max_failures_retained,
+            );
+            break 'sweep;
+        }
+        if batch.is_empty() {
+            break;
+        }
+
+        let results: Vec<KeyResult> = futures::stream::iter(batch)
+            .map(|job| sync_one(&ctx, job))
+            .buffer_unordered(transfers)
+            .collect()
+            .await;
+
+        let db_guard = db.lock().await;
+        for r in &results {
+            if let Err(e) = apply_effects(&db_guard, &rule.name, r) {
+                warn!(
+                    "replication rule '{}': sync state write for '{}' failed: {e}",
+                    rule.name, r.rel
+                );
+                totals.errors += 1;
+                had_any_error = true;
+            }
+            if r.copied {
+                totals.objects_copied += 1;
+                totals.bytes_copied += r.bytes_copied;
+                totals.delta_passthrough += r.delta_passthrough;
+                totals.bytes_egress_saved += r.bytes_egress_saved;
+                totals.reconstructed += r.reconstructed;
+            }
+            if r.deleted {
+                totals.objects_deleted += 1;
+            }
+            if r.skipped {
+                totals.objects_skipped += 1;
+            }
+            if let Some(msg) = &r.error {
+                totals.errors += 1;
+                had_any_error = true;
+                let _ = db_guard.replication_record_failure(
+                    &rule.name,
+                    FailureInsert {
+                        run_id: Some(run_id),
+                        occurred_at: current_unix_seconds(),
+                        source_key: &ends.abs(Side::Source, &r.rel),
+                        dest_key: &ends.abs(Side::Destination, &r.rel),
+                        error_message: msg,
+                    },
+                    max_failures_retained,
+                );
+            }
+        }
+        if let Err(e) = db_guard.replication_update_run_progress(run_id, totals) {
+            warn!(
+                "replication rule '{}': run progress persist failed: {e}",
+                rule.name
+            );
+        }
+        drop(db_guard);
+        flush_event_sink(&db, &rule.name, &events).await;
+    }
+    flush_event_sink(&db, &rule.name, &events).await;
+
+    let finished_at = current_unix_seconds();
+    let settle_result: Result<String, ConfigDbError> = async {
+        let db = db.lock().await;
+        if !killed && db.replication_run_cancel_requested(run_id).unwrap_or(false) {
+            killed = true;
+        }
+        let decision = settle_run(SettleInput {
+            killed,
+            stopped_paused,
+            hit_fatal_error,
+            had_any_error,
+            objects_copied: totals.objects_copied,
+            truncated: false,
+        });
+        let next_due = if hit_fatal_error {
+            finished_at + 60
+        } else if let Some(opens_at) = window_reopens {
+            opens_at.unwrap_or_else(|| compute_next_due(rule, finished_at))
+        } else {
+            compute_next_due(rule, finished_at)
+        };
+        if !db.replication_finish_run(
+            run_id,
+            &rule.name,
+            decision.status,
+            finished_at,
+            totals,
+            next_due,
+        )? {
+            warn!(
+                "replication rule '{}' run {} already terminal; settle to '{}' skipped",
+                rule.name, run_id, decision.status
+            );
+        }
+        Ok(decision.status.to_string())
+    }
+    .await;
+    super::state_store::bump_replication_run_version();
+    if let Some(handle) = heartbeat_handle {
+        handle.abort();
+    }
+    let status = settle_result?;
+    info!(
+        "Bidirectional replication run finished: rule='{}' status={} scanned={} copied={} deleted={} errors={}",
+        rule.name,
+        status,
+        totals.objects_scanned,
+        totals.objects_copied,
+        totals.objects_deleted,
+        totals.errors,
+    );
+    Ok((run_id, RunOutcome { status, totals }))
+}
+
+/// Event path: sync the key behind one outbox event (`bucket`/`key` on either
+/// side of `rule`). The caller holds the rule's lease. Events for replicas
+/// this rule wrote are ignored — that is the loop guard for copies that land
+/// through an S3 API and so emit events of their own.
+pub async fn sync_key(
+    engine: &Arc<DynEngine>,
+    db: &Arc<Mutex<ConfigDb>>,
+    rule: &ReplicationRule,
+    bucket: &str,
+    key: &str,
+) -> Result<(), String> {
+    if super::external::is_external(rule) {
+        return Ok(()); // refused; the sweep reports it
+    }
+    let ends = Endpoints::new(rule);
+    let Some(rel) = ends.rel_of(bucket, key) else {
+        return Ok(());
+    };
+    let abs_src = ends.abs(Side::Source, &rel);
+    let (include, exclude) = compile_rule_globs(rule).map_err(|e| e.to_string())?;
+    if !is_user_object_key(&abs_src)
+        || exclude.is_match(&abs_src)
+        || (!include.is_empty() && !include.is_match(&abs_src))
+    {
+        return Ok(());
+    }
+    match engine.head(bucket, key).await {
+        Ok(meta) if owned_by_rule(&meta, &rule.name) => return Ok(()),
+        Ok(_) | Err(EngineError::NotFound(_)) => {}
+        Err(e) => return Err(e.to_string()),
+    }
+
+    let events: EventSink = Default::default();
+    let ctx = SyncCtx {
+        engine,
+        ends: &ends,
+        object_timeout: None,
+        upload_concurrency: None,
+        bandwidth: super::throttle::budget(rule),
+        tombstone_retention_secs: tombstone_retention_secs(rule),
+        events: &events,
+    };
+    let (row, conflicted) = {
+        let db = db.lock().await;
+        (
+            db.replication_sync_row(&rule.name, &rel)
+                .map_err(|e| e.to_string())?,
+            db.replication_conflict_get(&rule.name, &rel)
+                .map_err(|e| e.to_string())?
+                .is_some(),
+        )
+    };
+    let job = KeyJob {
+        src: ctx.probe(Side::Source, &rel).await?,
+        dest: ctx.probe(Side::Destination, &rel).await?,
+        row,
+        conflicted,
+        rel,
+    };
+    let result = sync_one(&ctx, job).await;
+    apply_effects(&*db.lock().await, &rule.name, &result).map_err(|e| e.to_string())?;
+    flush_event_sink(db, &rule.name, &events).await;
+    match result.error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// What a manual resolution did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// The kept side was copied over the other.
+    Copied,
+    /// The kept side is deleted, so the other side was deleted too.
+    Deleted,
+}
+
+/// Resolve a conflict by keeping `keep`'s version (or its absence) on both
+/// sides, then clear it. The caller holds the rule's lease.
+pub async fn resolve_conflict(
+    engine: &Arc<DynEngine>,
+    db: &Arc<Mutex<ConfigDb>>,
+    rule: &ReplicationRule,
+    rel: &str,
+    keep: Side,
+) -> Result<Resolution, String> {
+    if super::external::is_external(rule) {
+        return Err(EXTERNAL_REFUSED.to_string());
+    }
+    let ends = Endpoints::new(rule);
+    let events: EventSink = Default::default();
+    let ctx = SyncCtx {
+        engine,
+        ends: &ends,
+        object_timeout: None,
+        upload_concurrency: None,
+        bandwidth: super::throttle::budget(rule),
+        tombstone_retention_secs: tombstone_retention_secs(rule),
+        events: &events,
+    };
+    let other = keep.other();
+    let (row, resolution) = match ctx.head(keep, rel).await? {
+        Some(meta) => {
+            let stamp = HlcStamp::of_object(&meta, ends.bucket(keep));
+            clock().observe(&stamp);
+            copy_stamped(&ctx, rel, keep, &meta, &stamp).await?;
+            let src_fp = ctx.probe(Side::Source, rel).await?.map(|l| l.fp);
+            let dest_fp = ctx.probe(Side::Destination, rel).await?.map(|l| l.fp);
+            (live_row(rel, &stamp, src_fp, dest_fp), Resolution::Copied)
+        }
+        None => {
+            let (bucket, key) = (ends.bucket(other), ends.abs(other, rel));
+            match engine.delete(bucket, &key).await {
+                Ok(_) | Err(EngineError::NotFound(_)) => {}
+                Err(e) => return Err(format!("delete {bucket}/{key} failed: {e}")),
+            }
+            (
+                tombstone_row(rel, &clock().now(ends.bucket(keep))),
+                Resolution::Deleted,
+            )
+        }
+    };
+    {
+        let db = db.lock().await;
+        db.replication_sync_upsert(&rule.name, &row)
+            .and_then(|_| db.replication_conflict_clear(&rule.name, rel))
+            .map_err(|e| e.to_string())?;
+    }
+    flush_event_sink(db, &rule.name, &events).await;
+    Ok(resolution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(src_fp: Option<&str>, dest_fp: Option<&str>, tombstone: bool) -> SyncRow {
+        SyncRow {
+            rel_key: "k".into(),
+            stamp: "0000000001000.00000@a".into(),
+            src_fp: src_fp.map(str::to_string),
+            dest_fp: dest_fp.map(str::to_string),
+            tombstone,
+            updated_at: 0,
+        }
+    }
+
+    fn copy(from: Side) -> Plan {
+        Plan::Copy { from }
+    }
+
+    #[test]
+    fn plan_without_history() {
+        assert_eq!(plan_key(None, None, None, true, false), Plan::InSync);
+        assert_eq!(
+            plan_key(Some("s"), None, None, true, false),
+            copy(Side::Source)
+        );
+        assert_eq!(
+            plan_key(None, Some("d"), None, true, false),
+            copy(Side::Destination)
+        );
+        assert_eq!(
+            plan_key(Some("s"), Some("d"), None, true, false),
+            Plan::Compare {
+                kind: ConflictKind::BothCreated
+            }
+        );
+    }
+
+    #[test]
+    fn plan_against_a_synced_row() {
+        let synced = row(Some("s1"), Some("d1"), false);
+        let plan = |s, d, deletes| plan_key(s, d, Some(&synced), deletes, false);
+        assert_eq!(plan(Some("s1"), Some("d1"), true), Plan::InSync);
+        assert_eq!(plan(Some("s2"), Some("d1"), true), copy(Side::Source));
+        assert_eq!(plan(Some("s1"), Some("d2"), true), copy(Side::Destination));
+        assert_eq!(
+            plan(Some("s2"), Some("d2"), true),
+            Plan::Compare {
+                kind: ConflictKind::BothModified
+            }
+        );
+        // Deleted on one side, untouched on the other: propagate the delete.
+        assert_eq!(
+            plan(None, Some("d1"), true),
+            Plan::Delete {
+                side: Side::Destination
+            }
+        );
+        assert_eq!(
+            plan(Some("s1"), None, true),
+            Plan::Delete { side: Side::Source }
+        );
+        // Deleted on one side, modified on the other: conflict.
+        assert_eq!(
+            plan(None, Some("d2"), true),
+            Plan::Conflict {
+                kind: ConflictKind::DeletedOnSource
+            }
+        );
+        assert_eq!(
+            plan(Some("s2"), None, true),
+            Plan::Conflict {
+                kind: ConflictKind::DeletedOnDestination
+            }
+        );
+        // Deletes not replicated: the surviving copy is restored.
+        assert_eq!(plan(None, Some("d1"), false), copy(Side::Destination));
+        assert_eq!(plan(Some("s2"), None, false), copy(Side::Source));
+        // Gone on both sides.
+        assert_eq!(plan(None, None, true), Plan::Tombstone);
+        assert_eq!(plan(None, None, false), Plan::Forget);
+    }
+
+    #[test]
+    fn unknown_row_fingerprint_counts_as_changed() {
+        let half = row(Some("s1"), None, false);
+        assert_eq!(
+            plan_key(Some("s1"), Some("d1"), Some(&half), true, false),
+            copy(Side::Destination)
+        );
+        assert_eq!(
+            plan_key(None, Some("d1"), Some(&half), true, false),
+            Plan::Conflict {
+                kind: ConflictKind::DeletedOnSource
+            }
+        );
+    }
+
+    #[test]
+    fn plan_against_a_tombstone() {
+        let t = row(None, None, true);
+        assert_eq!(plan_key(None, None, Some(&t), true, false), Plan::InSync);
+        assert_eq!(plan_key(None, None, Some(&t), true, true), Plan::Forget);
+        assert_eq!(
+            plan_key(Some("s"), None, Some(&t), true, false),
+            Plan::Revive { side: Side::Source }
+        );
+        assert_eq!(
+            plan_key(None, Some("d"), Some(&t), true, false),
+            Plan::Revive {
+                side: Side::Destination
+            }
+        );
+        assert_eq!(
+            plan_key(None, Some("d"), Some(&t), false, false),
+            copy(Side::Destination)
+        );
+        assert_eq!(
+            plan_key(Some("s"), Some("d"), Some(&t), true, false),
+            Plan::Compare {
+                kind: ConflictKind::BothCreated
+            }
+        );
+    }
+
+    fn meta(
+        sha: &str,
+        size: u64,
+        created_ms: i64,
+        owner: Option<&str>,
+        hlc: Option<&str>,
+    ) -> FileMetadata {
+        let mut m = FileMetadata::new_passthrough("k".into(), sha.into(), "md5".into(), size, None);
+        m.created_at = chrono::DateTime::from_timestamp_millis(created_ms).unwrap();
+        if let Some(owner) = owner {
+            m.user_metadata
+                .insert(REPLICATION_RULE_METADATA_KEY.into(), owner.into());
+        }
+        if let Some(hlc) = hlc {
+            m.user_metadata.insert(HLC_METADATA_KEY.into(), hlc.into());
+        }
+        m
+    }
+
+    #[test]
+    fn compare_adopts_identical_content() {
+        let kind = ConflictKind::BothCreated;
+        let (s, d) = (meta("a", 3, 1, None, None), meta("a", 3, 9, None, None));
+        assert_eq!(resolve_compare(kind, &s, &d, "r", "sa", "sb"), Plan::Adopt);
+    }
+
+    #[test]
+    fn compare_prefers_the_client_write_over_our_replica() {
+        let kind = ConflictKind::BothModified;
+        let ours = meta("a", 3, 1, Some("r"), None);
+        let theirs = meta("b", 3, 1, None, None);
+        assert_eq!(
+            resolve_compare(kind, &ours, &theirs, "r", "sa", "sb"),
+            copy(Side::Destination)
+        );
+        assert_eq!(
+            resolve_compare(kind, &theirs, &ours, "r", "sa", "sb"),
+            copy(Side::Source)
+        );
+        // Another rule's marker is a client write as far as this rule goes.
+        let foreign = meta("a", 3, 1, Some("other"), None);
+        assert_eq!(
+            resolve_compare(kind, &foreign, &theirs, "r", "sa", "sb"),
+            Plan::Conflict { kind }
+        );
+    }
+
+    #[test]
+    fn compare_orders_two_replicas_by_hlc() {
+        let kind = ConflictKind::BothModified;
+        let older = meta("a", 3, 1, Some("r"), Some("0000000000005.00000@sa"));
+        let newer = meta("b", 3, 1, Some("r"), Some("0000000000005.00001@sb"));
+        assert_eq!(
+            resolve_compare(kind, &older, &newer, "r", "sa", "sb"),
+            copy(Side::Destination)
+        );
+        assert_eq!(
+            resolve_compare(kind, &newer, &older, "r", "sa", "sb"),
+            copy(Side::Source)
+        );
+    }
+
+    #[test]
+    fn compare_holds_two_client_writes() {
+        let kind = ConflictKind::BothModified;
+        let (s, d) = (meta("a", 3, 1, None, None), meta("b", 3, 2, None, None));
+        assert_eq!(
+            resolve_compare(kind, &s, &d, "r", "sa", "sb"),
+            Plan::Conflict { kind }
+        );
+    }
+
+    #[test]
+    fn revive_copies_newer_and_deletes_stale_versions() {
+        let tombstone: HlcStamp = "0000000001000.00000@sa".parse().unwrap();
+        let recreated = meta("a", 3, 2_000, None, None);
+        let stale = meta("a", 3, 5_000, Some("r"), Some("0000000000500.00000@sb"));
+        assert_eq!(
+            resolve_revive(Side::Source, &recreated, "r", "sa", Some(&tombstone)),
+            copy(Side::Source)
+        );
+        assert_eq!(
+            resolve_revive(Side::Destination, &stale, "r", "sb", Some(&tombstone)),
+            Plan::Delete {
+                side: Side::Destination
+            }
+        );
+        assert_eq!(
+            resolve_revive(Side::Source, &stale, "r", "sa", None),
+            copy(Side::Source)
+        );
+        // Another rule's replica is not ours to delete.
+        assert_eq!(
+            resolve_revive(Side::Destination, &stale, "other", "sb", Some(&tombstone)),
+            Plan::Conflict {
+                kind: ConflictKind::DeletedOnSource
+            }
+        );
+    }
+
+    #[test]
+    fn revive_never_deletes_a_client_write_behind_a_skewed_clock() {
+        // The re-creating site's clock runs behind the tombstone's HLC.
+        let tombstone: HlcStamp = "0000000009000.00000@sa".parse().unwrap();
+        let rewritten = meta("b", 3, 4_000, None, None);
+        assert_eq!(
+            resolve_revive(Side::Destination, &rewritten, "r", "sb", Some(&tombstone)),
+            Plan::Conflict {
+                kind: ConflictKind::DeletedOnSource
+            }
+        );
+        assert_eq!(
+            resolve_revive(Side::Source, &rewritten, "r", "sa", Some(&tombstone)),
+            Plan::Conflict {
+                kind: ConflictKind::DeletedOnDestination
+            }
+        );
+    }
+
+    #[test]
+    fn rel_of_maps_either_side() {
+        let rule = ReplicationRule {
+            source: crate::config_sections::ReplicationEndpoint {
+                bucket: "a".into(),
+                prefix: "in".into(),
+                external: None,
+            },
+            destination: crate::config_sections::ReplicationEndpoint {
+                bucket: "a".into(),
+                prefix: "out/".into(),
+                external: None,
+            },
+            ..serde_yaml::from_str("{name: r, source: {bucket: x}, destination: {bucket: y}}")
+                .unwrap()
+        };
+        let ends = Endpoints::new(&rule);
+        assert_eq!(ends.rel_of("a", "in/x").as_deref(), Some("x"));
+        assert_eq!(ends.rel_of("a", "out/y/z").as_deref(), Some("y/z"));
+        assert_eq!(ends.rel_of("a", "elsewhere"), None);
+        assert_eq!(ends.rel_of("b", "in/x"), None);
+        assert_eq!(ends.abs(Side::Destination, "x"), "out/x");
+    }
+}
@@ -38,7 +38,7 @@ use tracing::{debug, info, warn};
 use crate::api::handlers::AppState;
 use crate::config::SharedConfig;
 use crate::config_db::ConfigDb;
-use crate::config_sections::ReplicationRule;
+use crate::config_sections::{ReplicationDirection, ReplicationRule};
 use crate::event_outbox::{
     current_unix_seconds, EventKind, EventOutboxRecord, EventSource, NewEvent,
 };
@@ -92,13 +92,17 @@ fn liveness_of_kind(kind: EventKind) -> Option<KeyAction> {
         | EventKind::ReplicationObjectCopied
         | EventKind::LifecycleTransitioned => Some(KeyAction::Copy),
         EventKind::ObjectDeleted | EventKind::LifecycleExpired => Some(KeyAction::Delete),
+        EventKind::MirrorRepair
+        | EventKind::ObjectAccessed
+        | EventKind::AccessDenied
+        | EventKind::AuthFailed => None,
     }
 }
 
 /// Parse a raw outbox `kind` string back to the typed [`EventKind`], or `None`
 /// for an unrecognized string (an old/foreign event kind that this build does
 /// not know about). Mirrors [`EventKind::as_str`].
-fn parse_event_kind(kind: &str) -> Option<EventKind> {
+pub(crate) fn parse_event_kind(kind: &str) -> Option<EventKind> {
     match kind {
         "ObjectCreated" => Some(EventKind::ObjectCreated),
         "ObjectDeleted" => Some(EventKind::ObjectDeleted),
@@ -106,10 +110,22 @@ fn parse_event_kind(kind: &str) -> Option<EventKind> {
         "ReplicationObjectCopied" => Some(EventKind::ReplicationObjectCopied),
         "LifecycleExpired" => Some(EventKind::LifecycleExpired),
         "LifecycleTransitioned" => Some(EventKind::LifecycleTransitioned),
+        "MirrorRepair" => Some(EventKind::MirrorRepair),
+        "ObjectAccessed" => Some(EventKind::ObjectAccessed),
+        "AccessDenied" => Some(EventKind::AccessDenied),
+        "AuthFailed" => Some(EventKind::AuthFailed),
         _ => None,
     }
 }
 
+/// Known event kinds that are NOT object-state transitions (internal work
+/// items and read/security events sharing the outbox). The consumer drops them before compaction so
+/// they can neither mask a key's real terminal event nor trip the
+/// unrecognized-kind warning.
+pub(crate) fn is_non_object_kind(kind: &str) -> bool {
+    parse_event_kind(kind).is_some_and(|k| liveness_of_kind(k).is_none())
+}
+
 /// Event kinds whose effect leaves the object PRESENT at the source.
 fn is_present_producing(kind: &str) -> bool {
     parse_event_kind(kind).and_then(liveness_of_kind) == Some(KeyAction::Copy)
@@ -233,7 +249,7 @@ pub fn group_events_by_key<'a>(
 /// the consumer can pre-filter before compiling globsets).
 ///
 /// A key may match multiple rules — all are returned; the consumer fans the
-/// action out to each.
+/// action out to each. A bidirectional rule matches on EITHER endpoint.
 pub fn match_rules<'a>(
     rules: &'a [ReplicationRule],
     bucket: &str,
@@ -242,16 +258,24 @@ pub fn match_rules<'a>(
     if !is_user_object_key(key) {
         return Vec::new();
     }
+    // Empty normalized prefix == whole bucket; otherwise require the key to
+    // live under it (prefix already carries a trailing slash, so this is a
+    // true path-boundary match, not a substring one). An external endpoint
+    // never matches: its bucket only shares a name with a proxy bucket, and
+    // its writes never reach the outbox (reconcile sweeps cover it).
+    let under = |endpoint: &crate::config_sections::ReplicationEndpoint| {
+        let prefix = normalize_prefix(&endpoint.prefix);
+        endpoint.external.is_none()
+            && endpoint.bucket == bucket
+            && (prefix.is_empty() || key.starts_with(&prefix))
+    };
     rules
         .iter()
         .filter(|rule| rule.enabled)
-        .filter(|rule| rule.source.bucket == bucket)
         .filter(|rule| {
-            let prefix = normalize_prefix(&rule.source.prefix);
-            // Empty normalized prefix == whole bucket; otherwise require the key
-            // to live under it (prefix already carries a trailing slash, so this
-            // is a true path-boundary match, not a substring one).
-            prefix.is_empty() || key.starts_with(&prefix)
+            under(&rule.source)
+                || (rule.direction == ReplicationDirection::Bidirectional
+                    && under(&rule.destination))
         })
         .collect()
 }
@@ -391,13 +415,25 @@ async fn drain_once(
     let engine = state.engine.load();
 
     // Compile globsets once per drain, keyed by rule name.
-    let groups = group_events_by_key(&rows);
+    let object_rows: Vec<EventOutboxRecord> = rows
+        .iter()
+        .filter(|r| !is_non_object_kind(&r.kind))
+        .cloned()
+        .collect();
+    let groups = group_events_by_key(&object_rows);
 
     // Track the highest contiguous id fully handled. We process keys in id
     // order of their LAST event; but the safe contiguous watermark is computed
     // over the raw rows: walk rows ascending and stop advancing at the first id
     // whose key-action failed.
     let mut failed_ids: std::collections::BTreeSet<i64> = std::collections::BTreeSet::new();
+    // Rules outside their transfer window this drain. Their keys are NOT
+    // stalled (holding the shared cursor would starve every other rule);
+    // the rule is marked due instead, and the reconcile that runs when the
+    // window opens picks the changes up.
+    super::throttle::configure(replication);
+    super::external::configure(replication);
+    let mut deferred: std::collections::HashMap<&str, bool> = std::collections::HashMap::new();
 
     for ((bucket, key), recs) in &groups {
         let kinds: Vec<&str> = recs.iter().map(|r| r.kind.as_str()).collect();
@@ -413,10 +449,19 @@ async fn drain_once(
         }
 
         for rule in matched {
+            let closed = *deferred
+                .entry(rule.name.as_str())
+                .or_insert_with(|| !super::throttle::rule_gate(rule).open);
+            if closed {
+                continue;
+            }
             // Maintenance gate: the destination bucket is being rewritten in
             // place (re-encryption). Stall this key's events — the cursor
             // does not advance past them, so they replay after the job ends.
-            if state.maintenance_gate.is_busy(&rule.destination.bucket) {
+            let bidirectional = rule.direction == ReplicationDirection::Bidirectional;
+            if super::external::under_maintenance(&state.maintenance_gate, &rule.destination)
+                || (bidirectional && state.maintenance_gate.is_busy(&rule.source.bucket))
+            {
                 debug!(
                     "event consumer: rule '{}' deferred — destination '{}' under maintenance",
                     rule.name, rule.destination.bucket
@@ -472,7 +517,15 @@ async fn drain_once(
                 }
             }
 
-            let outcome = apply_action(&engine, db, rule, bucket, key, action).await;
+            // A bidirectional rule re-derives the action from both sides and
+            // its sync state, so the compacted action only says "look".
+            let outcome = if bidirectional {
+                super::bidi::sync_key(&engine, db, rule, bucket, key)
+                    .await
+                    .map_err(Into::into)
+            } else {
+                apply_action(&engine, db, rule, bucket, key, action).await
+            };
 
             {
                 let dbg = db.lock().await;
@@ -508,6 +561,15 @@ async fn drain_once(
         }
     }
 
+    if deferred.values().any(|closed| *closed) {
+        let dbg = db.lock().await;
+        for (rule_name, _) in deferred.iter().filter(|(_, closed)| **closed) {
+            debug!("event consumer: rule '{rule_name}' outside its transfer window; deferred to reconcile");
+            let _ = dbg.replication_ensure_state(rule_name, now);
+            let _ = dbg.replication_mark_due(rule_name, now);
+        }
+    }
+
     // Advance the cursor to the highest CONTIGUOUS id with no failure (anything
     // at or past the first failed id is left for next tick → at-least-once).
     let watermark = contiguous_watermark(&rows, &failed_ids, cursor);
@@ -545,6 +607,9 @@ async fn apply_action(
     action: KeyAction,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let dest_key = rewrite_key(&rule.source.prefix, &rule.destination.prefix, key)?;
+    // The source is always proxy-routed here (`match_rules`); the destination
+    // may be an external endpoint with its own engine.
+    let ends = super::external::rule_engines(engine, rule).await?;
 
     match action {
         KeyAction::Noop => Ok(()),
@@ -559,7 +624,11 @@ async fn apply_action(
                 }
                 Err(e) => return Err(Box::new(e)),
             };
-            let dest_meta = engine.head(&rule.destination.bucket, &dest_key).await.ok();
+            let dest_meta = ends
+                .destination
+                .head(&rule.destination.bucket, &dest_key)
+                .await
+                .ok();
             let (include_globs, exclude_globs) = compile_rule_globs(rule)?;
             let decision = should_replicate(
                 key,
@@ -575,6 +644,7 @@ async fn apply_action(
                 // never rewrites), so we always use the prefix-rewritten
                 // `dest_key` computed above — same as the reconcile worker.
                 Decision::Copy { .. } => {
+                    let bandwidth = super::throttle::budget(rule);
                     let transfer = ObjectTransferRequest {
                         source_bucket: &rule.source.bucket,
                         source_key: key,
@@ -584,11 +654,14 @@ async fn apply_action(
                             metadata_key: REPLICATION_RULE_METADATA_KEY,
                             metadata_value: &rule.name,
                         }),
+                        extra_user_metadata: &[],
                         strip_user_metadata_keys: &[],
                         operation: "replication-event",
                         upload_concurrency: None,
+                        bandwidth: Some(&bandwidth),
+                        source_engine: ends.cross_source(),
                     };
-                    let outcome = copy_object_with_retries(engine, transfer).await?;
+                    let outcome = copy_object_with_retries(&ends.destination, transfer).await?;
                     // Emit ReplicationObjectCopied so the chain is observable
                     // (mirrors the reconcile worker).
                     emit_replication_copied(db, rule, key, &dest_key, outcome.bytes_copied).await;
@@ -611,7 +684,11 @@ async fn apply_action(
                 // Source reappeared → do NOT delete; the copy path will re-sync.
                 Ok(_) => Ok(()),
                 Err(crate::deltaglider::EngineError::NotFound(_)) => {
-                    match engine.delete(&rule.destination.bucket, &dest_key).await {
+                    match ends
+                        .destination
+                        .delete(&rule.destination.bucket, &dest_key)
+                        .await
+                    {
                         Ok(_) => Ok(()),
                         // Dest already gone → nothing to delete.
                         Err(crate::deltaglider::EngineError::NotFound(_)) => Ok(()),
@@ -655,7 +732,9 @@ async fn emit_replication_copied(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config_sections::{ConflictPolicy, ReplicationEndpoint, ReplicationRule};
+    use crate::config_sections::{
+        ConflictPolicy, ReplicationDirection, ReplicationEndpoint, ReplicationRule,
+    };
 
     fn rule(name: &str, src_bucket: &str, src_prefix: &str, enabled: bool) -> ReplicationRule {
         ReplicationRule {
@@ -664,16 +743,22 @@ mod tests {
             source: ReplicationEndpoint {
                 bucket: src_bucket.to_string(),
                 prefix: src_prefix.to_string(),
+                external: None,
             },
             destination: ReplicationEndpoint {
                 bucket: "dest".to_string(),
                 prefix: String::new(),
+                external: None,
             },
             interval: "24h".to_string(),
             batch_size: 100,
             replicate_deletes: false,
+            direction: ReplicationDirection::OneWay,
+            tombstone_retention: "30d".into(),
             conflict: ConflictPolicy::default(),
             strict_content_diff: false,
+            bandwidth_limit_mbps: None,
+            windows: Vec::new(),
             include_globs: Vec::new(),
             exclude_globs: Vec::new(),
         }
@@ -842,6 +927,35 @@ mod tests {
         assert!(!match_rules(&rules, "beshu", "ror/real.zip").is_empty());
     }
 
+    #[test]
+    fn match_rules_bidirectional_matches_either_endpoint() {
+        let mut two_way = rule("two-way", "office-a", "shared/", true);
+        two_way.direction = ReplicationDirection::Bidirectional;
+        two_way.destination.bucket = "office-b".into();
+        let one_way = rule("one-way", "office-c", "", true);
+        let rules = vec![two_way, one_way];
+        assert_eq!(match_rules(&rules, "office-a", "shared/x").len(), 1);
+        assert_eq!(match_rules(&rules, "office-b", "anything/x").len(), 1);
+        assert!(match_rules(&rules, "office-a", "private/x").is_empty());
+        // A one-way rule still only matches its source.
+        assert!(match_rules(&rules, "dest", "x").is_empty());
+    }
+
+    #[test]
+    fn match_rules_ignores_external_source() {
+        let mut pull = rule("pull", "beshu", "", true);
+        pull.source.external =
+            Some(serde_yaml::from_str("endpoint: https://s3.vendor.example").unwrap());
+        let push = rule("push", "beshu", "", true);
+        let rules = vec![pull, push];
+        // Same bucket name, but the external one is another endpoint's bucket.
+        let m = match_rules(&rules, "beshu", "ror/x.zip");
+        assert_eq!(
+            m.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(),
+            vec!["push"]
+        );
+    }
+
     #[test]
     fn match_rules_multi_rule_fanout() {
         let rules = vec![
@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! External replication endpoints: a rule side that is not a proxy-routed
+//! bucket but a bucket on an arbitrary S3 endpoint, reached with its own
+//! credentials (`rules[].source.external` / `rules[].destination.external`).
+//!
+//! Each endpoint gets its own engine over an `S3Backend` — the same client,
+//! timeouts and SSRF guard as a configured backend — built on first use and
+//! kept in a process-wide registry so a rule's sweeps and event-driven copies
+//! share one client. The copy path is unchanged: it reads through the source
+//! side's engine and stores through the destination side's.
+//!
+//! A **hydrated** endpoint (the default) holds plain objects: compression is
+//! off for its bucket, so a destination receives fully reconstructed objects
+//! any S3 client can read, and a source is read as-is. `hydrated: false`
+//! treats the bucket as DeltaGlider storage layout (another proxy's backing
+//! bucket): reads reconstruct deltas and writes delta-compress.
+
+use crate::bucket_policy::BucketPolicyConfig;
+use crate::config::{BackendConfig, Config};
+use crate::config_sections::{
+    ExternalS3Endpoint, ReplicationConfig, ReplicationEndpoint, ReplicationRule,
+};
+use crate::deltaglider::DynEngine;
+use crate::maintenance::gate::MaintenanceGate;
+use crate::metrics::Metrics;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The engines serving a rule's two sides. A proxy-routed side is the proxy
+/// engine itself; an external side is that endpoint's own engine.
+#[derive(Clone)]
+pub(crate) struct RuleEngines {
+    pub source: Arc<DynEngine>,
+    pub destination: Arc<DynEngine>,
+    /// The proxy engine's metrics — replication counters are proxy-wide even
+    /// when a side is external (external engines run without metrics).
+    pub metrics: Option<Arc<Metrics>>,
+}
+
+impl RuleEngines {
+    /// The source engine when it differs from the destination's — what
+    /// `ObjectTransferRequest::source_engine` takes.
+    pub fn cross_source(&self) -> Option<&Arc<DynEngine>> {
+        (!Arc::ptr_eq(&self.source, &self.destination)).then_some(&self.source)
+    }
+}
+
+/// Parity audits compare both sides through one engine's listing facts; a
+/// rule with an external side is verified by its next reconcile run instead.
+pub const PARITY_UNSUPPORTED: &str =
+    "parity audit is not supported for rules with an external endpoint";
+
+/// True when either side of `rule` is an external endpoint.
+pub fn is_external(rule: &ReplicationRule) -> bool {
+    rule.source.external.is_some() || rule.destination.external.is_some()
+}
+
+/// Registry key: the connection settings plus the bucket (the bucket's
+/// compression policy is baked into the engine).
+fn registry_key(bucket: &str, ext: &ExternalS3Endpoint) -> String {
+    format!(
+        "{bucket}\n{}",
+        serde_json::to_string(ext).unwrap_or_default()
+    )
+}
+
+static REGISTRY: std::sync::LazyLock<parking_lot::Mutex<HashMap<String, Arc<DynEngine>>>> =
+    std::sync::LazyLock::new(Default::default);
+
+/// Whether a maintenance job holds `endpoint`'s bucket. Maintenance rewrites
+/// proxy buckets only, so an external endpoint is never held — even when its
+/// bucket shares a name with one.
+pub fn under_maintenance(gate: &MaintenanceGate, endpoint: &ReplicationEndpoint) -> bool {
+    endpoint.external.is_none() && gate.is_busy(&endpoint.bucket)
+}
+
+/// The Jobs-screen view of one external side — where it points, never the
+/// credentials.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EndpointView {
+    /// `None` = AWS S3 in `region`.
+    pub endpoint: Option<String>,
+    pub region: String,
+    pub hydrated: bool,
+}
+
+/// The Jobs-screen view of a rule's external sides; `None` when both are
+/// proxy-routed.
+pub fn view(rule: &ReplicationRule) -> Option<serde_json::Value> {
+    let side = |e: &ReplicationEndpoint| {
+        e.external.as_ref().map(|x| EndpointView {
+            endpoint: x.endpoint.clone(),
+            region: x.region.clone(),
+            hydrated: x.hydrated,
+        })
+    };
+    is_external(rule).then(|| {
+        serde_json::json!({
+            "source": side(&rule.source),
+            "destination": side(&rule.destination),
+        })
+    })
+}
+
+/// Forget the engines of endpoints no rule references any more (edited
+/// credentials, deleted rules). The scheduler, event consumer and run-now
+/// call this alongside `throttle::configure`.
+pub fn configure(cfg: &ReplicationConfig) {
+    let live: std::collections::HashSet<String> = cfg
+        .rules
+        .iter()
+        .flat_map(|r| [&r.source, &r.destination])
+        .filter_map(|e| e.external.as_ref().map(|x| registry_key(&e.bucket, x)))
+        .collect();
+    REGISTRY.lock().retain(|key, _| live.contains(key));
+}
+
+/// The engine configuration for one external endpoint. Size ceilings follow
+/// the proxy engine's (`limits`: max object size, max passthrough size) so
+/// anything the proxy stores can also be replicated out.
+fn endpoint_config(bucket: &str, ext: &ExternalS3Endpoint, limits: (u64, u64)) -> Config {
+    let mut cfg = Config {
+        backend: BackendConfig::S3 {
+            endpoint: ext.endpoint.clone(),
+            region: ext.region.clone(),
+            force_path_style: ext.force_path_style,
+            access_key_id: ext.access_key_id.clone(),
+            secret_access_key: ext.secret_access_key.clone(),
+            allow_local: ext.allow_local,
+        },
+        max_object_size: limits.0,
+        max_passthrough_object_size: limits.1,
+        ..Config::default()
+    };
+    if ext.hydrated {
+        cfg.buckets.insert(
+            bucket.to_string(),
+            BucketPolicyConfig {
+                compression: Some(false),
+                ..Default::default()
+            },
+        );
+    }
+    cfg
+}
+
+/// The engine serving `endpoint`: the proxy engine for a proxy-routed
+/// bucket, otherwise the external endpoint's (built on first use).
+pub(crate) async fn endpoint_engine(
+    proxy: &Arc<DynEngine>,
+    endpoint: &ReplicationEndpoint,
+) -> Result<Arc<DynEngine>, String> {
+    let Some(ext) = endpoint.external.as_ref() else {
+        return Ok(proxy.clone());
+    };
+    let key = registry_key(&endpoint.bucket, ext);
+    if let Some(engine) = REGISTRY.lock().get(&key) {
+        return Ok(engine.clone());
+    }
+    let limits = (proxy.max_object_size(), proxy.max_passthrough_object_size());
+    let cfg = endpoint_config(&endpoint.bucket, ext, limits);
+    let engine = DynEngine::new(&cfg, None).await.map_err(|e| {
+        format!(
+            "external endpoint {} for bucket '{}' unavailable: {e}",
+            ext.endpoint.as_deref().unwrap_or("(AWS)"),
+            endpoint.bucket
+        )
+    })?;
+    // A concurrent first use may have built one too; keep whichever landed
+    // first so every caller shares a client.
+    Ok(REGISTRY
+        .lock()
+        .entry(key)
+        .or_insert_with(|| Arc::new(engine))
+        .clone())
+}
+
+/// Resolve both sides of `rule`.
+pub(crate) async fn rule_engines(
+    proxy: &Arc<DynEngine>,
+    rule: &ReplicationRule,
+) -> Result<RuleEngines, String> {
+    Ok(RuleEngines {
+        source: endpoint_engine(proxy, &rule.source).await?,
+        destination: endpoint_engine(proxy, &rule.destination).await?,
+        metrics: proxy.metrics().cloned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ext(hydrated: bool) -> ExternalS3Endpoint {
+        ExternalS3Endpoint {
+            endpoint: Some("https://s3.partner.example".into()),
+            region: "eu-central-1".into(),
+            force_path_style: false,
+            access_key_id: Some("AK".into()),
+            secret_access_key: Some("SK".into()),
+            allow_local: false,
+            hydrated,
+        }
+    }
+
+    #[test]
+    fn registry_key_tracks_bucket_and_settings() {
+        let a = registry_key("releases", &ext(true));
+        assert_eq!(a, registry_key("releases", &ext(true)));
+        assert_ne!(a, registry_key("other", &ext(true)));
+        assert_ne!(a, registry_key("releases", &ext(false)));
+        let mut rotated = ext(true);
+        rotated.secret_access_key = Some("SK2".into());
+        assert_ne!(a, registry_key("releases", &rotated));
+    }
+
+    #[test]
+    fn hydrated_endpoint_stores_plain_objects() {
+        let cfg = endpoint_config("releases", &ext(true), (1, 2));
+        let registry = crate::bucket_policy::BucketPolicyRegistry::new(
+            cfg.buckets.clone(),
+            cfg.max_delta_ratio,
+        );
+        assert!(!registry.compression_enabled("releases"));
+        assert_eq!(
+            (cfg.max_object_size, cfg.max_passthrough_object_size),
+            (1, 2)
+        );
+
+        let cfg = endpoint_config("releases", &ext(false), (1, 2));
+        assert!(
+            cfg.buckets.is_empty(),
+            "DeltaGlider layout keeps compression on"
+        );
+        match cfg.backend {
+            BackendConfig::S3 {
+                endpoint, region, ..
+            } => {
+                assert_eq!(endpoint.as_deref(), Some("https://s3.partner.example"));
+                assert_eq!(region, "eu-central-1");
+            }
+            other => panic!("expected an S3 backend, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Hybrid logical clock (HLC) stamps for bidirectional replication.
+//!
+//! Every version of a key on a bidirectional rule is identified by an
+//! [`HlcStamp`]: wall-clock milliseconds, a logical counter that breaks ties
+//! within one millisecond, and the site (bucket) that wrote it. A replica
+//! carries its ORIGIN write's stamp in the `dg-hlc` user-metadata key, so both
+//! sides of a synced key report the same version; an object written directly
+//! by a client has no stamp and is versioned by its `created_at`.
+//!
+//! The process-wide [`clock`] issues stamps for decisions made here (delete
+//! tombstones, manual conflict resolutions) and `observe`s every stamp it
+//! reads, so a tombstone always orders after the versions seen before it even
+//! when the two sites' wall clocks disagree.
+
+use crate::transfer::HLC_METADATA_KEY;
+use crate::types::FileMetadata;
+
+/// One version stamp. Ordered by `(wall_ms, logical, site)`; the zero-padded
+/// `Display` form sorts the same way as text.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HlcStamp {
+    pub wall_ms: i64,
+    pub logical: u32,
+    pub site: String,
+}
+
+impl HlcStamp {
+    /// The stamp of `meta` as stored on `site`: the carried `dg-hlc` stamp
+    /// when it parses, else `created_at` (a direct client write).
+    pub fn of_object(meta: &FileMetadata, site: &str) -> Self {
+        meta.user_metadata
+            .get(HLC_METADATA_KEY)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(|| Self {
+                wall_ms: meta.created_at.timestamp_millis(),
+                logical: 0,
+                site: site.to_string(),
+            })
+    }
+}
+
+impl std::fmt::Display for HlcStamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:013}.{:05}@{}", self.wall_ms, self.logical, self.site)
+    }
+}
+
+impl std::str::FromStr for HlcStamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (clock, site) = s
+            .split_once('@')
+            .ok_or_else(|| format!("HLC stamp {s:?} has no site"))?;
+        let (wall, logical) = clock
+            .split_once('.')
+            .ok_or_else(|| format!("HLC stamp {s:?} has no logical counter"))?;
+        Ok(Self {
+            wall_ms: wall
+                .parse()
+                .map_err(|e| format!("HLC stamp {s:?} wall clock: {e}"))?,
+            logical: logical
+                .parse()
+                .map_err(|e| format!("HLC stamp {s:?} logical counter: {e}"))?,
+            site: site.to_string(),
+        })
+    }
+}
+
+/// A hybrid logical clock: never goes backwards, and after `observe(s)`
+/// every stamp it issues orders after `s`.
+#[derive(Debug, Default)]
+pub struct HlcClock {
+    last: parking_lot::Mutex<(i64, u32)>,
+}
+
+impl HlcClock {
+    /// A fresh stamp for `site`.
+    pub fn now(&self, site: &str) -> HlcStamp {
+        self.now_at(chrono::Utc::now().timestamp_millis(), site)
+    }
+
+    fn now_at(&self, physical_ms: i64, site: &str) -> HlcStamp {
+        let mut last = self.last.lock();
+        *last = if physical_ms > last.0 {
+            (physical_ms, 0)
+        } else {
+            (last.0, last.1.saturating_add(1))
+        };
+        HlcStamp {
+            wall_ms: last.0,
+            logical: last.1,
+            site: site.to_string(),
+        }
+    }
+
+    /// Merge a stamp read from an object so later stamps order after it.
+    pub fn observe(&self, stamp: &HlcStamp) {
+        let mut last = self.last.lock();
+        if (stamp.wall_ms, stamp.logical) > *last {
+            *last = (stamp.wall_ms, stamp.logical);
+        }
+    }
+}
+
+/// The process-wide clock.
+pub fn clock() -> &'static HlcClock {
+    static CLOCK: std::sync::LazyLock<HlcClock> = std::sync::LazyLock::new(HlcClock::default);
+    &CLOCK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamp(wall_ms: i64, logical: u32, site: &str) -> HlcStamp {
+        HlcStamp {
+            wall_ms,
+            logical,
+            site: site.to_string(),
+        }
+    }
+
+    #[test]
+    fn display_round_trips_and_sorts_like_the_stamp() {
+        let a = stamp(1_700_000_000_000, 0, "site-a");
+        let b = stamp(1_700_000_000_000, 2, "site-a");
+        let c = stamp(1_700_000_000_001, 0, "site-b");
+        for s in [&a, &b, &c] {
+            assert_eq!(s.to_string().parse::<HlcStamp>().unwrap(), *s);
+        }
+        assert!(a < b && b < c);
+        assert!(a.to_string() < b.to_string() && b.to_string() < c.to_string());
+        assert!("garbage".parse::<HlcStamp>().is_err());
+        assert!("17.x@a".parse::<HlcStamp>().is_err());
+    }
+
+    #[test]
+    fn clock_is_monotonic_within_a_millisecond() {
+        let clock = HlcClock::default();
+        let first = clock.now_at(1_000, "a");
+        let second = clock.now_at(1_000, "a");
+        let behind = clock.now_at(900, "a");
+        assert_eq!(first, stamp(1_000, 0, "a"));
+        assert_eq!(second, stamp(1_000, 1, "a"));
+        assert_eq!(behind, stamp(1_000, 2, "a"));
+        assert_eq!(clock.now_at(1_001, "a"), stamp(1_001, 0, "a"));
+    }
+
+    #[test]
+    fn observed_stamps_from_a_clock_ahead_are_overtaken() {
+        let clock = HlcClock::default();
+        clock.observe(&stamp(5_000, 7, "remote"));
+        let next = clock.now_at(1_000, "local");
+        assert!(next > stamp(5_000, 7, "remote"));
+        assert_eq!((next.wall_ms, next.logical), (5_000, 8));
+        // Observing an older stamp never moves the clock back.
+        clock.observe(&stamp(10, 0, "remote"));
+        assert_eq!(clock.now_at(1_000, "local").logical, 9);
+    }
+
+    #[test]
+    fn object_stamp_prefers_the_carried_hlc() {
+        let mut meta =
+            FileMetadata::new_passthrough("k".into(), "sha".into(), "md5".into(), 3, None);
+        meta.created_at = chrono::DateTime::from_timestamp_millis(42_000).unwrap();
+        assert_eq!(HlcStamp::of_object(&meta, "a"), stamp(42_000, 0, "a"));
+        meta.user_metadata
+            .insert(HLC_METADATA_KEY.to_string(), stamp(7, 3, "b").to_string());
+        assert_eq!(HlcStamp::of_object(&meta, "a"), stamp(7, 3, "b"));
+        meta.user_metadata
+            .insert(HLC_METADATA_KEY.to_string(), "not-a-stamp".into());
+        assert_eq!(HlcStamp::of_object(&meta, "a"), stamp(42_000, 0, "a"));
+    }
+}
@@ -0,0 +1,249 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Repair of write-through mirrors (see [`crate::storage::mirror`]).
+//!
+//! A mirrored write acknowledged by one copy leaves the other copy lagging.
+//! [`repair_once`] runs in two steps:
+//!
+//! 1. **Flush.** Laggards become `MirrorRepair` rows in the event outbox, so
+//!    they survive a restart. [`install_journal`] makes the write path insert
+//!    the row before a degraded write is acknowledged; the tick only flushes
+//!    laggards that missed it (the outbox insert failed). The rows are
+//!    marked delivered in the same lock scope: they are internal work items,
+//!    never webhook payloads.
+//! 2. **Replay.** The `mirror_repair` listener walks the outbox from its
+//!    cursor, repairs each laggard from the other copy and advances to the
+//!    highest contiguous repaired id. A failed repair (the lagging backend is
+//!    still down) stops the walk; the next tick retries it in order.
+//!
+//! Without a config DB there is no outbox: laggards are repaired straight
+//! from memory and put back on failure.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::api::handlers::AppState;
+use crate::config_db::ConfigDb;
+use crate::event_outbox::{current_unix_seconds, EventKind, EventSource, NewEvent};
+use crate::storage::mirror::{
+    requeue_laggards, set_laggard_journal, take_pending_laggards, LaggardJournal, RepairOutcome,
+};
+use crate::storage::{MirrorLaggard, MirrorSet};
+
+/// Listener cursor name for the repair walk.
+pub const MIRROR_REPAIR_LISTENER: &str = "mirror_repair";
+
+/// Max outbox rows read per tick.
+const REPAIR_BATCH: u32 = 500;
+
+/// Single-flight guard: the periodic tick must not overlap a slow repair.
+static REPAIRING: AtomicBool = AtomicBool::new(false);
+
+/// Flush pending laggards to the outbox and replay outstanding repairs.
+/// Returns the number of copies repaired.
+pub async fn repair_once(state: &Arc<AppState>) -> usize {
+    if REPAIRING.swap(true, Ordering::AcqRel) {
+        return 0;
+    }
+    let engine = state.engine.load_full();
+    let repaired = match (state.config_db.as_ref(), engine.mirror_set()) {
+        (Some(db), mirrors) => {
+            flush_laggards(db).await;
+            match mirrors {
+                Some(mirrors) => replay_outbox(state, db, mirrors).await,
+                None => 0,
+            }
+        }
+        (None, Some(mirrors)) => repair_in_memory(state, mirrors).await,
+        (None, None) => 0,
+    };
+    REPAIRING.store(false, Ordering::Release);
+    if repaired > 0 {
+        info!("mirror: repaired {repaired} lagging cop(ies)");
+    }
+    repaired
+}
+
+/// The event outbox as the mirrors' [`LaggardJournal`].
+struct OutboxJournal(Arc<Mutex<ConfigDb>>);
+
+#[async_trait]
+impl LaggardJournal for OutboxJournal {
+    async fn record(&self, laggards: &[MirrorLaggard]) -> Result<(), String> {
+        queue_laggards(&self.0, laggards).await
+    }
+}
+
+/// Journal mirror laggards in `db`'s outbox before degraded writes return.
+pub fn install_journal(db: Arc<Mutex<ConfigDb>>) {
+    set_laggard_journal(Arc::new(OutboxJournal(db)));
+}
+
+/// Move the in-memory laggards into the outbox.
+async fn flush_laggards(db: &Arc<Mutex<ConfigDb>>) {
+    let laggards = take_pending_laggards();
+    if laggards.is_empty() {
+        return;
+    }
+    if let Err(e) = queue_laggards(db, &laggards).await {
+        warn!("mirror: cannot queue {} repair(s): {e}", laggards.len());
+        requeue_laggards(laggards);
+    }
+}
+
+/// Insert `laggards` as delivered `MirrorRepair` rows.
+async fn queue_laggards(
+    db: &Arc<Mutex<ConfigDb>>,
+    laggards: &[MirrorLaggard],
+) -> Result<(), String> {
+    let now = current_unix_seconds();
+    let events: Vec<NewEvent> = laggards
+        .iter()
+        .map(|l| {
+            NewEvent::new(
+                EventKind::MirrorRepair,
+                l.bucket.clone(),
+                l.object_key(),
+                EventSource::Mirror,
+                now,
+                serde_json::to_value(l).unwrap_or_default(),
+            )
+        })
+        .collect();
+    let dbg = db.lock().await;
+    // First flush ever: start the cursor just below these rows rather than
+    // at 0, so the walk doesn't scan the whole outbox history.
+    let seed = dbg
+        .listener_cursor_load(MIRROR_REPAIR_LISTENER)
+        .unwrap_or(0)
+        == 0;
+    let before = if seed {
+        dbg.event_outbox_recent(1)
+            .ok()
+            .and_then(|rows| rows.first().map(|r| r.id))
+            .unwrap_or(0)
+    } else {
+        0
+    };
+    let ids = dbg
+        .event_outbox_insert_many(&events)
+        .map_err(|e| e.to_string())?;
+    for id in ids {
+        let _ = dbg.event_outbox_mark_delivered(id, now);
+    }
+    if before > 0 {
+        let _ = dbg.listener_cursor_advance(MIRROR_REPAIR_LISTENER, before, now);
+    }
+    Ok(())
+}
+
+async fn replay_outbox(
+    state: &Arc<AppState>,
+    db: &Arc<Mutex<ConfigDb>>,
+    mirrors: &Arc<MirrorSet>,
+) -> usize {
+    let rows = {
+        let dbg = db.lock().await;
+        let cursor = dbg
+            .listener_cursor_load(MIRROR_REPAIR_LISTENER)
+            .unwrap_or(0);
+        match dbg.event_outbox_since(cursor, REPAIR_BATCH) {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!("mirror: failed to read outbox: {e}");
+                return 0;
+            }
+        }
+    };
+    let mut watermark = None;
+    let mut repaired = 0;
+    for row in &rows {
+        if row.kind == EventKind::MirrorRepair.as_str() {
+            let Ok(laggard) = serde_json::from_value::<MirrorLaggard>(row.payload.clone()) else {
+                warn!("mirror: skipping malformed repair event {}", row.id);
+                watermark = Some(row.id);
+                continue;
+            };
+            match repair_one(state, mirrors, &laggard).await {
+                Ok(true) => repaired += 1,
+                Ok(false) => {}
+                Err(()) => break,
+            }
+        }
+        watermark = Some(row.id);
+    }
+    if let Some(id) = watermark {
+        let _ = db.lock().await.listener_cursor_advance(
+            MIRROR_REPAIR_LISTENER,
+            id,
+            current_unix_seconds(),
+        );
+    }
+    repaired
+}
+
+async fn repair_in_memory(state: &Arc<AppState>, mirrors: &Arc<MirrorSet>) -> usize {
+    let mut failed = Vec::new();
+    let mut repaired = 0;
+    for laggard in take_pending_laggards() {
+        match repair_one(state, mirrors, &laggard).await {
+            Ok(true) => repaired += 1,
+            Ok(false) => {}
+            Err(()) => failed.push(laggard),
+        }
+    }
+    if !failed.is_empty() {
+        requeue_laggards(failed);
+    }
+    repaired
+}
+
+/// `Ok(true)` when a copy was repaired, `Ok(false)` when there was nothing
+/// left to repair, `Err` when the repair must be retried.
+async fn repair_one(
+    state: &Arc<AppState>,
+    mirrors: &MirrorSet,
+    laggard: &MirrorLaggard,
+) -> Result<bool, ()> {
+    match mirrors.repair(laggard).await {
+        Ok(RepairOutcome::Unmirrored) => {
+            debug!(
+                "mirror: dropping repair of {}/{} — bucket no longer mirrored there",
+                laggard.bucket,
+                laggard.object_key()
+            );
+            state
+                .metrics
+                .mirror_repairs_total
+                .with_label_values(&["dropped"])
+                .inc();
+            Ok(false)
+        }
+        Ok(_) => {
+            state
+                .metrics
+                .mirror_repairs_total
+                .with_label_values(&["repaired"])
+                .inc();
+            Ok(true)
+        }
+        Err(e) => {
+            warn!(
+                "mirror: repair of {}/{} on '{}' failed: {e}",
+                laggard.bucket,
+                laggard.object_key(),
+                laggard.backend
+            );
+            state
+                .metrics
+                .mirror_repairs_total
+                .with_label_values(&["failed"])
+                .inc();
+            Err(())
+        }
+    }
+}
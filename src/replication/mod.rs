@@ -12,17 +12,28 @@
 //!   later — v6 schema).
 //! - `worker` — async copy loop. Calls engine.retrieve on source,
 //!   engine.store on destination. Added later.
+//! - `bidi` — bidirectional (active-active) rules: two-way sync with
+//!   tombstones and a conflicts table; `hlc` stamps the versions.
+//! - `throttle` — bandwidth caps (token buckets shared per rule and
+//!   globally) and allowed-hours transfer windows.
+//! - `external` — rule sides on arbitrary S3 endpoints (own credentials),
+//!   each served by its own engine.
 //!
 //! The periodic scheduler wakes from `replication.tick_interval`, checks
 //! each rule's persisted `next_due_at`, skips paused/disabled rules, and
 //! executes due rules via the same worker used by "Run now".
 
+pub mod bidi;
 pub mod event_consumer;
+pub mod external;
+pub mod hlc;
+pub mod mirror_repair;
 pub mod parity;
 pub mod planner;
 pub mod remediation;
 pub mod scheduler;
 pub mod state_store;
+pub mod throttle;
 pub mod walk;
 pub mod worker;
 
@@ -37,6 +48,6 @@ pub use remediation::{
 };
 pub use state_store::{
     current_unix_seconds, FailureRecord, ObjectFailure, ParityResultRow, ReplicationState,
-    RunRecord, RunTotals,
+    RunRecord, RunTotals, SyncConflict, SyncRow,
 };
 pub use worker::{run_rule, RunConcurrency, RunOutcome};
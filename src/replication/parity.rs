@@ -506,6 +506,24 @@ pub fn diff_parity(
     out
 }
 
+/// PURE: diff the two copies of a write-through mirrored bucket (see
+/// [`crate::storage::mirror`]) — the primary plays "source", the mirror
+/// "destination". Keys are identical on both sides, so no rewrite; directory
+/// markers are skipped.
+pub fn diff_mirror(
+    primary: &[(String, FileMetadata)],
+    mirror: &[(String, FileMetadata)],
+) -> ParityDiff {
+    fn states(listing: &[(String, FileMetadata)]) -> BTreeMap<String, ObjState> {
+        listing
+            .iter()
+            .filter(|(k, _)| !k.ends_with('/'))
+            .map(|(k, m)| (k.clone(), ObjState::from_metadata(m)))
+            .collect()
+    }
+    diff_parity(&states(primary), &states(mirror))
+}
+
 fn push_capped(v: &mut Vec<ParityFinding>, f: ParityFinding) {
     if v.len() < SAMPLE_CAP {
         v.push(f);
@@ -1298,6 +1316,9 @@ pub async fn parity_audit(
     failures: Option<&tokio::sync::Mutex<ConfigDb>>,
     progress: Option<ParityProgress<'_>>,
 ) -> Result<ParityOutcome, String> {
+    if super::external::is_external(rule) {
+        return Err(super::external::PARITY_UNSUPPORTED.to_string());
+    }
     let progress = progress.as_ref();
     let (inc, exc) = compile_rule_globs(rule).map_err(|e| e.to_string())?;
     let source_prefix = normalize_prefix(&rule.source.prefix);
@@ -1613,6 +1634,34 @@ mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    #[test]
+    fn diff_mirror_compares_copies_key_for_key() {
+        let obj = |key: &str, sha: &str, size: u64| {
+            (
+                key.to_string(),
+                FileMetadata::new_passthrough(key.into(), sha.into(), "md5".into(), size, None),
+            )
+        };
+        let primary = vec![
+            obj("a.bin", "sha-a", 3),
+            obj("b.bin", "sha-b", 4),
+            obj("c.bin", "sha-c", 5),
+            obj("dir/", "", 0),
+        ];
+        let mirror = vec![
+            obj("a.bin", "sha-a", 3),
+            obj("b.bin", "sha-other", 4),
+            obj("d.bin", "sha-d", 6),
+        ];
+        let diff = diff_mirror(&primary, &mirror);
+        assert_eq!(diff.matched, 1);
+        assert_eq!(diff.checksum_mismatch, 1);
+        assert_eq!(diff.missing_on_dest, 1);
+        assert_eq!(diff.orphan_on_dest, 1);
+        assert_eq!(diff.missing_samples[0].key, "c.bin");
+        assert_eq!(diff.orphan_samples[0].key, "d.bin");
+    }
+
     #[test]
     fn classify_regime_truth_table() {
         use Regime::*;
@@ -268,31 +268,7 @@ pub fn should_replicate(
         // back to size-only here (can't prove a same-size difference); that
         // matches parity's tier-3 and avoids re-shipping on every sweep.
         (Some(dest), ConflictPolicy::ContentDiff) => {
-            let size_differs = src_meta.file_size != dest.file_size;
-            let sha_differs = !src_meta.file_sha256.is_empty()
-                && !dest.file_sha256.is_empty()
-                && src_meta.file_sha256 != dest.file_sha256;
-            // Tier-2 (sha absent a side): equal-size objects whose ETags differ
-            // ARE different bytes — copy. Mirrors parity::compare_pair so
-            // content-diff and Verify agree for foreign/legacy objects (those
-            // missing a logical SHA). Only comparable when the multipart shapes
-            // match (a multipart `-N` etag is md5-of-md5s, not the object md5);
-            // mismatched shapes demote to size-only, like parity's tier-3.
-            let (s_etag, s_parts) = resolve_etag_and_parts(src_meta);
-            let (d_etag, d_parts) = resolve_etag_and_parts(dest);
-            let etag_differs = !sha_differs
-                && s_parts == d_parts
-                && matches!((&s_etag, &d_etag), (Some(s), Some(d)) if s != d);
-            // Same-size objects we CANNOT prove identical: no comparable SHA
-            // (either side absent) AND no comparable etag (shape mismatch or
-            // absent). Under `strict_content_diff` these resolve to COPY
-            // (correctness) rather than the default size-only SKIP (convergence).
-            let indeterminate = !size_differs
-                && !sha_differs
-                && !etag_differs
-                && !have_comparable_hash(src_meta, dest, s_parts, d_parts, &s_etag, &d_etag);
-            if size_differs || sha_differs || etag_differs || (strict_content_diff && indeterminate)
-            {
+            if content_differs(src_meta, dest, strict_content_diff) {
                 Decision::Copy {
                     dest_key: source_key.to_string(),
                 }
@@ -320,6 +296,35 @@ pub fn should_replicate(
     }
 }
 
+/// The `content-diff` comparison, shared with bidirectional rules: `true` when
+/// `src` and `dest` hold different bytes. `strict` is `strict_content_diff`.
+pub fn content_differs(src_meta: &FileMetadata, dest: &FileMetadata, strict: bool) -> bool {
+    let size_differs = src_meta.file_size != dest.file_size;
+    let sha_differs = !src_meta.file_sha256.is_empty()
+        && !dest.file_sha256.is_empty()
+        && src_meta.file_sha256 != dest.file_sha256;
+    // Tier-2 (sha absent a side): equal-size objects whose ETags differ
+    // ARE different bytes — copy. Mirrors parity::compare_pair so
+    // content-diff and Verify agree for foreign/legacy objects (those
+    // missing a logical SHA). Only comparable when the multipart shapes
+    // match (a multipart `-N` etag is md5-of-md5s, not the object md5);
+    // mismatched shapes demote to size-only, like parity's tier-3.
+    let (s_etag, s_parts) = resolve_etag_and_parts(src_meta);
+    let (d_etag, d_parts) = resolve_etag_and_parts(dest);
+    let etag_differs = !sha_differs
+        && s_parts == d_parts
+        && matches!((&s_etag, &d_etag), (Some(s), Some(d)) if s != d);
+    // Same-size objects we CANNOT prove identical: no comparable SHA
+    // (either side absent) AND no comparable etag (shape mismatch or
+    // absent). Under `strict_content_diff` these resolve to COPY
+    // (correctness) rather than the default size-only SKIP (convergence).
+    let indeterminate = !size_differs
+        && !sha_differs
+        && !etag_differs
+        && !have_comparable_hash(src_meta, dest, s_parts, d_parts, &s_etag, &d_etag);
+    size_differs || sha_differs || etag_differs || (strict && indeterminate)
+}
+
 /// Build a batch plan from a listing page + a per-key destination
 /// lookup closure.
 ///
@@ -365,7 +370,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config_sections::{ConflictPolicy, ReplicationEndpoint, ReplicationRule};
+    use crate::config_sections::{
+        ConflictPolicy, ReplicationDirection, ReplicationEndpoint, ReplicationRule,
+    };
     use chrono::{TimeZone, Utc};
 
     fn make_meta(name: &str, size: u64, ts: chrono::DateTime<Utc>) -> FileMetadata {
@@ -387,16 +394,22 @@ mod tests {
             source: ReplicationEndpoint {
                 bucket: "a".into(),
                 prefix: String::new(),
+                external: None,
             },
             destination: ReplicationEndpoint {
                 bucket: "b".into(),
                 prefix: String::new(),
+                external: None,
             },
             interval: "1h".into(),
             batch_size: 100,
             replicate_deletes: false,
+            direction: ReplicationDirection::OneWay,
+            tombstone_retention: "30d".into(),
             conflict: ConflictPolicy::NewerWins,
             strict_content_diff: false,
+            bandwidth_limit_mbps: None,
+            windows: Vec::new(),
             include_globs: Vec::new(),
             exclude_globs: vec![".dg/*".into()],
         }
@@ -12,7 +12,7 @@ use crate::config::SharedConfig;
 use crate::config_db::ConfigDb;
 use crate::config_sections::ReplicationConfig;
 use crate::coordination::{CoordinationLease, LeaseSubsystem, LocalLease};
-use crate::replication::{current_unix_seconds, run_rule, RunLease};
+use crate::replication::{current_unix_seconds, run_rule, throttle, RunLease};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
@@ -61,6 +61,8 @@ async fn run_due_rules(
     instance_id: &str,
     lease: &Arc<dyn CoordinationLease>,
 ) {
+    throttle::configure(replication);
+    super::external::configure(replication);
     for rule in replication.rules.iter().filter(|rule| rule.enabled) {
         let now = current_unix_seconds();
         // Phase 1 (DB lock): advisory pre-filter — is the rule initialised, not
@@ -98,6 +100,15 @@ async fn run_due_rules(
         if !eligible {
             continue;
         }
+        // Outside its transfer window: leave next_due_at in the past so the
+        // first tick after the window opens picks the rule up.
+        if !throttle::rule_gate(rule).open {
+            debug!(
+                "Replication scheduler deferred rule '{}': outside its transfer window",
+                rule.name
+            );
+            continue;
+        }
 
         // Phase 2 (lease acquire — cross-instance arbiter). Exactly one node wins.
         let should_run = match lease
@@ -144,7 +155,7 @@ async fn run_due_rules(
             continue;
         }
 
-        if state.maintenance_gate.is_busy(&rule.destination.bucket) {
+        if super::external::under_maintenance(&state.maintenance_gate, &rule.destination) {
             info!(
                 "Replication scheduler deferring rule '{}': destination '{}' is under maintenance",
                 rule.name, rule.destination.bucket
@@ -8,6 +8,8 @@
 //!   run. Append-only; the worker inserts on begin, updates on finish.
 //! - `replication_failures`: per-object errors. Ring-bounded per
 //!   `record_failure`'s `max_retained`.
+//! - `replication_sync_state` / `replication_conflicts` (v31): per-key
+//!   last-synced state and held conflicts of bidirectional rules.
 //!
 //! Implemented as methods on [`ConfigDb`] so the SQLCipher mutex (held
 //! at the type boundary) serialises all replication state mutations
@@ -84,6 +86,35 @@ pub struct ObjectFailure {
     pub last_failed_at: i64,
 }
 
+/// Last-synced state of one key on a bidirectional rule (v31
+/// `replication_sync_state`). `rel_key` is relative to the rule's prefixes.
+/// A live row records both sides' listing fingerprints at the last sync; a
+/// tombstone row records a propagated delete (no fingerprints).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncRow {
+    pub rel_key: String,
+    /// `HlcStamp` of the synced version, or of the delete for a tombstone.
+    pub stamp: String,
+    pub src_fp: Option<String>,
+    pub dest_fp: Option<String>,
+    pub tombstone: bool,
+    pub updated_at: i64,
+}
+
+/// A key changed on both sides of a bidirectional rule since the last sync,
+/// held for manual resolution (v31 `replication_conflicts`). Absent
+/// stamps/sizes mean that side is deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncConflict {
+    pub rel_key: String,
+    pub kind: String,
+    pub src_stamp: Option<String>,
+    pub dest_stamp: Option<String>,
+    pub src_size: Option<i64>,
+    pub dest_size: Option<i64>,
+    pub detected_at: i64,
+}
+
 /// Totals emitted by the worker at run termination.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct RunTotals {
@@ -181,7 +212,8 @@ impl ConfigDb {
                     params![existing],
                 )?;
                 // Drop the rule's parity object-cache AND result row, run history,
-                // and failure ring too — all keyed by rule_name, else orphaned.
+                // failure ring and bidirectional sync state too — all keyed by
+                // rule_name, else orphaned.
                 tx.execute(
                     "DELETE FROM replication_parity_objects WHERE rule_name = ?",
                     params![existing],
@@ -198,6 +230,14 @@ impl ConfigDb {
                     "DELETE FROM replication_failures WHERE rule_name = ?",
                     params![existing],
                 )?;
+                tx.execute(
+                    "DELETE FROM replication_sync_state WHERE rule_name = ?",
+                    params![existing],
+                )?;
+                tx.execute(
+                    "DELETE FROM replication_conflicts WHERE rule_name = ?",
+                    params![existing],
+                )?;
                 removed += n;
             }
         }
@@ -324,6 +364,17 @@ impl ConfigDb {
         Ok(n > 0)
     }
 
+    /// Pull a rule's `next_due_at` forward to `due_at` (never pushes it back).
+    /// The event consumer uses this to hand keys it can't copy now (outside
+    /// the rule's transfer window) to the next scheduled reconcile.
+    pub fn replication_mark_due(&self, rule_name: &str, due_at: i64) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "UPDATE replication_state SET next_due_at = MIN(next_due_at, ?) WHERE rule_name = ?",
+            params![due_at, rule_name],
+        )?;
+        Ok(())
+    }
+
     /// Try to acquire the per-rule run lease.
     ///
     /// This is the single-flight guard shared by the periodic scheduler
@@ -818,6 +869,188 @@ impl ConfigDb {
         Ok(rows)
     }
 
+    // ── Bidirectional sync state + conflicts (v31) ────────────────────────────
+
+    /// Up to `limit` sync rows of a rule with `rel_key > after`, in key order —
+    /// the bidirectional sweep merge-joins these against both listings.
+    pub fn replication_sync_rows_after(
+        &self,
+        rule_name: &str,
+        after: &str,
+        limit: u32,
+    ) -> Result<Vec<SyncRow>, ConfigDbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT rel_key, stamp, src_fp, dest_fp, tombstone, updated_at
+             FROM replication_sync_state
+             WHERE rule_name = ? AND rel_key > ?
+             ORDER BY rel_key
+             LIMIT ?",
+        )?;
+        let rows = stmt
+            .query_map(params![rule_name, after, limit], sync_row_from)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    pub fn replication_sync_row(
+        &self,
+        rule_name: &str,
+        rel_key: &str,
+    ) -> Result<Option<SyncRow>, ConfigDbError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT rel_key, stamp, src_fp, dest_fp, tombstone, updated_at
+                 FROM replication_sync_state
+                 WHERE rule_name = ? AND rel_key = ?",
+                params![rule_name, rel_key],
+                sync_row_from,
+            )
+            .optional()?)
+    }
+
+    pub fn replication_sync_upsert(
+        &self,
+        rule_name: &str,
+        row: &SyncRow,
+    ) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "INSERT INTO replication_sync_state
+                (rule_name, rel_key, stamp, src_fp, dest_fp, tombstone, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(rule_name, rel_key) DO UPDATE SET
+                stamp = excluded.stamp,
+                src_fp = excluded.src_fp,
+                dest_fp = excluded.dest_fp,
+                tombstone = excluded.tombstone,
+                updated_at = excluded.updated_at",
+            params![
+                rule_name,
+                row.rel_key,
+                row.stamp,
+                row.src_fp,
+                row.dest_fp,
+                row.tombstone,
+                row.updated_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn replication_sync_delete(
+        &self,
+        rule_name: &str,
+        rel_key: &str,
+    ) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "DELETE FROM replication_sync_state WHERE rule_name = ? AND rel_key = ?",
+            params![rule_name, rel_key],
+        )?;
+        Ok(())
+    }
+
+    /// Record (or refresh) a conflict. A re-detected conflict keeps its
+    /// original `detected_at`.
+    pub fn replication_conflict_upsert(
+        &self,
+        rule_name: &str,
+        c: &SyncConflict,
+    ) -> Result<(), ConfigDbError> {
+        self.conn.execute(
+            "INSERT INTO replication_conflicts
+                (rule_name, rel_key, kind, src_stamp, dest_stamp, src_size, dest_size, detected_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(rule_name, rel_key) DO UPDATE SET
+                kind = excluded.kind,
+                src_stamp = excluded.src_stamp,
+                dest_stamp = excluded.dest_stamp,
+                src_size = excluded.src_size,
+                dest_size = excluded.dest_size",
+            params![
+                rule_name,
+                c.rel_key,
+                c.kind,
+                c.src_stamp,
+                c.dest_stamp,
+                c.src_size,
+                c.dest_size,
+                c.detected_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Open conflicts of a rule, oldest first, capped at `limit`.
+    pub fn replication_conflicts(
+        &self,
+        rule_name: &str,
+        limit: u32,
+    ) -> Result<Vec<SyncConflict>, ConfigDbError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT rel_key, kind, src_stamp, dest_stamp, src_size, dest_size, detected_at
+             FROM replication_conflicts
+             WHERE rule_name = ?
+             ORDER BY detected_at, rel_key
+             LIMIT ?",
+        )?;
+        let rows = stmt
+            .query_map(params![rule_name, limit], conflict_from)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    pub fn replication_conflict_count(&self, rule_name: &str) -> Result<i64, ConfigDbError> {
+        Ok(self.conn.query_row(
+            "SELECT COUNT(*) FROM replication_conflicts WHERE rule_name = ?",
+            params![rule_name],
+            |r| r.get(0),
+        )?)
+    }
+
+    /// Every conflicted key of a rule — the sweep leaves these alone.
+    pub fn replication_conflict_keys(
+        &self,
+        rule_name: &str,
+    ) -> Result<std::collections::HashSet<String>, ConfigDbError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT rel_key FROM replication_conflicts WHERE rule_name = ?")?;
+        let keys = stmt
+            .query_map(params![rule_name], |r| r.get::<_, String>(0))?
+            .collect::<Result<_, _>>()?;
+        Ok(keys)
+    }
+
+    pub fn replication_conflict_get(
+        &self,
+        rule_name: &str,
+        rel_key: &str,
+    ) -> Result<Option<SyncConflict>, ConfigDbError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT rel_key, kind, src_stamp, dest_stamp, src_size, dest_size, detected_at
+                 FROM replication_conflicts
+                 WHERE rule_name = ? AND rel_key = ?",
+                params![rule_name, rel_key],
+                conflict_from,
+            )
+            .optional()?)
+    }
+
+    /// Drop a conflict; `false` when there was none.
+    pub fn replication_conflict_clear(
+        &self,
+        rule_name: &str,
+        rel_key: &str,
+    ) -> Result<bool, ConfigDbError> {
+        let n = self.conn.execute(
+            "DELETE FROM replication_conflicts WHERE rule_name = ? AND rel_key = ?",
+            params![rule_name, rel_key],
+        )?;
+        Ok(n > 0)
+    }
+
     // ── Parity per-object logical-metadata cache (v18) ────────────────────────
     // The expensive part of a parity audit is recovering each DELTA object's
     // LOGICAL (sha256, size, etag) — only a per-object HEAD gives it, since the
@@ -1239,6 +1472,29 @@ impl ConfigDb {
     }
 }
 
+fn sync_row_from(r: &rusqlite::Row<'_>) -> rusqlite::Result<SyncRow> {
+    Ok(SyncRow {
+        rel_key: r.get(0)?,
+        stamp: r.get(1)?,
+        src_fp: r.get(2)?,
+        dest_fp: r.get(3)?,
+        tombstone: r.get(4)?,
+        updated_at: r.get(5)?,
+    })
+}
+
+fn conflict_from(r: &rusqlite::Row<'_>) -> rusqlite::Result<SyncConflict> {
+    Ok(SyncConflict {
+        rel_key: r.get(0)?,
+        kind: r.get(1)?,
+        src_stamp: r.get(2)?,
+        dest_stamp: r.get(3)?,
+        src_size: r.get(4)?,
+        dest_size: r.get(5)?,
+        detected_at: r.get(6)?,
+    })
+}
+
 /// The cached parity result row (the background-job state the UI polls).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParityResultRow {
@@ -1315,6 +1571,22 @@ mod tests {
         assert!(!db.replication_load_state("r").unwrap().unwrap().paused);
     }
 
+    #[test]
+    fn mark_due_only_moves_next_due_earlier() {
+        let db = db();
+        db.replication_ensure_state("r", 1_000).unwrap();
+        db.replication_mark_due("r", 400).unwrap();
+        assert_eq!(
+            db.replication_load_state("r").unwrap().unwrap().next_due_at,
+            400
+        );
+        db.replication_mark_due("r", 900).unwrap();
+        assert_eq!(
+            db.replication_load_state("r").unwrap().unwrap().next_due_at,
+            400
+        );
+    }
+
     #[test]
     fn set_paused_nonexistent_rule_returns_false() {
         let db = db();
@@ -1572,6 +1844,87 @@ mod tests {
         assert!(db.replication_load_state("r3").unwrap().is_none());
     }
 
+    fn sync_row(key: &str, stamp: &str, tombstone: bool) -> SyncRow {
+        SyncRow {
+            rel_key: key.to_string(),
+            stamp: stamp.to_string(),
+            src_fp: (!tombstone).then(|| "1:a".to_string()),
+            dest_fp: (!tombstone).then(|| "2:a".to_string()),
+            tombstone,
+            updated_at: 100,
+        }
+    }
+
+    fn conflict(key: &str, detected_at: i64) -> SyncConflict {
+        SyncConflict {
+            rel_key: key.to_string(),
+            kind: "both-modified".to_string(),
+            src_stamp: Some("s".to_string()),
+            dest_stamp: None,
+            src_size: Some(3),
+            dest_size: None,
+            detected_at,
+        }
+    }
+
+    #[test]
+    fn sync_rows_page_in_key_order_and_upsert_in_place() {
+        let db = db();
+        for k in ["c", "a", "b"] {
+            db.replication_sync_upsert("r", &sync_row(k, "s1", false))
+                .unwrap();
+        }
+        db.replication_sync_upsert("other", &sync_row("a0", "s1", false))
+            .unwrap();
+        let page = db.replication_sync_rows_after("r", "", 2).unwrap();
+        assert_eq!(
+            page.iter().map(|r| r.rel_key.as_str()).collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+        let rest = db.replication_sync_rows_after("r", "b", 10).unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].rel_key, "c");
+
+        db.replication_sync_upsert("r", &sync_row("a", "s2", true))
+            .unwrap();
+        let a = db.replication_sync_row("r", "a").unwrap().unwrap();
+        assert_eq!(a, sync_row("a", "s2", true));
+        db.replication_sync_delete("r", "a").unwrap();
+        assert!(db.replication_sync_row("r", "a").unwrap().is_none());
+    }
+
+    #[test]
+    fn conflict_upsert_keeps_first_detection_and_clears() {
+        let db = db();
+        db.replication_conflict_upsert("r", &conflict("k", 100))
+            .unwrap();
+        let mut again = conflict("k", 200);
+        again.kind = "deleted-on-source".to_string();
+        db.replication_conflict_upsert("r", &again).unwrap();
+        let got = db.replication_conflict_get("r", "k").unwrap().unwrap();
+        assert_eq!(got.detected_at, 100);
+        assert_eq!(got.kind, "deleted-on-source");
+        assert_eq!(db.replication_conflict_count("r").unwrap(), 1);
+        assert!(db.replication_conflict_keys("r").unwrap().contains("k"));
+        assert_eq!(db.replication_conflicts("r", 10).unwrap().len(), 1);
+        assert!(db.replication_conflict_clear("r", "k").unwrap());
+        assert!(!db.replication_conflict_clear("r", "k").unwrap());
+        assert_eq!(db.replication_conflict_count("r").unwrap(), 0);
+    }
+
+    #[test]
+    fn reconcile_rules_drops_orphan_sync_state() {
+        let db = db();
+        db.replication_ensure_state("gone", 100).unwrap();
+        db.replication_sync_upsert("gone", &sync_row("k", "s", false))
+            .unwrap();
+        db.replication_conflict_upsert("gone", &conflict("k", 100))
+            .unwrap();
+        db.replication_reconcile_rules(&[]).unwrap();
+        assert!(db.replication_sync_row("gone", "k").unwrap().is_none());
+        assert_eq!(db.replication_conflict_count("gone").unwrap(), 0);
+    }
+
     #[test]
     fn reconcile_on_boot_flips_running_to_failed() {
         let db = db();
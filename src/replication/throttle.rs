@@ -0,0 +1,435 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Replication bandwidth caps and allowed-hours windows.
+//!
+//! - **Windows** (`replication.windows` + `rules[].windows`) gate WHEN a rule
+//!   copies. The scheduler doesn't start a sweep outside them, a running
+//!   sweep stops at its next page boundary (cursor kept, rescheduled for the
+//!   opening), and the event consumer skips the rule's events but marks the
+//!   rule due — so the sweep that starts when the window opens picks up
+//!   everything that changed meanwhile. Run-now is an explicit operator
+//!   action and ignores windows.
+//! - **Bandwidth caps** are token buckets (`transfer::BandwidthLimiter`): one
+//!   per rule plus one shared global bucket, in a process-wide registry so a
+//!   rule's sweeps and event-driven copies draw from the SAME bucket and the
+//!   Jobs API can report which rules are being held back. Caps always apply,
+//!   run-now included.
+
+use crate::config_sections::{ReplicationConfig, ReplicationRule, ReplicationWindow};
+use crate::transfer::{BandwidthBudget, BandwidthLimiter};
+use chrono::{DateTime, Datelike, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A validated [`ReplicationWindow`].
+#[derive(Debug, Clone)]
+pub struct Window {
+    /// Days the window opens on, Monday first.
+    days: [bool; 7],
+    start: NaiveTime,
+    end: NaiveTime,
+    tz: Tz,
+}
+
+const DAY_NAMES: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// `mon` / `monday` → 0 … `sun` / `sunday` → 6.
+fn day_index(name: &str) -> Option<usize> {
+    if name.len() < 3 {
+        return None;
+    }
+    DAY_NAMES.iter().position(|d| d.starts_with(name))
+}
+
+fn parse_days(spec: &str) -> Result<[bool; 7], String> {
+    let spec = spec.trim().to_ascii_lowercase();
+    if spec.is_empty() || spec == "daily" {
+        return Ok([true; 7]);
+    }
+    let mut days = [false; 7];
+    for part in spec.split(',').map(str::trim) {
+        match part {
+            "weekdays" => days[..5].fill(true),
+            "weekends" => days[5..].fill(true),
+            _ => {
+                let bad = || format!("days {spec:?}: {part:?} is not a day or day range");
+                let (from, to) = match part.split_once('-') {
+                    Some((a, b)) => (
+                        day_index(a.trim()).ok_or_else(bad)?,
+                        day_index(b.trim()).ok_or_else(bad)?,
+                    ),
+                    None => {
+                        let d = day_index(part).ok_or_else(bad)?;
+                        (d, d)
+                    }
+                };
+                // Ranges wrap the week: `fri-mon` = Fri, Sat, Sun, Mon.
+                let mut d = from;
+                loop {
+                    days[d] = true;
+                    if d == to {
+                        break;
+                    }
+                    d = (d + 1) % 7;
+                }
+            }
+        }
+    }
+    Ok(days)
+}
+
+/// Validate one configured window. Config load reports the error as a
+/// warning and the runtime ignores that window.
+pub fn parse_window(w: &ReplicationWindow) -> Result<Window, String> {
+    let time = |field: &str, value: &str| {
+        NaiveTime::parse_from_str(value.trim(), "%H:%M")
+            .map_err(|_| format!("{field} {value:?} is not HH:MM"))
+    };
+    Ok(Window {
+        days: parse_days(&w.days)?,
+        start: time("start", &w.start)?,
+        end: time("end", &w.end)?,
+        tz: w
+            .timezone
+            .trim()
+            .parse()
+            .map_err(|_| format!("unknown time zone {:?}", w.timezone))?,
+    })
+}
+
+/// The valid windows of a configured list (invalid ones were already
+/// reported at config load).
+pub fn parse_windows(windows: &[ReplicationWindow]) -> Vec<Window> {
+    windows
+        .iter()
+        .filter_map(|w| parse_window(w).ok())
+        .collect()
+}
+
+impl Window {
+    fn is_open(&self, at: DateTime<Utc>) -> bool {
+        let local = at.with_timezone(&self.tz);
+        let t = local.time();
+        let today = local.weekday().num_days_from_monday() as usize;
+        let yesterday = (today + 6) % 7;
+        if self.start < self.end {
+            self.days[today] && t >= self.start && t < self.end
+        } else {
+            // Runs past midnight (or a full 24h when start == end): the tail
+            // after midnight belongs to the day the window opened on.
+            (self.days[today] && t >= self.start) || (self.days[yesterday] && t < self.end)
+        }
+    }
+
+    /// The first opening strictly after `after`.
+    fn next_start(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let first = after.with_timezone(&self.tz).date_naive();
+        (0..=8).find_map(|offset| {
+            let date = first + chrono::Duration::days(offset);
+            if !self.days[date.weekday().num_days_from_monday() as usize] {
+                return None;
+            }
+            let naive = date.and_time(self.start);
+            // A start inside a DST gap opens at the first valid instant after it.
+            let local = self.tz.from_local_datetime(&naive).earliest().or_else(|| {
+                self.tz
+                    .from_local_datetime(&(naive + chrono::Duration::hours(1)))
+                    .earliest()
+            })?;
+            let at = local.with_timezone(&Utc);
+            (at > after).then_some(at)
+        })
+    }
+}
+
+/// Any window open (an empty list never restricts).
+fn any_open(windows: &[Window], at: DateTime<Utc>) -> bool {
+    windows.is_empty() || windows.iter().any(|w| w.is_open(at))
+}
+
+/// When `windows` is next open, at or after `at`.
+fn next_open(windows: &[Window], at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if any_open(windows, at) {
+        return Some(at);
+    }
+    windows.iter().filter_map(|w| w.next_start(at)).min()
+}
+
+/// Whether a rule may copy now: the global AND the rule's windows are open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowGate {
+    pub open: bool,
+    /// Closed only: when both next open (unix seconds). `None` when they
+    /// never overlap.
+    pub opens_at: Option<i64>,
+}
+
+/// Pure window check over already-parsed lists.
+pub fn gate(global: &[Window], rule: &[Window], now: DateTime<Utc>) -> WindowGate {
+    if any_open(global, now) && any_open(rule, now) {
+        return WindowGate {
+            open: true,
+            opens_at: None,
+        };
+    }
+    // Leapfrog the two lists until they're open at the same instant. Every
+    // step moves strictly forward and windows repeat weekly, so a bounded
+    // number of steps finds the overlap or proves there is none.
+    let mut at = now;
+    for _ in 0..64 {
+        let Some(g) = next_open(global, at) else {
+            break;
+        };
+        let Some(r) = next_open(rule, g) else {
+            break;
+        };
+        if any_open(global, r) {
+            return WindowGate {
+                open: false,
+                opens_at: Some(r.timestamp()),
+            };
+        }
+        at = r;
+    }
+    WindowGate {
+        open: false,
+        opens_at: None,
+    }
+}
+
+/// Megabits per second → bytes per second; `None` / `0` = uncapped (0).
+pub(crate) fn mbps_to_bytes_per_sec(mbps: Option<u32>) -> u64 {
+    mbps.unwrap_or(0) as u64 * 125_000
+}
+
+struct Registry {
+    global_windows: Vec<Window>,
+    global: Arc<BandwidthLimiter>,
+    rules: HashMap<String, Arc<BandwidthBudget>>,
+}
+
+static REGISTRY: std::sync::LazyLock<parking_lot::Mutex<Registry>> =
+    std::sync::LazyLock::new(|| {
+        parking_lot::Mutex::new(Registry {
+            global_windows: Vec::new(),
+            global: Arc::new(BandwidthLimiter::new(0)),
+            rules: HashMap::new(),
+        })
+    });
+
+/// Load the global cap + windows from the live config and forget the
+/// budgets of deleted rules. The scheduler, event consumer and run-now call
+/// this before they start copying, so hot-reloaded limits take effect on the
+/// next tick.
+pub fn configure(cfg: &ReplicationConfig) {
+    let mut reg = REGISTRY.lock();
+    reg.global_windows = parse_windows(&cfg.windows);
+    reg.global
+        .set_rate(mbps_to_bytes_per_sec(cfg.bandwidth_limit_mbps));
+    reg.rules
+        .retain(|name, _| cfg.rules.iter().any(|r| &r.name == name));
+}
+
+/// The bandwidth budget every copy of `rule` draws from: the rule's own
+/// bucket (rate refreshed from `rule`) plus the shared global bucket.
+pub(crate) fn budget(rule: &ReplicationRule) -> Arc<BandwidthBudget> {
+    let mut reg = REGISTRY.lock();
+    let global = reg.global.clone();
+    let budget = reg
+        .rules
+        .entry(rule.name.clone())
+        .or_insert_with(|| {
+            Arc::new(BandwidthBudget::new(vec![
+                Arc::new(BandwidthLimiter::new(0)),
+                global,
+            ]))
+        })
+        .clone();
+    budget.limiters()[0].set_rate(mbps_to_bytes_per_sec(rule.bandwidth_limit_mbps));
+    budget
+}
+
+/// The rule's window gate right now, against the last-configured global
+/// windows.
+pub fn rule_gate(rule: &ReplicationRule) -> WindowGate {
+    gate_now(&parse_windows(&rule.windows))
+}
+
+/// [`gate`] against the configured global windows, right now. Takes
+/// pre-parsed rule windows so a run's control checks don't re-parse.
+pub fn gate_now(rule_windows: &[Window]) -> WindowGate {
+    let global = REGISTRY.lock().global_windows.clone();
+    gate(&global, rule_windows, Utc::now())
+}
+
+/// The Jobs-screen view of a rule's throttling.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ThrottleView {
+    pub window_open: bool,
+    pub opens_at: Option<i64>,
+    /// The tighter of the rule's and the global cap.
+    pub bandwidth_limit_mbps: Option<u32>,
+    /// A copy is waiting on the bandwidth cap right now.
+    pub throttled: bool,
+    /// Cumulative time copies were held back by the cap since the process
+    /// started.
+    pub throttled_seconds: u64,
+}
+
+/// `None` when neither the rule nor the global config sets a cap or a
+/// window. Reads windows + caps from `cfg` (not the registry) so the view is
+/// current even before the next scheduler tick.
+pub fn view(cfg: &ReplicationConfig, rule: &ReplicationRule) -> Option<ThrottleView> {
+    let caps = [cfg.bandwidth_limit_mbps, rule.bandwidth_limit_mbps];
+    let limit = caps.into_iter().flatten().filter(|m| *m > 0).min();
+    if limit.is_none() && cfg.windows.is_empty() && rule.windows.is_empty() {
+        return None;
+    }
+    let gate = gate(
+        &parse_windows(&cfg.windows),
+        &parse_windows(&rule.windows),
+        Utc::now(),
+    );
+    let budget = REGISTRY.lock().rules.get(&rule.name).cloned();
+    Some(ThrottleView {
+        window_open: gate.open,
+        opens_at: gate.opens_at,
+        bandwidth_limit_mbps: limit,
+        throttled: budget.as_ref().is_some_and(|b| b.is_waiting()),
+        throttled_seconds: budget.map(|b| b.throttled_secs()).unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(days: &str, start: &str, end: &str, tz: &str) -> Window {
+        parse_window(&ReplicationWindow {
+            days: days.into(),
+            start: start.into(),
+            end: end.into(),
+            timezone: tz.into(),
+        })
+        .unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn parse_days_accepts_ranges_lists_and_aliases() {
+        assert_eq!(parse_days("daily").unwrap(), [true; 7]);
+        assert_eq!(
+            parse_days("mon-fri").unwrap(),
+            [true, true, true, true, true, false, false]
+        );
+        assert_eq!(
+            parse_days("weekdays").unwrap(),
+            parse_days("mon-fri").unwrap()
+        );
+        assert_eq!(
+            parse_days("Sat, sunday").unwrap(),
+            [false, false, false, false, false, true, true]
+        );
+        // Ranges wrap the week.
+        assert_eq!(
+            parse_days("fri-mon").unwrap(),
+            [true, false, false, false, true, true, true]
+        );
+        assert!(parse_days("mo").is_err());
+        assert!(parse_days("mon-xyz").is_err());
+    }
+
+    #[test]
+    fn parse_window_rejects_bad_times_and_zones() {
+        let w = |start: &str, tz: &str| ReplicationWindow {
+            days: "daily".into(),
+            start: start.into(),
+            end: "06:00".into(),
+            timezone: tz.into(),
+        };
+        assert!(parse_window(&w("22:00", "Europe/Berlin")).is_ok());
+        assert!(parse_window(&w("25:00", "UTC"))
+            .unwrap_err()
+            .contains("HH:MM"));
+        assert!(parse_window(&w("22:00", "Mars/Olympus"))
+            .unwrap_err()
+            .contains("time zone"));
+    }
+
+    #[test]
+    fn overnight_window_belongs_to_its_opening_day() {
+        // 22:00–06:00 weekdays, UTC. 2026-10-16 is a Friday.
+        let w = [window("mon-fri", "22:00", "06:00", "UTC")];
+        assert!(any_open(&w, utc("2026-10-16T23:00:00Z")), "Fri night");
+        assert!(
+            any_open(&w, utc("2026-10-17T05:59:00Z")),
+            "Sat early = Fri's window"
+        );
+        assert!(!any_open(&w, utc("2026-10-17T23:00:00Z")), "Sat night");
+        assert!(
+            !any_open(&w, utc("2026-10-19T05:00:00Z")),
+            "Mon early = Sun's window"
+        );
+        assert!(!any_open(&w, utc("2026-10-19T12:00:00Z")), "business hours");
+        assert!(any_open(&w, utc("2026-10-19T22:00:00Z")), "opens at start");
+        assert!(!any_open(&w, utc("2026-10-20T06:00:00Z")), "closes at end");
+    }
+
+    #[test]
+    fn windows_are_evaluated_in_their_time_zone() {
+        // 09:00–17:00 New York (UTC-4 in October).
+        let w = [window("daily", "09:00", "17:00", "America/New_York")];
+        assert!(!any_open(&w, utc("2026-10-19T12:00:00Z")), "08:00 local");
+        assert!(any_open(&w, utc("2026-10-19T14:00:00Z")), "10:00 local");
+    }
+
+    #[test]
+    fn gate_reports_next_opening() {
+        let rule = [window("mon-fri", "22:00", "06:00", "UTC")];
+        // Saturday noon → next opening is Monday 22:00.
+        let g = gate(&[], &rule, utc("2026-10-17T12:00:00Z"));
+        assert!(!g.open);
+        assert_eq!(g.opens_at, Some(utc("2026-10-19T22:00:00Z").timestamp()));
+        assert!(
+            gate(&[], &[], utc("2026-10-17T12:00:00Z")).open,
+            "no windows = open"
+        );
+    }
+
+    #[test]
+    fn gate_intersects_global_and_rule_windows() {
+        let global = [window("daily", "20:00", "04:00", "UTC")];
+        let rule = [window("daily", "02:00", "08:00", "UTC")];
+        let g = gate(&global, &rule, utc("2026-10-19T12:00:00Z"));
+        assert_eq!(g.opens_at, Some(utc("2026-10-20T02:00:00Z").timestamp()));
+        assert!(gate(&global, &rule, utc("2026-10-20T03:00:00Z")).open);
+        // Disjoint windows never open.
+        let never = [window("daily", "10:00", "11:00", "UTC")];
+        let g = gate(&global, &never, utc("2026-10-19T12:00:00Z"));
+        assert_eq!(
+            g,
+            WindowGate {
+                open: false,
+                opens_at: None
+            }
+        );
+    }
+
+    #[test]
+    fn mbps_converts_to_bytes() {
+        assert_eq!(mbps_to_bytes_per_sec(None), 0);
+        assert_eq!(mbps_to_bytes_per_sec(Some(8)), 1_000_000);
+    }
+}
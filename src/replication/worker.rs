@@ -27,6 +27,7 @@
 //! `reconcile_on_boot` flips the running row to `failed` but the token
 //! stays — next legitimate run resumes from the saved cursor.
 
+use super::external::RuleEngines;
 use super::planner::{compile_rule_globs, normalize_prefix};
 use super::state_store::{current_unix_seconds, FailureInsert, RunTotals};
 use super::walk;
@@ -38,8 +39,8 @@ use crate::event_outbox::{EventKind, EventSource, NewEvent};
 use crate::job_loop::MAX_JOB_PAGES;
 use crate::metrics::{bump_peak, Metrics};
 use crate::transfer::{
-    copy_object_with_retries, CopyStrategy, ObjectTransferRequest, TransferProvenance,
-    REPLICATION_RULE_METADATA_KEY,
+    copy_object_with_retries, BandwidthBudget, CopyStrategy, ObjectTransferRequest,
+    TransferProvenance, REPLICATION_RULE_METADATA_KEY,
 };
 use futures::stream::StreamExt;
 use std::sync::Arc;
@@ -72,13 +73,13 @@ pub fn inflight_snapshot(rule: &str) -> Vec<InFlightCopy> {
 
 /// RAII registration of one in-flight copy: inserted on construction, removed
 /// on drop — a killed page (its collect future dropped) cleans up implicitly.
-struct InFlightGuard {
+pub(super) struct InFlightGuard {
     rule: String,
     key: String,
 }
 
 impl InFlightGuard {
-    fn new(rule: &str, key: &str, size: u64) -> Self {
+    pub(super) fn new(rule: &str, key: &str, size: u64) -> Self {
         INFLIGHT
             .lock()
             .entry(rule.to_string())
@@ -162,6 +163,14 @@ enum DriverDone {
     },
 }
 
+/// The engine serving one side of the walk.
+fn side_engine(ends: &RuleEngines, side: walk::Side) -> &Arc<DynEngine> {
+    match side {
+        walk::Side::Src => &ends.source,
+        walk::Side::Dest => &ends.destination,
+    }
+}
+
 /// (bucket, normalized rule prefix) for one side of the walk.
 fn side_target(
     rule: &ReplicationRule,
@@ -233,17 +242,17 @@ fn to_rel_listing(
 /// DB-FREE by contract (see `copy_one_object`) — failure text rides back in
 /// `DriverDone::Delete.error` for the driver to persist.
 async fn execute_delete(
-    engine: &Arc<DynEngine>,
+    ends: &RuleEngines,
     src_bucket: &str,
     dst_bucket: &str,
     abs_src: &str,
     abs_dest: &str,
     item_id: u64,
 ) -> DriverDone {
-    if let Some(m) = engine.metrics() {
+    if let Some(m) = &ends.metrics {
         m.replication_head_calls_total.inc();
     }
-    match engine.head(src_bucket, abs_src).await {
+    match ends.source.head(src_bucket, abs_src).await {
         Ok(_) => DriverDone::Delete {
             item_id,
             deleted: false,
@@ -255,7 +264,7 @@ async fn execute_delete(
                 // Same test-only stall as the copy path: lets a kill land
                 // deterministically mid-delete (inert in prod).
                 maybe_pass_stall().await;
-                match engine.delete(dst_bucket, abs_dest).await {
+                match ends.destination.delete(dst_bucket, abs_dest).await {
                     Ok(_) => DriverDone::Delete {
                         item_id,
                         deleted: true,
@@ -376,8 +385,24 @@ pub async fn run_rule(
     maintenance_gate: Option<Arc<crate::maintenance::gate::MaintenanceGate>>,
     coordination_lease: Option<Arc<dyn crate::coordination::CoordinationLease>>,
 ) -> Result<(i64, RunOutcome), crate::config_db::ConfigDbError> {
+    if rule.direction == crate::config_sections::ReplicationDirection::Bidirectional {
+        return super::bidi::run_bidirectional(
+            db,
+            engine,
+            rule,
+            max_failures_retained,
+            object_timeout,
+            triggered_by,
+            lease,
+            concurrency,
+            maintenance_gate,
+            coordination_lease,
+        )
+        .await;
+    }
     let transfers = concurrency.transfers.clamp(1, 64) as usize;
     let upload_concurrency = concurrency.upload_concurrency.clamp(1, 16) as usize;
+    let bandwidth = super::throttle::budget(rule);
     let started_at = current_unix_seconds();
 
     // Look up the saved continuation token to resume from a prior tick.
@@ -420,6 +445,9 @@ pub async fn run_rule(
     // page boundary AND raced against the in-flight page so a wedged object
     // (e.g. stuck on a dead B2 dest) aborts immediately, not after its timeout.
     let mut killed = false;
+    // Set when the transfer window closes mid-run: the run stops like a pause
+    // and is rescheduled for the reopening instead of the rule's cadence.
+    let mut window_reopens: Option<Option<i64>> = None;
     let cap = rule.batch_size.clamp(1, 10_000);
     let source_prefix = normalize_prefix(&rule.source.prefix);
     let dest_prefix = normalize_prefix(&rule.destination.prefix);
@@ -443,7 +471,9 @@ pub async fn run_rule(
         one_off: triggered_by == "run-now",
         max_failures_retained,
         dest_bucket: rule.destination.bucket.clone(),
-        maintenance_gate,
+        // Maintenance never rewrites an external destination.
+        maintenance_gate: maintenance_gate.filter(|_| rule.destination.external.is_none()),
+        windows: (triggered_by != "run-now").then(|| super::throttle::parse_windows(&rule.windows)),
     };
     let events_sink: EventSink = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
 
@@ -469,17 +499,33 @@ pub async fn run_rule(
             rule.name
         );
     }
-    let regime = walk::FactRegime {
-        src_lite_authoritative: engine.lite_list_carries_logical_facts(&rule.source.bucket),
-        dest_lite_authoritative: engine.lite_list_carries_logical_facts(&rule.destination.bucket),
+    // External sides are served by their own engines; an unreachable one
+    // (bad URL, SSRF-refused) fails the run before the walk starts.
+    let ends = match super::external::rule_engines(engine, rule).await {
+        Ok(ends) => Some(ends),
+        Err(e) => {
+            warn!("replication rule '{}': {e}", rule.name);
+            if let Err(le) =
+                log_failure(&db, &rule.name, run_id, "", "", &e, max_failures_retained).await
+            {
+                warn!(
+                    "replication rule '{}': failure-ring write failed: {le}",
+                    rule.name
+                );
+            }
+            totals.errors += 1;
+            hit_fatal_error = true;
+            None
+        }
     };
     let max_pages = match crate::config::env_parse::<u32>("DGP_TEST_MAX_JOB_PAGES") {
         Some(n) if n > 0 => n,
         _ => MAX_JOB_PAGES,
     };
     let dir_workers = concurrency.dir_concurrency.clamp(1, 16) as usize;
-    let mut machine = match compile_rule_globs(rule) {
-        Ok((include_globs, exclude_globs)) => Some(walk::WalkMachine::new(
+    let mut machine = match ends.as_ref().map(|ends| (ends, compile_rule_globs(rule))) {
+        None => None,
+        Some((ends, Ok((include_globs, exclude_globs)))) => Some(walk::WalkMachine::new(
             walk::WalkConfig {
                 scope,
                 rule_name: rule.name.clone(),
@@ -490,7 +536,14 @@ pub async fn run_rule(
                 replicate_deletes: rule.replicate_deletes,
                 include_globs,
                 exclude_globs,
-                regime,
+                regime: walk::FactRegime {
+                    src_lite_authoritative: ends
+                        .source
+                        .lite_list_carries_logical_facts(&rule.source.bucket),
+                    dest_lite_authoritative: ends
+                        .destination
+                        .lite_list_carries_logical_facts(&rule.destination.bucket),
+                },
                 page_size: cap,
                 max_pages,
                 dir_workers,
@@ -501,7 +554,7 @@ pub async fn run_rule(
             },
             resume,
         )),
-        Err(e) => {
+        Some((_, Err(e))) => {
             warn!("replication rule '{}' glob compile failed: {e}", rule.name);
             totals.errors += 1;
             hit_fatal_error = true;
@@ -517,7 +570,7 @@ pub async fn run_rule(
     let mut poison_cleared_any = false;
     let _progress_guard = WalkProgressGuard(rule.name.clone());
 
-    if let Some(machine) = machine.as_mut() {
+    if let (Some(machine), Some(ends)) = (machine.as_mut(), ends.as_ref()) {
         let mut inflight: futures::stream::FuturesUnordered<
             std::pin::Pin<Box<dyn std::future::Future<Output = DriverDone> + Send>>,
         > = futures::stream::FuturesUnordered::new();
@@ -587,6 +640,17 @@ pub async fn run_rule(
                         machine.drain(walk::DrainReason::LeaseLost);
                         break 'walk;
                     }
+                    Ok(ControlVerdict::OutsideWindow(opens_at)) => {
+                        info!(
+                            "replication rule '{}' left its transfer window mid-walk \
+                             (cursor preserved for resume)",
+                            rule.name
+                        );
+                        stopped_paused = true;
+                        window_reopens = Some(opens_at);
+                        machine.drain(walk::DrainReason::Paused);
+                        break 'walk;
+                    }
                 }
             }
 
@@ -608,14 +672,15 @@ pub async fn run_rule(
                         delimited,
                     } => {
                         lists_inflight += 1;
-                        let engine = engine.clone();
+                        let engine = side_engine(ends, side).clone();
+                        let metrics = ends.metrics.clone();
                         let (bucket, side_prefix) =
                             side_target(rule, &source_prefix, &dest_prefix, side);
                         let page_size = cap;
                         inflight.push(Box::pin(async move {
                             let abs_prefix = format!("{side_prefix}{rel_prefix}");
                             let abs_token = token.map(|t| format!("{side_prefix}{t}"));
-                            if let Some(m) = engine.metrics() {
+                            if let Some(m) = &metrics {
                                 m.replication_list_calls_total.inc();
                             }
                             let result = engine
@@ -639,14 +704,15 @@ pub async fn run_rule(
                         rel_keys,
                     } => {
                         heads_inflight += 1;
-                        let engine = engine.clone();
+                        let engine = side_engine(ends, side).clone();
+                        let metrics = ends.metrics.clone();
                         let (bucket, side_prefix) =
                             side_target(rule, &source_prefix, &dest_prefix, side);
                         inflight.push(Box::pin(async move {
                             let mut results = Vec::with_capacity(rel_keys.len());
                             for rel in rel_keys {
                                 let abs = format!("{side_prefix}{rel}");
-                                if let Some(m) = engine.metrics() {
+                                if let Some(m) = &metrics {
                                     m.replication_head_calls_total.inc();
                                 }
                                 let r = match engine.head(&bucket, &abs).await {
@@ -700,11 +766,12 @@ pub async fn run_rule(
                             }
                         }
                         copies_inflight += 1;
-                        let engine = engine.clone();
+                        let ends = ends.clone();
                         let rule_name = rule.name.clone();
                         let src_bucket = rule.source.bucket.clone();
                         let dst_bucket = rule.destination.bucket.clone();
                         let events = events_sink.clone();
+                        let bandwidth = bandwidth.clone();
                         let abs_src = format!("{source_prefix}{rel_key}");
                         let abs_dest = format!("{dest_prefix}{rel_key}");
                         // Register this dest write with the maintenance gate
@@ -721,12 +788,12 @@ pub async fn run_rule(
                             let _write_guard = write_guard;
                             // Guard increments objects_inflight (+peak) on entry and
                             // decrements on drop → proves the `transfers` concurrency.
-                            let _obj_guard = engine.metrics().cloned().map(ObjectGuard::new);
+                            let _obj_guard = ends.metrics.clone().map(ObjectGuard::new);
                             // Live "currently copying" registration for the Jobs UI
                             // (RAII: a kill dropping this future unregisters it).
                             let _inflight_reg = InFlightGuard::new(&rule_name, &abs_src, src_size);
                             let res = copy_one_object(
-                                &engine,
+                                &ends,
                                 &rule_name,
                                 &src_bucket,
                                 &dst_bucket,
@@ -734,6 +801,7 @@ pub async fn run_rule(
                                 &abs_dest,
                                 object_timeout,
                                 upload_concurrency,
+                                &bandwidth,
                                 &events,
                             )
                             .await;
@@ -746,14 +814,14 @@ pub async fn run_rule(
                     }
                     walk::Cmd::Delete { item_id, rel_key } => {
                         deletes_inflight += 1;
-                        let engine = engine.clone();
+                        let ends = ends.clone();
                         let src_bucket = rule.source.bucket.clone();
                         let dst_bucket = rule.destination.bucket.clone();
                         let abs_src = format!("{source_prefix}{rel_key}");
                         let abs_dest = format!("{dest_prefix}{rel_key}");
                         inflight.push(Box::pin(async move {
                             execute_delete(
-                                &engine,
+                                &ends,
                                 &src_bucket,
                                 &dst_bucket,
                                 &abs_src,
@@ -1132,7 +1200,9 @@ pub async fn run_rule(
             truncated,
         });
 
-        let next_due = if dest_unusable || backend_throttled {
+        let next_due = if let Some(opens_at) = window_reopens {
+            opens_at.unwrap_or_else(|| compute_next_due(rule, finished_at))
+        } else if dest_unusable || backend_throttled {
             // Dead dest won't recover in a minute, and a throttling backend
             // needs breathing room — back off to the rule's normal cadence
             // (but never faster than 60s) instead of hammering every minute.
@@ -1237,7 +1307,7 @@ struct PerObjectResult {
 /// tokio's fair semaphore and deadlock the whole proxy. All DB effects
 /// (poison-skip check, failure ring) live on the driver side.
 async fn copy_one_object(
-    engine: &Arc<DynEngine>,
+    ends: &RuleEngines,
     rule_name: &str,
     src_bucket: &str,
     dst_bucket: &str,
@@ -1245,6 +1315,7 @@ async fn copy_one_object(
     dest_key: &str,
     object_timeout: Option<std::time::Duration>,
     upload_concurrency: usize,
+    bandwidth: &BandwidthBudget,
     events: &EventSink,
 ) -> PerObjectResult {
     let mut out = PerObjectResult::default();
@@ -1261,9 +1332,12 @@ async fn copy_one_object(
             metadata_key: REPLICATION_RULE_METADATA_KEY,
             metadata_value: rule_name,
         }),
+        extra_user_metadata: &[],
         strip_user_metadata_keys: &[],
         operation: "replication",
         upload_concurrency: Some(upload_concurrency),
+        bandwidth: Some(bandwidth),
+        source_engine: ends.cross_source(),
     };
     // Bound the copy: a stalled object fails fast instead of hanging until
     // lease lapse. `Elapsed` routes into the Err arm below.
@@ -1271,7 +1345,7 @@ async fn copy_one_object(
         // Test-only stall INSIDE the timeout scope (inert in prod) so the
         // object-timeout Elapsed arm is deterministically exercisable.
         maybe_pass_stall().await;
-        copy_object_with_retries(engine, transfer).await
+        copy_object_with_retries(&ends.destination, transfer).await
     };
     let copy_result = match object_timeout {
         Some(timeout) => match tokio::time::timeout(timeout, copy_fut).await {
@@ -1405,7 +1479,7 @@ fn page_is_throttle_aborted(copied: i64, throttled: i64, attempted: i64) -> bool
 /// when it wins, the page's copy future is dropped and in-flight transfers abort.
 // ponytail: 1s poll → ≤1s kill latency. A notify channel would be tighter but
 // the run loop has no other reason to hold one; poll until that changes.
-fn spawn_lease_heartbeat(
+pub(super) fn spawn_lease_heartbeat(
     db: Arc<Mutex<ConfigDb>>,
     rule_name: &str,
     lease: Option<RunLease>,
@@ -1472,20 +1546,20 @@ fn spawn_lease_heartbeat(
 
 /// Inputs to the PURE terminal-settle decision (see `settle_run`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct SettleInput {
-    killed: bool,
-    stopped_paused: bool,
-    hit_fatal_error: bool,
-    had_any_error: bool,
-    objects_copied: i64,
+pub(super) struct SettleInput {
+    pub killed: bool,
+    pub stopped_paused: bool,
+    pub hit_fatal_error: bool,
+    pub had_any_error: bool,
+    pub objects_copied: i64,
     /// Forward pass ran out of page budget with pages still pending.
-    truncated: bool,
+    pub truncated: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct SettleDecision {
-    status: &'static str,
-    clear_cursor: bool,
+pub(super) struct SettleDecision {
+    pub status: &'static str,
+    pub clear_cursor: bool,
 }
 
 /// Terminal status + cursor decision, pure so the truth table is unit-tested.
@@ -1493,7 +1567,7 @@ struct SettleDecision {
 /// mid-sweep, cursor kept, next tick resumes the tail) > errors > clean.
 /// The cursor survives ANY interrupted/truncated pass — clearing it on a
 /// truncated "clean" run permanently orphaned objects past the page budget.
-fn settle_run(i: SettleInput) -> SettleDecision {
+pub(super) fn settle_run(i: SettleInput) -> SettleDecision {
     let status = if i.killed {
         "cancelled"
     } else if i.stopped_paused {
@@ -1514,13 +1588,17 @@ fn settle_run(i: SettleInput) -> SettleDecision {
     }
 }
 
-/// What a run-control check decided. Precedence: Killed > LeaseLost > Paused.
+/// What a run-control check decided. Precedence: Killed > LeaseLost > Paused
+/// > OutsideWindow.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ControlVerdict {
+pub(super) enum ControlVerdict {
     Continue,
     Killed,
     Paused,
     LeaseLost,
+    /// The rule's transfer window closed: stop like a pause (cursor kept) and
+    /// come back when it reopens (unix seconds; `None` = never overlaps).
+    OutsideWindow(Option<i64>),
 }
 
 /// PURE precedence fold for the page-boundary control check. `one_off`
@@ -1565,31 +1643,37 @@ fn control_verdict_with_maintenance(
 
 /// Uniform run-control: kill / pause / lease evaluated identically at every
 /// page boundary of every pass (forward copy, delete pass, oracle descent).
-struct RunControl {
-    db: Arc<Mutex<ConfigDb>>,
-    rule_name: String,
-    run_id: i64,
-    lease: Option<RunLease>,
+pub(super) struct RunControl {
+    pub db: Arc<Mutex<ConfigDb>>,
+    pub rule_name: String,
+    pub run_id: i64,
+    pub lease: Option<RunLease>,
     /// Cross-instance lease (when the job plane runs shared). When present the
     /// heartbeat + control renew go through it (matching the acquire), so an
     /// S3-CAS lease is renewed against the SAME object the scheduler took — not
     /// the node-local SQLite row.
-    coordination_lease: Option<Arc<dyn crate::coordination::CoordinationLease>>,
-    lease_alive: std::sync::Arc<std::sync::atomic::AtomicBool>,
-    one_off: bool,
-    max_failures_retained: u32,
+    pub coordination_lease: Option<Arc<dyn crate::coordination::CoordinationLease>>,
+    pub lease_alive: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    pub one_off: bool,
+    pub max_failures_retained: u32,
     /// Destination bucket + the write-gate: if a maintenance job starts
     /// rewriting the dest MID-RUN, defer (stop, cursor preserved) instead of
     /// writing into a bucket being migrated/re-encrypted (finding #12).
-    dest_bucket: String,
-    maintenance_gate: Option<Arc<crate::maintenance::gate::MaintenanceGate>>,
+    pub dest_bucket: String,
+    pub maintenance_gate: Option<Arc<crate::maintenance::gate::MaintenanceGate>>,
+    /// The rule's transfer windows, enforced at each check alongside the
+    /// global ones. `None` for run-now (an explicit one-off ignores windows).
+    pub windows: Option<Vec<super::throttle::Window>>,
 }
 
 impl RunControl {
     /// One db.lock: cancel flag + paused flag + (when `renew`) lease renewal.
     /// A lost lease is recorded as a run failure only on the `renew` variant
     /// so back-to-back checks don't double-log.
-    async fn check(&self, renew: bool) -> Result<ControlVerdict, crate::config_db::ConfigDbError> {
+    pub(super) async fn check(
+        &self,
+        renew: bool,
+    ) -> Result<ControlVerdict, crate::config_db::ConfigDbError> {
         let mut lease_ok =
             self.lease.is_none() || self.lease_alive.load(std::sync::atomic::Ordering::Acquire);
         // Read cancel/paused under the DB lock, then DROP it before any lease
@@ -1640,13 +1724,26 @@ impl RunControl {
             .as_ref()
             .map(|g| g.is_busy(&self.dest_bucket))
             .unwrap_or(false);
-        let verdict = control_verdict_with_maintenance(
+        let verdict = match control_verdict_with_maintenance(
             cancel_requested,
             paused,
             self.one_off,
             lease_ok,
             maint_busy,
-        );
+        ) {
+            ControlVerdict::Continue => match &self.windows {
+                Some(windows) => {
+                    let gate = super::throttle::gate_now(windows);
+                    if gate.open {
+                        ControlVerdict::Continue
+                    } else {
+                        ControlVerdict::OutsideWindow(gate.opens_at)
+                    }
+                }
+                None => ControlVerdict::Continue,
+            },
+            other => other,
+        };
         if verdict == ControlVerdict::LeaseLost && renew {
             log_failure(
                 &self.db,
@@ -1665,10 +1762,10 @@ impl RunControl {
 
 /// Per-run event sink: copy futures push events the moment a copy is durable,
 /// so a kill that drops the page's collect future can't lose them.
-type EventSink = std::sync::Arc<parking_lot::Mutex<Vec<NewEvent>>>;
+pub(super) type EventSink = std::sync::Arc<parking_lot::Mutex<Vec<NewEvent>>>;
 
 /// Drain the sink and flush under a freshly-acquired DB lock.
-async fn flush_event_sink(db: &Arc<Mutex<ConfigDb>>, rule_name: &str, sink: &EventSink) {
+pub(super) async fn flush_event_sink(db: &Arc<Mutex<ConfigDb>>, rule_name: &str, sink: &EventSink) {
     let mut drained: Vec<NewEvent> = std::mem::take(&mut *sink.lock());
     flush_page_events(db, rule_name, &mut drained).await;
 }
@@ -1728,7 +1825,7 @@ async fn log_failure(
 /// Compute when this rule should next be due. Falls back to a 1-hour
 /// recovery window if the rule's `interval` is unparseable (should
 /// never happen in practice — validated at Config::check time).
-fn compute_next_due(rule: &ReplicationRule, finished_at: i64) -> i64 {
+pub(super) fn compute_next_due(rule: &ReplicationRule, finished_at: i64) -> i64 {
     match humantime::parse_duration(&rule.interval) {
         Ok(d) => finished_at + d.as_secs() as i64,
         Err(_) => finished_at + 3600,
@@ -1738,7 +1835,9 @@ fn compute_next_due(rule: &ReplicationRule, finished_at: i64) -> i64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config_sections::{ConflictPolicy, ReplicationEndpoint, ReplicationRule};
+    use crate::config_sections::{
+        ConflictPolicy, ReplicationDirection, ReplicationEndpoint, ReplicationRule,
+    };
 
     #[test]
     fn throttle_abort_fires_for_small_batch_full_page() {
@@ -1949,16 +2048,22 @@ mod tests {
             source: ReplicationEndpoint {
                 bucket: "a".into(),
                 prefix: String::new(),
+                external: None,
             },
             destination: ReplicationEndpoint {
                 bucket: "b".into(),
                 prefix: String::new(),
+                external: None,
             },
             interval: "1h".into(),
             batch_size: 100,
             replicate_deletes: false,
+            direction: ReplicationDirection::OneWay,
+            tombstone_retention: "30d".into(),
             conflict: ConflictPolicy::NewerWins,
             strict_content_diff: false,
+            bandwidth_limit_mbps: None,
+            windows: Vec::new(),
             include_globs: Vec::new(),
             exclude_globs: vec![".dg/*".into()],
         }
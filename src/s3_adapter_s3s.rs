@@ -19,6 +19,7 @@ use crate::deltaglider::RetrieveResponse;
 use crate::iam::{
     user_can_see_common_prefix, user_can_see_listed_key, AuthenticatedUser, ListScope, S3Action,
 };
+use crate::read_fallback::{FallbackReason, ReadFallbackRoute};
 use crate::storage::StorageError;
 use crate::types::FileMetadata;
 use futures::stream::BoxStream;
@@ -84,7 +85,7 @@ impl DeltaGliderS3Service {
                 bucket,
                 key,
                 crate::event_outbox::EventSource::S3Api,
-                crate::replication::current_unix_seconds(),
+                crate::event_outbox::current_unix_seconds(),
                 payload,
             ),
         )
@@ -122,14 +123,19 @@ impl s3s::S3 for DeltaGliderS3Service {
         &self,
         req: s3s::S3Request<s3s::dto::HeadObjectInput>,
     ) -> s3s::S3Result<s3s::S3Response<s3s::dto::HeadObjectOutput>> {
+        let route = req.extensions.get::<ReadFallbackRoute>().cloned();
+        let requester =
+            crate::access_events::Requester::from_request(&req.extensions, &req.headers);
         let input = req.input;
-        let meta = self
-            .state
-            .engine
-            .load()
-            .head(&input.bucket, &input.key)
-            .await
-            .map_err(engine_error_to_s3s)?;
+        let engine = self.state.engine.load_full();
+        let (engine, key) = (&engine, input.key.as_str());
+        let (meta, stale) = read_with_fallback(
+            &self.state,
+            route.as_ref(),
+            &input.bucket,
+            move |b| async move { engine.head(&b, key).await },
+        )
+        .await?;
         evaluate_read_conditionals_s3s(
             &meta,
             input.if_match.as_ref(),
@@ -158,6 +164,18 @@ impl s3s::S3 for DeltaGliderS3Service {
         let mut resp = s3s::S3Response::new(output);
         resp.status = status;
         add_storage_debug_headers(&mut resp.headers, &meta);
+        mark_stale_read(&mut resp.headers, stale.as_deref());
+        self.state
+            .access_events
+            .object_read(
+                true,
+                &input.bucket,
+                &input.key,
+                &meta,
+                input.range.map(|r| r.to_header_string()),
+                &requester,
+            )
+            .await;
         Ok(resp)
     }
 
@@ -165,12 +183,23 @@ impl s3s::S3 for DeltaGliderS3Service {
         &self,
         req: s3s::S3Request<s3s::dto::GetObjectInput>,
     ) -> s3s::S3Result<s3s::S3Response<s3s::dto::GetObjectOutput>> {
+        let route = req.extensions.get::<ReadFallbackRoute>().cloned();
+        let requester =
+            crate::access_events::Requester::from_request(&req.extensions, &req.headers);
         let input = req.input;
-        let engine = self.state.engine.load();
-        let head = engine
-            .head(&input.bucket, &input.key)
-            .await
-            .map_err(engine_error_to_s3s)?;
+        let engine = self.state.engine.load_full();
+        let (head, stale) = {
+            let (engine, key) = (&engine, input.key.as_str());
+            read_with_fallback(
+                &self.state,
+                route.as_ref(),
+                &input.bucket,
+                move |b| async move { engine.head(&b, key).await },
+            )
+            .await?
+        };
+        // Every later read stays on whichever copy answered the HEAD.
+        let read_bucket = stale.as_deref().unwrap_or(&input.bucket);
         evaluate_read_conditionals_s3s(
             &head,
             input.if_match.as_ref(),
@@ -188,7 +217,7 @@ impl s3s::S3 for DeltaGliderS3Service {
             let content_range = format!("bytes {start}-{end_inclusive}/{}", head.file_size);
 
             if let Some((stream, content_length, metadata)) = engine
-                .retrieve_stream_range(&input.bucket, &input.key, start, end_inclusive, None)
+                .retrieve_stream_range(read_bucket, &input.key, start, end_inclusive, None)
                 .await
                 .map_err(engine_error_to_s3s)?
             {
@@ -200,11 +229,23 @@ impl s3s::S3 for DeltaGliderS3Service {
                 let mut resp =
                     s3s::S3Response::with_status(output, axum::http::StatusCode::PARTIAL_CONTENT);
                 add_storage_debug_headers(&mut resp.headers, &metadata);
+                mark_stale_read(&mut resp.headers, stale.as_deref());
+                self.state
+                    .access_events
+                    .object_read(
+                        false,
+                        &input.bucket,
+                        &input.key,
+                        &head,
+                        Some(range.to_header_string()),
+                        &requester,
+                    )
+                    .await;
                 return Ok(resp);
             }
 
             let (data, metadata) = engine
-                .retrieve(&input.bucket, &input.key)
+                .retrieve(read_bucket, &input.key)
                 .await
                 .map_err(engine_error_to_s3s)?;
             // `checked` was validated against the HEAD `file_size`, but we
@@ -229,14 +270,23 @@ impl s3s::S3 for DeltaGliderS3Service {
             let mut resp =
                 s3s::S3Response::with_status(output, axum::http::StatusCode::PARTIAL_CONTENT);
             add_storage_debug_headers(&mut resp.headers, &metadata);
+            mark_stale_read(&mut resp.headers, stale.as_deref());
+            self.state
+                .access_events
+                .object_read(
+                    false,
+                    &input.bucket,
+                    &input.key,
+                    &head,
+                    Some(range.to_header_string()),
+                    &requester,
+                )
+                .await;
             return Ok(resp);
         }
 
-        let response = self
-            .state
-            .engine
-            .load()
-            .retrieve_stream(&input.bucket, &input.key)
+        let response = engine
+            .retrieve_stream(read_bucket, &input.key)
             .await
             .map_err(engine_error_to_s3s)?;
         let (body, metadata) = match response {
@@ -255,6 +305,11 @@ impl s3s::S3 for DeltaGliderS3Service {
         apply_get_response_overrides(&input, &mut output);
         let mut resp = s3s::S3Response::new(output);
         add_storage_debug_headers(&mut resp.headers, &metadata);
+        mark_stale_read(&mut resp.headers, stale.as_deref());
+        self.state
+            .access_events
+            .object_read(false, &input.bucket, &input.key, &head, None, &requester)
+            .await;
         Ok(resp)
     }
 
@@ -277,22 +332,31 @@ impl s3s::S3 for DeltaGliderS3Service {
         req: s3s::S3Request<s3s::dto::ListObjectsInput>,
     ) -> s3s::S3Result<s3s::S3Response<s3s::dto::ListObjectsOutput>> {
         let list_scope = req.extensions.get::<ListScope>().cloned();
+        let route = req.extensions.get::<ReadFallbackRoute>().cloned();
         let input = req.input;
         let max_keys = input.max_keys.unwrap_or(1000).clamp(1, 1000) as u32;
-        let mut page = self
-            .state
-            .engine
-            .load()
-            .list_objects(
+        let engine = self.state.engine.load_full();
+        let (mut page, stale) = {
+            let (engine, input) = (&engine, &input);
+            read_with_fallback(
+                &self.state,
+                route.as_ref(),
                 &input.bucket,
-                input.prefix.as_deref().unwrap_or(""),
-                input.delimiter.as_deref(),
-                max_keys,
-                input.marker.as_deref(),
-                false,
+                move |b| async move {
+                    engine
+                        .list_objects(
+                            &b,
+                            input.prefix.as_deref().unwrap_or(""),
+                            input.delimiter.as_deref(),
+                            max_keys,
+                            input.marker.as_deref(),
+                            false,
+                        )
+                        .await
+                },
             )
-            .await
-            .map_err(engine_error_to_s3s)?;
+            .await?
+        };
         if let Some(ListScope::Filtered { user }) = list_scope {
             page.objects
                 .retain(|(key, _)| user_can_see_listed_key(&user, &input.bucket, key));
@@ -322,7 +386,7 @@ impl s3s::S3 for DeltaGliderS3Service {
                 prefix: Some(p.clone()),
             })
             .collect();
-        Ok(s3s::S3Response::new(s3s::dto::ListObjectsOutput {
+        let mut resp = s3s::S3Response::new(s3s::dto::ListObjectsOutput {
             name: Some(input.bucket.clone()),
             prefix: input.prefix.clone(),
             delimiter: input.delimiter.clone(),
@@ -334,7 +398,9 @@ impl s3s::S3 for DeltaGliderS3Service {
             common_prefixes: Some(common_prefixes),
             encoding_type: input.encoding_type,
             ..Default::default()
-        }))
+        });
+        mark_stale_read(&mut resp.headers, stale.as_deref());
+        Ok(resp)
     }
 
     async fn list_objects_v2(
@@ -343,22 +409,31 @@ impl s3s::S3 for DeltaGliderS3Service {
     ) -> s3s::S3Result<s3s::S3Response<s3s::dto::ListObjectsV2Output>> {
         let list_scope = req.extensions.get::<ListScope>().cloned();
         let include_metadata = query_flag(&req.uri, "metadata", "true");
+        let route = req.extensions.get::<ReadFallbackRoute>().cloned();
         let input = req.input;
         let max_keys = input.max_keys.unwrap_or(1000).clamp(1, 1000) as u32;
-        let mut page = self
-            .state
-            .engine
-            .load()
-            .list_objects(
+        let engine = self.state.engine.load_full();
+        let (mut page, stale) = {
+            let (engine, input) = (&engine, &input);
+            read_with_fallback(
+                &self.state,
+                route.as_ref(),
                 &input.bucket,
-                input.prefix.as_deref().unwrap_or(""),
-                input.delimiter.as_deref(),
-                max_keys,
-                input.continuation_token.as_deref(),
-                false,
+                move |b| async move {
+                    engine
+                        .list_objects(
+                            &b,
+                            input.prefix.as_deref().unwrap_or(""),
+                            input.delimiter.as_deref(),
+                            max_keys,
+                            input.continuation_token.as_deref(),
+                            false,
+                        )
+                        .await
+                },
             )
-            .await
-            .map_err(engine_error_to_s3s)?;
+            .await?
+        };
         if let Some(ListScope::Filtered { user }) = list_scope {
             page.objects
                 .retain(|(key, _)| user_can_see_listed_key(&user, &input.bucket, key));
@@ -378,6 +453,7 @@ impl s3s::S3 for DeltaGliderS3Service {
         if let Some(metadata_ext) = metadata_ext {
             resp.extensions.insert(metadata_ext);
         }
+        mark_stale_read(&mut resp.headers, stale.as_deref());
         Ok(resp)
     }
 
@@ -589,15 +665,34 @@ impl s3s::S3 for DeltaGliderS3Service {
 
         // Check object emptiness first: only visible objects are hard blockers.
         // Mirror the axum handler in `src/api/handlers/bucket.rs::delete_bucket`
-        // — keep both adapters' contracts in sync.
+        // — keep both adapters' contracts in sync. Parts staged by the upload
+        // registry list like objects; look past them.
+        let staged_parts = engine
+            .registry_staged_part_count(&bucket)
+            .await
+            .map_err(engine_error_to_s3s)?;
         let page = engine
-            .list_objects(&bucket, "", None, 1, None, false)
+            .list_objects(&bucket, "", None, staged_parts as u32 + 1, None, false)
             .await
             .map_err(engine_error_to_s3s)?;
-        let has_objects = !page.objects.is_empty();
-        let first_object = page.objects.first().map(|(key, _)| key.as_str());
-
-        let mpu_count = self.state.multipart.count_uploads_for_bucket(&bucket);
+        let staging = format!("{}/", crate::multipart_registry::STAGING_PREFIX);
+        let first_object = page
+            .objects
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .find(|key| !key.starts_with(&staging));
+        let has_objects = first_object.is_some();
+
+        let mut mpu_count = self.state.multipart.count_uploads_for_bucket(&bucket);
+        if engine.stages_client_multipart(&bucket) {
+            // Staged uploads live in the bucket (registry ones are purged
+            // below, backend-held ones go with it).
+            mpu_count += engine
+                .list_staged_multipart_uploads(&bucket)
+                .await
+                .map(|u| u.len())
+                .unwrap_or(0);
+        }
         if has_objects {
             let sample = first_object.unwrap_or("<unknown>");
             return Err(s3s::s3_error!(
@@ -632,6 +727,10 @@ impl s3s::S3 for DeltaGliderS3Service {
                 }
             }
         }
+        engine
+            .purge_staged_multipart_uploads(&bucket)
+            .await
+            .map_err(engine_error_to_s3s)?;
 
         engine
             .delete_bucket(&bucket)
@@ -645,12 +744,47 @@ impl s3s::S3 for DeltaGliderS3Service {
         req: s3s::S3Request<s3s::dto::DeleteObjectInput>,
     ) -> s3s::S3Result<s3s::S3Response<s3s::dto::DeleteObjectOutput>> {
         let auth_user = req.extensions.get::<AuthenticatedUser>().cloned();
+        let route = req.extensions.get::<ReadFallbackRoute>().cloned();
         let input = req.input;
         crate::api::handlers::object_helpers::check_client_write_allowed(
             &self.state,
             &input.bucket,
         )
         .map_err(engine_error_to_s3s)?;
+        if let Some(route) = route {
+            if input.key.ends_with('/') {
+                return Err(s3s::s3_error!(
+                    ServiceUnavailable,
+                    "prefix deletes are not queued while bucket '{}' is served from its replica",
+                    input.bucket
+                ));
+            }
+            queue_fallback_write(&self.state, &input.bucket, &input.key).await?;
+            match self
+                .state
+                .engine
+                .load()
+                .delete(&route.replica, &input.key)
+                .await
+            {
+                Ok(_) => {
+                    self.emit_object_event(
+                        crate::event_outbox::EventKind::ObjectDeleted,
+                        &route.replica,
+                        &input.key,
+                        serde_json::json!({}),
+                    )
+                    .await;
+                }
+                // Not on the replica (yet): the queued delete still applies
+                // to the primary on replay.
+                Err(crate::deltaglider::EngineError::NotFound(_)) => {}
+                Err(e) => return Err(engine_error_to_s3s(e)),
+            }
+            let mut resp = s3s::S3Response::new(s3s::dto::DeleteObjectOutput::default());
+            mark_queued_write(&mut resp.headers, &route.replica);
+            return Ok(resp);
+        }
         if input.key.ends_with('/') {
             let (deleted, denied) = recursive_delete_prefix_s3s(
                 &self.state,
@@ -747,7 +881,7 @@ impl s3s::S3 for DeltaGliderS3Service {
                             input.bucket.clone(),
                             key.clone(),
                             crate::event_outbox::EventSource::S3Api,
-                            crate::replication::current_unix_seconds(),
+                            crate::event_outbox::current_unix_seconds(),
                             serde_json::json!({}),
                         ));
                     }
@@ -797,6 +931,7 @@ impl s3s::S3 for DeltaGliderS3Service {
             .extensions
             .get::<crate::api::auth::SignedPayloadHash>()
             .cloned();
+        let route = req.extensions.get::<ReadFallbackRoute>().cloned();
         let input = req.input;
         let engine = self.state.engine.load();
         // Gate BEFORE head_bucket: a marked bucket refuses client bytes
@@ -807,8 +942,13 @@ impl s3s::S3 for DeltaGliderS3Service {
             &input.bucket,
         )
         .map_err(engine_error_to_s3s)?;
+        // Primary down + `writes: queue`: the bytes land on the replica.
+        // Policy gates (write block, quota) stay keyed on the client's bucket.
+        let target = route
+            .as_ref()
+            .map_or(input.bucket.clone(), |r| r.replica.clone());
         if !engine
-            .head_bucket(&input.bucket)
+            .head_bucket(&target)
             .await
             .map_err(engine_error_to_s3s)?
         {
@@ -834,12 +974,15 @@ impl s3s::S3 for DeltaGliderS3Service {
         .map_err(engine_error_to_s3s)?;
         evaluate_put_etag_conditionals_s3s(
             engine.as_ref(),
-            &input.bucket,
+            &target,
             &input.key,
             input.if_match.as_ref(),
             input.if_none_match.as_ref(),
         )
         .await?;
+        if route.is_some() {
+            queue_fallback_write(&self.state, &input.bucket, &input.key).await?;
+        }
         let content_type = input.content_type;
         let user_metadata = input.metadata.unwrap_or_default();
         // Large delta-eligible objects: route through the streaming spool store so
@@ -862,7 +1005,7 @@ impl s3s::S3 for DeltaGliderS3Service {
             })?;
             engine
                 .store_spooled_delta(
-                    &input.bucket,
+                    &target,
                     &input.key,
                     &spool,
                     data.len() as u64,
@@ -874,25 +1017,21 @@ impl s3s::S3 for DeltaGliderS3Service {
                 .map_err(engine_error_to_s3s)?
         } else {
             engine
-                .store(
-                    &input.bucket,
-                    &input.key,
-                    &data,
-                    content_type,
-                    user_metadata,
-                )
+                .store(&target, &input.key, &data, content_type, user_metadata)
                 .await
                 .map_err(engine_error_to_s3s)?
         };
+        let mut payload = serde_json::json!({
+            "content_length": data.len(),
+            "storage_type": result.metadata.storage_info.label(),
+            "etag": result.metadata.etag(),
+        });
+        crate::event_outbox::attach_user_metadata(&mut payload, &result.metadata.user_metadata);
         self.emit_object_event(
             crate::event_outbox::EventKind::ObjectCreated,
-            &input.bucket,
+            &target,
             &input.key,
-            serde_json::json!({
-                "content_length": data.len(),
-                "storage_type": result.metadata.storage_info.label(),
-                "etag": result.metadata.etag(),
-            }),
+            payload,
         )
         .await;
         let mut resp = s3s::S3Response::new(s3s::dto::PutObjectOutput {
@@ -900,6 +1039,9 @@ impl s3s::S3 for DeltaGliderS3Service {
             ..Default::default()
         });
         add_storage_debug_headers(&mut resp.headers, &result.metadata);
+        if let Some(route) = route {
+            mark_queued_write(&mut resp.headers, &route.replica);
+        }
         Ok(resp)
     }
 
@@ -988,15 +1130,17 @@ impl s3s::S3 for DeltaGliderS3Service {
             .map_err(engine_error_to_s3s)?;
         // A copy creates a new object at the destination — emit ObjectCreated
         // for the dest key. Routing decides whether a replication rule cares.
+        let mut payload = serde_json::json!({
+            "content_length": data.len(),
+            "storage_type": result.metadata.storage_info.label(),
+            "etag": result.metadata.etag(),
+        });
+        crate::event_outbox::attach_user_metadata(&mut payload, &result.metadata.user_metadata);
         self.emit_object_event(
             crate::event_outbox::EventKind::ObjectCreated,
             &input.bucket,
             &input.key,
-            serde_json::json!({
-                "content_length": data.len(),
-                "storage_type": result.metadata.storage_info.label(),
-                "etag": result.metadata.etag(),
-            }),
+            payload,
         )
         .await;
         Ok(s3s::S3Response::new(s3s::dto::CopyObjectOutput {
@@ -1026,18 +1170,30 @@ impl s3s::S3 for DeltaGliderS3Service {
             "DGP_MPU_DELTA_RECONSTRUCT_MAX_BYTES",
             64 * 1024 * 1024,
         );
-        let upload_id = self
-            .state
-            .multipart
-            .create_with_relay_policy(
-                &input.bucket,
-                &input.key,
-                input.content_type.clone(),
-                input.metadata.unwrap_or_default(),
-                Some(delta_limit),
-                false,
-            )
-            .map_err(engine_error_to_s3s)?;
+        let engine = self.state.engine.load();
+        let upload_id = if engine.stages_client_multipart(&input.bucket) {
+            engine
+                .create_staged_multipart(
+                    &input.bucket,
+                    &input.key,
+                    input.content_type.clone(),
+                    input.metadata.unwrap_or_default(),
+                )
+                .await
+                .map_err(engine_error_to_s3s)?
+        } else {
+            self.state
+                .multipart
+                .create_with_relay_policy(
+                    &input.bucket,
+                    &input.key,
+                    input.content_type.clone(),
+                    input.metadata.unwrap_or_default(),
+                    Some(delta_limit),
+                    false,
+                )
+                .map_err(engine_error_to_s3s)?
+        };
         Ok(s3s::S3Response::new(
             s3s::dto::CreateMultipartUploadOutput {
                 bucket: Some(input.bucket),
@@ -1062,17 +1218,15 @@ impl s3s::S3 for DeltaGliderS3Service {
         )
         .await?;
         validate_content_md5_s3s(input.content_md5.as_deref(), &data)?;
-        let etag = self
-            .state
-            .multipart
-            .upload_part(
-                &input.upload_id,
-                &input.bucket,
-                &input.key,
-                input.part_number as u32,
-                data,
-            )
-            .map_err(engine_error_to_s3s)?;
+        let etag = store_upload_part(
+            &self.state,
+            &input.upload_id,
+            &input.bucket,
+            &input.key,
+            input.part_number,
+            data,
+        )
+        .await?;
         Ok(s3s::S3Response::new(s3s::dto::UploadPartOutput {
             e_tag: Some(parse_s3s_etag(&etag)?),
             ..Default::default()
@@ -1084,6 +1238,28 @@ impl s3s::S3 for DeltaGliderS3Service {
         req: s3s::S3Request<s3s::dto::AbortMultipartUploadInput>,
     ) -> s3s::S3Result<s3s::S3Response<s3s::dto::AbortMultipartUploadOutput>> {
         let input = req.input;
+        if let Some(staged) =
+            staged_upload(&self.state, &input.bucket, &input.key, &input.upload_id).await?
+        {
+            // Same C4 rule as the in-memory store: an abort racing the
+            // completion would report "aborted" for an object that lands.
+            if staged_completion_in_progress(&self.state, &staged).await? {
+                return Err(engine_error_to_s3s(
+                    crate::api::errors::S3Error::InvalidRequest(
+                        "Cannot abort: upload is currently being completed".to_string(),
+                    ),
+                ));
+            }
+            self.state
+                .engine
+                .load()
+                .abort_staged_multipart(&staged)
+                .await
+                .map_err(engine_error_to_s3s)?;
+            return Ok(s3s::S3Response::new(
+                s3s::dto::AbortMultipartUploadOutput::default(),
+            ));
+        }
         self.state
             .multipart
             .abort(&input.upload_id, &input.bucket, &input.key)
@@ -1100,17 +1276,37 @@ impl s3s::S3 for DeltaGliderS3Service {
         let input = req.input;
         let max_parts = input.max_parts.unwrap_or(1000).clamp(1, 1000) as u32;
         let marker = input.part_number_marker.unwrap_or(0) as u32;
-        let (parts, is_truncated, next_marker) = self
-            .state
-            .multipart
-            .list_parts_paginated(
-                &input.upload_id,
-                &input.bucket,
-                &input.key,
-                marker,
-                max_parts,
-            )
-            .map_err(engine_error_to_s3s)?;
+        let staged =
+            staged_upload(&self.state, &input.bucket, &input.key, &input.upload_id).await?;
+        let (parts, is_truncated, next_marker) = if let Some(staged) = staged {
+            let parts = self
+                .state
+                .engine
+                .load()
+                .list_staged_parts(&staged)
+                .await
+                .map_err(engine_error_to_s3s)?
+                .into_iter()
+                .map(|p| crate::multipart::PartInfo {
+                    part_number: p.part_number as u32,
+                    etag: p.etag,
+                    size: p.size,
+                    last_modified: p.last_modified,
+                })
+                .collect();
+            crate::multipart::paginate_parts(parts, marker, max_parts)
+        } else {
+            self.state
+                .multipart
+                .list_parts_paginated(
+                    &input.upload_id,
+                    &input.bucket,
+                    &input.key,
+                    marker,
+                    max_parts,
+                )
+                .map_err(engine_error_to_s3s)?
+        };
         let parts = parts
             .into_iter()
             .map(|p| s3s::dto::Part {
@@ -1148,6 +1344,8 @@ impl s3s::S3 for DeltaGliderS3Service {
         .map_err(engine_error_to_s3s)?;
         ensure_bucket_exists_s3s(&self.state, &input.bucket).await?;
         let requested_parts = completed_parts_to_request(input.multipart_upload.as_ref())?;
+        let staged =
+            staged_upload(&self.state, &input.bucket, &input.key, &input.upload_id).await?;
 
         // Completion registry: exactly one request runs the store pipeline, on a
         // DETACHED task (a client disconnect must not cancel a half-done store);
@@ -1191,6 +1389,18 @@ impl s3s::S3 for DeltaGliderS3Service {
                 )),
             },
             crate::multipart::BeginComplete::Owner(publisher) => {
+                if let Some(staged) = staged {
+                    let (etag, meta) = complete_staged_upload(
+                        &self.state,
+                        staged,
+                        &input.bucket,
+                        &input.key,
+                        requested_parts,
+                        publisher,
+                    )
+                    .await?;
+                    return complete_response(&etag, Some(&meta));
+                }
                 // Admission (quota) + routing decisions run ONLY for the owner: a
                 // tombstone/join retry must never be re-admitted — its bytes are
                 // already committed, and the freeze/quota gates would wrongly
@@ -1254,12 +1464,35 @@ impl s3s::S3 for DeltaGliderS3Service {
         &self,
         req: s3s::S3Request<s3s::dto::ListMultipartUploadsInput>,
     ) -> s3s::S3Result<s3s::S3Response<s3s::dto::ListMultipartUploadsOutput>> {
+        let list_scope = req.extensions.get::<ListScope>().cloned();
         let input = req.input;
         ensure_bucket_exists_s3s(&self.state, &input.bucket).await?;
         let max_uploads = input.max_uploads.unwrap_or(1000).clamp(1, 1000) as u32;
+        let engine = self.state.engine.load();
+        let staged = if engine.stages_client_multipart(&input.bucket) {
+            engine
+                .list_staged_multipart_uploads(&input.bucket)
+                .await
+                .map_err(engine_error_to_s3s)?
+                .into_iter()
+                .map(|u| crate::multipart::UploadInfo {
+                    key: crate::types::ObjectKey {
+                        bucket: input.bucket.clone(),
+                        prefix: u.prefix,
+                        filename: u.filename,
+                    }
+                    .full_key(),
+                    upload_id: u.upload.upload_id,
+                    initiated: u.initiated,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
         let (uploads, is_truncated, next_key, next_upload_id) =
-            self.state.multipart.list_uploads_paginated(
+            self.state.multipart.list_uploads_paginated_with(
                 Some(&input.bucket),
+                staged,
                 input.prefix.as_deref(),
                 input.key_marker.as_deref().unwrap_or(""),
                 input.upload_id_marker.as_deref().unwrap_or(""),
@@ -1267,6 +1500,12 @@ impl s3s::S3 for DeltaGliderS3Service {
             );
         let uploads = uploads
             .into_iter()
+            .filter(|u| match &list_scope {
+                Some(ListScope::Filtered { user }) => {
+                    user_can_see_listed_key(user, &input.bucket, &u.key)
+                }
+                _ => true,
+            })
             .map(|u| s3s::dto::MultipartUpload {
                 key: Some(u.key),
                 upload_id: Some(u.upload_id),
@@ -1348,17 +1587,15 @@ impl s3s::S3 for DeltaGliderS3Service {
         } else {
             bytes::Bytes::from(data)
         };
-        let etag = self
-            .state
-            .multipart
-            .upload_part(
-                &input.upload_id,
-                &input.bucket,
-                &input.key,
-                input.part_number as u32,
-                part,
-            )
-            .map_err(engine_error_to_s3s)?;
+        let etag = store_upload_part(
+            &self.state,
+            &input.upload_id,
+            &input.bucket,
+            &input.key,
+            input.part_number,
+            part,
+        )
+        .await?;
         Ok(s3s::S3Response::new(s3s::dto::UploadPartCopyOutput {
             copy_part_result: Some(s3s::dto::CopyPartResult {
                 e_tag: Some(parse_s3s_etag(&etag)?),
@@ -1855,6 +2092,257 @@ async fn await_completion_outcome(
     }
 }
 
+/// The backend-staged upload `upload_id` of `bucket/key`, when the bucket
+/// stages client multipart (`Engine::stages_client_multipart`) and the id
+/// isn't one of the in-memory `MultipartStore`'s. `None` sends the request
+/// down the in-memory path, which also answers NoSuchUpload for unknown ids.
+async fn staged_upload(
+    state: &Arc<AppState>,
+    bucket: &str,
+    key: &str,
+    upload_id: &str,
+) -> s3s::S3Result<Option<crate::storage::StagedMultipartUpload>> {
+    if state.multipart.contains(upload_id) {
+        return Ok(None);
+    }
+    let engine = state.engine.load();
+    if !engine.stages_client_multipart(bucket) {
+        return Ok(None);
+    }
+    engine
+        .open_staged_multipart(bucket, key, upload_id)
+        .await
+        .map_err(engine_error_to_s3s)
+}
+
+/// Is `staged` being completed — by a request on this node, or (registry
+/// uploads) under a completion claim taken by any node?
+async fn staged_completion_in_progress(
+    state: &Arc<AppState>,
+    staged: &crate::storage::StagedMultipartUpload,
+) -> s3s::S3Result<bool> {
+    if state
+        .multipart
+        .completion_in_flight(&staged.upload.upload_id)
+    {
+        return Ok(true);
+    }
+    state
+        .engine
+        .load()
+        .staged_completion_claimed(staged)
+        .await
+        .map_err(engine_error_to_s3s)
+}
+
+/// UploadPart / UploadPartCopy: stage the part on the backend or buffer it
+/// in the `MultipartStore`, whichever holds the upload. Returns the ETag.
+async fn store_upload_part(
+    state: &Arc<AppState>,
+    upload_id: &str,
+    bucket: &str,
+    key: &str,
+    part_number: i32,
+    data: bytes::Bytes,
+) -> s3s::S3Result<String> {
+    let Some(staged) = staged_upload(state, bucket, key, upload_id).await? else {
+        return state
+            .multipart
+            .upload_part(upload_id, bucket, key, part_number as u32, data)
+            .map_err(engine_error_to_s3s);
+    };
+    if !(1..=10000).contains(&part_number) {
+        return Err(engine_error_to_s3s(
+            crate::api::errors::S3Error::InvalidArgument(
+                "Part number must be between 1 and 10000".to_string(),
+            ),
+        ));
+    }
+    if staged_completion_in_progress(state, &staged).await? {
+        return Err(engine_error_to_s3s(
+            crate::api::errors::S3Error::InvalidRequest(
+                "upload is currently being completed".to_string(),
+            ),
+        ));
+    }
+    let part = state
+        .engine
+        .load()
+        .upload_staged_part(&staged, part_number, data)
+        .await
+        .map_err(engine_error_to_s3s)?;
+    Ok(part.etag)
+}
+
+/// CompleteMultipartUpload for a backend-staged upload, run by the
+/// completion registry's owner. A registry upload is first claimed in the
+/// upload registry, which other nodes see; the claim is released if the
+/// completion fails. Then, like the in-memory owner branch: quota
+/// admission, and a DETACHED store that publishes its outcome to joined
+/// retries, so a client disconnect can't cancel it halfway. Delta-eligible uploads up
+/// to `DGP_MPU_DELTA_RECONSTRUCT_MAX_BYTES` are read back and stored through
+/// the delta pipeline; the rest are assembled natively by the backend
+/// without buffering.
+async fn complete_staged_upload(
+    state: &Arc<AppState>,
+    staged: crate::storage::StagedMultipartUpload,
+    bucket: &str,
+    key: &str,
+    requested_parts: Vec<(u32, String)>,
+    publisher: crate::multipart::CompletionPublisher,
+) -> s3s::S3Result<(String, FileMetadata)> {
+    let engine = state.engine.load();
+    if !engine
+        .claim_staged_completion(&staged)
+        .await
+        .map_err(engine_error_to_s3s)?
+    {
+        return Err(engine_error_to_s3s(
+            crate::api::errors::S3Error::InvalidRequest(
+                "upload is currently being completed".to_string(),
+            ),
+        ));
+    }
+    let admitted = async {
+        let uploaded: Vec<(u32, String, u64)> = engine
+            .list_staged_parts(&staged)
+            .await?
+            .into_iter()
+            .map(|p| (p.part_number as u32, p.etag, p.size))
+            .collect();
+        let admitted = crate::multipart::validate_staged_parts(
+            &uploaded,
+            &requested_parts,
+            engine.max_passthrough_object_size(),
+        )?;
+        crate::api::handlers::object_helpers::check_quota(state, bucket, admitted.0)?;
+        Ok::<_, crate::api::errors::S3Error>(admitted)
+    }
+    .await;
+    let (total_size, etag) = match admitted {
+        Ok(admitted) => admitted,
+        Err(e) => {
+            engine.release_staged_completion(&staged).await;
+            return Err(engine_error_to_s3s(e));
+        }
+    };
+    let delta_limit = crate::config::env_parse_with_default(
+        "DGP_MPU_DELTA_RECONSTRUCT_MAX_BYTES",
+        64 * 1024 * 1024,
+    );
+    let through_delta = engine.is_delta_eligible(key) && total_size <= delta_limit;
+
+    let state = state.clone();
+    let (bucket, key) = (bucket.to_string(), key.to_string());
+    let handle = tokio::spawn(async move {
+        let result = finish_staged_upload(
+            &state,
+            &staged,
+            &bucket,
+            &key,
+            &requested_parts,
+            total_size,
+            etag,
+            through_delta,
+        )
+        .await;
+        match &result {
+            Ok((etag, _)) => publisher.publish(Ok(etag.clone())),
+            Err(e) => {
+                state.engine.load().release_staged_completion(&staged).await;
+                publisher.publish(Err(e.to_string()))
+            }
+        }
+        result
+    });
+    match handle.await {
+        Ok(Ok(done)) => Ok(done),
+        Ok(Err(e)) => Err(engine_error_to_s3s(e)),
+        Err(join_err) => Err(engine_error_to_s3s(
+            crate::api::errors::S3Error::InternalError(format!(
+                "completion task failed: {join_err}"
+            )),
+        )),
+    }
+}
+
+/// The store half of [`complete_staged_upload`], on the detached task.
+#[allow(clippy::too_many_arguments)]
+async fn finish_staged_upload(
+    state: &Arc<AppState>,
+    staged: &crate::storage::StagedMultipartUpload,
+    bucket: &str,
+    key: &str,
+    requested_parts: &[(u32, String)],
+    total_size: u64,
+    etag: String,
+    through_delta: bool,
+) -> Result<(String, FileMetadata), crate::deltaglider::EngineError> {
+    // Same chaos hook as `run_multipart_completion`.
+    let stall_ms: u64 = crate::config::env_parse_with_default("DGP_TEST_COMPLETE_STALL_MS", 0);
+    if stall_ms > 0 {
+        tokio::time::sleep(std::time::Duration::from_millis(stall_ms)).await;
+    }
+    let engine = state.engine.load();
+    let result = if through_delta {
+        let mut data = Vec::with_capacity(total_size as usize);
+        for (part_number, _) in requested_parts {
+            let part = engine.read_staged_part(staged, *part_number as i32).await?;
+            data.extend_from_slice(&part);
+        }
+        let result = engine
+            .store_with_multipart_etag(
+                bucket,
+                key,
+                &data,
+                staged.metadata.content_type.clone(),
+                staged.metadata.user_metadata.clone(),
+                etag.clone(),
+            )
+            .await?;
+        if let Err(e) = engine.abort_staged_multipart(staged).await {
+            tracing::warn!(
+                "Failed to drop staging of completed multipart upload {}: {}",
+                staged.upload.upload_id,
+                e
+            );
+        }
+        result
+    } else {
+        let parts: Vec<crate::storage::UploadedPart> = requested_parts
+            .iter()
+            .map(|(part_number, etag)| crate::storage::UploadedPart {
+                part_number: *part_number as i32,
+                etag: format!("\"{}\"", etag.trim_matches('"')),
+            })
+            .collect();
+        engine
+            .complete_staged_multipart(bucket, key, staged, &parts, total_size, etag.clone())
+            .await?
+    };
+    if crate::replication::event_consumer::is_user_object_key(key) {
+        let mut payload = serde_json::json!({
+            "content_length": result.metadata.file_size,
+            "etag": etag,
+            "storage_type": result.metadata.storage_info.label(),
+        });
+        crate::event_outbox::attach_user_metadata(&mut payload, &result.metadata.user_metadata);
+        crate::api::handlers::object_helpers::enqueue_object_event(
+            state,
+            crate::event_outbox::NewEvent::new(
+                crate::event_outbox::EventKind::ObjectCreated,
+                bucket,
+                key,
+                crate::event_outbox::EventSource::S3Api,
+                crate::event_outbox::current_unix_seconds(),
+                payload,
+            ),
+        )
+        .await;
+    }
+    Ok((etag, result.metadata))
+}
+
 /// The CompleteMultipartUpload store pipeline. Runs on a DETACHED tokio task so a
 /// client disconnect cannot cancel a half-done store (which used to roll back the
 /// whole upload and poison the SDK's retry). Owns finish/rollback + event emission.
@@ -1942,6 +2430,14 @@ async fn run_multipart_completion(
     };
     state.multipart.finish_upload(&upload_id);
     if crate::replication::event_consumer::is_user_object_key(&key) {
+        let mut payload = serde_json::json!({
+            "content_length": store_meta.as_ref().map(|m| m.file_size),
+            "etag": etag,
+            "storage_type": store_meta.as_ref().map(|m| m.storage_info.label()),
+        });
+        if let Some(meta) = &store_meta {
+            crate::event_outbox::attach_user_metadata(&mut payload, &meta.user_metadata);
+        }
         crate::api::handlers::object_helpers::enqueue_object_event(
             &state,
             crate::event_outbox::NewEvent::new(
@@ -1949,11 +2445,8 @@ async fn run_multipart_completion(
                 &bucket,
                 &key,
                 crate::event_outbox::EventSource::S3Api,
-                crate::replication::current_unix_seconds(),
-                serde_json::json!({
-                    "etag": etag,
-                    "storage_type": store_meta.as_ref().map(|m| m.storage_info.label()),
-                }),
+                crate::event_outbox::current_unix_seconds(),
+                payload,
             ),
         )
         .await;
@@ -1961,6 +2454,98 @@ async fn run_multipart_completion(
     Ok((etag, store_meta))
 }
 
+/// Run a read against `bucket` — or against its replica when the health gate
+/// diverted the request (`route`) or the primary fails with a backend error
+/// and the bucket's `read_fallback` has `on_error`. Returns the result and,
+/// when the replica answered, its name (for [`mark_stale_read`]). If the
+/// replica fails as well, the primary's error is reported.
+async fn read_with_fallback<T, F, Fut>(
+    state: &Arc<AppState>,
+    route: Option<&ReadFallbackRoute>,
+    bucket: &str,
+    read: F,
+) -> s3s::S3Result<(T, Option<String>)>
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = Result<T, crate::deltaglider::EngineError>>,
+{
+    let reads = &state.metrics.read_fallback_reads_total;
+    if let Some(route) = route {
+        let out = read(route.replica.clone())
+            .await
+            .map_err(engine_error_to_s3s)?;
+        reads
+            .with_label_values(&[FallbackReason::PrimaryUnhealthy.as_str()])
+            .inc();
+        return Ok((out, Some(route.replica.clone())));
+    }
+    let err = match read(bucket.to_string()).await {
+        Ok(out) => return Ok((out, None)),
+        Err(err) if crate::read_fallback::is_backend_fault(&err) => err,
+        Err(err) => return Err(engine_error_to_s3s(err)),
+    };
+    let replica = state
+        .engine
+        .load()
+        .bucket_policy_registry()
+        .read_fallback(bucket)
+        .filter(|f| f.on_error)
+        .map(|f| f.bucket.clone());
+    let Some(replica) = replica else {
+        return Err(engine_error_to_s3s(err));
+    };
+    match read(replica.clone()).await {
+        Ok(out) => {
+            tracing::warn!(
+                "read of bucket '{bucket}' failed ({err}); served from replica '{replica}'"
+            );
+            reads
+                .with_label_values(&[FallbackReason::BackendError.as_str()])
+                .inc();
+            Ok((out, Some(replica)))
+        }
+        Err(_) => Err(engine_error_to_s3s(err)),
+    }
+}
+
+fn mark_stale_read(headers: &mut axum::http::HeaderMap, replica: Option<&str>) {
+    if let Some(value) = replica.and_then(|r| axum::http::HeaderValue::from_str(r).ok()) {
+        headers.insert(crate::read_fallback::STALE_READ_HEADER, value);
+    }
+}
+
+fn mark_queued_write(headers: &mut axum::http::HeaderMap, replica: &str) {
+    if let Ok(value) = axum::http::HeaderValue::from_str(replica) {
+        headers.insert(crate::read_fallback::QUEUED_WRITE_HEADER, value);
+    }
+}
+
+/// Record a write about to land on the replica so it is replayed onto the
+/// primary after recovery. Queued BEFORE the write: replay reconciles from
+/// the replica's state, so an entry for a write that then fails is harmless,
+/// while a write without an entry would never reach the primary.
+async fn queue_fallback_write(state: &Arc<AppState>, bucket: &str, key: &str) -> s3s::S3Result<()> {
+    let Some(db) = state.config_db.as_ref() else {
+        return Err(s3s::s3_error!(
+            ServiceUnavailable,
+            "bucket '{bucket}' is served from its replica and writes cannot be queued \
+             without the config database"
+        ));
+    };
+    db.lock()
+        .await
+        .read_fallback_enqueue(bucket, key, crate::event_outbox::current_unix_seconds())
+        .map_err(|e| {
+            tracing::error!("read fallback: cannot queue write {bucket}/{key}: {e}");
+            s3s::s3_error!(
+                ServiceUnavailable,
+                "cannot queue write while the backend is down"
+            )
+        })?;
+    state.metrics.read_fallback_writes_queued_total.inc();
+    Ok(())
+}
+
 fn engine_error_to_s3s(err: impl Into<crate::api::S3Error>) -> s3s::S3Error {
     match err.into() {
         crate::api::S3Error::NoSuchKey(_) => s3s::s3_error!(NoSuchKey),
@@ -2272,6 +2857,7 @@ mod tests {
                 .iter()
                 .map(permission_to_iam_policy)
                 .collect(),
+            session_policy: None,
         };
         // From a denied IP → AccessDenied (was silently allowed before the fix).
         assert!(
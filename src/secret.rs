@@ -17,6 +17,9 @@
 //!    opaque to everything except the SQLCipher driver. Not yet
 //!    migrated.
 //!
+//! Webhook signing secrets ([`WebhookSecret`]) came later and implement
+//! the trait from the start.
+//!
 //! When the next enterprise integration lands (KMS, HashiCorp Vault,
 //! AWS Secrets Manager), it should arrive as a single new [`Secret`]
 //! impl rather than three parallel implementations across the three
@@ -117,6 +120,46 @@ pub trait Secret: Send + Sync {
 /// Vault) is a runtime config decision.
 pub type DynSecret = std::sync::Arc<dyn Secret>;
 
+/// HMAC key for signing webhook deliveries (see
+/// [`crate::webhook_signature`]). The material is the configured secret
+/// string's UTF-8 bytes, used as-is; the id is a truncated
+/// `SHA-256("deltaglider-webhook" || material)`, safe to log when tracing
+/// which of a rotating pair signed a delivery.
+pub struct WebhookSecret {
+    id: SecretId,
+    material: Vec<u8>,
+}
+
+impl WebhookSecret {
+    pub fn new(secret: &str) -> Self {
+        use sha2::{Digest, Sha256};
+        let material = secret.as_bytes().to_vec();
+        let mut hasher = Sha256::new();
+        hasher.update(b"deltaglider-webhook");
+        hasher.update(&material);
+        let digest = hasher.finalize();
+        Self {
+            id: SecretId::new(format!("whsec-{}", hex::encode(&digest[..8]))),
+            material,
+        }
+    }
+}
+
+impl Drop for WebhookSecret {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.material);
+    }
+}
+
+impl Secret for WebhookSecret {
+    fn id(&self) -> &SecretId {
+        &self.id
+    }
+    fn material(&self) -> &[u8] {
+        &self.material
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,6 +239,15 @@ mod tests {
         assert_ne!(a.id(), b.id());
     }
 
+    #[test]
+    fn webhook_secret_id_is_stable_and_hides_material() {
+        let a = WebhookSecret::new("whsec-test-one");
+        assert_eq!(a.material(), b"whsec-test-one");
+        assert_eq!(a.id(), WebhookSecret::new("whsec-test-one").id());
+        assert_ne!(a.id(), WebhookSecret::new("whsec-test-two").id());
+        assert!(!a.id().as_str().contains("test-one"));
+    }
+
     /// `DynSecret` (Arc<dyn Secret>) works for hot-reload patterns.
     #[test]
     fn dyn_secret_arc_works() {
@@ -0,0 +1,272 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! [`SessionBackend`] — where admin sessions live beyond the node's cache.
+//!
+//! [`SessionStore`](super::SessionStore) always serves requests from its
+//! in-process cache; the backend decides whether anyone else can see a session:
+//!   - [`LocalSessionBackend`] — nothing is persisted. The cache IS the store,
+//!     exactly the pre-HA behaviour: sessions die with the process and are
+//!     invisible to peers (sticky load balancing required).
+//!   - [`S3SessionBackend`] — one encrypted object per session in the
+//!     coordination bucket, so a cookie minted on node A validates on node B and
+//!     survives restarts. Objects are keyed by `SHA-256(token)`: the bearer token
+//!     itself never reaches storage.
+//!
+//! Backend errors are `String`s, like [`crate::coordination::CoordinationLease`].
+
+use async_trait::async_trait;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+
+use super::{AuthMethod, S3SessionCredentials, SessionKind};
+use crate::storage::encrypting::{self, EncryptionKey};
+
+/// The persisted form of a session (everything except the token).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StoredSession {
+    /// Non-secret short id shown in the sessions list (token prefix).
+    pub id: String,
+    pub created_unix: i64,
+    pub ip: Option<IpAddr>,
+    pub s3_creds: Option<S3SessionCredentials>,
+    pub auth_method: AuthMethod,
+    pub kind: SessionKind,
+}
+
+/// Storage key for a session token: hex `SHA-256(token)`.
+pub(crate) fn storage_key(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[async_trait]
+pub trait SessionBackend: Send + Sync {
+    /// True when other instances read the same sessions. A non-shared backend
+    /// is never consulted on a cache miss.
+    fn is_shared(&self) -> bool;
+
+    /// Load one session by [`storage_key`]. `Ok(None)` = absent.
+    async fn load(&self, key: &str) -> Result<Option<StoredSession>, String>;
+
+    /// Create or overwrite one session.
+    async fn save(&self, key: &str, session: &StoredSession) -> Result<(), String>;
+
+    /// Delete one session. Deleting an absent key is not an error.
+    async fn delete(&self, key: &str) -> Result<(), String>;
+
+    /// Every stored session with its key (for listing, revocation and sweeps).
+    async fn list(&self) -> Result<Vec<(String, StoredSession)>, String>;
+}
+
+/// Node-local sessions: the store's cache is the only copy.
+pub struct LocalSessionBackend;
+
+#[async_trait]
+impl SessionBackend for LocalSessionBackend {
+    fn is_shared(&self) -> bool {
+        false
+    }
+
+    async fn load(&self, _key: &str) -> Result<Option<StoredSession>, String> {
+        Ok(None)
+    }
+
+    async fn save(&self, _key: &str, _session: &StoredSession) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn delete(&self, _key: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<(String, StoredSession)>, String> {
+        Ok(Vec::new())
+    }
+}
+
+const SESSION_PREFIX: &str = "_dgp/sessions/";
+
+/// Sessions as AES-256-GCM objects in the coordination bucket.
+///
+/// The key is derived from the bootstrap password hash, which every instance
+/// sharing a config DB already holds. Changing the bootstrap password makes
+/// existing session objects undecryptable; they read as absent, which forces
+/// a re-login.
+pub struct S3SessionBackend {
+    client: Client,
+    bucket: String,
+    key: EncryptionKey,
+}
+
+impl S3SessionBackend {
+    pub fn new(client: Client, bucket: String, bootstrap_password_hash: &str) -> Self {
+        Self {
+            client,
+            bucket,
+            key: Self::derive_key(bootstrap_password_hash),
+        }
+    }
+
+    fn derive_key(bootstrap_password_hash: &str) -> EncryptionKey {
+        let mut hasher = Sha256::new();
+        hasher.update(b"deltaglider-session-store-v1\0");
+        hasher.update(bootstrap_password_hash.as_bytes());
+        EncryptionKey(hasher.finalize().into())
+    }
+
+    fn object_key(key: &str) -> String {
+        format!("{SESSION_PREFIX}{key}")
+    }
+
+    fn seal(&self, session: &StoredSession) -> Result<Vec<u8>, String> {
+        let json = serde_json::to_vec(session).map_err(|e| format!("session encode: {e}"))?;
+        encrypting::encrypt(&self.key, &json).map_err(|e| e.to_string())
+    }
+
+    /// `None` for an object this key can't open (foreign or pre-rotation).
+    fn open(&self, blob: &[u8]) -> Option<StoredSession> {
+        let json = encrypting::decrypt(&self.key, blob).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+#[async_trait]
+impl SessionBackend for S3SessionBackend {
+    fn is_shared(&self) -> bool {
+        true
+    }
+
+    async fn load(&self, key: &str) -> Result<Option<StoredSession>, String> {
+        match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(key))
+            .send()
+            .await
+        {
+            Ok(out) => {
+                let bytes = out
+                    .body
+                    .collect()
+                    .await
+                    .map_err(|e| format!("session body read: {e}"))?
+                    .into_bytes();
+                Ok(self.open(&bytes))
+            }
+            Err(e) => {
+                if crate::config_db_sync::is_object_absent(
+                    &crate::config_db_sync::sdk_error_signal(&e),
+                ) {
+                    Ok(None)
+                } else {
+                    Err(format!("{e:?}"))
+                }
+            }
+        }
+    }
+
+    async fn save(&self, key: &str, session: &StoredSession) -> Result<(), String> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(key))
+            .body(ByteStream::from(self.seal(session)?))
+            .content_type("application/octet-stream")
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(Self::object_key(key))
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    async fn list(&self) -> Result<Vec<(String, StoredSession)>, String> {
+        let mut keys = Vec::new();
+        let mut continuation: Option<String> = None;
+        loop {
+            let out = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(SESSION_PREFIX)
+                .set_continuation_token(continuation.take())
+                .send()
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            keys.extend(
+                out.contents()
+                    .iter()
+                    .filter_map(|o| o.key())
+                    .filter_map(|k| k.strip_prefix(SESSION_PREFIX))
+                    .map(str::to_string),
+            );
+            match out.next_continuation_token() {
+                Some(token) if out.is_truncated() == Some(true) => {
+                    continuation = Some(token.to_string())
+                }
+                _ => break,
+            }
+        }
+
+        let mut sessions = Vec::with_capacity(keys.len());
+        for key in keys {
+            match self.load(&key).await? {
+                Some(session) => sessions.push((key, session)),
+                // Deleted between LIST and GET, or sealed under another key.
+                None => tracing::debug!("session object {key} is gone or unreadable; skipping"),
+            }
+        }
+        Ok(sessions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_key_hides_the_token() {
+        let token = "a".repeat(64);
+        let key = storage_key(&token);
+        assert_eq!(key.len(), 64);
+        assert_ne!(key, token);
+        assert_eq!(key, storage_key(&token), "stable across calls");
+    }
+
+    #[test]
+    fn sealed_sessions_need_the_same_bootstrap_hash() {
+        let session = StoredSession {
+            id: "abcdef012345".into(),
+            created_unix: 1,
+            ip: None,
+            s3_creds: Some(S3SessionCredentials::anonymous(
+                "http://x".into(),
+                "us-east-1".into(),
+                "b".into(),
+            )),
+            auth_method: AuthMethod::IamLoginAs {
+                access_key_id: "AK1".into(),
+            },
+            kind: SessionKind::AdminGui,
+        };
+        let key = S3SessionBackend::derive_key("$2b$hash-one");
+        let other = S3SessionBackend::derive_key("$2b$hash-two");
+        let json = serde_json::to_vec(&session).unwrap();
+        let blob = encrypting::encrypt(&key, &json).unwrap();
+        assert!(!blob.windows(3).any(|w| w == b"AK1"), "plaintext leaked");
+        assert!(encrypting::decrypt(&key, &blob).is_ok());
+        assert!(encrypting::decrypt(&other, &blob).is_err());
+    }
+}
@@ -0,0 +1,1345 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Admin GUI session store: an in-process cache over a pluggable
+//! [`SessionBackend`]. The default backend keeps sessions node-local; the
+//! shared one stores them in the coordination bucket so a cookie works on
+//! every instance behind a round-robin load balancer and survives restarts.
+
+mod backend;
+
+pub use backend::{LocalSessionBackend, S3SessionBackend, SessionBackend, StoredSession};
+
+use backend::storage_key;
+use parking_lot::RwLock;
+use rand::rngs::OsRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use zeroize::Zeroize;
+
+/// Maximum number of concurrent sessions (across all instances when the
+/// backend is shared). Oldest sessions are evicted on overflow.
+/// Overridable at startup via `DGP_MAX_SESSIONS` env var.
+fn default_max_sessions() -> usize {
+    crate::config::env_parse_with_default("DGP_MAX_SESSIONS", 10usize).max(1)
+}
+
+/// Default session TTL: 4 hours.
+/// Overridable at startup via `DGP_SESSION_TTL_HOURS` env var.
+fn default_session_ttl() -> Duration {
+    let hours: u64 = crate::config::env_parse_with_default("DGP_SESSION_TTL_HOURS", 4);
+    Duration::from_secs(hours * 3600)
+}
+
+/// How long a cached session is trusted before the shared backend is asked
+/// again, bounding how late a logout or revocation on another instance is
+/// seen here. Overridable via `DGP_SESSION_RECHECK_SECS` (default 30).
+fn default_recheck() -> Duration {
+    Duration::from_secs(crate::config::env_parse_with_default(
+        "DGP_SESSION_RECHECK_SECS",
+        30u64,
+    ))
+}
+
+fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// S3 credentials stored in a server-side session.
+/// Held in memory, and — with a shared session backend — in the encrypted
+/// session object. Never written to localStorage.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct S3SessionCredentials {
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+impl S3SessionCredentials {
+    /// Sentinel key pair used by open-mode (`authentication: none`) browser
+    /// sessions, where the proxy has no real SigV4 keys. THE single home for
+    /// the `"anonymous"` literal — call sites must not duplicate it.
+    pub const ANONYMOUS_KEY: &'static str = "anonymous";
+
+    /// Build open-mode anonymous S3 credentials. The access/secret pair is the
+    /// [`ANONYMOUS_KEY`](Self::ANONYMOUS_KEY) sentinel; endpoint/region/bucket
+    /// come from the caller.
+    pub fn anonymous(endpoint: String, region: String, bucket: String) -> Self {
+        S3SessionCredentials {
+            endpoint,
+            region,
+            bucket,
+            access_key_id: Self::ANONYMOUS_KEY.to_string(),
+            secret_access_key: Self::ANONYMOUS_KEY.to_string(),
+        }
+    }
+}
+
+impl Drop for S3SessionCredentials {
+    fn drop(&mut self) {
+        // Zero out the secret on drop to prevent it from lingering in memory
+        // after the session is invalidated. Uses the `zeroize` crate which is
+        // designed for this purpose and avoids the need for unsafe code.
+        self.secret_access_key.zeroize();
+    }
+}
+
+/// How the admin session was created (for audit logging and UI display).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AuthMethod {
+    /// Bootstrap password login.
+    Bootstrap,
+    /// IAM user login via access key + secret.
+    IamLoginAs { access_key_id: String },
+    /// IAM user browser connect (non-admin): cookie + stored S3 creds only.
+    IamBrowserLift { access_key_id: String },
+    /// Open-auth mode: anonymous S3 browser session (no IAM identity).
+    OpenLift,
+    /// External provider login (OAuth/OIDC).
+    External { provider_name: String, user_id: i64 },
+}
+
+/// Whether a session may call full admin GUI APIs (config, IAM, operator tools).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionKind {
+    /// Config, IAM, diagnostics, usage scanner, etc.
+    AdminGui,
+    /// S3 browser only: session check, logout, stored S3 credentials — no admin surface.
+    S3BrowserLift,
+}
+
+#[derive(Clone)]
+struct SessionInfo {
+    created_at: Instant,
+    /// Wall-clock creation time (unix seconds) — compared against the synced
+    /// revocation epoch (`created_unix <= revoked_since` ⇒ invalid), and the
+    /// TTL clock for sessions minted on another instance. `created_at` is
+    /// monotonic and can't be compared to a wall-clock timestamp.
+    created_unix: i64,
+    ip: Option<IpAddr>,
+    s3_creds: Option<S3SessionCredentials>,
+    auth_method: AuthMethod,
+    kind: SessionKind,
+    /// When the backend last confirmed the session (or this node created it).
+    checked_at: Instant,
+}
+
+impl SessionInfo {
+    fn from_stored(stored: StoredSession) -> Self {
+        let now = Instant::now();
+        let age = Duration::from_secs(now_unix().saturating_sub(stored.created_unix).max(0) as u64);
+        Self {
+            // Best effort: the wall-clock TTL check in `entry_live` is what
+            // bounds a session whose age exceeds this process's uptime.
+            created_at: now.checked_sub(age).unwrap_or(now),
+            created_unix: stored.created_unix,
+            ip: stored.ip,
+            s3_creds: stored.s3_creds,
+            auth_method: stored.auth_method,
+            kind: stored.kind,
+            checked_at: now,
+        }
+    }
+
+    fn to_stored(&self, token: &str) -> StoredSession {
+        StoredSession {
+            id: SessionStore::session_id(token),
+            created_unix: self.created_unix,
+            ip: self.ip,
+            s3_creds: self.s3_creds.clone(),
+            auth_method: self.auth_method.clone(),
+            kind: self.kind,
+        }
+    }
+}
+
+impl AuthMethod {
+    /// The identity a cross-instance revocation targets: the IAM access_key_id,
+    /// or `provider:user_id` for external logins. Bootstrap/open have no
+    /// revocable identity (None) — those are cleared by restart / password reset.
+    fn revocation_identity(&self) -> Option<String> {
+        match self {
+            AuthMethod::IamLoginAs { access_key_id }
+            | AuthMethod::IamBrowserLift { access_key_id } => Some(access_key_id.clone()),
+            AuthMethod::External {
+                provider_name,
+                user_id,
+            } => Some(format!("{provider_name}:{user_id}")),
+            AuthMethod::Bootstrap | AuthMethod::OpenLift => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            AuthMethod::Bootstrap => "bootstrap",
+            AuthMethod::IamLoginAs { .. } => "iam",
+            AuthMethod::IamBrowserLift { .. } => "iam_browser",
+            AuthMethod::OpenLift => "open",
+            AuthMethod::External { .. } => "external",
+        }
+    }
+}
+
+/// Redacted view of a live session for the admin revocation UI. Never carries
+/// the auth token or any S3 secret.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    /// Non-secret short id (token prefix) — used to target revocation.
+    pub id: String,
+    pub ip: Option<String>,
+    pub age_secs: u64,
+    pub admin_gui: bool,
+    /// Auth kind: bootstrap / iam / iam_browser / open / external.
+    pub auth: String,
+    /// Revocation identity: access_key_id (IAM) or `provider:user_id`
+    /// (external); None for bootstrap/open.
+    pub identity: Option<String>,
+}
+
+/// Thread-safe session store: an in-memory cache in front of a
+/// [`SessionBackend`].
+///
+/// With [`LocalSessionBackend`] the cache is the whole store. With a shared
+/// backend every create/update/delete is written through, a cache miss is
+/// loaded from the backend, and cached entries are re-read once they are older
+/// than the recheck interval, so a logout or revocation on one instance reaches
+/// the others within that interval.
+pub struct SessionStore {
+    sessions: RwLock<HashMap<String, SessionInfo>>,
+    ttl: Duration,
+    max_sessions: usize,
+    recheck: Duration,
+    /// identity → revoke epoch (unix secs). A session with
+    /// `created_unix <= revoked_since` is invalid on EVERY instance. Snapshotted
+    /// from the synced `session_revocations` table (refreshed on revoke + after
+    /// config sync) so `entry_valid` stays a pure in-memory check.
+    revocations: RwLock<HashMap<String, i64>>,
+    backend: Arc<dyn SessionBackend>,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionStore {
+    /// A node-local store (sessions are not shared and do not survive restart).
+    pub fn new() -> Self {
+        Self::with_backend(Arc::new(LocalSessionBackend))
+    }
+
+    pub fn with_backend(backend: Arc<dyn SessionBackend>) -> Self {
+        Self {
+            sessions: RwLock::new(HashMap::new()),
+            ttl: default_session_ttl(),
+            max_sessions: default_max_sessions(),
+            recheck: default_recheck(),
+            revocations: RwLock::new(HashMap::new()),
+            backend,
+        }
+    }
+
+    /// Override the env-derived cap and recheck interval.
+    pub fn with_tunables(mut self, max_sessions: usize, recheck: Duration) -> Self {
+        self.max_sessions = max_sessions.max(1);
+        self.recheck = recheck;
+        self
+    }
+
+    /// True when sessions are visible to other instances.
+    pub fn is_shared(&self) -> bool {
+        self.backend.is_shared()
+    }
+
+    /// The configured concurrent-session cap.
+    pub fn max_sessions(&self) -> usize {
+        self.max_sessions
+    }
+
+    /// MERGE a revocation snapshot (from the synced `session_revocations`
+    /// table) monotonically. Called on startup, after each revoke, and after a
+    /// config sync. A wholesale replace would un-revoke a compromised session:
+    /// a revocation recorded locally via `note_revocation` but not yet in the
+    /// incoming snapshot would be wiped, resurrecting the session (X-ray H20).
+    /// So we keep the MAX epoch per identity and never DROP an identity the
+    /// snapshot omits — revocations only ever advance, never regress.
+    pub fn set_revocations(&self, rows: Vec<(String, i64)>) {
+        let mut map = self.revocations.write();
+        for (identity, revoked_since) in rows {
+            let e = map.entry(identity).or_insert(revoked_since);
+            *e = (*e).max(revoked_since);
+        }
+    }
+
+    /// Record a local revocation immediately (so the current instance rejects the
+    /// identity without waiting for a DB round-trip / sync). MAX so it's monotonic.
+    pub fn note_revocation(&self, identity: &str, revoked_since: i64) {
+        let mut r = self.revocations.write();
+        let e = r.entry(identity.to_string()).or_insert(revoked_since);
+        *e = (*e).max(revoked_since);
+    }
+
+    /// The configured session TTL.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// TTL + cross-instance revocation-epoch check (no IP binding) — the
+    /// "does this session still exist anywhere" half of `entry_valid`.
+    fn entry_live(&self, info: &SessionInfo) -> bool {
+        if info.created_at.elapsed() >= self.ttl {
+            return false;
+        }
+        // Wall-clock TTL too: a session loaded from a shared backend may be
+        // older than this process, so its monotonic `created_at` is clamped.
+        if now_unix().saturating_sub(info.created_unix) >= self.ttl.as_secs() as i64 {
+            return false;
+        }
+        self.not_revoked(info.created_unix, &info.auth_method)
+    }
+
+    /// Cross-instance revocation: if this session's identity was revoked at or
+    /// after the session was created, it's invalid — even if it was minted on
+    /// another node (the stolen-cookie / compromised-key escape hatch).
+    fn not_revoked(&self, created_unix: i64, auth_method: &AuthMethod) -> bool {
+        if let Some(identity) = auth_method.revocation_identity() {
+            if let Some(&revoked_since) = self.revocations.read().get(&identity) {
+                if created_unix <= revoked_since {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn stored_live(&self, stored: &StoredSession) -> bool {
+        now_unix().saturating_sub(stored.created_unix) < self.ttl.as_secs() as i64
+            && self.not_revoked(stored.created_unix, &stored.auth_method)
+    }
+
+    fn entry_valid(&self, info: &SessionInfo, ip: Option<IpAddr>) -> bool {
+        if !self.entry_live(info) {
+            return false;
+        }
+        if !ip_ok(info.ip, ip) {
+            match (info.ip, ip) {
+                (Some(stored_ip), Some(caller_ip)) => tracing::warn!(
+                    "Session IP mismatch: stored={}, caller={}",
+                    stored_ip,
+                    caller_ip
+                ),
+                (Some(stored_ip), None) => tracing::warn!(
+                    "Session has IP binding ({}) but caller provided no IP",
+                    stored_ip
+                ),
+                _ => {}
+            }
+            return false;
+        }
+        true
+    }
+
+    /// Evict the oldest cached sessions until there is room for one more.
+    fn make_room(&self, sessions: &mut HashMap<String, SessionInfo>) {
+        while sessions.len() >= self.max_sessions {
+            if let Some(oldest_token) = sessions
+                .iter()
+                .min_by_key(|(_, info)| info.created_at)
+                .map(|(token, _)| token.clone())
+            {
+                tracing::warn!(
+                    "Evicting oldest admin session to make room (max {})",
+                    self.max_sessions
+                );
+                sessions.remove(&oldest_token);
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Write a cached session through to a shared backend. A failed write
+    /// leaves the session valid on this instance only (sticky behaviour).
+    async fn persist(&self, token: &str, stored: Option<StoredSession>) {
+        if !self.backend.is_shared() {
+            return;
+        }
+        let Some(stored) = stored else {
+            return;
+        };
+        if let Err(e) = self.backend.save(&storage_key(token), &stored).await {
+            tracing::warn!("Session store: failed to persist session: {e}");
+        }
+    }
+
+    /// Delete backend objects by key, returning how many deletes succeeded.
+    async fn delete_stored(&self, keys: &[String]) -> usize {
+        let mut deleted = 0;
+        for key in keys {
+            match self.backend.delete(key).await {
+                Ok(()) => deleted += 1,
+                Err(e) => tracing::warn!("Session store: failed to delete session: {e}"),
+            }
+        }
+        deleted
+    }
+
+    /// Stored sessions, or empty (with a warning) when the backend is unreachable.
+    async fn stored_sessions(&self) -> Vec<(String, StoredSession)> {
+        if !self.backend.is_shared() {
+            return Vec::new();
+        }
+        self.backend.list().await.unwrap_or_else(|e| {
+            tracing::warn!("Session store: failed to list sessions: {e}");
+            Vec::new()
+        })
+    }
+
+    /// Apply the cap across every instance: drop the oldest of the `stored`
+    /// live sessions beyond `max_sessions`. Runs from the periodic sweep, not
+    /// on login — listing the shared store costs a LIST plus a GET per
+    /// session — so the union can exceed the cap until the next sweep.
+    async fn enforce_shared_cap(&self, mut stored: Vec<(String, StoredSession)>) {
+        if stored.len() <= self.max_sessions {
+            return;
+        }
+        stored.sort_by_key(|(_, s)| s.created_unix);
+        let excess: Vec<String> = stored[..stored.len() - self.max_sessions]
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect();
+        tracing::warn!(
+            "Evicting {} oldest admin session(s) to make room (max {})",
+            excess.len(),
+            self.max_sessions
+        );
+        self.sessions
+            .write()
+            .retain(|token, _| !excess.contains(&storage_key(token)));
+        self.delete_stored(&excess).await;
+    }
+
+    /// Resolve a token to its session: from the cache, or — for a shared
+    /// backend — from the backend on a miss or once the cached copy is due a
+    /// recheck. A backend error keeps serving the cached copy (revocation
+    /// epochs still apply); it never admits a session the cache doesn't hold.
+    async fn lookup(&self, token: &str) -> Option<SessionInfo> {
+        let cached = self.sessions.read().get(token).cloned();
+        if !self.backend.is_shared() {
+            return cached;
+        }
+        if let Some(info) = &cached {
+            if info.checked_at.elapsed() < self.recheck {
+                return cached;
+            }
+        } else if !is_token_shaped(token) {
+            // Don't turn arbitrary cookie garbage into backend reads.
+            return None;
+        }
+        match self.backend.load(&storage_key(token)).await {
+            Ok(Some(stored)) => {
+                let mut info = SessionInfo::from_stored(stored);
+                let mut sessions = self.sessions.write();
+                match sessions.get(token) {
+                    // Keep the precise monotonic clock of a session we hold.
+                    Some(existing) => info.created_at = existing.created_at,
+                    None => self.make_room(&mut sessions),
+                }
+                sessions.insert(token.to_string(), info.clone());
+                Some(info)
+            }
+            Ok(None) => {
+                if cached.is_some() {
+                    self.sessions.write().remove(token);
+                }
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Session store: failed to load session: {e}");
+                cached
+            }
+        }
+    }
+
+    /// Create a new session and return the token (64-char hex string).
+    /// Stores the client IP for later validation.
+    /// If the maximum number of concurrent sessions is reached, the oldest session is evicted.
+    pub async fn create_session(
+        &self,
+        ip: Option<IpAddr>,
+        auth_method: AuthMethod,
+        kind: SessionKind,
+    ) -> String {
+        let mut bytes = [0u8; 32];
+        OsRng.fill(&mut bytes);
+        let token = hex::encode(bytes);
+
+        let now = Instant::now();
+        let info = SessionInfo {
+            created_at: now,
+            created_unix: now_unix(),
+            ip,
+            s3_creds: None,
+            auth_method,
+            kind,
+            checked_at: now,
+        };
+        let stored = info.to_stored(&token);
+        {
+            let mut sessions = self.sessions.write();
+            self.make_room(&mut sessions);
+            sessions.insert(token.clone(), info);
+        }
+
+        self.persist(&token, Some(stored)).await;
+
+        token
+    }
+
+    /// Check if a session token is valid (exists, not expired, and IP matches if stored).
+    pub async fn validate(&self, token: &str, ip: Option<IpAddr>) -> bool {
+        self.lookup(token)
+            .await
+            .map(|info| self.entry_valid(&info, ip))
+            .unwrap_or(false)
+    }
+
+    /// Full admin GUI (config, IAM, operator APIs). `S3BrowserLift` sessions return false.
+    pub async fn allows_admin_gui(&self, token: &str, ip: Option<IpAddr>) -> bool {
+        let Some(info) = self.lookup(token).await else {
+            return false;
+        };
+        if !self.entry_valid(&info, ip) {
+            return false;
+        }
+        info.kind == SessionKind::AdminGui
+    }
+
+    /// Remove a session (logout) here and in the shared backend.
+    pub async fn remove(&self, token: &str) {
+        self.sessions.write().remove(token);
+        if self.backend.is_shared() {
+            self.delete_stored(&[storage_key(token)]).await;
+        }
+    }
+
+    /// A non-secret session id derived from the token — the first 12 hex chars.
+    /// Enough to identify a session in the admin list and target it for
+    /// revocation without exposing the full 64-char auth token.
+    fn session_id(token: &str) -> String {
+        token.chars().take(12).collect()
+    }
+
+    fn summary(
+        id: String,
+        ip: Option<IpAddr>,
+        created_unix: i64,
+        kind: SessionKind,
+        auth_method: &AuthMethod,
+    ) -> SessionSummary {
+        SessionSummary {
+            id,
+            ip: ip.map(|i| i.to_string()),
+            age_secs: now_unix().saturating_sub(created_unix).max(0) as u64,
+            admin_gui: kind == SessionKind::AdminGui,
+            auth: auth_method.label().to_string(),
+            // The same string revoke-by-identity matches on, so what the
+            // admin sees in the table is exactly what they can revoke.
+            identity: auth_method.revocation_identity(),
+        }
+    }
+
+    /// List live (non-expired, non-revoked) sessions for the admin revocation
+    /// UI — every instance's with a shared backend. Redacted: never returns the
+    /// token or S3 secret — only a short id, IP, age, kind, and the revocation
+    /// identity (access key / provider:user_id).
+    pub async fn list(&self) -> Vec<SessionSummary> {
+        let stored = self.stored_sessions().await;
+        let stored_keys: HashSet<&str> = stored.iter().map(|(k, _)| k.as_str()).collect();
+        let mut out: Vec<SessionSummary> = stored
+            .iter()
+            .filter(|(_, s)| self.stored_live(s))
+            .map(|(_, s)| Self::summary(s.id.clone(), s.ip, s.created_unix, s.kind, &s.auth_method))
+            .collect();
+        // Plus cached sessions the backend doesn't have (node-local store, or
+        // a write-through that failed).
+        let sessions = self.sessions.read();
+        out.extend(
+            sessions
+                .iter()
+                .filter(|(token, _)| !stored_keys.contains(storage_key(token).as_str()))
+                .filter(|(_, info)| self.entry_live(info))
+                .map(|(token, info)| {
+                    Self::summary(
+                        Self::session_id(token),
+                        info.ip,
+                        info.created_unix,
+                        info.kind,
+                        &info.auth_method,
+                    )
+                }),
+        );
+        out
+    }
+
+    /// True if `token` (a full session token) has the given short `id`. Used to
+    /// stop an admin revoking their own session via the revoke-by-id route.
+    pub fn session_id_matches(&self, token: &str, id: &str) -> bool {
+        Self::session_id(token) == id
+    }
+
+    /// Revoke a session by its non-secret id (force-logout), on every instance
+    /// when the backend is shared. Returns true if a session matched. The id is
+    /// a token prefix; matching by prefix is safe because a 12-hex-char (48-bit)
+    /// collision among the capped number of live sessions is negligible, and we
+    /// only ever revoke server-held tokens.
+    pub async fn revoke_by_id(&self, id: &str) -> bool {
+        let local = {
+            let mut sessions = self.sessions.write();
+            let targets: Vec<String> = sessions
+                .keys()
+                .filter(|t| Self::session_id(t) == id)
+                .cloned()
+                .collect();
+            for t in &targets {
+                sessions.remove(t);
+            }
+            targets.len()
+        };
+        let remote: Vec<String> = self
+            .stored_sessions()
+            .await
+            .into_iter()
+            .filter(|(_, s)| s.id == id)
+            .map(|(key, _)| key)
+            .collect();
+        self.delete_stored(&remote).await;
+        local > 0 || !remote.is_empty()
+    }
+
+    /// Force-logout EVERY session matching a revocation identity (IAM
+    /// access_key_id or `provider:user_id` for external logins), including
+    /// other instances' sessions in a shared backend. Used when a key is
+    /// compromised — rotating the key alone does NOT invalidate already-minted
+    /// session cookies. Returns the count revoked.
+    pub async fn revoke_by_identity(&self, identity: &str) -> usize {
+        let local: HashSet<String> = {
+            let mut sessions = self.sessions.write();
+            let targets: Vec<String> = sessions
+                .iter()
+                .filter(|(_, info)| {
+                    info.auth_method.revocation_identity().as_deref() == Some(identity)
+                })
+                .map(|(t, _)| t.clone())
+                .collect();
+            for t in &targets {
+                sessions.remove(t);
+            }
+            targets.iter().map(|t| storage_key(t)).collect()
+        };
+        let remote: Vec<String> = self
+            .stored_sessions()
+            .await
+            .into_iter()
+            .filter(|(_, s)| s.auth_method.revocation_identity().as_deref() == Some(identity))
+            .map(|(key, _)| key)
+            .collect();
+        self.delete_stored(&remote).await;
+        let remote_only = remote.iter().filter(|k| !local.contains(*k)).count();
+        local.len() + remote_only
+    }
+
+    /// Store S3 credentials in an existing session.
+    pub async fn set_s3_creds(&self, token: &str, creds: S3SessionCredentials) {
+        let stored = {
+            let mut sessions = self.sessions.write();
+            sessions.get_mut(token).map(|info| {
+                info.s3_creds = Some(creds);
+                info.to_stored(token)
+            })
+        };
+        self.persist(token, stored).await;
+    }
+
+    /// Retrieve S3 credentials from a session — full validity gate (TTL +
+    /// revocation epoch + IP binding), same as `validate`.
+    pub async fn get_s3_creds(
+        &self,
+        token: &str,
+        ip: Option<IpAddr>,
+    ) -> Option<S3SessionCredentials> {
+        let info = self.lookup(token).await?;
+        if !self.entry_valid(&info, ip) {
+            return None;
+        }
+        info.s3_creds.clone()
+    }
+
+    /// Get the auth method for a valid session token.
+    pub async fn auth_method(&self, token: &str, ip: Option<IpAddr>) -> Option<AuthMethod> {
+        let info = self.lookup(token).await?;
+        if self.entry_valid(&info, ip) {
+            Some(info.auth_method.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Clear S3 credentials from a session.
+    pub async fn clear_s3_creds(&self, token: &str) {
+        let stored = {
+            let mut sessions = self.sessions.write();
+            sessions.get_mut(token).map(|info| {
+                info.s3_creds = None;
+                info.to_stored(token)
+            })
+        };
+        self.persist(token, stored).await;
+    }
+
+    /// Remove all expired sessions, from the cache and the shared backend,
+    /// and apply the cluster-wide cap to the stored ones that remain.
+    pub async fn cleanup_expired(&self) {
+        // Drop both TTL-expired AND revoked sessions. entry_live checks both, so
+        // a revoked-but-not-yet-TTL-expired session is evicted here rather than
+        // lingering in memory until its TTL — defense in depth so no future
+        // epoch-handling bug can resurrect a session revoked long ago.
+        self.sessions
+            .write()
+            .retain(|_, info| self.entry_live(info));
+        let (live, dead): (Vec<_>, Vec<_>) = self
+            .stored_sessions()
+            .await
+            .into_iter()
+            .partition(|(_, s)| self.stored_live(s));
+        let dead: Vec<String> = dead.into_iter().map(|(key, _)| key).collect();
+        self.delete_stored(&dead).await;
+        self.enforce_shared_cap(live).await;
+    }
+}
+
+/// Session tokens are 64 lowercase hex chars (see `create_session`).
+fn is_token_shaped(token: &str) -> bool {
+    token.len() == 64
+        && token
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Pure IP-binding check: a session bound to an IP is only valid for a caller
+/// presenting that same IP; unbound sessions accept any caller.
+fn ip_ok(stored: Option<IpAddr>, caller: Option<IpAddr>) -> bool {
+    match stored {
+        None => true,
+        Some(stored) => caller == Some(stored),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl SessionStore {
+        /// Test accessor: the revocation epoch for an identity, if any.
+        fn revoked_epoch(&self, identity: &str) -> Option<i64> {
+            self.revocations.read().get(identity).copied()
+        }
+    }
+
+    /// X-ray H20: a config-sync snapshot must MERGE monotonically, never
+    /// replace. A locally-recorded revocation that the incoming snapshot omits
+    /// must survive — else a compromised session is silently un-revoked.
+    #[test]
+    fn set_revocations_merges_monotonically_and_never_unrevokes() {
+        let store = SessionStore::new();
+        // Local revocation (e.g. operator revoked a compromised key here).
+        store.note_revocation("iam:alice", 1000);
+        // A sync snapshot arrives that does NOT contain alice (stale peer / not
+        // yet propagated) but adds bob.
+        store.set_revocations(vec![("iam:bob".into(), 500)]);
+        assert_eq!(
+            store.revoked_epoch("iam:alice"),
+            Some(1000),
+            "local revocation must NOT be wiped by a snapshot that omits it"
+        );
+        assert_eq!(store.revoked_epoch("iam:bob"), Some(500));
+        // A later snapshot with a HIGHER epoch for alice advances it; a lower
+        // one never regresses it.
+        store.set_revocations(vec![("iam:alice".into(), 2000)]);
+        assert_eq!(store.revoked_epoch("iam:alice"), Some(2000));
+        store.set_revocations(vec![("iam:alice".into(), 100)]);
+        assert_eq!(
+            store.revoked_epoch("iam:alice"),
+            Some(2000),
+            "a lower incoming epoch must not regress a revocation"
+        );
+    }
+
+    /// Session-cleanup critic-gap: cleanup_expired must EVICT a revoked session,
+    /// not just leave entry_live to reject it — so no future epoch-handling bug
+    /// can resurrect a long-revoked session lingering in the map.
+    #[tokio::test]
+    async fn cleanup_expired_evicts_revoked_sessions() {
+        let store = SessionStore::new();
+        let token = store
+            .create_session(
+                None,
+                AuthMethod::IamLoginAs {
+                    access_key_id: "AKIACOMPROMISED".into(),
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+        assert!(store.validate(&token, None).await, "fresh session is valid");
+        assert_eq!(store.sessions.read().len(), 1);
+
+        // Revoke the identity at a high epoch (>= the session's created_unix).
+        store.note_revocation("AKIACOMPROMISED", i64::MAX);
+        assert!(
+            !store.validate(&token, None).await,
+            "revoked session is invalid"
+        );
+
+        // cleanup_expired must physically remove it, not just keep it invalid.
+        store.cleanup_expired().await;
+        assert_eq!(
+            store.sessions.read().len(),
+            0,
+            "a revoked session must be evicted by cleanup, not linger until TTL"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_and_validate() {
+        let store = SessionStore::new();
+        let token = store
+            .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        assert_eq!(token.len(), 64);
+        assert!(store.validate(&token, None).await);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_token() {
+        let store = SessionStore::new();
+        assert!(!store.validate("nonexistent", None).await);
+    }
+
+    #[tokio::test]
+    async fn test_remove() {
+        let store = SessionStore::new();
+        let token = store
+            .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        assert!(store.validate(&token, None).await);
+        store.remove(&token).await;
+        assert!(!store.validate(&token, None).await);
+    }
+
+    #[tokio::test]
+    async fn test_ip_binding() {
+        let store = SessionStore::new();
+        let ip1: IpAddr = "10.0.0.1".parse().unwrap();
+        let ip2: IpAddr = "10.0.0.2".parse().unwrap();
+
+        let token = store
+            .create_session(Some(ip1), AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+
+        // Same IP works
+        assert!(store.validate(&token, Some(ip1)).await);
+        // Different IP rejected
+        assert!(!store.validate(&token, Some(ip2)).await);
+        // No caller IP provided — rejected (session has IP binding)
+        assert!(!store.validate(&token, None).await);
+    }
+
+    #[tokio::test]
+    async fn test_max_sessions_eviction() {
+        let store = SessionStore::new();
+        let mut tokens = Vec::new();
+        for _ in 0..store.max_sessions() {
+            tokens.push(
+                store
+                    .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+                    .await,
+            );
+        }
+
+        // All sessions valid
+        for t in &tokens {
+            assert!(store.validate(t, None).await);
+        }
+
+        // Add one more — oldest should be evicted
+        let new_token = store
+            .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        assert!(store.validate(&new_token, None).await);
+        assert!(!store.validate(&tokens[0], None).await); // oldest evicted
+        assert_eq!(store.sessions.read().len(), store.max_sessions());
+    }
+
+    // ── AuthMethod tests ──
+
+    #[tokio::test]
+    async fn test_auth_method_bootstrap() {
+        let store = SessionStore::new();
+        let token = store
+            .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        let method = store.auth_method(&token, None).await;
+        assert!(matches!(method, Some(AuthMethod::Bootstrap)));
+    }
+
+    #[tokio::test]
+    async fn test_auth_method_iam_login_as() {
+        let store = SessionStore::new();
+        let token = store
+            .create_session(
+                None,
+                AuthMethod::IamLoginAs {
+                    access_key_id: "AKTEST01".into(),
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+        let method = store.auth_method(&token, None).await.unwrap();
+        match method {
+            AuthMethod::IamLoginAs { access_key_id } => {
+                assert_eq!(access_key_id, "AKTEST01");
+            }
+            _ => panic!("Expected IamLoginAs"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auth_method_external() {
+        let store = SessionStore::new();
+        let token = store
+            .create_session(
+                None,
+                AuthMethod::External {
+                    provider_name: "google".into(),
+                    user_id: 42,
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+        let method = store.auth_method(&token, None).await.unwrap();
+        match method {
+            AuthMethod::External {
+                provider_name,
+                user_id,
+            } => {
+                assert_eq!(provider_name, "google");
+                assert_eq!(user_id, 42);
+            }
+            _ => panic!("Expected External"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auth_method_none_for_invalid_token() {
+        let store = SessionStore::new();
+        assert!(store.auth_method("nonexistent", None).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_auth_method_respects_ip_binding() {
+        let store = SessionStore::new();
+        let ip1: IpAddr = "10.0.0.1".parse().unwrap();
+        let ip2: IpAddr = "10.0.0.2".parse().unwrap();
+        let token = store
+            .create_session(Some(ip1), AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        assert!(matches!(
+            store.auth_method(&token, Some(ip1)).await,
+            Some(AuthMethod::Bootstrap)
+        ));
+        assert!(store.auth_method(&token, Some(ip2)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_allows_admin_gui_rejects_browser_lift() {
+        let store = SessionStore::new();
+        let admin_t = store
+            .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        let lift_t = store
+            .create_session(
+                None,
+                AuthMethod::IamBrowserLift {
+                    access_key_id: "AKX".into(),
+                },
+                SessionKind::S3BrowserLift,
+            )
+            .await;
+        assert!(store.allows_admin_gui(&admin_t, None).await);
+        assert!(!store.allows_admin_gui(&lift_t, None).await);
+    }
+
+    #[tokio::test]
+    async fn test_list_and_revoke_sessions() {
+        let store = SessionStore::new();
+        let admin_t = store
+            .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        let user_t = store
+            .create_session(
+                None,
+                AuthMethod::IamLoginAs {
+                    access_key_id: "AKIA1".into(),
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+
+        // list() is redacted: short id, no token.
+        let list = store.list().await;
+        assert_eq!(list.len(), 2);
+        assert!(list.iter().all(|s| s.id.len() == 12));
+        assert!(list.iter().any(|s| s.identity.as_deref() == Some("AKIA1")));
+
+        // revoke_by_id force-logs-out the targeted session, leaves the other.
+        let admin_id = SessionStore::session_id(&admin_t);
+        assert!(store.revoke_by_id(&admin_id).await);
+        assert!(!store.validate(&admin_t, None).await);
+        assert!(store.validate(&user_t, None).await);
+        assert!(
+            !store.revoke_by_id("deadbeefdead").await,
+            "unknown id → false"
+        );
+
+        // revoke_by_identity kills every session of that IAM user.
+        let n = store.revoke_by_identity("AKIA1").await;
+        assert_eq!(n, 1);
+        assert!(!store.validate(&user_t, None).await);
+    }
+
+    #[test]
+    fn ip_ok_truth_table() {
+        let a: IpAddr = "10.0.0.1".parse().unwrap();
+        let b: IpAddr = "10.0.0.2".parse().unwrap();
+        // Unbound session: any caller (including none) is fine.
+        assert!(ip_ok(None, None));
+        assert!(ip_ok(None, Some(a)));
+        // Bound session: only the exact same IP passes.
+        assert!(ip_ok(Some(a), Some(a)));
+        assert!(!ip_ok(Some(a), Some(b)));
+        assert!(!ip_ok(Some(a), None));
+    }
+
+    #[tokio::test]
+    async fn revoke_by_identity_matches_iam_and_external_but_not_bootstrap() {
+        let store = SessionStore::new();
+        let login_t = store
+            .create_session(
+                None,
+                AuthMethod::IamLoginAs {
+                    access_key_id: "AKZZ".into(),
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+        let lift_t = store
+            .create_session(
+                None,
+                AuthMethod::IamBrowserLift {
+                    access_key_id: "AKZZ".into(),
+                },
+                SessionKind::S3BrowserLift,
+            )
+            .await;
+        let boot_t = store
+            .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        let ext_t = store
+            .create_session(
+                None,
+                AuthMethod::External {
+                    provider_name: "okta".into(),
+                    user_id: 7,
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+
+        // Both IAM variants match the access key; bootstrap/external untouched.
+        assert_eq!(store.revoke_by_identity("AKZZ").await, 2);
+        assert!(!store.validate(&login_t, None).await);
+        assert!(!store.validate(&lift_t, None).await);
+        assert!(store.validate(&boot_t, None).await);
+        assert!(store.validate(&ext_t, None).await);
+
+        // External sessions are revocable via provider:user_id.
+        assert_eq!(store.revoke_by_identity("okta:7").await, 1);
+        assert!(!store.validate(&ext_t, None).await);
+
+        // Bootstrap has no revocation identity — nothing to match.
+        assert_eq!(store.revoke_by_identity("bootstrap").await, 0);
+        assert!(store.validate(&boot_t, None).await);
+    }
+
+    #[tokio::test]
+    async fn list_omits_revoked_and_shows_external_identity() {
+        let store = SessionStore::new();
+        store
+            .create_session(
+                None,
+                AuthMethod::External {
+                    provider_name: "okta".into(),
+                    user_id: 7,
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+        store
+            .create_session(
+                None,
+                AuthMethod::IamLoginAs {
+                    access_key_id: "AKL1".into(),
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+
+        // External rows expose the FULL provider:user_id revocation identity.
+        let list = store.list().await;
+        assert_eq!(list.len(), 2);
+        assert!(list.iter().any(|s| s.identity.as_deref() == Some("okta:7")));
+
+        // A revocation epoch at/after creation hides the session from list().
+        store.note_revocation("AKL1", now_unix() + 1);
+        let list = store.list().await;
+        assert_eq!(list.len(), 1);
+        assert!(list.iter().all(|s| s.identity.as_deref() != Some("AKL1")));
+    }
+
+    #[tokio::test]
+    async fn get_s3_creds_gated_on_revocation_and_ip() {
+        let ip1: IpAddr = "10.0.0.1".parse().unwrap();
+        let ip2: IpAddr = "10.0.0.2".parse().unwrap();
+        let store = SessionStore::new();
+        let token = store
+            .create_session(
+                Some(ip1),
+                AuthMethod::IamBrowserLift {
+                    access_key_id: "AKS3".into(),
+                },
+                SessionKind::S3BrowserLift,
+            )
+            .await;
+        store
+            .set_s3_creds(
+                &token,
+                S3SessionCredentials {
+                    endpoint: "http://localhost:9000".into(),
+                    region: "us-east-1".into(),
+                    bucket: "b".into(),
+                    access_key_id: "AKS3".into(),
+                    secret_access_key: "sekrit".into(),
+                },
+            )
+            .await;
+
+        assert!(store.get_s3_creds(&token, Some(ip1)).await.is_some());
+        // Wrong / missing caller IP: the stored secret must not come back.
+        assert!(store.get_s3_creds(&token, Some(ip2)).await.is_none());
+        assert!(store.get_s3_creds(&token, None).await.is_none());
+
+        // Revoked identity: creds gone even from the right IP.
+        store.note_revocation("AKS3", now_unix() + 1);
+        assert!(store.get_s3_creds(&token, Some(ip1)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn cross_instance_revocation_snapshot_invalidates_by_identity() {
+        let store = SessionStore::new();
+        // A session minted "on another node" — model it via a normal create.
+        let t = store
+            .create_session(
+                None,
+                AuthMethod::IamLoginAs {
+                    access_key_id: "AKIA9".into(),
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+        assert!(
+            store.validate(&t, None).await,
+            "valid before any revocation"
+        );
+
+        // A revocation with epoch in the FUTURE (>= created_unix) invalidates it,
+        // even though the session lives only in this store — this is what a synced
+        // revocation from another instance looks like after set_revocations().
+        let future = now_unix() + 3600;
+        store.set_revocations(vec![("AKIA9".to_string(), future)]);
+        assert!(
+            !store.validate(&t, None).await,
+            "revoked identity is rejected"
+        );
+
+        // A different identity's session is untouched.
+        let other = store
+            .create_session(
+                None,
+                AuthMethod::IamLoginAs {
+                    access_key_id: "AKIB0".into(),
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+        assert!(store.validate(&other, None).await);
+
+        // A revocation epoch BEFORE the session was created does NOT invalidate a
+        // session created afterwards (revocation only kills pre-existing sessions).
+        let store2 = SessionStore::new();
+        store2.set_revocations(vec![("AKIC1".to_string(), now_unix() - 3600)]);
+        let fresh = store2
+            .create_session(
+                None,
+                AuthMethod::IamLoginAs {
+                    access_key_id: "AKIC1".into(),
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+        assert!(
+            store2.validate(&fresh, None).await,
+            "a session created AFTER the revoke epoch stays valid"
+        );
+    }
+
+    // ── Shared backend ──
+
+    /// A shared backend two stores can point at: models two instances behind
+    /// a round-robin load balancer.
+    #[derive(Default)]
+    struct MemoryBackend(parking_lot::Mutex<HashMap<String, StoredSession>>);
+
+    #[async_trait::async_trait]
+    impl SessionBackend for MemoryBackend {
+        fn is_shared(&self) -> bool {
+            true
+        }
+        async fn load(&self, key: &str) -> Result<Option<StoredSession>, String> {
+            Ok(self.0.lock().get(key).cloned())
+        }
+        async fn save(&self, key: &str, session: &StoredSession) -> Result<(), String> {
+            self.0.lock().insert(key.to_string(), session.clone());
+            Ok(())
+        }
+        async fn delete(&self, key: &str) -> Result<(), String> {
+            self.0.lock().remove(key);
+            Ok(())
+        }
+        async fn list(&self) -> Result<Vec<(String, StoredSession)>, String> {
+            Ok(self
+                .0
+                .lock()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect())
+        }
+    }
+
+    fn instance_pair(max_sessions: usize) -> (SessionStore, SessionStore) {
+        let backend: Arc<dyn SessionBackend> = Arc::new(MemoryBackend::default());
+        let node = || {
+            SessionStore::with_backend(backend.clone()).with_tunables(max_sessions, Duration::ZERO)
+        };
+        (node(), node())
+    }
+
+    #[tokio::test]
+    async fn shared_session_validates_on_another_instance() {
+        let (a, b) = instance_pair(10);
+        let token = a
+            .create_session(
+                None,
+                AuthMethod::IamBrowserLift {
+                    access_key_id: "AKSH".into(),
+                },
+                SessionKind::S3BrowserLift,
+            )
+            .await;
+        a.set_s3_creds(
+            &token,
+            S3SessionCredentials::anonymous(String::new(), "us-east-1".into(), String::new()),
+        )
+        .await;
+
+        assert!(
+            b.validate(&token, None).await,
+            "cookie minted on A works on B"
+        );
+        assert!(!b.allows_admin_gui(&token, None).await, "kind travels too");
+        assert!(b.get_s3_creds(&token, None).await.is_some());
+        assert!(!b.validate(&"0".repeat(64), None).await);
+    }
+
+    #[tokio::test]
+    async fn shared_logout_and_revocation_reach_other_instances() {
+        let (a, b) = instance_pair(10);
+        let t1 = a
+            .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        assert!(b.validate(&t1, None).await);
+        // Logout on B: A's cached copy is rechecked and dropped.
+        b.remove(&t1).await;
+        assert!(!a.validate(&t1, None).await);
+
+        let t2 = a
+            .create_session(
+                None,
+                AuthMethod::IamLoginAs {
+                    access_key_id: "AKREV".into(),
+                },
+                SessionKind::AdminGui,
+            )
+            .await;
+        assert!(a.validate(&t2, None).await);
+        // Identity revoke on B kills the session A minted and A holds.
+        assert_eq!(b.revoke_by_identity("AKREV").await, 1);
+        assert!(!a.validate(&t2, None).await);
+        assert!(b.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn shared_store_caps_sessions_across_instances() {
+        let (a, b) = instance_pair(2);
+        let first = a
+            .create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        b.create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        assert_eq!(a.list().await.len(), 2);
+        // created_unix has one-second resolution: make the first session older.
+        let aged = {
+            let mut sessions = a.sessions.write();
+            let info = sessions.get_mut(&first).unwrap();
+            info.created_unix -= 10;
+            info.to_stored(&first)
+        };
+        a.persist(&first, Some(aged)).await;
+
+        b.create_session(None, AuthMethod::Bootstrap, SessionKind::AdminGui)
+            .await;
+        // Login never lists the shared store; the periodic sweep applies the cap.
+        assert_eq!(b.list().await.len(), 3);
+        b.cleanup_expired().await;
+        assert_eq!(b.list().await.len(), 2, "cap applies to the union");
+        assert!(!a.validate(&first, None).await, "oldest evicted everywhere");
+    }
+}
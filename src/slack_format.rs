@@ -16,8 +16,8 @@ use globset::{Glob, GlobSet, GlobSetBuilder};
 use serde_json::{json, Value};
 
 use crate::config_sections::EventDeliveryConfig;
-use crate::event_outbox::EventOutboxRecord;
-use crate::replication::event_consumer::is_user_object_key;
+use crate::event_outbox::{EventKind, EventOutboxRecord};
+use crate::replication::event_consumer::{is_user_object_key, parse_event_kind};
 
 /// Build a globset from patterns; an empty pattern list yields an empty set
 /// (matches nothing — callers treat "empty include" as "match all").
@@ -44,7 +44,8 @@ pub fn should_notify(
     if !cfg.slack_notify_kinds.iter().any(|k| k == &event.kind) {
         return false;
     }
-    if !is_user_object_key(&event.key) {
+    let access = parse_event_kind(&event.kind).is_some_and(EventKind::is_access);
+    if !access && !is_user_object_key(&event.key) {
         return false;
     }
     if exclude.is_match(&event.key) {
@@ -115,6 +116,9 @@ fn kind_presentation(kind: &str) -> (&'static str, &'static str) {
         "ObjectDeleted" => ("🗑️", "Object deleted"),
         "LifecycleTransitioned" => ("➿", "Lifecycle transition"),
         "LifecycleExpired" => ("⌛", "Lifecycle expiry"),
+        "ObjectAccessed" => ("⬇️", "Object downloaded"),
+        "AccessDenied" => ("⛔", "Access denied"),
+        "AuthFailed" => ("🔐", "Authentication failed"),
         _ => ("🔔", "Object event"),
     }
 }
@@ -177,8 +181,13 @@ pub fn slack_message(event: &EventOutboxRecord, _cfg: &EventDeliveryConfig) -> V
         text.push_str(&format!(" ({s})"));
     }
 
-    // Context line: storage strategy · etag · timestamp.
+    // Context line: who · from where · storage strategy · etag · timestamp.
     let mut context_bits: Vec<String> = Vec::new();
+    for (field, label) in [("principal", "by"), ("source_ip", "from")] {
+        if let Some(v) = event.payload.get(field).and_then(|v| v.as_str()) {
+            context_bits.push(format!("{label}: {}", escape(v)));
+        }
+    }
     if let Some(s) = &storage {
         context_bits.push(format!("storage: {s}"));
     }
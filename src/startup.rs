@@ -12,6 +12,7 @@ use deltaglider_proxy::iam::authorization_middleware;
 use deltaglider_proxy::iam::{AuthConfig, IamState, SharedIamState};
 use deltaglider_proxy::metrics::Metrics;
 use deltaglider_proxy::rate_limiter::RateLimiter;
+use deltaglider_proxy::session::SessionStore;
 use std::io::IsTerminal;
 use std::sync::Arc;
 use std::time::Duration;
@@ -86,9 +87,12 @@ pub fn log_startup_banner(config: &Config) {
     info!("  Listen address: {}", config.listen_addr);
 
     match &config.backend {
-        BackendConfig::Filesystem { path } => {
+        BackendConfig::Filesystem { path, metadata } => {
             info!("  Backend: Filesystem");
             info!("  Data directory: {:?}", path);
+            if !metadata.is_default() {
+                info!("  Metadata: {}", metadata.as_str());
+            }
         }
         BackendConfig::S3 {
             endpoint, region, ..
@@ -99,6 +103,36 @@ pub fn log_startup_banner(config: &Config) {
                 info!("  Endpoint: {}", ep);
             }
         }
+        BackendConfig::AzureBlob {
+            account, endpoint, ..
+        } => {
+            info!("  Backend: Azure Blob");
+            info!("  Account: {}", account);
+            if let Some(ep) = endpoint {
+                info!("  Endpoint: {}", ep);
+            }
+        }
+        BackendConfig::Gcs {
+            project, endpoint, ..
+        } => {
+            info!("  Backend: Google Cloud Storage");
+            if let Some(project) = project {
+                info!("  Project: {}", project);
+            }
+            if let Some(ep) = endpoint {
+                info!("  Endpoint: {}", ep);
+            }
+        }
+        BackendConfig::Erasure {
+            data_shards,
+            parity_shards,
+            ..
+        } => {
+            info!(
+                "  Backend: Erasure coded ({}+{})",
+                data_shards, parity_shards
+            );
+        }
     }
 
     info!("  Max delta ratio: {}", config.max_delta_ratio);
@@ -237,6 +271,9 @@ pub fn init_metrics(config: &Config) -> Arc<Metrics> {
     let backend_type = match &config.backend {
         BackendConfig::Filesystem { .. } => "filesystem",
         BackendConfig::S3 { .. } => "s3",
+        BackendConfig::AzureBlob { .. } => "azure",
+        BackendConfig::Gcs { .. } => "gcs",
+        BackendConfig::Erasure { .. } => "erasure",
     };
     metrics
         .build_info
@@ -389,10 +426,9 @@ pub fn build_s3_router(
                     Ok(SecretKey::from(auth.secret_access_key.clone()))
                 }
                 IamState::Iam(index) => index
-                    .get(access_key)
-                    .filter(|user| user.enabled)
-                    .map(|user| SecretKey::from(user.secret_access_key.clone()))
-                    .ok_or_else(|| s3s::s3_error!(InvalidAccessKeyId)),
+                    .authenticate(access_key, chrono::Utc::now().timestamp())
+                    .map(|(secret, _)| SecretKey::from(secret.to_string()))
+                    .map_err(|_| s3s::s3_error!(InvalidAccessKeyId)),
                 _ => Err(s3s::s3_error!(InvalidAccessKeyId)),
             }
         }
@@ -744,6 +780,12 @@ pub fn build_s3_router(
         .layer(middleware::from_fn(
             deltaglider_proxy::coordination::health::backend_health_gate_middleware,
         ))
+        // Admission `throttle` blocks: inside SigV4 so per-access-key and
+        // per-group limits see the verified caller. Over the request rate →
+        // 503 SlowDown; byte rates pace the bodies.
+        .layer(middleware::from_fn(
+            deltaglider_proxy::admission::throttle_middleware,
+        ))
         .layer(middleware::from_fn(sigv4_auth_middleware))
         // Maintenance write-gate: runs after admission, before SigV4. A
         // PERMANENT layer whose contents (the busy-bucket set) swap
@@ -757,6 +799,10 @@ pub fn build_s3_router(
             deltaglider_proxy::admission::admission_middleware,
         ))
         .layer(axum::Extension(iam_state.clone()))
+        // `aws:SecureTransport` for IAM conditions: TLS at our own listener.
+        .layer(axum::Extension(
+            deltaglider_proxy::iam::middleware::ListenerTls(config.tls_enabled()),
+        ))
         .layer(axum::Extension(public_prefix_snapshot.clone()))
         .layer(axum::Extension(admission_chain.clone()))
         .layer(axum::Extension(state.maintenance_gate.clone()))
@@ -775,6 +821,7 @@ pub fn build_s3_router(
     }
 
     router
+        .layer(axum::Extension(state.access_events.clone()))
         .layer(axum::Extension(replay_cache.clone()))
         .layer(axum::Extension(rate_limiter.clone()))
         .layer(axum::Extension(metrics.clone()))
@@ -1145,10 +1192,12 @@ fn init_config_db_attempt(
             if let Ok(users) = db.load_users() {
                 if !users.is_empty() {
                     let groups = db.load_groups().unwrap_or_default();
+                    let service_accounts = db.load_service_accounts().unwrap_or_default();
                     info!(
-                        "Loaded {} IAM users, {} groups from {}",
+                        "Loaded {} IAM users, {} groups, {} service accounts from {}",
                         users.len(),
                         groups.len(),
+                        service_accounts.len(),
                         db_file.display()
                     );
                     // DB users force IAM mode, overriding `authentication: none`
@@ -1165,7 +1214,11 @@ fn init_config_db_attempt(
                             db_file.display()
                         );
                     }
-                    let state = deltaglider_proxy::iam::IamIndex::build_iam_state(users, groups);
+                    let state = deltaglider_proxy::iam::IamIndex::build_iam_state(
+                        users,
+                        groups,
+                        service_accounts,
+                    );
                     iam_state.store(Arc::new(state));
                 }
                 // If no users exist, keep current IamState (Legacy or Disabled)
@@ -1401,6 +1454,106 @@ pub async fn build_reference_lock(
     }
 }
 
+/// Build the admin session store.
+///
+/// Gated like the reference lock: no `config_sync_bucket` → node-local sessions
+/// (the pre-HA behaviour; a multi-instance deployment needs sticky load
+/// balancing). With a coordination bucket, sessions are stored there, sealed
+/// with a key derived from the bootstrap password hash, so any instance can
+/// validate any cookie and sessions survive restarts. `DGP_SESSION_STORE=memory`
+/// opts out. A client-build failure is non-fatal: warn and stay node-local.
+pub async fn build_session_store(config: &Config, admin_password_hash: &str) -> SessionStore {
+    use deltaglider_proxy::session::S3SessionBackend;
+
+    let sync_bucket = match &config.config_sync_bucket {
+        Some(b) if !b.is_empty() => b.clone(),
+        _ => {
+            info!("Admin sessions: node-local (single-instance; no coordination bucket)");
+            return SessionStore::new();
+        }
+    };
+    let mode = std::env::var("DGP_SESSION_STORE").unwrap_or_default();
+    match mode.trim().to_ascii_lowercase().as_str() {
+        "" | "shared" => {}
+        "memory" => {
+            info!("Admin sessions: node-local (DGP_SESSION_STORE=memory; sticky load balancing required)");
+            return SessionStore::new();
+        }
+        other => {
+            warn!("Unknown DGP_SESSION_STORE '{other}' (expected memory|shared) — using shared");
+        }
+    }
+    match ConfigDbSync::build_client(&config.backend).await {
+        Ok(client) => {
+            info!("Admin sessions: shared via coordination bucket '{sync_bucket}'");
+            SessionStore::with_backend(Arc::new(S3SessionBackend::new(
+                client,
+                sync_bucket,
+                admin_password_hash,
+            )))
+        }
+        Err(e) => {
+            warn!(
+                "Admin sessions: coordination client build failed ({e}) — node-local fallback \
+                 (sticky load balancing required)"
+            );
+            SessionStore::new()
+        }
+    }
+}
+
+/// Build the durable multipart upload registry (see
+/// `deltaglider_proxy::multipart_registry`).
+///
+/// `DGP_MULTIPART_STORE` picks it: `durable` (the default with a
+/// coordination bucket) or `memory` (the default without; uploads live in
+/// the node's `MultipartStore` and die with it). Durable uploads are tracked
+/// in the coordination bucket so any node can finish them, or — single-
+/// instance — in the ConfigDb so they survive a restart. A client-build
+/// failure is non-fatal: warn and stay in memory.
+pub async fn build_upload_registry(
+    config: &Config,
+    config_db: Option<&Arc<tokio::sync::Mutex<deltaglider_proxy::config_db::ConfigDb>>>,
+) -> Option<Arc<dyn deltaglider_proxy::multipart_registry::UploadRegistry>> {
+    use deltaglider_proxy::multipart_registry::{ConfigDbUploadRegistry, S3UploadRegistry};
+
+    let sync_bucket = config.config_sync_bucket.clone().filter(|b| !b.is_empty());
+    let mode = std::env::var("DGP_MULTIPART_STORE").unwrap_or_default();
+    let durable = match mode.trim().to_ascii_lowercase().as_str() {
+        "" => sync_bucket.is_some(),
+        "durable" => true,
+        "memory" => false,
+        other => {
+            warn!("Unknown DGP_MULTIPART_STORE '{other}' (expected memory|durable) — using the default");
+            sync_bucket.is_some()
+        }
+    };
+    if !durable {
+        info!("Multipart uploads: node-local (in memory; lost on restart)");
+        return None;
+    }
+    let Some(sync_bucket) = sync_bucket else {
+        let Some(db) = config_db else {
+            warn!("Multipart uploads: no config DB for the durable registry — node-local fallback");
+            return None;
+        };
+        info!("Multipart uploads: durable, tracked in the config DB (single-instance)");
+        return Some(Arc::new(ConfigDbUploadRegistry::new(db.clone())));
+    };
+    match ConfigDbSync::build_client(&config.backend).await {
+        Ok(client) => {
+            info!("Multipart uploads: durable, shared via coordination bucket '{sync_bucket}'");
+            Some(Arc::new(S3UploadRegistry::new(client, sync_bucket)))
+        }
+        Err(e) => {
+            warn!(
+                "Multipart uploads: coordination client build failed ({e}) — node-local fallback"
+            );
+            None
+        }
+    }
+}
+
 /// Startup gate (guard B): under multi-instance, every NAMED S3 backend that
 /// hosts a client-writable routed bucket must enforce conditional writes —
 /// without CAS, two nodes' concurrent same-deltaspace PUTs can corrupt
@@ -1442,7 +1595,7 @@ pub async fn validate_backend_write_capability(
     let groups = client_writable_s3_backends(config);
     if groups.is_empty() {
         info!(
-            "Backend capability gate: no client-writable buckets on named S3 backends — \
+            "Backend capability gate: no client-writable buckets on named S3/GCS backends — \
              nothing to validate (default backend is covered by the coordination gate)"
         );
         return;
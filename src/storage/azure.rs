@@ -0,0 +1,2029 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Azure Blob Storage backend
+//!
+//! Talks to the Blob service REST API directly over `reqwest`, signing every
+//! request with Shared Key (HMAC-SHA256 over the canonical request, see
+//! [`string_to_sign`]). There is no Azure SDK dependency.
+//!
+//! Each API bucket maps 1:1 to a blob container, and keys follow the same
+//! layout as the S3 backend: `reference.bin`, `<name>.delta`, passthrough
+//! objects under their original name.
+//!
+//! DG metadata lives in blob metadata. Azure metadata names must be C#
+//! identifiers, so the bare `dg-file-sha256` key is stored as
+//! `dg_dfile_dsha256` ([`encode_meta_name`]); values outside printable ASCII
+//! are RFC 2047 encoded ([`encode_meta_value`]). List Blobs is always asked
+//! for `include=metadata`, so listings and deltaspace scans carry the full DG
+//! metadata inline — no per-blob HEAD storm like the S3 backend needs.
+//!
+//! Multipart uploads map onto block blobs: every part is a Put Block and
+//! complete is a Put Block List that stamps the final metadata. Uploads from
+//! local files are staged the same way in [`BLOCK_SIZE`] blocks so a large
+//! passthrough never sits in memory whole.
+
+use super::s3::{confirmable_candidates, list_anchor, probe_hit_serves_candidate, S3Backend};
+use super::traits::{
+    multipart_etag, DelegatedListResult, MultipartUpload, StorageBackend, StorageError,
+    UploadedPart,
+};
+use crate::config::BackendConfig;
+use crate::types::{FileMetadata, StorageInfo};
+use async_trait::async_trait;
+use base64::Engine;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use hmac::{Hmac, Mac};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, Url};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{debug, instrument, warn};
+
+/// Blob service REST version sent on every request. Azurite supports it,
+/// and it is new enough for a 5000 MiB single Put Blob.
+const API_VERSION: &str = "2021-08-06";
+
+/// Block size for staged uploads from local files. Objects up to this size
+/// go up in a single Put Blob.
+const BLOCK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Max concurrent exact-key probes after an anchored delegated listing
+/// (same bound as the S3 backend's HEAD fan-out).
+const MAX_CONCURRENT_PROBES: usize = 10;
+
+/// Metadata header prefix on requests and HEAD responses.
+const META_HEADER_PREFIX: &str = "x-ms-meta-";
+
+/// RFC 2047 wrapper for metadata values that can't travel as a bare header.
+const RFC2047_PREFIX: &str = "=?UTF-8?B?";
+const RFC2047_SUFFIX: &str = "?=";
+
+pub struct AzureBlobBackend {
+    http: reqwest::Client,
+    account: String,
+    /// Decoded Shared Key account key.
+    key: Vec<u8>,
+    /// Blob service endpoint without a trailing slash. Azurite's
+    /// path-style endpoint carries the account as its first path segment.
+    endpoint: String,
+}
+
+impl AzureBlobBackend {
+    /// Create a new Azure Blob backend from configuration.
+    pub async fn new(config: &BackendConfig) -> Result<Self, StorageError> {
+        let BackendConfig::AzureBlob {
+            account,
+            account_key,
+            endpoint,
+            allow_local,
+        } = config
+        else {
+            return Err(StorageError::Other(
+                "AzureBlobBackend requires Azure configuration".to_string(),
+            ));
+        };
+
+        let account_key = account_key
+            .as_deref()
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| {
+                StorageError::Other(
+                    "Azure backend requires an explicit account_key (Shared Key auth)".to_string(),
+                )
+            })?;
+        let key = base64::engine::general_purpose::STANDARD
+            .decode(account_key.trim())
+            .map_err(|e| StorageError::Other(format!("Azure account_key is not base64: {e}")))?;
+
+        let endpoint = endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://{account}.blob.core.windows.net"));
+        // Same SSRF posture as the S3 backend: an operator-supplied endpoint
+        // pointing at IMDS / private ranges is refused unless the backend is
+        // explicitly opted into dev mode (Azurite on localhost).
+        let env_allow = crate::config::env_bool("DGP_BACKEND_ALLOW_LOCAL", false);
+        let kind = if *allow_local || env_allow {
+            crate::security::UrlKind::BackendDev
+        } else {
+            crate::security::UrlKind::Backend
+        };
+        crate::security::validate_outbound_url(&endpoint, kind).map_err(|e| {
+            StorageError::Other(format!(
+                "Refusing to use Azure endpoint {endpoint:?}: {e}. \
+                 Set `allow_local: true` in the backend config (or \
+                 DGP_BACKEND_ALLOW_LOCAL=true env) to permit http:// + \
+                 private IPs for dev/CI."
+            ))
+        })?;
+
+        let http = reqwest::Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(10))
+            .read_timeout(std::time::Duration::from_secs(60))
+            .dns_resolver(std::sync::Arc::new(
+                crate::security::SsrfGuardedResolver::new(kind),
+            ))
+            .build()
+            .map_err(|e| StorageError::Other(format!("Azure HTTP client build failed: {e}")))?;
+
+        debug!("AzureBlobBackend initialized (account {account}, endpoint {endpoint})");
+        Ok(Self {
+            http,
+            account: account.clone(),
+            key,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+        })
+    }
+
+    // === Key layout (identical to the S3 backend) ===
+
+    fn prefixed_key(prefix: &str, filename: &str) -> String {
+        if prefix.is_empty() {
+            filename.to_string()
+        } else {
+            format!("{}/{}", prefix, filename)
+        }
+    }
+
+    fn reference_key(prefix: &str) -> String {
+        Self::prefixed_key(prefix, "reference.bin")
+    }
+
+    fn delta_key(prefix: &str, filename: &str) -> String {
+        Self::prefixed_key(prefix, &format!("{}.delta", filename))
+    }
+
+    fn passthrough_key(prefix: &str, filename: &str) -> String {
+        Self::prefixed_key(prefix, filename)
+    }
+
+    // === Request plumbing ===
+
+    /// Build the request URL. Blob names are percent-encoded per `/`
+    /// segment; query values are percent-encoded (never `+` for space).
+    fn url(
+        &self,
+        container: &str,
+        blob: Option<&str>,
+        query: &[(&str, String)],
+    ) -> Result<Url, StorageError> {
+        let mut url = self.endpoint.clone();
+        // Account-level calls (List Containers) address the endpoint itself,
+        // the way the Azure SDKs do: no trailing slash.
+        if !container.is_empty() {
+            url.push('/');
+            url.push_str(container);
+        }
+        if let Some(blob) = blob {
+            url.push('/');
+            url.push_str(
+                &blob
+                    .split('/')
+                    .map(|seg| urlencoding::encode(seg).into_owned())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            );
+        }
+        for (i, (k, v)) in query.iter().enumerate() {
+            url.push(if i == 0 { '?' } else { '&' });
+            url.push_str(k);
+            url.push('=');
+            url.push_str(&urlencoding::encode(v));
+        }
+        Url::parse(&url).map_err(|e| StorageError::Other(format!("invalid Azure URL {url}: {e}")))
+    }
+
+    /// Sign and send a request, retrying transient failures (500/503,
+    /// connection errors) with the same 100/200/400 ms backoff the S3
+    /// backend uses. Non-2xx responses are classified into `StorageError`.
+    async fn send(&self, req: BlobRequest<'_>) -> Result<reqwest::Response, StorageError> {
+        let url = self.url(req.container, req.blob, &req.query)?;
+        let headers = req.header_map()?;
+        let body = req.body.clone().unwrap_or_default();
+
+        let backoff_ms = [100u64, 200, 400];
+        for attempt in 0..=backoff_ms.len() {
+            // Re-signed per attempt: `x-ms-date` must stay within the
+            // service's 15-minute skew window.
+            let builder = self.signed(&req, &url, &headers, &body);
+            let retry_reason = match builder.send().await {
+                Ok(resp) if resp.status().is_success() => return Ok(resp),
+                Ok(resp) => {
+                    let status = resp.status().as_u16();
+                    if (status == 500 || status == 503) && attempt < backoff_ms.len() {
+                        format!("HTTP {status}")
+                    } else {
+                        return Err(error_from_response(resp, req.container, req.blob).await);
+                    }
+                }
+                Err(e) if (e.is_connect() || e.is_timeout()) && attempt < backoff_ms.len() => {
+                    e.to_string()
+                }
+                Err(e) => {
+                    return Err(StorageError::Other(format!(
+                        "Azure {} {}/{} failed: {e}",
+                        req.method,
+                        req.container,
+                        req.blob.unwrap_or("")
+                    )))
+                }
+            };
+            warn!(
+                "Azure {} {}/{} failed (attempt {}), retrying in {}ms: {}",
+                req.method,
+                req.container,
+                req.blob.unwrap_or(""),
+                attempt + 1,
+                backoff_ms[attempt],
+                retry_reason
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(backoff_ms[attempt])).await;
+        }
+        unreachable!("retry loop must return on every path")
+    }
+
+    /// One signed attempt: stamp `x-ms-date`, compute the Shared Key
+    /// signature, attach the body.
+    fn signed(
+        &self,
+        req: &BlobRequest<'_>,
+        url: &Url,
+        headers: &HeaderMap,
+        body: &Bytes,
+    ) -> reqwest::RequestBuilder {
+        let mut headers = headers.clone();
+        let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        headers.insert(
+            "x-ms-date",
+            HeaderValue::from_str(&date).expect("RFC 1123 date is a valid header"),
+        );
+        let sts = string_to_sign(
+            &self.account,
+            req.method.as_str(),
+            &headers,
+            body.len() as u64,
+            url,
+        );
+        let auth = format!("SharedKey {}:{}", self.account, sign(&self.key, &sts));
+        headers.insert(
+            reqwest::header::AUTHORIZATION,
+            HeaderValue::from_str(&auth).expect("base64 signature is a valid header"),
+        );
+
+        let builder = self
+            .http
+            .request(req.method.clone(), url.clone())
+            .headers(headers);
+        // PUT always carries a body (possibly empty) so reqwest emits
+        // `Content-Length: 0` — Azure answers 411 without it.
+        if req.body.is_some() || req.method == Method::PUT {
+            builder.body(body.clone())
+        } else {
+            builder
+        }
+    }
+
+    /// Single authenticated List Containers call, no retries, for the
+    /// backend health probe. `Ok(status)` when the service answered at all;
+    /// `Err` carries the transport failure.
+    pub async fn probe_status(&self) -> Result<u16, String> {
+        let req = BlobRequest::new(Method::GET, "", None)
+            .query("comp", "list")
+            .query("maxresults", "1");
+        let url = self.url("", None, &req.query).map_err(|e| e.to_string())?;
+        let headers = req.header_map().map_err(|e| e.to_string())?;
+        self.signed(&req, &url, &headers, &Bytes::new())
+            .send()
+            .await
+            .map(|resp| resp.status().as_u16())
+            .map_err(|e| e.to_string())
+    }
+
+    // === Blob operations ===
+
+    /// Single-shot Put Blob with DG metadata.
+    async fn put_blob(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let len = data.len();
+        let req = BlobRequest::new(Method::PUT, bucket, Some(key))
+            .header("x-ms-blob-type", "BlockBlob")
+            .header("x-ms-blob-content-type", "application/octet-stream")
+            .metadata(metadata)
+            .body(data);
+        self.send(req).await?;
+        debug!(
+            "Azure PUT {}/{} ({} bytes) with DG metadata",
+            bucket, key, len
+        );
+        Ok(())
+    }
+
+    /// Upload a local file. Small files go up in one Put Blob; larger ones
+    /// are staged in `BLOCK_SIZE` blocks and committed with Put Block List,
+    /// so memory stays O(block) regardless of object size.
+    async fn put_blob_from_file(
+        &self,
+        bucket: &str,
+        key: &str,
+        source_path: &std::path::Path,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let len = tokio::fs::metadata(source_path).await?.len();
+        if len <= BLOCK_SIZE as u64 {
+            let data = tokio::fs::read(source_path).await?;
+            return self
+                .put_blob(bucket, key, Bytes::from(data), metadata)
+                .await;
+        }
+        let staging = uuid::Uuid::new_v4().simple().to_string();
+        let mut file = tokio::fs::File::open(source_path).await?;
+        let mut block_ids = Vec::new();
+        loop {
+            let mut buf = Vec::with_capacity(BLOCK_SIZE);
+            (&mut file)
+                .take(BLOCK_SIZE as u64)
+                .read_to_end(&mut buf)
+                .await?;
+            if buf.is_empty() {
+                break;
+            }
+            let id = block_id(&staging, block_ids.len() + 1);
+            self.put_block(bucket, key, &id, Bytes::from(buf)).await?;
+            block_ids.push(id);
+        }
+        self.put_block_list(bucket, key, &block_ids, metadata)
+            .await?;
+        debug!(
+            "Azure PUT {}/{} ({} bytes, {} blocks) from file",
+            bucket,
+            key,
+            len,
+            block_ids.len()
+        );
+        Ok(())
+    }
+
+    /// Stage one uncommitted block.
+    async fn put_block(
+        &self,
+        bucket: &str,
+        key: &str,
+        block_id: &str,
+        data: Bytes,
+    ) -> Result<(), StorageError> {
+        let req = BlobRequest::new(Method::PUT, bucket, Some(key))
+            .query("comp", "block")
+            .query("blockid", block_id)
+            .body(data);
+        self.send(req).await?;
+        Ok(())
+    }
+
+    /// Commit staged blocks (in order) as the blob's content, replacing its
+    /// metadata with `metadata`.
+    async fn put_block_list(
+        &self,
+        bucket: &str,
+        key: &str,
+        block_ids: &[String],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?><BlockList>");
+        for id in block_ids {
+            xml.push_str("<Latest>");
+            xml.push_str(id);
+            xml.push_str("</Latest>");
+        }
+        xml.push_str("</BlockList>");
+        let req = BlobRequest::new(Method::PUT, bucket, Some(key))
+            .query("comp", "blocklist")
+            .header("x-ms-blob-content-type", "application/octet-stream")
+            .metadata(metadata)
+            .body(Bytes::from(xml));
+        self.send(req).await?;
+        Ok(())
+    }
+
+    /// Replace a blob's metadata without touching its bytes.
+    async fn set_blob_metadata(
+        &self,
+        bucket: &str,
+        key: &str,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let req = BlobRequest::new(Method::PUT, bucket, Some(key))
+            .query("comp", "metadata")
+            .metadata(metadata);
+        self.send(req).await?;
+        Ok(())
+    }
+
+    async fn get_blob(&self, bucket: &str, key: &str) -> Result<Vec<u8>, StorageError> {
+        let resp = self
+            .send(BlobRequest::new(Method::GET, bucket, Some(key)))
+            .await?;
+        let data = resp
+            .bytes()
+            .await
+            .map_err(|e| StorageError::Other(format!("Failed to read Azure response body: {e}")))?
+            .to_vec();
+        debug!("Azure GET {}/{} ({} bytes)", bucket, key, data.len());
+        Ok(data)
+    }
+
+    /// HEAD a blob and build its FileMetadata. Mirrors the S3 backend:
+    /// missing/corrupt DG metadata degrades to passthrough, loudly only for
+    /// `.delta` / `reference.bin` where it breaks reconstruction.
+    async fn get_blob_metadata(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        let resp = self
+            .send(BlobRequest::new(Method::HEAD, bucket, Some(key)))
+            .await?;
+        let headers = resp.headers();
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+
+        let meta: HashMap<String, String> = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let encoded = name.as_str().strip_prefix(META_HEADER_PREFIX)?;
+                let value = value.to_str().ok()?;
+                Some((decode_meta_name(encoded), decode_meta_value(value)))
+            })
+            .collect();
+        // Stable per-blob timestamp for the `created_at` fallback — never
+        // `now()` (see `resolve_created_at` in the S3 backend).
+        let last_modified = header("last-modified")
+            .as_deref()
+            .and_then(parse_http_date)
+            .unwrap_or_else(Utc::now);
+
+        let delta_critical = key.ends_with(".delta") || key.ends_with("reference.bin");
+        if !meta.is_empty() {
+            match S3Backend::headers_to_metadata(&meta, last_modified) {
+                Ok(parsed) => return Ok(parsed),
+                Err(e) if delta_critical => warn!(
+                    "PATHOLOGICAL | {}/{} has missing/corrupt DG metadata — \
+                     delta reconstruction will not work. Error: {}",
+                    bucket, key, e
+                ),
+                Err(e) => debug!(
+                    "No DG metadata for {}/{} — serving as passthrough. Error: {}",
+                    bucket, key, e
+                ),
+            }
+        } else if delta_critical {
+            warn!(
+                "PATHOLOGICAL | {}/{} has NO DG metadata! Delta reconstruction will not work.",
+                bucket, key
+            );
+        }
+
+        let size = header("content-length")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        let etag = blob_etag(header("content-md5").as_deref(), header("etag").as_deref());
+        Ok(FileMetadata::fallback(
+            key.rsplit('/').next().unwrap_or(key).to_string(),
+            size,
+            etag,
+            last_modified,
+            header("content-type"),
+            StorageInfo::Passthrough,
+        ))
+    }
+
+    /// Delete a blob. A missing blob is success, matching S3's idempotent
+    /// DeleteObject — the engine's cleanup paths rely on that.
+    async fn delete_blob(&self, bucket: &str, key: &str) -> Result<(), StorageError> {
+        match self
+            .send(BlobRequest::new(Method::DELETE, bucket, Some(key)))
+            .await
+        {
+            Ok(_) | Err(StorageError::NotFound(_)) => {
+                debug!("Azure DELETE {}/{}", bucket, key);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// GET (optionally ranged) and hand back the body as a stream.
+    async fn get_blob_stream(
+        &self,
+        bucket: &str,
+        key: &str,
+        range: Option<(u64, u64)>,
+    ) -> Result<(reqwest::Response, u64), StorageError> {
+        let mut req = BlobRequest::new(Method::GET, bucket, Some(key));
+        if let Some((start, end)) = range {
+            req = req.header("x-ms-range", &format!("bytes={start}-{end}"));
+        }
+        let resp = self.send(req).await?;
+        let len = resp.content_length().unwrap_or(0);
+        Ok((resp, len))
+    }
+
+    // === Listing ===
+
+    /// One List Blobs page (`include=metadata`).
+    async fn list_page(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        delimiter: Option<&str>,
+        marker: Option<&str>,
+        max_results: Option<u32>,
+    ) -> Result<BlobPage, StorageError> {
+        let mut req = BlobRequest::new(Method::GET, bucket, None)
+            .query("restype", "container")
+            .query("comp", "list")
+            .query("include", "metadata");
+        if !prefix.is_empty() {
+            req = req.query("prefix", prefix);
+        }
+        if let Some(d) = delimiter.filter(|d| !d.is_empty()) {
+            req = req.query("delimiter", d);
+        }
+        if let Some(m) = marker {
+            req = req.query("marker", m);
+        }
+        if let Some(n) = max_results {
+            req = req.query("maxresults", &n.to_string());
+        }
+        let body = self
+            .send(req)
+            .await?
+            .text()
+            .await
+            .map_err(|e| StorageError::Other(format!("Failed to read Azure listing: {e}")))?;
+        parse_blob_list(&body)
+    }
+
+    /// Every blob under `prefix` (no delimiter), across all pages.
+    async fn list_all(&self, bucket: &str, prefix: &str) -> Result<Vec<ListedBlob>, StorageError> {
+        let mut blobs = Vec::new();
+        let mut marker: Option<String> = None;
+        loop {
+            let page = self
+                .list_page(bucket, prefix, None, marker.as_deref(), None)
+                .await?;
+            blobs.extend(page.blobs);
+            match page.next_marker {
+                Some(m) => marker = Some(m),
+                None => break,
+            }
+        }
+        Ok(blobs)
+    }
+
+    /// Classify listed blobs into user-visible `(key, metadata)` pairs:
+    /// directory markers pass through, `reference.bin` is dropped, `.delta`
+    /// maps back to the user key, and duplicates keep the latest.
+    fn listed_entries(blobs: Vec<ListedBlob>) -> Vec<(String, FileMetadata)> {
+        let mut entries = Vec::new();
+        for blob in blobs {
+            if blob.name.ends_with('/') && blob.size == 0 {
+                entries.push((
+                    blob.name.clone(),
+                    FileMetadata::directory_marker(&blob.name),
+                ));
+                continue;
+            }
+            let filename = blob.name.rsplit('/').next().unwrap_or(&blob.name);
+            if filename == "reference.bin" {
+                continue;
+            }
+            let key_prefix = &blob.name[..blob.name.len() - filename.len()];
+            let user_key = format!("{}{}", key_prefix, filename.trim_end_matches(".delta"));
+            let meta = blob.file_metadata();
+            entries.push((user_key, meta));
+        }
+        crate::types::dedup_keep_latest(entries)
+    }
+}
+
+#[async_trait]
+impl StorageBackend for AzureBlobBackend {
+    // === Bucket operations ===
+
+    #[instrument(skip(self))]
+    async fn create_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        let req = BlobRequest::new(Method::PUT, bucket, None).query("restype", "container");
+        self.send(req).await?;
+        debug!("Created Azure container: {}", bucket);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        // Azure happily deletes a non-empty container; the trait contract
+        // (and S3) refuses, so check first.
+        let page = self.list_page(bucket, "", None, None, Some(1)).await?;
+        if !page.blobs.is_empty() {
+            return Err(StorageError::BucketNotEmpty(bucket.to_string()));
+        }
+        let req = BlobRequest::new(Method::DELETE, bucket, None).query("restype", "container");
+        self.send(req).await?;
+        debug!("Deleted Azure container: {}", bucket);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_buckets(&self) -> Result<Vec<String>, StorageError> {
+        let dated = self.list_buckets_with_dates().await?;
+        Ok(dated.into_iter().map(|(name, _)| name).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_buckets_with_dates(&self) -> Result<Vec<(String, DateTime<Utc>)>, StorageError> {
+        let mut buckets = Vec::new();
+        let mut marker: Option<String> = None;
+        loop {
+            let mut req = BlobRequest::new(Method::GET, "", None).query("comp", "list");
+            if let Some(ref m) = marker {
+                req = req.query("marker", m);
+            }
+            let body =
+                self.send(req).await?.text().await.map_err(|e| {
+                    StorageError::Other(format!("Failed to read Azure listing: {e}"))
+                })?;
+            let page = parse_container_list(&body)?;
+            buckets.extend(page.containers);
+            match page.next_marker {
+                Some(m) => marker = Some(m),
+                None => break,
+            }
+        }
+        buckets.sort_by(|a, b| a.0.cmp(&b.0));
+        debug!("Listed {} Azure containers", buckets.len());
+        Ok(buckets)
+    }
+
+    #[instrument(skip(self))]
+    async fn head_bucket(&self, bucket: &str) -> Result<bool, StorageError> {
+        let req = BlobRequest::new(Method::HEAD, bucket, None).query("restype", "container");
+        match self.send(req).await {
+            Ok(_) => Ok(true),
+            // Only a genuine 404 reads as absent; throttling / 5xx propagate
+            // so routing never re-places a bucket on a transient error.
+            Err(StorageError::BucketNotFound(_)) | Err(StorageError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    // === Reference file operations ===
+
+    #[instrument(skip(self, data, metadata))]
+    async fn put_reference(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::reference_key(prefix);
+        self.put_blob(bucket, &key, Bytes::copy_from_slice(data), metadata)
+            .await
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn put_reference_from_file(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        source_path: &std::path::Path,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::reference_key(prefix);
+        self.put_blob_from_file(bucket, &key, source_path, metadata)
+            .await
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn put_reference_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::reference_key(prefix);
+        self.set_blob_metadata(bucket, &key, metadata).await
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn put_passthrough_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        self.set_blob_metadata(bucket, &key, metadata).await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_reference(&self, bucket: &str, prefix: &str) -> Result<Vec<u8>, StorageError> {
+        self.get_blob(bucket, &Self::reference_key(prefix)).await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_reference_to_file(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        dest: &std::path::Path,
+    ) -> Result<u64, StorageError> {
+        let key = Self::reference_key(prefix);
+        let (mut resp, _) = self.get_blob_stream(bucket, &key, None).await?;
+        let mut file = tokio::fs::File::create(dest).await?;
+        let mut written = 0u64;
+        while let Some(chunk) = resp
+            .chunk()
+            .await
+            .map_err(|e| StorageError::Other(format!("Failed to read Azure response body: {e}")))?
+        {
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+        Ok(written)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_reference_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.get_blob_metadata(bucket, &Self::reference_key(prefix))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn has_reference(&self, bucket: &str, prefix: &str) -> Result<bool, StorageError> {
+        let key = Self::reference_key(prefix);
+        match self
+            .send(BlobRequest::new(Method::HEAD, bucket, Some(&key)))
+            .await
+        {
+            Ok(_) => Ok(true),
+            // Only a blob-level 404 is "absent"; anything else (throttle,
+            // 5xx, missing container) must propagate so the write path never
+            // overwrites a live reference.bin on a backend hiccup.
+            Err(StorageError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_reference(&self, bucket: &str, prefix: &str) -> Result<(), StorageError> {
+        self.delete_blob(bucket, &Self::reference_key(prefix)).await
+    }
+
+    // === Delta file operations ===
+
+    #[instrument(skip(self, data, metadata))]
+    async fn put_delta(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::delta_key(prefix, filename);
+        self.put_blob(bucket, &key, Bytes::copy_from_slice(data), metadata)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_delta(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.get_blob(bucket, &Self::delta_key(prefix, filename))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_delta_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.get_blob_metadata(bucket, &Self::delta_key(prefix, filename))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_delta(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<(), StorageError> {
+        self.delete_blob(bucket, &Self::delta_key(prefix, filename))
+            .await
+    }
+
+    // === Passthrough file operations ===
+
+    #[instrument(skip(self, data, metadata))]
+    async fn put_passthrough(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        self.put_blob(bucket, &key, Bytes::copy_from_slice(data), metadata)
+            .await
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn put_passthrough_file(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        source_path: &std::path::Path,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        self.put_blob_from_file(bucket, &key, source_path, metadata)
+            .await
+    }
+
+    /// Stage each relay part file as its own block instead of assembling
+    /// the object in memory (the trait default).
+    #[instrument(skip(self, part_paths, metadata))]
+    async fn put_passthrough_parts(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        part_paths: &[std::path::PathBuf],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let staging = uuid::Uuid::new_v4().simple().to_string();
+        let mut block_ids = Vec::with_capacity(part_paths.len());
+        for path in part_paths {
+            let data = tokio::fs::read(path).await?;
+            let id = block_id(&staging, block_ids.len() + 1);
+            self.put_block(bucket, &key, &id, Bytes::from(data)).await?;
+            block_ids.push(id);
+        }
+        self.put_block_list(bucket, &key, &block_ids, metadata)
+            .await
+    }
+
+    /// Stage chunks as blocks once the object outgrows a single Put Blob,
+    /// so the chunks are never copied into one contiguous buffer.
+    #[instrument(skip(self, chunks, metadata))]
+    async fn put_passthrough_chunked(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        chunks: &[Bytes],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let total: usize = chunks.iter().map(|c| c.len()).sum();
+        if total <= BLOCK_SIZE {
+            let mut buf = Vec::with_capacity(total);
+            for chunk in chunks {
+                buf.extend_from_slice(chunk);
+            }
+            return self
+                .put_blob(bucket, &key, Bytes::from(buf), metadata)
+                .await;
+        }
+        let staging = uuid::Uuid::new_v4().simple().to_string();
+        let mut block_ids = Vec::with_capacity(chunks.len());
+        for chunk in chunks.iter().filter(|c| !c.is_empty()) {
+            let id = block_id(&staging, block_ids.len() + 1);
+            self.put_block(bucket, &key, &id, chunk.clone()).await?;
+            block_ids.push(id);
+        }
+        self.put_block_list(bucket, &key, &block_ids, metadata)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_passthrough(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.get_blob(bucket, &Self::passthrough_key(prefix, filename))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_passthrough_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.get_blob_metadata(bucket, &Self::passthrough_key(prefix, filename))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_passthrough(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<(), StorageError> {
+        self.delete_blob(bucket, &Self::passthrough_key(prefix, filename))
+            .await
+    }
+
+    // === Streaming operations ===
+
+    #[instrument(skip(self))]
+    async fn get_passthrough_stream(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<BoxStream<'static, Result<Bytes, StorageError>>, StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let (resp, _) = self.get_blob_stream(bucket, &key, None).await?;
+        debug!("Azure GET stream {}/{}", bucket, key);
+        Ok(body_stream(resp))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_passthrough_stream_range(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let (resp, len) = self
+            .get_blob_stream(bucket, &key, Some((start, end)))
+            .await?;
+        debug!(
+            "Azure GET range stream {}/{} (bytes={}-{}, {} bytes)",
+            bucket, key, start, end, len
+        );
+        Ok((body_stream(resp), len))
+    }
+
+    // === Multipart upload (block lists) ===
+
+    fn supports_native_multipart(&self, _bucket: &str) -> bool {
+        true
+    }
+
+    /// No server-side call: a block blob needs no upload session. The
+    /// upload id namespaces this upload's block ids.
+    async fn create_multipart_upload(
+        &self,
+        bucket: &str,
+        _prefix: &str,
+        _filename: &str,
+        _metadata: &FileMetadata,
+    ) -> Result<MultipartUpload, StorageError> {
+        Ok(MultipartUpload {
+            bucket: bucket.to_string(),
+            upload_id: uuid::Uuid::new_v4().simple().to_string(),
+            native: true,
+            backend: None,
+        })
+    }
+
+    #[instrument(skip(self, upload, data))]
+    async fn upload_part(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+        part_number: i32,
+        data: Bytes,
+    ) -> Result<UploadedPart, StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let md5 = {
+            use md5::{Digest, Md5};
+            hex::encode(Md5::digest(&data))
+        };
+        let id = block_id(&upload.upload_id, part_number.max(0) as usize);
+        self.put_block(&upload.bucket, &key, &id, data).await?;
+        Ok(UploadedPart {
+            part_number,
+            etag: format!("\"{md5}\""),
+        })
+    }
+
+    /// Put Block List with the final metadata. Returns the S3-style
+    /// multipart ETag (`md5(concatenated part md5s)-<n>`) so clients see
+    /// the same shape they would from an S3 backend.
+    #[instrument(skip(self, upload, parts, _assembled, metadata))]
+    async fn complete_multipart_upload(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+        parts: &[UploadedPart],
+        _assembled: &[Bytes],
+        metadata: &FileMetadata,
+    ) -> Result<String, StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let block_ids: Vec<String> = parts
+            .iter()
+            .map(|p| block_id(&upload.upload_id, p.part_number.max(0) as usize))
+            .collect();
+        self.put_block_list(&upload.bucket, &key, &block_ids, metadata)
+            .await?;
+        Ok(multipart_etag(parts))
+    }
+
+    /// Uncommitted blocks cannot be deleted explicitly; Azure garbage
+    /// collects them after a week, and they never become visible content.
+    async fn abort_multipart_upload(
+        &self,
+        _upload: &MultipartUpload,
+        _prefix: &str,
+        _filename: &str,
+    ) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    // === Scanning operations ===
+
+    /// Listing carries the DG metadata inline (`include=metadata`), so a
+    /// deltaspace scan is one paged LIST with no per-delta HEAD.
+    #[instrument(skip(self))]
+    async fn scan_deltaspace(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<FileMetadata>, StorageError> {
+        let search_prefix = if prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", prefix)
+        };
+        let scanning_root = prefix.is_empty();
+        let metadata: Vec<FileMetadata> = self
+            .list_all(bucket, &search_prefix)
+            .await?
+            .into_iter()
+            .filter(|blob| !(scanning_root && blob.name.contains('/')))
+            .map(|blob| blob.file_metadata())
+            .collect();
+        debug!(
+            "Scanned {} blobs in deltaspace {}/{}",
+            metadata.len(),
+            bucket,
+            prefix
+        );
+        Ok(metadata)
+    }
+
+    #[instrument(skip(self))]
+    async fn list_deltaspaces(&self, bucket: &str) -> Result<Vec<String>, StorageError> {
+        let prefixes: HashSet<String> = self
+            .list_all(bucket, "")
+            .await?
+            .into_iter()
+            .map(|blob| match blob.name.rfind('/') {
+                Some(idx) => blob.name[..idx].to_string(),
+                None => String::new(),
+            })
+            .collect();
+        debug!(
+            "Found {} deltaspaces in container {}",
+            prefixes.len(),
+            bucket
+        );
+        Ok(prefixes.into_iter().collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn total_size(&self, bucket: Option<&str>) -> Result<u64, StorageError> {
+        let buckets = match bucket {
+            Some(b) => vec![b.to_string()],
+            None => self.list_buckets().await?,
+        };
+        let mut total = 0u64;
+        for b in &buckets {
+            total += self
+                .list_all(b, "")
+                .await?
+                .iter()
+                .map(|blob| blob.size)
+                .sum::<u64>();
+        }
+        Ok(total)
+    }
+
+    async fn put_directory_marker(&self, bucket: &str, key: &str) -> Result<(), StorageError> {
+        let req = BlobRequest::new(Method::PUT, bucket, Some(key))
+            .header("x-ms-blob-type", "BlockBlob")
+            .header("x-ms-blob-content-type", "application/x-directory");
+        self.send(req).await?;
+        debug!("Created directory marker: {}/{}", bucket, key);
+        Ok(())
+    }
+
+    async fn bulk_list_objects(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<(String, FileMetadata)>, StorageError> {
+        let results = Self::listed_entries(self.list_all(bucket, prefix).await?);
+        debug!(
+            "Bulk listed {} objects in {}/{}",
+            results.len(),
+            bucket,
+            prefix
+        );
+        Ok(results)
+    }
+
+    /// Delimiter collapsing is delegated to List Blobs, with the same anchor
+    /// early exit and candidate probes as the S3 backend. A continued page
+    /// re-reads from the prefix start (there is no start-after), but only
+    /// entries past the continuation token are kept.
+    #[instrument(skip(self))]
+    async fn list_objects_delegated(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        delimiter: Option<&str>,
+        max_keys: u32,
+        continuation_token: Option<&str>,
+    ) -> Result<Option<DelegatedListResult>, StorageError> {
+        // Azure has no start-after: emulate it by dropping everything at or
+        // below the continuation token (raw key order, as S3 would).
+        let token = continuation_token.unwrap_or("");
+        let mut common_prefixes = BTreeSet::new();
+        let mut raw: Vec<ListedBlob> = Vec::new();
+        let mut marker: Option<String> = None;
+        let mut settled_anchor: Option<String> = None;
+
+        loop {
+            let page = self
+                .list_page(bucket, prefix, delimiter, marker.as_deref(), None)
+                .await?;
+            for p in page.prefixes {
+                // Only the internal `.dg/` directory is hidden; other
+                // dot-prefixed folders are legitimate user keys.
+                let last_seg = p.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+                if last_seg != ".dg" && p.as_str() > token {
+                    common_prefixes.insert(p);
+                }
+            }
+            raw.extend(page.blobs.into_iter().filter(|b| b.name.as_str() > token));
+            match page.next_marker {
+                Some(m) => marker = Some(m),
+                None => break,
+            }
+            if let Some(anchor) = list_anchor(
+                raw.iter().map(|b| b.name.as_str()),
+                common_prefixes.iter().map(|p| p.as_str()),
+                max_keys,
+                continuation_token,
+            ) {
+                if raw.last().is_some_and(|b| b.name > anchor) {
+                    settled_anchor = Some(anchor);
+                    break;
+                }
+            }
+        }
+
+        // Confirm the bounded set of `p.delta` keys that can sort past the
+        // anchor yet belong on this page (see `confirmable_candidates`).
+        if let Some(anchor) = settled_anchor {
+            let last_read = raw.last().map(|b| b.name.clone());
+            let candidates =
+                confirmable_candidates(&anchor, prefix, delimiter, last_read.as_deref());
+            let probes = candidates.into_iter().map(|candidate| async move {
+                let page = self
+                    .list_page(bucket, &candidate, None, None, Some(3))
+                    .await?;
+                Ok::<_, StorageError>(
+                    page.blobs
+                        .into_iter()
+                        .filter(|b| probe_hit_serves_candidate(&b.name, &candidate))
+                        .collect::<Vec<_>>(),
+                )
+            });
+            let results: Vec<Result<Vec<ListedBlob>, StorageError>> = futures::stream::iter(probes)
+                .buffer_unordered(MAX_CONCURRENT_PROBES)
+                .collect()
+                .await;
+            for result in results {
+                raw.extend(result?);
+            }
+        }
+
+        let objects = Self::listed_entries(raw);
+        let page = crate::deltaglider::interleave_and_paginate(
+            objects,
+            common_prefixes.into_iter().collect(),
+            max_keys,
+            continuation_token,
+        );
+        debug!(
+            "Delegated list: {} objects + {} prefixes in {}/{}",
+            page.objects.len(),
+            page.common_prefixes.len(),
+            bucket,
+            prefix
+        );
+        Ok(Some(DelegatedListResult {
+            objects: page.objects,
+            common_prefixes: page.common_prefixes,
+            is_truncated: page.is_truncated,
+            next_continuation_token: page.next_continuation_token,
+        }))
+    }
+}
+
+/// One Blob service request before signing.
+struct BlobRequest<'a> {
+    method: Method,
+    /// Container name; empty for account-level calls (List Containers).
+    container: &'a str,
+    blob: Option<&'a str>,
+    query: Vec<(&'static str, String)>,
+    headers: Vec<(String, String)>,
+    body: Option<Bytes>,
+}
+
+impl<'a> BlobRequest<'a> {
+    fn new(method: Method, container: &'a str, blob: Option<&'a str>) -> Self {
+        Self {
+            method,
+            container,
+            blob,
+            query: Vec::new(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    fn query(mut self, name: &'static str, value: &str) -> Self {
+        self.query.push((name, value.to_string()));
+        self
+    }
+
+    fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Attach DG metadata as `x-ms-meta-*` headers.
+    fn metadata(mut self, metadata: &FileMetadata) -> Self {
+        for (name, value) in metadata.to_bare_metadata_map() {
+            self.headers.push((
+                format!("{META_HEADER_PREFIX}{}", encode_meta_name(&name)),
+                encode_meta_value(&value),
+            ));
+        }
+        self
+    }
+
+    fn body(mut self, body: Bytes) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Request headers plus `x-ms-version` (date and auth are per attempt).
+    fn header_map(&self) -> Result<HeaderMap, StorageError> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| StorageError::Other(format!("invalid header name {name}: {e}")))?;
+            let value = HeaderValue::from_str(value).map_err(|e| {
+                StorageError::Other(format!("invalid header value for {name}: {e}"))
+            })?;
+            headers.insert(name, value);
+        }
+        headers.insert("x-ms-version", HeaderValue::from_static(API_VERSION));
+        Ok(headers)
+    }
+}
+
+/// Shared Key string-to-sign for the Blob service (2009-09-19 and later).
+/// Pure so the canonicalisation is unit-tested without a server.
+///
+/// `Content-Length` is empty when zero (2015-02-21+). The canonicalized
+/// resource is `/<account><encoded url path>` followed by one
+/// `\nname:value` line per query parameter, names lowercased and sorted,
+/// repeated values comma-joined. Against Azurite the URL path already
+/// starts with `/devstoreaccount1`, so the account appears twice — that is
+/// what the emulator expects.
+fn string_to_sign(
+    account: &str,
+    method: &str,
+    headers: &HeaderMap,
+    content_length: u64,
+    url: &Url,
+) -> String {
+    let h = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+    };
+    let length = if content_length == 0 {
+        String::new()
+    } else {
+        content_length.to_string()
+    };
+    let mut sts = format!(
+        "{method}\n{}\n{}\n{length}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n",
+        h("content-encoding"),
+        h("content-language"),
+        h("content-md5"),
+        h("content-type"),
+        h("date"),
+        h("if-modified-since"),
+        h("if-match"),
+        h("if-none-match"),
+        h("if-unmodified-since"),
+        h("range"),
+    );
+
+    let mut ms_headers: Vec<(String, String)> = headers
+        .iter()
+        .filter(|(name, _)| name.as_str().starts_with("x-ms-"))
+        .map(|(name, value)| {
+            (
+                name.as_str().to_string(),
+                value.to_str().unwrap_or("").trim().to_string(),
+            )
+        })
+        .collect();
+    ms_headers.sort();
+    for (name, value) in ms_headers {
+        sts.push_str(&name);
+        sts.push(':');
+        sts.push_str(&value);
+        sts.push('\n');
+    }
+
+    sts.push('/');
+    sts.push_str(account);
+    sts.push_str(url.path());
+    let mut params: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (name, value) in url.query_pairs() {
+        params
+            .entry(name.to_lowercase())
+            .or_default()
+            .push(value.into_owned());
+    }
+    for (name, mut values) in params {
+        values.sort();
+        sts.push('\n');
+        sts.push_str(&name);
+        sts.push(':');
+        sts.push_str(&values.join(","));
+    }
+    sts
+}
+
+/// Base64 HMAC-SHA256 of the string-to-sign under the account key.
+fn sign(key: &[u8], string_to_sign: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(string_to_sign.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+/// Block ids must be base64 and the same length for every block of a blob;
+/// `<32-hex namespace>-<5-digit part>` satisfies both for up to 99 999
+/// parts (S3 caps multipart at 10 000).
+fn block_id(namespace: &str, part: usize) -> String {
+    base64::engine::general_purpose::STANDARD.encode(format!("{namespace}-{part:05}"))
+}
+
+/// Encode a bare metadata key as a C# identifier: `[a-z0-9]` pass
+/// through, `-` → `_d`, `_` → `__`, any other byte → `_xHH`. Names are
+/// lowercased first (Azure treats them case-insensitively, and S3 user
+/// metadata keys are lowercase anyway), which keeps the mapping lossless.
+fn encode_meta_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for b in name.to_ascii_lowercase().bytes() {
+        match b {
+            b'a'..=b'z' | b'0'..=b'9' => out.push(b as char),
+            b'-' => out.push_str("_d"),
+            b'_' => out.push_str("__"),
+            _ => out.push_str(&format!("_x{b:02x}")),
+        }
+    }
+    out
+}
+
+/// Inverse of [`encode_meta_name`]. Unknown escapes are kept verbatim so a
+/// foreign metadata name still round-trips into something readable.
+fn decode_meta_name(encoded: &str) -> String {
+    let encoded = encoded.to_ascii_lowercase();
+    let bytes = encoded.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'_' {
+            out.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        match bytes.get(i + 1) {
+            Some(b'd') => {
+                out.push(b'-');
+                i += 2;
+            }
+            Some(b'_') => {
+                out.push(b'_');
+                i += 2;
+            }
+            Some(b'x') => match encoded
+                .get(i + 2..i + 4)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+            {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 4;
+                }
+                None => {
+                    out.push(b'_');
+                    i += 1;
+                }
+            },
+            _ => {
+                out.push(b'_');
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Header-safe metadata value. Printable ASCII without edge whitespace
+/// travels verbatim; anything else (UTF-8, control bytes, empty, values
+/// that would be trimmed in transit) is RFC 2047 base64-wrapped.
+fn encode_meta_value(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value.trim() == value
+        && !value.starts_with(RFC2047_PREFIX)
+        && value.bytes().all(|b| (0x20..=0x7e).contains(&b));
+    if plain {
+        value.to_string()
+    } else {
+        format!(
+            "{RFC2047_PREFIX}{}{RFC2047_SUFFIX}",
+            base64::engine::general_purpose::STANDARD.encode(value)
+        )
+    }
+}
+
+/// Inverse of [`encode_meta_value`].
+fn decode_meta_value(value: &str) -> String {
+    value
+        .strip_prefix(RFC2047_PREFIX)
+        .and_then(|rest| rest.strip_suffix(RFC2047_SUFFIX))
+        .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// S3-shaped ETag for a blob without DG metadata: the hex Content-MD5 when
+/// Azure has one (single Put Blob uploads), else Azure's own opaque ETag.
+fn blob_etag(content_md5: Option<&str>, etag: Option<&str>) -> String {
+    content_md5
+        .filter(|m| !m.is_empty())
+        .and_then(|m| base64::engine::general_purpose::STANDARD.decode(m).ok())
+        .map(hex::encode)
+        .unwrap_or_else(|| etag.unwrap_or_default().trim_matches('"').to_string())
+}
+
+/// Parse an RFC 1123 HTTP date (`Last-Modified`).
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Stream a response body chunk by chunk. A read error is yielded once and
+/// ends the stream.
+fn body_stream(resp: reqwest::Response) -> BoxStream<'static, Result<Bytes, StorageError>> {
+    Box::pin(futures::stream::unfold(Some(resp), |resp| async move {
+        let mut resp = resp?;
+        match resp.chunk().await {
+            Ok(Some(chunk)) => Some((Ok(chunk), Some(resp))),
+            Ok(None) => None,
+            Err(e) => Some((
+                Err(StorageError::Other(format!(
+                    "Failed to read Azure response body: {e}"
+                ))),
+                None,
+            )),
+        }
+    }))
+}
+
+/// Turn a non-2xx response into a `StorageError`, reading the
+/// `x-ms-error-code` header and (for non-HEAD calls) the XML `<Message>`.
+async fn error_from_response(
+    resp: reqwest::Response,
+    container: &str,
+    blob: Option<&str>,
+) -> StorageError {
+    let status = resp.status().as_u16();
+    let code = resp
+        .headers()
+        .get("x-ms-error-code")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let body = resp.text().await.unwrap_or_default();
+    let message = body
+        .split_once("<Message>")
+        .and_then(|(_, rest)| rest.split_once("</Message>"))
+        .map(|(m, _)| m.lines().next().unwrap_or(m).to_string())
+        .unwrap_or_default();
+    classify_error(status, code.as_deref(), container, blob, &message)
+}
+
+/// Map a Blob service error onto `StorageError`. Pure — unit-tested.
+///
+/// 404 splits on `x-ms-error-code`: `ContainerNotFound` (or any 404 on a
+/// container-level call) is `BucketNotFound`, everything else on a blob is
+/// `NotFound`. 429/503 (`ServerBusy`) are `Throttled` so the API layer
+/// answers SlowDown rather than a permanent 500.
+fn classify_error(
+    status: u16,
+    code: Option<&str>,
+    container: &str,
+    blob: Option<&str>,
+    message: &str,
+) -> StorageError {
+    let code_str = code.unwrap_or("");
+    match (status, blob) {
+        (404, _) if code_str == "ContainerNotFound" => {
+            StorageError::BucketNotFound(container.to_string())
+        }
+        (404, Some(blob)) => StorageError::NotFound(blob.to_string()),
+        (404, None) => StorageError::BucketNotFound(container.to_string()),
+        (409, _) if code_str == "ContainerAlreadyExists" => {
+            StorageError::AlreadyExists(container.to_string())
+        }
+        (409, Some(blob)) if code_str == "BlobAlreadyExists" => {
+            StorageError::AlreadyExists(blob.to_string())
+        }
+        (429 | 503, _) => StorageError::Throttled(format!(
+            "Azure {status} {code_str} on {container}: {message}"
+        )),
+        _ => StorageError::Other(format!(
+            "Azure Blob error {status} {code_str} on {}/{}: {message}",
+            container,
+            blob.unwrap_or("")
+        )),
+    }
+}
+
+// === List XML ===
+
+/// Lightweight blob info from List Blobs.
+struct ListedBlob {
+    name: String,
+    size: u64,
+    last_modified: Option<DateTime<Utc>>,
+    etag: String,
+    content_type: Option<String>,
+    /// Decoded bare metadata (`dg-*`, `user-*`, `content-type`).
+    metadata: HashMap<String, String>,
+}
+
+impl ListedBlob {
+    /// Full DG metadata when the listing carried it; otherwise the same
+    /// listing-only fallback the S3 backend builds (delta stub / reference /
+    /// passthrough by key shape).
+    fn file_metadata(&self) -> FileMetadata {
+        let last_modified = self.last_modified.unwrap_or_else(Utc::now);
+        if !self.metadata.is_empty() {
+            if let Ok(meta) = S3Backend::headers_to_metadata(&self.metadata, last_modified) {
+                return meta;
+            }
+        }
+        let filename = self.name.rsplit('/').next().unwrap_or(&self.name);
+        let storage_info = if filename.ends_with(".delta") {
+            StorageInfo::delta_stub(self.size)
+        } else if filename == "reference.bin" {
+            StorageInfo::Reference {
+                source_name: String::new(),
+            }
+        } else {
+            StorageInfo::Passthrough
+        };
+        FileMetadata::fallback(
+            filename.trim_end_matches(".delta").to_string(),
+            self.size,
+            self.etag.clone(),
+            last_modified,
+            self.content_type.clone(),
+            storage_info,
+        )
+    }
+}
+
+struct BlobPage {
+    blobs: Vec<ListedBlob>,
+    prefixes: Vec<String>,
+    next_marker: Option<String>,
+}
+
+struct ContainerPage {
+    containers: Vec<(String, DateTime<Utc>)>,
+    next_marker: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BlobListXml {
+    #[serde(default)]
+    blobs: Option<BlobsXml>,
+    #[serde(default)]
+    next_marker: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BlobsXml {
+    #[serde(rename = "$value", default)]
+    entries: Vec<BlobEntryXml>,
+}
+
+#[derive(Deserialize)]
+enum BlobEntryXml {
+    Blob(BlobXml),
+    BlobPrefix(BlobPrefixXml),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BlobXml {
+    name: NameXml,
+    properties: BlobPropertiesXml,
+    #[serde(default)]
+    metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BlobPrefixXml {
+    name: NameXml,
+}
+
+/// `<Name>` text. A struct (not a bare String) so a `Encoded="…"`
+/// attribute doesn't break deserialization.
+#[derive(Deserialize)]
+struct NameXml {
+    #[serde(rename = "$text", default)]
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct BlobPropertiesXml {
+    #[serde(rename = "Last-Modified", default)]
+    last_modified: Option<String>,
+    #[serde(rename = "Etag", default)]
+    etag: Option<String>,
+    #[serde(rename = "Content-Length", default)]
+    content_length: Option<u64>,
+    #[serde(rename = "Content-Type", default)]
+    content_type: Option<String>,
+    #[serde(rename = "Content-MD5", default)]
+    content_md5: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerListXml {
+    #[serde(default)]
+    containers: Option<ContainersXml>,
+    #[serde(default)]
+    next_marker: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ContainersXml {
+    #[serde(rename = "Container", default)]
+    containers: Vec<ContainerXml>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerXml {
+    name: String,
+    #[serde(default)]
+    properties: Option<ContainerPropertiesXml>,
+}
+
+#[derive(Deserialize)]
+struct ContainerPropertiesXml {
+    #[serde(rename = "Last-Modified", default)]
+    last_modified: Option<String>,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.is_empty())
+}
+
+/// Parse a List Blobs response body.
+fn parse_blob_list(xml: &str) -> Result<BlobPage, StorageError> {
+    let parsed: BlobListXml = quick_xml::de::from_str(xml)
+        .map_err(|e| StorageError::Other(format!("invalid Azure List Blobs response: {e}")))?;
+    let mut page = BlobPage {
+        blobs: Vec::new(),
+        prefixes: Vec::new(),
+        next_marker: non_empty(parsed.next_marker),
+    };
+    for entry in parsed.blobs.map(|b| b.entries).unwrap_or_default() {
+        match entry {
+            BlobEntryXml::Blob(blob) => {
+                let props = blob.properties;
+                page.blobs.push(ListedBlob {
+                    name: blob.name.text,
+                    size: props.content_length.unwrap_or(0),
+                    last_modified: props.last_modified.as_deref().and_then(parse_http_date),
+                    etag: blob_etag(props.content_md5.as_deref(), props.etag.as_deref()),
+                    content_type: non_empty(props.content_type),
+                    metadata: blob
+                        .metadata
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|(k, v)| (decode_meta_name(&k), decode_meta_value(&v)))
+                        .collect(),
+                });
+            }
+            BlobEntryXml::BlobPrefix(prefix) => page.prefixes.push(prefix.name.text),
+        }
+    }
+    Ok(page)
+}
+
+/// Parse a List Containers response body.
+fn parse_container_list(xml: &str) -> Result<ContainerPage, StorageError> {
+    let parsed: ContainerListXml = quick_xml::de::from_str(xml)
+        .map_err(|e| StorageError::Other(format!("invalid Azure List Containers response: {e}")))?;
+    Ok(ContainerPage {
+        containers: parsed
+            .containers
+            .map(|c| c.containers)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|c| {
+                let created = c
+                    .properties
+                    .and_then(|p| p.last_modified)
+                    .as_deref()
+                    .and_then(parse_http_date)
+                    .unwrap_or_else(Utc::now);
+                (c.name, created)
+            })
+            .collect(),
+        next_marker: non_empty(parsed.next_marker),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meta_names_round_trip_as_csharp_identifiers() {
+        for name in [
+            "dg-file-sha256",
+            "dg-multipart-etag",
+            "content-type",
+            "user-build_id",
+            "user-x.y",
+        ] {
+            let encoded = encode_meta_name(name);
+            assert!(
+                encoded
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_'),
+                "{encoded} is not an identifier"
+            );
+            assert_eq!(decode_meta_name(&encoded), name);
+        }
+        assert_eq!(encode_meta_name("dg-file-sha256"), "dg_dfile_dsha256");
+        assert_eq!(encode_meta_name("user-x.y"), "user_dx_x2ey");
+    }
+
+    #[test]
+    fn meta_values_wrap_only_when_not_header_safe() {
+        assert_eq!(encode_meta_value("reference"), "reference");
+        for value in ["", " padded ", "naïve", "line\nbreak", "=?UTF-8?B?x?="] {
+            let encoded = encode_meta_value(value);
+            assert!(encoded.starts_with(RFC2047_PREFIX), "{value:?} not wrapped");
+            assert!(encoded.is_ascii());
+            assert_eq!(decode_meta_value(&encoded), value);
+        }
+    }
+
+    #[test]
+    fn string_to_sign_canonicalizes_headers_and_resource() {
+        let url = Url::parse(
+            "http://127.0.0.1:10000/devstoreaccount1/bucket/a%20b/c.txt?comp=block&blockid=QQ%3D%3D",
+        )
+        .unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ms-version", HeaderValue::from_static(API_VERSION));
+        headers.insert(
+            "x-ms-date",
+            HeaderValue::from_static("Mon, 05 Oct 2026 10:00:00 GMT"),
+        );
+        headers.insert("x-ms-meta-dg_dnote", HeaderValue::from_static(" delta "));
+        headers.insert("x-ms-range", HeaderValue::from_static("bytes=0-9"));
+
+        let sts = string_to_sign("devstoreaccount1", "PUT", &headers, 12, &url);
+        assert_eq!(
+            sts,
+            "PUT\n\n\n12\n\n\n\n\n\n\n\n\n\
+             x-ms-date:Mon, 05 Oct 2026 10:00:00 GMT\n\
+             x-ms-meta-dg_dnote:delta\n\
+             x-ms-range:bytes=0-9\n\
+             x-ms-version:2021-08-06\n\
+             /devstoreaccount1/devstoreaccount1/bucket/a%20b/c.txt\n\
+             blockid:QQ==\n\
+             comp:block"
+        );
+
+        // Zero length signs as an empty Content-Length line.
+        let sts = string_to_sign("acct", "GET", &HeaderMap::new(), 0, &url);
+        assert!(sts.starts_with("GET\n\n\n\n"));
+    }
+
+    #[test]
+    fn block_ids_are_fixed_length_base64() {
+        let ns = "0123456789abcdef0123456789abcdef";
+        let a = block_id(ns, 1);
+        let b = block_id(ns, 10_000);
+        assert_eq!(a.len(), b.len());
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(&a)
+            .unwrap();
+        assert_eq!(decoded, format!("{ns}-00001").into_bytes());
+    }
+
+    #[test]
+    fn classify_error_truth_table() {
+        assert!(matches!(
+            classify_error(404, Some("BlobNotFound"), "b", Some("k"), ""),
+            StorageError::NotFound(k) if k == "k"
+        ));
+        assert!(matches!(
+            classify_error(404, Some("ContainerNotFound"), "b", Some("k"), ""),
+            StorageError::BucketNotFound(b) if b == "b"
+        ));
+        assert!(matches!(
+            classify_error(404, None, "b", None, ""),
+            StorageError::BucketNotFound(_)
+        ));
+        assert!(matches!(
+            classify_error(409, Some("ContainerAlreadyExists"), "b", None, ""),
+            StorageError::AlreadyExists(_)
+        ));
+        assert!(matches!(
+            classify_error(503, Some("ServerBusy"), "b", Some("k"), ""),
+            StorageError::Throttled(_)
+        ));
+        assert!(matches!(
+            classify_error(429, None, "b", None, ""),
+            StorageError::Throttled(_)
+        ));
+        assert!(matches!(
+            classify_error(403, Some("AuthenticationFailed"), "b", Some("k"), "sig"),
+            StorageError::Other(_)
+        ));
+    }
+
+    #[test]
+    fn parses_blob_listing_with_metadata_and_prefixes() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="http://127.0.0.1:10000/devstoreaccount1" ContainerName="bucket">
+  <Prefix>app/</Prefix>
+  <Delimiter>/</Delimiter>
+  <Blobs>
+    <Blob>
+      <Name>app/v1.zip.delta</Name>
+      <Properties>
+        <Last-Modified>Mon, 05 Oct 2026 10:00:00 GMT</Last-Modified>
+        <Etag>0x8DCE0AA1B2C3D4E</Etag>
+        <Content-Length>42</Content-Length>
+        <Content-Type>application/octet-stream</Content-Type>
+        <Content-MD5 />
+      </Properties>
+      <Metadata>
+        <dg_dnote>delta</dg_dnote>
+        <user_downer>=?UTF-8?B?w6l0w6k=?=</user_downer>
+      </Metadata>
+    </Blob>
+    <BlobPrefix><Name>app/sub/</Name></BlobPrefix>
+    <Blob>
+      <Name>app/readme.txt</Name>
+      <Properties>
+        <Last-Modified>Mon, 05 Oct 2026 10:00:01 GMT</Last-Modified>
+        <Etag>"0x8DCE0AA1B2C3D4F"</Etag>
+        <Content-Length>5</Content-Length>
+        <Content-MD5>XUFAKrxLKna5cZ2REBfFkg==</Content-MD5>
+      </Properties>
+      <Metadata />
+    </Blob>
+  </Blobs>
+  <NextMarker>2!84!abc</NextMarker>
+</EnumerationResults>"#;
+        let page = parse_blob_list(xml).unwrap();
+        assert_eq!(page.prefixes, vec!["app/sub/".to_string()]);
+        assert_eq!(page.next_marker.as_deref(), Some("2!84!abc"));
+        assert_eq!(page.blobs.len(), 2);
+        let delta = &page.blobs[0];
+        assert_eq!(delta.name, "app/v1.zip.delta");
+        assert_eq!(delta.size, 42);
+        assert_eq!(
+            delta.metadata.get("dg-note").map(String::as_str),
+            Some("delta")
+        );
+        assert_eq!(
+            delta.metadata.get("user-owner").map(String::as_str),
+            Some("été")
+        );
+        assert_eq!(delta.etag, "0x8DCE0AA1B2C3D4E");
+        let plain = &page.blobs[1];
+        assert_eq!(plain.etag, "5d41402abc4b2a76b9719d911017c592");
+        assert!(plain.metadata.is_empty());
+
+        // Listing-only fallback for the blob without DG metadata.
+        let meta = plain.file_metadata();
+        assert_eq!(meta.file_size, 5);
+        assert_eq!(meta.md5, "5d41402abc4b2a76b9719d911017c592");
+        assert!(matches!(meta.storage_info, StorageInfo::Passthrough));
+    }
+
+    #[test]
+    fn empty_listing_has_no_marker() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ContainerName="bucket"><Blobs /><NextMarker /></EnumerationResults>"#;
+        let page = parse_blob_list(xml).unwrap();
+        assert!(page.blobs.is_empty());
+        assert!(page.next_marker.is_none());
+    }
+
+    #[test]
+    fn parses_container_listing() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="http://127.0.0.1:10000/devstoreaccount1">
+  <Containers>
+    <Container>
+      <Name>beta</Name>
+      <Properties><Last-Modified>Mon, 05 Oct 2026 10:00:00 GMT</Last-Modified></Properties>
+    </Container>
+    <Container><Name>alpha</Name><Properties /></Container>
+  </Containers>
+  <NextMarker />
+</EnumerationResults>"#;
+        let page = parse_container_list(xml).unwrap();
+        let names: Vec<&str> = page.containers.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["beta", "alpha"]);
+        assert_eq!(
+            page.containers[0].1,
+            parse_http_date("Mon, 05 Oct 2026 10:00:00 GMT").unwrap()
+        );
+        assert!(page.next_marker.is_none());
+    }
+}
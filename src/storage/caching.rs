@@ -0,0 +1,960 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Read-through disk cache wrapper for any StorageBackend.
+//!
+//! `CachingBackend` sits directly on a raw backend (below
+//! `EncryptingBackend`, so cached bytes stay in their at-rest form) and
+//! serves object reads from a [`DiskCache`]:
+//!
+//! - whole-object reads (`get_reference`, `get_delta`, `get_passthrough`,
+//!   `get_reference_to_file`) hit when every block is cached, otherwise
+//!   fetch from the backend and cache the result;
+//! - `get_passthrough_stream` tees a miss into the cache as it streams;
+//! - `get_passthrough_stream_range` serves the cached leading blocks and
+//!   fetches the rest as one block-aligned backend range, caching what it
+//!   reads — so repeated range reads of a hot window stop hitting the WAN.
+//!
+//! Every write or delete through the wrapper invalidates the affected key
+//! after the backend call (success or not). Metadata reads, listings and
+//! multipart plumbing pass through unchanged.
+
+use super::disk_cache::{DiskCache, FillHandle, BLOCK_SIZE};
+use super::traits::{
+    BucketListing, DelegatedListResult, LiteScanResult, MultipartUpload, StagedMultipartUpload,
+    StagedPart, StagedSweepReport, StorageBackend, StorageError, UploadedPart,
+};
+use crate::types::FileMetadata;
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const KIND_REFERENCE: &str = "reference";
+const KIND_DELTA: &str = "delta";
+const KIND_PASSTHROUGH: &str = "passthrough";
+
+/// Disk-cache wrapper. See the module docs.
+pub struct CachingBackend {
+    inner: Arc<dyn StorageBackend>,
+    cache: Arc<DiskCache>,
+    /// Backend name; keeps same-named buckets on different backends apart.
+    namespace: String,
+}
+
+impl CachingBackend {
+    pub fn new(
+        namespace: impl Into<String>,
+        inner: Box<dyn StorageBackend>,
+        cache: Arc<DiskCache>,
+    ) -> Self {
+        Self {
+            inner: Arc::from(inner),
+            cache,
+            namespace: namespace.into(),
+        }
+    }
+
+    fn bucket_prefix(&self, bucket: &str) -> String {
+        format!("{}/{}/", self.namespace, bucket)
+    }
+
+    fn reference_key(&self, bucket: &str, prefix: &str) -> String {
+        format!("{}reference:{}", self.bucket_prefix(bucket), prefix)
+    }
+
+    fn object_key(&self, kind: &str, bucket: &str, prefix: &str, filename: &str) -> String {
+        format!("{}{kind}:{prefix}/{filename}", self.bucket_prefix(bucket))
+    }
+
+    /// Hit → cached bytes; miss → `fetch`, then cache the result.
+    async fn read_through<F>(
+        &self,
+        key: String,
+        kind: &'static str,
+        fetch: F,
+    ) -> Result<Vec<u8>, StorageError>
+    where
+        F: std::future::Future<Output = Result<Vec<u8>, StorageError>> + Send,
+    {
+        if let Some(data) = self.cache.read_whole(&key).await {
+            self.cache.record_hit(kind);
+            return Ok(data);
+        }
+        self.cache.record_miss(kind);
+        let fill = self.cache.begin_fill(&key);
+        let data = fetch.await?;
+        self.cache.fill_whole(fill, &data).await;
+        Ok(data)
+    }
+
+    fn source(&self, bucket: &str, prefix: &str, filename: &str) -> Source {
+        Source {
+            inner: Arc::clone(&self.inner),
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+            filename: filename.to_string(),
+        }
+    }
+}
+
+/// Owned handle for re-fetching a passthrough object inside a stream.
+struct Source {
+    inner: Arc<dyn StorageBackend>,
+    bucket: String,
+    prefix: String,
+    filename: String,
+}
+
+impl Source {
+    async fn range(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError> {
+        self.inner
+            .get_passthrough_stream_range(&self.bucket, &self.prefix, &self.filename, start, end)
+            .await
+    }
+}
+
+/// Backend stream being teed into the cache block by block.
+struct Tee {
+    stream: BoxStream<'static, Result<Bytes, StorageError>>,
+    /// Object offset of `buf[0]` (always block-aligned).
+    base: u64,
+    buf: BytesMut,
+    fill: FillHandle,
+    /// Exclusive end of what was requested from the backend. Reaching EOF
+    /// before it reveals the object size.
+    requested_end: u64,
+}
+
+/// State for the cache-serving / teeing read stream.
+struct ReadState {
+    cache: Arc<DiskCache>,
+    src: Source,
+    key: String,
+    /// Next object offset to emit.
+    pos: u64,
+    /// Last object offset to emit (inclusive).
+    end: u64,
+    /// Offsets below this are served from cached blocks.
+    cached_until: u64,
+    tee: Option<Tee>,
+    /// Direct backend stream after a cached block turned out unreadable.
+    fallback: Option<BoxStream<'static, Result<Bytes, StorageError>>>,
+    done: bool,
+}
+
+impl ReadState {
+    /// Slice of a block starting at `block_start` that falls in `[pos, end]`.
+    fn take_emit(&mut self, block_start: u64, block: &Bytes, out: &mut BytesMut) {
+        let block_end = block_start + block.len() as u64;
+        if self.pos >= block_end || self.pos > self.end || self.pos < block_start {
+            return;
+        }
+        let from = (self.pos - block_start) as usize;
+        let to = (self.end.saturating_add(1).min(block_end) - block_start) as usize;
+        out.extend_from_slice(&block[from..to]);
+        self.pos = block_start + to as u64;
+    }
+
+    async fn next_chunk(&mut self) -> Option<Result<Bytes, StorageError>> {
+        loop {
+            if self.done {
+                return None;
+            }
+            if let Some(stream) = self.fallback.as_mut() {
+                let item = stream.next().await;
+                if !matches!(item, Some(Ok(_))) {
+                    self.done = true;
+                }
+                return item;
+            }
+            if self.pos > self.end {
+                self.done = true;
+                return None;
+            }
+            if self.pos < self.cached_until {
+                let idx = self.pos / BLOCK_SIZE;
+                match self.cache.read_block(&self.key, idx).await {
+                    Some(block) => {
+                        let mut out = BytesMut::new();
+                        self.take_emit(idx * BLOCK_SIZE, &block, &mut out);
+                        if out.is_empty() {
+                            // Cached object is shorter than promised.
+                            self.done = true;
+                            return None;
+                        }
+                        return Some(Ok(out.freeze()));
+                    }
+                    None => {
+                        // Evicted/unreadable under us: serve the rest of the
+                        // request straight from the backend.
+                        self.tee = None;
+                        match self.src.range(self.pos, self.end).await {
+                            Ok((stream, _)) => self.fallback = Some(stream),
+                            Err(e) => {
+                                self.done = true;
+                                return Some(Err(e));
+                            }
+                        }
+                        continue;
+                    }
+                }
+            }
+            let Some(tee) = self.tee.as_mut() else {
+                self.done = true;
+                return None;
+            };
+            match tee.stream.next().await {
+                Some(Ok(chunk)) => {
+                    tee.buf.extend_from_slice(&chunk);
+                    let mut out = BytesMut::new();
+                    while self
+                        .tee
+                        .as_ref()
+                        .is_some_and(|t| t.buf.len() as u64 >= BLOCK_SIZE)
+                    {
+                        let tee = self.tee.as_mut().expect("checked");
+                        let block = tee.buf.split_to(BLOCK_SIZE as usize).freeze();
+                        let block_start = tee.base;
+                        tee.base += BLOCK_SIZE;
+                        tee.fill
+                            .store_block(block_start / BLOCK_SIZE, block.clone())
+                            .await;
+                        self.take_emit(block_start, &block, &mut out);
+                    }
+                    if !out.is_empty() {
+                        return Some(Ok(out.freeze()));
+                    }
+                }
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    let mut tee = self.tee.take().expect("checked");
+                    let block = tee.buf.split().freeze();
+                    let eof = tee.base + block.len() as u64;
+                    if !block.is_empty() {
+                        tee.fill
+                            .store_block(tee.base / BLOCK_SIZE, block.clone())
+                            .await;
+                    }
+                    if eof < tee.requested_end {
+                        tee.fill.set_size(eof).await;
+                    }
+                    self.done = true;
+                    let mut out = BytesMut::new();
+                    self.take_emit(tee.base, &block, &mut out);
+                    return (!out.is_empty()).then(|| Ok(out.freeze()));
+                }
+            }
+        }
+    }
+
+    fn into_stream(self) -> BoxStream<'static, Result<Bytes, StorageError>> {
+        futures::stream::unfold(self, |mut state| async move {
+            state.next_chunk().await.map(|item| (item, state))
+        })
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl StorageBackend for CachingBackend {
+    // ── Cached reads ──
+
+    async fn get_reference(&self, b: &str, p: &str) -> Result<Vec<u8>, StorageError> {
+        self.read_through(
+            self.reference_key(b, p),
+            KIND_REFERENCE,
+            self.inner.get_reference(b, p),
+        )
+        .await
+    }
+
+    async fn get_reference_to_file(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        dest: &Path,
+    ) -> Result<u64, StorageError> {
+        use tokio::io::AsyncWriteExt;
+        let key = self.reference_key(bucket, prefix);
+        if let Some(size) = self.cache.complete_size(&key).await {
+            let mut file = tokio::fs::File::create(dest).await?;
+            let mut written = 0u64;
+            for idx in 0..size.div_ceil(BLOCK_SIZE) {
+                match self.cache.read_block(&key, idx).await {
+                    Some(block) => {
+                        file.write_all(&block).await?;
+                        written += block.len() as u64;
+                    }
+                    None => break,
+                }
+            }
+            if written == size {
+                file.flush().await?;
+                self.cache.record_hit(KIND_REFERENCE);
+                return Ok(size);
+            }
+        }
+        self.cache.record_miss(KIND_REFERENCE);
+        let fill = self.cache.begin_fill(&key);
+        let size = self
+            .inner
+            .get_reference_to_file(bucket, prefix, dest)
+            .await?;
+        self.cache.fill_from_file(fill, dest, size).await;
+        Ok(size)
+    }
+
+    async fn get_delta(&self, b: &str, p: &str, f: &str) -> Result<Vec<u8>, StorageError> {
+        self.read_through(
+            self.object_key(KIND_DELTA, b, p, f),
+            KIND_DELTA,
+            self.inner.get_delta(b, p, f),
+        )
+        .await
+    }
+
+    async fn get_passthrough(&self, b: &str, p: &str, f: &str) -> Result<Vec<u8>, StorageError> {
+        self.read_through(
+            self.object_key(KIND_PASSTHROUGH, b, p, f),
+            KIND_PASSTHROUGH,
+            self.inner.get_passthrough(b, p, f),
+        )
+        .await
+    }
+
+    async fn get_passthrough_stream(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<BoxStream<'static, Result<Bytes, StorageError>>, StorageError> {
+        let key = self.object_key(KIND_PASSTHROUGH, bucket, prefix, filename);
+        let src = self.source(bucket, prefix, filename);
+        if let Some(size) = self.cache.complete_size(&key).await {
+            self.cache.record_hit(KIND_PASSTHROUGH);
+            let state = ReadState {
+                cache: Arc::clone(&self.cache),
+                src,
+                key,
+                pos: 0,
+                end: size - 1,
+                cached_until: size,
+                tee: None,
+                fallback: None,
+                done: false,
+            };
+            return Ok(state.into_stream());
+        }
+        self.cache.record_miss(KIND_PASSTHROUGH);
+        let fill = self.cache.begin_fill(&key);
+        let stream = self
+            .inner
+            .get_passthrough_stream(bucket, prefix, filename)
+            .await?;
+        let state = ReadState {
+            cache: Arc::clone(&self.cache),
+            src,
+            key,
+            pos: 0,
+            end: u64::MAX,
+            cached_until: 0,
+            tee: Some(Tee {
+                stream,
+                base: 0,
+                buf: BytesMut::new(),
+                fill,
+                requested_end: u64::MAX,
+            }),
+            fallback: None,
+            done: false,
+        };
+        Ok(state.into_stream())
+    }
+
+    async fn get_passthrough_stream_range(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError> {
+        let key = self.object_key(KIND_PASSTHROUGH, bucket, prefix, filename);
+        let first = start / BLOCK_SIZE;
+        let plan = self.cache.plan(&key, first, end / BLOCK_SIZE).await;
+        let end = match plan.size {
+            Some(size) if start < size => end.min(size - 1),
+            // Unsatisfiable (or empty object): let the backend produce its
+            // own error / empty response.
+            Some(_) => {
+                return self
+                    .inner
+                    .get_passthrough_stream_range(bucket, prefix, filename, start, end)
+                    .await
+            }
+            None => end,
+        };
+        let src = self.source(bucket, prefix, filename);
+        let Some(first_missing) = plan.first_missing else {
+            self.cache.record_hit(KIND_PASSTHROUGH);
+            let state = ReadState {
+                cache: Arc::clone(&self.cache),
+                src,
+                key,
+                pos: start,
+                end,
+                cached_until: end + 1,
+                tee: None,
+                fallback: None,
+                done: false,
+            };
+            return Ok((state.into_stream(), end - start + 1));
+        };
+
+        self.cache.record_miss(KIND_PASSTHROUGH);
+        let fill = self.cache.begin_fill(&key);
+        let fetch_start = first_missing * BLOCK_SIZE;
+        let mut fetch_end = (end / BLOCK_SIZE)
+            .saturating_add(1)
+            .saturating_mul(BLOCK_SIZE)
+            .saturating_sub(1);
+        if let Some(size) = plan.size {
+            fetch_end = fetch_end.min(size - 1);
+        }
+        let (stream, fetched_len) = src.range(fetch_start, fetch_end).await?;
+        if fetched_len == 0 {
+            // Backend without native ranges returned the full object:
+            // don't guess at offsets, serve the caller's range uncached.
+            drop(stream);
+            return src.range(start, end).await;
+        }
+        let end = end.min(fetch_start + fetched_len - 1);
+        let state = ReadState {
+            cache: Arc::clone(&self.cache),
+            src,
+            key,
+            pos: start,
+            end,
+            cached_until: fetch_start,
+            tee: Some(Tee {
+                stream,
+                base: fetch_start,
+                buf: BytesMut::new(),
+                fill,
+                requested_end: fetch_end.saturating_add(1),
+            }),
+            fallback: None,
+            done: false,
+        };
+        Ok((state.into_stream(), end.saturating_sub(start) + 1))
+    }
+
+    // ── Writes + deletes: run, then invalidate ──
+
+    async fn put_reference(
+        &self,
+        b: &str,
+        p: &str,
+        data: &[u8],
+        m: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let result = self.inner.put_reference(b, p, data, m).await;
+        self.cache.invalidate(&self.reference_key(b, p)).await;
+        result
+    }
+
+    async fn put_reference_from_file(
+        &self,
+        b: &str,
+        p: &str,
+        source_path: &Path,
+        m: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let result = self
+            .inner
+            .put_reference_from_file(b, p, source_path, m)
+            .await;
+        self.cache.invalidate(&self.reference_key(b, p)).await;
+        result
+    }
+
+    async fn delete_reference(&self, b: &str, p: &str) -> Result<(), StorageError> {
+        let result = self.inner.delete_reference(b, p).await;
+        self.cache.invalidate(&self.reference_key(b, p)).await;
+        result
+    }
+
+    async fn put_delta(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+        data: &[u8],
+        m: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let result = self.inner.put_delta(b, p, f, data, m).await;
+        self.cache
+            .invalidate(&self.object_key(KIND_DELTA, b, p, f))
+            .await;
+        result
+    }
+
+    async fn delete_delta(&self, b: &str, p: &str, f: &str) -> Result<(), StorageError> {
+        let result = self.inner.delete_delta(b, p, f).await;
+        self.cache
+            .invalidate(&self.object_key(KIND_DELTA, b, p, f))
+            .await;
+        result
+    }
+
+    async fn put_passthrough(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+        data: &[u8],
+        m: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let result = self.inner.put_passthrough(b, p, f, data, m).await;
+        self.cache
+            .invalidate(&self.object_key(KIND_PASSTHROUGH, b, p, f))
+            .await;
+        result
+    }
+
+    async fn put_passthrough_file(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+        source_path: &Path,
+        m: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let result = self
+            .inner
+            .put_passthrough_file(b, p, f, source_path, m)
+            .await;
+        self.cache
+            .invalidate(&self.object_key(KIND_PASSTHROUGH, b, p, f))
+            .await;
+        result
+    }
+
+    async fn put_passthrough_parts(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+        part_paths: &[PathBuf],
+        m: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let result = self
+            .inner
+            .put_passthrough_parts(b, p, f, part_paths, m)
+            .await;
+        self.cache
+            .invalidate(&self.object_key(KIND_PASSTHROUGH, b, p, f))
+            .await;
+        result
+    }
+
+    async fn put_passthrough_chunked(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+        chunks: &[Bytes],
+        m: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let result = self.inner.put_passthrough_chunked(b, p, f, chunks, m).await;
+        self.cache
+            .invalidate(&self.object_key(KIND_PASSTHROUGH, b, p, f))
+            .await;
+        result
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        upload: &MultipartUpload,
+        p: &str,
+        f: &str,
+        parts: &[UploadedPart],
+        assembled: &[Bytes],
+        m: &FileMetadata,
+    ) -> Result<String, StorageError> {
+        let result = self
+            .inner
+            .complete_multipart_upload(upload, p, f, parts, assembled, m)
+            .await;
+        self.cache
+            .invalidate(&self.object_key(KIND_PASSTHROUGH, &upload.bucket, p, f))
+            .await;
+        result
+    }
+
+    async fn delete_passthrough(&self, b: &str, p: &str, f: &str) -> Result<(), StorageError> {
+        let result = self.inner.delete_passthrough(b, p, f).await;
+        self.cache
+            .invalidate(&self.object_key(KIND_PASSTHROUGH, b, p, f))
+            .await;
+        result
+    }
+
+    async fn delete_bucket(&self, b: &str) -> Result<(), StorageError> {
+        let result = self.inner.delete_bucket(b).await;
+        self.cache.invalidate_prefix(&self.bucket_prefix(b)).await;
+        result
+    }
+
+    // ── Pass-through ──
+
+    async fn create_bucket(&self, b: &str) -> Result<(), StorageError> {
+        self.inner.create_bucket(b).await
+    }
+    async fn ensure_declared_bucket(&self, b: &str) -> Result<(), StorageError> {
+        self.inner.ensure_declared_bucket(b).await
+    }
+    async fn list_buckets(&self) -> Result<Vec<String>, StorageError> {
+        self.inner.list_buckets().await
+    }
+    async fn list_buckets_with_dates(
+        &self,
+    ) -> Result<Vec<(String, chrono::DateTime<chrono::Utc>)>, StorageError> {
+        self.inner.list_buckets_with_dates().await
+    }
+    async fn list_bucket_origins(&self) -> Result<Vec<BucketListing>, StorageError> {
+        self.inner.list_bucket_origins().await
+    }
+    async fn head_bucket(&self, b: &str) -> Result<bool, StorageError> {
+        self.inner.head_bucket(b).await
+    }
+    async fn has_reference(&self, b: &str, p: &str) -> Result<bool, StorageError> {
+        self.inner.has_reference(b, p).await
+    }
+    async fn get_reference_metadata(&self, b: &str, p: &str) -> Result<FileMetadata, StorageError> {
+        self.inner.get_reference_metadata(b, p).await
+    }
+    async fn put_reference_metadata(
+        &self,
+        b: &str,
+        p: &str,
+        m: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        self.inner.put_reference_metadata(b, p, m).await
+    }
+    async fn get_delta_metadata(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.inner.get_delta_metadata(b, p, f).await
+    }
+    async fn get_passthrough_metadata(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.inner.get_passthrough_metadata(b, p, f).await
+    }
+    async fn put_passthrough_metadata(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+        m: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        // Metadata-only rewrite: the cached bytes stay valid.
+        self.inner.put_passthrough_metadata(b, p, f, m).await
+    }
+    async fn create_multipart_upload(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+        m: &FileMetadata,
+    ) -> Result<MultipartUpload, StorageError> {
+        self.inner.create_multipart_upload(b, p, f, m).await
+    }
+    async fn upload_part(
+        &self,
+        upload: &MultipartUpload,
+        p: &str,
+        f: &str,
+        part_number: i32,
+        data: Bytes,
+    ) -> Result<UploadedPart, StorageError> {
+        self.inner
+            .upload_part(upload, p, f, part_number, data)
+            .await
+    }
+    async fn abort_multipart_upload(
+        &self,
+        upload: &MultipartUpload,
+        p: &str,
+        f: &str,
+    ) -> Result<(), StorageError> {
+        self.inner.abort_multipart_upload(upload, p, f).await
+    }
+    fn persists_multipart_uploads(&self, b: &str) -> bool {
+        self.inner.persists_multipart_uploads(b)
+    }
+    async fn resume_multipart_upload(
+        &self,
+        b: &str,
+        p: &str,
+        f: &str,
+        upload_id: &str,
+    ) -> Result<StagedMultipartUpload, StorageError> {
+        self.inner.resume_multipart_upload(b, p, f, upload_id).await
+    }
+    async fn list_multipart_parts(
+        &self,
+        upload: &MultipartUpload,
+        p: &str,
+        f: &str,
+    ) -> Result<Vec<StagedPart>, StorageError> {
+        self.inner.list_multipart_parts(upload, p, f).await
+    }
+    async fn read_multipart_part(
+        &self,
+        upload: &MultipartUpload,
+        p: &str,
+        f: &str,
+        part_number: i32,
+    ) -> Result<Bytes, StorageError> {
+        self.inner
+            .read_multipart_part(upload, p, f, part_number)
+            .await
+    }
+    async fn list_multipart_uploads(
+        &self,
+        b: &str,
+    ) -> Result<Vec<StagedMultipartUpload>, StorageError> {
+        self.inner.list_multipart_uploads(b).await
+    }
+    async fn sweep_multipart_uploads(
+        &self,
+        idle: std::time::Duration,
+    ) -> Result<StagedSweepReport, StorageError> {
+        self.inner.sweep_multipart_uploads(idle).await
+    }
+    fn multipart_storage_label(&self, b: &str) -> &'static str {
+        self.inner.multipart_storage_label(b)
+    }
+    fn supports_native_multipart(&self, b: &str) -> bool {
+        self.inner.supports_native_multipart(b)
+    }
+    fn lite_list_carries_logical_facts(&self, b: &str) -> bool {
+        self.inner.lite_list_carries_logical_facts(b)
+    }
+    fn stamps_encryption_key_id(&self, b: &str) -> bool {
+        self.inner.stamps_encryption_key_id(b)
+    }
+    fn reserves_filename(&self, b: &str, f: &str) -> bool {
+        self.inner.reserves_filename(b, f)
+    }
+    async fn scan_deltaspace(&self, b: &str, p: &str) -> Result<Vec<FileMetadata>, StorageError> {
+        self.inner.scan_deltaspace(b, p).await
+    }
+    async fn scan_deltaspace_lite(&self, b: &str, p: &str) -> Result<LiteScanResult, StorageError> {
+        self.inner.scan_deltaspace_lite(b, p).await
+    }
+    async fn list_deltaspaces(&self, b: &str) -> Result<Vec<String>, StorageError> {
+        self.inner.list_deltaspaces(b).await
+    }
+    async fn total_size(&self, b: Option<&str>) -> Result<u64, StorageError> {
+        self.inner.total_size(b).await
+    }
+    async fn put_directory_marker(&self, b: &str, k: &str) -> Result<(), StorageError> {
+        self.inner.put_directory_marker(b, k).await
+    }
+    async fn bulk_list_objects(
+        &self,
+        b: &str,
+        p: &str,
+    ) -> Result<Vec<(String, FileMetadata)>, StorageError> {
+        self.inner.bulk_list_objects(b, p).await
+    }
+    async fn enrich_list_metadata(
+        &self,
+        b: &str,
+        o: Vec<(String, FileMetadata)>,
+    ) -> Result<Vec<(String, FileMetadata)>, StorageError> {
+        self.inner.enrich_list_metadata(b, o).await
+    }
+    async fn list_objects_delegated(
+        &self,
+        b: &str,
+        p: &str,
+        d: Option<&str>,
+        m: u32,
+        t: Option<&str>,
+    ) -> Result<Option<DelegatedListResult>, StorageError> {
+        self.inner.list_objects_delegated(b, p, d, m, t).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::disk_cache::DiskCacheLimits;
+    use super::*;
+    use crate::storage::FilesystemBackend;
+
+    async fn collect(stream: BoxStream<'static, Result<Bytes, StorageError>>) -> Vec<u8> {
+        let chunks: Vec<Bytes> = stream.map(|c| c.unwrap()).collect().await;
+        chunks.concat()
+    }
+
+    fn meta(data: &[u8]) -> FileMetadata {
+        FileMetadata::new_passthrough(
+            "obj.bin".to_string(),
+            "sha".to_string(),
+            "md5".to_string(),
+            data.len() as u64,
+            None,
+        )
+    }
+
+    async fn setup() -> (tempfile::TempDir, tempfile::TempDir, CachingBackend) {
+        let data_dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let inner = FilesystemBackend::new(data_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        inner.create_bucket("b").await.unwrap();
+        let cache = DiskCache::open(
+            cache_dir.path().to_path_buf(),
+            DiskCacheLimits {
+                max_bytes: 64 << 20,
+                max_object_bytes: 64 << 20,
+                ttl: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+        let backend = CachingBackend::new("test", Box::new(inner), cache);
+        (data_dir, cache_dir, backend)
+    }
+
+    fn pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[tokio::test]
+    async fn stream_miss_fills_then_hits() {
+        let (_d, _c, backend) = setup().await;
+        let data = pattern(3 * BLOCK_SIZE as usize + 17);
+        backend
+            .put_passthrough("b", "p", "obj.bin", &data, &meta(&data))
+            .await
+            .unwrap();
+
+        let first = collect(
+            backend
+                .get_passthrough_stream("b", "p", "obj.bin")
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(first, data);
+        assert_eq!(backend.cache.stats().used_bytes, data.len() as u64);
+
+        let second = collect(
+            backend
+                .get_passthrough_stream("b", "p", "obj.bin")
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(second, data);
+    }
+
+    #[tokio::test]
+    async fn range_reads_fill_only_touched_blocks() {
+        let (_d, _c, backend) = setup().await;
+        let data = pattern(4 * BLOCK_SIZE as usize + 5);
+        backend
+            .put_passthrough("b", "p", "obj.bin", &data, &meta(&data))
+            .await
+            .unwrap();
+
+        let (start, end) = (BLOCK_SIZE + 10, 2 * BLOCK_SIZE + 20);
+        let (stream, len) = backend
+            .get_passthrough_stream_range("b", "p", "obj.bin", start, end)
+            .await
+            .unwrap();
+        assert_eq!(len, end - start + 1);
+        assert_eq!(collect(stream).await, &data[start as usize..=end as usize]);
+        assert_eq!(backend.cache.stats().blocks, 2);
+
+        // Overlapping range: block 1 cached, block 0 fetched.
+        let (stream, len) = backend
+            .get_passthrough_stream_range("b", "p", "obj.bin", 5, BLOCK_SIZE + 50)
+            .await
+            .unwrap();
+        assert_eq!(len, BLOCK_SIZE + 46);
+        assert_eq!(collect(stream).await, &data[5..=(BLOCK_SIZE as usize + 50)]);
+
+        // Open-ended tail read discovers the size.
+        let tail_start = 4 * BLOCK_SIZE;
+        let (stream, _) = backend
+            .get_passthrough_stream_range("b", "p", "obj.bin", tail_start, u64::MAX - 1)
+            .await
+            .unwrap();
+        assert_eq!(collect(stream).await, &data[tail_start as usize..]);
+        let plan = backend
+            .cache
+            .plan(
+                &backend.object_key(KIND_PASSTHROUGH, "b", "p", "obj.bin"),
+                0,
+                0,
+            )
+            .await;
+        assert_eq!(plan.size, Some(data.len() as u64));
+    }
+
+    #[tokio::test]
+    async fn writes_and_deletes_invalidate() {
+        let (_d, _c, backend) = setup().await;
+        let v1 = pattern(1000);
+        backend
+            .put_passthrough("b", "p", "obj.bin", &v1, &meta(&v1))
+            .await
+            .unwrap();
+        assert_eq!(
+            backend.get_passthrough("b", "p", "obj.bin").await.unwrap(),
+            v1
+        );
+        assert_eq!(backend.cache.stats().entries, 1);
+
+        let v2 = vec![9u8; 500];
+        backend
+            .put_passthrough("b", "p", "obj.bin", &v2, &meta(&v2))
+            .await
+            .unwrap();
+        assert_eq!(backend.cache.stats().entries, 0);
+        assert_eq!(
+            backend.get_passthrough("b", "p", "obj.bin").await.unwrap(),
+            v2
+        );
+
+        backend
+            .delete_passthrough("b", "p", "obj.bin")
+            .await
+            .unwrap();
+        assert_eq!(backend.cache.stats().entries, 0);
+        assert!(backend.get_passthrough("b", "p", "obj.bin").await.is_err());
+    }
+}
@@ -0,0 +1,1023 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Read-through object cache on local disk (the storage half of
+//! `advanced.disk_cache`).
+//!
+//! Objects are cached as fixed-size blocks ([`BLOCK_SIZE`]) so a range read
+//! only has to fetch (and cache) the blocks it touches. Layout under the
+//! cache root:
+//!
+//! ```text
+//! <root>/<h[0..2]>/<h>-<entry id>/meta.json   {key, id, size, created}
+//! <root>/<h[0..2]>/<h>-<entry id>/<block>.blk
+//! ```
+//!
+//! where `h` is the SHA-256 of the cache key. Every entry gets a fresh id,
+//! so an invalidated entry's directory can be removed without racing a
+//! refill of the same key. The index lives in memory and is rebuilt from
+//! the directory tree on open (LRU order seeded from block mtimes).
+//!
+//! Fills race invalidations: a reader that fetched bytes before a PUT
+//! landed must not park them in the cache after the PUT's invalidation.
+//! Readers register a [`FillHandle`] BEFORE fetching from the backend;
+//! invalidating a key with registered fills bumps an epoch and any block
+//! stored under an older epoch is discarded.
+//!
+//! The cache is process-global per root path: config applies rebuild the
+//! engine, and [`DiskCache::open`] hands back the live instance (with the
+//! new limits) instead of re-indexing the directory.
+
+use super::StorageError;
+use crate::metrics::Metrics;
+use bytes::Bytes;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// Cache block size. Range reads are widened to block boundaries.
+pub const BLOCK_SIZE: u64 = 1024 * 1024;
+
+const META_FILE: &str = "meta.json";
+const BLOCK_EXT: &str = "blk";
+
+static REGISTRY: LazyLock<Mutex<HashMap<PathBuf, Weak<DiskCache>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Size/TTL budgets, resolved from [`crate::config_sections::DiskCacheConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskCacheLimits {
+    pub max_bytes: u64,
+    pub max_object_bytes: u64,
+    pub ttl: Option<Duration>,
+}
+
+impl DiskCacheLimits {
+    pub fn from_config(cfg: &crate::config_sections::DiskCacheConfig) -> Self {
+        Self {
+            max_bytes: cfg.max_size_bytes(),
+            max_object_bytes: cfg.max_object_size_bytes(),
+            ttl: cfg.ttl_duration(),
+        }
+    }
+}
+
+/// Point-in-time cache occupancy (admin API + metrics scrape).
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DiskCacheStats {
+    pub used_bytes: u64,
+    pub max_bytes: u64,
+    pub entries: usize,
+    pub blocks: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EntryMeta {
+    key: String,
+    id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    /// Unix seconds. TTL is measured from here.
+    created: u64,
+}
+
+struct Block {
+    len: u64,
+    tick: u64,
+}
+
+struct Entry {
+    id: u64,
+    /// Object size, once a fill has seen EOF. `None` while only leading or
+    /// middle blocks are known.
+    size: Option<u64>,
+    created: u64,
+    blocks: BTreeMap<u64, Block>,
+}
+
+struct State {
+    limits: DiskCacheLimits,
+    entries: BTreeMap<String, Entry>,
+    /// LRU order: tick → (key, block index). Lowest tick is evicted first.
+    lru: BTreeMap<u64, (String, u64)>,
+    tick: u64,
+    used: u64,
+    next_id: u64,
+    epoch: u64,
+    /// Registered fills per key (see module docs).
+    fills: HashMap<String, usize>,
+    /// Epoch of the latest invalidation per key that had fills in flight.
+    invalidated: HashMap<String, u64>,
+}
+
+/// Outcome of a block lookup for `[first, last]`.
+pub(crate) struct BlockPlan {
+    /// Object size when known.
+    pub size: Option<u64>,
+    /// First block in the range that is NOT cached (`None` = all cached).
+    pub first_missing: Option<u64>,
+}
+
+pub struct DiskCache {
+    root: PathBuf,
+    state: Mutex<State>,
+    metrics: RwLock<Option<Arc<Metrics>>>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn key_hash(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+fn block_count(size: u64) -> u64 {
+    size.div_ceil(BLOCK_SIZE)
+}
+
+impl State {
+    fn expired(&self, entry: &Entry) -> bool {
+        match self.limits.ttl {
+            Some(ttl) => now_secs().saturating_sub(entry.created) >= ttl.as_secs(),
+            None => false,
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn touch(&mut self, key: &str, idx: u64) {
+        let tick = self.next_tick();
+        if let Some(block) = self
+            .entries
+            .get_mut(key)
+            .and_then(|e| e.blocks.get_mut(&idx))
+        {
+            let old = std::mem::replace(&mut block.tick, tick);
+            self.lru.remove(&old);
+            self.lru.insert(tick, (key.to_string(), idx));
+        }
+    }
+
+    /// Drop `key` from the index; returns the entry id for directory removal.
+    fn remove_entry(&mut self, key: &str) -> Option<u64> {
+        let entry = self.entries.remove(key)?;
+        for block in entry.blocks.values() {
+            self.lru.remove(&block.tick);
+            self.used -= block.len;
+        }
+        Some(entry.id)
+    }
+
+    /// Invalidate `key`: drop the entry and poison registered fills.
+    fn invalidate(&mut self, key: &str) -> Option<u64> {
+        if self.fills.contains_key(key) {
+            self.epoch += 1;
+            self.invalidated.insert(key.to_string(), self.epoch);
+        }
+        self.remove_entry(key)
+    }
+
+    fn is_stale(&self, key: &str, epoch: u64) -> bool {
+        self.invalidated.get(key).is_some_and(|e| *e > epoch)
+    }
+
+    /// Evict least-recently-used blocks until `used <= max_bytes`. Returns
+    /// (key, entry id, Some(block) | None for the whole entry) to delete.
+    fn evict(&mut self) -> Vec<(String, u64, Option<u64>)> {
+        let mut victims = Vec::new();
+        while self.used > self.limits.max_bytes {
+            let Some((_, (key, idx))) = self.lru.pop_first() else {
+                break;
+            };
+            let Some(entry) = self.entries.get_mut(&key) else {
+                continue;
+            };
+            if let Some(block) = entry.blocks.remove(&idx) {
+                self.used -= block.len;
+            }
+            let id = entry.id;
+            if entry.blocks.is_empty() {
+                self.entries.remove(&key);
+                victims.push((key, id, None));
+            } else {
+                victims.push((key, id, Some(idx)));
+            }
+        }
+        victims
+    }
+}
+
+impl DiskCache {
+    /// Open (or re-attach to) the cache rooted at `root`. A second open of the
+    /// same root returns the live instance with `limits` applied.
+    pub async fn open(
+        root: PathBuf,
+        limits: DiskCacheLimits,
+        metrics: Option<Arc<Metrics>>,
+    ) -> Result<Arc<Self>, StorageError> {
+        tokio::fs::create_dir_all(&root).await?;
+        let root = tokio::fs::canonicalize(&root).await?;
+
+        let existing = REGISTRY.lock().get(&root).and_then(Weak::upgrade);
+        if let Some(existing) = existing {
+            existing.reconfigure(limits, metrics).await;
+            return Ok(existing);
+        }
+
+        let scan_root = root.clone();
+        let (entries, used) = tokio::task::spawn_blocking(move || scan_root_dir(&scan_root))
+            .await
+            .map_err(super::join_error)?;
+
+        let mut lru = BTreeMap::new();
+        let mut ordered: Vec<(SystemTime, String, u64)> = Vec::new();
+        let mut indexed = BTreeMap::new();
+        let mut next_id = uuid::Uuid::new_v4().as_u64_pair().0 >> 1;
+        for (key, scanned) in entries {
+            next_id = next_id.max(scanned.entry.id + 1);
+            for (idx, mtime) in &scanned.mtimes {
+                ordered.push((*mtime, key.clone(), *idx));
+            }
+            indexed.insert(key, scanned.entry);
+        }
+        ordered.sort();
+        let mut tick = 0;
+        for (_, key, idx) in ordered {
+            tick += 1;
+            if let Some(block) = indexed.get_mut(&key).and_then(|e| e.blocks.get_mut(&idx)) {
+                block.tick = tick;
+            }
+            lru.insert(tick, (key, idx));
+        }
+        debug!(
+            "disk cache at {}: indexed {} entries ({} bytes)",
+            root.display(),
+            indexed.len(),
+            used
+        );
+
+        let cache = Arc::new(Self {
+            root: root.clone(),
+            state: Mutex::new(State {
+                limits,
+                entries: indexed,
+                lru,
+                tick,
+                used,
+                next_id,
+                epoch: 0,
+                fills: HashMap::new(),
+                invalidated: HashMap::new(),
+            }),
+            metrics: RwLock::new(metrics),
+        });
+
+        let raced = {
+            let mut registry = REGISTRY.lock();
+            registry.retain(|_, w| w.strong_count() > 0);
+            let existing = registry.get(&root).and_then(Weak::upgrade);
+            if existing.is_none() {
+                registry.insert(root, Arc::downgrade(&cache));
+            }
+            existing
+        };
+        if let Some(existing) = raced {
+            // Lost an open race; the winner's index is authoritative.
+            let metrics = cache.metrics.read().clone();
+            existing.reconfigure(limits, metrics).await;
+            return Ok(existing);
+        }
+        cache.enforce_budget().await;
+        Ok(cache)
+    }
+
+    async fn reconfigure(&self, limits: DiskCacheLimits, metrics: Option<Arc<Metrics>>) {
+        self.state.lock().limits = limits;
+        *self.metrics.write() = metrics;
+        self.enforce_budget().await;
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn limits(&self) -> DiskCacheLimits {
+        self.state.lock().limits
+    }
+
+    pub fn stats(&self) -> DiskCacheStats {
+        let state = self.state.lock();
+        DiskCacheStats {
+            used_bytes: state.used,
+            max_bytes: state.limits.max_bytes,
+            entries: state.entries.len(),
+            blocks: state.lru.len(),
+        }
+    }
+
+    pub(crate) fn record_hit(&self, kind: &str) {
+        if let Some(m) = self.metrics.read().as_ref() {
+            m.disk_cache_hits_total.with_label_values(&[kind]).inc();
+        }
+    }
+
+    pub(crate) fn record_miss(&self, kind: &str) {
+        if let Some(m) = self.metrics.read().as_ref() {
+            m.disk_cache_misses_total.with_label_values(&[kind]).inc();
+        }
+    }
+
+    fn entry_dir(&self, key: &str, id: u64) -> PathBuf {
+        let h = key_hash(key);
+        self.root.join(&h[..2]).join(format!("{h}-{id:016x}"))
+    }
+
+    fn block_path(&self, key: &str, id: u64, idx: u64) -> PathBuf {
+        self.entry_dir(key, id).join(format!("{idx}.{BLOCK_EXT}"))
+    }
+
+    /// Drop expired entries on access. Returns the entry id to remove from
+    /// disk when `key` was expired.
+    fn expire_locked(state: &mut State, key: &str) -> Option<u64> {
+        let expired = state.entries.get(key).is_some_and(|e| state.expired(e));
+        if expired {
+            state.remove_entry(key)
+        } else {
+            None
+        }
+    }
+
+    /// Size of `key` when EVERY block is cached (a full-object hit).
+    pub(crate) async fn complete_size(&self, key: &str) -> Option<u64> {
+        let (result, expired) = {
+            let mut state = self.state.lock();
+            let expired = Self::expire_locked(&mut state, key);
+            let result = state.entries.get(key).and_then(|e| {
+                let size = e.size?;
+                (size > 0 && e.blocks.len() as u64 == block_count(size)).then_some(size)
+            });
+            (result, expired)
+        };
+        if let Some(id) = expired {
+            self.remove_dir(key, id).await;
+        }
+        result
+    }
+
+    /// Which blocks of `[first, last]` are cached. Scans only until the first
+    /// gap, so a huge `last` (open-ended range) stays cheap.
+    pub(crate) async fn plan(&self, key: &str, first: u64, last: u64) -> BlockPlan {
+        let (plan, expired) = {
+            let mut state = self.state.lock();
+            let expired = Self::expire_locked(&mut state, key);
+            let plan = match state.entries.get(key) {
+                None => BlockPlan {
+                    size: None,
+                    first_missing: Some(first),
+                },
+                Some(e) => {
+                    let last = match e.size {
+                        Some(size) if size > 0 => last.min(block_count(size) - 1),
+                        _ => last,
+                    };
+                    let mut idx = first;
+                    while idx <= last && e.blocks.contains_key(&idx) {
+                        idx += 1;
+                    }
+                    BlockPlan {
+                        size: e.size,
+                        first_missing: (idx <= last).then_some(idx),
+                    }
+                }
+            };
+            (plan, expired)
+        };
+        if let Some(id) = expired {
+            self.remove_dir(key, id).await;
+        }
+        plan
+    }
+
+    /// Read one cached block, bumping its LRU position. A missing or
+    /// short file drops the block from the index and reads as a miss.
+    pub(crate) async fn read_block(&self, key: &str, idx: u64) -> Option<Bytes> {
+        let (id, len) = {
+            let mut state = self.state.lock();
+            let entry = state.entries.get(key)?;
+            if state.expired(entry) {
+                return None;
+            }
+            let id = entry.id;
+            let len = entry.blocks.get(&idx)?.len;
+            state.touch(key, idx);
+            (id, len)
+        };
+        match tokio::fs::read(self.block_path(key, id, idx)).await {
+            Ok(data) if data.len() as u64 == len => Some(Bytes::from(data)),
+            Ok(_) | Err(_) => {
+                debug!("disk cache block {key}#{idx} unreadable; dropping entry");
+                self.invalidate(key).await;
+                None
+            }
+        }
+    }
+
+    /// The whole object when every block is cached.
+    pub(crate) async fn read_whole(&self, key: &str) -> Option<Vec<u8>> {
+        let size = self.complete_size(key).await?;
+        let mut out = Vec::with_capacity(size as usize);
+        for idx in 0..block_count(size) {
+            out.extend_from_slice(&self.read_block(key, idx).await?);
+        }
+        (out.len() as u64 == size).then_some(out)
+    }
+
+    /// Register a fill for `key`. Call BEFORE fetching from the backend.
+    pub(crate) fn begin_fill(self: &Arc<Self>, key: &str) -> FillHandle {
+        let mut state = self.state.lock();
+        *state.fills.entry(key.to_string()).or_insert(0) += 1;
+        FillHandle {
+            cache: Arc::clone(self),
+            key: key.to_string(),
+            epoch: state.epoch,
+            poisoned: false,
+        }
+    }
+
+    fn end_fill(&self, key: &str) {
+        let mut state = self.state.lock();
+        if let Some(n) = state.fills.get_mut(key) {
+            *n -= 1;
+            if *n == 0 {
+                state.fills.remove(key);
+                state.invalidated.remove(key);
+            }
+        }
+    }
+
+    /// Cache a whole object fetched by a miss. Objects over the per-object
+    /// budget are skipped.
+    pub(crate) async fn fill_whole(&self, mut fill: FillHandle, data: &[u8]) {
+        if data.is_empty() || data.len() as u64 > self.limits().max_object_bytes {
+            return;
+        }
+        for (idx, chunk) in data.chunks(BLOCK_SIZE as usize).enumerate() {
+            fill.store_block(idx as u64, Bytes::copy_from_slice(chunk))
+                .await;
+        }
+        fill.set_size(data.len() as u64).await;
+    }
+
+    /// Cache a whole object a miss already wrote to `path`.
+    pub(crate) async fn fill_from_file(&self, mut fill: FillHandle, path: &Path, size: u64) {
+        use tokio::io::AsyncReadExt;
+        if size == 0 || size > self.limits().max_object_bytes {
+            return;
+        }
+        let Ok(mut file) = tokio::fs::File::open(path).await else {
+            return;
+        };
+        for idx in 0..block_count(size) {
+            let len = BLOCK_SIZE.min(size - idx * BLOCK_SIZE) as usize;
+            let mut buf = vec![0u8; len];
+            if file.read_exact(&mut buf).await.is_err() {
+                return;
+            }
+            fill.store_block(idx, Bytes::from(buf)).await;
+        }
+        fill.set_size(size).await;
+    }
+
+    /// Drop `key` (after a write or delete through the proxy).
+    pub async fn invalidate(&self, key: &str) {
+        let removed = self.state.lock().invalidate(key);
+        if let Some(id) = removed {
+            self.remove_dir(key, id).await;
+        }
+    }
+
+    /// Drop every key starting with `prefix` (bucket deletion).
+    pub async fn invalidate_prefix(&self, prefix: &str) {
+        let removed: Vec<(String, u64)> = {
+            let mut state = self.state.lock();
+            let keys: Vec<String> = state
+                .entries
+                .range(prefix.to_string()..)
+                .map(|(k, _)| k)
+                .take_while(|k| k.starts_with(prefix))
+                .cloned()
+                .collect();
+            let filling: Vec<String> = state
+                .fills
+                .keys()
+                .filter(|k| k.starts_with(prefix))
+                .cloned()
+                .collect();
+            for key in filling {
+                state.invalidate(&key);
+            }
+            keys.into_iter()
+                .filter_map(|k| state.invalidate(&k).map(|id| (k, id)))
+                .collect()
+        };
+        for (key, id) in removed {
+            self.remove_dir(&key, id).await;
+        }
+    }
+
+    async fn remove_dir(&self, key: &str, id: u64) {
+        let dir = self.entry_dir(key, id);
+        if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("disk cache: failed to remove {}: {e}", dir.display());
+            }
+        }
+    }
+
+    async fn enforce_budget(&self) {
+        let victims = self.state.lock().evict();
+        self.delete_victims(victims).await;
+    }
+
+    async fn delete_victims(&self, victims: Vec<(String, u64, Option<u64>)>) {
+        if victims.is_empty() {
+            return;
+        }
+        if let Some(m) = self.metrics.read().as_ref() {
+            m.disk_cache_evictions_total.inc_by(victims.len() as u64);
+        }
+        for (key, id, block) in victims {
+            match block {
+                Some(idx) => {
+                    let _ = tokio::fs::remove_file(self.block_path(&key, id, idx)).await;
+                }
+                None => self.remove_dir(&key, id).await,
+            }
+        }
+    }
+
+    async fn write_meta(&self, key: &str, meta: &EntryMeta) -> std::io::Result<()> {
+        let dir = self.entry_dir(key, meta.id);
+        let body = serde_json::to_vec(meta).map_err(std::io::Error::other)?;
+        write_atomic(&dir, META_FILE, &body).await
+    }
+}
+
+/// A registered fill (see module docs). Stores are best-effort: cache I/O
+/// errors are logged and never surface to the reader.
+pub(crate) struct FillHandle {
+    cache: Arc<DiskCache>,
+    key: String,
+    epoch: u64,
+    poisoned: bool,
+}
+
+impl FillHandle {
+    /// Cache block `idx`. Only the final block of an object may be short.
+    pub(crate) async fn store_block(&mut self, idx: u64, data: Bytes) {
+        if self.poisoned || data.is_empty() || data.len() as u64 > BLOCK_SIZE {
+            return;
+        }
+        let len = data.len() as u64;
+        let end = idx * BLOCK_SIZE + len;
+        let cache = &self.cache;
+
+        // Phase 1: validate + reserve the entry.
+        enum Reserve {
+            Skip,
+            Poison(Option<u64>),
+            Write(u64, Option<EntryMeta>),
+        }
+        let reserved = {
+            let mut state = cache.state.lock();
+            if state.is_stale(&self.key, self.epoch) {
+                Reserve::Poison(None)
+            } else if end > state.limits.max_object_bytes || len > state.limits.max_bytes {
+                Reserve::Skip
+            } else if let Some(entry) = state.entries.get(&self.key) {
+                let mismatched = match entry.size {
+                    Some(size) => end > size || (len < BLOCK_SIZE && end != size),
+                    None => false,
+                };
+                if mismatched || state.expired(entry) {
+                    // The object changed (or aged out) under the cache.
+                    Reserve::Poison(state.invalidate(&self.key))
+                } else if entry.blocks.get(&idx).is_some_and(|b| b.len == len) {
+                    state.touch(&self.key, idx);
+                    Reserve::Skip
+                } else {
+                    Reserve::Write(entry.id, None)
+                }
+            } else {
+                let id = state.next_id;
+                state.next_id += 1;
+                let created = now_secs();
+                state.entries.insert(
+                    self.key.clone(),
+                    Entry {
+                        id,
+                        size: None,
+                        created,
+                        blocks: BTreeMap::new(),
+                    },
+                );
+                let meta = EntryMeta {
+                    key: self.key.clone(),
+                    id,
+                    size: None,
+                    created,
+                };
+                Reserve::Write(id, Some(meta))
+            }
+        };
+        let (id, new_meta) = match reserved {
+            Reserve::Skip => return,
+            Reserve::Poison(removed) => {
+                self.poisoned = true;
+                if let Some(id) = removed {
+                    cache.remove_dir(&self.key, id).await;
+                }
+                return;
+            }
+            Reserve::Write(id, meta) => (id, meta),
+        };
+
+        // Phase 2: write outside the lock.
+        let dir = cache.entry_dir(&self.key, id);
+        let mut written = match &new_meta {
+            Some(meta) => cache.write_meta(&self.key, meta).await,
+            None => Ok(()),
+        };
+        if written.is_ok() {
+            written = write_atomic(&dir, &format!("{idx}.{BLOCK_EXT}"), &data).await;
+        }
+        if let Err(e) = written {
+            debug!("disk cache: failed to store {}#{idx}: {e}", self.key);
+            return;
+        }
+
+        // Phase 3: commit unless invalidated meanwhile.
+        enum Commit {
+            Stored(Vec<(String, u64, Option<u64>)>, Option<EntryMeta>),
+            Orphaned { stale: bool, same_entry: bool },
+        }
+        let committed = {
+            let mut state = cache.state.lock();
+            let stale = state.is_stale(&self.key, self.epoch);
+            let same_entry = state.entries.get(&self.key).is_some_and(|e| e.id == id);
+            if stale || !same_entry {
+                Commit::Orphaned { stale, same_entry }
+            } else {
+                let tick = state.next_tick();
+                let entry = state.entries.get_mut(&self.key).expect("checked above");
+                let replaced = entry.blocks.insert(idx, Block { len, tick });
+                let size_meta = if len < BLOCK_SIZE && entry.size.is_none() {
+                    entry.size = Some(end);
+                    Some(EntryMeta {
+                        key: self.key.clone(),
+                        id,
+                        size: Some(end),
+                        created: entry.created,
+                    })
+                } else {
+                    None
+                };
+                if let Some(old) = replaced {
+                    state.lru.remove(&old.tick);
+                    state.used -= old.len;
+                }
+                state.lru.insert(tick, (self.key.clone(), idx));
+                state.used += len;
+                Commit::Stored(state.evict(), size_meta)
+            }
+        };
+        let (victims, size_meta) = match committed {
+            Commit::Stored(victims, size_meta) => (victims, size_meta),
+            Commit::Orphaned { stale, same_entry } => {
+                self.poisoned = stale;
+                if same_entry {
+                    let _ = tokio::fs::remove_file(dir.join(format!("{idx}.{BLOCK_EXT}"))).await;
+                } else {
+                    cache.remove_dir(&self.key, id).await;
+                }
+                return;
+            }
+        };
+        if let Some(m) = cache.metrics.read().as_ref() {
+            m.disk_cache_fill_bytes_total.inc_by(len);
+        }
+        if let Some(meta) = size_meta {
+            let _ = cache.write_meta(&self.key, &meta).await;
+        }
+        cache.delete_victims(victims).await;
+    }
+
+    /// Record the object size once a fill reached EOF. A size that
+    /// contradicts the cached blocks invalidates the entry.
+    pub(crate) async fn set_size(&mut self, size: u64) {
+        if self.poisoned {
+            return;
+        }
+        let cache = &self.cache;
+        enum Outcome {
+            Nothing,
+            Write(EntryMeta),
+            Drop(Option<u64>),
+        }
+        let outcome = {
+            let mut state = cache.state.lock();
+            if state.is_stale(&self.key, self.epoch) {
+                Outcome::Nothing
+            } else {
+                match state.entries.get_mut(&self.key) {
+                    None => Outcome::Nothing,
+                    Some(entry) => {
+                        let consistent = entry.size.is_none_or(|s| s == size)
+                            && entry.blocks.iter().all(|(idx, b)| {
+                                let end = idx * BLOCK_SIZE + b.len;
+                                end < size && b.len == BLOCK_SIZE || end == size
+                            });
+                        if !consistent {
+                            Outcome::Drop(state.invalidate(&self.key))
+                        } else if entry.size.is_none() {
+                            entry.size = Some(size);
+                            Outcome::Write(EntryMeta {
+                                key: self.key.clone(),
+                                id: entry.id,
+                                size: Some(size),
+                                created: entry.created,
+                            })
+                        } else {
+                            Outcome::Nothing
+                        }
+                    }
+                }
+            }
+        };
+        match outcome {
+            Outcome::Nothing => {}
+            Outcome::Write(meta) => {
+                let _ = cache.write_meta(&self.key, &meta).await;
+            }
+            Outcome::Drop(id) => {
+                self.poisoned = true;
+                if let Some(id) = id {
+                    cache.remove_dir(&self.key, id).await;
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FillHandle {
+    fn drop(&mut self) {
+        self.cache.end_fill(&self.key);
+    }
+}
+
+/// Write `name` into `dir` via a temp file + rename, so a crash never
+/// leaves a torn block that the re-index would adopt.
+async fn write_atomic(dir: &Path, name: &str, data: &[u8]) -> std::io::Result<()> {
+    tokio::fs::create_dir_all(dir).await?;
+    let tmp = dir.join(format!(".{name}.{}.tmp", uuid::Uuid::new_v4().simple()));
+    if let Err(e) = tokio::fs::write(&tmp, data).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e);
+    }
+    tokio::fs::rename(&tmp, dir.join(name)).await
+}
+
+struct ScannedEntry {
+    entry: Entry,
+    mtimes: Vec<(u64, SystemTime)>,
+}
+
+/// Rebuild the index from disk. Anything that doesn't form a consistent
+/// entry (no meta, hash mismatch, temp files, blocks past EOF, duplicate
+/// entries for one key) is deleted.
+fn scan_root_dir(root: &Path) -> (BTreeMap<String, ScannedEntry>, u64) {
+    let mut entries: BTreeMap<String, ScannedEntry> = BTreeMap::new();
+    let mut used = 0u64;
+    let Ok(shards) = std::fs::read_dir(root) else {
+        return (entries, used);
+    };
+    for shard in shards.flatten() {
+        if !shard.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let Ok(dirs) = std::fs::read_dir(shard.path()) else {
+            continue;
+        };
+        for dir in dirs.flatten() {
+            let path = dir.path();
+            match scan_entry_dir(&path) {
+                Some((key, scanned)) => {
+                    let replace = entries
+                        .get(&key)
+                        .is_none_or(|prev| prev.entry.created < scanned.entry.created);
+                    if replace {
+                        if let Some(prev) = entries.insert(key.clone(), scanned) {
+                            let h = key_hash(&key);
+                            let stale = shard.path().join(format!("{h}-{:016x}", prev.entry.id));
+                            let _ = std::fs::remove_dir_all(stale);
+                        }
+                    } else {
+                        let _ = std::fs::remove_dir_all(&path);
+                    }
+                }
+                None => {
+                    let _ = std::fs::remove_dir_all(&path);
+                }
+            }
+        }
+    }
+    for scanned in entries.values() {
+        used += scanned.entry.blocks.values().map(|b| b.len).sum::<u64>();
+    }
+    (entries, used)
+}
+
+fn scan_entry_dir(dir: &Path) -> Option<(String, ScannedEntry)> {
+    let name = dir.file_name()?.to_str()?;
+    let (hash, _) = name.split_once('-')?;
+    let meta: EntryMeta = serde_json::from_slice(&std::fs::read(dir.join(META_FILE)).ok()?).ok()?;
+    if key_hash(&meta.key) != hash || *name != format!("{hash}-{:016x}", meta.id) {
+        return None;
+    }
+    let mut blocks = BTreeMap::new();
+    let mut mtimes = Vec::new();
+    for file in std::fs::read_dir(dir).ok()?.flatten() {
+        let fname = file.file_name();
+        let fname = fname.to_string_lossy();
+        if fname == META_FILE {
+            continue;
+        }
+        let parsed = fname
+            .strip_suffix(&format!(".{BLOCK_EXT}"))
+            .and_then(|i| i.parse::<u64>().ok());
+        let (Some(idx), Ok(md)) = (parsed, file.metadata()) else {
+            let _ = std::fs::remove_file(file.path());
+            continue;
+        };
+        let len = md.len();
+        let end = idx * BLOCK_SIZE + len;
+        let valid = len > 0
+            && len <= BLOCK_SIZE
+            && match meta.size {
+                Some(size) => end == size || (end < size && len == BLOCK_SIZE),
+                None => len == BLOCK_SIZE,
+            };
+        if !valid {
+            let _ = std::fs::remove_file(file.path());
+            continue;
+        }
+        blocks.insert(idx, Block { len, tick: 0 });
+        mtimes.push((idx, md.modified().unwrap_or(UNIX_EPOCH)));
+    }
+    if blocks.is_empty() {
+        return None;
+    }
+    Some((
+        meta.key,
+        ScannedEntry {
+            entry: Entry {
+                id: meta.id,
+                size: meta.size,
+                created: meta.created,
+                blocks,
+            },
+            mtimes,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(max_bytes: u64) -> DiskCacheLimits {
+        DiskCacheLimits {
+            max_bytes,
+            max_object_bytes: max_bytes,
+            ttl: None,
+        }
+    }
+
+    fn pattern(len: usize, seed: u8) -> Vec<u8> {
+        (0..len)
+            .map(|i| (i as u8).wrapping_mul(31) ^ seed)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn whole_object_round_trip_and_reindex() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = pattern(2 * BLOCK_SIZE as usize + 123, 7);
+        {
+            let cache = DiskCache::open(dir.path().to_path_buf(), limits(64 << 20), None)
+                .await
+                .unwrap();
+            assert!(cache.read_whole("k").await.is_none());
+            cache.fill_whole(cache.begin_fill("k"), &data).await;
+            assert_eq!(cache.complete_size("k").await, Some(data.len() as u64));
+            assert_eq!(cache.read_whole("k").await.unwrap(), data);
+            assert_eq!(cache.stats().blocks, 3);
+        }
+        // Registry only holds weak refs: a fresh open re-indexes from disk.
+        let cache = DiskCache::open(dir.path().to_path_buf(), limits(64 << 20), None)
+            .await
+            .unwrap();
+        assert_eq!(cache.stats().used_bytes, data.len() as u64);
+        assert_eq!(cache.read_whole("k").await.unwrap(), data);
+    }
+
+    #[tokio::test]
+    async fn invalidation_poisons_in_flight_fill() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(dir.path().to_path_buf(), limits(64 << 20), None)
+            .await
+            .unwrap();
+        let mut fill = cache.begin_fill("k");
+        // A write lands between the backend fetch and the cache store.
+        cache.invalidate("k").await;
+        fill.store_block(0, Bytes::from(pattern(100, 1))).await;
+        fill.set_size(100).await;
+        drop(fill);
+        assert!(cache.read_whole("k").await.is_none());
+        assert_eq!(cache.stats().entries, 0);
+
+        // A fill registered after the invalidation is accepted.
+        cache
+            .fill_whole(cache.begin_fill("k"), &pattern(100, 2))
+            .await;
+        assert_eq!(cache.read_whole("k").await.unwrap(), pattern(100, 2));
+    }
+
+    #[tokio::test]
+    async fn lru_eviction_respects_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(dir.path().to_path_buf(), limits(3 * BLOCK_SIZE), None)
+            .await
+            .unwrap();
+        let block = pattern(BLOCK_SIZE as usize, 3);
+        for key in ["a", "b", "c"] {
+            cache.fill_whole(cache.begin_fill(key), &block).await;
+        }
+        // Touch "a" so "b" is the least recently used.
+        assert!(cache.read_whole("a").await.is_some());
+        cache.fill_whole(cache.begin_fill("d"), &block).await;
+
+        assert_eq!(cache.stats().used_bytes, 3 * BLOCK_SIZE);
+        assert!(cache.read_whole("a").await.is_some());
+        assert!(cache.read_whole("b").await.is_none());
+        assert!(cache.read_whole("d").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn partial_blocks_and_size_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::open(dir.path().to_path_buf(), limits(64 << 20), None)
+            .await
+            .unwrap();
+        let mut fill = cache.begin_fill("k");
+        fill.store_block(1, Bytes::from(pattern(BLOCK_SIZE as usize, 4)))
+            .await;
+        drop(fill);
+        let plan = cache.plan("k", 0, 3).await;
+        assert_eq!(plan.first_missing, Some(0));
+        let plan = cache.plan("k", 1, 1).await;
+        assert_eq!(plan.first_missing, None);
+        assert_eq!(plan.size, None);
+
+        // EOF inside block 0 contradicts the cached block 1.
+        let mut fill = cache.begin_fill("k");
+        fill.set_size(10).await;
+        drop(fill);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[tokio::test]
+    async fn oversized_objects_are_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut l = limits(64 << 20);
+        l.max_object_bytes = 1000;
+        let cache = DiskCache::open(dir.path().to_path_buf(), l, None)
+            .await
+            .unwrap();
+        cache
+            .fill_whole(cache.begin_fill("k"), &pattern(1001, 5))
+            .await;
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
@@ -51,8 +51,8 @@
 
 use super::io_to_storage_error;
 use super::traits::{
-    DelegatedListResult, LiteScanResult, MultipartUpload, StorageBackend, StorageError,
-    UploadedPart,
+    DelegatedListResult, LiteScanResult, MultipartUpload, StagedMultipartUpload, StagedPart,
+    StagedSweepReport, StorageBackend, StorageError, UploadedPart,
 };
 use crate::types::FileMetadata;
 use aes_gcm::aead::{Aead, Payload};
@@ -1508,6 +1508,63 @@ impl<B: StorageBackend + Send + Sync> StorageBackend for EncryptingBackend<B> {
             .await
     }
 
+    fn persists_multipart_uploads(&self, bucket: &str) -> bool {
+        // Staged parts would land on disk in plaintext.
+        if self.actively_encrypts() {
+            return false;
+        }
+        self.inner.persists_multipart_uploads(bucket)
+    }
+
+    async fn resume_multipart_upload(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        upload_id: &str,
+    ) -> Result<StagedMultipartUpload, StorageError> {
+        self.inner
+            .resume_multipart_upload(bucket, prefix, filename, upload_id)
+            .await
+    }
+
+    async fn list_multipart_parts(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<Vec<StagedPart>, StorageError> {
+        self.inner
+            .list_multipart_parts(upload, prefix, filename)
+            .await
+    }
+
+    async fn read_multipart_part(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+        part_number: i32,
+    ) -> Result<Bytes, StorageError> {
+        self.inner
+            .read_multipart_part(upload, prefix, filename, part_number)
+            .await
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        bucket: &str,
+    ) -> Result<Vec<StagedMultipartUpload>, StorageError> {
+        self.inner.list_multipart_uploads(bucket).await
+    }
+
+    async fn sweep_multipart_uploads(
+        &self,
+        idle: std::time::Duration,
+    ) -> Result<StagedSweepReport, StorageError> {
+        self.inner.sweep_multipart_uploads(idle).await
+    }
+
     fn multipart_storage_label(&self, bucket: &str) -> &'static str {
         if self.actively_encrypts() {
             return "aes256-gcm-proxy";
@@ -1533,6 +1590,14 @@ impl<B: StorageBackend + Send + Sync> StorageBackend for EncryptingBackend<B> {
         self.inner.lite_list_carries_logical_facts(bucket)
     }
 
+    fn stamps_encryption_key_id(&self, _bucket: &str) -> bool {
+        self.actively_encrypts() && self.current_key_id().is_some()
+    }
+
+    fn reserves_filename(&self, bucket: &str, filename: &str) -> bool {
+        self.inner.reserves_filename(bucket, filename)
+    }
+
     async fn get_passthrough_stream_range(
         &self,
         bucket: &str,
@@ -3106,6 +3171,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_only_stamped_encrypting_writes_report_stamps_key_id() {
+        // The delta fast path rewrites legacy source blobs in place only
+        // when this is true: a rewrite through any other wrapper would land
+        // as plaintext.
+        let wrapper = |key: Option<EncryptionKey>, key_id: Option<&str>, write_mode| {
+            let cfg = Arc::new(ArcSwap::new(Arc::new(EncryptionConfig {
+                key,
+                key_id: key_id.map(String::from),
+                write_mode,
+                ..Default::default()
+            })));
+            EncryptingBackend::new(CountingBackend::new(), cfg)
+        };
+        assert!(
+            wrapper(Some(test_key()), Some("current"), WriteMode::Encrypt)
+                .stamps_encryption_key_id("b")
+        );
+        assert!(
+            !wrapper(Some(test_key()), Some("current"), WriteMode::PassThrough)
+                .stamps_encryption_key_id("b")
+        );
+        assert!(!wrapper(Some(test_key()), None, WriteMode::Encrypt).stamps_encryption_key_id("b"));
+        assert!(!wrapper(None, None, WriteMode::Encrypt).stamps_encryption_key_id("b"));
+    }
+
     #[test]
     fn test_write_mode_encrypt_still_works_normally() {
         // The Encrypt default continues to encrypt + stamp markers.
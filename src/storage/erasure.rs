@@ -0,0 +1,1407 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Reed-Solomon erasure-coded storage across several backends.
+//!
+//! `ErasureBackend` (`type: erasure`) splits every stored blob — reference,
+//! delta or passthrough — into `k` data shards plus `m` parity shards and
+//! writes shard `i` to shard backend `i` under the same bucket and key. Any
+//! `k` shards rebuild the blob, so up to `m` shard backends may be lost. It
+//! sits below `EncryptingBackend` (shards of an encrypted backend hold
+//! ciphertext) and directly on the raw shard backends, which nothing else
+//! routes to.
+//!
+//! Every shard starts with a 24-byte header:
+//!
+//! ```text
+//! ["DGEC"] [version u8] [k u8] [m u8] [index u8] [blob length u64 LE] [generation u64 LE]
+//! ```
+//!
+//! The generation is the write's timestamp in nanoseconds. A read only
+//! combines shards of one generation, so a shard left behind by a degraded
+//! write is never mixed into a newer blob.
+//!
+//! - **Writes** go to every shard backend concurrently and are acknowledged
+//!   once `write_quorum` of them accept. Metadata is stored next to every
+//!   shard, stamped with the shard's generation.
+//! - **Reads** fetch enough shards to overlap every write quorum (at least
+//!   the `k` data shards); parity shards are fetched only when a data shard
+//!   is missing, unreadable or stale.
+//! - **Metadata reads and listings** ask at least `k` and at least
+//!   `k + m - write_quorum + 1` shard backends (the read quorum) and keep a
+//!   copy of the generation a read rebuilds: the newest one held by `k`
+//!   shards. A degraded write that reached fewer shards doesn't show in HEAD
+//!   while GET still returns the older blob.
+//! - [`ErasureBackend::repair_bucket`] rewrites missing and stale shards from
+//!   the surviving ones.
+//!
+//! Streaming reads decode the whole blob first, so a read holds the blob in
+//! memory — the same bound as the proxy-encrypted write path.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::join_all;
+use futures::stream::BoxStream;
+use reed_solomon_erasure::galois_8::ReedSolomon;
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use super::traits::{LiteScanResult, StorageBackend, StorageError};
+use crate::metrics::Metrics;
+use crate::types::{FileMetadata, StorageInfo};
+
+const MAGIC: &[u8; 4] = b"DGEC";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 24;
+
+/// Blobs left with fewer than `k` shards are removed by repair once they are
+/// this old; a younger one may belong to a write still in flight.
+const ORPHAN_GRACE: chrono::TimeDelta = chrono::TimeDelta::hours(1);
+
+/// User-metadata key stamping each shard's metadata with its generation.
+/// Never returned above this backend.
+const GENERATION_KEY: &str = "dg-ec-generation";
+
+fn stamped(metadata: &FileMetadata, generation: u64) -> FileMetadata {
+    let mut metadata = metadata.clone();
+    metadata
+        .user_metadata
+        .insert(GENERATION_KEY.to_string(), generation.to_string());
+    metadata
+}
+
+fn unstamped(mut metadata: FileMetadata) -> FileMetadata {
+    metadata.user_metadata.remove(GENERATION_KEY);
+    metadata
+}
+
+fn generation_of(metadata: &FileMetadata) -> Option<u64> {
+    metadata.user_metadata.get(GENERATION_KEY)?.parse().ok()
+}
+
+/// The copy describing what a read rebuilds: of the newest generation held
+/// by at least `k` copies, else of the newest generation seen; the newest
+/// `created_at` within it. Copies without a stamp (written before stamping,
+/// or from a listing that carries no user metadata) fall back to the newest
+/// `created_at` overall.
+fn pick_copy(copies: Vec<FileMetadata>, k: usize) -> Option<FileMetadata> {
+    let newest = |copies: Vec<FileMetadata>| copies.into_iter().max_by_key(|m| m.created_at);
+    let Some(generations) = copies.iter().map(generation_of).collect::<Option<Vec<_>>>() else {
+        return newest(copies).map(unstamped);
+    };
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for generation in &generations {
+        *counts.entry(*generation).or_default() += 1;
+    }
+    let chosen = counts
+        .iter()
+        .filter(|(_, n)| **n >= k)
+        .map(|(g, _)| *g)
+        .max()
+        .or_else(|| counts.keys().copied().max())?;
+    newest(
+        copies
+            .into_iter()
+            .zip(generations)
+            .filter(|(_, g)| *g == chosen)
+            .map(|(m, _)| m)
+            .collect(),
+    )
+    .map(unstamped)
+}
+
+/// Which stored file a shard belongs to.
+#[derive(Debug, Clone, Copy)]
+enum Blob<'a> {
+    Reference { prefix: &'a str },
+    Delta { prefix: &'a str, filename: &'a str },
+    Passthrough { prefix: &'a str, filename: &'a str },
+}
+
+impl<'a> Blob<'a> {
+    /// The blob a scanned metadata entry describes.
+    fn of(prefix: &'a str, meta: &'a FileMetadata) -> Self {
+        match meta.storage_info {
+            StorageInfo::Reference { .. } => Blob::Reference { prefix },
+            StorageInfo::Delta { .. } => Blob::Delta {
+                prefix,
+                filename: &meta.original_name,
+            },
+            StorageInfo::Passthrough => Blob::Passthrough {
+                prefix,
+                filename: &meta.original_name,
+            },
+        }
+    }
+
+    fn describe(self) -> String {
+        match self {
+            Blob::Reference { prefix } => format!("{prefix}/reference.bin"),
+            Blob::Delta { prefix, filename } => format!("{prefix}/{filename}.delta"),
+            Blob::Passthrough { prefix, filename } => format!("{prefix}/{filename}"),
+        }
+    }
+
+    async fn get(
+        self,
+        backend: &dyn StorageBackend,
+        bucket: &str,
+    ) -> Result<Vec<u8>, StorageError> {
+        match self {
+            Blob::Reference { prefix } => backend.get_reference(bucket, prefix).await,
+            Blob::Delta { prefix, filename } => backend.get_delta(bucket, prefix, filename).await,
+            Blob::Passthrough { prefix, filename } => {
+                backend.get_passthrough(bucket, prefix, filename).await
+            }
+        }
+    }
+
+    async fn put(
+        self,
+        backend: &dyn StorageBackend,
+        bucket: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        match self {
+            Blob::Reference { prefix } => {
+                backend.put_reference(bucket, prefix, data, metadata).await
+            }
+            Blob::Delta { prefix, filename } => {
+                backend
+                    .put_delta(bucket, prefix, filename, data, metadata)
+                    .await
+            }
+            Blob::Passthrough { prefix, filename } => {
+                backend
+                    .put_passthrough(bucket, prefix, filename, data, metadata)
+                    .await
+            }
+        }
+    }
+
+    async fn delete(self, backend: &dyn StorageBackend, bucket: &str) -> Result<(), StorageError> {
+        match self {
+            Blob::Reference { prefix } => backend.delete_reference(bucket, prefix).await,
+            Blob::Delta { prefix, filename } => {
+                backend.delete_delta(bucket, prefix, filename).await
+            }
+            Blob::Passthrough { prefix, filename } => {
+                backend.delete_passthrough(bucket, prefix, filename).await
+            }
+        }
+    }
+
+    async fn metadata(
+        self,
+        backend: &dyn StorageBackend,
+        bucket: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        match self {
+            Blob::Reference { prefix } => backend.get_reference_metadata(bucket, prefix).await,
+            Blob::Delta { prefix, filename } => {
+                backend.get_delta_metadata(bucket, prefix, filename).await
+            }
+            Blob::Passthrough { prefix, filename } => {
+                backend
+                    .get_passthrough_metadata(bucket, prefix, filename)
+                    .await
+            }
+        }
+    }
+}
+
+/// One parsed shard.
+struct Shard {
+    len: u64,
+    generation: u64,
+    payload: Vec<u8>,
+}
+
+/// Shards gathered for one blob, indexed by shard number.
+struct Gathered {
+    slots: Vec<Option<Shard>>,
+    not_found: usize,
+    error: Option<StorageError>,
+}
+
+/// Metadata answers collected from shard backends.
+#[derive(Default)]
+struct MetadataVote {
+    copies: Vec<FileMetadata>,
+    not_found: usize,
+    error: Option<StorageError>,
+}
+
+impl MetadataVote {
+    fn absorb(&mut self, result: Result<FileMetadata, StorageError>) {
+        match result {
+            Ok(meta) => self.copies.push(meta),
+            Err(StorageError::NotFound(_)) => self.not_found += 1,
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+    }
+
+    /// Does the newest generation answered have `k` copies, so no further
+    /// answer can change [`pick_copy`]'s choice? Unstamped copies settle it.
+    fn settled(&self, k: usize) -> bool {
+        let generations: Option<Vec<u64>> = self.copies.iter().map(generation_of).collect();
+        let Some(generations) = generations else {
+            return true;
+        };
+        generations
+            .iter()
+            .max()
+            .is_some_and(|newest| generations.iter().filter(|g| *g == newest).count() >= k)
+    }
+}
+
+/// A blob rebuilt from its shards.
+struct Rebuilt {
+    len: u64,
+    generation: u64,
+    /// Every shard payload (`full`) or just the data shards.
+    shards: Vec<Vec<u8>>,
+}
+
+/// What a repair pass did.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ErasureRepairReport {
+    pub buckets: u64,
+    pub blobs_scanned: u64,
+    pub shards_repaired: u64,
+    /// Blobs with fewer than `k` shards, still inside the orphan grace.
+    pub unrecoverable: u64,
+    /// Blobs with fewer than `k` shards, older than the grace, removed.
+    pub orphans_removed: u64,
+    /// Shard writes or deletes that failed; the next pass retries them.
+    pub failed: u64,
+}
+
+/// Erasure-coding wrapper over `k + m` shard backends. See the module docs.
+/// Cloning shares the same shard backends.
+#[derive(Clone)]
+pub struct ErasureBackend {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    name: String,
+    shards: Vec<(String, Arc<dyn StorageBackend>)>,
+    data_shards: usize,
+    parity_shards: usize,
+    write_quorum: usize,
+    codec: ReedSolomon,
+    metrics: Option<Arc<Metrics>>,
+}
+
+fn codec_error(e: reed_solomon_erasure::Error) -> StorageError {
+    StorageError::Other(format!("erasure coding failed: {e:?}"))
+}
+
+impl ErasureBackend {
+    /// # Errors
+    /// Returns an error when the shard count doesn't match `k + m`, the
+    /// layout is out of range, or `write_quorum` is outside `k..=k+m`.
+    pub fn new(
+        name: impl Into<String>,
+        shards: Vec<(String, Arc<dyn StorageBackend>)>,
+        data_shards: u8,
+        parity_shards: u8,
+        write_quorum: Option<u8>,
+        metrics: Option<Arc<Metrics>>,
+    ) -> Result<Self, StorageError> {
+        let name = name.into();
+        let (k, m) = (usize::from(data_shards), usize::from(parity_shards));
+        if shards.len() != k + m {
+            return Err(StorageError::Other(format!(
+                "erasure backend '{name}' lists {} shard backends for {k}+{m} shards",
+                shards.len()
+            )));
+        }
+        let codec = ReedSolomon::new(k, m).map_err(|e| {
+            StorageError::Other(format!(
+                "erasure backend '{name}': invalid layout {k}+{m}: {e:?}"
+            ))
+        })?;
+        let write_quorum = write_quorum.map_or(k + m, usize::from);
+        if !(k..=k + m).contains(&write_quorum) {
+            return Err(StorageError::Other(format!(
+                "erasure backend '{name}': write_quorum {write_quorum} is outside {k}..={}",
+                k + m
+            )));
+        }
+        Ok(Self {
+            inner: Arc::new(Inner {
+                name,
+                shards,
+                data_shards: k,
+                parity_shards: m,
+                write_quorum,
+                codec,
+                metrics,
+            }),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// Shard backend names, in shard order.
+    pub fn shard_backends(&self) -> Vec<&str> {
+        self.inner.shards.iter().map(|(n, _)| n.as_str()).collect()
+    }
+
+    fn total(&self) -> usize {
+        self.inner.data_shards + self.inner.parity_shards
+    }
+
+    /// Shard backends a read must reach to overlap every write quorum.
+    fn read_quorum(&self) -> usize {
+        self.total() - self.inner.write_quorum + 1
+    }
+
+    fn shard(&self, idx: usize) -> &dyn StorageBackend {
+        self.inner.shards[idx].1.as_ref()
+    }
+
+    // === Shard fan-out ===
+
+    /// Settle a write fanned out to every shard backend. `absent_ok` treats
+    /// NotFound as done (deletes); an error every shard agrees on is returned
+    /// as is.
+    fn settle_write(
+        &self,
+        what: &str,
+        results: Vec<Result<(), StorageError>>,
+        absent_ok: bool,
+    ) -> Result<(), StorageError> {
+        let mut acked = 0;
+        let mut absent = 0;
+        let mut first_err = None;
+        for result in results {
+            match result {
+                Ok(()) => acked += 1,
+                Err(StorageError::NotFound(_) | StorageError::BucketNotFound(_)) if absent_ok => {
+                    absent += 1;
+                }
+                Err(e) => {
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+        if absent == self.total() {
+            return Err(StorageError::NotFound(what.to_string()));
+        }
+        if acked + absent >= self.inner.write_quorum {
+            if acked + absent < self.total() {
+                warn!(
+                    "erasure '{}': {what} acknowledged by {} of {} shard backends: {}",
+                    self.inner.name,
+                    acked + absent,
+                    self.total(),
+                    first_err.map(|e| e.to_string()).unwrap_or_default()
+                );
+                if let Some(m) = &self.inner.metrics {
+                    m.erasure_degraded_writes_total
+                        .with_label_values(&[&self.inner.name])
+                        .inc();
+                }
+            }
+            return Ok(());
+        }
+        Err(first_err.unwrap_or_else(|| {
+            StorageError::Other(format!(
+                "erasure '{}': {what} reached {acked} of {} required shard backends",
+                self.inner.name, self.inner.write_quorum
+            ))
+        }))
+    }
+
+    /// Fetch shards `range` of `blob` into `gathered`.
+    async fn gather(
+        &self,
+        bucket: &str,
+        blob: Blob<'_>,
+        range: std::ops::Range<usize>,
+        gathered: &mut Gathered,
+    ) {
+        let start = range.start;
+        let results = join_all(range.map(|i| blob.get(self.shard(i), bucket))).await;
+        for (offset, result) in results.into_iter().enumerate() {
+            let idx = start + offset;
+            match result {
+                Ok(raw) => match self.inner.parse_shard(idx, raw) {
+                    Some(shard) => gathered.slots[idx] = Some(shard),
+                    None => debug!(
+                        "erasure '{}': shard {idx} of {} is malformed",
+                        self.inner.name,
+                        blob.describe()
+                    ),
+                },
+                Err(StorageError::NotFound(_)) => gathered.not_found += 1,
+                Err(e) => {
+                    gathered.error.get_or_insert(e);
+                }
+            }
+        }
+    }
+
+    /// Read every shard of `blob`, or just enough of them when `all` is
+    /// false: the read-quorum wave first, the rest only when that wave
+    /// can't give a consistent newest generation.
+    async fn gather_blob(&self, bucket: &str, blob: Blob<'_>, all: bool) -> Gathered {
+        let mut gathered = Gathered {
+            slots: (0..self.total()).map(|_| None).collect(),
+            not_found: 0,
+            error: None,
+        };
+        let first = if all {
+            self.total()
+        } else {
+            self.inner.data_shards.max(self.read_quorum())
+        };
+        self.gather(bucket, blob, 0..first, &mut gathered).await;
+        if first < self.total() && !self.inner.data_complete(&gathered.slots) {
+            self.gather(bucket, blob, first..self.total(), &mut gathered)
+                .await;
+        }
+        gathered
+    }
+
+    fn unreadable(&self, blob: Blob<'_>, gathered: Gathered) -> StorageError {
+        // More than m shards definitively absent: the blob can't exist.
+        if gathered.not_found > self.inner.parity_shards || gathered.error.is_none() {
+            return StorageError::NotFound(blob.describe());
+        }
+        let found = gathered.slots.iter().flatten().count();
+        StorageError::Other(format!(
+            "erasure '{}': {} is unreadable — {found} of {} required shards available: {}",
+            self.inner.name,
+            blob.describe(),
+            self.inner.data_shards,
+            gathered.error.map(|e| e.to_string()).unwrap_or_default()
+        ))
+    }
+
+    async fn read_blob(&self, bucket: &str, blob: Blob<'_>) -> Result<Vec<u8>, StorageError> {
+        let gathered = self.gather_blob(bucket, blob, false).await;
+        let degraded = !self.inner.data_complete(&gathered.slots);
+        let inner = Arc::clone(&self.inner);
+        let slots = gathered.slots;
+        let (rebuilt, slots) = tokio::task::spawn_blocking(move || {
+            let rebuilt = inner.rebuild(&slots, false);
+            (rebuilt, slots)
+        })
+        .await
+        .map_err(super::join_error)?;
+        let Some(rebuilt) = rebuilt? else {
+            return Err(self.unreadable(
+                blob,
+                Gathered {
+                    slots,
+                    not_found: gathered.not_found,
+                    error: gathered.error,
+                },
+            ));
+        };
+        if degraded {
+            debug!(
+                "erasure '{}': rebuilt {} from parity",
+                self.inner.name,
+                blob.describe()
+            );
+            if let Some(m) = &self.inner.metrics {
+                m.erasure_degraded_reads_total
+                    .with_label_values(&[&self.inner.name])
+                    .inc();
+            }
+        }
+        let mut data = Vec::with_capacity(rebuilt.len as usize);
+        for shard in rebuilt.shards {
+            data.extend_from_slice(&shard);
+        }
+        data.truncate(rebuilt.len as usize);
+        Ok(data)
+    }
+
+    async fn write_blob(
+        &self,
+        bucket: &str,
+        blob: Blob<'_>,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let generation = chrono::Utc::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default()
+            .max(0) as u64;
+        let inner = Arc::clone(&self.inner);
+        let owned = data.to_vec();
+        let shards = tokio::task::spawn_blocking(move || inner.encode(&owned, generation))
+            .await
+            .map_err(super::join_error)??;
+        let metadata = stamped(metadata, generation);
+        let results = join_all(
+            shards
+                .iter()
+                .enumerate()
+                .map(|(i, shard)| blob.put(self.shard(i), bucket, shard, &metadata)),
+        )
+        .await;
+        self.settle_write(&blob.describe(), results, false)
+    }
+
+    async fn delete_blob(&self, bucket: &str, blob: Blob<'_>) -> Result<(), StorageError> {
+        let results = join_all((0..self.total()).map(|i| blob.delete(self.shard(i), bucket))).await;
+        self.settle_write(&blob.describe(), results, true)
+    }
+
+    /// Metadata of the generation a read rebuilds (see [`pick_copy`]).
+    /// The read quorum, and at least `k` shard backends, are asked
+    /// concurrently; while that falls short of the quorum or of `k` copies
+    /// of the newest generation (a shard backend is down, lost its shard or
+    /// holds a degraded write) further shard backends are asked one by one.
+    /// Absence is conclusive once more than `m` shard backends report it —
+    /// a stored blob is missing from at most `m`.
+    async fn read_metadata(
+        &self,
+        bucket: &str,
+        blob: Blob<'_>,
+    ) -> Result<FileMetadata, StorageError> {
+        let quorum = self.read_quorum();
+        let k = self.inner.data_shards;
+        let first = quorum.max(k);
+        let absent = self.inner.parity_shards + 1;
+        let mut vote = MetadataVote::default();
+        for result in join_all((0..first).map(|i| blob.metadata(self.shard(i), bucket))).await {
+            vote.absorb(result);
+        }
+        for next in first..self.total() {
+            if (vote.copies.len() >= quorum && vote.settled(k)) || vote.not_found >= absent {
+                break;
+            }
+            vote.absorb(blob.metadata(self.shard(next), bucket).await);
+        }
+        if let Some(meta) = pick_copy(vote.copies, k) {
+            return Ok(meta);
+        }
+        match vote.error {
+            Some(e) if vote.not_found < absent => Err(e),
+            _ => Err(StorageError::NotFound(blob.describe())),
+        }
+    }
+
+    /// Run `op` on every shard backend; fail unless the read quorum answered.
+    async fn read_all<'s, T, F, Fut>(&'s self, op: F) -> Result<Vec<T>, StorageError>
+    where
+        F: Fn(&'s dyn StorageBackend) -> Fut,
+        Fut: std::future::Future<Output = Result<T, StorageError>>,
+    {
+        let results = join_all((0..self.total()).map(|i| op(self.shard(i)))).await;
+        let mut out = Vec::with_capacity(results.len());
+        let mut first_err = None;
+        for result in results {
+            match result {
+                Ok(v) => out.push(v),
+                Err(e) => {
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+        if out.len() < self.read_quorum() {
+            return Err(first_err.unwrap_or_else(|| {
+                StorageError::Other(format!("erasure '{}': no shard answered", self.inner.name))
+            }));
+        }
+        Ok(out)
+    }
+
+    // === Repair ===
+
+    /// Rewrite missing and stale shards of every bucket.
+    ///
+    /// # Errors
+    /// Returns an error when the bucket list can't be read.
+    pub async fn repair(&self) -> Result<ErasureRepairReport, StorageError> {
+        let mut report = ErasureRepairReport::default();
+        for bucket in self.list_buckets().await? {
+            self.repair_bucket(&bucket, &mut report).await?;
+        }
+        Ok(report)
+    }
+
+    /// Rewrite missing and stale shards of one bucket. Blobs whose shard
+    /// backends all hold the same copy are not read.
+    ///
+    /// # Errors
+    /// Returns an error when fewer than the read quorum of shard backends
+    /// can list the bucket.
+    pub async fn repair_bucket(
+        &self,
+        bucket: &str,
+        report: &mut ErasureRepairReport,
+    ) -> Result<(), StorageError> {
+        report.buckets += 1;
+        for idx in 0..self.total() {
+            if matches!(self.shard(idx).head_bucket(bucket).await, Ok(false)) {
+                match self.shard(idx).create_bucket(bucket).await {
+                    Ok(()) | Err(StorageError::AlreadyExists(_)) => {}
+                    Err(_) => report.failed += 1,
+                }
+            }
+        }
+        let mut prefixes: BTreeSet<String> = BTreeSet::from([String::new()]);
+        for listed in self.read_all(|b| b.list_deltaspaces(bucket)).await? {
+            prefixes.extend(listed);
+        }
+        for prefix in &prefixes {
+            let scans =
+                join_all((0..self.total()).map(|i| self.shard(i).scan_deltaspace(bucket, prefix)))
+                    .await;
+            let reachable: Vec<bool> = scans.iter().map(Result::is_ok).collect();
+            // (kind, name) → per-shard metadata.
+            let mut blobs: HashMap<(&str, String), Vec<Option<FileMetadata>>> = HashMap::new();
+            for (idx, scan) in scans.into_iter().enumerate() {
+                for meta in scan.unwrap_or_default() {
+                    let key = (meta.storage_info.label(), meta.original_name.clone());
+                    blobs.entry(key).or_insert_with(|| vec![None; self.total()])[idx] = Some(meta);
+                }
+            }
+            for copies in blobs.into_values() {
+                report.blobs_scanned += 1;
+                let present: Vec<FileMetadata> = copies.iter().flatten().cloned().collect();
+                let in_sync = copies
+                    .iter()
+                    .zip(&reachable)
+                    .all(|(c, up)| *up && c.is_some())
+                    && present.windows(2).all(|w| {
+                        w[0].created_at == w[1].created_at
+                            && generation_of(&w[0]) == generation_of(&w[1])
+                    });
+                let Some(newest) = pick_copy(present, self.inner.data_shards) else {
+                    continue;
+                };
+                if !in_sync {
+                    self.repair_blob(bucket, Blob::of(prefix, &newest), &newest, report)
+                        .await;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn repair_blob(
+        &self,
+        bucket: &str,
+        blob: Blob<'_>,
+        metadata: &FileMetadata,
+        report: &mut ErasureRepairReport,
+    ) {
+        let gathered = self.gather_blob(bucket, blob, true).await;
+        let inner = Arc::clone(&self.inner);
+        let slots = gathered.slots;
+        let joined = tokio::task::spawn_blocking(move || {
+            let rebuilt = inner.rebuild(&slots, true);
+            (rebuilt, slots)
+        })
+        .await;
+        let Ok((Ok(rebuilt), slots)) = joined else {
+            report.failed += 1;
+            return;
+        };
+        let Some(rebuilt) = rebuilt else {
+            if chrono::Utc::now() - metadata.created_at > ORPHAN_GRACE {
+                warn!(
+                    "erasure '{}': removing unrecoverable {}/{}",
+                    self.inner.name,
+                    bucket,
+                    blob.describe()
+                );
+                match self.delete_blob(bucket, blob).await {
+                    Ok(()) | Err(StorageError::NotFound(_)) => report.orphans_removed += 1,
+                    Err(_) => report.failed += 1,
+                }
+            } else {
+                report.unrecoverable += 1;
+            }
+            return;
+        };
+        let metadata = stamped(metadata, rebuilt.generation);
+        for (idx, slot) in slots.iter().enumerate() {
+            if slot
+                .as_ref()
+                .is_some_and(|s| s.generation == rebuilt.generation)
+            {
+                continue;
+            }
+            let mut shard = Vec::with_capacity(HEADER_LEN + rebuilt.shards[idx].len());
+            shard.extend_from_slice(&self.inner.header(idx, rebuilt.len, rebuilt.generation));
+            shard.extend_from_slice(&rebuilt.shards[idx]);
+            match blob.put(self.shard(idx), bucket, &shard, &metadata).await {
+                Ok(()) => {
+                    report.shards_repaired += 1;
+                    if let Some(m) = &self.inner.metrics {
+                        m.erasure_shards_repaired_total
+                            .with_label_values(&[&self.inner.name])
+                            .inc();
+                    }
+                }
+                Err(e) => {
+                    debug!(
+                        "erasure '{}': repair of shard {idx} of {} failed: {e}",
+                        self.inner.name,
+                        blob.describe()
+                    );
+                    report.failed += 1;
+                }
+            }
+        }
+    }
+}
+
+impl Inner {
+    fn total(&self) -> usize {
+        self.data_shards + self.parity_shards
+    }
+
+    fn shard_len(&self, blob_len: u64) -> usize {
+        (blob_len as usize).div_ceil(self.data_shards).max(1)
+    }
+
+    fn header(&self, index: usize, len: u64, generation: u64) -> [u8; HEADER_LEN] {
+        let mut header = [0u8; HEADER_LEN];
+        header[..4].copy_from_slice(MAGIC);
+        header[4] = VERSION;
+        header[5] = self.data_shards as u8;
+        header[6] = self.parity_shards as u8;
+        header[7] = index as u8;
+        header[8..16].copy_from_slice(&len.to_le_bytes());
+        header[16..24].copy_from_slice(&generation.to_le_bytes());
+        header
+    }
+
+    /// Validate a stored shard against this layout and its position.
+    fn parse_shard(&self, index: usize, mut raw: Vec<u8>) -> Option<Shard> {
+        if raw.len() < HEADER_LEN
+            || &raw[..4] != MAGIC
+            || raw[4] != VERSION
+            || usize::from(raw[5]) != self.data_shards
+            || usize::from(raw[6]) != self.parity_shards
+            || usize::from(raw[7]) != index
+        {
+            return None;
+        }
+        let len = u64::from_le_bytes(raw[8..16].try_into().ok()?);
+        let generation = u64::from_le_bytes(raw[16..24].try_into().ok()?);
+        if raw.len() - HEADER_LEN != self.shard_len(len) {
+            return None;
+        }
+        raw.drain(..HEADER_LEN);
+        Some(Shard {
+            len,
+            generation,
+            payload: raw,
+        })
+    }
+
+    /// Split `data` into `k + m` headed shards.
+    fn encode(&self, data: &[u8], generation: u64) -> Result<Vec<Vec<u8>>, StorageError> {
+        let len = data.len() as u64;
+        let shard_len = self.shard_len(len);
+        let mut shards: Vec<Vec<u8>> = (0..self.total()).map(|_| vec![0u8; shard_len]).collect();
+        for (shard, chunk) in shards.iter_mut().zip(data.chunks(shard_len)) {
+            shard[..chunk.len()].copy_from_slice(chunk);
+        }
+        self.codec.encode(&mut shards).map_err(codec_error)?;
+        Ok(shards
+            .into_iter()
+            .enumerate()
+            .map(|(idx, payload)| {
+                let mut shard = Vec::with_capacity(HEADER_LEN + shard_len);
+                shard.extend_from_slice(&self.header(idx, len, generation));
+                shard.extend_from_slice(&payload);
+                shard
+            })
+            .collect())
+    }
+
+    /// The newest generation present among `slots`.
+    fn newest_generation(slots: &[Option<Shard>]) -> Option<u64> {
+        slots.iter().flatten().map(|s| s.generation).max()
+    }
+
+    /// Are the data shards all present and of the newest generation seen?
+    fn data_complete(&self, slots: &[Option<Shard>]) -> bool {
+        let Some(newest) = Self::newest_generation(slots) else {
+            return false;
+        };
+        slots[..self.data_shards]
+            .iter()
+            .all(|s| s.as_ref().is_some_and(|s| s.generation == newest))
+    }
+
+    /// Rebuild the newest generation that has at least `k` shards. `full`
+    /// also regenerates the parity shards (repair); otherwise only the data
+    /// shards are returned. `Ok(None)` when no generation has `k` shards.
+    fn rebuild(
+        &self,
+        slots: &[Option<Shard>],
+        full: bool,
+    ) -> Result<Option<Rebuilt>, StorageError> {
+        let mut counts: HashMap<(u64, u64), usize> = HashMap::new();
+        for shard in slots.iter().flatten() {
+            *counts.entry((shard.generation, shard.len)).or_default() += 1;
+        }
+        let Some((generation, len)) = counts
+            .into_iter()
+            .filter(|(_, n)| *n >= self.data_shards)
+            .map(|(key, _)| key)
+            .max()
+        else {
+            return Ok(None);
+        };
+        let mut work: Vec<Option<Vec<u8>>> = slots
+            .iter()
+            .map(|s| {
+                s.as_ref()
+                    .filter(|s| s.generation == generation && s.len == len)
+                    .map(|s| s.payload.clone())
+            })
+            .collect();
+        if full {
+            self.codec.reconstruct(&mut work).map_err(codec_error)?;
+        } else {
+            self.codec
+                .reconstruct_data(&mut work)
+                .map_err(codec_error)?;
+            work.truncate(self.data_shards);
+        }
+        Ok(Some(Rebuilt {
+            len,
+            generation,
+            shards: work.into_iter().map(Option::unwrap_or_default).collect(),
+        }))
+    }
+}
+
+/// Merge per-shard listings: sorted by key, one [`pick_copy`] per key.
+fn merge_listings(
+    listings: Vec<Vec<(String, FileMetadata)>>,
+    k: usize,
+) -> Vec<(String, FileMetadata)> {
+    let mut copies: HashMap<String, Vec<FileMetadata>> = HashMap::new();
+    for (key, meta) in listings.into_iter().flatten() {
+        copies.entry(key).or_default().push(meta);
+    }
+    let mut out: Vec<_> = copies
+        .into_iter()
+        .filter_map(|(key, copies)| Some((key, pick_copy(copies, k)?)))
+        .collect();
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
+/// Merge per-shard deltaspace scans: one [`pick_copy`] per stored file.
+fn merge_scans(scans: Vec<Vec<FileMetadata>>, k: usize) -> Vec<FileMetadata> {
+    let mut copies: HashMap<(&'static str, String), Vec<FileMetadata>> = HashMap::new();
+    for meta in scans.into_iter().flatten() {
+        let key = (meta.storage_info.label(), meta.original_name.clone());
+        copies.entry(key).or_default().push(meta);
+    }
+    copies
+        .into_values()
+        .filter_map(|copies| pick_copy(copies, k))
+        .collect()
+}
+
+#[async_trait]
+impl StorageBackend for ErasureBackend {
+    async fn create_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        let results =
+            join_all((0..self.total()).map(|i| self.shard(i).create_bucket(bucket))).await;
+        if results
+            .iter()
+            .all(|r| matches!(r, Err(StorageError::AlreadyExists(_))))
+        {
+            return Err(StorageError::AlreadyExists(bucket.to_string()));
+        }
+        let results = results
+            .into_iter()
+            .map(|r| match r {
+                Err(StorageError::AlreadyExists(_)) => Ok(()),
+                other => other,
+            })
+            .collect();
+        self.settle_write(&format!("create bucket {bucket}"), results, false)
+    }
+
+    async fn ensure_declared_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        let results =
+            join_all((0..self.total()).map(|i| self.shard(i).ensure_declared_bucket(bucket))).await;
+        self.settle_write(&format!("declare bucket {bucket}"), results, false)
+    }
+
+    async fn delete_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        let results =
+            join_all((0..self.total()).map(|i| self.shard(i).delete_bucket(bucket))).await;
+        if let Some(Err(e)) = results
+            .iter()
+            .find(|r| matches!(r, Err(StorageError::BucketNotEmpty(_))))
+        {
+            return Err(StorageError::BucketNotEmpty(e.to_string()));
+        }
+        match self.settle_write(&format!("delete bucket {bucket}"), results, true) {
+            Err(StorageError::NotFound(_)) => Err(StorageError::BucketNotFound(bucket.to_string())),
+            other => other,
+        }
+    }
+
+    async fn list_buckets(&self) -> Result<Vec<String>, StorageError> {
+        let mut names: BTreeSet<String> = BTreeSet::new();
+        for listed in self.read_all(|b| b.list_buckets()).await? {
+            names.extend(listed);
+        }
+        Ok(names.into_iter().collect())
+    }
+
+    async fn head_bucket(&self, bucket: &str) -> Result<bool, StorageError> {
+        let answers = self.read_all(|b| b.head_bucket(bucket)).await?;
+        Ok(answers.into_iter().any(|exists| exists))
+    }
+
+    async fn get_reference(&self, bucket: &str, prefix: &str) -> Result<Vec<u8>, StorageError> {
+        self.read_blob(bucket, Blob::Reference { prefix }).await
+    }
+
+    async fn put_reference(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        self.write_blob(bucket, Blob::Reference { prefix }, data, metadata)
+            .await
+    }
+
+    async fn put_reference_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let results = join_all((0..self.total()).map(|i| {
+            self.shard(i)
+                .put_reference_metadata(bucket, prefix, metadata)
+        }))
+        .await;
+        self.settle_write(&Blob::Reference { prefix }.describe(), results, false)
+    }
+
+    async fn put_passthrough_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let results = join_all((0..self.total()).map(|i| {
+            self.shard(i)
+                .put_passthrough_metadata(bucket, prefix, filename, metadata)
+        }))
+        .await;
+        self.settle_write(
+            &Blob::Passthrough { prefix, filename }.describe(),
+            results,
+            false,
+        )
+    }
+
+    async fn get_reference_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.read_metadata(bucket, Blob::Reference { prefix }).await
+    }
+
+    async fn has_reference(&self, bucket: &str, prefix: &str) -> Result<bool, StorageError> {
+        match self.read_metadata(bucket, Blob::Reference { prefix }).await {
+            Ok(_) => Ok(true),
+            Err(StorageError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn delete_reference(&self, bucket: &str, prefix: &str) -> Result<(), StorageError> {
+        self.delete_blob(bucket, Blob::Reference { prefix }).await
+    }
+
+    async fn get_delta(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_blob(bucket, Blob::Delta { prefix, filename })
+            .await
+    }
+
+    async fn put_delta(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        self.write_blob(bucket, Blob::Delta { prefix, filename }, data, metadata)
+            .await
+    }
+
+    async fn get_delta_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.read_metadata(bucket, Blob::Delta { prefix, filename })
+            .await
+    }
+
+    async fn delete_delta(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<(), StorageError> {
+        self.delete_blob(bucket, Blob::Delta { prefix, filename })
+            .await
+    }
+
+    async fn get_passthrough(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.read_blob(bucket, Blob::Passthrough { prefix, filename })
+            .await
+    }
+
+    async fn put_passthrough(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        self.write_blob(
+            bucket,
+            Blob::Passthrough { prefix, filename },
+            data,
+            metadata,
+        )
+        .await
+    }
+
+    async fn get_passthrough_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.read_metadata(bucket, Blob::Passthrough { prefix, filename })
+            .await
+    }
+
+    async fn delete_passthrough(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<(), StorageError> {
+        self.delete_blob(bucket, Blob::Passthrough { prefix, filename })
+            .await
+    }
+
+    /// Decodes the whole blob, then streams it (see the module docs).
+    async fn get_passthrough_stream(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<BoxStream<'static, Result<Bytes, StorageError>>, StorageError> {
+        let data = self.get_passthrough(bucket, prefix, filename).await?;
+        Ok(Box::pin(futures::stream::once(async move {
+            Ok(Bytes::from(data))
+        })))
+    }
+
+    /// Decodes the whole blob, then serves the range (see the module docs).
+    async fn get_passthrough_stream_range(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError> {
+        let data = Bytes::from(self.get_passthrough(bucket, prefix, filename).await?);
+        let len = data.len() as u64;
+        let from = start.min(len) as usize;
+        let to = end.saturating_add(1).min(len) as usize;
+        let slice = data.slice(from..to.max(from));
+        let slice_len = slice.len() as u64;
+        Ok((
+            Box::pin(futures::stream::once(async move { Ok(slice) })),
+            slice_len,
+        ))
+    }
+
+    fn lite_list_carries_logical_facts(&self, bucket: &str) -> bool {
+        // Lite listings of shard backends that report stored sizes would
+        // report shard sizes.
+        self.inner
+            .shards
+            .iter()
+            .all(|(_, b)| b.lite_list_carries_logical_facts(bucket))
+    }
+
+    fn reserves_filename(&self, bucket: &str, filename: &str) -> bool {
+        self.inner
+            .shards
+            .iter()
+            .any(|(_, b)| b.reserves_filename(bucket, filename))
+    }
+
+    async fn scan_deltaspace(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<FileMetadata>, StorageError> {
+        Ok(merge_scans(
+            self.read_all(|b| b.scan_deltaspace(bucket, prefix)).await?,
+            self.inner.data_shards,
+        ))
+    }
+
+    async fn scan_deltaspace_lite(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<LiteScanResult, StorageError> {
+        let scans = self
+            .read_all(|b| b.scan_deltaspace_lite(bucket, prefix))
+            .await?;
+        let originals_estimated = scans.iter().any(|s| s.originals_estimated);
+        Ok(LiteScanResult {
+            metadata: merge_scans(
+                scans.into_iter().map(|s| s.metadata).collect(),
+                self.inner.data_shards,
+            ),
+            originals_estimated,
+        })
+    }
+
+    async fn list_deltaspaces(&self, bucket: &str) -> Result<Vec<String>, StorageError> {
+        let mut prefixes: BTreeSet<String> = BTreeSet::new();
+        for listed in self.read_all(|b| b.list_deltaspaces(bucket)).await? {
+            prefixes.extend(listed);
+        }
+        Ok(prefixes.into_iter().collect())
+    }
+
+    /// Stored bytes across every reachable shard backend, parity included.
+    async fn total_size(&self, bucket: Option<&str>) -> Result<u64, StorageError> {
+        Ok(self
+            .read_all(|b| b.total_size(bucket))
+            .await?
+            .into_iter()
+            .sum())
+    }
+
+    async fn put_directory_marker(&self, bucket: &str, key: &str) -> Result<(), StorageError> {
+        let results =
+            join_all((0..self.total()).map(|i| self.shard(i).put_directory_marker(bucket, key)))
+                .await;
+        self.settle_write(key, results, false)
+    }
+
+    async fn bulk_list_objects(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<(String, FileMetadata)>, StorageError> {
+        Ok(merge_listings(
+            self.read_all(|b| b.bulk_list_objects(bucket, prefix))
+                .await?,
+            self.inner.data_shards,
+        ))
+    }
+
+    async fn enrich_list_metadata(
+        &self,
+        bucket: &str,
+        objects: Vec<(String, FileMetadata)>,
+    ) -> Result<Vec<(String, FileMetadata)>, StorageError> {
+        let mut last_err = None;
+        for (_, backend) in &self.inner.shards {
+            match backend.enrich_list_metadata(bucket, objects.clone()).await {
+                Ok(enriched) => {
+                    return Ok(enriched
+                        .into_iter()
+                        .map(|(key, meta)| (key, unstamped(meta)))
+                        .collect())
+                }
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| StorageError::Other("no shard backends".to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inner(k: u8, m: u8) -> Inner {
+        Inner {
+            name: "ec".into(),
+            shards: Vec::new(),
+            data_shards: k.into(),
+            parity_shards: m.into(),
+            write_quorum: usize::from(k + m),
+            codec: ReedSolomon::new(k.into(), m.into()).unwrap(),
+            metrics: None,
+        }
+    }
+
+    fn parse_all(inner: &Inner, shards: Vec<Vec<u8>>) -> Vec<Option<Shard>> {
+        shards
+            .into_iter()
+            .enumerate()
+            .map(|(i, raw)| inner.parse_shard(i, raw))
+            .collect()
+    }
+
+    fn decode(inner: &Inner, slots: &[Option<Shard>]) -> Option<Vec<u8>> {
+        let rebuilt = inner.rebuild(slots, false).unwrap()?;
+        let mut data: Vec<u8> = rebuilt.shards.concat();
+        data.truncate(rebuilt.len as usize);
+        Some(data)
+    }
+
+    #[test]
+    fn any_k_shards_rebuild_the_blob() {
+        let inner = inner(3, 2);
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let shards = inner.encode(&data, 1).unwrap();
+        assert_eq!(shards.len(), 5);
+        for lost in [[0, 1], [1, 4], [3, 4], [0, 2]] {
+            let mut slots = parse_all(&inner, shards.clone());
+            for idx in lost {
+                slots[idx] = None;
+            }
+            assert_eq!(
+                decode(&inner, &slots).as_deref(),
+                Some(&data[..]),
+                "{lost:?}"
+            );
+        }
+        let mut slots = parse_all(&inner, shards);
+        slots[0] = None;
+        slots[1] = None;
+        slots[2] = None;
+        assert!(decode(&inner, &slots).is_none(), "two shards can't rebuild");
+    }
+
+    #[test]
+    fn empty_blob_round_trips() {
+        let inner = inner(2, 1);
+        let slots = parse_all(&inner, inner.encode(b"", 1).unwrap());
+        assert_eq!(decode(&inner, &slots).as_deref(), Some(&b""[..]));
+    }
+
+    #[test]
+    fn stale_shards_are_not_mixed_into_a_newer_generation() {
+        let inner = inner(2, 2);
+        let old = inner.encode(b"old contents", 1).unwrap();
+        let new = inner.encode(b"new contents!", 2).unwrap();
+        // Shard 0 missed the newer write.
+        let mixed = vec![
+            old[0].clone(),
+            new[1].clone(),
+            new[2].clone(),
+            new[3].clone(),
+        ];
+        let slots = parse_all(&inner, mixed);
+        assert!(!inner.data_complete(&slots));
+        assert_eq!(
+            decode(&inner, &slots).as_deref(),
+            Some(&b"new contents!"[..])
+        );
+    }
+
+    #[tokio::test]
+    async fn head_reports_the_generation_get_rebuilds() {
+        let dirs: Vec<_> = (0..3).map(|_| tempfile::tempdir().unwrap()).collect();
+        let mut shards: Vec<(String, Arc<dyn StorageBackend>)> = Vec::new();
+        for (i, dir) in dirs.iter().enumerate() {
+            let backend = crate::storage::FilesystemBackend::new(dir.path().to_path_buf())
+                .await
+                .unwrap();
+            backend.create_bucket("b").await.unwrap();
+            shards.push((format!("s{i}"), Arc::new(backend)));
+        }
+        let ec = ErasureBackend::new("ec", shards, 2, 1, Some(2), None).unwrap();
+        let meta = |size| {
+            FileMetadata::new_passthrough("f.txt".into(), "sha".into(), "md5".into(), size, None)
+        };
+        ec.put_passthrough("b", "p", "f.txt", b"old contents", &meta(12))
+            .await
+            .unwrap();
+
+        // A newer write that reached a single shard: too few to rebuild.
+        let newer = meta(13);
+        let generation = newer.created_at.timestamp_nanos_opt().unwrap() as u64;
+        let shard = ec.inner.encode(b"new contents!", generation).unwrap();
+        ec.shard(0)
+            .put_passthrough("b", "p", "f.txt", &shard[0], &stamped(&newer, generation))
+            .await
+            .unwrap();
+
+        let head = ec
+            .get_passthrough_metadata("b", "p", "f.txt")
+            .await
+            .unwrap();
+        assert_eq!(head.file_size, 12);
+        assert!(!head.user_metadata.contains_key(GENERATION_KEY));
+        assert_eq!(
+            ec.get_passthrough("b", "p", "f.txt").await.unwrap(),
+            b"old contents"
+        );
+        let listed = ec.bulk_list_objects("b", "").await.unwrap();
+        assert_eq!(listed[0].1.file_size, 12);
+    }
+
+    #[test]
+    fn shards_from_another_layout_or_position_are_rejected() {
+        let inner = inner(2, 1);
+        let shards = inner.encode(b"payload", 1).unwrap();
+        assert!(inner.parse_shard(1, shards[0].clone()).is_none());
+        assert!(self::inner(3, 1)
+            .parse_shard(0, shards[0].clone())
+            .is_none());
+        let mut truncated = shards[0].clone();
+        truncated.pop();
+        assert!(inner.parse_shard(0, truncated).is_none());
+    }
+}
@@ -1,9 +1,14 @@
 // SPDX-License-Identifier: BUSL-1.1
 
-//! Filesystem-based storage backend with xattr-based metadata
-
-use super::traits::{DelegatedListResult, StorageBackend, StorageError};
-use super::xattr_meta;
+//! Filesystem-based storage backend with xattr-based (or sidecar-file)
+//! metadata
+
+use super::traits::{
+    DelegatedListResult, MultipartUpload, StagedMultipartUpload, StagedPart, StagedSweepReport,
+    StorageBackend, StorageError, UploadedPart,
+};
+use super::{fs_multipart, sidecar_meta, xattr_meta};
+use crate::config::FilesystemMetadataMode;
 use crate::types::FileMetadata;
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -14,7 +19,7 @@ use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 use tokio::fs;
 use tokio_util::io::ReaderStream;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 /// Async-safe path existence check (avoids blocking the Tokio runtime)
 async fn path_exists(path: &Path) -> bool {
@@ -31,11 +36,64 @@ async fn is_dir(path: &Path) -> bool {
 
 use super::io_to_storage_error;
 
-/// Atomically write data + metadata to a file using write-to-temp + xattr + fsync + rename.
+/// Read a data file's metadata in the backend's mode.
 ///
-/// The xattr is written to the temp file BEFORE the rename, so a crash can never
+/// Returns `StorageError::NotFound` if the file carries none.
+async fn read_metadata(
+    mode: FilesystemMetadataMode,
+    path: &Path,
+) -> Result<FileMetadata, StorageError> {
+    match mode {
+        FilesystemMetadataMode::Xattr => xattr_meta::read_metadata(path).await,
+        FilesystemMetadataMode::Sidecar => sidecar_meta::read_metadata(path).await,
+    }
+}
+
+/// Replace an existing data file's metadata, leaving its bytes untouched.
+async fn write_metadata(
+    mode: FilesystemMetadataMode,
+    path: &Path,
+    metadata: &FileMetadata,
+) -> Result<(), StorageError> {
+    match mode {
+        FilesystemMetadataMode::Xattr => xattr_meta::write_metadata(path, metadata).await,
+        FilesystemMetadataMode::Sidecar => sidecar_meta::write_metadata(path, metadata).await,
+    }
+}
+
+/// Attach `metadata` to a fully written temp file, fsync it and rename it
+/// to `target`. Blocking.
+///
+/// xattr mode sets the attribute on the temp file BEFORE the rename; sidecar
+/// mode records an entry for it before the rename (see `sidecar_meta`).
+/// Either way a crash can never leave a data file without its metadata.
+fn persist_with_metadata(
+    mode: FilesystemMetadataMode,
+    tmp: NamedTempFile,
+    target: &Path,
+    metadata: Option<&FileMetadata>,
+) -> Result<(), StorageError> {
+    if let (FilesystemMetadataMode::Xattr, Some(meta)) = (mode, metadata) {
+        let json = serde_json::to_vec(meta)?;
+        xattr::set(tmp.path(), xattr_meta::XATTR_NAME, &json).map_err(io_to_storage_error)?;
+    }
+    tmp.as_file().sync_all().map_err(io_to_storage_error)?;
+    match (mode, metadata) {
+        (FilesystemMetadataMode::Sidecar, Some(meta)) => sidecar_meta::persist(tmp, target, meta),
+        _ => {
+            tmp.persist(target)
+                .map_err(|e| io_to_storage_error(e.error))?;
+            Ok(())
+        }
+    }
+}
+
+/// Atomically write data + metadata to a file using write-to-temp + metadata + fsync + rename.
+///
+/// The metadata is recorded for the temp file BEFORE the rename, so a crash can never
 /// leave a data file without its metadata. Either both are visible or neither is.
 async fn atomic_write_with_metadata(
+    mode: FilesystemMetadataMode,
     path: &Path,
     data: &[u8],
     metadata: Option<&FileMetadata>,
@@ -46,19 +104,12 @@ async fn atomic_write_with_metadata(
         .to_path_buf();
     let path = path.to_path_buf();
     let data = data.to_vec();
-    let meta_json = metadata.map(serde_json::to_vec).transpose()?;
+    let metadata = metadata.cloned();
 
     tokio::task::spawn_blocking(move || {
         let mut tmp = NamedTempFile::new_in(&parent).map_err(io_to_storage_error)?;
         tmp.write_all(&data).map_err(io_to_storage_error)?;
-        // Write xattr to temp file BEFORE rename — atomic metadata+data visibility.
-        if let Some(json) = &meta_json {
-            xattr::set(tmp.path(), xattr_meta::XATTR_NAME, json).map_err(io_to_storage_error)?;
-        }
-        tmp.as_file().sync_all().map_err(io_to_storage_error)?;
-        tmp.persist(&path)
-            .map_err(|e| io_to_storage_error(e.error))?;
-        Ok(())
+        persist_with_metadata(mode, tmp, &path, metadata.as_ref())
     })
     .await
     .map_err(super::join_error)?
@@ -79,6 +130,7 @@ async fn hardlink_or_copy(src: &Path, dest: &Path) -> Result<(), StorageError> {
 
 /// Atomically copy file data + metadata to destination using temp + rename.
 async fn atomic_copy_with_metadata(
+    mode: FilesystemMetadataMode,
     source_path: &Path,
     target_path: &Path,
     metadata: &FileMetadata,
@@ -89,17 +141,13 @@ async fn atomic_copy_with_metadata(
         .to_path_buf();
     let source = source_path.to_path_buf();
     let target = target_path.to_path_buf();
-    let meta_json = serde_json::to_vec(metadata)?;
+    let metadata = metadata.clone();
 
     tokio::task::spawn_blocking(move || {
         let mut src = std::fs::File::open(&source).map_err(io_to_storage_error)?;
         let mut tmp = NamedTempFile::new_in(&parent).map_err(io_to_storage_error)?;
         std::io::copy(&mut src, &mut tmp).map_err(io_to_storage_error)?;
-        xattr::set(tmp.path(), xattr_meta::XATTR_NAME, &meta_json).map_err(io_to_storage_error)?;
-        tmp.as_file().sync_all().map_err(io_to_storage_error)?;
-        tmp.persist(&target)
-            .map_err(|e| io_to_storage_error(e.error))?;
-        Ok(())
+        persist_with_metadata(mode, tmp, &target, Some(&metadata))
     })
     .await
     .map_err(super::join_error)?
@@ -152,12 +200,16 @@ pub(crate) fn synthesise_unmanaged_etag(
 /// ```
 ///
 /// Metadata is stored as a `user.dg.metadata` extended attribute on each
-/// data file's inode — no sidecar `.meta` files needed.
+/// data file's inode — no sidecar `.meta` files needed. With
+/// `metadata: sidecar` it lives in a hidden `.dgmeta.{name}` file next to
+/// each data file instead, for filesystems without xattr support.
 ///
 /// Each bucket is a real subdirectory under the root.
 pub struct FilesystemBackend {
     /// Root directory for all data
     root: PathBuf,
+    /// Where per-file metadata is kept
+    mode: FilesystemMetadataMode,
 }
 
 impl FilesystemBackend {
@@ -165,13 +217,27 @@ impl FilesystemBackend {
     ///
     /// Validates xattr support at startup.
     pub async fn new(root: PathBuf) -> Result<Self, StorageError> {
+        Self::with_metadata_mode(root, FilesystemMetadataMode::Xattr).await
+    }
+
+    /// Create a filesystem backend keeping metadata in `mode`. xattr mode
+    /// validates xattr support; sidecar mode validates mtime resolution.
+    pub async fn with_metadata_mode(
+        root: PathBuf,
+        mode: FilesystemMetadataMode,
+    ) -> Result<Self, StorageError> {
         // Ensure root directory exists
         fs::create_dir_all(&root).await?;
 
-        // Validate that the filesystem supports xattrs
-        xattr_meta::validate_xattr_support(&root).await?;
+        // Validate that the filesystem can hold metadata in this mode
+        match mode {
+            FilesystemMetadataMode::Xattr => xattr_meta::validate_xattr_support(&root).await?,
+            FilesystemMetadataMode::Sidecar => {
+                sidecar_meta::validate_mtime_resolution(&root).await?
+            }
+        }
 
-        Ok(Self { root })
+        Ok(Self { root, mode })
     }
 
     /// Get the bucket directory
@@ -482,7 +548,10 @@ impl FilesystemBackend {
                 } else if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
                     // Any data file (reference, delta, or passthrough with original name)
                     // indicates this directory is an active deltaspace.
-                    if name == "reference.bin" || name.ends_with(".delta") || !name.starts_with('.')
+                    if (name == "reference.bin"
+                        || name.ends_with(".delta")
+                        || !name.starts_with('.'))
+                        && sidecar_meta::data_name(name).is_none()
                     {
                         has_deltaglider_files = true;
                     }
@@ -499,9 +568,10 @@ impl FilesystemBackend {
         })
     }
 
-    /// Recursively walk directories, reading xattr metadata for each data file
+    /// Recursively walk directories, reading metadata for each data file
     /// and producing (user_visible_key, FileMetadata) pairs in a single pass.
     fn bulk_walk_recursive<'a>(
+        mode: FilesystemMetadataMode,
         deltaspaces_dir: &'a Path,
         current_dir: &'a Path,
         results: &'a mut Vec<(String, FileMetadata)>,
@@ -513,7 +583,7 @@ impl FilesystemBackend {
                 let path = entry.path();
                 let ft = entry.file_type().await?;
                 if ft.is_dir() {
-                    Self::bulk_walk_recursive(deltaspaces_dir, &path, results).await?;
+                    Self::bulk_walk_recursive(mode, deltaspaces_dir, &path, results).await?;
                     continue;
                 }
 
@@ -527,8 +597,8 @@ impl FilesystemBackend {
                     continue;
                 }
 
-                // Read xattr metadata, falling back to filesystem stats for unmanaged files
-                let meta = match xattr_meta::read_metadata(&path).await {
+                // Read metadata, falling back to filesystem stats for unmanaged files
+                let meta = match read_metadata(mode, &path).await {
                     Ok(m) => m,
                     Err(StorageError::NotFound(_)) => {
                         match Self::fallback_metadata_from_path(&path, &name).await {
@@ -540,7 +610,7 @@ impl FilesystemBackend {
                         }
                     }
                     Err(e) => {
-                        debug!("Error reading xattr for {:?}: {}", path, e);
+                        debug!("Error reading metadata for {:?}: {}", path, e);
                         continue;
                     }
                 };
@@ -609,7 +679,7 @@ impl FilesystemBackend {
         filename: &str,
     ) -> Result<(), StorageError> {
         self.ensure_dir(bucket, data_path).await?;
-        atomic_write_with_metadata(data_path, data, Some(metadata)).await?;
+        atomic_write_with_metadata(self.mode, data_path, data, Some(metadata)).await?;
         debug!(
             "Wrote {} ({} bytes) for {}/{}",
             label,
@@ -641,6 +711,12 @@ impl FilesystemBackend {
                 StorageError::from(e)
             }
         })?;
+        if self.mode == FilesystemMetadataMode::Sidecar {
+            let data_path = data_path.to_path_buf();
+            tokio::task::spawn_blocking(move || sidecar_meta::remove_metadata(&data_path))
+                .await
+                .map_err(super::join_error)??;
+        }
         if let Some(parent) = data_path.parent() {
             Self::prune_empty_dirs(parent, prune_root).await?;
         }
@@ -854,7 +930,7 @@ impl StorageBackend for FilesystemBackend {
             fs::create_dir_all(parent).await?;
         }
         hardlink_or_copy(source_path, &dest).await?;
-        xattr_meta::write_metadata(&dest, metadata).await
+        write_metadata(self.mode, &dest, metadata).await
     }
 
     async fn put_reference_metadata(
@@ -864,7 +940,7 @@ impl StorageBackend for FilesystemBackend {
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
         self.require_bucket_exists(bucket).await?;
-        xattr_meta::write_metadata(&self.reference_path(bucket, prefix), metadata).await
+        write_metadata(self.mode, &self.reference_path(bucket, prefix), metadata).await
     }
 
     async fn put_passthrough_metadata(
@@ -881,9 +957,10 @@ impl StorageBackend for FilesystemBackend {
                 "{bucket}/{prefix}/{filename}"
             )));
         }
-        // xattr write only — bytes and mtime untouched (setxattr changes
-        // ctime, not mtime), so the served LastModified is stable.
-        xattr_meta::write_metadata(&path, metadata).await
+        // Metadata write only — bytes and mtime untouched (setxattr changes
+        // ctime, not mtime; a sidecar is a separate file), so the served
+        // LastModified is stable.
+        write_metadata(self.mode, &path, metadata).await
     }
 
     #[instrument(skip(self))]
@@ -893,10 +970,10 @@ impl StorageBackend for FilesystemBackend {
         prefix: &str,
     ) -> Result<FileMetadata, StorageError> {
         let path = self.reference_path(bucket, prefix);
-        match xattr_meta::read_metadata(&path).await {
+        match read_metadata(self.mode, &path).await {
             Ok(meta) => Ok(meta),
             Err(StorageError::NotFound(_)) => {
-                // No xattr metadata — fall back to filesystem stats if the file exists.
+                // No metadata — fall back to filesystem stats if the file exists.
                 Self::fallback_metadata_from_path(&path, "reference.bin").await
             }
             Err(other) => Err(other),
@@ -969,10 +1046,10 @@ impl StorageBackend for FilesystemBackend {
         filename: &str,
     ) -> Result<FileMetadata, StorageError> {
         let path = self.delta_path(bucket, prefix, filename);
-        match xattr_meta::read_metadata(&path).await {
+        match read_metadata(self.mode, &path).await {
             Ok(meta) => Ok(meta),
             Err(StorageError::NotFound(_)) => {
-                // No xattr metadata — fall back to filesystem stats if the file exists.
+                // No metadata — fall back to filesystem stats if the file exists.
                 Self::fallback_metadata_from_path(&path, filename).await
             }
             Err(other) => Err(other),
@@ -1048,7 +1125,7 @@ impl StorageBackend for FilesystemBackend {
         self.require_bucket_exists(bucket).await?;
         let data_path = self.passthrough_path(bucket, prefix, filename);
         self.ensure_dir(bucket, &data_path).await?;
-        atomic_copy_with_metadata(source_path, &data_path, metadata).await?;
+        atomic_copy_with_metadata(self.mode, source_path, &data_path, metadata).await?;
         debug!(
             "Copied passthrough file {:?} -> {:?} for {}/{}",
             source_path, data_path, prefix, filename
@@ -1074,7 +1151,8 @@ impl StorageBackend for FilesystemBackend {
             .to_path_buf();
         let target = data_path.clone();
         let parts: Vec<PathBuf> = part_paths.to_vec();
-        let meta_json = serde_json::to_vec(metadata)?;
+        let metadata = metadata.clone();
+        let mode = self.mode;
 
         tokio::task::spawn_blocking(move || {
             let mut tmp = NamedTempFile::new_in(&parent).map_err(io_to_storage_error)?;
@@ -1082,12 +1160,7 @@ impl StorageBackend for FilesystemBackend {
                 let mut src = std::fs::File::open(path).map_err(io_to_storage_error)?;
                 std::io::copy(&mut src, &mut tmp).map_err(io_to_storage_error)?;
             }
-            xattr::set(tmp.path(), xattr_meta::XATTR_NAME, &meta_json)
-                .map_err(io_to_storage_error)?;
-            tmp.as_file().sync_all().map_err(io_to_storage_error)?;
-            tmp.persist(&target)
-                .map_err(|e| io_to_storage_error(e.error))?;
-            Ok(())
+            persist_with_metadata(mode, tmp, &target, Some(&metadata))
         })
         .await
         .map_err(super::join_error)?
@@ -1101,10 +1174,10 @@ impl StorageBackend for FilesystemBackend {
         filename: &str,
     ) -> Result<FileMetadata, StorageError> {
         let path = self.passthrough_path(bucket, prefix, filename);
-        match xattr_meta::read_metadata(&path).await {
+        match read_metadata(self.mode, &path).await {
             Ok(meta) => Ok(meta),
             Err(StorageError::NotFound(_)) => {
-                // No xattr metadata — file may exist without DG metadata (unmanaged).
+                // No metadata — file may exist without DG metadata (unmanaged).
                 // Fall back to filesystem stats if the file exists.
                 Self::fallback_metadata_from_path(&path, filename).await
             }
@@ -1154,20 +1227,16 @@ impl StorageBackend for FilesystemBackend {
         let target = data_path.clone();
         let chunks: Vec<Bytes> = chunks.to_vec();
         let num_chunks = chunks.len();
-        let meta_json = serde_json::to_vec(metadata)?;
+        let metadata = metadata.clone();
+        let mode = self.mode;
 
         tokio::task::spawn_blocking(move || -> Result<(), StorageError> {
             let mut tmp = NamedTempFile::new_in(&parent).map_err(io_to_storage_error)?;
             for chunk in &chunks {
                 tmp.write_all(chunk).map_err(io_to_storage_error)?;
             }
-            // Write xattr before rename — atomic metadata+data visibility.
-            xattr::set(tmp.path(), xattr_meta::XATTR_NAME, &meta_json)
-                .map_err(io_to_storage_error)?;
-            tmp.as_file().sync_all().map_err(io_to_storage_error)?;
-            tmp.persist(&target)
-                .map_err(|e| io_to_storage_error(e.error))?;
-            Ok(())
+            // Metadata before rename — atomic metadata+data visibility.
+            persist_with_metadata(mode, tmp, &target, Some(&metadata))
         })
         .await
         .map_err(super::join_error)??;
@@ -1241,6 +1310,198 @@ impl StorageBackend for FilesystemBackend {
         Ok((Box::pin(stream), range_len))
     }
 
+    // === Multipart upload (on-disk staging, see `fs_multipart`) ===
+
+    fn supports_native_multipart(&self, _bucket: &str) -> bool {
+        true
+    }
+
+    fn persists_multipart_uploads(&self, _bucket: &str) -> bool {
+        true
+    }
+
+    fn reserves_filename(&self, _bucket: &str, filename: &str) -> bool {
+        self.mode == FilesystemMetadataMode::Sidecar
+            && filename.starts_with(sidecar_meta::SIDECAR_PREFIX)
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn create_multipart_upload(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        metadata: &FileMetadata,
+    ) -> Result<MultipartUpload, StorageError> {
+        self.require_bucket_exists(bucket).await?;
+        let bucket_dir = self.bucket_dir(bucket);
+        let (prefix_owned, filename_owned) = (prefix.to_string(), filename.to_string());
+        let metadata = metadata.clone();
+        let upload_id = tokio::task::spawn_blocking(move || {
+            fs_multipart::create(&bucket_dir, &prefix_owned, &filename_owned, &metadata)
+        })
+        .await
+        .map_err(super::join_error)??;
+        debug!(
+            "Staged multipart upload {} for {}/{}/{}",
+            upload_id, bucket, prefix, filename
+        );
+        Ok(MultipartUpload {
+            bucket: bucket.to_string(),
+            upload_id,
+            native: true,
+            backend: None,
+        })
+    }
+
+    #[instrument(skip(self, upload, data))]
+    async fn upload_part(
+        &self,
+        upload: &MultipartUpload,
+        _prefix: &str,
+        _filename: &str,
+        part_number: i32,
+        data: Bytes,
+    ) -> Result<UploadedPart, StorageError> {
+        let bucket_dir = self.bucket_dir(&upload.bucket);
+        let upload_id = upload.upload_id.clone();
+        tokio::task::spawn_blocking(move || {
+            fs_multipart::write_part(&bucket_dir, &upload_id, part_number, &data)
+        })
+        .await
+        .map_err(super::join_error)?
+    }
+
+    /// Concatenate the staged parts next to the target and rename the result
+    /// into place with `metadata` (whole-object hashes filled in when empty).
+    /// Returns the S3-style multipart ETag.
+    #[instrument(skip(self, upload, parts, _assembled, metadata))]
+    async fn complete_multipart_upload(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+        parts: &[UploadedPart],
+        _assembled: &[Bytes],
+        metadata: &FileMetadata,
+    ) -> Result<String, StorageError> {
+        self.require_bucket_exists(&upload.bucket).await?;
+        let data_path = self.passthrough_path(&upload.bucket, prefix, filename);
+        self.ensure_dir(&upload.bucket, &data_path).await?;
+        let parent = data_path
+            .parent()
+            .ok_or_else(|| StorageError::Other("Cannot write to a path with no parent".into()))?
+            .to_path_buf();
+        let bucket_dir = self.bucket_dir(&upload.bucket);
+        let upload_id = upload.upload_id.clone();
+        let parts = parts.to_vec();
+        let metadata = metadata.clone();
+        let mode = self.mode;
+        tokio::task::spawn_blocking(move || {
+            let (tmp, metadata, etag) =
+                fs_multipart::assemble(&bucket_dir, &upload_id, &parts, &parent, &metadata)?;
+            persist_with_metadata(mode, tmp, &data_path, Some(&metadata))?;
+            // The object is in place; leftover staging is only disk space
+            // and the sweep reclaims it.
+            if let Err(e) = fs_multipart::remove(&bucket_dir, &upload_id) {
+                warn!("Failed to remove staging for multipart upload {upload_id}: {e}");
+            }
+            Ok(etag)
+        })
+        .await
+        .map_err(super::join_error)?
+    }
+
+    #[instrument(skip(self, upload))]
+    async fn abort_multipart_upload(
+        &self,
+        upload: &MultipartUpload,
+        _prefix: &str,
+        _filename: &str,
+    ) -> Result<(), StorageError> {
+        let bucket_dir = self.bucket_dir(&upload.bucket);
+        let upload_id = upload.upload_id.clone();
+        tokio::task::spawn_blocking(move || fs_multipart::remove(&bucket_dir, &upload_id))
+            .await
+            .map_err(super::join_error)?
+    }
+
+    async fn resume_multipart_upload(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        upload_id: &str,
+    ) -> Result<StagedMultipartUpload, StorageError> {
+        let bucket_dir = self.bucket_dir(bucket);
+        let (bucket, prefix, filename, upload_id) = (
+            bucket.to_string(),
+            prefix.to_string(),
+            filename.to_string(),
+            upload_id.to_string(),
+        );
+        tokio::task::spawn_blocking(move || {
+            fs_multipart::resume(&bucket_dir, &bucket, &prefix, &filename, &upload_id)
+        })
+        .await
+        .map_err(super::join_error)?
+    }
+
+    async fn list_multipart_parts(
+        &self,
+        upload: &MultipartUpload,
+        _prefix: &str,
+        _filename: &str,
+    ) -> Result<Vec<StagedPart>, StorageError> {
+        let bucket_dir = self.bucket_dir(&upload.bucket);
+        let upload_id = upload.upload_id.clone();
+        tokio::task::spawn_blocking(move || fs_multipart::list_parts(&bucket_dir, &upload_id))
+            .await
+            .map_err(super::join_error)?
+    }
+
+    async fn read_multipart_part(
+        &self,
+        upload: &MultipartUpload,
+        _prefix: &str,
+        _filename: &str,
+        part_number: i32,
+    ) -> Result<Bytes, StorageError> {
+        let bucket_dir = self.bucket_dir(&upload.bucket);
+        let upload_id = upload.upload_id.clone();
+        let data = tokio::task::spawn_blocking(move || {
+            fs_multipart::read_part(&bucket_dir, &upload_id, part_number)
+        })
+        .await
+        .map_err(super::join_error)??;
+        Ok(Bytes::from(data))
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        bucket: &str,
+    ) -> Result<Vec<StagedMultipartUpload>, StorageError> {
+        let bucket_dir = self.bucket_dir(bucket);
+        let bucket = bucket.to_string();
+        tokio::task::spawn_blocking(move || fs_multipart::list_uploads(&bucket_dir, &bucket))
+            .await
+            .map_err(super::join_error)?
+    }
+
+    async fn sweep_multipart_uploads(
+        &self,
+        idle: std::time::Duration,
+    ) -> Result<StagedSweepReport, StorageError> {
+        let mut report = StagedSweepReport::default();
+        for bucket in self.list_buckets().await? {
+            let bucket_dir = self.bucket_dir(&bucket);
+            report += tokio::task::spawn_blocking(move || fs_multipart::sweep(&bucket_dir, idle))
+                .await
+                .map_err(super::join_error)??;
+        }
+        Ok(report)
+    }
+
     // === Scanning operations ===
 
     #[instrument(skip(self))]
@@ -1262,20 +1523,22 @@ impl StorageBackend for FilesystemBackend {
 
             if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
                 // Match data files: reference.bin, *.delta, or passthrough files (any other file)
-                let is_data_file =
-                    name == "reference.bin" || name.ends_with(".delta") || !name.starts_with('.'); // passthrough files have original names
+                let is_data_file = (name == "reference.bin"
+                    || name.ends_with(".delta")
+                    || !name.starts_with('.')) // passthrough files have original names
+                    && sidecar_meta::data_name(name).is_none();
 
                 if is_data_file {
-                    match xattr_meta::read_metadata(&path).await {
+                    match read_metadata(self.mode, &path).await {
                         Ok(meta) => metadata_list.push(meta),
                         Err(StorageError::NotFound(_)) => {
-                            // No xattr — try filesystem stats for unmanaged files
+                            // No metadata — try filesystem stats for unmanaged files
                             if let Ok(meta) = Self::fallback_metadata_from_path(&path, name).await {
                                 metadata_list.push(meta);
                             }
                         }
                         Err(e) => {
-                            debug!("Error reading xattr for {:?}: {}", path, e);
+                            debug!("Error reading metadata for {:?}: {}", path, e);
                         }
                     }
                 }
@@ -1330,7 +1593,7 @@ impl StorageBackend for FilesystemBackend {
         }
 
         let mut results: Vec<(String, FileMetadata)> = Vec::new();
-        Self::bulk_walk_recursive(&deltaspaces_dir, &walk_root, &mut results).await?;
+        Self::bulk_walk_recursive(self.mode, &deltaspaces_dir, &walk_root, &mut results).await?;
 
         debug!(
             "Bulk listed {} objects in {}/{}",
@@ -1501,7 +1764,7 @@ impl StorageBackend for FilesystemBackend {
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or_default();
-            let meta = match xattr_meta::read_metadata(&path).await {
+            let meta = match read_metadata(self.mode, &path).await {
                 Ok(m) => m,
                 Err(StorageError::NotFound(_)) => {
                     match Self::fallback_metadata_from_path(&path, filename).await {
@@ -1516,7 +1779,10 @@ impl StorageBackend for FilesystemBackend {
                     }
                 }
                 Err(e) => {
-                    debug!("Skipping {:?} in delegated list (xattr error): {}", path, e);
+                    debug!(
+                        "Skipping {:?} in delegated list (metadata read error): {}",
+                        path, e
+                    );
                     continue;
                 }
             };
@@ -0,0 +1,491 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! On-disk multipart staging for the filesystem backend.
+//!
+//! An upload lives in `{bucket}/.dg-multipart/{upload_id}/`: an
+//! `upload.json` manifest written at create (target key, create-time
+//! metadata, initiation time) plus one `part-NNNNN.{md5}` file per part,
+//! each written to a temp file, fsynced and renamed into place. The
+//! directory sits outside `deltaspaces/`, so it is never listed, and it
+//! outlives the process: an upload that was neither completed nor aborted
+//! is found again by scanning the directory.
+//!
+//! Completion concatenates the parts into a temp file next to the target,
+//! hashing as it copies, and renames it into place with its metadata like
+//! any other write. Memory stays at one copy buffer whatever the size.
+//!
+//! All functions here are blocking.
+
+use super::io_to_storage_error;
+use super::traits::{
+    multipart_etag, MultipartUpload, StagedMultipartUpload, StagedPart, StagedSweepReport,
+    StorageError, UploadedPart,
+};
+use crate::types::FileMetadata;
+use chrono::{DateTime, Utc};
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tempfile::NamedTempFile;
+
+/// Staging directory under each bucket root. Dot-prefixed and outside
+/// `deltaspaces/`: never part of the object view, and pruned with the
+/// bucket's other internal residue on DeleteBucket.
+pub(crate) const STAGING_DIR: &str = ".dg-multipart";
+
+const MANIFEST: &str = "upload.json";
+const PART_PREFIX: &str = "part-";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    prefix: String,
+    filename: String,
+    metadata: FileMetadata,
+    initiated: DateTime<Utc>,
+}
+
+fn not_found(upload_id: &str) -> StorageError {
+    StorageError::NotFound(format!("multipart upload {upload_id}"))
+}
+
+/// Upload ids are 32 lowercase hex chars. Anything else is refused before
+/// it is joined onto a path — the id comes straight from the client.
+fn is_upload_id(id: &str) -> bool {
+    id.len() == 32
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+fn upload_dir(bucket_dir: &Path, upload_id: &str) -> PathBuf {
+    bucket_dir.join(STAGING_DIR).join(upload_id)
+}
+
+fn part_name(part_number: i32, md5: &str) -> String {
+    format!("{PART_PREFIX}{part_number:05}.{md5}")
+}
+
+/// `(part_number, md5)` of a part file name.
+fn parse_part_name(name: &str) -> Option<(i32, &str)> {
+    let (number, md5) = name.strip_prefix(PART_PREFIX)?.split_once('.')?;
+    Some((number.parse().ok()?, md5))
+}
+
+fn mtime(meta: &std::fs::Metadata) -> DateTime<Utc> {
+    meta.modified()
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now())
+}
+
+fn staged(bucket: &str, upload_id: &str, manifest: Manifest) -> StagedMultipartUpload {
+    StagedMultipartUpload {
+        upload: MultipartUpload {
+            bucket: bucket.to_string(),
+            upload_id: upload_id.to_string(),
+            native: true,
+            backend: None,
+        },
+        prefix: manifest.prefix,
+        filename: manifest.filename,
+        metadata: manifest.metadata,
+        initiated: manifest.initiated,
+    }
+}
+
+fn read_manifest(dir: &Path) -> Result<Manifest, StorageError> {
+    let bytes = std::fs::read(dir.join(MANIFEST)).map_err(io_to_storage_error)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Write `bytes` to `dir/name` via temp + fsync + rename.
+fn write_durable(dir: &Path, name: &str, bytes: &[u8]) -> Result<(), StorageError> {
+    let mut tmp = NamedTempFile::new_in(dir).map_err(io_to_storage_error)?;
+    tmp.write_all(bytes).map_err(io_to_storage_error)?;
+    tmp.as_file().sync_all().map_err(io_to_storage_error)?;
+    tmp.persist(dir.join(name))
+        .map_err(|e| io_to_storage_error(e.error))?;
+    Ok(())
+}
+
+fn remove_dir(dir: &Path) -> Result<(), StorageError> {
+    match std::fs::remove_dir_all(dir) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(io_to_storage_error(e)),
+    }
+}
+
+/// Start an upload to `prefix/filename` and return its id. The bucket
+/// directory must already exist.
+pub(super) fn create(
+    bucket_dir: &Path,
+    prefix: &str,
+    filename: &str,
+    metadata: &FileMetadata,
+) -> Result<String, StorageError> {
+    let staging = bucket_dir.join(STAGING_DIR);
+    match std::fs::create_dir(&staging) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(io_to_storage_error(e)),
+    }
+    let upload_id = uuid::Uuid::new_v4().simple().to_string();
+    let dir = staging.join(&upload_id);
+    std::fs::create_dir(&dir).map_err(io_to_storage_error)?;
+    let manifest = Manifest {
+        prefix: prefix.to_string(),
+        filename: filename.to_string(),
+        metadata: metadata.clone(),
+        initiated: Utc::now(),
+    };
+    write_durable(&dir, MANIFEST, &serde_json::to_vec(&manifest)?)?;
+    Ok(upload_id)
+}
+
+/// Re-open upload `upload_id`, checking it targets `prefix/filename`.
+pub(super) fn resume(
+    bucket_dir: &Path,
+    bucket: &str,
+    prefix: &str,
+    filename: &str,
+    upload_id: &str,
+) -> Result<StagedMultipartUpload, StorageError> {
+    if !is_upload_id(upload_id) {
+        return Err(not_found(upload_id));
+    }
+    let manifest = match read_manifest(&upload_dir(bucket_dir, upload_id)) {
+        Ok(m) => m,
+        Err(StorageError::NotFound(_)) => return Err(not_found(upload_id)),
+        Err(e) => return Err(e),
+    };
+    if manifest.prefix != prefix || manifest.filename != filename {
+        return Err(not_found(upload_id));
+    }
+    Ok(staged(bucket, upload_id, manifest))
+}
+
+/// Durably store one part, replacing an earlier upload of the same number.
+pub(super) fn write_part(
+    bucket_dir: &Path,
+    upload_id: &str,
+    part_number: i32,
+    data: &[u8],
+) -> Result<UploadedPart, StorageError> {
+    let dir = upload_dir(bucket_dir, upload_id);
+    if !is_upload_id(upload_id) || !dir.join(MANIFEST).is_file() {
+        return Err(not_found(upload_id));
+    }
+    let md5 = hex::encode(Md5::digest(data));
+    let name = part_name(part_number, &md5);
+    write_durable(&dir, &name, data)?;
+    // Drop the previous upload of this part number, if any.
+    for entry in std::fs::read_dir(&dir).map_err(io_to_storage_error)? {
+        let entry = entry.map_err(io_to_storage_error)?;
+        let other = entry.file_name();
+        let Some(other) = other.to_str() else {
+            continue;
+        };
+        if other != name && parse_part_name(other).is_some_and(|(n, _)| n == part_number) {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+    Ok(UploadedPart {
+        part_number,
+        etag: format!("\"{md5}\""),
+    })
+}
+
+/// Part files of an upload by part number; the newest wins should a crash
+/// have left two for one number.
+fn part_files(dir: &Path) -> Result<Vec<(StagedPart, PathBuf)>, StorageError> {
+    let mut parts: Vec<(StagedPart, PathBuf)> = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_to_storage_error)? {
+        let entry = entry.map_err(io_to_storage_error)?;
+        let name = entry.file_name();
+        let Some((part_number, md5)) = name.to_str().and_then(parse_part_name) else {
+            continue;
+        };
+        let stat = entry.metadata().map_err(io_to_storage_error)?;
+        let part = StagedPart {
+            part_number,
+            etag: format!("\"{md5}\""),
+            size: stat.len(),
+            last_modified: mtime(&stat),
+        };
+        match parts.iter_mut().find(|(p, _)| p.part_number == part_number) {
+            Some(existing) if existing.0.last_modified < part.last_modified => {
+                *existing = (part, entry.path());
+            }
+            Some(_) => {}
+            None => parts.push((part, entry.path())),
+        }
+    }
+    parts.sort_by_key(|(p, _)| p.part_number);
+    Ok(parts)
+}
+
+pub(super) fn list_parts(
+    bucket_dir: &Path,
+    upload_id: &str,
+) -> Result<Vec<StagedPart>, StorageError> {
+    if !is_upload_id(upload_id) {
+        return Err(not_found(upload_id));
+    }
+    match part_files(&upload_dir(bucket_dir, upload_id)) {
+        Ok(parts) => Ok(parts.into_iter().map(|(p, _)| p).collect()),
+        Err(StorageError::NotFound(_)) => Err(not_found(upload_id)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Bytes of one staged part, verified against the MD5 in its file name.
+pub(super) fn read_part(
+    bucket_dir: &Path,
+    upload_id: &str,
+    part_number: i32,
+) -> Result<Vec<u8>, StorageError> {
+    if !is_upload_id(upload_id) {
+        return Err(not_found(upload_id));
+    }
+    let dir = upload_dir(bucket_dir, upload_id);
+    let path = match part_files(&dir) {
+        Ok(parts) => parts
+            .into_iter()
+            .find(|(p, _)| p.part_number == part_number)
+            .map(|(_, path)| path),
+        Err(StorageError::NotFound(_)) => return Err(not_found(upload_id)),
+        Err(e) => return Err(e),
+    };
+    let path = path.ok_or_else(|| {
+        StorageError::NotFound(format!(
+            "part {part_number} of multipart upload {upload_id}"
+        ))
+    })?;
+    let data = std::fs::read(&path).map_err(io_to_storage_error)?;
+    let expected = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(parse_part_name)
+        .map(|(_, md5)| md5.to_string())
+        .unwrap_or_default();
+    if hex::encode(Md5::digest(&data)) != expected {
+        return Err(StorageError::Other(format!(
+            "part {part_number} of multipart upload {upload_id} changed on disk since upload"
+        )));
+    }
+    Ok(data)
+}
+
+/// Concatenate `parts` (in the given order) into a temp file in
+/// `target_dir`, verifying each part against its ETag on the way. Fills in
+/// the whole-object hashes on `metadata` when the caller left them empty
+/// (client uploads: nobody saw the full body) and checks the assembled size
+/// against `metadata.file_size`. Returns the temp file, the updated metadata
+/// and the multipart ETag. The staging directory is left in place until the
+/// caller has renamed the result into place.
+pub(super) fn assemble(
+    bucket_dir: &Path,
+    upload_id: &str,
+    parts: &[UploadedPart],
+    target_dir: &Path,
+    metadata: &FileMetadata,
+) -> Result<(NamedTempFile, FileMetadata, String), StorageError> {
+    let dir = upload_dir(bucket_dir, upload_id);
+    if !is_upload_id(upload_id) || !dir.join(MANIFEST).is_file() {
+        return Err(not_found(upload_id));
+    }
+    let mut tmp = NamedTempFile::new_in(target_dir).map_err(io_to_storage_error)?;
+    let mut sha256 = Sha256::new();
+    let mut md5 = Md5::new();
+    let mut total = 0u64;
+    let mut buf = vec![0u8; 1024 * 1024];
+    for part in parts {
+        let expected = part.etag.trim_matches('"');
+        let path = dir.join(part_name(part.part_number, expected));
+        let mut src = std::fs::File::open(&path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                StorageError::NotFound(format!(
+                    "part {} of multipart upload {upload_id}",
+                    part.part_number
+                ))
+            } else {
+                io_to_storage_error(e)
+            }
+        })?;
+        let mut part_md5 = Md5::new();
+        loop {
+            let n = src.read(&mut buf).map_err(io_to_storage_error)?;
+            if n == 0 {
+                break;
+            }
+            tmp.write_all(&buf[..n]).map_err(io_to_storage_error)?;
+            sha256.update(&buf[..n]);
+            md5.update(&buf[..n]);
+            part_md5.update(&buf[..n]);
+            total += n as u64;
+        }
+        if hex::encode(part_md5.finalize()) != expected {
+            return Err(StorageError::Other(format!(
+                "part {} of multipart upload {upload_id} changed on disk since upload",
+                part.part_number
+            )));
+        }
+    }
+    if total != metadata.file_size {
+        return Err(StorageError::Other(format!(
+            "multipart upload {upload_id} assembled {total} bytes, expected {}",
+            metadata.file_size
+        )));
+    }
+    let mut metadata = metadata.clone();
+    if metadata.file_sha256.is_empty() {
+        metadata.file_sha256 = hex::encode(sha256.finalize());
+        metadata.md5 = hex::encode(md5.finalize());
+    }
+    Ok((tmp, metadata, multipart_etag(parts)))
+}
+
+/// Drop an upload's staging directory (complete or abort).
+pub(super) fn remove(bucket_dir: &Path, upload_id: &str) -> Result<(), StorageError> {
+    if !is_upload_id(upload_id) {
+        return Err(not_found(upload_id));
+    }
+    remove_dir(&upload_dir(bucket_dir, upload_id))
+}
+
+/// Upload directories under a bucket, by id.
+fn upload_dirs(bucket_dir: &Path) -> Result<Vec<(String, PathBuf)>, StorageError> {
+    let entries = match std::fs::read_dir(bucket_dir.join(STAGING_DIR)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_to_storage_error(e)),
+    };
+    let mut dirs = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(io_to_storage_error)?;
+        if let Some(id) = entry.file_name().to_str().filter(|id| is_upload_id(id)) {
+            dirs.push((id.to_string(), entry.path()));
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+pub(super) fn list_uploads(
+    bucket_dir: &Path,
+    bucket: &str,
+) -> Result<Vec<StagedMultipartUpload>, StorageError> {
+    let mut uploads = Vec::new();
+    for (id, dir) in upload_dirs(bucket_dir)? {
+        // A directory without a manifest is a create that crashed halfway
+        // (or an abort in progress): not an upload, left for the sweep.
+        match read_manifest(&dir) {
+            Ok(manifest) => uploads.push(staged(bucket, &id, manifest)),
+            Err(StorageError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(uploads)
+}
+
+/// Remove uploads in `bucket_dir` whose last activity (create or part
+/// upload) is older than `idle`.
+pub(super) fn sweep(
+    bucket_dir: &Path,
+    idle: std::time::Duration,
+) -> Result<StagedSweepReport, StorageError> {
+    let cutoff = SystemTime::now()
+        .checked_sub(idle)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    let mut report = StagedSweepReport::default();
+    for (_, dir) in upload_dirs(bucket_dir)? {
+        let mut last_activity = std::fs::metadata(&dir)
+            .and_then(|m| m.modified())
+            .map_err(io_to_storage_error)?;
+        let mut bytes = 0u64;
+        for entry in std::fs::read_dir(&dir).map_err(io_to_storage_error)? {
+            let stat = entry
+                .and_then(|e| e.metadata())
+                .map_err(io_to_storage_error)?;
+            bytes += stat.len();
+            if let Ok(modified) = stat.modified() {
+                last_activity = last_activity.max(modified);
+            }
+        }
+        if last_activity <= cutoff {
+            remove_dir(&dir)?;
+            report.uploads += 1;
+            report.bytes += bytes;
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(size: u64) -> FileMetadata {
+        FileMetadata::new_passthrough("obj.bin".into(), String::new(), String::new(), size, None)
+    }
+
+    #[test]
+    fn parts_survive_reopen_and_assemble_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = create(dir.path(), "a/b", "obj.bin", &meta(0)).unwrap();
+        write_part(dir.path(), &id, 2, b"world").unwrap();
+        write_part(dir.path(), &id, 1, b"stale").unwrap();
+        let one = write_part(dir.path(), &id, 1, b"hello ").unwrap();
+        let two = UploadedPart {
+            part_number: 2,
+            etag: list_parts(dir.path(), &id).unwrap()[1].etag.clone(),
+        };
+
+        let parts = list_parts(dir.path(), &id).unwrap();
+        assert_eq!(
+            parts
+                .iter()
+                .map(|p| (p.part_number, p.size))
+                .collect::<Vec<_>>(),
+            vec![(1, 6), (2, 5)]
+        );
+        assert!(matches!(
+            resume(dir.path(), "b", "a/b", "other.bin", &id),
+            Err(StorageError::NotFound(_))
+        ));
+        let upload = resume(dir.path(), "b", "a/b", "obj.bin", &id).unwrap();
+        assert_eq!(list_uploads(dir.path(), "b").unwrap().len(), 1);
+
+        let (tmp, meta, etag) =
+            assemble(dir.path(), &id, &[one, two], dir.path(), &meta(11)).unwrap();
+        assert_eq!(std::fs::read(tmp.path()).unwrap(), b"hello world");
+        assert_eq!(meta.md5, hex::encode(Md5::digest(b"hello world")));
+        assert!(etag.ends_with("-2"));
+        remove(dir.path(), &upload.upload.upload_id).unwrap();
+        assert!(list_uploads(dir.path(), "b").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_foreign_ids_and_sweeps_idle_uploads() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            write_part(dir.path(), "../../etc", 1, b"x"),
+            Err(StorageError::NotFound(_))
+        ));
+        let id = create(dir.path(), "", "obj.bin", &meta(0)).unwrap();
+        write_part(dir.path(), &id, 1, b"abc").unwrap();
+
+        let kept = sweep(dir.path(), std::time::Duration::from_secs(3600)).unwrap();
+        assert_eq!(kept, StagedSweepReport::default());
+        let swept = sweep(dir.path(), std::time::Duration::ZERO).unwrap();
+        assert_eq!(swept.uploads, 1);
+        assert!(swept.bytes >= 3);
+        assert!(matches!(
+            list_parts(dir.path(), &id),
+            Err(StorageError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,2230 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Google Cloud Storage backend (JSON API)
+//!
+//! Talks to the GCS JSON API directly over `reqwest` instead of pointing the
+//! S3 backend at the XML interop endpoint, whose multipart and metadata
+//! behaviour diverges from S3 in ways the engine trips over. There is no
+//! Google SDK dependency: service-account keys are exchanged for OAuth
+//! access tokens with a self-signed RS256 JWT ([`TokenSource`]).
+//!
+//! Each API bucket maps 1:1 to a GCS bucket, and keys follow the same
+//! layout as the S3 backend: `reference.bin`, `<name>.delta`, passthrough
+//! objects under their original name.
+//!
+//! DG metadata lives in the object's custom `metadata` map. GCS accepts any
+//! UTF-8 there, so the bare metadata map is stored verbatim, and listings
+//! return it inline — deltaspace scans need no per-object metadata GET.
+//!
+//! Uploads above [`UPLOAD_CHUNK`] use resumable sessions so a large object
+//! never sits in memory whole. Multipart uploads stage every part as a
+//! temporary object under `.deltaglider/mpu/<upload-id>/` and complete with
+//! `compose` (in a tree when there are more than [`MAX_COMPOSE_SOURCES`]
+//! parts). Generation preconditions (`ifGenerationMatch=0`) give the
+//! conditional-create semantics the capability gate checks for; see
+//! [`GcsBackend::probe_generation_precondition`].
+
+use super::s3::{confirmable_candidates, list_anchor, probe_hit_serves_candidate, S3Backend};
+use super::traits::{
+    multipart_etag, DelegatedListResult, MultipartUpload, StorageBackend, StorageError,
+    UploadedPart,
+};
+use crate::config::BackendConfig;
+use crate::types::{FileMetadata, StorageInfo};
+use async_trait::async_trait;
+use base64::Engine;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use reqwest::{Method, StatusCode, Url};
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{debug, instrument, warn};
+
+/// Public GCS endpoint (JSON API under `/storage/v1`, uploads under
+/// `/upload/storage/v1`).
+const DEFAULT_ENDPOINT: &str = "https://storage.googleapis.com";
+
+/// OAuth scope requested for service-account and metadata-server tokens.
+const OAUTH_SCOPE: &str = "https://www.googleapis.com/auth/devstorage.read_write";
+
+/// Default OAuth token endpoint when the key file does not name one.
+const DEFAULT_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+
+/// GCE / GKE metadata-server token endpoint (workload identity).
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Resumable upload chunk size. Must be a multiple of 256 KiB; objects up
+/// to this size go up in a single multipart (metadata + media) request.
+const UPLOAD_CHUNK: usize = 8 * 1024 * 1024;
+
+/// GCS `compose` accepts at most this many source objects per call.
+const MAX_COMPOSE_SOURCES: usize = 32;
+
+/// Max concurrent exact-key probes after an anchored delegated listing
+/// (same bound as the S3 backend's HEAD fan-out).
+const MAX_CONCURRENT_PROBES: usize = 10;
+
+/// Staging area for multipart part objects, one sub-prefix per upload.
+const MPU_STAGING_PREFIX: &str = ".deltaglider/mpu/";
+
+/// Refresh cached access tokens this long before they expire.
+const TOKEN_REFRESH_MARGIN: std::time::Duration = std::time::Duration::from_secs(60);
+
+pub struct GcsBackend {
+    http: reqwest::Client,
+    /// Endpoint without a trailing slash.
+    endpoint: String,
+    /// Project used to create and list buckets. Falls back to the
+    /// service-account key's `project_id`.
+    project: Option<String>,
+    auth: TokenSource,
+}
+
+impl GcsBackend {
+    /// Create a new GCS backend from configuration.
+    pub async fn new(config: &BackendConfig) -> Result<Self, StorageError> {
+        let BackendConfig::Gcs {
+            project,
+            service_account_key,
+            endpoint,
+            allow_local,
+        } = config
+        else {
+            return Err(StorageError::Other(
+                "GcsBackend requires GCS configuration".to_string(),
+            ));
+        };
+
+        let endpoint = endpoint
+            .clone()
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+        // Same SSRF posture as the S3 backend: an operator-supplied endpoint
+        // pointing at IMDS / private ranges is refused unless the backend is
+        // explicitly opted into dev mode (fake-gcs-server on localhost).
+        let env_allow = crate::config::env_bool("DGP_BACKEND_ALLOW_LOCAL", false);
+        let kind = if *allow_local || env_allow {
+            crate::security::UrlKind::BackendDev
+        } else {
+            crate::security::UrlKind::Backend
+        };
+        let refuse = |url: &str, e: String| {
+            StorageError::Other(format!(
+                "Refusing to use GCS endpoint {url:?}: {e}. \
+                 Set `allow_local: true` in the backend config (or \
+                 DGP_BACKEND_ALLOW_LOCAL=true env) to permit http:// + \
+                 private IPs for dev/CI."
+            ))
+        };
+        crate::security::validate_outbound_url(&endpoint, kind)
+            .map_err(|e| refuse(&endpoint, e.to_string()))?;
+
+        // No redirects: a resumable chunk answers `308 Resume Incomplete`,
+        // which must reach us rather than be followed.
+        let http = reqwest::Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(10))
+            .read_timeout(std::time::Duration::from_secs(60))
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(std::sync::Arc::new(
+                crate::security::SsrfGuardedResolver::new(kind),
+            ))
+            .build()
+            .map_err(|e| StorageError::Other(format!("GCS HTTP client build failed: {e}")))?;
+
+        let (credential, key_project) = match service_account_key.as_deref().map(str::trim) {
+            Some(json) if !json.is_empty() => {
+                let key: ServiceAccountKey = serde_json::from_str(json).map_err(|e| {
+                    StorageError::Other(format!(
+                        "GCS service_account_key is not a service-account JSON key: {e}"
+                    ))
+                })?;
+                let token_uri = key
+                    .token_uri
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_TOKEN_URI.to_string());
+                crate::security::validate_outbound_url(&token_uri, kind)
+                    .map_err(|e| refuse(&token_uri, e.to_string()))?;
+                let signing_key = jsonwebtoken::EncodingKey::from_rsa_pem(
+                    key.private_key.as_bytes(),
+                )
+                .map_err(|e| {
+                    StorageError::Other(format!("GCS service account private_key is invalid: {e}"))
+                })?;
+                (
+                    Credential::ServiceAccount {
+                        email: key.client_email,
+                        signing_key,
+                        token_uri,
+                    },
+                    key.project_id,
+                )
+            }
+            // An explicit endpoint without a key is an emulator
+            // (fake-gcs-server accepts unauthenticated requests).
+            _ if !endpoint.starts_with(DEFAULT_ENDPOINT) => (Credential::Anonymous, None),
+            // Production without a key: workload identity via the metadata
+            // server. Fixed, non-operator URL, so it bypasses the SSRF guard.
+            _ => {
+                let metadata_http = reqwest::Client::builder()
+                    .connect_timeout(std::time::Duration::from_secs(5))
+                    .timeout(std::time::Duration::from_secs(10))
+                    .build()
+                    .map_err(|e| {
+                        StorageError::Other(format!("GCS metadata client build failed: {e}"))
+                    })?;
+                (Credential::MetadataServer(metadata_http), None)
+            }
+        };
+
+        let project = project.clone().or(key_project);
+        debug!(
+            "GcsBackend initialized (endpoint {endpoint}, project {})",
+            project.as_deref().unwrap_or("-")
+        );
+        Ok(Self {
+            http,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            project,
+            auth: TokenSource::new(credential),
+        })
+    }
+
+    // === Key layout (identical to the S3 backend) ===
+
+    fn prefixed_key(prefix: &str, filename: &str) -> String {
+        if prefix.is_empty() {
+            filename.to_string()
+        } else {
+            format!("{}/{}", prefix, filename)
+        }
+    }
+
+    fn reference_key(prefix: &str) -> String {
+        Self::prefixed_key(prefix, "reference.bin")
+    }
+
+    fn delta_key(prefix: &str, filename: &str) -> String {
+        Self::prefixed_key(prefix, &format!("{}.delta", filename))
+    }
+
+    fn passthrough_key(prefix: &str, filename: &str) -> String {
+        Self::prefixed_key(prefix, filename)
+    }
+
+    fn staging_prefix(upload_id: &str) -> String {
+        format!("{MPU_STAGING_PREFIX}{upload_id}/")
+    }
+
+    // === URLs ===
+
+    /// `/storage/v1/b` (bucket collection).
+    fn buckets_url(&self) -> String {
+        format!("{}/storage/v1/b", self.endpoint)
+    }
+
+    /// `/storage/v1/b/<bucket>`.
+    fn bucket_url(&self, bucket: &str) -> String {
+        format!("{}/{}", self.buckets_url(), urlencoding::encode(bucket))
+    }
+
+    /// `/storage/v1/b/<bucket>/o/<object>`. The object name is encoded as a
+    /// single path segment (`/` becomes `%2F`), as the JSON API requires.
+    fn object_url(&self, bucket: &str, object: &str) -> String {
+        format!(
+            "{}/o/{}",
+            self.bucket_url(bucket),
+            urlencoding::encode(object)
+        )
+    }
+
+    /// `/upload/storage/v1/b/<bucket>/o` (media and resumable uploads).
+    fn upload_url(&self, bucket: &str) -> String {
+        format!(
+            "{}/upload/storage/v1/b/{}/o",
+            self.endpoint,
+            urlencoding::encode(bucket)
+        )
+    }
+
+    // === Request plumbing ===
+
+    /// Authenticate and send a request, retrying transient failures (500,
+    /// 502, 503, 504, connection errors) with the same 100/200/400 ms
+    /// backoff the S3 backend uses. Any final response is returned as-is;
+    /// see [`send`](Self::send) for status classification.
+    async fn send_raw(&self, req: &GcsRequest<'_>) -> Result<reqwest::Response, StorageError> {
+        let url = req.url()?;
+        let backoff_ms = [100u64, 200, 400];
+        for attempt in 0..=backoff_ms.len() {
+            let mut builder = self.http.request(req.method.clone(), url.clone());
+            for (name, value) in &req.headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            if let Some(token) = self.auth.token(&self.http).await? {
+                builder = builder.bearer_auth(token);
+            }
+            // Body-carrying verbs always send one (possibly empty) so
+            // reqwest emits `Content-Length: 0` — GCS answers 411 without.
+            builder = match &req.body {
+                Some(body) => builder.body(body.clone()),
+                None if req.method != Method::GET && req.method != Method::DELETE => {
+                    builder.body(Bytes::new())
+                }
+                None => builder,
+            };
+            let retry_reason = match builder.send().await {
+                Ok(resp) => {
+                    let status = resp.status().as_u16();
+                    if matches!(status, 500 | 502 | 503 | 504) && attempt < backoff_ms.len() {
+                        format!("HTTP {status}")
+                    } else {
+                        return Ok(resp);
+                    }
+                }
+                Err(e) if (e.is_connect() || e.is_timeout()) && attempt < backoff_ms.len() => {
+                    e.to_string()
+                }
+                Err(e) => {
+                    return Err(StorageError::Other(format!(
+                        "GCS {} {}/{} failed: {e}",
+                        req.method,
+                        req.bucket,
+                        req.object.unwrap_or("")
+                    )))
+                }
+            };
+            warn!(
+                "GCS {} {}/{} failed (attempt {}), retrying in {}ms: {}",
+                req.method,
+                req.bucket,
+                req.object.unwrap_or(""),
+                attempt + 1,
+                backoff_ms[attempt],
+                retry_reason
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(backoff_ms[attempt])).await;
+        }
+        unreachable!("retry loop must return on every path")
+    }
+
+    /// [`send_raw`](Self::send_raw), classifying non-2xx responses into
+    /// `StorageError`.
+    async fn send(&self, req: GcsRequest<'_>) -> Result<reqwest::Response, StorageError> {
+        let resp = self.send_raw(&req).await?;
+        if resp.status().is_success() {
+            Ok(resp)
+        } else {
+            Err(error_from_response(resp, req.bucket, req.object).await)
+        }
+    }
+
+    /// Send and decode a JSON response body.
+    async fn send_json<T: serde::de::DeserializeOwned>(
+        &self,
+        req: GcsRequest<'_>,
+    ) -> Result<T, StorageError> {
+        let bucket = req.bucket.to_string();
+        let body =
+            self.send(req).await?.bytes().await.map_err(|e| {
+                StorageError::Other(format!("Failed to read GCS response body: {e}"))
+            })?;
+        serde_json::from_slice(&body)
+            .map_err(|e| StorageError::Other(format!("invalid GCS response for {bucket}: {e}")))
+    }
+
+    /// Single authenticated bucket-list call, no retries, for the backend
+    /// health probe. `Ok(status)` when the service answered at all (a token
+    /// endpoint rejection reads as 401); `Err` carries the transport failure.
+    pub async fn probe_status(&self) -> Result<u16, String> {
+        let token = match self.auth.token(&self.http).await {
+            Ok(token) => token,
+            Err(StorageError::Other(e)) if e.contains("token endpoint rejected") => return Ok(401),
+            Err(e) => return Err(e.to_string()),
+        };
+        let mut builder = self
+            .http
+            .get(self.buckets_url())
+            .query(&[("maxResults", "1")]);
+        if let Some(project) = &self.project {
+            builder = builder.query(&[("project", project)]);
+        }
+        if let Some(token) = token {
+            builder = builder.bearer_auth(token);
+        }
+        builder
+            .send()
+            .await
+            .map(|resp| resp.status().as_u16())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Conditional-write capability probe: an unconditional write followed
+    /// by an `ifGenerationMatch=0` (create-only) write to the same key, which
+    /// MUST fail with 412. `Ok(false)` when the precondition was ignored.
+    /// The probe object is deleted best-effort.
+    pub async fn probe_generation_precondition(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<bool, String> {
+        self.put_media(bucket, key, Bytes::from_static(b"1"), None)
+            .await
+            .map_err(|e| format!("probe could not write to '{bucket}': {e}"))?;
+        let req = GcsRequest::new(Method::POST, self.upload_url(bucket), bucket, Some(key))
+            .query("uploadType", "media")
+            .query("name", key)
+            .query("ifGenerationMatch", "0")
+            .header("content-type", "application/octet-stream")
+            .body(Bytes::from_static(b"2"));
+        let supported = match self.send_raw(&req).await {
+            Ok(resp) if resp.status() == StatusCode::PRECONDITION_FAILED => Ok(true),
+            Ok(resp) if resp.status().is_success() => Ok(false),
+            Ok(resp) => Err(format!(
+                "probe re-PUT to '{bucket}' failed with a non-conditional error: {}",
+                error_from_response(resp, bucket, Some(key)).await
+            )),
+            Err(e) => Err(format!("probe re-PUT to '{bucket}' failed: {e}")),
+        };
+        let _ = self.delete_object(bucket, key).await;
+        supported
+    }
+
+    // === Object operations ===
+
+    /// Upload with DG metadata: one multipart (metadata + media) request up
+    /// to [`UPLOAD_CHUNK`], a resumable session beyond that.
+    async fn put_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let len = data.len();
+        if len > UPLOAD_CHUNK {
+            let mut session = self
+                .start_resumable(bucket, key, metadata, len as u64)
+                .await?;
+            session.write(self, &data).await?;
+            session.finish(self).await?;
+        } else {
+            let resource = object_resource(key, "application/octet-stream", Some(metadata));
+            let boundary = format!("dg-{}", uuid::Uuid::new_v4().simple());
+            let req = GcsRequest::new(Method::POST, self.upload_url(bucket), bucket, Some(key))
+                .query("uploadType", "multipart")
+                .header(
+                    "content-type",
+                    &format!("multipart/related; boundary={boundary}"),
+                )
+                .body(multipart_related_body(&boundary, &resource, &data));
+            self.send(req).await?;
+        }
+        debug!(
+            "GCS PUT {}/{} ({} bytes) with DG metadata",
+            bucket, key, len
+        );
+        Ok(())
+    }
+
+    /// Bare media upload (no custom metadata) — multipart part objects and
+    /// the capability probe.
+    async fn put_media(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        content_type: Option<&str>,
+    ) -> Result<(), StorageError> {
+        let req = GcsRequest::new(Method::POST, self.upload_url(bucket), bucket, Some(key))
+            .query("uploadType", "media")
+            .query("name", key)
+            .header(
+                "content-type",
+                content_type.unwrap_or("application/octet-stream"),
+            )
+            .body(data);
+        self.send(req).await?;
+        Ok(())
+    }
+
+    /// Upload a local file. Small files go up in one request; larger ones
+    /// stream through a resumable session in [`UPLOAD_CHUNK`] pieces, so
+    /// memory stays O(chunk) regardless of object size.
+    async fn put_object_from_file(
+        &self,
+        bucket: &str,
+        key: &str,
+        source_path: &std::path::Path,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let len = tokio::fs::metadata(source_path).await?.len();
+        if len <= UPLOAD_CHUNK as u64 {
+            let data = tokio::fs::read(source_path).await?;
+            return self
+                .put_object(bucket, key, Bytes::from(data), metadata)
+                .await;
+        }
+        let mut session = self.start_resumable(bucket, key, metadata, len).await?;
+        let mut file = tokio::fs::File::open(source_path).await?;
+        session.write_from(self, &mut file).await?;
+        session.finish(self).await?;
+        debug!("GCS PUT {}/{} ({} bytes) from file", bucket, key, len);
+        Ok(())
+    }
+
+    /// Open a resumable upload session for `total` bytes. The object
+    /// resource (name, content type, DG metadata) is fixed here; the
+    /// returned session only carries bytes.
+    async fn start_resumable(
+        &self,
+        bucket: &str,
+        key: &str,
+        metadata: &FileMetadata,
+        total: u64,
+    ) -> Result<ResumableUpload, StorageError> {
+        let resource = object_resource(key, "application/octet-stream", Some(metadata));
+        let req = GcsRequest::new(Method::POST, self.upload_url(bucket), bucket, Some(key))
+            .query("uploadType", "resumable")
+            .header("content-type", "application/json; charset=UTF-8")
+            .header("x-upload-content-type", "application/octet-stream")
+            .header("x-upload-content-length", &total.to_string())
+            .body(Bytes::from(resource.to_string()));
+        let resp = self.send(req).await?;
+        let session_uri = resp
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                StorageError::Other(format!(
+                    "GCS resumable upload for {bucket}/{key} returned no session URI"
+                ))
+            })?
+            .to_string();
+        Ok(ResumableUpload {
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+            session_uri,
+            offset: 0,
+            total,
+            pending: Vec::with_capacity(UPLOAD_CHUNK.min(total as usize)),
+        })
+    }
+
+    /// PUT one chunk of a resumable session. Intermediate chunks must be
+    /// answered with `308` acknowledging every byte up to `end`; the final
+    /// chunk with the object resource (2xx).
+    async fn put_resumable_chunk(
+        &self,
+        upload: &ResumableUpload,
+        data: Bytes,
+    ) -> Result<(), StorageError> {
+        let len = data.len() as u64;
+        let range = content_range(upload.offset, len, upload.total);
+        let req = GcsRequest::new(
+            Method::PUT,
+            upload.session_uri.clone(),
+            &upload.bucket,
+            Some(&upload.key),
+        )
+        .header("content-range", &range)
+        .body(data);
+        let resp = self.send_raw(&req).await?;
+        let last = upload.offset + len >= upload.total;
+        match resp.status() {
+            s if s.is_success() && last => Ok(()),
+            StatusCode::PERMANENT_REDIRECT if !last => {
+                let acked = resp
+                    .headers()
+                    .get(reqwest::header::RANGE)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.rsplit('-').next())
+                    .and_then(|v| v.parse::<u64>().ok());
+                if acked == Some(upload.offset + len - 1) {
+                    Ok(())
+                } else {
+                    Err(StorageError::Other(format!(
+                        "GCS resumable upload {}/{} persisted {:?} after sending {}",
+                        upload.bucket, upload.key, acked, range
+                    )))
+                }
+            }
+            _ => Err(error_from_response(resp, &upload.bucket, Some(&upload.key)).await),
+        }
+    }
+
+    /// Fetch the object resource (JSON metadata, no bytes).
+    async fn get_object_resource(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<GcsObject, StorageError> {
+        self.send_json(GcsRequest::new(
+            Method::GET,
+            self.object_url(bucket, key),
+            bucket,
+            Some(key),
+        ))
+        .await
+    }
+
+    /// Replace an object's custom metadata without touching its bytes.
+    /// PATCH merges metadata maps, so keys that are no longer present are
+    /// sent as `null`; `ifMetagenerationMatch` keeps a concurrent metadata
+    /// writer from being half-merged (we re-read and retry instead).
+    async fn set_object_metadata(
+        &self,
+        bucket: &str,
+        key: &str,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let desired = metadata.to_bare_metadata_map();
+        for _ in 0..3 {
+            let current = self.get_object_resource(bucket, key).await?;
+            let patch = metadata_patch(&current.metadata, &desired);
+            let req = GcsRequest::new(
+                Method::PATCH,
+                self.object_url(bucket, key),
+                bucket,
+                Some(key),
+            )
+            .query(
+                "ifMetagenerationMatch",
+                current.metageneration.as_deref().unwrap_or("0"),
+            )
+            .header("content-type", "application/json; charset=UTF-8")
+            .body(Bytes::from(patch.to_string()));
+            let resp = self.send_raw(&req).await?;
+            match resp.status() {
+                s if s.is_success() => return Ok(()),
+                StatusCode::PRECONDITION_FAILED => {
+                    debug!("GCS metadata race on {}/{}, re-reading", bucket, key);
+                }
+                _ => return Err(error_from_response(resp, bucket, Some(key)).await),
+            }
+        }
+        Err(StorageError::Other(format!(
+            "GCS metadata update on {bucket}/{key} kept losing to concurrent writers"
+        )))
+    }
+
+    async fn get_object(&self, bucket: &str, key: &str) -> Result<Vec<u8>, StorageError> {
+        let req = GcsRequest::new(Method::GET, self.object_url(bucket, key), bucket, Some(key))
+            .query("alt", "media");
+        let data = self
+            .send(req)
+            .await?
+            .bytes()
+            .await
+            .map_err(|e| StorageError::Other(format!("Failed to read GCS response body: {e}")))?
+            .to_vec();
+        debug!("GCS GET {}/{} ({} bytes)", bucket, key, data.len());
+        Ok(data)
+    }
+
+    /// Read an object's resource and build its FileMetadata. Mirrors the S3
+    /// backend: missing/corrupt DG metadata degrades to passthrough, loudly
+    /// only for `.delta` / `reference.bin` where it breaks reconstruction.
+    async fn get_object_metadata(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        let object = self.get_object_resource(bucket, key).await?;
+        // Stable per-object timestamp for the `created_at` fallback — never
+        // `now()` (see `resolve_created_at` in the S3 backend).
+        let last_modified = object.updated_at().unwrap_or_else(Utc::now);
+
+        let delta_critical = key.ends_with(".delta") || key.ends_with("reference.bin");
+        if !object.metadata.is_empty() {
+            match S3Backend::headers_to_metadata(&object.metadata, last_modified) {
+                Ok(parsed) => return Ok(parsed),
+                Err(e) if delta_critical => warn!(
+                    "PATHOLOGICAL | {}/{} has missing/corrupt DG metadata — \
+                     delta reconstruction will not work. Error: {}",
+                    bucket, key, e
+                ),
+                Err(e) => debug!(
+                    "No DG metadata for {}/{} — serving as passthrough. Error: {}",
+                    bucket, key, e
+                ),
+            }
+        } else if delta_critical {
+            warn!(
+                "PATHOLOGICAL | {}/{} has NO DG metadata! Delta reconstruction will not work.",
+                bucket, key
+            );
+        }
+
+        Ok(FileMetadata::fallback(
+            key.rsplit('/').next().unwrap_or(key).to_string(),
+            object.size(),
+            object.etag(),
+            last_modified,
+            object.content_type.clone(),
+            StorageInfo::Passthrough,
+        ))
+    }
+
+    /// Delete an object. A missing object is success, matching S3's
+    /// idempotent DeleteObject — the engine's cleanup paths rely on that.
+    pub async fn delete_object(&self, bucket: &str, key: &str) -> Result<(), StorageError> {
+        match self
+            .send(GcsRequest::new(
+                Method::DELETE,
+                self.object_url(bucket, key),
+                bucket,
+                Some(key),
+            ))
+            .await
+        {
+            Ok(_) | Err(StorageError::NotFound(_)) => {
+                debug!("GCS DELETE {}/{}", bucket, key);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// GET (optionally ranged) and hand back the body as a stream.
+    async fn get_object_stream(
+        &self,
+        bucket: &str,
+        key: &str,
+        range: Option<(u64, u64)>,
+    ) -> Result<(reqwest::Response, u64), StorageError> {
+        let mut req = GcsRequest::new(Method::GET, self.object_url(bucket, key), bucket, Some(key))
+            .query("alt", "media");
+        if let Some((start, end)) = range {
+            req = req.header("range", &format!("bytes={start}-{end}"));
+        }
+        let resp = self.send(req).await?;
+        let len = resp.content_length().unwrap_or(0);
+        Ok((resp, len))
+    }
+
+    /// Compose `sources` (in order) into `dest`, stamping the final content
+    /// type and DG metadata. More than [`MAX_COMPOSE_SOURCES`] sources are
+    /// composed in rounds into intermediates under `staging`.
+    async fn compose_tree(
+        &self,
+        bucket: &str,
+        dest: &str,
+        mut sources: Vec<String>,
+        staging: &str,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let mut round = 0;
+        while sources.len() > MAX_COMPOSE_SOURCES {
+            let mut next = Vec::with_capacity(sources.len().div_ceil(MAX_COMPOSE_SOURCES));
+            for (i, group) in sources.chunks(MAX_COMPOSE_SOURCES).enumerate() {
+                let intermediate = format!("{staging}c{round}-{i:05}");
+                self.compose(bucket, &intermediate, group, None).await?;
+                next.push(intermediate);
+            }
+            sources = next;
+            round += 1;
+        }
+        self.compose(bucket, dest, &sources, Some(metadata)).await
+    }
+
+    /// One `compose` call (≤ [`MAX_COMPOSE_SOURCES`] sources).
+    async fn compose(
+        &self,
+        bucket: &str,
+        dest: &str,
+        sources: &[String],
+        metadata: Option<&FileMetadata>,
+    ) -> Result<(), StorageError> {
+        let body = serde_json::json!({
+            "sourceObjects": sources
+                .iter()
+                .map(|name| serde_json::json!({ "name": name }))
+                .collect::<Vec<_>>(),
+            "destination": object_resource(dest, "application/octet-stream", metadata),
+        });
+        let req = GcsRequest::new(
+            Method::POST,
+            format!("{}/compose", self.object_url(bucket, dest)),
+            bucket,
+            Some(dest),
+        )
+        .header("content-type", "application/json; charset=UTF-8")
+        .body(Bytes::from(body.to_string()));
+        self.send(req).await?;
+        Ok(())
+    }
+
+    /// Delete every staged object of a multipart upload (best-effort per
+    /// object; the listing itself must succeed).
+    async fn delete_staging(&self, bucket: &str, upload_id: &str) -> Result<(), StorageError> {
+        let staged = self
+            .list_all(bucket, &Self::staging_prefix(upload_id))
+            .await?;
+        for object in staged {
+            if let Err(e) = self.delete_object(bucket, &object.name).await {
+                warn!(
+                    "GCS multipart cleanup could not delete {}/{}: {}",
+                    bucket, object.name, e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    // === Listing ===
+
+    /// One objects.list page. `start_offset` is inclusive (GCS semantics).
+    async fn list_page(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        delimiter: Option<&str>,
+        page_token: Option<&str>,
+        max_results: Option<u32>,
+        start_offset: Option<&str>,
+    ) -> Result<ObjectPage, StorageError> {
+        let mut req = GcsRequest::new(
+            Method::GET,
+            format!("{}/o", self.bucket_url(bucket)),
+            bucket,
+            None,
+        );
+        if !prefix.is_empty() {
+            req = req.query("prefix", prefix);
+        }
+        if let Some(d) = delimiter.filter(|d| !d.is_empty()) {
+            req = req.query("delimiter", d);
+        }
+        if let Some(t) = page_token {
+            req = req.query("pageToken", t);
+        }
+        if let Some(n) = max_results {
+            req = req.query("maxResults", &n.to_string());
+        }
+        if let Some(s) = start_offset.filter(|s| !s.is_empty()) {
+            req = req.query("startOffset", s);
+        }
+        let page: ObjectPage = self.send_json(req).await?;
+        Ok(page)
+    }
+
+    /// Every object under `prefix` (no delimiter), across all pages.
+    async fn list_all(&self, bucket: &str, prefix: &str) -> Result<Vec<GcsObject>, StorageError> {
+        let mut objects = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let page = self
+                .list_page(bucket, prefix, None, token.as_deref(), None, None)
+                .await?;
+            objects.extend(page.items);
+            match page.next_page_token {
+                Some(t) if !t.is_empty() => token = Some(t),
+                _ => break,
+            }
+        }
+        Ok(objects)
+    }
+
+    /// Classify listed objects into user-visible `(key, metadata)` pairs:
+    /// directory markers pass through, `reference.bin` is dropped, `.delta`
+    /// maps back to the user key, and duplicates keep the latest.
+    fn listed_entries(objects: Vec<GcsObject>) -> Vec<(String, FileMetadata)> {
+        let mut entries = Vec::new();
+        for object in objects {
+            if object.name.ends_with('/') && object.size() == 0 {
+                entries.push((
+                    object.name.clone(),
+                    FileMetadata::directory_marker(&object.name),
+                ));
+                continue;
+            }
+            let filename = object.name.rsplit('/').next().unwrap_or(&object.name);
+            if filename == "reference.bin" {
+                continue;
+            }
+            let key_prefix = &object.name[..object.name.len() - filename.len()];
+            let user_key = format!("{}{}", key_prefix, filename.trim_end_matches(".delta"));
+            let meta = object.file_metadata();
+            entries.push((user_key, meta));
+        }
+        crate::types::dedup_keep_latest(entries)
+    }
+}
+
+#[async_trait]
+impl StorageBackend for GcsBackend {
+    // === Bucket operations ===
+
+    #[instrument(skip(self))]
+    async fn create_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        let mut req = GcsRequest::new(Method::POST, self.buckets_url(), bucket, None)
+            .header("content-type", "application/json; charset=UTF-8")
+            .body(Bytes::from(
+                serde_json::json!({ "name": bucket }).to_string(),
+            ));
+        if let Some(project) = &self.project {
+            req = req.query("project", project);
+        }
+        self.send(req).await?;
+        debug!("Created GCS bucket: {}", bucket);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        let page = self
+            .list_page(bucket, "", None, None, Some(1), None)
+            .await?;
+        if !page.items.is_empty() {
+            return Err(StorageError::BucketNotEmpty(bucket.to_string()));
+        }
+        self.send(GcsRequest::new(
+            Method::DELETE,
+            self.bucket_url(bucket),
+            bucket,
+            None,
+        ))
+        .await?;
+        debug!("Deleted GCS bucket: {}", bucket);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_buckets(&self) -> Result<Vec<String>, StorageError> {
+        let dated = self.list_buckets_with_dates().await?;
+        Ok(dated.into_iter().map(|(name, _)| name).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_buckets_with_dates(&self) -> Result<Vec<(String, DateTime<Utc>)>, StorageError> {
+        let mut buckets = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let mut req = GcsRequest::new(Method::GET, self.buckets_url(), "", None);
+            if let Some(project) = &self.project {
+                req = req.query("project", project);
+            }
+            if let Some(ref t) = token {
+                req = req.query("pageToken", t);
+            }
+            let page: BucketPage = self.send_json(req).await?;
+            buckets.extend(page.items.into_iter().map(|b| {
+                let created = b
+                    .time_created
+                    .as_deref()
+                    .and_then(parse_rfc3339)
+                    .unwrap_or_else(Utc::now);
+                (b.name, created)
+            }));
+            match page.next_page_token {
+                Some(t) if !t.is_empty() => token = Some(t),
+                _ => break,
+            }
+        }
+        buckets.sort_by(|a, b| a.0.cmp(&b.0));
+        debug!("Listed {} GCS buckets", buckets.len());
+        Ok(buckets)
+    }
+
+    #[instrument(skip(self))]
+    async fn head_bucket(&self, bucket: &str) -> Result<bool, StorageError> {
+        let req = GcsRequest::new(Method::GET, self.bucket_url(bucket), bucket, None)
+            .query("fields", "name");
+        match self.send(req).await {
+            Ok(_) => Ok(true),
+            // Only a genuine 404 reads as absent; throttling / 5xx propagate
+            // so routing never re-places a bucket on a transient error.
+            Err(StorageError::BucketNotFound(_)) | Err(StorageError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    // === Reference file operations ===
+
+    #[instrument(skip(self, data, metadata))]
+    async fn put_reference(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::reference_key(prefix);
+        self.put_object(bucket, &key, Bytes::copy_from_slice(data), metadata)
+            .await
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn put_reference_from_file(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        source_path: &std::path::Path,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::reference_key(prefix);
+        self.put_object_from_file(bucket, &key, source_path, metadata)
+            .await
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn put_reference_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::reference_key(prefix);
+        self.set_object_metadata(bucket, &key, metadata).await
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn put_passthrough_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        self.set_object_metadata(bucket, &key, metadata).await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_reference(&self, bucket: &str, prefix: &str) -> Result<Vec<u8>, StorageError> {
+        self.get_object(bucket, &Self::reference_key(prefix)).await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_reference_to_file(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        dest: &std::path::Path,
+    ) -> Result<u64, StorageError> {
+        let key = Self::reference_key(prefix);
+        let (mut resp, _) = self.get_object_stream(bucket, &key, None).await?;
+        let mut file = tokio::fs::File::create(dest).await?;
+        let mut written = 0u64;
+        while let Some(chunk) = resp
+            .chunk()
+            .await
+            .map_err(|e| StorageError::Other(format!("Failed to read GCS response body: {e}")))?
+        {
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+        Ok(written)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_reference_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.get_object_metadata(bucket, &Self::reference_key(prefix))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn has_reference(&self, bucket: &str, prefix: &str) -> Result<bool, StorageError> {
+        let key = Self::reference_key(prefix);
+        let req = GcsRequest::new(
+            Method::GET,
+            self.object_url(bucket, &key),
+            bucket,
+            Some(&key),
+        )
+        .query("fields", "name");
+        match self.send(req).await {
+            Ok(_) => Ok(true),
+            // Only an object-level 404 is "absent"; anything else (throttle,
+            // 5xx, missing bucket) must propagate so the write path never
+            // overwrites a live reference.bin on a backend hiccup.
+            Err(StorageError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_reference(&self, bucket: &str, prefix: &str) -> Result<(), StorageError> {
+        self.delete_object(bucket, &Self::reference_key(prefix))
+            .await
+    }
+
+    // === Delta file operations ===
+
+    #[instrument(skip(self, data, metadata))]
+    async fn put_delta(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::delta_key(prefix, filename);
+        self.put_object(bucket, &key, Bytes::copy_from_slice(data), metadata)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_delta(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.get_object(bucket, &Self::delta_key(prefix, filename))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_delta_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.get_object_metadata(bucket, &Self::delta_key(prefix, filename))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_delta(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<(), StorageError> {
+        self.delete_object(bucket, &Self::delta_key(prefix, filename))
+            .await
+    }
+
+    // === Passthrough file operations ===
+
+    #[instrument(skip(self, data, metadata))]
+    async fn put_passthrough(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        data: &[u8],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        self.put_object(bucket, &key, Bytes::copy_from_slice(data), metadata)
+            .await
+    }
+
+    #[instrument(skip(self, metadata))]
+    async fn put_passthrough_file(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        source_path: &std::path::Path,
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        self.put_object_from_file(bucket, &key, source_path, metadata)
+            .await
+    }
+
+    /// Stream the relay part files through one resumable session instead of
+    /// assembling the object in memory (the trait default).
+    #[instrument(skip(self, part_paths, metadata))]
+    async fn put_passthrough_parts(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        part_paths: &[std::path::PathBuf],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let mut total = 0u64;
+        for path in part_paths {
+            total += tokio::fs::metadata(path).await?.len();
+        }
+        let mut session = self.start_resumable(bucket, &key, metadata, total).await?;
+        for path in part_paths {
+            let mut file = tokio::fs::File::open(path).await?;
+            session.write_from(self, &mut file).await?;
+        }
+        session.finish(self).await
+    }
+
+    /// Feed chunks through a resumable session once the object outgrows a
+    /// single request, so they are never copied into one contiguous buffer.
+    #[instrument(skip(self, chunks, metadata))]
+    async fn put_passthrough_chunked(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        chunks: &[Bytes],
+        metadata: &FileMetadata,
+    ) -> Result<(), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let total: usize = chunks.iter().map(|c| c.len()).sum();
+        if total <= UPLOAD_CHUNK {
+            let mut buf = Vec::with_capacity(total);
+            for chunk in chunks {
+                buf.extend_from_slice(chunk);
+            }
+            return self
+                .put_object(bucket, &key, Bytes::from(buf), metadata)
+                .await;
+        }
+        let mut session = self
+            .start_resumable(bucket, &key, metadata, total as u64)
+            .await?;
+        for chunk in chunks {
+            session.write(self, chunk).await?;
+        }
+        session.finish(self).await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_passthrough(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<Vec<u8>, StorageError> {
+        self.get_object(bucket, &Self::passthrough_key(prefix, filename))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_passthrough_metadata(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<FileMetadata, StorageError> {
+        self.get_object_metadata(bucket, &Self::passthrough_key(prefix, filename))
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_passthrough(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<(), StorageError> {
+        self.delete_object(bucket, &Self::passthrough_key(prefix, filename))
+            .await
+    }
+
+    // === Streaming operations ===
+
+    #[instrument(skip(self))]
+    async fn get_passthrough_stream(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<BoxStream<'static, Result<Bytes, StorageError>>, StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let (resp, _) = self.get_object_stream(bucket, &key, None).await?;
+        debug!("GCS GET stream {}/{}", bucket, key);
+        Ok(body_stream(resp))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_passthrough_stream_range(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let (resp, len) = self
+            .get_object_stream(bucket, &key, Some((start, end)))
+            .await?;
+        debug!(
+            "GCS GET range stream {}/{} (bytes={}-{}, {} bytes)",
+            bucket, key, start, end, len
+        );
+        Ok((body_stream(resp), len))
+    }
+
+    // === Multipart upload (staged parts + compose) ===
+
+    fn supports_native_multipart(&self, _bucket: &str) -> bool {
+        true
+    }
+
+    /// No server-side call: parts are plain objects under a staging prefix
+    /// named by the upload id, so any instance can continue the upload.
+    async fn create_multipart_upload(
+        &self,
+        bucket: &str,
+        _prefix: &str,
+        _filename: &str,
+        _metadata: &FileMetadata,
+    ) -> Result<MultipartUpload, StorageError> {
+        Ok(MultipartUpload {
+            bucket: bucket.to_string(),
+            upload_id: uuid::Uuid::new_v4().simple().to_string(),
+            native: true,
+            backend: None,
+        })
+    }
+
+    #[instrument(skip(self, upload, data))]
+    async fn upload_part(
+        &self,
+        upload: &MultipartUpload,
+        _prefix: &str,
+        _filename: &str,
+        part_number: i32,
+        data: Bytes,
+    ) -> Result<UploadedPart, StorageError> {
+        let md5 = {
+            use md5::{Digest, Md5};
+            hex::encode(Md5::digest(&data))
+        };
+        let staged = part_object_name(&upload.upload_id, part_number);
+        self.put_media(&upload.bucket, &staged, data, None).await?;
+        Ok(UploadedPart {
+            part_number,
+            etag: format!("\"{md5}\""),
+        })
+    }
+
+    /// Compose the staged parts (in order) into the final object with its
+    /// metadata, then drop the staging objects. Returns the S3-style
+    /// multipart ETag (`md5(concatenated part md5s)-<n>`) so clients see
+    /// the same shape they would from an S3 backend.
+    #[instrument(skip(self, upload, parts, _assembled, metadata))]
+    async fn complete_multipart_upload(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+        parts: &[UploadedPart],
+        _assembled: &[Bytes],
+        metadata: &FileMetadata,
+    ) -> Result<String, StorageError> {
+        let key = Self::passthrough_key(prefix, filename);
+        let sources: Vec<String> = parts
+            .iter()
+            .map(|p| part_object_name(&upload.upload_id, p.part_number))
+            .collect();
+        if sources.is_empty() {
+            self.put_object(&upload.bucket, &key, Bytes::new(), metadata)
+                .await?;
+        } else {
+            self.compose_tree(
+                &upload.bucket,
+                &key,
+                sources,
+                &Self::staging_prefix(&upload.upload_id),
+                metadata,
+            )
+            .await?;
+        }
+        if let Err(e) = self.delete_staging(&upload.bucket, &upload.upload_id).await {
+            warn!(
+                "GCS multipart {} completed but staging cleanup failed: {}",
+                upload.upload_id, e
+            );
+        }
+        Ok(multipart_etag(parts))
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        upload: &MultipartUpload,
+        _prefix: &str,
+        _filename: &str,
+    ) -> Result<(), StorageError> {
+        self.delete_staging(&upload.bucket, &upload.upload_id).await
+    }
+
+    // === Scanning operations ===
+
+    /// Listing carries the DG metadata inline, so a deltaspace scan is one
+    /// paged LIST with no per-delta metadata GET.
+    #[instrument(skip(self))]
+    async fn scan_deltaspace(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<FileMetadata>, StorageError> {
+        let search_prefix = if prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", prefix)
+        };
+        let scanning_root = prefix.is_empty();
+        let metadata: Vec<FileMetadata> = self
+            .list_all(bucket, &search_prefix)
+            .await?
+            .into_iter()
+            .filter(|object| !(scanning_root && object.name.contains('/')))
+            .map(|object| object.file_metadata())
+            .collect();
+        debug!(
+            "Scanned {} objects in deltaspace {}/{}",
+            metadata.len(),
+            bucket,
+            prefix
+        );
+        Ok(metadata)
+    }
+
+    #[instrument(skip(self))]
+    async fn list_deltaspaces(&self, bucket: &str) -> Result<Vec<String>, StorageError> {
+        let prefixes: HashSet<String> = self
+            .list_all(bucket, "")
+            .await?
+            .into_iter()
+            .map(|object| match object.name.rfind('/') {
+                Some(idx) => object.name[..idx].to_string(),
+                None => String::new(),
+            })
+            .collect();
+        debug!("Found {} deltaspaces in bucket {}", prefixes.len(), bucket);
+        Ok(prefixes.into_iter().collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn total_size(&self, bucket: Option<&str>) -> Result<u64, StorageError> {
+        let buckets = match bucket {
+            Some(b) => vec![b.to_string()],
+            None => self.list_buckets().await?,
+        };
+        let mut total = 0u64;
+        for b in &buckets {
+            total += self
+                .list_all(b, "")
+                .await?
+                .iter()
+                .map(GcsObject::size)
+                .sum::<u64>();
+        }
+        Ok(total)
+    }
+
+    async fn put_directory_marker(&self, bucket: &str, key: &str) -> Result<(), StorageError> {
+        self.put_media(bucket, key, Bytes::new(), Some("application/x-directory"))
+            .await?;
+        debug!("Created directory marker: {}/{}", bucket, key);
+        Ok(())
+    }
+
+    async fn bulk_list_objects(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<(String, FileMetadata)>, StorageError> {
+        let results = Self::listed_entries(self.list_all(bucket, prefix).await?);
+        debug!(
+            "Bulk listed {} objects in {}/{}",
+            results.len(),
+            bucket,
+            prefix
+        );
+        Ok(results)
+    }
+
+    /// Delimiter collapsing is delegated to objects.list, with the same
+    /// anchor early exit and candidate probes as the S3 backend. A continued
+    /// page starts at the continuation token via `startOffset` (inclusive,
+    /// so the token itself is filtered back out).
+    #[instrument(skip(self))]
+    async fn list_objects_delegated(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        delimiter: Option<&str>,
+        max_keys: u32,
+        continuation_token: Option<&str>,
+    ) -> Result<Option<DelegatedListResult>, StorageError> {
+        let token = continuation_token.unwrap_or("");
+        let mut common_prefixes = BTreeSet::new();
+        let mut raw: Vec<GcsObject> = Vec::new();
+        let mut page_token: Option<String> = None;
+        let mut settled_anchor: Option<String> = None;
+
+        loop {
+            // `startOffset` only on the first page; the page token carries
+            // the position afterwards.
+            let start_offset = if page_token.is_none() {
+                continuation_token
+            } else {
+                None
+            };
+            let page = self
+                .list_page(
+                    bucket,
+                    prefix,
+                    delimiter,
+                    page_token.as_deref(),
+                    None,
+                    start_offset,
+                )
+                .await?;
+            for p in page.prefixes {
+                // Only the internal `.dg/` directory is hidden; other
+                // dot-prefixed folders are legitimate user keys.
+                let last_seg = p.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+                if last_seg != ".dg" && p.as_str() > token {
+                    common_prefixes.insert(p);
+                }
+            }
+            raw.extend(page.items.into_iter().filter(|o| o.name.as_str() > token));
+            match page.next_page_token {
+                Some(t) if !t.is_empty() => page_token = Some(t),
+                _ => break,
+            }
+            if let Some(anchor) = list_anchor(
+                raw.iter().map(|o| o.name.as_str()),
+                common_prefixes.iter().map(|p| p.as_str()),
+                max_keys,
+                continuation_token,
+            ) {
+                if raw.last().is_some_and(|o| o.name > anchor) {
+                    settled_anchor = Some(anchor);
+                    break;
+                }
+            }
+        }
+
+        // Confirm the bounded set of `p.delta` keys that can sort past the
+        // anchor yet belong on this page (see `confirmable_candidates`).
+        if let Some(anchor) = settled_anchor {
+            let last_read = raw.last().map(|o| o.name.clone());
+            let candidates =
+                confirmable_candidates(&anchor, prefix, delimiter, last_read.as_deref());
+            let probes = candidates.into_iter().map(|candidate| async move {
+                let page = self
+                    .list_page(bucket, &candidate, None, None, Some(3), None)
+                    .await?;
+                Ok::<_, StorageError>(
+                    page.items
+                        .into_iter()
+                        .filter(|o| probe_hit_serves_candidate(&o.name, &candidate))
+                        .collect::<Vec<_>>(),
+                )
+            });
+            let results: Vec<Result<Vec<GcsObject>, StorageError>> = futures::stream::iter(probes)
+                .buffer_unordered(MAX_CONCURRENT_PROBES)
+                .collect()
+                .await;
+            for result in results {
+                raw.extend(result?);
+            }
+        }
+
+        let objects = Self::listed_entries(raw);
+        let page = crate::deltaglider::interleave_and_paginate(
+            objects,
+            common_prefixes.into_iter().collect(),
+            max_keys,
+            continuation_token,
+        );
+        debug!(
+            "Delegated list: {} objects + {} prefixes in {}/{}",
+            page.objects.len(),
+            page.common_prefixes.len(),
+            bucket,
+            prefix
+        );
+        Ok(Some(DelegatedListResult {
+            objects: page.objects,
+            common_prefixes: page.common_prefixes,
+            is_truncated: page.is_truncated,
+            next_continuation_token: page.next_continuation_token,
+        }))
+    }
+}
+
+/// One JSON API request before authentication.
+struct GcsRequest<'a> {
+    method: Method,
+    /// Absolute URL without the query string.
+    url: String,
+    /// Bucket and object the request addresses (error classification and
+    /// logs); `bucket` is empty for project-level calls.
+    bucket: &'a str,
+    object: Option<&'a str>,
+    query: Vec<(&'static str, String)>,
+    headers: Vec<(String, String)>,
+    body: Option<Bytes>,
+}
+
+impl<'a> GcsRequest<'a> {
+    fn new(method: Method, url: String, bucket: &'a str, object: Option<&'a str>) -> Self {
+        Self {
+            method,
+            url,
+            bucket,
+            object,
+            query: Vec::new(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    fn query(mut self, name: &'static str, value: &str) -> Self {
+        self.query.push((name, value.to_string()));
+        self
+    }
+
+    fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn body(mut self, body: Bytes) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Full URL with percent-encoded query parameters (never `+` for space),
+    /// appended to any the URL already carries (a resumable session's
+    /// `upload_id`).
+    fn url(&self) -> Result<Url, StorageError> {
+        let mut url = self.url.clone();
+        for (k, v) in &self.query {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(k);
+            url.push('=');
+            url.push_str(&urlencoding::encode(v));
+        }
+        Url::parse(&url).map_err(|e| StorageError::Other(format!("invalid GCS URL {url}: {e}")))
+    }
+}
+
+/// An open resumable upload session. Bytes are buffered up to
+/// [`UPLOAD_CHUNK`] and flushed as 256 KiB-aligned chunks; the final chunk
+/// (any size) goes out on [`finish`](Self::finish).
+struct ResumableUpload {
+    bucket: String,
+    key: String,
+    session_uri: String,
+    /// Bytes the service has acknowledged.
+    offset: u64,
+    total: u64,
+    pending: Vec<u8>,
+}
+
+impl ResumableUpload {
+    /// Append bytes, flushing every full chunk.
+    async fn write(&mut self, backend: &GcsBackend, mut data: &[u8]) -> Result<(), StorageError> {
+        while !data.is_empty() {
+            let take = (UPLOAD_CHUNK - self.pending.len()).min(data.len());
+            if take == 0 {
+                return Err(StorageError::Other(format!(
+                    "GCS resumable upload {}/{} exceeds its declared {} bytes",
+                    self.bucket, self.key, self.total
+                )));
+            }
+            self.pending.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            // Hold a full chunk back when it is the last one, so `finish`
+            // always has the final (size-stamped) PUT to send.
+            let is_last = self.offset + self.pending.len() as u64 >= self.total;
+            if self.pending.len() == UPLOAD_CHUNK && !is_last {
+                self.flush(backend).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Append a reader's full contents, [`UPLOAD_CHUNK`] at a time.
+    async fn write_from<R: tokio::io::AsyncRead + Unpin>(
+        &mut self,
+        backend: &GcsBackend,
+        reader: &mut R,
+    ) -> Result<(), StorageError> {
+        let mut buf = Vec::with_capacity(UPLOAD_CHUNK);
+        loop {
+            buf.clear();
+            (&mut *reader)
+                .take(UPLOAD_CHUNK as u64)
+                .read_to_end(&mut buf)
+                .await?;
+            if buf.is_empty() {
+                return Ok(());
+            }
+            self.write(backend, &buf).await?;
+        }
+    }
+
+    async fn flush(&mut self, backend: &GcsBackend) -> Result<(), StorageError> {
+        let chunk = Bytes::from(std::mem::replace(
+            &mut self.pending,
+            Vec::with_capacity(UPLOAD_CHUNK),
+        ));
+        let len = chunk.len() as u64;
+        backend.put_resumable_chunk(self, chunk).await?;
+        self.offset += len;
+        Ok(())
+    }
+
+    /// Send the final chunk. The byte count must match the size declared
+    /// when the session was opened.
+    async fn finish(mut self, backend: &GcsBackend) -> Result<(), StorageError> {
+        let sent = self.offset + self.pending.len() as u64;
+        if sent != self.total {
+            return Err(StorageError::Other(format!(
+                "GCS resumable upload {}/{} got {} bytes, expected {}",
+                self.bucket, self.key, sent, self.total
+            )));
+        }
+        self.flush(backend).await
+    }
+}
+
+// === Auth ===
+
+/// Where access tokens come from.
+enum Credential {
+    /// No `Authorization` header (fake-gcs-server and other emulators).
+    Anonymous,
+    /// JWT-bearer grant signed with a service-account key.
+    ServiceAccount {
+        email: String,
+        signing_key: jsonwebtoken::EncodingKey,
+        token_uri: String,
+    },
+    /// The GCE/GKE metadata server (workload identity).
+    MetadataServer(reqwest::Client),
+}
+
+/// Caches one access token and refreshes it shortly before expiry.
+struct TokenSource {
+    credential: Credential,
+    cached: tokio::sync::Mutex<Option<(String, std::time::Instant)>>,
+}
+
+impl TokenSource {
+    fn new(credential: Credential) -> Self {
+        Self {
+            credential,
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Current bearer token, or `None` for anonymous access.
+    async fn token(&self, http: &reqwest::Client) -> Result<Option<String>, StorageError> {
+        if matches!(self.credential, Credential::Anonymous) {
+            return Ok(None);
+        }
+        let mut cached = self.cached.lock().await;
+        if let Some((token, expires)) = cached.as_ref() {
+            if std::time::Instant::now() + TOKEN_REFRESH_MARGIN < *expires {
+                return Ok(Some(token.clone()));
+            }
+        }
+        let fetched = self.fetch(http).await?;
+        let expires =
+            std::time::Instant::now() + std::time::Duration::from_secs(fetched.expires_in);
+        *cached = Some((fetched.access_token.clone(), expires));
+        Ok(Some(fetched.access_token))
+    }
+
+    async fn fetch(&self, http: &reqwest::Client) -> Result<TokenResponse, StorageError> {
+        let resp = match &self.credential {
+            Credential::Anonymous => unreachable!("anonymous access never fetches a token"),
+            Credential::ServiceAccount {
+                email,
+                signing_key,
+                token_uri,
+            } => {
+                let now = Utc::now().timestamp();
+                let assertion = jsonwebtoken::encode(
+                    &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256),
+                    &JwtClaims {
+                        iss: email,
+                        scope: OAUTH_SCOPE,
+                        aud: token_uri,
+                        iat: now,
+                        exp: now + 3600,
+                    },
+                    signing_key,
+                )
+                .map_err(|e| StorageError::Other(format!("GCS JWT signing failed: {e}")))?;
+                let form = serde_urlencoded::to_string([
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                    ("assertion", assertion.as_str()),
+                ])
+                .map_err(|e| StorageError::Other(format!("GCS token request: {e}")))?;
+                http.post(token_uri)
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(form)
+                    .send()
+                    .await
+            }
+            Credential::MetadataServer(metadata_http) => {
+                metadata_http
+                    .get(METADATA_TOKEN_URL)
+                    .header("Metadata-Flavor", "Google")
+                    .send()
+                    .await
+            }
+        }
+        .map_err(|e| StorageError::Other(format!("GCS token request failed: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(StorageError::Other(format!(
+                "GCS token endpoint rejected the credentials ({status}): {body}"
+            )));
+        }
+        serde_json::from_str(&body)
+            .map_err(|e| StorageError::Other(format!("invalid GCS token response: {e}")))
+    }
+}
+
+#[derive(Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    #[serde(default)]
+    token_uri: Option<String>,
+    #[serde(default)]
+    project_id: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct JwtClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default = "default_token_lifetime")]
+    expires_in: u64,
+}
+
+fn default_token_lifetime() -> u64 {
+    3600
+}
+
+// === Pure helpers ===
+
+/// Staged object name for one multipart part (zero-padded so staging
+/// listings sort in part order).
+fn part_object_name(upload_id: &str, part_number: i32) -> String {
+    format!(
+        "{}p{:05}",
+        GcsBackend::staging_prefix(upload_id),
+        part_number.max(0)
+    )
+}
+
+/// JSON object resource for uploads and compose destinations.
+fn object_resource(
+    name: &str,
+    content_type: &str,
+    metadata: Option<&FileMetadata>,
+) -> serde_json::Value {
+    let mut resource = serde_json::json!({ "name": name, "contentType": content_type });
+    if let Some(metadata) = metadata {
+        resource["metadata"] = serde_json::json!(metadata.to_bare_metadata_map());
+    }
+    resource
+}
+
+/// `multipart/related` upload body: the JSON resource, then the media.
+fn multipart_related_body(boundary: &str, resource: &serde_json::Value, data: &[u8]) -> Bytes {
+    let head = format!(
+        "--{boundary}\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{resource}\r\n\
+         --{boundary}\r\nContent-Type: application/octet-stream\r\n\r\n"
+    );
+    let tail = format!("\r\n--{boundary}--\r\n");
+    let mut body = Vec::with_capacity(head.len() + data.len() + tail.len());
+    body.extend_from_slice(head.as_bytes());
+    body.extend_from_slice(data);
+    body.extend_from_slice(tail.as_bytes());
+    Bytes::from(body)
+}
+
+/// PATCH body that turns `current` metadata into exactly `desired`: PATCH
+/// merges, so dropped keys are cleared with `null`.
+fn metadata_patch(
+    current: &HashMap<String, String>,
+    desired: &HashMap<String, String>,
+) -> serde_json::Value {
+    let mut map = serde_json::Map::new();
+    for key in current.keys().filter(|k| !desired.contains_key(*k)) {
+        map.insert(key.clone(), serde_json::Value::Null);
+    }
+    for (key, value) in desired {
+        map.insert(key.clone(), serde_json::Value::String(value.clone()));
+    }
+    serde_json::json!({ "metadata": map })
+}
+
+/// `Content-Range` for a resumable chunk of `len` bytes at `offset`.
+fn content_range(offset: u64, len: u64, total: u64) -> String {
+    if len == 0 {
+        format!("bytes */{total}")
+    } else {
+        format!("bytes {}-{}/{}", offset, offset + len - 1, total)
+    }
+}
+
+fn parse_rfc3339(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Stream a response body chunk by chunk. A read error is yielded once and
+/// ends the stream.
+fn body_stream(resp: reqwest::Response) -> BoxStream<'static, Result<Bytes, StorageError>> {
+    Box::pin(futures::stream::unfold(Some(resp), |resp| async move {
+        let mut resp = resp?;
+        match resp.chunk().await {
+            Ok(Some(chunk)) => Some((Ok(chunk), Some(resp))),
+            Ok(None) => None,
+            Err(e) => Some((
+                Err(StorageError::Other(format!(
+                    "Failed to read GCS response body: {e}"
+                ))),
+                None,
+            )),
+        }
+    }))
+}
+
+/// Turn a non-2xx response into a `StorageError`, reading the JSON error
+/// envelope's `message` when there is one.
+async fn error_from_response(
+    resp: reqwest::Response,
+    bucket: &str,
+    object: Option<&str>,
+) -> StorageError {
+    let status = resp.status().as_u16();
+    let body = resp.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<ErrorEnvelope>(&body)
+        .map(|e| e.error.message)
+        .unwrap_or(body);
+    classify_error(status, bucket, object, &message)
+}
+
+/// Map a JSON API error onto `StorageError`. Pure — unit-tested.
+///
+/// A 404 on an object call is `NotFound` unless GCS says the BUCKET is
+/// missing; a 404 on a bucket-level call is `BucketNotFound`. 429/503 are
+/// `Throttled` so the API layer answers SlowDown rather than a permanent
+/// 500.
+fn classify_error(status: u16, bucket: &str, object: Option<&str>, message: &str) -> StorageError {
+    let bucket_missing = message.to_ascii_lowercase().contains("bucket");
+    match (status, object) {
+        (404, Some(object)) if !bucket_missing => StorageError::NotFound(object.to_string()),
+        (404, _) => StorageError::BucketNotFound(bucket.to_string()),
+        (409, None) => StorageError::AlreadyExists(bucket.to_string()),
+        (409, Some(object)) => StorageError::AlreadyExists(object.to_string()),
+        (429 | 503, _) => StorageError::Throttled(format!("GCS {status} on {bucket}: {message}")),
+        _ => StorageError::Other(format!(
+            "GCS error {status} on {}/{}: {message}",
+            bucket,
+            object.unwrap_or("")
+        )),
+    }
+}
+
+// === JSON shapes ===
+
+#[derive(Deserialize)]
+struct ErrorEnvelope {
+    error: ErrorBody,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    message: String,
+}
+
+/// An object resource, as returned by objects.get and objects.list.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcsObject {
+    name: String,
+    /// Decimal string (int64 in the JSON API).
+    #[serde(default)]
+    size: Option<String>,
+    #[serde(default)]
+    updated: Option<String>,
+    #[serde(default)]
+    md5_hash: Option<String>,
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    content_type: Option<String>,
+    #[serde(default)]
+    metageneration: Option<String>,
+    /// Bare DG metadata (`dg-*`, `user-*`, `content-type`).
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+impl GcsObject {
+    fn size(&self) -> u64 {
+        self.size
+            .as_deref()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0)
+    }
+
+    fn updated_at(&self) -> Option<DateTime<Utc>> {
+        self.updated.as_deref().and_then(parse_rfc3339)
+    }
+
+    /// S3-shaped ETag for an object without DG metadata: the hex MD5 when
+    /// GCS has one (composite objects don't), else GCS's own opaque ETag.
+    fn etag(&self) -> String {
+        self.md5_hash
+            .as_deref()
+            .and_then(|m| base64::engine::general_purpose::STANDARD.decode(m).ok())
+            .map(hex::encode)
+            .unwrap_or_else(|| self.etag.clone().unwrap_or_default())
+    }
+
+    /// Full DG metadata when the object carries it; otherwise the same
+    /// listing-only fallback the S3 backend builds (delta stub / reference /
+    /// passthrough by key shape).
+    fn file_metadata(&self) -> FileMetadata {
+        let last_modified = self.updated_at().unwrap_or_else(Utc::now);
+        if !self.metadata.is_empty() {
+            if let Ok(meta) = S3Backend::headers_to_metadata(&self.metadata, last_modified) {
+                return meta;
+            }
+        }
+        let filename = self.name.rsplit('/').next().unwrap_or(&self.name);
+        let size = self.size();
+        let storage_info = if filename.ends_with(".delta") {
+            StorageInfo::delta_stub(size)
+        } else if filename == "reference.bin" {
+            StorageInfo::Reference {
+                source_name: String::new(),
+            }
+        } else {
+            StorageInfo::Passthrough
+        };
+        FileMetadata::fallback(
+            filename.trim_end_matches(".delta").to_string(),
+            size,
+            self.etag(),
+            last_modified,
+            self.content_type.clone(),
+            storage_info,
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectPage {
+    #[serde(default)]
+    items: Vec<GcsObject>,
+    #[serde(default)]
+    prefixes: Vec<String>,
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketPage {
+    #[serde(default)]
+    items: Vec<BucketResource>,
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketResource {
+    name: String,
+    #[serde(default)]
+    time_created: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_urls_encode_the_name_as_one_segment() {
+        let backend_url = |bucket: &str, object: &str| {
+            format!(
+                "https://storage.googleapis.com/storage/v1/b/{}/o/{}",
+                urlencoding::encode(bucket),
+                urlencoding::encode(object)
+            )
+        };
+        assert_eq!(
+            backend_url("b", "releases/v1 final.zip"),
+            "https://storage.googleapis.com/storage/v1/b/b/o/releases%2Fv1%20final.zip"
+        );
+        let req = GcsRequest::new(Method::GET, backend_url("b", "a/b"), "b", Some("a/b"))
+            .query("alt", "media")
+            .query("name", "x y&z");
+        assert_eq!(
+            req.url().unwrap().as_str(),
+            "https://storage.googleapis.com/storage/v1/b/b/o/a%2Fb?alt=media&name=x%20y%26z"
+        );
+    }
+
+    #[test]
+    fn multipart_body_frames_resource_then_media() {
+        let resource = object_resource("k", "application/octet-stream", None);
+        let body = multipart_related_body("XYZ", &resource, b"payload");
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.starts_with("--XYZ\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{"));
+        assert!(text.contains("\"name\":\"k\""));
+        assert!(text.ends_with(
+            "--XYZ\r\nContent-Type: application/octet-stream\r\n\r\npayload\r\n--XYZ--\r\n"
+        ));
+    }
+
+    #[test]
+    fn metadata_patch_clears_dropped_keys() {
+        let current = HashMap::from([
+            ("dg-tool".to_string(), "old".to_string()),
+            ("user-stale".to_string(), "x".to_string()),
+        ]);
+        let desired = HashMap::from([
+            ("dg-tool".to_string(), "new".to_string()),
+            ("user-owner".to_string(), "équipe".to_string()),
+        ]);
+        let patch = metadata_patch(&current, &desired);
+        assert_eq!(patch["metadata"]["dg-tool"], "new");
+        assert_eq!(patch["metadata"]["user-owner"], "équipe");
+        assert!(patch["metadata"]["user-stale"].is_null());
+        assert_eq!(patch["metadata"].as_object().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn content_range_shapes() {
+        assert_eq!(content_range(0, 10, 25), "bytes 0-9/25");
+        assert_eq!(content_range(10, 15, 25), "bytes 10-24/25");
+        assert_eq!(content_range(0, 0, 0), "bytes */0");
+        assert_eq!(
+            UPLOAD_CHUNK % (256 * 1024),
+            0,
+            "chunks must be 256 KiB aligned"
+        );
+    }
+
+    #[test]
+    fn part_names_sort_in_part_order() {
+        let a = part_object_name("u1", 2);
+        let b = part_object_name("u1", 10);
+        assert_eq!(a, ".deltaglider/mpu/u1/p00002");
+        assert!(a < b);
+    }
+
+    #[test]
+    fn classify_error_truth_table() {
+        assert!(matches!(
+            classify_error(404, "b", Some("k"), "No such object: b/k"),
+            StorageError::NotFound(k) if k == "k"
+        ));
+        assert!(matches!(
+            classify_error(404, "b", Some("k"), "The specified bucket does not exist."),
+            StorageError::BucketNotFound(b) if b == "b"
+        ));
+        assert!(matches!(
+            classify_error(404, "b", None, "Not Found"),
+            StorageError::BucketNotFound(_)
+        ));
+        assert!(matches!(
+            classify_error(409, "b", None, "You already own this bucket."),
+            StorageError::AlreadyExists(b) if b == "b"
+        ));
+        assert!(matches!(
+            classify_error(429, "b", Some("k"), "rate"),
+            StorageError::Throttled(_)
+        ));
+        assert!(matches!(
+            classify_error(503, "b", None, "busy"),
+            StorageError::Throttled(_)
+        ));
+        assert!(matches!(
+            classify_error(412, "b", Some("k"), "Precondition Failed"),
+            StorageError::Other(_)
+        ));
+    }
+
+    #[test]
+    fn parses_object_listing_with_metadata_and_prefixes() {
+        let json = r#"{
+            "kind": "storage#objects",
+            "prefixes": ["dir/"],
+            "items": [
+                {"name": "a.txt", "size": "5", "updated": "2026-01-02T03:04:05.000Z",
+                 "md5Hash": "XUFAKrxLKna5cZ2REBfFkg==", "etag": "CJ+2", "contentType": "text/plain",
+                 "metageneration": "1"},
+                {"name": "builds/v2.zip.delta", "size": "42", "etag": "CAE=",
+                 "metadata": {"dg-tool": "deltaglider"}}
+            ],
+            "nextPageToken": "tok"
+        }"#;
+        let page: ObjectPage = serde_json::from_str(json).unwrap();
+        assert_eq!(page.prefixes, vec!["dir/"]);
+        assert_eq!(page.next_page_token.as_deref(), Some("tok"));
+        let a = &page.items[0];
+        assert_eq!(a.size(), 5);
+        assert_eq!(a.etag(), "5d41402abc4b2a76b9719d911017c592");
+        assert_eq!(
+            a.updated_at().unwrap().to_rfc3339(),
+            "2026-01-02T03:04:05+00:00"
+        );
+        let delta = &page.items[1];
+        assert_eq!(delta.etag(), "CAE=");
+        assert_eq!(delta.metadata["dg-tool"], "deltaglider");
+        // Incomplete DG metadata falls back to the key-shape stub.
+        let meta = delta.file_metadata();
+        assert_eq!(meta.original_name, "v2.zip");
+        assert!(meta.is_delta());
+
+        let empty: ObjectPage = serde_json::from_str(r#"{"kind":"storage#objects"}"#).unwrap();
+        assert!(empty.items.is_empty() && empty.next_page_token.is_none());
+    }
+
+    #[test]
+    fn parses_error_envelope_and_bucket_listing() {
+        let err: ErrorEnvelope = serde_json::from_str(
+            r#"{"error":{"code":404,"message":"No such object: b/k","errors":[]}}"#,
+        )
+        .unwrap();
+        assert_eq!(err.error.message, "No such object: b/k");
+
+        let page: BucketPage = serde_json::from_str(
+            r#"{"items":[{"name":"b1","timeCreated":"2026-03-04T00:00:00Z"},{"name":"b2"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(page.items.len(), 2);
+        assert!(page.items[0]
+            .time_created
+            .as_deref()
+            .and_then(parse_rfc3339)
+            .is_some());
+    }
+}
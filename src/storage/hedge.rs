@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Hedged passthrough reads.
+//!
+//! A bucket with a `hedge` policy has a second copy: its write-through
+//! `mirror`, else its `read_fallback` replica. [`super::RoutingBackend`]
+//! opens a passthrough GET on the preferred copy and waits for its first
+//! byte. If none arrives within the threshold, the same read goes to the
+//! other copy and whichever produces a first byte first is served; the
+//! loser is dropped (which cancels it). A read that fails on the preferred
+//! copy also goes to the other one.
+//!
+//! The other copy is only served when its stored `file_sha256` and
+//! `file_size` match the metadata the engine resolved for the object, so a
+//! replica that replication hasn't caught up yet never answers.
+//!
+//! The threshold is the bucket's `after_ms`, else the p95 of the preferred
+//! backend's recent times-to-first-byte on hedged reads — at least
+//! `DGP_HEDGE_MIN_DELAY_MS` (default 20), and `DGP_HEDGE_DEFAULT_DELAY_MS`
+//! (default 500) until the backend has [`MIN_SAMPLES`] samples.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use futures::stream::{BoxStream, StreamExt};
+use parking_lot::Mutex;
+
+use super::traits::StorageError;
+use crate::metrics::Metrics;
+use crate::types::FileMetadata;
+
+/// Samples a backend needs before its p95 replaces the default threshold.
+pub const MIN_SAMPLES: usize = 20;
+
+/// Recent first-byte latencies kept per backend.
+const WINDOW: usize = 256;
+
+/// Where a hedged bucket's second copy lives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HedgeCopy {
+    /// The bucket's write-through mirror.
+    Mirror,
+    /// A replica bucket (virtual name) kept by replication.
+    Replica(String),
+}
+
+/// One hedged bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HedgeRoute {
+    pub copy: HedgeCopy,
+    /// Fixed threshold; `None` = derived from the backend's latencies.
+    pub after: Option<Duration>,
+}
+
+/// The hedged buckets of one engine and the latencies feeding their
+/// thresholds.
+pub struct HedgeSet {
+    routes: HashMap<String, HedgeRoute>,
+    /// Backend name → recent times to first byte, oldest first.
+    samples: Mutex<HashMap<String, VecDeque<Duration>>>,
+    min_delay: Duration,
+    default_delay: Duration,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl HedgeSet {
+    pub fn new(routes: HashMap<String, HedgeRoute>, metrics: Option<Arc<Metrics>>) -> Self {
+        Self {
+            routes,
+            samples: Mutex::new(HashMap::new()),
+            min_delay: Duration::from_millis(crate::config::env_parse_with_default(
+                "DGP_HEDGE_MIN_DELAY_MS",
+                20u64,
+            )),
+            default_delay: Duration::from_millis(crate::config::env_parse_with_default(
+                "DGP_HEDGE_DEFAULT_DELAY_MS",
+                500u64,
+            )),
+            metrics,
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(HashMap::new(), None)
+    }
+
+    pub fn route(&self, bucket: &str) -> Option<&HedgeRoute> {
+        self.routes.get(bucket)
+    }
+
+    /// Hedged bucket names, sorted.
+    pub fn buckets(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.routes.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// p95 of `backend`'s recent first-byte latencies, once it has enough.
+    fn p95(&self, backend: &str) -> Option<Duration> {
+        let samples = self.samples.lock();
+        let window = samples.get(backend)?;
+        if window.len() < MIN_SAMPLES {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = window.iter().copied().collect();
+        sorted.sort_unstable();
+        let idx = (sorted.len() * 95).div_ceil(100) - 1;
+        Some(sorted[idx])
+    }
+
+    /// How long a read on `backend` runs alone before it is hedged.
+    pub(super) fn delay(&self, route: &HedgeRoute, backend: &str) -> Duration {
+        route.after.unwrap_or_else(|| {
+            self.p95(backend)
+                .unwrap_or(self.default_delay)
+                .max(self.min_delay)
+        })
+    }
+
+    fn sample(&self, backend: &str, elapsed: Duration) {
+        let mut samples = self.samples.lock();
+        let window = samples.entry(backend.to_string()).or_default();
+        if window.len() == WINDOW {
+            window.pop_front();
+        }
+        window.push_back(elapsed);
+    }
+
+    /// A first byte arrived from `backend` after `elapsed`.
+    fn record(&self, backend: &str, elapsed: Duration) {
+        self.sample(backend, elapsed);
+        if let Some(m) = &self.metrics {
+            m.backend_first_byte_seconds
+                .with_label_values(&[backend])
+                .observe(elapsed.as_secs_f64());
+        }
+    }
+
+    fn note_winner(&self, winner: &str) {
+        if let Some(m) = &self.metrics {
+            m.hedged_reads_total.with_label_values(&[winner]).inc();
+        }
+    }
+
+    /// Run `primary` and, past the threshold or on its failure, `copy` (see
+    /// the module docs). Both futures must resolve once the first byte is in
+    /// hand — see [`first_byte`].
+    pub(super) async fn race<T, P, C, F>(
+        &self,
+        route: &HedgeRoute,
+        primary_name: &str,
+        primary: P,
+        copy_name: &str,
+        copy: F,
+    ) -> Result<T, StorageError>
+    where
+        P: Future<Output = Result<T, StorageError>>,
+        F: FnOnce() -> C,
+        C: Future<Output = Result<T, StorageError>>,
+    {
+        let delay = self.delay(route, primary_name);
+        let started = Instant::now();
+        tokio::pin!(primary);
+        // A zero threshold hedges every read up front rather than racing
+        // the primary against an already-expired timer.
+        let early = if delay.is_zero() {
+            None
+        } else {
+            match tokio::time::timeout(delay, &mut primary).await {
+                Ok(Ok(v)) => {
+                    self.record(primary_name, started.elapsed());
+                    return Ok(v);
+                }
+                Ok(Err(err)) => Some(err),
+                Err(_) => None,
+            }
+        };
+
+        let copy_started = Instant::now();
+        let copy = copy();
+        tokio::pin!(copy);
+        if let Some(err) = early {
+            return match copy.await {
+                Ok(v) => {
+                    self.record(copy_name, copy_started.elapsed());
+                    self.note_winner("copy");
+                    Ok(v)
+                }
+                Err(_) => Err(err),
+            };
+        }
+        tokio::select! {
+            res = &mut primary => match res {
+                Ok(v) => {
+                    self.record(primary_name, started.elapsed());
+                    self.note_winner("primary");
+                    Ok(v)
+                }
+                Err(err) => match copy.await {
+                    Ok(v) => {
+                        self.record(copy_name, copy_started.elapsed());
+                        self.note_winner("copy");
+                        Ok(v)
+                    }
+                    Err(_) => Err(err),
+                },
+            },
+            res = &mut copy => match res {
+                Ok(v) => {
+                    self.record(copy_name, copy_started.elapsed());
+                    // The primary's first byte is at least this late: keep
+                    // the lower bound so slow reads still raise its p95.
+                    self.sample(primary_name, started.elapsed());
+                    self.note_winner("copy");
+                    Ok(v)
+                }
+                Err(_) => {
+                    let res = primary.await;
+                    if res.is_ok() {
+                        self.record(primary_name, started.elapsed());
+                        self.note_winner("primary");
+                    }
+                    res
+                }
+            },
+        }
+    }
+}
+
+/// Wait for `stream`'s first chunk and put it back in front, so a read
+/// counts as answered once data (or end of stream) has arrived.
+pub(super) async fn first_byte(
+    mut stream: BoxStream<'static, Result<Bytes, StorageError>>,
+) -> Result<BoxStream<'static, Result<Bytes, StorageError>>, StorageError> {
+    match stream.next().await {
+        Some(Ok(chunk)) => Ok(futures::stream::once(async move { Ok(chunk) })
+            .chain(stream)
+            .boxed()),
+        Some(Err(err)) => Err(err),
+        None => Ok(futures::stream::empty().boxed()),
+    }
+}
+
+/// Err unless the copy's metadata is the object the engine resolved.
+pub(super) fn check_copy(
+    found: &FileMetadata,
+    expected: &FileMetadata,
+    filename: &str,
+) -> Result<(), StorageError> {
+    if found.file_sha256 == expected.file_sha256 && found.file_size == expected.file_size {
+        Ok(())
+    } else {
+        Err(StorageError::Other(format!(
+            "hedge copy of '{filename}' differs from the object being read"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(after_ms: Option<u64>) -> HedgeRoute {
+        HedgeRoute {
+            copy: HedgeCopy::Replica("replica".into()),
+            after: after_ms.map(Duration::from_millis),
+        }
+    }
+
+    #[test]
+    fn threshold_follows_p95_once_enough_samples() {
+        let set = HedgeSet::empty();
+        let derived = route(None);
+        assert_eq!(set.delay(&derived, "slow"), set.default_delay);
+        for ms in 1..=100 {
+            set.record("slow", Duration::from_millis(ms * 10));
+        }
+        assert_eq!(set.delay(&derived, "slow"), Duration::from_millis(950));
+        for _ in 0..MIN_SAMPLES {
+            set.record("fast", Duration::from_micros(10));
+        }
+        assert_eq!(set.delay(&derived, "fast"), set.min_delay);
+        assert_eq!(set.delay(&route(Some(7)), "slow"), Duration::from_millis(7));
+    }
+
+    #[tokio::test]
+    async fn slow_primary_loses_to_the_copy_and_failures_fall_over() {
+        let set = HedgeSet::empty();
+        let hedged = route(Some(10));
+        let slow = async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok("primary")
+        };
+        let won = set
+            .race(&hedged, "p", slow, "c", || async { Ok("copy") })
+            .await;
+        assert_eq!(won.unwrap(), "copy");
+
+        let fast = async { Ok("primary") };
+        let won = set
+            .race(&hedged, "p", fast, "c", || async { Ok("copy") })
+            .await;
+        assert_eq!(won.unwrap(), "primary");
+
+        let failed = async { Err(StorageError::Other("down".into())) };
+        let won = set
+            .race(&hedged, "p", failed, "c", || async { Ok("copy") })
+            .await;
+        assert_eq!(won.unwrap(), "copy");
+
+        let failed = async { Err::<&str, _>(StorageError::Other("down".into())) };
+        let stale = || async { Err(StorageError::Other("differs".into())) };
+        let err = set
+            .race(&hedged, "p", failed, "c", stale)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("down"), "{err}");
+    }
+}
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Offline migration of a filesystem backend between metadata modes
+//! (`xattr` ↔ `sidecar`).
+//!
+//! Walks `{root}/{bucket}/deltaspaces/**` and moves each data file's
+//! metadata to the target mode: the new copy is written first and the old
+//! one removed after, so an interrupted run loses nothing and simply picks
+//! up where it stopped when re-run. Files carrying neither are unmanaged
+//! (served from filesystem stats in either mode) and are left alone.
+//! Sidecars whose data file is gone are removed.
+//!
+//! Run it with the proxy stopped: a write racing the migration may land
+//! in the old mode.
+
+use super::traits::StorageError;
+use super::{io_to_storage_error, sidecar_meta, xattr_meta};
+use crate::config::FilesystemMetadataMode;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// What [`migrate_filesystem_metadata`] did (or would do, on a dry run).
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct MetadataMigrationReport {
+    pub to: &'static str,
+    /// Data files whose metadata was moved.
+    pub migrated: u64,
+    /// Data files already in the target mode.
+    pub already_migrated: u64,
+    /// Data files with no metadata in either mode.
+    pub unmanaged: u64,
+    /// Sidecars removed because their data file no longer exists.
+    pub orphans_removed: u64,
+    /// Files that could not be migrated, with the reason.
+    pub errors: Vec<String>,
+    pub dry_run: bool,
+}
+
+/// Move every data file under `root` to metadata mode `to`. Idempotent.
+pub async fn migrate_filesystem_metadata(
+    root: &Path,
+    to: FilesystemMetadataMode,
+    dry_run: bool,
+) -> Result<MetadataMigrationReport, StorageError> {
+    if !root.is_dir() {
+        return Err(StorageError::NotFound(root.display().to_string()));
+    }
+    if to == FilesystemMetadataMode::Xattr && !dry_run {
+        xattr_meta::validate_xattr_support(root).await?;
+    }
+    let root = root.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut report = MetadataMigrationReport {
+            to: to.as_str(),
+            dry_run,
+            ..Default::default()
+        };
+        for bucket in read_dir(&root)? {
+            let deltaspaces = bucket.join("deltaspaces");
+            if deltaspaces.is_dir() {
+                walk(&deltaspaces, to, &mut report)?;
+            }
+        }
+        Ok(report)
+    })
+    .await
+    .map_err(super::join_error)?
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, StorageError> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_to_storage_error)? {
+        paths.push(entry.map_err(io_to_storage_error)?.path());
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn walk(
+    dir: &Path,
+    to: FilesystemMetadataMode,
+    report: &mut MetadataMigrationReport,
+) -> Result<(), StorageError> {
+    for path in read_dir(dir)? {
+        if path.is_dir() {
+            walk(&path, to, report)?;
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if let Some(data) = sidecar_meta::data_name(name) {
+            if !path.with_file_name(data).exists() {
+                if !report.dry_run {
+                    std::fs::remove_file(&path).map_err(io_to_storage_error)?;
+                }
+                report.orphans_removed += 1;
+            }
+            continue;
+        }
+        // Other dot-files are atomic-write temps and probes, not objects.
+        if name.starts_with('.') {
+            continue;
+        }
+        if let Err(e) = migrate_file(&path, to, report) {
+            report.errors.push(format!("{}: {e}", path.display()));
+        }
+    }
+    Ok(())
+}
+
+fn migrate_file(
+    path: &Path,
+    to: FilesystemMetadataMode,
+    report: &mut MetadataMigrationReport,
+) -> Result<(), StorageError> {
+    let xattr = xattr::get(path, xattr_meta::XATTR_NAME).map_err(io_to_storage_error)?;
+    let sidecar = match sidecar_meta::read_raw(path) {
+        Ok(value) => Some(value),
+        Err(StorageError::NotFound(_)) => None,
+        Err(e) => return Err(e),
+    };
+    // The source mode is authoritative: a leftover target copy is from an
+    // interrupted run and gets overwritten.
+    match (to, xattr, sidecar) {
+        (FilesystemMetadataMode::Sidecar, Some(raw), _) => {
+            let value: serde_json::Value = serde_json::from_slice(&raw)?;
+            if !report.dry_run {
+                sidecar_meta::write_raw(path, value)?;
+                xattr::remove(path, xattr_meta::XATTR_NAME).map_err(io_to_storage_error)?;
+            }
+            report.migrated += 1;
+        }
+        (FilesystemMetadataMode::Xattr, _, Some(value)) => {
+            if !report.dry_run {
+                let raw = serde_json::to_vec(&value)?;
+                xattr::set(path, xattr_meta::XATTR_NAME, &raw).map_err(io_to_storage_error)?;
+                sidecar_meta::remove_metadata(path)?;
+            }
+            report.migrated += 1;
+        }
+        (FilesystemMetadataMode::Sidecar, None, Some(_))
+        | (FilesystemMetadataMode::Xattr, Some(_), None) => report.already_migrated += 1,
+        (_, None, None) => report.unmanaged += 1,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{FilesystemBackend, StorageBackend};
+    use crate::types::FileMetadata;
+
+    #[tokio::test]
+    async fn round_trips_between_modes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let xattr_backend = FilesystemBackend::new(root.clone()).await.unwrap();
+        xattr_backend.create_bucket("b").await.unwrap();
+        let meta =
+            FileMetadata::new_passthrough("k.txt".into(), "sha".into(), "md5".into(), 2, None);
+        xattr_backend
+            .put_passthrough("b", "p", "k.txt", b"hi", &meta)
+            .await
+            .unwrap();
+        let ds = root.join("b/deltaspaces/p");
+        std::fs::write(ds.join("unmanaged.txt"), b"raw").unwrap();
+        std::fs::write(ds.join(".dgmeta.gone.txt"), b"{}").unwrap();
+
+        let report = migrate_filesystem_metadata(&root, FilesystemMetadataMode::Sidecar, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            (report.migrated, report.unmanaged, report.orphans_removed),
+            (1, 1, 1),
+            "{report:?}"
+        );
+        assert!(xattr::get(ds.join("k.txt"), xattr_meta::XATTR_NAME)
+            .unwrap()
+            .is_none());
+        let sidecar_backend =
+            FilesystemBackend::with_metadata_mode(root.clone(), FilesystemMetadataMode::Sidecar)
+                .await
+                .unwrap();
+        let read = sidecar_backend
+            .get_passthrough_metadata("b", "p", "k.txt")
+            .await
+            .unwrap();
+        assert_eq!(read.file_sha256, "sha");
+
+        let again = migrate_filesystem_metadata(&root, FilesystemMetadataMode::Sidecar, false)
+            .await
+            .unwrap();
+        assert_eq!((again.migrated, again.already_migrated), (0, 1));
+
+        let back = migrate_filesystem_metadata(&root, FilesystemMetadataMode::Xattr, false)
+            .await
+            .unwrap();
+        assert_eq!(back.migrated, 1);
+        assert!(!ds.join(".dgmeta.k.txt").exists());
+        let read = xattr_backend
+            .get_passthrough_metadata("b", "p", "k.txt")
+            .await
+            .unwrap();
+        assert_eq!(read.file_sha256, "sha");
+    }
+}
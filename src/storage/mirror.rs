@@ -0,0 +1,567 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Write-through mirrored buckets.
+//!
+//! A bucket with a `mirror` policy is stored twice: on its own backend and on
+//! the mirror backend. [`super::RoutingBackend`] sends every write to both
+//! copies concurrently and settles the pair through [`MirrorSet::settle_write`]:
+//!
+//! - both accepted → acknowledged;
+//! - one accepted, `write_quorum: 1` → acknowledged once the other copy is
+//!   recorded as a [`MirrorLaggard`];
+//! - otherwise → the write fails (a copy that did accept it is still recorded
+//!   as lagging so the pair converges).
+//!
+//! A laggard is written through the installed [`LaggardJournal`] (the event
+//! outbox, see `replication::mirror_repair`) before the write returns, so a
+//! restart can't lose it; a quorum-1 write whose laggard can't be journaled
+//! is refused. Without a journal, or when it fails, laggards collect in a
+//! process-wide queue (it must survive engine rebuilds on config reload)
+//! that the repair task drains. Repair is state-based — the lagging copy is
+//! made to match the other one, whatever that holds by then — so a late or
+//! repeated repair is harmless.
+//!
+//! Reads prefer the primary, unless it recently failed or holds an unrepaired
+//! lagging copy of the file being read; a failed read is retried once on the
+//! other copy.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::traits::{StorageBackend, StorageError};
+use crate::metrics::Metrics;
+use crate::types::FileMetadata;
+
+/// How long a copy that failed a request is ranked behind the other one.
+const FAILURE_PENALTY: Duration = Duration::from_secs(30);
+
+/// One mirrored bucket's two copies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MirrorRoute {
+    pub primary: String,
+    pub primary_bucket: String,
+    pub mirror: String,
+    pub mirror_bucket: String,
+    pub write_quorum: u8,
+}
+
+/// Which stored file a laggard refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "file", rename_all = "snake_case")]
+pub enum MirrorFile {
+    Reference,
+    Delta { filename: String },
+    Passthrough { filename: String },
+}
+
+/// A copy of one stored file that missed a write.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MirrorLaggard {
+    /// Virtual bucket name.
+    pub bucket: String,
+    pub prefix: String,
+    #[serde(flatten)]
+    pub file: MirrorFile,
+    /// Backend holding the lagging copy.
+    pub backend: String,
+}
+
+impl MirrorLaggard {
+    /// `prefix/filename` — the outbox row's key column.
+    pub fn object_key(&self) -> String {
+        let name = match &self.file {
+            MirrorFile::Reference => "reference.bin",
+            MirrorFile::Delta { filename } | MirrorFile::Passthrough { filename } => filename,
+        };
+        if self.prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{name}", self.prefix)
+        }
+    }
+}
+
+/// Durable store for laggards, written before a degraded write returns.
+#[async_trait]
+pub trait LaggardJournal: Send + Sync {
+    async fn record(&self, laggards: &[MirrorLaggard]) -> Result<(), String>;
+}
+
+/// Laggards not yet written to the outbox.
+static PENDING: Mutex<Vec<MirrorLaggard>> = Mutex::new(Vec::new());
+
+/// Laggards not yet repaired — reads steer around these copies.
+static UNREPAIRED: LazyLock<Mutex<HashSet<MirrorLaggard>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+static JOURNAL: RwLock<Option<Arc<dyn LaggardJournal>>> = RwLock::new(None);
+
+/// Journal laggards recorded from now on through `journal`.
+pub fn set_laggard_journal(journal: Arc<dyn LaggardJournal>) {
+    *JOURNAL.write() = Some(journal);
+}
+
+/// Record a laggard: journaled when a journal is installed, else queued in
+/// memory. An `Err` means it only made the in-memory queue.
+async fn record_laggard(laggard: MirrorLaggard) -> Result<(), String> {
+    UNREPAIRED.lock().insert(laggard.clone());
+    let journal = JOURNAL.read().clone();
+    let result = match journal {
+        Some(journal) => journal.record(std::slice::from_ref(&laggard)).await,
+        None => {
+            PENDING.lock().push(laggard);
+            return Ok(());
+        }
+    };
+    if result.is_err() {
+        PENDING.lock().push(laggard);
+    }
+    result
+}
+
+/// Drain the laggards recorded since the last call.
+pub fn take_pending_laggards() -> Vec<MirrorLaggard> {
+    std::mem::take(&mut *PENDING.lock())
+}
+
+/// Put back laggards that could not be written to the outbox.
+pub fn requeue_laggards(laggards: Vec<MirrorLaggard>) {
+    let mut pending = PENDING.lock();
+    let newer = std::mem::replace(&mut *pending, laggards);
+    pending.extend(newer);
+}
+
+/// Does this error say something about the backend (as opposed to a
+/// definitive answer like NotFound)? Only these demote a copy.
+fn is_backend_fault(err: &StorageError) -> bool {
+    matches!(
+        err,
+        StorageError::Io(_)
+            | StorageError::S3(_)
+            | StorageError::Throttled(_)
+            | StorageError::Other(_)
+    )
+}
+
+/// What a repair did to the lagging copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairOutcome {
+    /// Copied the file from the other copy.
+    Copied,
+    /// The other copy no longer has the file; removed it.
+    Deleted,
+    /// The bucket is no longer mirrored; nothing to do.
+    Unmirrored,
+}
+
+/// The mirrored buckets of one engine, sharing the routing layer's backends.
+pub struct MirrorSet {
+    backends: HashMap<String, Arc<Box<dyn StorageBackend>>>,
+    routes: HashMap<String, MirrorRoute>,
+    /// Backend name → when its read/write failure penalty expires.
+    penalized: Mutex<HashMap<String, Instant>>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl MirrorSet {
+    /// # Errors
+    /// Returns an error if a route names a backend that isn't configured.
+    pub fn new(
+        backends: HashMap<String, Arc<Box<dyn StorageBackend>>>,
+        routes: HashMap<String, MirrorRoute>,
+        metrics: Option<Arc<Metrics>>,
+    ) -> Result<Self, StorageError> {
+        for (bucket, route) in &routes {
+            for name in [&route.primary, &route.mirror] {
+                if !backends.contains_key(name) {
+                    return Err(StorageError::Other(format!(
+                        "Bucket '{bucket}' mirrors through unknown backend '{name}'"
+                    )));
+                }
+            }
+        }
+        Ok(Self {
+            backends,
+            routes,
+            penalized: Mutex::new(HashMap::new()),
+            metrics,
+        })
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            backends: HashMap::new(),
+            routes: HashMap::new(),
+            penalized: Mutex::new(HashMap::new()),
+            metrics: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    pub fn route(&self, bucket: &str) -> Option<&MirrorRoute> {
+        self.routes.get(bucket)
+    }
+
+    /// Mirrored bucket names, sorted.
+    pub fn buckets(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.routes.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn backend(&self, name: &str) -> &dyn StorageBackend {
+        self.backends[name].as_ref().as_ref()
+    }
+
+    /// `(primary, mirror)` as `(backend, real bucket)` pairs.
+    pub(super) fn pair<'a>(
+        &'a self,
+        route: &'a MirrorRoute,
+    ) -> (
+        (&'a dyn StorageBackend, &'a str),
+        (&'a dyn StorageBackend, &'a str),
+    ) {
+        (
+            (self.backend(&route.primary), &route.primary_bucket),
+            (self.backend(&route.mirror), &route.mirror_bucket),
+        )
+    }
+
+    fn is_penalized(&self, backend: &str) -> bool {
+        let mut penalized = self.penalized.lock();
+        match penalized.get(backend) {
+            Some(until) if *until > Instant::now() => true,
+            Some(_) => {
+                penalized.remove(backend);
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn penalize(&self, backend: &str) {
+        self.penalized
+            .lock()
+            .insert(backend.to_string(), Instant::now() + FAILURE_PENALTY);
+    }
+
+    /// The two copies in the order a read should try them, each as
+    /// `(backend name, backend, real bucket)`. `file` is the stored file
+    /// being read, when the read targets one.
+    pub(super) fn read_order<'a>(
+        &'a self,
+        bucket: &str,
+        route: &'a MirrorRoute,
+        file: Option<(&str, &MirrorFile)>,
+    ) -> [(&'a str, &'a dyn StorageBackend, &'a str); 2] {
+        let primary = (
+            route.primary.as_str(),
+            self.backend(&route.primary),
+            route.primary_bucket.as_str(),
+        );
+        let mirror = (
+            route.mirror.as_str(),
+            self.backend(&route.mirror),
+            route.mirror_bucket.as_str(),
+        );
+        let primary_lags = file.is_some_and(|(prefix, file)| {
+            UNREPAIRED.lock().contains(&MirrorLaggard {
+                bucket: bucket.to_string(),
+                prefix: prefix.to_string(),
+                file: file.clone(),
+                backend: route.primary.clone(),
+            })
+        });
+        if primary_lags || (self.is_penalized(&route.primary) && !self.is_penalized(&route.mirror))
+        {
+            [mirror, primary]
+        } else {
+            [primary, mirror]
+        }
+    }
+
+    /// Note the outcome of a read attempt on `backend`.
+    pub(super) fn note_read_failure(&self, backend: &str, err: &StorageError) {
+        if is_backend_fault(err) {
+            self.penalize(backend);
+        }
+    }
+
+    pub(super) fn note_failover(&self) {
+        if let Some(m) = &self.metrics {
+            m.mirror_read_failovers_total.inc();
+        }
+    }
+
+    /// Combine the two copies' write results (see the module docs). `file`
+    /// names the stored file written, so a copy that missed it can be
+    /// repaired; bucket-level writes pass `None`.
+    pub(super) async fn settle_write<T>(
+        &self,
+        bucket: &str,
+        route: &MirrorRoute,
+        file: Option<(&str, MirrorFile)>,
+        primary: Result<T, StorageError>,
+        mirror: Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        let (value, lagging, err) = match (primary, mirror) {
+            (Ok(v), Ok(_)) => return Ok(v),
+            (Err(e), Err(_)) => return Err(e),
+            (Ok(v), Err(e)) => (v, &route.mirror, e),
+            (Err(e), Ok(v)) => (v, &route.primary, e),
+        };
+        if is_backend_fault(&err) {
+            self.penalize(lagging);
+        }
+        if let Some(m) = &self.metrics {
+            m.mirror_degraded_writes_total.inc();
+        }
+        if let Some((prefix, file)) = file {
+            let recorded = record_laggard(MirrorLaggard {
+                bucket: bucket.to_string(),
+                prefix: prefix.to_string(),
+                file,
+                backend: lagging.clone(),
+            })
+            .await;
+            if let Err(e) = recorded {
+                warn!(
+                    "mirror: bucket '{bucket}' write missed backend '{lagging}' ({err}) \
+                     and its repair could not be journaled ({e}); write refused"
+                );
+                return Err(err);
+            }
+        }
+        if route.write_quorum <= 1 {
+            warn!(
+                "mirror: bucket '{bucket}' write missed backend '{lagging}' ({err}); \
+                 acknowledged by the other copy, repair queued"
+            );
+            Ok(value)
+        } else {
+            Err(err)
+        }
+    }
+
+    /// Bring the lagging copy of one stored file in line with the other copy.
+    pub async fn repair(&self, laggard: &MirrorLaggard) -> Result<RepairOutcome, StorageError> {
+        let Some(route) = self.routes.get(&laggard.bucket) else {
+            UNREPAIRED.lock().remove(laggard);
+            return Ok(RepairOutcome::Unmirrored);
+        };
+        let ((primary, primary_bucket), (mirror, mirror_bucket)) = self.pair(route);
+        let ((source, source_bucket), (target, target_bucket)) = if laggard.backend == route.primary
+        {
+            ((mirror, mirror_bucket), (primary, primary_bucket))
+        } else if laggard.backend == route.mirror {
+            ((primary, primary_bucket), (mirror, mirror_bucket))
+        } else {
+            // Mirror re-pointed at another backend since; the new pair is
+            // the parity audit's concern.
+            UNREPAIRED.lock().remove(laggard);
+            return Ok(RepairOutcome::Unmirrored);
+        };
+        let prefix = laggard.prefix.as_str();
+        let outcome = match &laggard.file {
+            MirrorFile::Reference => {
+                match source.get_reference_metadata(source_bucket, prefix).await {
+                    Ok(meta) => {
+                        let data = source.get_reference(source_bucket, prefix).await?;
+                        target
+                            .put_reference(target_bucket, prefix, &data, &meta)
+                            .await?;
+                        RepairOutcome::Copied
+                    }
+                    Err(StorageError::NotFound(_)) => {
+                        ignore_not_found(target.delete_reference(target_bucket, prefix).await)?;
+                        RepairOutcome::Deleted
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            MirrorFile::Delta { filename } => {
+                match source
+                    .get_delta_metadata(source_bucket, prefix, filename)
+                    .await
+                {
+                    Ok(meta) => {
+                        let data = source.get_delta(source_bucket, prefix, filename).await?;
+                        target
+                            .put_delta(target_bucket, prefix, filename, &data, &meta)
+                            .await?;
+                        RepairOutcome::Copied
+                    }
+                    Err(StorageError::NotFound(_)) => {
+                        ignore_not_found(
+                            target.delete_delta(target_bucket, prefix, filename).await,
+                        )?;
+                        RepairOutcome::Deleted
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            MirrorFile::Passthrough { filename } => {
+                match source
+                    .get_passthrough_metadata(source_bucket, prefix, filename)
+                    .await
+                {
+                    Ok(meta) => {
+                        let data = source
+                            .get_passthrough(source_bucket, prefix, filename)
+                            .await?;
+                        target
+                            .put_passthrough(target_bucket, prefix, filename, &data, &meta)
+                            .await?;
+                        RepairOutcome::Copied
+                    }
+                    Err(StorageError::NotFound(_)) => {
+                        ignore_not_found(
+                            target
+                                .delete_passthrough(target_bucket, prefix, filename)
+                                .await,
+                        )?;
+                        RepairOutcome::Deleted
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+        UNREPAIRED.lock().remove(laggard);
+        Ok(outcome)
+    }
+
+    /// Full object listings of both copies — `(primary, mirror)` — for the
+    /// parity audit.
+    #[allow(clippy::type_complexity)]
+    pub async fn list_copies(
+        &self,
+        bucket: &str,
+    ) -> Result<(Vec<(String, FileMetadata)>, Vec<(String, FileMetadata)>), StorageError> {
+        let route = self
+            .routes
+            .get(bucket)
+            .ok_or_else(|| StorageError::BucketNotFound(format!("{bucket} is not mirrored")))?;
+        let ((primary, primary_bucket), (mirror, mirror_bucket)) = self.pair(route);
+        let (p, m) = tokio::join!(
+            primary.bulk_list_objects(primary_bucket, ""),
+            mirror.bulk_list_objects(mirror_bucket, "")
+        );
+        Ok((p?, m?))
+    }
+}
+
+/// Deleting something already absent is a successful delete.
+pub(super) fn ignore_not_found(res: Result<(), StorageError>) -> Result<(), StorageError> {
+    match res {
+        Err(StorageError::NotFound(_)) => Ok(()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(quorum: u8) -> MirrorRoute {
+        MirrorRoute {
+            primary: "a".into(),
+            primary_bucket: "critical".into(),
+            mirror: "b".into(),
+            mirror_bucket: "critical".into(),
+            write_quorum: quorum,
+        }
+    }
+
+    fn passthrough(name: &str) -> Option<(&'static str, MirrorFile)> {
+        Some((
+            "p",
+            MirrorFile::Passthrough {
+                filename: name.into(),
+            },
+        ))
+    }
+
+    fn laggard(name: &str, backend: &str) -> MirrorLaggard {
+        MirrorLaggard {
+            bucket: "critical".into(),
+            prefix: "p".into(),
+            file: MirrorFile::Passthrough {
+                filename: name.into(),
+            },
+            backend: backend.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn settle_write_honours_quorum() {
+        let set = MirrorSet::empty();
+        let fault = || StorageError::S3("boom".into());
+
+        assert!(set
+            .settle_write("critical", &route(2), None, Ok(()), Ok(()))
+            .await
+            .is_ok());
+        assert!(set
+            .settle_write::<()>("critical", &route(1), None, Err(fault()), Err(fault()))
+            .await
+            .is_err());
+
+        // Quorum 1: one copy suffices, the other is queued for repair.
+        assert!(set
+            .settle_write(
+                "critical",
+                &route(1),
+                passthrough("q1"),
+                Ok(()),
+                Err(fault())
+            )
+            .await
+            .is_ok());
+        assert!(UNREPAIRED.lock().contains(&laggard("q1", "b")));
+
+        // Quorum 2: the write fails, but the copy that took it still gets
+        // the other one repaired so the pair converges.
+        assert!(set
+            .settle_write(
+                "critical",
+                &route(2),
+                passthrough("q2"),
+                Err(fault()),
+                Ok(())
+            )
+            .await
+            .is_err());
+        assert!(UNREPAIRED.lock().contains(&laggard("q2", "a")));
+
+        let pending = take_pending_laggards();
+        assert!(pending.contains(&laggard("q1", "b")));
+        assert!(pending.contains(&laggard("q2", "a")));
+    }
+
+    #[test]
+    fn laggard_round_trips_through_outbox_payload() {
+        let l = laggard("app.zip", "b");
+        assert_eq!(l.object_key(), "p/app.zip");
+        let json = serde_json::to_value(&l).unwrap();
+        assert_eq!(json["file"], "passthrough");
+        assert_eq!(json["filename"], "app.zip");
+        let back: MirrorLaggard = serde_json::from_value(json).unwrap();
+        assert_eq!(back, l);
+
+        let reference = MirrorLaggard {
+            prefix: String::new(),
+            file: MirrorFile::Reference,
+            ..l
+        };
+        assert_eq!(reference.object_key(), "reference.bin");
+    }
+}
@@ -2,22 +2,43 @@
 
 //! Storage backend abstraction
 
+mod azure;
+mod caching;
+pub mod disk_cache;
 pub mod encrypting;
+mod erasure;
 mod filesystem;
+mod fs_multipart;
+mod gcs;
+mod hedge;
+mod metadata_migration;
+pub mod mirror;
 pub(crate) mod routing;
 mod s3;
+pub(crate) mod sidecar_meta;
+mod span;
 mod traits;
 #[cfg(unix)]
 pub(crate) mod xattr_meta;
 
+pub use azure::AzureBlobBackend;
+pub use caching::CachingBackend;
+pub use disk_cache::{DiskCache, DiskCacheLimits, DiskCacheStats};
 pub use encrypting::{EncryptingBackend, EncryptionConfig, EncryptionKey, WriteMode};
+pub use erasure::{ErasureBackend, ErasureRepairReport};
 pub use filesystem::FilesystemBackend;
+pub use gcs::GcsBackend;
+pub use hedge::{HedgeCopy, HedgeRoute, HedgeSet};
+pub use metadata_migration::{migrate_filesystem_metadata, MetadataMigrationReport};
+pub use mirror::{MirrorLaggard, MirrorRoute, MirrorSet};
 pub use routing::RoutingBackend;
 pub use s3::{
     NativeEncryptionConfig, S3Backend, DELEGATED_LIST_PROBE_REQUESTS, DELEGATED_LIST_UPSTREAM_PAGES,
 };
+pub use span::{SpanMemberRoute, SpanRoute, SpanSet};
 pub use traits::{
-    BucketListing, DelegatedListResult, MultipartUpload, StorageBackend, StorageError, UploadedPart,
+    BucketListing, DelegatedListResult, MultipartUpload, StagedMultipartUpload, StagedPart,
+    StagedSweepReport, StorageBackend, StorageError, UploadedPart,
 };
 
 /// ENOSPC raw error code on Linux and macOS.
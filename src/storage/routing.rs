@@ -6,6 +6,13 @@
 //! each call to the correct underlying backend based on the bucket name.
 //! The engine sees a single `StorageBackend` — caches, codec, prefix locks,
 //! and compression policies remain shared across all backends.
+//!
+//! Two route types span several backends: a bucket with a `mirror` policy
+//! writes to two backends and reads from the healthier copy (see
+//! [`super::mirror`]); a bucket with a `span` policy places each deltaspace
+//! on one of an ordered list of backends by free capacity (see
+//! [`super::span`]). A bucket with a `hedge` policy races slow passthrough
+//! reads against its second copy (see [`super::hedge`]).
 
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
@@ -18,11 +25,19 @@ use tracing::warn;
 
 use crate::types::FileMetadata;
 
+use super::hedge::{check_copy, first_byte, HedgeCopy, HedgeRoute, HedgeSet};
+use super::mirror::{ignore_not_found, MirrorFile, MirrorSet};
+use super::span::{merge_listings, merge_lite_scans, SpanSet};
 use super::traits::{
-    BucketListing, DelegatedListResult, LiteScanResult, MultipartUpload, StorageBackend,
-    StorageError, UploadedPart,
+    BucketListing, DelegatedListResult, LiteScanResult, MultipartUpload, StagedMultipartUpload,
+    StagedPart, StagedSweepReport, StorageBackend, StorageError, UploadedPart,
 };
 
+/// `upload_id` of the buffered multipart upload handed out for mirrored
+/// buckets: parts are retained by the caller and the assembled object is
+/// written to both copies at completion.
+const MIRRORED_UPLOAD_ID: &str = "mirrored-buffered";
+
 /// Route entry: maps a virtual bucket to a backend and optional real bucket name.
 #[derive(Debug, Clone)]
 struct BucketRoute {
@@ -77,6 +92,12 @@ pub struct RoutingBackend {
     /// How long a successful listing stays fresh enough to serve without
     /// re-probing upstream (coalesces near-simultaneous ListBuckets calls).
     list_fresh: std::time::Duration,
+    /// Write-through mirrored buckets (empty unless configured).
+    mirrors: Arc<MirrorSet>,
+    /// Capacity-spanned buckets (empty unless configured).
+    spans: Arc<SpanSet>,
+    /// Hedged buckets (empty unless configured).
+    hedges: Arc<HedgeSet>,
 }
 
 impl RoutingBackend {
@@ -163,9 +184,72 @@ impl RoutingBackend {
             list_cooldown,
             list_timeout,
             list_fresh,
+            mirrors: Arc::new(MirrorSet::empty()),
+            spans: Arc::new(SpanSet::empty()),
+            hedges: Arc::new(HedgeSet::empty()),
         })
     }
 
+    /// Attach the mirrored-bucket table. Mirrored buckets bypass the
+    /// single-backend routes for every object and bucket operation.
+    pub fn with_mirrors(mut self, mirrors: Arc<MirrorSet>) -> Self {
+        self.mirrors = mirrors;
+        self
+    }
+
+    /// Attach the spanned-bucket table. Like mirrors, spanned buckets bypass
+    /// the single-backend routes.
+    pub fn with_spans(mut self, spans: Arc<SpanSet>) -> Self {
+        self.spans = spans;
+        self
+    }
+
+    /// Attach the hedged-bucket table. Only passthrough reads made through
+    /// the `*_hedged` methods are hedged.
+    pub fn with_hedges(mut self, hedges: Arc<HedgeSet>) -> Self {
+        self.hedges = hedges;
+        self
+    }
+
+    /// The two copies a hedged read races, preferred first, each as
+    /// `(backend name, backend, real bucket)`. `None` when the bucket's
+    /// mirror is gone (the read is then not hedged).
+    async fn hedge_pair<'a>(
+        &'a self,
+        bucket: &'a str,
+        route: &'a HedgeRoute,
+        prefix: &str,
+        filename: &str,
+    ) -> Option<HedgeLegs<'a>> {
+        match &route.copy {
+            HedgeCopy::Mirror => {
+                let mirror = self.mirrors.route(bucket)?;
+                let [(pn, p, pb), (cn, c, cb)] = self.mirrors.read_order(
+                    bucket,
+                    mirror,
+                    Some((prefix, &passthrough_file(filename))),
+                );
+                Some([
+                    (pn.to_string(), p, Cow::Borrowed(pb)),
+                    (cn.to_string(), c, Cow::Borrowed(cb)),
+                ])
+            }
+            HedgeCopy::Replica(replica) => {
+                let primary = self.resolve_existing_named(bucket).await;
+                let copy = self.resolve_existing_named(replica).await;
+                Some([primary, copy])
+            }
+        }
+    }
+
+    /// A failed read on a mirrored bucket's copy demotes that copy, as
+    /// `mirrored_read!` does.
+    fn note_hedge_failure(&self, route: &HedgeRoute, backend: &str, err: &StorageError) {
+        if route.copy == HedgeCopy::Mirror {
+            self.mirrors.note_read_failure(backend, err);
+        }
+    }
+
     /// Reverse-lookup: given a backend name and real bucket, find the virtual name.
     /// Returns `None` if no route maps to this (backend, real_bucket) pair.
     fn reverse_lookup(&self, backend_name: &str, real_bucket: &str) -> Option<String> {
@@ -286,6 +370,116 @@ macro_rules! route_existing {
     }};
 }
 
+/// Mirrored bucket → run the write on both copies concurrently and settle
+/// the pair; otherwise fall through. `$file` is `Option<(prefix, MirrorFile)>`.
+macro_rules! mirrored_write {
+    ($self:ident, $bucket:ident, $file:expr, $method:ident $(, $arg:expr)*) => {
+        if let Some(route) = $self.mirrors.route($bucket) {
+            let ((p, pb), (m, mb)) = $self.mirrors.pair(route);
+            let (rp, rm) = tokio::join!(p.$method(pb $(, $arg)*), m.$method(mb $(, $arg)*));
+            return $self.mirrors.settle_write($bucket, route, $file, rp, rm).await;
+        }
+    };
+}
+
+/// Same, for deletes: a copy that never had the file deleted it fine.
+macro_rules! mirrored_delete {
+    ($self:ident, $bucket:ident, $file:expr, $method:ident $(, $arg:expr)*) => {
+        if let Some(route) = $self.mirrors.route($bucket) {
+            let ((p, pb), (m, mb)) = $self.mirrors.pair(route);
+            let (rp, rm) = tokio::join!(p.$method(pb $(, $arg)*), m.$method(mb $(, $arg)*));
+            return $self.mirrors.settle_write(
+                $bucket,
+                route,
+                $file,
+                ignore_not_found(rp),
+                ignore_not_found(rm),
+            )
+            .await;
+        }
+    };
+}
+
+/// Mirrored bucket → read the preferred copy, retrying once on the other.
+/// `$file` is `Option<(&prefix, &MirrorFile)>`.
+macro_rules! mirrored_read {
+    ($self:ident, $bucket:ident, $file:expr, $method:ident $(, $arg:expr)*) => {
+        if let Some(route) = $self.mirrors.route($bucket) {
+            let [(first_name, first, first_bucket), (_, second, second_bucket)] =
+                $self.mirrors.read_order($bucket, route, $file);
+            return match first.$method(first_bucket $(, $arg)*).await {
+                Ok(v) => Ok(v),
+                Err(err) => {
+                    $self.mirrors.note_read_failure(first_name, &err);
+                    match second.$method(second_bucket $(, $arg)*).await {
+                        Ok(v) => {
+                            $self.mirrors.note_failover();
+                            Ok(v)
+                        }
+                        Err(_) => Err(err),
+                    }
+                }
+            };
+        }
+    };
+}
+
+/// Spanned bucket → run the op on the member holding `$prefix`'s
+/// deltaspace (the first member when none does).
+macro_rules! spanned {
+    ($self:ident, $bucket:ident, $prefix:expr, $method:ident $(, $arg:expr)*) => {
+        if let Some(route) = $self.spans.route($bucket) {
+            let idx = $self.spans.read_index($bucket, route, $prefix).await?;
+            let (backend, real) = $self.spans.member(route, idx);
+            return backend.$method(real $(, $arg)*).await;
+        }
+    };
+}
+
+/// Spanned bucket → run the write on the member holding `$prefix`'s
+/// deltaspace, placing a new deltaspace by free capacity.
+macro_rules! spanned_write {
+    ($self:ident, $bucket:ident, $prefix:expr, $method:ident $(, $arg:expr)*) => {
+        if let Some(route) = $self.spans.route($bucket) {
+            let idx = $self.spans.write_index($bucket, route, $prefix).await?;
+            let (backend, real) = $self.spans.member(route, idx);
+            let result = backend.$method(real $(, $arg)*).await;
+            if result.is_ok() {
+                $self.spans.placed($bucket, route, $prefix, idx);
+            }
+            return result;
+        }
+    };
+}
+
+/// Spanned bucket → run a bucket-level op on every member in fill order,
+/// stopping at the first error.
+macro_rules! spanned_all {
+    ($self:ident, $bucket:ident, $method:ident $(, $arg:expr)*) => {
+        if let Some(route) = $self.spans.route($bucket) {
+            for (backend, real) in $self.spans.members(route) {
+                backend.$method(real $(, $arg)*).await?;
+            }
+            return Ok(());
+        }
+    };
+}
+
+/// A hedged read's two copies: `(backend name, backend, real bucket)`.
+type HedgeLegs<'a> = [(String, &'a dyn StorageBackend, Cow<'a, str>); 2];
+
+fn delta_file(filename: &str) -> MirrorFile {
+    MirrorFile::Delta {
+        filename: filename.to_string(),
+    }
+}
+
+fn passthrough_file(filename: &str) -> MirrorFile {
+    MirrorFile::Passthrough {
+        filename: filename.to_string(),
+    }
+}
+
 impl RoutingBackend {
     /// Query every backend CONCURRENTLY, in stable name order — one dead
     /// backend costs a single timeout, not one per backend in sequence.
@@ -522,7 +716,21 @@ impl StorageBackend for RoutingBackend {
     // === Bucket operations ===
 
     async fn create_bucket(&self, bucket: &str) -> Result<(), StorageError> {
-        let res = route_existing!(self, bucket, create_bucket);
+        if let Some(route) = self.spans.route(bucket) {
+            for (backend, real) in self.spans.members(route) {
+                backend.create_bucket(real).await?;
+            }
+            self.invalidate_listing_freshness();
+            return Ok(());
+        }
+        let res = match self.mirrors.route(bucket) {
+            Some(route) => {
+                let ((p, pb), (m, mb)) = self.mirrors.pair(route);
+                let (rp, rm) = tokio::join!(p.create_bucket(pb), m.create_bucket(mb));
+                self.mirrors.settle_write(bucket, route, None, rp, rm).await
+            }
+            None => route_existing!(self, bucket, create_bucket),
+        };
         if res.is_ok() {
             self.invalidate_listing_freshness();
         }
@@ -532,11 +740,33 @@ impl StorageBackend for RoutingBackend {
     /// Route a declared bucket to its target backend so the backend decides
     /// whether to create it (filesystem → mkdir, others → no-op). #63.
     async fn ensure_declared_bucket(&self, bucket: &str) -> Result<(), StorageError> {
+        mirrored_write!(self, bucket, None, ensure_declared_bucket);
+        spanned_all!(self, bucket, ensure_declared_bucket);
         route_existing!(self, bucket, ensure_declared_bucket)
     }
 
     async fn delete_bucket(&self, bucket: &str) -> Result<(), StorageError> {
-        let res = route_existing!(self, bucket, delete_bucket);
+        if let Some(route) = self.spans.route(bucket) {
+            // Refuse up front rather than leave some members deleted.
+            for (backend, real) in self.spans.members(route) {
+                if !backend.list_deltaspaces(real).await?.is_empty() {
+                    return Err(StorageError::BucketNotEmpty(bucket.to_string()));
+                }
+            }
+            for (backend, real) in self.spans.members(route) {
+                backend.delete_bucket(real).await?;
+            }
+            self.invalidate_listing_freshness();
+            return Ok(());
+        }
+        let res = match self.mirrors.route(bucket) {
+            Some(route) => {
+                let ((p, pb), (m, mb)) = self.mirrors.pair(route);
+                let (rp, rm) = tokio::join!(p.delete_bucket(pb), m.delete_bucket(mb));
+                self.mirrors.settle_write(bucket, route, None, rp, rm).await
+            }
+            None => route_existing!(self, bucket, delete_bucket),
+        };
         if res.is_ok() {
             self.invalidate_listing_freshness();
         }
@@ -727,6 +957,11 @@ impl StorageBackend for RoutingBackend {
     }
 
     async fn head_bucket(&self, bucket: &str) -> Result<bool, StorageError> {
+        mirrored_read!(self, bucket, None, head_bucket);
+        if let Some(route) = self.spans.route(bucket) {
+            let (backend, real) = self.spans.member(route, 0);
+            return backend.head_bucket(real).await;
+        }
         let (backend, real_bucket) = self.resolve_existing(bucket).await;
         backend.head_bucket(&real_bucket).await
     }
@@ -734,6 +969,14 @@ impl StorageBackend for RoutingBackend {
     // === Reference file operations ===
 
     async fn get_reference(&self, bucket: &str, prefix: &str) -> Result<Vec<u8>, StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &MirrorFile::Reference)),
+            get_reference,
+            prefix
+        );
+        spanned!(self, bucket, prefix, get_reference, prefix);
         route_existing!(self, bucket, get_reference, prefix)
     }
 
@@ -743,6 +986,15 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         dest: &std::path::Path,
     ) -> Result<u64, StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &MirrorFile::Reference)),
+            get_reference_to_file,
+            prefix,
+            dest
+        );
+        spanned!(self, bucket, prefix, get_reference_to_file, prefix, dest);
         // Delegate to the routed backend's streaming impl (filesystem hardlink /
         // S3 stream-to-file) rather than the buffering default.
         route_existing!(self, bucket, get_reference_to_file, prefix, dest)
@@ -755,6 +1007,16 @@ impl StorageBackend for RoutingBackend {
         data: &[u8],
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
+        mirrored_write!(
+            self,
+            bucket,
+            Some((prefix, MirrorFile::Reference)),
+            put_reference,
+            prefix,
+            data,
+            metadata
+        );
+        spanned_write!(self, bucket, prefix, put_reference, prefix, data, metadata);
         route_existing!(self, bucket, put_reference, prefix, data, metadata)
     }
 
@@ -765,6 +1027,24 @@ impl StorageBackend for RoutingBackend {
         source_path: &std::path::Path,
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
+        mirrored_write!(
+            self,
+            bucket,
+            Some((prefix, MirrorFile::Reference)),
+            put_reference_from_file,
+            prefix,
+            source_path,
+            metadata
+        );
+        spanned_write!(
+            self,
+            bucket,
+            prefix,
+            put_reference_from_file,
+            prefix,
+            source_path,
+            metadata
+        );
         route_existing!(
             self,
             bucket,
@@ -781,6 +1061,22 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
+        mirrored_write!(
+            self,
+            bucket,
+            Some((prefix, MirrorFile::Reference)),
+            put_reference_metadata,
+            prefix,
+            metadata
+        );
+        spanned_write!(
+            self,
+            bucket,
+            prefix,
+            put_reference_metadata,
+            prefix,
+            metadata
+        );
         route_existing!(self, bucket, put_reference_metadata, prefix, metadata)
     }
 
@@ -789,15 +1085,39 @@ impl StorageBackend for RoutingBackend {
         bucket: &str,
         prefix: &str,
     ) -> Result<FileMetadata, StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &MirrorFile::Reference)),
+            get_reference_metadata,
+            prefix
+        );
+        spanned!(self, bucket, prefix, get_reference_metadata, prefix);
         route_existing!(self, bucket, get_reference_metadata, prefix)
     }
 
     async fn has_reference(&self, bucket: &str, prefix: &str) -> Result<bool, StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &MirrorFile::Reference)),
+            has_reference,
+            prefix
+        );
+        spanned!(self, bucket, prefix, has_reference, prefix);
         let (backend, real_bucket) = self.resolve_existing(bucket).await;
         backend.has_reference(&real_bucket, prefix).await
     }
 
     async fn delete_reference(&self, bucket: &str, prefix: &str) -> Result<(), StorageError> {
+        mirrored_delete!(
+            self,
+            bucket,
+            Some((prefix, MirrorFile::Reference)),
+            delete_reference,
+            prefix
+        );
+        spanned!(self, bucket, prefix, delete_reference, prefix);
         route_existing!(self, bucket, delete_reference, prefix)
     }
 
@@ -809,6 +1129,15 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         filename: &str,
     ) -> Result<Vec<u8>, StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &delta_file(filename))),
+            get_delta,
+            prefix,
+            filename
+        );
+        spanned!(self, bucket, prefix, get_delta, prefix, filename);
         route_existing!(self, bucket, get_delta, prefix, filename)
     }
 
@@ -820,6 +1149,17 @@ impl StorageBackend for RoutingBackend {
         data: &[u8],
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
+        mirrored_write!(
+            self,
+            bucket,
+            Some((prefix, delta_file(filename))),
+            put_delta,
+            prefix,
+            filename,
+            data,
+            metadata
+        );
+        spanned_write!(self, bucket, prefix, put_delta, prefix, filename, data, metadata);
         route_existing!(self, bucket, put_delta, prefix, filename, data, metadata)
     }
 
@@ -829,6 +1169,15 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         filename: &str,
     ) -> Result<FileMetadata, StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &delta_file(filename))),
+            get_delta_metadata,
+            prefix,
+            filename
+        );
+        spanned!(self, bucket, prefix, get_delta_metadata, prefix, filename);
         route_existing!(self, bucket, get_delta_metadata, prefix, filename)
     }
 
@@ -838,6 +1187,15 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         filename: &str,
     ) -> Result<(), StorageError> {
+        mirrored_delete!(
+            self,
+            bucket,
+            Some((prefix, delta_file(filename))),
+            delete_delta,
+            prefix,
+            filename
+        );
+        spanned!(self, bucket, prefix, delete_delta, prefix, filename);
         route_existing!(self, bucket, delete_delta, prefix, filename)
     }
 
@@ -849,6 +1207,15 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         filename: &str,
     ) -> Result<Vec<u8>, StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &passthrough_file(filename))),
+            get_passthrough,
+            prefix,
+            filename
+        );
+        spanned!(self, bucket, prefix, get_passthrough, prefix, filename);
         route_existing!(self, bucket, get_passthrough, prefix, filename)
     }
 
@@ -860,6 +1227,26 @@ impl StorageBackend for RoutingBackend {
         data: &[u8],
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
+        mirrored_write!(
+            self,
+            bucket,
+            Some((prefix, passthrough_file(filename))),
+            put_passthrough,
+            prefix,
+            filename,
+            data,
+            metadata
+        );
+        spanned_write!(
+            self,
+            bucket,
+            prefix,
+            put_passthrough,
+            prefix,
+            filename,
+            data,
+            metadata
+        );
         route_existing!(
             self,
             bucket,
@@ -883,6 +1270,26 @@ impl StorageBackend for RoutingBackend {
         source_path: &std::path::Path,
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
+        mirrored_write!(
+            self,
+            bucket,
+            Some((prefix, passthrough_file(filename))),
+            put_passthrough_file,
+            prefix,
+            filename,
+            source_path,
+            metadata
+        );
+        spanned_write!(
+            self,
+            bucket,
+            prefix,
+            put_passthrough_file,
+            prefix,
+            filename,
+            source_path,
+            metadata
+        );
         route_existing!(
             self,
             bucket,
@@ -902,6 +1309,26 @@ impl StorageBackend for RoutingBackend {
         part_paths: &[std::path::PathBuf],
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
+        mirrored_write!(
+            self,
+            bucket,
+            Some((prefix, passthrough_file(filename))),
+            put_passthrough_parts,
+            prefix,
+            filename,
+            part_paths,
+            metadata
+        );
+        spanned_write!(
+            self,
+            bucket,
+            prefix,
+            put_passthrough_parts,
+            prefix,
+            filename,
+            part_paths,
+            metadata
+        );
         route_existing!(
             self,
             bucket,
@@ -919,6 +1346,22 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         filename: &str,
     ) -> Result<FileMetadata, StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &passthrough_file(filename))),
+            get_passthrough_metadata,
+            prefix,
+            filename
+        );
+        spanned!(
+            self,
+            bucket,
+            prefix,
+            get_passthrough_metadata,
+            prefix,
+            filename
+        );
         route_existing!(self, bucket, get_passthrough_metadata, prefix, filename)
     }
 
@@ -929,6 +1372,24 @@ impl StorageBackend for RoutingBackend {
         filename: &str,
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
+        mirrored_write!(
+            self,
+            bucket,
+            Some((prefix, passthrough_file(filename))),
+            put_passthrough_metadata,
+            prefix,
+            filename,
+            metadata
+        );
+        spanned_write!(
+            self,
+            bucket,
+            prefix,
+            put_passthrough_metadata,
+            prefix,
+            filename,
+            metadata
+        );
         route_existing!(
             self,
             bucket,
@@ -945,6 +1406,15 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         filename: &str,
     ) -> Result<(), StorageError> {
+        mirrored_delete!(
+            self,
+            bucket,
+            Some((prefix, passthrough_file(filename))),
+            delete_passthrough,
+            prefix,
+            filename
+        );
+        spanned!(self, bucket, prefix, delete_passthrough, prefix, filename);
         route_existing!(self, bucket, delete_passthrough, prefix, filename)
     }
 
@@ -956,6 +1426,22 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         filename: &str,
     ) -> Result<BoxStream<'static, Result<Bytes, StorageError>>, StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &passthrough_file(filename))),
+            get_passthrough_stream,
+            prefix,
+            filename
+        );
+        spanned!(
+            self,
+            bucket,
+            prefix,
+            get_passthrough_stream,
+            prefix,
+            filename
+        );
         route_existing!(self, bucket, get_passthrough_stream, prefix, filename)
     }
 
@@ -967,6 +1453,26 @@ impl StorageBackend for RoutingBackend {
         start: u64,
         end: u64,
     ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError> {
+        mirrored_read!(
+            self,
+            bucket,
+            Some((prefix, &passthrough_file(filename))),
+            get_passthrough_stream_range,
+            prefix,
+            filename,
+            start,
+            end
+        );
+        spanned!(
+            self,
+            bucket,
+            prefix,
+            get_passthrough_stream_range,
+            prefix,
+            filename,
+            start,
+            end
+        );
         route_existing!(
             self,
             bucket,
@@ -978,6 +1484,90 @@ impl StorageBackend for RoutingBackend {
         )
     }
 
+    async fn get_passthrough_stream_hedged(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        expected: &FileMetadata,
+    ) -> Result<BoxStream<'static, Result<Bytes, StorageError>>, StorageError> {
+        let Some(route) = self.hedges.route(bucket) else {
+            return self.get_passthrough_stream(bucket, prefix, filename).await;
+        };
+        let Some([(pn, p, pb), (cn, c, cb)]) =
+            self.hedge_pair(bucket, route, prefix, filename).await
+        else {
+            return self.get_passthrough_stream(bucket, prefix, filename).await;
+        };
+        let primary = async {
+            let res = match p.get_passthrough_stream(&pb, prefix, filename).await {
+                Ok(stream) => first_byte(stream).await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = &res {
+                self.note_hedge_failure(route, &pn, err);
+            }
+            res
+        };
+        let copy = || async {
+            let (meta, stream) =
+                tokio::join!(c.get_passthrough_metadata(&cb, prefix, filename), async {
+                    first_byte(c.get_passthrough_stream(&cb, prefix, filename).await?).await
+                });
+            check_copy(&meta?, expected, filename)?;
+            stream
+        };
+        self.hedges.race(route, &pn, primary, &cn, copy).await
+    }
+
+    async fn get_passthrough_stream_range_hedged(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        start: u64,
+        end: u64,
+        expected: &FileMetadata,
+    ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError> {
+        let Some(route) = self.hedges.route(bucket) else {
+            return self
+                .get_passthrough_stream_range(bucket, prefix, filename, start, end)
+                .await;
+        };
+        let Some([(pn, p, pb), (cn, c, cb)]) =
+            self.hedge_pair(bucket, route, prefix, filename).await
+        else {
+            return self
+                .get_passthrough_stream_range(bucket, prefix, filename, start, end)
+                .await;
+        };
+        let primary = async {
+            let res = match p
+                .get_passthrough_stream_range(&pb, prefix, filename, start, end)
+                .await
+            {
+                Ok((stream, len)) => first_byte(stream).await.map(|s| (s, len)),
+                Err(err) => Err(err),
+            };
+            if let Err(err) = &res {
+                self.note_hedge_failure(route, &pn, err);
+            }
+            res
+        };
+        let copy = || async {
+            let (meta, read) =
+                tokio::join!(c.get_passthrough_metadata(&cb, prefix, filename), async {
+                    let (stream, len) = c
+                        .get_passthrough_stream_range(&cb, prefix, filename, start, end)
+                        .await?;
+                    Ok::<_, StorageError>((first_byte(stream).await?, len))
+                });
+            check_copy(&meta?, expected, filename)?;
+            read
+        };
+        self.hedges.race(route, &pn, primary, &cn, copy).await
+    }
+
     async fn put_passthrough_chunked(
         &self,
         bucket: &str,
@@ -986,6 +1576,26 @@ impl StorageBackend for RoutingBackend {
         chunks: &[Bytes],
         metadata: &FileMetadata,
     ) -> Result<(), StorageError> {
+        mirrored_write!(
+            self,
+            bucket,
+            Some((prefix, passthrough_file(filename))),
+            put_passthrough_chunked,
+            prefix,
+            filename,
+            chunks,
+            metadata
+        );
+        spanned_write!(
+            self,
+            bucket,
+            prefix,
+            put_passthrough_chunked,
+            prefix,
+            filename,
+            chunks,
+            metadata
+        );
         route_existing!(
             self,
             bucket,
@@ -1006,6 +1616,26 @@ impl StorageBackend for RoutingBackend {
         filename: &str,
         metadata: &FileMetadata,
     ) -> Result<MultipartUpload, StorageError> {
+        if self.mirrors.route(bucket).is_some() {
+            // No native multipart across two backends: the caller buffers the
+            // parts and completion writes the assembled object to both copies.
+            return Ok(MultipartUpload {
+                bucket: bucket.to_string(),
+                upload_id: MIRRORED_UPLOAD_ID.to_string(),
+                native: false,
+                backend: None,
+            });
+        }
+        if let Some(route) = self.spans.route(bucket) {
+            let idx = self.spans.write_index(bucket, route, prefix).await?;
+            let (backend, real) = self.spans.member(route, idx);
+            let mut upload = backend
+                .create_multipart_upload(real, prefix, filename, metadata)
+                .await?;
+            self.spans.placed(bucket, route, prefix, idx);
+            upload.backend = Some(route.members[idx].backend.clone());
+            return Ok(upload);
+        }
         let (name, backend, real_bucket) = self.resolve_existing_named(bucket).await;
         let mut upload = backend
             .create_multipart_upload(&real_bucket, prefix, filename, metadata)
@@ -1024,6 +1654,12 @@ impl StorageBackend for RoutingBackend {
         part_number: i32,
         data: Bytes,
     ) -> Result<UploadedPart, StorageError> {
+        if upload.upload_id == MIRRORED_UPLOAD_ID {
+            return Ok(UploadedPart {
+                part_number,
+                etag: String::new(),
+            });
+        }
         self.resolve_multipart_backend(upload)
             .upload_part(upload, prefix, filename, part_number, data)
             .await
@@ -1038,6 +1674,11 @@ impl StorageBackend for RoutingBackend {
         assembled: &[Bytes],
         metadata: &FileMetadata,
     ) -> Result<String, StorageError> {
+        if upload.upload_id == MIRRORED_UPLOAD_ID {
+            self.put_passthrough_chunked(&upload.bucket, prefix, filename, assembled, metadata)
+                .await?;
+            return Ok(format!("\"{}\"", metadata.md5));
+        }
         self.resolve_multipart_backend(upload)
             .complete_multipart_upload(upload, prefix, filename, parts, assembled, metadata)
             .await
@@ -1049,6 +1690,9 @@ impl StorageBackend for RoutingBackend {
         prefix: &str,
         filename: &str,
     ) -> Result<(), StorageError> {
+        if upload.upload_id == MIRRORED_UPLOAD_ID {
+            return Ok(());
+        }
         self.resolve_multipart_backend(upload)
             .abort_multipart_upload(upload, prefix, filename)
             .await
@@ -1058,6 +1702,30 @@ impl StorageBackend for RoutingBackend {
         // Route by explicit policy only (sync, no head probing). For
         // unrouted buckets fall back to the default backend's label. This
         // is a conservative capability hint, not a correctness boundary.
+        // A mirrored bucket reports proxy-AES if EITHER copy encrypts, so the
+        // transfer layer never plans native multipart against it.
+        if let Some(route) = self.mirrors.route(bucket) {
+            let ((p, pb), (m, mb)) = self.mirrors.pair(route);
+            let label = p.multipart_storage_label(pb);
+            let mirror_label = m.multipart_storage_label(mb);
+            return if mirror_label == "aes256-gcm-proxy" {
+                mirror_label
+            } else {
+                label
+            };
+        }
+        if let Some(route) = self.spans.route(bucket) {
+            let labels: Vec<&'static str> = self
+                .spans
+                .members(route)
+                .map(|(backend, real)| backend.multipart_storage_label(real))
+                .collect();
+            return labels
+                .iter()
+                .copied()
+                .find(|l| *l == "aes256-gcm-proxy")
+                .unwrap_or(labels[0]);
+        }
         if let Some(route) = self.routes.get(bucket) {
             return self.backends[&route.backend_name]
                 .as_ref()
@@ -1068,6 +1736,15 @@ impl StorageBackend for RoutingBackend {
     }
 
     fn supports_native_multipart(&self, bucket: &str) -> bool {
+        if self.mirrors.route(bucket).is_some() {
+            return false;
+        }
+        if let Some(route) = self.spans.route(bucket) {
+            return self
+                .spans
+                .members(route)
+                .all(|(backend, real)| backend.supports_native_multipart(real));
+        }
         if let Some(route) = self.routes.get(bucket) {
             return self.backends[&route.backend_name]
                 .as_ref()
@@ -1077,7 +1754,146 @@ impl StorageBackend for RoutingBackend {
         self.default_backend().supports_native_multipart(bucket)
     }
 
+    fn persists_multipart_uploads(&self, bucket: &str) -> bool {
+        // A mirrored upload is buffered and written to both copies at once.
+        if self.mirrors.route(bucket).is_some() {
+            return false;
+        }
+        if let Some(route) = self.spans.route(bucket) {
+            return self
+                .spans
+                .members(route)
+                .all(|(backend, real)| backend.persists_multipart_uploads(real));
+        }
+        if let Some(route) = self.routes.get(bucket) {
+            return self.backends[&route.backend_name]
+                .as_ref()
+                .as_ref()
+                .persists_multipart_uploads(bucket);
+        }
+        self.default_backend().persists_multipart_uploads(bucket)
+    }
+
+    async fn resume_multipart_upload(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        upload_id: &str,
+    ) -> Result<StagedMultipartUpload, StorageError> {
+        if self.mirrors.route(bucket).is_some() {
+            return Err(StorageError::NotFound(format!(
+                "multipart upload {upload_id}"
+            )));
+        }
+        if let Some(route) = self.spans.route(bucket) {
+            // Placement may not be known again after a restart: ask each
+            // member, the upload is on exactly one.
+            for (idx, member) in route.members.iter().enumerate() {
+                let (backend, real) = self.spans.member(route, idx);
+                match backend
+                    .resume_multipart_upload(real, prefix, filename, upload_id)
+                    .await
+                {
+                    Ok(mut staged) => {
+                        staged.upload.backend = Some(member.backend.clone());
+                        return Ok(staged);
+                    }
+                    Err(StorageError::NotFound(_)) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            return Err(StorageError::NotFound(format!(
+                "multipart upload {upload_id}"
+            )));
+        }
+        let (name, backend, real_bucket) = self.resolve_existing_named(bucket).await;
+        let mut staged = backend
+            .resume_multipart_upload(&real_bucket, prefix, filename, upload_id)
+            .await?;
+        staged.upload.backend = Some(name);
+        Ok(staged)
+    }
+
+    async fn list_multipart_parts(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<Vec<StagedPart>, StorageError> {
+        if upload.upload_id == MIRRORED_UPLOAD_ID {
+            return Ok(Vec::new());
+        }
+        self.resolve_multipart_backend(upload)
+            .list_multipart_parts(upload, prefix, filename)
+            .await
+    }
+
+    async fn read_multipart_part(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+        part_number: i32,
+    ) -> Result<Bytes, StorageError> {
+        self.resolve_multipart_backend(upload)
+            .read_multipart_part(upload, prefix, filename, part_number)
+            .await
+    }
+
+    async fn list_multipart_uploads(
+        &self,
+        bucket: &str,
+    ) -> Result<Vec<StagedMultipartUpload>, StorageError> {
+        if self.mirrors.route(bucket).is_some() {
+            return Ok(Vec::new());
+        }
+        if let Some(route) = self.spans.route(bucket) {
+            let mut uploads = Vec::new();
+            for (idx, member) in route.members.iter().enumerate() {
+                let (backend, real) = self.spans.member(route, idx);
+                for mut staged in backend.list_multipart_uploads(real).await? {
+                    staged.upload.backend = Some(member.backend.clone());
+                    uploads.push(staged);
+                }
+            }
+            return Ok(uploads);
+        }
+        let (name, backend, real_bucket) = self.resolve_existing_named(bucket).await;
+        let mut uploads = backend.list_multipart_uploads(&real_bucket).await?;
+        for staged in &mut uploads {
+            staged.upload.backend = Some(name.clone());
+        }
+        Ok(uploads)
+    }
+
+    /// Sweeps every configured backend; one failing backend doesn't stop
+    /// the others.
+    async fn sweep_multipart_uploads(
+        &self,
+        idle: std::time::Duration,
+    ) -> Result<StagedSweepReport, StorageError> {
+        let mut report = StagedSweepReport::default();
+        for (name, backend) in &self.backends {
+            match backend.sweep_multipart_uploads(idle).await {
+                Ok(r) => report += r,
+                Err(e) => warn!("multipart staging sweep on backend '{name}' failed: {e}"),
+            }
+        }
+        Ok(report)
+    }
+
     fn lite_list_carries_logical_facts(&self, bucket: &str) -> bool {
+        if let Some(route) = self.mirrors.route(bucket) {
+            let ((p, pb), (m, mb)) = self.mirrors.pair(route);
+            return p.lite_list_carries_logical_facts(pb) && m.lite_list_carries_logical_facts(mb);
+        }
+        if let Some(route) = self.spans.route(bucket) {
+            return self
+                .spans
+                .members(route)
+                .all(|(backend, real)| backend.lite_list_carries_logical_facts(real));
+        }
         if let Some(route) = self.routes.get(bucket) {
             return self.backends[&route.backend_name]
                 .as_ref()
@@ -1088,6 +1904,46 @@ impl StorageBackend for RoutingBackend {
             .lite_list_carries_logical_facts(bucket)
     }
 
+    fn stamps_encryption_key_id(&self, bucket: &str) -> bool {
+        if let Some(route) = self.mirrors.route(bucket) {
+            let ((p, pb), (m, mb)) = self.mirrors.pair(route);
+            return p.stamps_encryption_key_id(pb) && m.stamps_encryption_key_id(mb);
+        }
+        if let Some(route) = self.spans.route(bucket) {
+            return self
+                .spans
+                .members(route)
+                .all(|(backend, real)| backend.stamps_encryption_key_id(real));
+        }
+        if let Some(route) = self.routes.get(bucket) {
+            return self.backends[&route.backend_name]
+                .as_ref()
+                .as_ref()
+                .stamps_encryption_key_id(bucket);
+        }
+        self.default_backend().stamps_encryption_key_id(bucket)
+    }
+
+    fn reserves_filename(&self, bucket: &str, filename: &str) -> bool {
+        if let Some(route) = self.mirrors.route(bucket) {
+            let ((p, pb), (m, mb)) = self.mirrors.pair(route);
+            return p.reserves_filename(pb, filename) || m.reserves_filename(mb, filename);
+        }
+        if let Some(route) = self.spans.route(bucket) {
+            return self
+                .spans
+                .members(route)
+                .any(|(backend, real)| backend.reserves_filename(real, filename));
+        }
+        if let Some(route) = self.routes.get(bucket) {
+            return self.backends[&route.backend_name]
+                .as_ref()
+                .as_ref()
+                .reserves_filename(bucket, filename);
+        }
+        self.default_backend().reserves_filename(bucket, filename)
+    }
+
     // === Scanning operations ===
 
     async fn scan_deltaspace(
@@ -1095,6 +1951,14 @@ impl StorageBackend for RoutingBackend {
         bucket: &str,
         prefix: &str,
     ) -> Result<Vec<FileMetadata>, StorageError> {
+        mirrored_read!(self, bucket, None, scan_deltaspace, prefix);
+        if let Some(route) = self.spans.route(bucket) {
+            let mut all = Vec::new();
+            for (backend, real) in self.spans.members(route) {
+                all.extend(backend.scan_deltaspace(real, prefix).await?);
+            }
+            return Ok(all);
+        }
         route_existing!(self, bucket, scan_deltaspace, prefix)
     }
 
@@ -1103,10 +1967,28 @@ impl StorageBackend for RoutingBackend {
         bucket: &str,
         prefix: &str,
     ) -> Result<LiteScanResult, StorageError> {
+        mirrored_read!(self, bucket, None, scan_deltaspace_lite, prefix);
+        if let Some(route) = self.spans.route(bucket) {
+            let mut scans = Vec::new();
+            for (backend, real) in self.spans.members(route) {
+                scans.push(backend.scan_deltaspace_lite(real, prefix).await?);
+            }
+            return Ok(merge_lite_scans(scans));
+        }
         route_existing!(self, bucket, scan_deltaspace_lite, prefix)
     }
 
     async fn list_deltaspaces(&self, bucket: &str) -> Result<Vec<String>, StorageError> {
+        mirrored_read!(self, bucket, None, list_deltaspaces);
+        if let Some(route) = self.spans.route(bucket) {
+            let mut all = Vec::new();
+            for (backend, real) in self.spans.members(route) {
+                all.extend(backend.list_deltaspaces(real).await?);
+            }
+            all.sort();
+            all.dedup();
+            return Ok(all);
+        }
         route_existing!(self, bucket, list_deltaspaces)
     }
 
@@ -1114,6 +1996,21 @@ impl StorageBackend for RoutingBackend {
     async fn total_size(&self, bucket: Option<&str>) -> Result<u64, StorageError> {
         match bucket {
             Some(b) => {
+                if let Some(route) = self.spans.route(b) {
+                    let mut total = 0u64;
+                    for (backend, real) in self.spans.members(route) {
+                        total += backend.total_size(Some(real)).await?;
+                    }
+                    return Ok(total);
+                }
+                if let Some(route) = self.mirrors.route(b) {
+                    let [(_, first, first_bucket), (_, second, second_bucket)] =
+                        self.mirrors.read_order(b, route, None);
+                    return match first.total_size(Some(first_bucket)).await {
+                        Ok(size) => Ok(size),
+                        Err(err) => second.total_size(Some(second_bucket)).await.or(Err(err)),
+                    };
+                }
                 let (backend, real_bucket) = self.resolve_existing(b).await;
                 backend.total_size(Some(&real_bucket)).await
             }
@@ -1141,6 +2038,11 @@ impl StorageBackend for RoutingBackend {
     }
 
     async fn put_directory_marker(&self, bucket: &str, key: &str) -> Result<(), StorageError> {
+        mirrored_write!(self, bucket, None, put_directory_marker, key);
+        if let Some(route) = self.spans.route(bucket) {
+            let (backend, real) = self.spans.member(route, 0);
+            return backend.put_directory_marker(real, key).await;
+        }
         route_existing!(self, bucket, put_directory_marker, key)
     }
 
@@ -1149,6 +2051,14 @@ impl StorageBackend for RoutingBackend {
         bucket: &str,
         prefix: &str,
     ) -> Result<Vec<(String, FileMetadata)>, StorageError> {
+        mirrored_read!(self, bucket, None, bulk_list_objects, prefix);
+        if let Some(route) = self.spans.route(bucket) {
+            let mut listings = Vec::new();
+            for (backend, real) in self.spans.members(route) {
+                listings.push(backend.bulk_list_objects(real, prefix).await?);
+            }
+            return Ok(merge_listings(listings));
+        }
         route_existing!(self, bucket, bulk_list_objects, prefix)
     }
 
@@ -1157,6 +2067,22 @@ impl StorageBackend for RoutingBackend {
         bucket: &str,
         objects: Vec<(String, FileMetadata)>,
     ) -> Result<Vec<(String, FileMetadata)>, StorageError> {
+        mirrored_read!(self, bucket, None, enrich_list_metadata, objects.clone());
+        if let Some(route) = self.spans.route(bucket) {
+            // Enrich each object on the member holding its deltaspace.
+            let mut by_member: HashMap<usize, Vec<(String, FileMetadata)>> = HashMap::new();
+            for (key, meta) in objects {
+                let prefix = crate::types::ObjectKey::parse(bucket, &key).prefix;
+                let idx = self.spans.read_index(bucket, route, &prefix).await?;
+                by_member.entry(idx).or_default().push((key, meta));
+            }
+            let mut enriched = Vec::new();
+            for (idx, objs) in by_member {
+                let (backend, real) = self.spans.member(route, idx);
+                enriched.push(backend.enrich_list_metadata(real, objs).await?);
+            }
+            return Ok(merge_listings(enriched));
+        }
         let (backend, real_bucket) = self.resolve_existing(bucket).await;
         backend.enrich_list_metadata(&real_bucket, objects).await
     }
@@ -1169,6 +2095,20 @@ impl StorageBackend for RoutingBackend {
         max_keys: u32,
         continuation_token: Option<&str>,
     ) -> Result<Option<DelegatedListResult>, StorageError> {
+        if self.spans.route(bucket).is_some() {
+            // No single store to page natively: merge in memory instead.
+            return Ok(None);
+        }
+        mirrored_read!(
+            self,
+            bucket,
+            None,
+            list_objects_delegated,
+            prefix,
+            delimiter,
+            max_keys,
+            continuation_token
+        );
         let (backend, real_bucket) = self.resolve_existing(bucket).await;
         backend
             .list_objects_delegated(
@@ -1494,6 +2434,9 @@ mod tests {
             list_cooldown: std::time::Duration::from_secs(30),
             list_timeout: std::time::Duration::from_secs(5),
             list_fresh: std::time::Duration::ZERO,
+            mirrors: Arc::new(MirrorSet::empty()),
+            spans: Arc::new(SpanSet::empty()),
+            hedges: Arc::new(HedgeSet::empty()),
         };
 
         assert_eq!(
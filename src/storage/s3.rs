@@ -601,8 +601,10 @@ impl S3Backend {
     /// here: a synthesised "now" makes replication's NewerWins (which compares
     /// `created_at`) re-copy the object every tick. See the RCA in
     /// docs/plan/rca-replication-recopy-2026-06-30.md.
-    fn headers_to_metadata(
-        &self,
+    ///
+    /// Associated (no `&self`) so the Azure backend, which stores the same
+    /// bare `dg-*` map in blob metadata, parses it with the same rules.
+    pub(super) fn headers_to_metadata(
         headers: &HashMap<String, String>,
         created_at_fallback: DateTime<Utc>,
     ) -> Result<FileMetadata, StorageError> {
@@ -1010,7 +1012,7 @@ impl S3Backend {
         // corrupted (missing required fields), fall back to passthrough metadata
         // from the HEAD response itself.
         if !headers.is_empty() {
-            match self.headers_to_metadata(&headers, s3_last_modified) {
+            match Self::headers_to_metadata(&headers, s3_last_modified) {
                 Ok(meta) => return Ok(meta),
                 Err(e) if delta_critical => {
                     warn!(
@@ -2424,7 +2426,7 @@ impl StorageBackend for S3Backend {
 /// Distinct counting matters too: `k` and `k.delta` dedup into one entry, so
 /// counting duplicates could stop the fetch with an under-filled page and a
 /// false `is_truncated=false`.
-fn list_anchor<'a>(
+pub(super) fn list_anchor<'a>(
     raw_keys: impl Iterator<Item = &'a str>,
     common_prefixes: impl Iterator<Item = &'a str>,
     max_keys: u32,
@@ -2514,7 +2516,7 @@ fn late_delta_candidates(anchor: &str) -> Vec<String> {
 ///    candidate also sorts below `last_read` — the probe cannot find
 ///    anything new. (When the candidate IS a prefix of `last_read`, foreign
 ///    multi-suffix forms may still lie beyond it, so the probe stays.)
-fn confirmable_candidates(
+pub(super) fn confirmable_candidates(
     anchor: &str,
     request_prefix: &str,
     delimiter: Option<&str>,
@@ -2547,7 +2549,7 @@ fn confirmable_candidates(
 /// classification path (`trim_end_matches(".delta")`) maps to the same user
 /// key. Anything else under the prefix (`p.deltafoo`, `p.delta/x`) is a
 /// different user key and must not be injected here.
-fn probe_hit_serves_candidate(key: &str, candidate: &str) -> bool {
+pub(super) fn probe_hit_serves_candidate(key: &str, candidate: &str) -> bool {
     key.strip_prefix(candidate).is_some_and(|rest| {
         rest.len() % ".delta".len() == 0
             && rest
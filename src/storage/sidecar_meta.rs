@@ -0,0 +1,346 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Sidecar-file metadata storage for the filesystem backend, for
+//! filesystems without extended attributes (`metadata: sidecar`).
+//!
+//! A data file `{dir}/{name}` keeps its metadata in the hidden file
+//! `{dir}/.dgmeta.{name}`. Each sidecar entry is tied to the data file it
+//! describes by `(size, mtime)` — inode as tie-breaker — so an entry never
+//! answers for bytes it wasn't written with. That needs sub-second mtimes;
+//! [`validate_mtime_resolution`] refuses filesystems without them.
+//!
+//! Writes keep the same guarantee as the xattr path: a crash can never leave
+//! a data file visible without its metadata. [`persist`] first rewrites the
+//! sidecar (atomically, temp + rename) with entries for BOTH the staged file
+//! and the live one, then renames the data file into place, then drops the
+//! entry for the replaced file. Whichever data file survives a crash has its
+//! entry. Writers of one path are serialised so the entry lists can't race.
+
+use super::io_to_storage_error;
+use super::traits::StorageError;
+use crate::types::FileMetadata;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
+use tempfile::NamedTempFile;
+
+/// Sidecar filename prefix. The leading dot keeps sidecars in the backend's
+/// hidden namespace: never listed, pruned with an emptied bucket.
+pub(crate) const SIDECAR_PREFIX: &str = ".dgmeta.";
+
+/// Lock stripes serialising sidecar rewrites, keyed by data path.
+const STRIPES: usize = 64;
+
+/// Which data file an entry describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Identity {
+    size: u64,
+    mtime_ns: u64,
+    ino: u64,
+}
+
+impl Identity {
+    fn of(stat: &std::fs::Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+        let mtime_ns = stat
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX));
+        Self {
+            size: stat.len(),
+            mtime_ns,
+            ino: stat.ino(),
+        }
+    }
+
+    /// `(size, mtime)` match: inodes aren't stable on every filesystem that
+    /// lacks xattrs (some network and FUSE mounts renumber on remount).
+    fn same_content(&self, other: &Identity) -> bool {
+        self.size == other.size && self.mtime_ns == other.mtime_ns
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    #[serde(flatten)]
+    identity: Identity,
+    /// Kept as JSON so fields this build doesn't know survive a rewrite.
+    metadata: serde_json::Value,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Sidecar {
+    entries: Vec<Entry>,
+}
+
+impl Sidecar {
+    /// The entry for the data file with identity `id`, if any.
+    fn find(&self, id: &Identity) -> Option<&Entry> {
+        let matching: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|e| e.identity.same_content(id))
+            .collect();
+        matching
+            .iter()
+            .find(|e| e.identity.ino == id.ino)
+            .or(matching.first())
+            .copied()
+    }
+}
+
+/// Path of the sidecar for data file `path`.
+pub(crate) fn sidecar_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{SIDECAR_PREFIX}{name}"))
+}
+
+/// The data file a sidecar named `name` belongs to, if it is one.
+pub(crate) fn data_name(name: &str) -> Option<&str> {
+    name.strip_prefix(SIDECAR_PREFIX).filter(|n| !n.is_empty())
+}
+
+fn stripe(path: &Path) -> parking_lot::MutexGuard<'static, ()> {
+    use std::hash::{Hash, Hasher};
+    static LOCKS: OnceLock<Vec<parking_lot::Mutex<()>>> = OnceLock::new();
+    let locks = LOCKS.get_or_init(|| (0..STRIPES).map(|_| parking_lot::Mutex::new(())).collect());
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    path.hash(&mut hasher);
+    locks[hasher.finish() as usize % STRIPES].lock()
+}
+
+fn load(sidecar: &Path) -> Result<Sidecar, StorageError> {
+    match std::fs::read(sidecar) {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Sidecar::default()),
+        Err(e) => Err(io_to_storage_error(e)),
+    }
+}
+
+/// Atomically replace `sidecar` (temp + fsync + rename); no entries
+/// removes it.
+fn store(sidecar: &Path, content: &Sidecar) -> Result<(), StorageError> {
+    if content.entries.is_empty() {
+        return remove_file(sidecar);
+    }
+    let parent = sidecar
+        .parent()
+        .ok_or_else(|| StorageError::Other("Cannot write a sidecar with no parent".into()))?;
+    let mut tmp = NamedTempFile::new_in(parent).map_err(io_to_storage_error)?;
+    tmp.write_all(&serde_json::to_vec(content)?)
+        .map_err(io_to_storage_error)?;
+    tmp.as_file().sync_all().map_err(io_to_storage_error)?;
+    tmp.persist(sidecar)
+        .map_err(|e| io_to_storage_error(e.error))?;
+    Ok(())
+}
+
+fn remove_file(path: &Path) -> Result<(), StorageError> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(io_to_storage_error(e)),
+    }
+}
+
+fn stat(path: &Path) -> Result<Option<Identity>, StorageError> {
+    match std::fs::metadata(path) {
+        Ok(stat) => Ok(Some(Identity::of(&stat))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(io_to_storage_error(e)),
+    }
+}
+
+/// Blocking read of the raw metadata JSON for `path`.
+///
+/// `StorageError::NotFound` when the data file has no matching entry.
+pub(crate) fn read_raw(path: &Path) -> Result<serde_json::Value, StorageError> {
+    let not_found =
+        || StorageError::NotFound(format!("No metadata sidecar for {}", path.display()));
+    let id = stat(path)?.ok_or_else(not_found)?;
+    load(&sidecar_path(path))?
+        .find(&id)
+        .map(|e| e.metadata.clone())
+        .ok_or_else(not_found)
+}
+
+/// Blocking write of raw metadata JSON for the existing data file `path`,
+/// leaving its bytes and mtime untouched.
+pub(crate) fn write_raw(path: &Path, metadata: serde_json::Value) -> Result<(), StorageError> {
+    let _guard = stripe(path);
+    let identity = stat(path)?.ok_or_else(|| StorageError::NotFound(path.display().to_string()))?;
+    store(
+        &sidecar_path(path),
+        &Sidecar {
+            entries: vec![Entry { identity, metadata }],
+        },
+    )
+}
+
+/// Refuse a data directory whose filesystem keeps mtimes at whole seconds or
+/// coarser (FAT keeps 2 s, some NFS and SMB mounts 1 s). Entries are matched
+/// by `(size, mtime)`, so on such a filesystem two same-size writes within
+/// one tick would share an identity and a reader could get the replaced
+/// file's metadata.
+pub async fn validate_mtime_resolution(root: &Path) -> Result<(), StorageError> {
+    let probe = root.join(".dg_mtime_probe");
+    tokio::task::spawn_blocking(move || {
+        let written = UNIX_EPOCH + std::time::Duration::new(1_700_000_000, 123_456_789);
+        let result = (|| -> std::io::Result<std::time::SystemTime> {
+            let file = std::fs::File::create(&probe)?;
+            file.set_modified(written)?;
+            file.sync_all()?;
+            std::fs::metadata(&probe)?.modified()
+        })();
+        let _ = std::fs::remove_file(&probe);
+        let read = result.map_err(io_to_storage_error)?;
+        let subsecond = read
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.subsec_nanos());
+        if subsecond == 0 {
+            return Err(StorageError::Other(
+                "Filesystem at data directory stores file modification times at whole-second \
+                 precision or coarser. `metadata: sidecar` identifies files by size and mtime \
+                 and needs sub-second mtimes — use a local filesystem such as ext4, XFS or \
+                 tmpfs, or an NFS/SMB mount that preserves nanosecond timestamps."
+                    .into(),
+            ));
+        }
+        Ok(())
+    })
+    .await
+    .map_err(super::join_error)?
+}
+
+/// Read metadata from the sidecar of a data file.
+///
+/// Returns `StorageError::NotFound` if the file has no matching entry.
+pub async fn read_metadata(path: &Path) -> Result<FileMetadata, StorageError> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || Ok(serde_json::from_value(read_raw(&path)?)?))
+        .await
+        .map_err(super::join_error)?
+}
+
+/// Write metadata for the existing data file `path` into its sidecar.
+pub async fn write_metadata(path: &Path, metadata: &FileMetadata) -> Result<(), StorageError> {
+    let path = path.to_path_buf();
+    let value = serde_json::to_value(metadata)?;
+    tokio::task::spawn_blocking(move || write_raw(&path, value))
+        .await
+        .map_err(super::join_error)?
+}
+
+/// Drop the sidecar of a deleted data file. Blocking.
+pub(crate) fn remove_metadata(path: &Path) -> Result<(), StorageError> {
+    let _guard = stripe(path);
+    remove_file(&sidecar_path(path))
+}
+
+/// Rename the fsynced `tmp` into place at `target` with `metadata` (the
+/// crash-safe sequence in the module docs). Blocking.
+pub(crate) fn persist(
+    tmp: NamedTempFile,
+    target: &Path,
+    metadata: &FileMetadata,
+) -> Result<(), StorageError> {
+    let metadata = serde_json::to_value(metadata)?;
+    let staged = Identity::of(&tmp.as_file().metadata().map_err(io_to_storage_error)?);
+    let sidecar = sidecar_path(target);
+
+    let _guard = stripe(target);
+    let mut entries = vec![Entry {
+        identity: staged,
+        metadata,
+    }];
+    if let Some(live) = stat(target)? {
+        if let Some(entry) = load(&sidecar)?.find(&live) {
+            entries.push(entry.clone());
+        }
+    }
+    store(&sidecar, &Sidecar { entries })?;
+
+    tmp.persist(target)
+        .map_err(|e| io_to_storage_error(e.error))?;
+
+    // Drop the replaced file's entry. On failure the extra entry is inert:
+    // it describes bytes that no longer exist.
+    let mut current = load(&sidecar)?;
+    current.entries.retain(|e| e.identity == staged);
+    store(&sidecar, &current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::StorageInfo;
+
+    fn meta(name: &str, size: u64) -> FileMetadata {
+        FileMetadata::new_passthrough(name.to_string(), "sha".into(), "md5".into(), size, None)
+    }
+
+    fn stage(dir: &Path, bytes: &[u8]) -> NamedTempFile {
+        let mut tmp = NamedTempFile::new_in(dir).unwrap();
+        tmp.write_all(bytes).unwrap();
+        tmp
+    }
+
+    #[tokio::test]
+    async fn persist_round_trips_and_leaves_one_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("obj.bin");
+
+        persist(stage(dir.path(), b"one"), &target, &meta("obj.bin", 3)).unwrap();
+        persist(stage(dir.path(), b"second"), &target, &meta("obj.bin", 6)).unwrap();
+
+        let read = read_metadata(&target).await.unwrap();
+        assert_eq!(read.file_size, 6);
+        assert!(matches!(read.storage_info, StorageInfo::Passthrough));
+        assert_eq!(load(&sidecar_path(&target)).unwrap().entries.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn crash_before_rename_keeps_the_live_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("obj.bin");
+        persist(stage(dir.path(), b"live"), &target, &meta("obj.bin", 4)).unwrap();
+
+        // The first step of a second write, then a "crash" before the rename.
+        let staged = stage(dir.path(), b"staged!");
+        let staged_id = Identity::of(&staged.as_file().metadata().unwrap());
+        let mut sidecar = load(&sidecar_path(&target)).unwrap();
+        sidecar.entries.insert(
+            0,
+            Entry {
+                identity: staged_id,
+                metadata: serde_json::to_value(meta("obj.bin", 7)).unwrap(),
+            },
+        );
+        store(&sidecar_path(&target), &sidecar).unwrap();
+        drop(staged);
+
+        assert_eq!(read_metadata(&target).await.unwrap().file_size, 4);
+    }
+
+    #[tokio::test]
+    async fn rewritten_bytes_lose_their_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("obj.bin");
+        persist(stage(dir.path(), b"managed"), &target, &meta("obj.bin", 7)).unwrap();
+
+        std::fs::write(&target, b"edited outside the proxy").unwrap();
+        assert!(matches!(
+            read_metadata(&target).await,
+            Err(StorageError::NotFound(_))
+        ));
+
+        remove_metadata(&target).unwrap();
+        assert!(!sidecar_path(&target).exists());
+    }
+}
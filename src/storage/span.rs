@@ -0,0 +1,416 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Capacity-spanned buckets.
+//!
+//! A bucket with a `span` policy is stored across an ordered list of
+//! backends. The unit of placement is the deltaspace (the key prefix a
+//! reference and its deltas share), so a delta is always written next to its
+//! reference:
+//!
+//! - an existing deltaspace is found by probing the members
+//!   (`has_reference`, then one delimited listing page of the deltaspace's
+//!   level). The members are the only shared state, so the probe always
+//!   runs; the in-process placement index just puts the last known member
+//!   first;
+//! - a new deltaspace goes to the first member whose usage is below its
+//!   `capacity_bytes`, and is remembered only once its first write
+//!   succeeds. Usage comes from `total_size`, re-measured at most every
+//!   `DGP_SPAN_USAGE_TTL_SECS` (default 60), so capacity is a soft limit
+//!   like `quota_bytes`. Two instances placing the same new prefix at the
+//!   same moment can still pick different members.
+//!
+//! Bucket-wide reads (listings, scans, sizes) fan out over every member and
+//! merge; on a key present on two members the earlier member wins.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use super::traits::{LiteScanResult, StorageBackend, StorageError};
+use crate::metrics::Metrics;
+use crate::types::FileMetadata;
+
+/// One member of a spanned bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanMemberRoute {
+    pub backend: String,
+    pub real_bucket: String,
+    pub capacity_bytes: Option<u64>,
+}
+
+/// A spanned bucket's members, in fill order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanRoute {
+    pub members: Vec<SpanMemberRoute>,
+}
+
+/// The spanned buckets of one engine, sharing the routing layer's backends.
+pub struct SpanSet {
+    backends: HashMap<String, Arc<Box<dyn StorageBackend>>>,
+    routes: HashMap<String, SpanRoute>,
+    /// (bucket, deltaspace prefix) → member index last seen holding it.
+    placement: Mutex<HashMap<(String, String), usize>>,
+    /// (bucket, member index) → (measured at, bytes).
+    usage: Mutex<HashMap<(String, usize), (Instant, u64)>>,
+    /// How long a measured usage is trusted before `total_size` runs again.
+    usage_ttl: Duration,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl SpanSet {
+    /// # Errors
+    /// Returns an error if a route names a backend that isn't configured.
+    pub fn new(
+        backends: HashMap<String, Arc<Box<dyn StorageBackend>>>,
+        routes: HashMap<String, SpanRoute>,
+        metrics: Option<Arc<Metrics>>,
+    ) -> Result<Self, StorageError> {
+        for (bucket, route) in &routes {
+            if route.members.is_empty() {
+                return Err(StorageError::Other(format!(
+                    "Bucket '{bucket}' spans no backends"
+                )));
+            }
+            for member in &route.members {
+                if !backends.contains_key(&member.backend) {
+                    return Err(StorageError::Other(format!(
+                        "Bucket '{bucket}' spans unknown backend '{}'",
+                        member.backend
+                    )));
+                }
+            }
+        }
+        Ok(Self {
+            backends,
+            routes,
+            placement: Mutex::new(HashMap::new()),
+            usage: Mutex::new(HashMap::new()),
+            usage_ttl: Duration::from_secs(crate::config::env_parse_with_default(
+                "DGP_SPAN_USAGE_TTL_SECS",
+                60u64,
+            )),
+            metrics,
+        })
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            backends: HashMap::new(),
+            routes: HashMap::new(),
+            placement: Mutex::new(HashMap::new()),
+            usage: Mutex::new(HashMap::new()),
+            usage_ttl: Duration::ZERO,
+            metrics: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    pub fn route(&self, bucket: &str) -> Option<&SpanRoute> {
+        self.routes.get(bucket)
+    }
+
+    /// Spanned bucket names, sorted.
+    pub fn buckets(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.routes.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// `(backend, real bucket)` of member `idx`.
+    pub(super) fn member<'a>(
+        &'a self,
+        route: &'a SpanRoute,
+        idx: usize,
+    ) -> (&'a dyn StorageBackend, &'a str) {
+        let member = &route.members[idx];
+        (
+            self.backends[&member.backend].as_ref().as_ref(),
+            member.real_bucket.as_str(),
+        )
+    }
+
+    /// Every member as `(backend, real bucket)`, in fill order.
+    pub(super) fn members<'a>(
+        &'a self,
+        route: &'a SpanRoute,
+    ) -> impl Iterator<Item = (&'a dyn StorageBackend, &'a str)> + 'a {
+        (0..route.members.len()).map(move |idx| self.member(route, idx))
+    }
+
+    /// The member holding `prefix`'s deltaspace, if any member has it.
+    ///
+    /// The members themselves are the shared truth: another instance may
+    /// have placed, removed or re-placed the deltaspace since this process
+    /// last saw it. The placement index only orders the probe, so a
+    /// remembered member costs one probe and a stale entry is dropped.
+    async fn locate(
+        &self,
+        bucket: &str,
+        route: &SpanRoute,
+        prefix: &str,
+    ) -> Result<Option<usize>, StorageError> {
+        let key = (bucket.to_string(), prefix.to_string());
+        let hint = self.placement.lock().get(&key).copied();
+        let order = hint
+            .into_iter()
+            .chain((0..route.members.len()).filter(|idx| Some(*idx) != hint));
+        for idx in order {
+            let (backend, real) = self.member(route, idx);
+            if holds_deltaspace(backend, real, prefix).await? {
+                if hint != Some(idx) {
+                    self.placement.lock().insert(key, idx);
+                }
+                return Ok(Some(idx));
+            }
+        }
+        if hint.is_some() {
+            self.placement.lock().remove(&key);
+        }
+        Ok(None)
+    }
+
+    /// Member index a read of `prefix` goes to: where the deltaspace lives,
+    /// else the first member (which answers NotFound like any backend).
+    pub(super) async fn read_index(
+        &self,
+        bucket: &str,
+        route: &SpanRoute,
+        prefix: &str,
+    ) -> Result<usize, StorageError> {
+        Ok(self.locate(bucket, route, prefix).await?.unwrap_or(0))
+    }
+
+    /// Member index a write to `prefix` goes to: where the deltaspace lives,
+    /// else the first member with free capacity. A new placement is not
+    /// remembered here; the caller reports it through [`Self::placed`] once
+    /// the write has succeeded.
+    pub(super) async fn write_index(
+        &self,
+        bucket: &str,
+        route: &SpanRoute,
+        prefix: &str,
+    ) -> Result<usize, StorageError> {
+        if let Some(idx) = self.locate(bucket, route, prefix).await? {
+            return Ok(idx);
+        }
+        for (idx, member) in route.members.iter().enumerate() {
+            if let Some(capacity) = member.capacity_bytes {
+                if self.member_usage(bucket, route, idx).await? >= capacity {
+                    continue;
+                }
+            }
+            return Ok(idx);
+        }
+        Err(StorageError::DiskFull)
+    }
+
+    /// Record that a write to `prefix` succeeded on member `idx`.
+    pub(super) fn placed(&self, bucket: &str, route: &SpanRoute, prefix: &str, idx: usize) {
+        let previous = self
+            .placement
+            .lock()
+            .insert((bucket.to_string(), prefix.to_string()), idx);
+        if previous != Some(idx) {
+            if let Some(m) = &self.metrics {
+                m.span_placements_total
+                    .with_label_values(&[bucket, &route.members[idx].backend])
+                    .inc();
+            }
+        }
+    }
+
+    /// Bytes the bucket holds on member `idx`, re-measured after `usage_ttl`.
+    async fn member_usage(
+        &self,
+        bucket: &str,
+        route: &SpanRoute,
+        idx: usize,
+    ) -> Result<u64, StorageError> {
+        let key = (bucket.to_string(), idx);
+        if let Some((at, bytes)) = self.usage.lock().get(&key) {
+            if at.elapsed() < self.usage_ttl {
+                return Ok(*bytes);
+            }
+        }
+        let (backend, real) = self.member(route, idx);
+        let bytes = backend.total_size(Some(real)).await?;
+        self.usage.lock().insert(key, (Instant::now(), bytes));
+        if let Some(m) = &self.metrics {
+            m.span_member_usage_bytes
+                .with_label_values(&[bucket, &route.members[idx].backend])
+                .set(bytes as f64);
+        }
+        Ok(bytes)
+    }
+
+    /// Bytes the bucket holds per member (fresh measurement), in fill order.
+    pub async fn usage(&self, bucket: &str) -> Result<Vec<(String, u64)>, StorageError> {
+        let route = self
+            .routes
+            .get(bucket)
+            .ok_or_else(|| StorageError::BucketNotFound(format!("{bucket} is not spanned")))?;
+        self.usage.lock().retain(|(b, _), _| b != bucket);
+        let mut out = Vec::with_capacity(route.members.len());
+        for (idx, member) in route.members.iter().enumerate() {
+            out.push((
+                member.backend.clone(),
+                self.member_usage(bucket, route, idx).await?,
+            ));
+        }
+        Ok(out)
+    }
+}
+
+/// Entries asked of a member's delimited listing when probing for a
+/// deltaspace: enough to get past a few sub-prefixes in one page.
+const PROBE_KEYS: u32 = 100;
+
+/// Whether `backend` holds anything in `prefix`'s deltaspace: a reference
+/// HEAD, then one delimited listing page of the deltaspace's own level.
+/// Only a backend that can't delegate listings, or a page of nothing but
+/// sub-prefixes, falls back to the lite scan.
+async fn holds_deltaspace(
+    backend: &dyn StorageBackend,
+    real: &str,
+    prefix: &str,
+) -> Result<bool, StorageError> {
+    if backend.has_reference(real, prefix).await? {
+        return Ok(true);
+    }
+    let level = if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("{prefix}/")
+    };
+    if let Some(page) = backend
+        .list_objects_delegated(real, &level, Some("/"), PROBE_KEYS, None)
+        .await?
+    {
+        if page.objects.iter().any(|(key, _)| !key.ends_with('/')) {
+            return Ok(true);
+        }
+        if !page.is_truncated {
+            return Ok(false);
+        }
+    }
+    Ok(!backend
+        .scan_deltaspace_lite(real, prefix)
+        .await?
+        .metadata
+        .is_empty())
+}
+
+/// Merge per-member listings: sorted by key, earlier member wins a duplicate.
+pub(super) fn merge_listings(
+    listings: Vec<Vec<(String, FileMetadata)>>,
+) -> Vec<(String, FileMetadata)> {
+    let mut merged: Vec<(String, FileMetadata)> = listings.into_iter().flatten().collect();
+    // Stable sort keeps member order among equal keys.
+    merged.sort_by(|a, b| a.0.cmp(&b.0));
+    merged.dedup_by(|later, earlier| later.0 == earlier.0);
+    merged
+}
+
+/// Merge per-member lite scans of one deltaspace.
+pub(super) fn merge_lite_scans(scans: Vec<LiteScanResult>) -> LiteScanResult {
+    let mut out = LiteScanResult {
+        metadata: Vec::new(),
+        originals_estimated: false,
+    };
+    for scan in scans {
+        out.metadata.extend(scan.metadata);
+        out.originals_estimated |= scan.originals_estimated;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(key: &str, size: u64) -> (String, FileMetadata) {
+        (
+            key.to_string(),
+            FileMetadata::new_passthrough(key.into(), "sha".into(), "md5".into(), size, None),
+        )
+    }
+
+    /// A two-member span over fresh filesystem backends, with `archive`
+    /// created on both.
+    async fn fs_span(dirs: &[tempfile::TempDir; 2]) -> SpanSet {
+        let mut backends: HashMap<String, Arc<Box<dyn StorageBackend>>> = HashMap::new();
+        let mut members = Vec::new();
+        for (name, dir) in ["a", "b"].into_iter().zip(dirs) {
+            let backend = crate::storage::FilesystemBackend::new(dir.path().to_path_buf())
+                .await
+                .unwrap();
+            backend.create_bucket("archive").await.unwrap();
+            backends.insert(name.to_string(), Arc::new(Box::new(backend)));
+            members.push(SpanMemberRoute {
+                backend: name.to_string(),
+                real_bucket: "archive".to_string(),
+                capacity_bytes: None,
+            });
+        }
+        let routes = HashMap::from([("archive".to_string(), SpanRoute { members })]);
+        SpanSet::new(backends, routes, None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn placement_follows_the_members_not_the_local_index() {
+        let dirs = [tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap()];
+        let spans = fs_span(&dirs).await;
+        let route = spans.route("archive").unwrap().clone();
+
+        // Choosing a member remembers nothing until the write succeeds.
+        assert_eq!(spans.write_index("archive", &route, "p").await.unwrap(), 0);
+        assert!(spans.placement.lock().is_empty());
+        spans.placed("archive", &route, "p", 0);
+
+        // Another instance stored the deltaspace on member 1: the probe of
+        // the shared members wins over this process's stale entry.
+        let (other, real) = spans.member(&route, 1);
+        let (_, meta) = meta("p/f.txt", 4);
+        other
+            .put_passthrough(real, "p", "f.txt", b"data", &meta)
+            .await
+            .unwrap();
+        assert_eq!(spans.read_index("archive", &route, "p").await.unwrap(), 1);
+        assert_eq!(spans.write_index("archive", &route, "p").await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn a_nested_prefix_does_not_place_its_parent() {
+        let dirs = [tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap()];
+        let spans = fs_span(&dirs).await;
+        let route = spans.route("archive").unwrap().clone();
+
+        let (other, real) = spans.member(&route, 1);
+        let (_, meta) = meta("p/sub/f.txt", 4);
+        other
+            .put_passthrough(real, "p/sub", "f.txt", b"data", &meta)
+            .await
+            .unwrap();
+        assert_eq!(spans.locate("archive", &route, "p").await.unwrap(), None);
+        assert_eq!(
+            spans.locate("archive", &route, "p/sub").await.unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn merged_listing_is_sorted_and_earlier_member_wins() {
+        let merged = merge_listings(vec![
+            vec![meta("b", 1), meta("d", 1)],
+            vec![meta("a", 2), meta("b", 2), meta("c", 2)],
+        ]);
+        let keys: Vec<&str> = merged.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["a", "b", "c", "d"]);
+        assert_eq!(merged[1].1.file_size, 1, "member 0's copy of 'b' wins");
+    }
+}
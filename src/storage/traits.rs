@@ -375,6 +375,37 @@ pub trait StorageBackend: Send + Sync {
         end: u64, // inclusive
     ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError>;
 
+    /// [`get_passthrough_stream`](Self::get_passthrough_stream) for a file whose
+    /// metadata the caller already holds. A layer with a second copy of the
+    /// bucket may hedge the read onto that copy, serving it only when its
+    /// metadata matches `expected` (see [`super::hedge`]). Default: a plain read.
+    async fn get_passthrough_stream_hedged(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        expected: &FileMetadata,
+    ) -> Result<BoxStream<'static, Result<Bytes, StorageError>>, StorageError> {
+        let _ = expected;
+        self.get_passthrough_stream(bucket, prefix, filename).await
+    }
+
+    /// Ranged counterpart of
+    /// [`get_passthrough_stream_hedged`](Self::get_passthrough_stream_hedged).
+    async fn get_passthrough_stream_range_hedged(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        start: u64,
+        end: u64, // inclusive
+        expected: &FileMetadata,
+    ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError> {
+        let _ = expected;
+        self.get_passthrough_stream_range(bucket, prefix, filename, start, end)
+            .await
+    }
+
     /// Store a passthrough file from pre-split chunks without assembling into a contiguous buffer.
     /// Default implementation collects chunks and delegates to `put_passthrough()`.
     async fn put_passthrough_chunked(
@@ -398,8 +429,9 @@ pub trait StorageBackend: Send + Sync {
     //
     // These four methods let the transfer layer drive a multipart upload
     // to the backend with bounded memory. `S3Backend` overrides them with
-    // native aws-sdk multipart so the prod S3→S3 path stays O(part_size).
-    // The DEFAULT impls (filesystem + test spies) report `native: false`,
+    // native aws-sdk multipart so the prod S3→S3 path stays O(part_size);
+    // `FilesystemBackend` stages parts on disk under the bucket.
+    // The DEFAULT impls (erasure + test spies) report `native: false`,
     // which makes the transfer layer retain each part's bytes and hand the
     // assembled body to `complete_multipart_upload` — correct but O(object)
     // in memory. Proxy-AES-encrypting backends are gated OFF at the
@@ -475,6 +507,80 @@ pub trait StorageBackend: Send + Sync {
         Ok(())
     }
 
+    // === Persisted multipart uploads ===
+    //
+    // A backend that keeps in-progress uploads as durable state (filesystem
+    // staging) can take client multipart uploads directly: they survive a
+    // proxy restart, and the methods below find them again by id.
+
+    /// Does the backend serving `bucket` persist multipart uploads so they
+    /// can be resumed by id after a restart? When true, client multipart
+    /// uploads are staged on the backend instead of buffered by the proxy.
+    /// Default `false`.
+    fn persists_multipart_uploads(&self, _bucket: &str) -> bool {
+        false
+    }
+
+    /// Re-open the persisted upload `upload_id` targeting `prefix/filename`.
+    /// `StorageError::NotFound` when there is no such upload for that key.
+    async fn resume_multipart_upload(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        filename: &str,
+        upload_id: &str,
+    ) -> Result<StagedMultipartUpload, StorageError> {
+        let _ = (bucket, prefix, filename);
+        Err(StorageError::NotFound(format!(
+            "multipart upload {upload_id}"
+        )))
+    }
+
+    /// Parts uploaded so far to a persisted upload, in part-number order.
+    async fn list_multipart_parts(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+    ) -> Result<Vec<StagedPart>, StorageError> {
+        let _ = (upload, prefix, filename);
+        Ok(Vec::new())
+    }
+
+    /// Bytes of one part of a persisted upload. Lets a small upload be
+    /// completed through the delta pipeline instead of a native complete.
+    async fn read_multipart_part(
+        &self,
+        upload: &MultipartUpload,
+        prefix: &str,
+        filename: &str,
+        part_number: i32,
+    ) -> Result<Bytes, StorageError> {
+        let _ = (prefix, filename);
+        Err(StorageError::NotFound(format!(
+            "part {part_number} of multipart upload {}",
+            upload.upload_id
+        )))
+    }
+
+    /// Persisted uploads to `bucket` that were neither completed nor aborted.
+    async fn list_multipart_uploads(
+        &self,
+        bucket: &str,
+    ) -> Result<Vec<StagedMultipartUpload>, StorageError> {
+        let _ = bucket;
+        Ok(Vec::new())
+    }
+
+    /// Abort every persisted upload with no activity for `idle`.
+    async fn sweep_multipart_uploads(
+        &self,
+        idle: std::time::Duration,
+    ) -> Result<StagedSweepReport, StorageError> {
+        let _ = idle;
+        Ok(StagedSweepReport::default())
+    }
+
     /// Encryption-mode label for the backend serving `bucket`, matching
     /// `BackendEncryptionConfig::mode_tag` (`"none"` / `"aes256-gcm-proxy"`
     /// / `"sse-kms"` / `"sse-s3"`). Feeds the pure
@@ -490,7 +596,7 @@ pub trait StorageBackend: Send + Sync {
     /// memory-bounded on such backends — a `false` backend forces the caller
     /// to retain every part's bytes for `complete` (O(object_size) RAM), so
     /// the streaming gate must route those to the spooled/buffered path
-    /// instead. Default `false` (the buffering default impl + filesystem).
+    /// instead. Default `false` (the buffering default impl).
     fn supports_native_multipart(&self, _bucket: &str) -> bool {
         false
     }
@@ -506,6 +612,23 @@ pub trait StorageBackend: Send + Sync {
         true
     }
 
+    /// Do writes to `bucket` encrypt at rest AND stamp the key id? A blob
+    /// rewritten through such a backend comes back encrypted and stamped;
+    /// anywhere else (no wrapper, PassThrough shim, no key) it lands as
+    /// plaintext. Default `false`; the `EncryptingBackend` wrapper and
+    /// `RoutingBackend` override it.
+    fn stamps_encryption_key_id(&self, _bucket: &str) -> bool {
+        false
+    }
+
+    /// Does the backend serving `bucket` keep its own files under names
+    /// like `filename`, next to the objects? Such a name can't be stored as
+    /// an object: the write would clobber the backend's file. The filesystem
+    /// backend in sidecar mode reserves `.dgmeta.*`. Default `false`.
+    fn reserves_filename(&self, _bucket: &str, _filename: &str) -> bool {
+        false
+    }
+
     // === Scanning operations ===
 
     /// Scan a deltaspace directory and return all file metadata
@@ -635,6 +758,55 @@ pub struct UploadedPart {
     pub etag: String,
 }
 
+/// S3-style multipart ETag from the part ETags: md5 over the concatenated
+/// binary part md5s, suffixed with the part count.
+pub(crate) fn multipart_etag(parts: &[UploadedPart]) -> String {
+    use md5::{Digest, Md5};
+    let mut hasher = Md5::new();
+    for part in parts {
+        if let Ok(raw) = hex::decode(part.etag.trim_matches('"')) {
+            hasher.update(raw);
+        }
+    }
+    format!("{}-{}", hex::encode(hasher.finalize()), parts.len())
+}
+
+/// A multipart upload persisted by the backend (see
+/// [`StorageBackend::persists_multipart_uploads`]).
+#[derive(Debug, Clone)]
+pub struct StagedMultipartUpload {
+    pub upload: MultipartUpload,
+    pub prefix: String,
+    pub filename: String,
+    /// Metadata given at create: content type and user metadata.
+    pub metadata: FileMetadata,
+    pub initiated: chrono::DateTime<chrono::Utc>,
+}
+
+/// One part of a persisted multipart upload.
+#[derive(Debug, Clone)]
+pub struct StagedPart {
+    pub part_number: i32,
+    /// Quoted MD5 of the part bytes.
+    pub etag: String,
+    pub size: u64,
+    pub last_modified: chrono::DateTime<chrono::Utc>,
+}
+
+/// What [`StorageBackend::sweep_multipart_uploads`] reclaimed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StagedSweepReport {
+    pub uploads: u64,
+    pub bytes: u64,
+}
+
+impl std::ops::AddAssign for StagedSweepReport {
+    fn add_assign(&mut self, other: Self) {
+        self.uploads += other.uploads;
+        self.bytes += other.bytes;
+    }
+}
+
 /// Result from `list_objects_delegated` when the backend handles delimiter
 /// collapsing natively.
 pub struct DelegatedListResult {
@@ -902,6 +1074,34 @@ macro_rules! impl_storage_backend_for_box {
                     .await
             }
 
+            async fn get_passthrough_stream_hedged(
+                &self,
+                bucket: &str,
+                prefix: &str,
+                filename: &str,
+                expected: &FileMetadata,
+            ) -> Result<BoxStream<'static, Result<Bytes, StorageError>>, StorageError> {
+                (**self)
+                    .get_passthrough_stream_hedged(bucket, prefix, filename, expected)
+                    .await
+            }
+
+            async fn get_passthrough_stream_range_hedged(
+                &self,
+                bucket: &str,
+                prefix: &str,
+                filename: &str,
+                start: u64,
+                end: u64,
+                expected: &FileMetadata,
+            ) -> Result<(BoxStream<'static, Result<Bytes, StorageError>>, u64), StorageError> {
+                (**self)
+                    .get_passthrough_stream_range_hedged(
+                        bucket, prefix, filename, start, end, expected,
+                    )
+                    .await
+            }
+
             async fn put_passthrough_chunked(
                 &self,
                 bucket: &str,
@@ -961,6 +1161,53 @@ macro_rules! impl_storage_backend_for_box {
                     .abort_multipart_upload(upload, prefix, filename)
                     .await
             }
+            fn persists_multipart_uploads(&self, bucket: &str) -> bool {
+                (**self).persists_multipart_uploads(bucket)
+            }
+            async fn resume_multipart_upload(
+                &self,
+                bucket: &str,
+                prefix: &str,
+                filename: &str,
+                upload_id: &str,
+            ) -> Result<StagedMultipartUpload, StorageError> {
+                (**self)
+                    .resume_multipart_upload(bucket, prefix, filename, upload_id)
+                    .await
+            }
+            async fn list_multipart_parts(
+                &self,
+                upload: &MultipartUpload,
+                prefix: &str,
+                filename: &str,
+            ) -> Result<Vec<StagedPart>, StorageError> {
+                (**self)
+                    .list_multipart_parts(upload, prefix, filename)
+                    .await
+            }
+            async fn read_multipart_part(
+                &self,
+                upload: &MultipartUpload,
+                prefix: &str,
+                filename: &str,
+                part_number: i32,
+            ) -> Result<Bytes, StorageError> {
+                (**self)
+                    .read_multipart_part(upload, prefix, filename, part_number)
+                    .await
+            }
+            async fn list_multipart_uploads(
+                &self,
+                bucket: &str,
+            ) -> Result<Vec<StagedMultipartUpload>, StorageError> {
+                (**self).list_multipart_uploads(bucket).await
+            }
+            async fn sweep_multipart_uploads(
+                &self,
+                idle: std::time::Duration,
+            ) -> Result<StagedSweepReport, StorageError> {
+                (**self).sweep_multipart_uploads(idle).await
+            }
             fn multipart_storage_label(&self, bucket: &str) -> &'static str {
                 (**self).multipart_storage_label(bucket)
             }
@@ -970,6 +1217,12 @@ macro_rules! impl_storage_backend_for_box {
             fn lite_list_carries_logical_facts(&self, bucket: &str) -> bool {
                 (**self).lite_list_carries_logical_facts(bucket)
             }
+            fn stamps_encryption_key_id(&self, bucket: &str) -> bool {
+                (**self).stamps_encryption_key_id(bucket)
+            }
+            fn reserves_filename(&self, bucket: &str, filename: &str) -> bool {
+                (**self).reserves_filename(bucket, filename)
+            }
 
             async fn scan_deltaspace(
                 &self,
@@ -1029,3 +1282,29 @@ macro_rules! impl_storage_backend_for_box {
 }
 
 impl_storage_backend_for_box!();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multipart_etag_matches_s3_shape() {
+        use md5::{Digest, Md5};
+        let p1 = hex::encode(Md5::digest(b"part one"));
+        let p2 = hex::encode(Md5::digest(b"part two"));
+        let parts = [
+            UploadedPart {
+                part_number: 1,
+                etag: format!("\"{p1}\""),
+            },
+            UploadedPart {
+                part_number: 2,
+                etag: format!("\"{p2}\""),
+            },
+        ];
+        let mut concat = hex::decode(&p1).unwrap();
+        concat.extend(hex::decode(&p2).unwrap());
+        let expected = format!("{}-2", hex::encode(Md5::digest(&concat)));
+        assert_eq!(multipart_etag(&parts), expected);
+    }
+}
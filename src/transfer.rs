@@ -13,8 +13,10 @@ use crate::storage::UploadedPart;
 use crate::transfer_plan::{self, PartSpan};
 use bytes::{Bytes, BytesMut};
 use futures::stream::{StreamExt, TryStreamExt};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tracing::{info, warn};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
 /// RAII guard for one in-flight streaming-copy part. Increments
 /// `parts_inflight` (+peak) on construction; records resident part bytes via
@@ -62,8 +64,139 @@ impl Drop for PartGuard {
     }
 }
 
+/// Token bucket enforcing a bytes/second ceiling on copies. Burst is one
+/// second's worth; beyond that the bucket goes into DEBT, so concurrent
+/// copies queue behind each other and the long-run rate holds at the ceiling
+/// even when one charge (a 64 MiB part) dwarfs the burst. Rate `0` = uncapped.
+#[derive(Debug)]
+pub(crate) struct BandwidthLimiter {
+    bytes_per_sec: AtomicU64,
+    bucket: parking_lot::Mutex<TokenBucket>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl BandwidthLimiter {
+    pub(crate) fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec: AtomicU64::new(bytes_per_sec),
+            bucket: parking_lot::Mutex::new(TokenBucket {
+                tokens: bytes_per_sec as f64,
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    /// Change the ceiling (hot reload). A changed rate starts from a full
+    /// bucket: debt run up under the old rate is forgiven.
+    pub(crate) fn set_rate(&self, bytes_per_sec: u64) {
+        if self.bytes_per_sec.swap(bytes_per_sec, Ordering::Relaxed) != bytes_per_sec {
+            *self.bucket.lock() = TokenBucket {
+                tokens: bytes_per_sec as f64,
+                refilled: Instant::now(),
+            };
+        }
+    }
+
+    /// Take `bytes` of tokens at `now`; returns how long the caller must wait
+    /// before moving them.
+    fn reserve(&self, bytes: u64, now: Instant) -> Duration {
+        let rate = self.bytes_per_sec.load(Ordering::Relaxed);
+        if rate == 0 {
+            return Duration::ZERO;
+        }
+        let rate = rate as f64;
+        let mut b = self.bucket.lock();
+        let elapsed = now.saturating_duration_since(b.refilled).as_secs_f64();
+        b.tokens = (b.tokens + elapsed * rate).min(rate);
+        b.refilled = now;
+        b.tokens -= bytes as f64;
+        if b.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-b.tokens / rate)
+        }
+    }
+}
+
+/// The buckets one copy draws from (replication: the rule's own cap + the
+/// global cap) and how long copies were held back by them, for the Jobs
+/// screen.
+#[derive(Debug)]
+pub(crate) struct BandwidthBudget {
+    limiters: Vec<Arc<BandwidthLimiter>>,
+    waiting: AtomicUsize,
+    throttled_ms: AtomicU64,
+}
+
+impl BandwidthBudget {
+    pub(crate) fn new(limiters: Vec<Arc<BandwidthLimiter>>) -> Self {
+        Self {
+            limiters,
+            waiting: AtomicUsize::new(0),
+            throttled_ms: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn limiters(&self) -> &[Arc<BandwidthLimiter>] {
+        &self.limiters
+    }
+
+    /// Charge `bytes` against every bucket and sleep off the longest wait.
+    /// Never called with a lock held — a throttled copy sleeps for seconds.
+    pub(crate) async fn charge(&self, bytes: u64) {
+        let now = Instant::now();
+        let wait = self
+            .limiters
+            .iter()
+            .map(|l| l.reserve(bytes, now))
+            .max()
+            .unwrap_or(Duration::ZERO);
+        if wait.is_zero() {
+            return;
+        }
+        self.waiting.fetch_add(1, Ordering::Relaxed);
+        // Settle the counters even when a kill drops the sleeping copy.
+        struct Waiting<'a>(&'a BandwidthBudget, Instant);
+        impl Drop for Waiting<'_> {
+            fn drop(&mut self) {
+                self.0.waiting.fetch_sub(1, Ordering::Relaxed);
+                self.0
+                    .throttled_ms
+                    .fetch_add(self.1.elapsed().as_millis() as u64, Ordering::Relaxed);
+            }
+        }
+        let _waiting = Waiting(self, now);
+        tokio::time::sleep(wait).await;
+    }
+
+    /// A copy is sleeping on the cap right now.
+    pub(crate) fn is_waiting(&self) -> bool {
+        self.waiting.load(Ordering::Relaxed) > 0
+    }
+
+    pub(crate) fn throttled_secs(&self) -> u64 {
+        self.throttled_ms.load(Ordering::Relaxed) / 1000
+    }
+}
+
+/// Charge a copy's bandwidth budget, if it has one.
+async fn charge_bandwidth(request: &ObjectTransferRequest<'_>, bytes: u64) {
+    if let Some(budget) = request.bandwidth {
+        budget.charge(bytes).await;
+    }
+}
+
 pub(crate) const DEFAULT_COPY_MAX_ATTEMPTS: u32 = 3;
 pub(crate) const REPLICATION_RULE_METADATA_KEY: &str = "dg-replication-rule";
+/// Hybrid-logical-clock version stamp carried by bidirectional-replication
+/// copies (`replication::hlc`). The copy keeps the ORIGIN write's stamp, so
+/// both sides of a synced key agree on its version.
+pub(crate) const HLC_METADATA_KEY: &str = "dg-hlc";
 pub(crate) const LIFECYCLE_RULE_METADATA_KEY: &str = "dg-lifecycle-rule";
 
 #[derive(Debug, Clone, Copy)]
@@ -72,13 +205,16 @@ pub(crate) struct TransferProvenance<'a> {
     pub metadata_value: &'a str,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Clone, Copy)]
 pub(crate) struct ObjectTransferRequest<'a> {
     pub source_bucket: &'a str,
     pub source_key: &'a str,
     pub destination_bucket: &'a str,
     pub destination_key: &'a str,
     pub provenance: Option<TransferProvenance<'a>>,
+    /// Extra user-metadata entries stamped on the destination copy after the
+    /// provenance marker (bidirectional replication's `dg-hlc` stamp).
+    pub extra_user_metadata: &'a [(&'a str, &'a str)],
     /// User-metadata keys to DROP from the copied metadata before the
     /// destination store. The re-encryption job uses this to shed stale
     /// `dg-encrypted` / `dg-encryption-key-id` markers when rewriting
@@ -92,6 +228,15 @@ pub(crate) struct ObjectTransferRequest<'a> {
     /// falls back to the env-resolved `transfer_plan::upload_concurrency()`.
     /// Only the replication worker overrides it (from config).
     pub upload_concurrency: Option<usize>,
+    /// Bandwidth caps charged for the bytes this copy moves (replication's
+    /// per-rule + global caps). `None` = uncapped.
+    pub bandwidth: Option<&'a BandwidthBudget>,
+    /// Engine holding the source when it is not the destination's — a
+    /// replication rule reading from an external S3 endpoint, or writing to
+    /// one (then this is the proxy engine). `None` = one engine serves both
+    /// sides. A cross-engine copy skips the same-engine shortcuts (multipart
+    /// stream-copy, the delta fast path) and moves the reconstructed body.
+    pub source_engine: Option<&'a Arc<DynEngine>>,
 }
 
 /// How one object was physically moved source→dest. Surfaced to the
@@ -172,12 +317,16 @@ pub(crate) async fn copy_object_with_retries(
         .into())
 }
 
+/// `engine` serves the destination (and the source, unless the request
+/// names its own `source_engine`).
 async fn copy_object_once(
     engine: &Arc<DynEngine>,
     request: ObjectTransferRequest<'_>,
 ) -> Result<ObjectTransferOutcome, Box<dyn std::error::Error + Send + Sync>> {
+    let source = request.source_engine.unwrap_or(engine);
+    let same_engine = request.source_engine.is_none();
     // HEAD first so callers get a crisp source-disappeared failure row.
-    let source_head = engine
+    let source_head = source
         .head(request.source_bucket, request.source_key)
         .await
         .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> {
@@ -191,6 +340,7 @@ async fn copy_object_once(
     let label = source_head.storage_info.label();
     let threshold = transfer_plan::stream_copy_threshold();
     if transfer_plan::should_stream_copy(source_head.file_size, label, threshold)
+        && same_engine
         && engine.destination_supports_native_multipart(request.destination_bucket)
     {
         return stream_copy_passthrough(engine, request, &source_head).await;
@@ -200,10 +350,12 @@ async fn copy_object_once(
     // `.delta` blob verbatim (seeding the dest reference if needed). Any
     // `Ok(None)` = the gate said fall back → the buffered reconstruct path
     // below runs unchanged (no duplication).
-    if matches!(
-        source_head.storage_info,
-        crate::types::StorageInfo::Delta { .. }
-    ) {
+    if same_engine
+        && matches!(
+            source_head.storage_info,
+            crate::types::StorageInfo::Delta { .. }
+        )
+    {
         if let Some(outcome) = delta_passthrough_copy(engine, request, &source_head).await? {
             return Ok(outcome);
         }
@@ -220,7 +372,7 @@ async fn copy_object_once(
         }
     }
 
-    let (data, meta) = engine
+    let (data, meta) = source
         .retrieve(request.source_bucket, request.source_key)
         .await
         .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> {
@@ -230,6 +382,7 @@ async fn copy_object_once(
     let content_type = meta.content_type.clone();
     let mut user_metadata = meta.user_metadata.clone();
     let bytes = data.len();
+    charge_bandwidth(&request, bytes as u64).await;
 
     if let Some(provenance) = request.provenance {
         user_metadata.insert(
@@ -237,6 +390,10 @@ async fn copy_object_once(
             provenance.metadata_value.to_string(),
         );
     }
+
+    for (key, value) in request.extra_user_metadata {
+        user_metadata.insert(key.to_string(), value.to_string());
+    }
     for key in request.strip_user_metadata_keys {
         user_metadata.remove(*key);
     }
@@ -338,6 +495,9 @@ async fn stream_copy_passthrough(
             provenance.metadata_value.to_string(),
         );
     }
+    for (key, value) in request.extra_user_metadata {
+        user_metadata.insert(key.to_string(), value.to_string());
+    }
     for key in request.strip_user_metadata_keys {
         user_metadata.remove(*key);
     }
@@ -384,6 +544,7 @@ async fn stream_copy_passthrough(
     let src_key = request.source_key.to_string();
     let pinned_head = source_head.clone();
     let metrics = engine.metrics().cloned();
+    let bandwidth = request.bandwidth;
     let results: Result<Vec<PartUploadResult>, String> =
         futures::stream::iter(spans.iter().copied())
             .map(|span| {
@@ -412,6 +573,9 @@ async fn stream_copy_passthrough(
                     if let Some(g) = guard.as_mut() {
                         g.resident(len);
                     }
+                    if let Some(budget) = bandwidth {
+                        budget.charge(len).await;
+                    }
                     let retained = if native {
                         None
                     } else {
@@ -810,7 +974,11 @@ pub(crate) fn is_transient_copy_error(message: &str) -> bool {
 // destination if the dest deltaspace holds the byte-identical reference
 // the delta was encoded against. The gate `can_delta_passthrough` is the
 // single decision point; corruption is impossible as long as it returns
-// `Fallback` on any sha/enc doubt. v1 ships ONLY plaintext sources.
+// `Fallback` on any sha/key doubt. Encryption is per blob, not per
+// object: the raw accessors decrypt with the source key and re-encrypt
+// with the dest key, so differently-keyed sides RE-WRAP the delta (and a
+// seeded reference) instead of rehydrating the object. Legacy blobs with
+// no stamped key_id are backfilled in place first (`backfill_*_key_id`).
 
 /// At-rest encryption fingerprint of a blob, derived from metadata markers.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -837,33 +1005,34 @@ pub(crate) struct DestRefFacts {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum DeltaPassthroughDecision {
     ShipVerbatim,
+    /// Same reference, different at-rest keys (or plaintext on one side):
+    /// the delta is decrypted with the source key and re-encrypted with the
+    /// dest key on the way through — still no reconstruct.
+    ShipRewrapped,
     SeedThenShip,
-    Fallback { reason: &'static str },
+    Fallback {
+        reason: &'static str,
+    },
 }
 
-/// True iff the two fingerprints can host the SAME verbatim blob: both
-/// plaintext, or both encrypted with an equal KNOWN key_id. `Encrypted{None}`
-/// is never compatible — we can't prove the two blobs share a key.
-fn enc_compatible(a: &EncFingerprint, b: &EncFingerprint) -> bool {
-    match (a, b) {
-        (EncFingerprint::Plaintext, EncFingerprint::Plaintext) => true,
-        (
-            EncFingerprint::Encrypted { key_id: Some(ka) },
-            EncFingerprint::Encrypted { key_id: Some(kb) },
-        ) => ka == kb,
-        _ => false,
-    }
+/// True iff we know which key (if any) protects the blob: plaintext, or
+/// encrypted under a stamped key_id. `Encrypted{None}` is a legacy blob
+/// whose key we can't name without decrypting it — the copy backfills one
+/// before gating, and a blob that stays unstamped falls back.
+fn key_known(enc: &EncFingerprint) -> bool {
+    !matches!(enc, EncFingerprint::Encrypted { key_id: None })
 }
 
-/// PURE decision: can we ship the source `.delta` verbatim to the dest?
+/// PURE decision: can we ship the source `.delta` to the dest?
 ///
 /// Precedence is exact and load-bearing:
 ///   1. dest present AND sha differs → Fallback{ref_sha_mismatch}
-///      UNCONDITIONALLY (before any enc check). A wrong reference is
+///      UNCONDITIONALLY (before any key check). A wrong reference is
 ///      silent corruption; nothing overrides this.
-///   2. enc incompatible → Fallback{enc_incompatible}.
+///   2. either side's key unknown → Fallback{key_id_unknown}.
 ///   3. dest absent → SeedThenShip (we'll seed the matching reference).
-///   4. dest present + sha equal + enc compatible → ShipVerbatim.
+///   4. dest present + sha equal + same fingerprint → ShipVerbatim.
+///   5. dest present + sha equal + different fingerprint → ShipRewrapped.
 pub(crate) fn can_delta_passthrough(
     src: &SrcDeltaFacts,
     dest_ref: Option<&DestRefFacts>,
@@ -874,12 +1043,16 @@ pub(crate) fn can_delta_passthrough(
                 reason: "ref_sha_mismatch",
             };
         }
-        if !enc_compatible(&src.enc, &dest.enc) {
+        if !key_known(&src.enc) || !key_known(&dest.enc) {
             return DeltaPassthroughDecision::Fallback {
-                reason: "enc_incompatible",
+                reason: "key_id_unknown",
             };
         }
-        DeltaPassthroughDecision::ShipVerbatim
+        if src.enc == dest.enc {
+            DeltaPassthroughDecision::ShipVerbatim
+        } else {
+            DeltaPassthroughDecision::ShipRewrapped
+        }
     } else {
         DeltaPassthroughDecision::SeedThenShip
     }
@@ -899,6 +1072,71 @@ fn enc_fingerprint(meta: &crate::types::FileMetadata) -> EncFingerprint {
     }
 }
 
+/// Re-wrap a legacy source delta — encrypted before key ids were stamped —
+/// in place under its backend's current key, so it carries a key_id and
+/// qualifies for the fast path. The read decrypts with the key an unstamped
+/// blob resolves to and AEAD-authenticates it, so the stamp is proven, not
+/// guessed; the write re-encrypts and stamps (the re-encrypt job's rewrite,
+/// for one blob). This mutates the SOURCE as a side effect of a copy, so the
+/// caller only runs it when the source backend encrypts with a stamped key
+/// (`stamps_encryption_key_id`) — elsewhere the rewrite would
+/// store the delta as plaintext. `dg-created-at` is kept, so the served
+/// LastModified — and replication's newer-wins view of the object — does not
+/// move. Runs under the source prefix lock and only touches the blob it was
+/// called for. Returns the delta's at-rest metadata afterwards.
+async fn backfill_delta_key_id(
+    engine: &Arc<DynEngine>,
+    bucket: &str,
+    prefix: &str,
+    filename: &str,
+    ref_sha256: &str,
+) -> Result<crate::types::FileMetadata, Box<dyn std::error::Error + Send + Sync>> {
+    engine
+        .with_dest_prefix_lock(prefix, || async {
+            let mut meta = engine.delta_meta(bucket, prefix, filename).await?;
+            let unchanged = matches!(
+                &meta.storage_info,
+                crate::types::StorageInfo::Delta { ref_sha256: r, .. } if r == ref_sha256
+            );
+            if !unchanged || key_known(&enc_fingerprint(&meta)) {
+                // Rewritten or stamped since the HEAD — nothing to backfill.
+                return Ok(meta);
+            }
+            let data = engine.get_delta_raw(bucket, prefix, filename).await?;
+            strip_encryption_markers(&mut meta.user_metadata);
+            engine
+                .put_delta_raw(bucket, prefix, filename, &data, &meta)
+                .await?;
+            info!(
+                "rewrote legacy source delta {bucket}/{prefix}/{filename} in place \
+                 to stamp its encryption key id"
+            );
+            Ok(engine.delta_meta(bucket, prefix, filename).await?)
+        })
+        .await
+}
+
+/// Reference counterpart of [`backfill_delta_key_id`] for the DEST side, so
+/// a legacy dest reference doesn't block the ship. The caller must hold the
+/// dest prefix lock (the ship's critical section) and, as for the delta,
+/// only call it when the destination encrypts with a stamped key. The
+/// decrypting read also proves the destination can still open its own
+/// reference. Returns the reference's at-rest metadata afterwards.
+async fn backfill_reference_key_id(
+    engine: &Arc<DynEngine>,
+    bucket: &str,
+    prefix: &str,
+) -> Result<crate::types::FileMetadata, Box<dyn std::error::Error + Send + Sync>> {
+    let data = engine.get_reference_raw(bucket, prefix).await?;
+    let mut meta = engine.reference_metadata_raw(bucket, prefix).await?;
+    strip_encryption_markers(&mut meta.user_metadata);
+    engine
+        .put_reference_raw(bucket, prefix, &data, &meta)
+        .await?;
+    info!("backfilled key id on legacy reference {bucket}/{prefix}");
+    Ok(engine.reference_metadata_raw(bucket, prefix).await?)
+}
+
 /// Try the delta fast path. `Ok(None)` = fell back; the caller runs the
 /// existing reconstruct path. Enforces three corruption-defense layers
 /// (gate sha-check, seed sha-assert, post-lock re-gate).
@@ -950,14 +1188,35 @@ async fn delta_passthrough_copy(
 
     // ENCRYPTED sources are eligible: get_delta_raw / get_reference_raw DECRYPT
     // to plaintext, and put_delta_raw / put_reference_raw RE-ENCRYPT under the
-    // DEST key (+re-stamp the dest key-id) — so a same-key-id ship is byte-
-    // preserving (skips the whole-object xdelta3 reconstruct). The gate below
-    // Fallbacks on enc-incompatible / Encrypted{None}. We MUST strip the source
-    // encryption markers before the ship (see the `meta` strip below): a dest
-    // wrapper in decrypt-only PassThrough mode (or no key) does NOT re-stamp, so
-    // a surviving source marker on a now-plaintext body would make the read pick
+    // DEST key (+re-stamp the dest key-id) — so any ship between known keys
+    // re-wraps the blobs and skips the whole-object xdelta3 reconstruct. The
+    // gate below Fallbacks on Encrypted{None}, so a legacy source delta gets
+    // its key id backfilled first — only when the source backend re-encrypts
+    // with a stamped key, else the rewrite would store it as plaintext and the
+    // copy falls back instead. We MUST strip the source encryption markers
+    // before the ship (see the `meta` strip below): a dest wrapper in
+    // decrypt-only PassThrough mode (or no key) does NOT re-stamp, so a
+    // surviving source marker on a now-plaintext body would make the read pick
     // the wrong key and hard-fail.
-    let src_enc = enc_fingerprint(source_head);
+    let mut src_enc = enc_fingerprint(source_head);
+    if !key_known(&src_enc) && engine.stamps_encryption_key_id(request.source_bucket) {
+        match backfill_delta_key_id(
+            engine,
+            request.source_bucket,
+            &src_prefix,
+            &src_filename,
+            &src_ref_sha256,
+        )
+        .await
+        {
+            Ok(m) => src_enc = enc_fingerprint(&m),
+            // Best-effort: the gate falls back on the still-unknown key.
+            Err(e) => debug!(
+                "delta fast path: key id backfill for {}/{} failed: {e}",
+                request.source_bucket, request.source_key
+            ),
+        }
+    }
 
     let src = SrcDeltaFacts {
         ref_sha256: src_ref_sha256.clone(),
@@ -978,6 +1237,10 @@ async fn delta_passthrough_copy(
             provenance.metadata_value.to_string(),
         );
     }
+    for (key, value) in request.extra_user_metadata {
+        meta.user_metadata
+            .insert(key.to_string(), value.to_string());
+    }
     for key in request.strip_user_metadata_keys {
         meta.user_metadata.remove(*key);
     }
@@ -989,6 +1252,10 @@ async fn delta_passthrough_copy(
     // job (maintenance/worker.rs strip_encryption_markers).
     strip_encryption_markers(&mut meta.user_metadata);
 
+    // Charge the delta blob against the bandwidth caps BEFORE the prefix lock:
+    // a throttled copy must never sleep while holding it.
+    charge_bandwidth(&request, src_delta_size).await;
+
     // Decide AND ship under the dest prefix lock, so the gate's reference read
     // and the delta write are one critical section — mirrors the normal PUT
     // path (store.rs) and closes the gate→write TOCTOU. A concurrent reference
@@ -1016,18 +1283,33 @@ async fn delta_passthrough_copy(
     let shipped: Result<Option<u64>, Box<dyn std::error::Error + Send + Sync>> = engine
         .with_dest_prefix_lock(&dest_prefix, || async move {
             // Re-read the dest reference UNDER the lock and re-run the SAME pure
-            // gate — identical sha + enc precedence to the first read.
-            let dest_ref = engine2
-                .reference_meta(&dest_bucket, &dest_prefix2)
-                .await
-                .map(|m| DestRefFacts {
-                    file_sha256: m.file_sha256.clone(),
-                    enc: enc_fingerprint(&m),
+            // gate — identical sha + key precedence to the first read. A legacy
+            // (unstamped) reference matching our delta is backfilled first.
+            let mut dest_ref_meta = engine2.reference_meta(&dest_bucket, &dest_prefix2).await;
+            let legacy_ref = engine2.stamps_encryption_key_id(&dest_bucket)
+                && dest_ref_meta.as_ref().is_some_and(|m| {
+                    m.file_sha256 == src.ref_sha256 && !key_known(&enc_fingerprint(m))
                 });
+            if legacy_ref {
+                match backfill_reference_key_id(&engine2, &dest_bucket, &dest_prefix2).await {
+                    Ok(m) => dest_ref_meta = Some(m),
+                    Err(e) => debug!(
+                        "delta fast path: key id backfill for reference {dest_bucket}/{dest_prefix2} failed: {e}"
+                    ),
+                }
+            }
+            let dest_ref = dest_ref_meta.map(|m| DestRefFacts {
+                file_sha256: m.file_sha256.clone(),
+                enc: enc_fingerprint(&m),
+            });
             let mut seeded_ref_bytes = 0u64;
             match can_delta_passthrough(&src, dest_ref.as_ref()) {
                 DeltaPassthroughDecision::Fallback { .. } => return Ok(None),
                 DeltaPassthroughDecision::ShipVerbatim => {}
+                DeltaPassthroughDecision::ShipRewrapped => debug!(
+                    "delta fast path: re-wrapping {src_bucket}/{src_prefix2}/{src_filename2} \
+                     for {dest_bucket}/{dest_prefix2}"
+                ),
                 DeltaPassthroughDecision::SeedThenShip => {
                     // Seed the dest reference verbatim, asserting it matches
                     // src.ref_sha256 before writing (defense layer 2).
@@ -1188,7 +1470,9 @@ async fn spooled_copy(
     use tokio::io::AsyncWriteExt;
 
     // Stream the (reconstructed) source to a spool file — bounded memory.
-    let resp = engine
+    let resp = request
+        .source_engine
+        .unwrap_or(engine)
         .retrieve_stream(request.source_bucket, request.source_key)
         .await
         .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> {
@@ -1209,6 +1493,7 @@ async fn spooled_copy(
             let chunk = chunk.map_err(|e| -> Box<dyn std::error::Error + Send + Sync> {
                 format!("source stream error: {e}").into()
             })?;
+            charge_bandwidth(request, chunk.len() as u64).await;
             file.write_all(&chunk).await?;
         }
         file.flush().await?;
@@ -1222,6 +1507,9 @@ async fn spooled_copy(
             provenance.metadata_value.to_string(),
         );
     }
+    for (key, value) in request.extra_user_metadata {
+        user_metadata.insert(key.to_string(), value.to_string());
+    }
     for key in request.strip_user_metadata_keys {
         user_metadata.remove(*key);
     }
@@ -1333,6 +1621,21 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn bandwidth_limiter_bursts_then_runs_into_debt() {
+        let limiter = BandwidthLimiter::new(1_000);
+        let t0 = Instant::now();
+        // One second's worth is free; the next 500 bytes wait half a second.
+        assert_eq!(limiter.reserve(1_000, t0), Duration::ZERO);
+        assert_eq!(limiter.reserve(500, t0), Duration::from_millis(500));
+        // A second later the debt is paid and 500 bytes of credit accrued.
+        let t1 = t0 + Duration::from_secs(1);
+        assert_eq!(limiter.reserve(500, t1), Duration::ZERO);
+        // Rate 0 = uncapped.
+        limiter.set_rate(0);
+        assert_eq!(limiter.reserve(u64::MAX, t1), Duration::ZERO);
+    }
+
     #[test]
     fn copy_strategy_as_str_is_snake_case() {
         assert_eq!(CopyStrategy::DeltaPassthrough.as_str(), "delta_passthrough");
@@ -1405,12 +1708,18 @@ mod tests {
     }
 
     #[test]
-    fn row_plaintext_match_encrypted_fallback_enc_incompatible() {
+    fn row_plaintext_match_encrypted_rewraps() {
         assert_eq!(
             can_delta_passthrough(&plain_src("aaa"), Some(&enc_dest("aaa", Some("k")))),
-            DeltaPassthroughDecision::Fallback {
-                reason: "enc_incompatible"
-            }
+            DeltaPassthroughDecision::ShipRewrapped
+        );
+    }
+
+    #[test]
+    fn row_encrypted_k_match_plaintext_rewraps() {
+        assert_eq!(
+            can_delta_passthrough(&enc_src("aaa", Some("k")), Some(&plain_dest("aaa"))),
+            DeltaPassthroughDecision::ShipRewrapped
         );
     }
 
@@ -1436,24 +1745,40 @@ mod tests {
     }
 
     #[test]
-    fn row_encrypted_k_match_encrypted_j_fallback() {
+    fn row_encrypted_k_match_encrypted_j_rewraps() {
+        // Differently-keyed backends (e.g. on-prem → cloud DR): the delta is
+        // decrypted with k and re-encrypted with j, never rehydrated.
         assert_eq!(
             can_delta_passthrough(
                 &enc_src("aaa", Some("k")),
                 Some(&enc_dest("aaa", Some("j")))
             ),
+            DeltaPassthroughDecision::ShipRewrapped
+        );
+    }
+
+    #[test]
+    fn row_encrypted_none_both_fallback_key_id_unknown() {
+        assert_eq!(
+            can_delta_passthrough(&enc_src("aaa", None), Some(&enc_dest("aaa", None))),
             DeltaPassthroughDecision::Fallback {
-                reason: "enc_incompatible"
+                reason: "key_id_unknown"
             }
         );
     }
 
     #[test]
-    fn row_encrypted_none_both_fallback_enc_incompatible() {
+    fn row_encrypted_none_one_side_fallback_key_id_unknown() {
         assert_eq!(
-            can_delta_passthrough(&enc_src("aaa", None), Some(&enc_dest("aaa", None))),
+            can_delta_passthrough(&enc_src("aaa", None), Some(&enc_dest("aaa", Some("k")))),
+            DeltaPassthroughDecision::Fallback {
+                reason: "key_id_unknown"
+            }
+        );
+        assert_eq!(
+            can_delta_passthrough(&plain_src("aaa"), Some(&enc_dest("aaa", None))),
             DeltaPassthroughDecision::Fallback {
-                reason: "enc_incompatible"
+                reason: "key_id_unknown"
             }
         );
     }
@@ -1491,17 +1816,8 @@ mod gate_proptests {
         ]
     }
 
-    fn compatible(a: &EncFingerprint, b: &EncFingerprint) -> bool {
-        matches!(
-            (a, b),
-            (EncFingerprint::Plaintext, EncFingerprint::Plaintext)
-        ) || matches!(
-            (a, b),
-            (
-                EncFingerprint::Encrypted { key_id: Some(x) },
-                EncFingerprint::Encrypted { key_id: Some(y) },
-            ) if x == y
-        )
+    fn known(a: &EncFingerprint) -> bool {
+        !matches!(a, EncFingerprint::Encrypted { key_id: None })
     }
 
     proptest! {
@@ -1521,6 +1837,7 @@ mod gate_proptests {
             // prop_assert format-string parser.
             let is_fallback = matches!(decision, DeltaPassthroughDecision::Fallback { .. });
             let is_ship = matches!(decision, DeltaPassthroughDecision::ShipVerbatim);
+            let is_rewrap = matches!(decision, DeltaPassthroughDecision::ShipRewrapped);
             let is_seed = matches!(decision, DeltaPassthroughDecision::SeedThenShip);
 
             // Invariant 1: sha-differ ⇒ always Fallback.
@@ -1529,19 +1846,26 @@ mod gate_proptests {
                     prop_assert!(is_fallback);
                 }
             }
-            // Invariant 2: ShipVerbatim ⇒ dest present ∧ sha equal ∧ enc compatible.
+            // Invariant 2: ShipVerbatim ⇒ dest present ∧ sha equal ∧ same known key.
             if is_ship {
                 let d = dest_ref.as_ref().expect("ship requires a dest ref");
                 prop_assert_eq!(&d.file_sha256, &src.ref_sha256);
-                prop_assert!(compatible(&src.enc, &d.enc));
+                prop_assert!(known(&src.enc) && src.enc == d.enc);
+            }
+            // Invariant 2b: ShipRewrapped ⇒ dest present ∧ sha equal ∧ both
+            // keys known ∧ they differ.
+            if is_rewrap {
+                let d = dest_ref.as_ref().expect("rewrap requires a dest ref");
+                prop_assert_eq!(&d.file_sha256, &src.ref_sha256);
+                prop_assert!(known(&src.enc) && known(&d.enc) && src.enc != d.enc);
             }
             // Invariant 3: SeedThenShip ⇒ dest absent.
             if is_seed {
                 prop_assert!(dest_ref.is_none());
             }
-            // Invariant 4: never Ship/Seed when enc incompatible (present dest).
+            // Invariant 4: never ship when either key is unknown (present dest).
             if let Some(d) = dest_ref.as_ref() {
-                if !compatible(&src.enc, &d.enc) {
+                if !known(&src.enc) || !known(&d.enc) {
                     prop_assert!(is_fallback);
                 }
             }
@@ -2005,9 +2329,12 @@ mod multipart_abort_tests {
             destination_bucket: "dstb",
             destination_key: "dst.bin",
             provenance: None,
+            extra_user_metadata: &[],
             strip_user_metadata_keys: &[],
             operation: "test",
             upload_concurrency: Some(2),
+            bandwidth: None,
+            source_engine: None,
         }
     }
 
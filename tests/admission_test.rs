@@ -817,3 +817,94 @@ storage:
         "error must name both conflicting keys, got: {err}"
     );
 }
+
+#[tokio::test]
+async fn test_throttle_block_rejects_over_rate_and_shows_in_trace() {
+    let server = TestServer::builder().auth("THRK", "THRS").build().await;
+    let admin = admin_http_client(&server.endpoint()).await;
+
+    apply_admission_yaml(
+        &admin,
+        &server.endpoint(),
+        r#"
+admission:
+  blocks:
+    - name: per-key
+      match:
+        access_key: [THRK]
+      action:
+        type: throttle
+        per: access-key
+        requests_per_sec: 1
+        burst: 2
+"#,
+    )
+    .await;
+
+    // SDK retries would mask the SlowDown; disable them.
+    let config = aws_sdk_s3::Config::builder()
+        .behavior_version(aws_sdk_s3::config::BehaviorVersion::latest())
+        .region(aws_sdk_s3::config::Region::new("us-east-1"))
+        .endpoint_url(server.endpoint())
+        .credentials_provider(aws_sdk_s3::config::Credentials::new(
+            "THRK", "THRS", None, None, "test",
+        ))
+        .force_path_style(true)
+        .retry_config(aws_sdk_s3::config::retry::RetryConfig::disabled())
+        .build();
+    let client = aws_sdk_s3::Client::from_conf(config);
+
+    let mut throttled = 0;
+    for _ in 0..5 {
+        if let Err(e) = client
+            .list_objects_v2()
+            .bucket(server.bucket())
+            .send()
+            .await
+        {
+            let status = e.raw_response().map(|r| r.status().as_u16());
+            assert_eq!(status, Some(503), "only SlowDown expected, got {e:?}");
+            throttled += 1;
+        }
+    }
+    assert!(
+        throttled >= 2,
+        "burst of 2 at 1 req/s must reject most of 5 back-to-back requests, got {throttled}"
+    );
+    assert!(
+        prometheus_counter_has_labels(
+            &metrics_text(&server.endpoint()).await,
+            "deltaglider_admission_throttle_rejections_total",
+            &["block=\"per-key\""],
+        ),
+        "throttle rejections must be counted per block"
+    );
+
+    // Throttle blocks never decide; the trace lists them separately and
+    // only for the keys they match.
+    let trace = |key: &'static str| {
+        let admin = admin.clone();
+        let endpoint = server.endpoint();
+        async move {
+            admin
+                .post(format!("{endpoint}/_/api/admin/config/trace"))
+                .json(&json!({
+                    "method": "GET",
+                    "path": "/bucket/key.txt",
+                    "authenticated": true,
+                    "access_key": key
+                }))
+                .send()
+                .await
+                .unwrap()
+                .json::<serde_json::Value>()
+                .await
+                .unwrap()
+        }
+    };
+    let hit = trace("THRK").await;
+    assert_eq!(hit["admission"]["decision"], "continue");
+    assert_eq!(hit["throttles"], json!(["per-key"]));
+    let miss = trace("SOMEONE-ELSE").await;
+    assert_eq!(miss["throttles"], json!([]));
+}
@@ -274,6 +274,7 @@ async fn delta_passthrough_falls_back_on_different_dest_reference() {
 
 /// AES key for the encrypted verbatim-ship test (32 bytes, hex).
 const ENC_KEY: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+const OTHER_ENC_KEY: &str = "fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
 
 /// Phase 0a: an ENCRYPTED delta source replicated to a same-key-id destination
 /// must take the delta-passthrough FAST PATH (ship the .delta blob, no xdelta3
@@ -337,6 +338,89 @@ async fn delta_passthrough_encrypted_same_key_ships_verbatim_and_reads_back() {
     );
 }
 
+/// Source and destination on two backends with DIFFERENT proxy keys: the
+/// key-ids never match, so before re-wrap support every delta fell back to a
+/// full reconstruct + re-encode. The fast path now decrypts the delta with
+/// the source key and re-encrypts it with the destination key — the dest GET
+/// must still reconstruct byte-identical, and the run must count it as a
+/// passthrough.
+#[tokio::test]
+async fn delta_passthrough_encrypted_different_keys_rewraps_and_reads_back() {
+    let dir_a = tempfile::TempDir::new().unwrap();
+    let dir_b = tempfile::TempDir::new().unwrap();
+    let backends = format!(
+        concat!(
+            "backends:\n",
+            "  - name: ka\n",
+            "    type: filesystem\n",
+            "    path: \"{}\"\n",
+            "    encryption:\n",
+            "      mode: aes256-gcm-proxy\n",
+            "      key: \"{}\"\n",
+            "  - name: kb\n",
+            "    type: filesystem\n",
+            "    path: \"{}\"\n",
+            "    encryption:\n",
+            "      mode: aes256-gcm-proxy\n",
+            "      key: \"{}\"\n",
+            "default_backend: ka\n",
+        ),
+        dir_a.path().display(),
+        ENC_KEY,
+        dir_b.path().display(),
+        OTHER_ENC_KEY,
+    );
+    let server = TestServer::builder()
+        .auth("bootstrap_key", "bootstrap_secret")
+        .bucket_policy("dp-src", "backend: ka")
+        .bucket_policy("dp-dst", "backend: kb")
+        .extra_yaml_storage_section(&format!("{}{}", backends, RULE_YAML))
+        .build()
+        .await;
+
+    let s3 = server.s3_client().await;
+    for b in ["dp-src", "dp-dst"] {
+        s3.create_bucket().bucket(b).send().await.ok();
+    }
+
+    let v2 = seed_delta(&server, "dp-src", "rel/").await;
+
+    let r1 = run_now(&server).await;
+    assert_eq!(r1["status"].as_str(), Some("succeeded"), "run1: {}", r1);
+    assert_eq!(
+        r1["objects_processed"].as_i64(),
+        Some(2),
+        "copied v1+v2: {}",
+        r1
+    );
+
+    // A delta shipped still sealed under the source key would fail AEAD on
+    // the dest read — byte-identical here proves the re-wrap.
+    let dest_v2 = get_bytes(&server, "dp-dst", "rel/v2.tar").await;
+    assert_eq!(
+        dest_v2, v2,
+        "re-keyed dest v2 must reconstruct byte-identical"
+    );
+
+    let run1 = latest_run(&server).await;
+    let dp = run1["delta_passthrough"].as_i64().unwrap_or(0);
+    if xdelta3_available() {
+        assert!(
+            dp >= 1,
+            "differently-keyed delta must ship via the re-wrap fast path (delta_passthrough=0): {}",
+            run1
+        );
+    }
+
+    let r2 = run_now(&server).await;
+    assert_eq!(
+        r2["objects_processed"].as_i64(),
+        Some(0),
+        "2nd run no-op: {}",
+        r2
+    );
+}
+
 /// Regression (convergence): a destination delta object whose DG metadata was
 /// STRIPPED (the prod Hetzner signature — HEAD falls back to the raw delta size,
 /// GET would serve the delta blob as-is) is made healthy again by the next
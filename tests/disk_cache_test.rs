@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! End-to-end tests for the read-through disk cache (`disk_cache:`).
+//!
+//! The cache normally skips filesystem backends; these tests opt the
+//! singleton filesystem backend in via `backends: [default]` so the whole
+//! path (engine wiring, invalidation on PUT, range reads, prefetch,
+//! metrics) runs without a remote store.
+
+mod common;
+
+use common::{
+    admin_http_client, generate_binary, get_bytes, metrics_text, prometheus_counter_has_labels,
+    put_object, TestServer,
+};
+use serde_json::{json, Value};
+
+async fn cached_server(cache_dir: &std::path::Path) -> TestServer {
+    TestServer::builder()
+        .extra_yaml_root(&format!(
+            "disk_cache:\n  enabled: true\n  path: \"{}\"\n  max_size_mb: 64\n  backends: [default]\n",
+            cache_dir.display()
+        ))
+        .build()
+        .await
+}
+
+#[tokio::test]
+async fn repeat_reads_hit_and_writes_invalidate() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let server = cached_server(cache_dir.path()).await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+    let bucket = server.bucket();
+
+    let v1 = generate_binary(3 * 1024 * 1024 + 100, 1);
+    put_object(
+        &http,
+        &endpoint,
+        bucket,
+        "blob.bin",
+        v1.clone(),
+        "application/octet-stream",
+    )
+    .await;
+    assert_eq!(get_bytes(&http, &endpoint, bucket, "blob.bin").await, v1);
+    assert_eq!(get_bytes(&http, &endpoint, bucket, "blob.bin").await, v1);
+
+    let metrics = metrics_text(&endpoint).await;
+    assert!(
+        prometheus_counter_has_labels(
+            &metrics,
+            "deltaglider_disk_cache_hits_total",
+            &["kind=\"passthrough\""]
+        ),
+        "second GET should be a disk cache hit:\n{metrics}"
+    );
+
+    // Range read served (at least partly) from the cached blocks.
+    let resp = http
+        .get(format!("{}/{}/blob.bin", endpoint, bucket))
+        .header("range", "bytes=1048570-2097160")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 206);
+    assert_eq!(
+        resp.bytes().await.unwrap().to_vec(),
+        v1[1048570..=2097160].to_vec()
+    );
+
+    // Overwrite: the next GET must see the new bytes, not the cache.
+    let v2 = generate_binary(1024, 2);
+    put_object(
+        &http,
+        &endpoint,
+        bucket,
+        "blob.bin",
+        v2.clone(),
+        "application/octet-stream",
+    )
+    .await;
+    assert_eq!(get_bytes(&http, &endpoint, bucket, "blob.bin").await, v2);
+}
+
+#[tokio::test]
+async fn prefetch_warms_prefix_and_reports_stats() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let server = cached_server(cache_dir.path()).await;
+    let http = reqwest::Client::new();
+    let admin = admin_http_client(&server.endpoint()).await;
+    let endpoint = server.endpoint();
+    let bucket = server.bucket();
+
+    for (i, key) in ["rel/a.bin", "rel/b.bin", "other/c.bin"].iter().enumerate() {
+        put_object(
+            &http,
+            &endpoint,
+            bucket,
+            key,
+            generate_binary(200_000, i as u64),
+            "application/octet-stream",
+        )
+        .await;
+    }
+
+    let resp = admin
+        .post(format!("{}/_/api/admin/disk-cache/prefetch", endpoint))
+        .json(&json!({ "bucket": bucket, "prefix": "rel/" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let r: Value = resp.json().await.unwrap();
+    assert_eq!(r["requested"].as_u64(), Some(2), "{r}");
+    assert_eq!(r["prefetched"].as_u64(), Some(2), "{r}");
+    assert_eq!(r["truncated"].as_bool(), Some(false));
+    assert!(r["stats"]["used_bytes"].as_u64().unwrap() >= 400_000, "{r}");
+
+    let status: Value = admin
+        .get(format!("{}/_/api/admin/disk-cache", endpoint))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(status["enabled"].as_bool(), Some(true));
+    assert_eq!(status["stats"]["entries"].as_u64(), Some(2), "{status}");
+}
+
+#[tokio::test]
+async fn prefetch_conflicts_when_cache_disabled() {
+    let server = TestServer::filesystem().await;
+    let admin = admin_http_client(&server.endpoint()).await;
+    let resp = admin
+        .post(format!(
+            "{}/_/api/admin/disk-cache/prefetch",
+            server.endpoint()
+        ))
+        .json(&json!({ "bucket": server.bucket() }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 409);
+}
+
+/// Proxy-encrypted backend: the cache holds ciphertext (it sits below the
+/// encryption wrapper), and chunked-ciphertext range reads — a 16-byte
+/// header fetch plus an open-ended body fetch — still decrypt correctly
+/// from cached blocks.
+#[tokio::test]
+async fn encrypted_backend_caches_ciphertext() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let server = TestServer::builder()
+        .encryption_key("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
+        .extra_yaml_root(&format!(
+            "disk_cache:\n  enabled: true\n  path: \"{}\"\n  backends: [default]\n",
+            cache_dir.path().display()
+        ))
+        .build()
+        .await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+    let bucket = server.bucket();
+
+    let data = generate_binary(2 * 1024 * 1024 + 777, 9);
+    put_object(
+        &http,
+        &endpoint,
+        bucket,
+        "sealed.bin",
+        data.clone(),
+        "application/octet-stream",
+    )
+    .await;
+    for _ in 0..2 {
+        assert_eq!(
+            get_bytes(&http, &endpoint, bucket, "sealed.bin").await,
+            data
+        );
+        let resp = http
+            .get(format!("{}/{}/sealed.bin", endpoint, bucket))
+            .header("range", "bytes=1500000-1600000")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status().as_u16(), 206);
+        assert_eq!(
+            resp.bytes().await.unwrap().to_vec(),
+            data[1_500_000..=1_600_000].to_vec()
+        );
+    }
+
+    // No cached block may contain a plaintext window.
+    let needle = &data[4096..4096 + 64];
+    let mut stack = vec![cache_dir.path().to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path.extension().is_some_and(|e| e == "blk") {
+                let bytes = std::fs::read(&path).unwrap();
+                assert!(
+                    !bytes.windows(needle.len()).any(|w| w == needle),
+                    "plaintext found in {}",
+                    path.display()
+                );
+            }
+        }
+    }
+}
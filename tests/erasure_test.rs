@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Erasure coding: a `type: erasure` backend stores each object as 2 data
+//! shards + 1 parity shard on three filesystem backends. Losing any one
+//! shard keeps the object readable, and the repair endpoint rewrites it.
+//!
+//! No MinIO needed: every shard backend is a filesystem backend.
+
+mod common;
+
+use common::{
+    admin_http_client, generate_binary, get_bytes, metrics_text, prometheus_counter_has_labels,
+    put_object, TestServer,
+};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Every stored file named `name` under `dir` (layout-agnostic).
+fn find_files(dir: &Path, name: &str) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return found;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            found.extend(find_files(&path, name));
+        } else if path.file_name().is_some_and(|n| n == name) {
+            found.push(path);
+        }
+    }
+    found
+}
+
+#[tokio::test]
+async fn lost_shard_is_served_from_parity_and_repaired() {
+    let dirs: Vec<_> = (0..3)
+        .map(|_| tempfile::tempdir().expect("tempdir"))
+        .collect();
+    let server = TestServer::builder()
+        .extra_yaml_storage_section(&format!(
+            r#"
+default_backend: ec
+backends:
+  - name: d1
+    type: filesystem
+    path: {}
+  - name: d2
+    type: filesystem
+    path: {}
+  - name: p1
+    type: filesystem
+    path: {}
+  - name: ec
+    type: erasure
+    data_shards: 2
+    parity_shards: 1
+    shards: [d1, d2, p1]
+"#,
+            dirs[0].path().display(),
+            dirs[1].path().display(),
+            dirs[2].path().display()
+        ))
+        .build()
+        .await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+
+    let resp = http.put(format!("{endpoint}/vault")).send().await.unwrap();
+    assert!(resp.status().is_success(), "create: {}", resp.status());
+
+    let data = generate_binary(300_001, 7);
+    put_object(
+        &http,
+        &endpoint,
+        "vault",
+        "blob.bin",
+        data.clone(),
+        "application/octet-stream",
+    )
+    .await;
+
+    // One shard per backend, each about half the object.
+    for dir in &dirs {
+        let files = find_files(dir.path(), "blob.bin");
+        assert_eq!(files.len(), 1, "one shard in {}", dir.path().display());
+        let len = std::fs::metadata(&files[0]).unwrap().len();
+        assert!(len < data.len() as u64 * 3 / 4, "shard is {len} bytes");
+    }
+    assert_eq!(get_bytes(&http, &endpoint, "vault", "blob.bin").await, data);
+
+    // Lose a data shard: the read is rebuilt from the parity shard.
+    std::fs::remove_file(&find_files(dirs[0].path(), "blob.bin")[0]).unwrap();
+    assert_eq!(get_bytes(&http, &endpoint, "vault", "blob.bin").await, data);
+    let resp = http
+        .get(format!("{endpoint}/vault/blob.bin"))
+        .header("range", "bytes=150000-150099")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 206);
+    assert_eq!(
+        resp.bytes().await.unwrap().to_vec(),
+        data[150000..150100].to_vec()
+    );
+    let metrics = metrics_text(&endpoint).await;
+    assert!(
+        prometheus_counter_has_labels(
+            &metrics,
+            "deltaglider_erasure_degraded_reads_total",
+            &["backend=\"ec\""]
+        ),
+        "degraded read counted:\n{metrics}"
+    );
+
+    // Repair rewrites the lost shard.
+    let admin = admin_http_client(&endpoint).await;
+    let resp = admin
+        .post(format!("{endpoint}/_/api/admin/erasure/ec/repair"))
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success(), "repair: {}", resp.status());
+    let report: Value = resp.json().await.unwrap();
+    assert_eq!(report["shards_repaired"], 1, "{report}");
+    assert_eq!(report["unrecoverable"], 0, "{report}");
+    assert_eq!(find_files(dirs[0].path(), "blob.bin").len(), 1);
+
+    // The repaired shard is usable: lose another one and read again.
+    std::fs::remove_file(&find_files(dirs[2].path(), "blob.bin")[0]).unwrap();
+    assert_eq!(get_bytes(&http, &endpoint, "vault", "blob.bin").await, data);
+
+    // Two of three shards gone: beyond what one parity shard covers.
+    std::fs::remove_file(&find_files(dirs[1].path(), "blob.bin")[0]).unwrap();
+    let resp = http
+        .get(format!("{endpoint}/vault/blob.bin"))
+        .send()
+        .await
+        .unwrap();
+    assert!(!resp.status().is_success(), "unreadable: {}", resp.status());
+
+    let layouts: Value = admin
+        .get(format!("{endpoint}/_/api/admin/erasure"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(layouts[0]["backend"], "ec");
+    assert_eq!(layouts[0]["shards"].as_array().unwrap().len(), 3);
+}
@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Multipart uploads to a filesystem bucket are staged on disk under
+//! `<bucket>/.dg-multipart/<upload_id>/`: they survive a proxy restart, are
+//! listed by ListMultipartUploads, and leave nothing behind once completed
+//! or aborted.
+
+mod common;
+
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use common::{generate_binary, TestServer};
+
+async fn start(dir: &std::path::Path) -> TestServer {
+    TestServer::builder()
+        .extra_yaml_storage_section(&format!(
+            r#"
+default_backend: local
+backends:
+  - name: local
+    type: filesystem
+    path: {}
+"#,
+            dir.display()
+        ))
+        .build()
+        .await
+}
+
+#[tokio::test]
+async fn staged_upload_survives_restart_and_completes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let first = generate_binary(1024 * 1024, 7);
+    let second = generate_binary(300_000, 8);
+
+    let (upload_id, etag1) = {
+        let server = start(dir.path()).await;
+        let s3 = server.s3_client().await;
+        s3.create_bucket().bucket("media").send().await.unwrap();
+        let upload_id = s3
+            .create_multipart_upload()
+            .bucket("media")
+            .key("clips/take.mp4")
+            .content_type("video/mp4")
+            .send()
+            .await
+            .unwrap()
+            .upload_id
+            .unwrap();
+        let etag1 = s3
+            .upload_part()
+            .bucket("media")
+            .key("clips/take.mp4")
+            .upload_id(&upload_id)
+            .part_number(1)
+            .body(ByteStream::from(first.clone()))
+            .send()
+            .await
+            .unwrap()
+            .e_tag
+            .unwrap();
+        (upload_id, etag1)
+    };
+    let staging = dir.path().join("media/.dg-multipart").join(&upload_id);
+    assert!(staging.join("upload.json").is_file(), "upload not staged");
+
+    // A new process finds the upload again.
+    let server = start(dir.path()).await;
+    let s3 = server.s3_client().await;
+    let uploads = s3
+        .list_multipart_uploads()
+        .bucket("media")
+        .send()
+        .await
+        .unwrap();
+    let listed: Vec<_> = uploads
+        .uploads()
+        .iter()
+        .map(|u| (u.key().unwrap(), u.upload_id().unwrap()))
+        .collect();
+    assert_eq!(listed, vec![("clips/take.mp4", upload_id.as_str())]);
+    let parts = s3
+        .list_parts()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .upload_id(&upload_id)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(parts.parts().len(), 1);
+    assert_eq!(parts.parts()[0].e_tag(), Some(etag1.as_str()));
+    assert_eq!(parts.parts()[0].size(), Some(first.len() as i64));
+
+    let etag2 = s3
+        .upload_part()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .upload_id(&upload_id)
+        .part_number(2)
+        .body(ByteStream::from(second.clone()))
+        .send()
+        .await
+        .unwrap()
+        .e_tag
+        .unwrap();
+    let done = s3
+        .complete_multipart_upload()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .upload_id(&upload_id)
+        .multipart_upload(
+            CompletedMultipartUpload::builder()
+                .parts(CompletedPart::builder().part_number(1).e_tag(etag1).build())
+                .parts(CompletedPart::builder().part_number(2).e_tag(etag2).build())
+                .build(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        done.e_tag().unwrap().ends_with("-2\""),
+        "{:?}",
+        done.e_tag()
+    );
+    assert!(!staging.exists(), "staging left behind after complete");
+
+    let got = s3
+        .get_object()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(got.e_tag(), done.e_tag());
+    assert_eq!(got.content_type(), Some("video/mp4"));
+    let body = got.body.collect().await.unwrap().into_bytes();
+    assert_eq!(body.as_ref(), [first, second].concat().as_slice());
+}
+
+#[tokio::test]
+async fn aborted_staged_upload_is_removed() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let server = start(dir.path()).await;
+    let s3 = server.s3_client().await;
+    s3.create_bucket().bucket("media").send().await.unwrap();
+    let upload_id = s3
+        .create_multipart_upload()
+        .bucket("media")
+        .key("draft.bin")
+        .send()
+        .await
+        .unwrap()
+        .upload_id
+        .unwrap();
+    s3.upload_part()
+        .bucket("media")
+        .key("draft.bin")
+        .upload_id(&upload_id)
+        .part_number(1)
+        .body(ByteStream::from(generate_binary(10_000, 1)))
+        .send()
+        .await
+        .unwrap();
+
+    s3.abort_multipart_upload()
+        .bucket("media")
+        .key("draft.bin")
+        .upload_id(&upload_id)
+        .send()
+        .await
+        .unwrap();
+    assert!(!dir
+        .path()
+        .join("media/.dg-multipart")
+        .join(&upload_id)
+        .exists());
+    let uploads = s3
+        .list_multipart_uploads()
+        .bucket("media")
+        .send()
+        .await
+        .unwrap();
+    assert!(uploads.uploads().is_empty());
+    let err = s3
+        .list_parts()
+        .bucket("media")
+        .key("draft.bin")
+        .upload_id(&upload_id)
+        .send()
+        .await
+        .unwrap_err();
+    assert_eq!(err.into_service_error().meta().code(), Some("NoSuchUpload"));
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Filesystem backend with `metadata: sidecar`: object metadata lives in
+//! hidden `.dgmeta.*` files instead of xattrs, stays out of listings, and
+//! follows overwrites and deletes. Keys named like a sidecar are refused.
+
+mod common;
+
+use common::{
+    delete_object, generate_binary, get_bytes, head_headers, list_objects_raw, put_object,
+    read_xattr_metadata, TestServer,
+};
+
+#[tokio::test]
+async fn sidecar_mode_round_trips_metadata_without_xattrs() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let server = TestServer::builder()
+        .extra_yaml_storage_section(&format!(
+            r#"
+default_backend: local
+backends:
+  - name: local
+    type: filesystem
+    path: {}
+    metadata: sidecar
+"#,
+            dir.path().display()
+        ))
+        .build()
+        .await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+    let resp = http.put(format!("{endpoint}/docs")).send().await.unwrap();
+    assert!(resp.status().is_success(), "create: {}", resp.status());
+
+    let first = generate_binary(30_000, 1);
+    put_object(
+        &http,
+        &endpoint,
+        "docs",
+        "a/report.txt",
+        first,
+        "text/plain",
+    )
+    .await;
+    let second = generate_binary(40_000, 2);
+    put_object(
+        &http,
+        &endpoint,
+        "docs",
+        "a/report.txt",
+        second.clone(),
+        "application/x-report",
+    )
+    .await;
+
+    assert_eq!(
+        get_bytes(&http, &endpoint, "docs", "a/report.txt").await,
+        second
+    );
+    let headers = head_headers(&http, &endpoint, "docs", "a/report.txt").await;
+    assert_eq!(headers["content-type"], "application/x-report");
+    assert_eq!(headers["content-length"], "40000");
+
+    let prefix_dir = dir.path().join("docs/deltaspaces/a");
+    let sidecar = prefix_dir.join(".dgmeta.report.txt");
+    assert!(sidecar.exists(), "sidecar written next to the data file");
+    assert!(
+        read_xattr_metadata(dir.path()).is_empty(),
+        "no xattrs in sidecar mode"
+    );
+
+    let listing = list_objects_raw(&http, &endpoint, "docs", "prefix=a/").await;
+    assert!(listing.contains("<Key>a/report.txt</Key>"), "{listing}");
+    assert!(
+        !listing.contains("dgmeta"),
+        "sidecars stay hidden: {listing}"
+    );
+    assert!(listing.contains("<Size>40000</Size>"), "{listing}");
+
+    // A key named like a sidecar would overwrite the metadata of its
+    // neighbour: refused, and the neighbour still reads back.
+    let sidecar_before = std::fs::read(&sidecar).unwrap();
+    let resp = http
+        .put(format!("{endpoint}/docs/a/.dgmeta.report.txt"))
+        .body(b"{}".to_vec())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400, "reserved name: {}", resp.status());
+    assert_eq!(std::fs::read(&sidecar).unwrap(), sidecar_before);
+    assert_eq!(
+        get_bytes(&http, &endpoint, "docs", "a/report.txt").await,
+        second
+    );
+
+    delete_object(&http, &endpoint, "docs", "a/report.txt").await;
+    assert!(!sidecar.exists(), "delete removes the sidecar");
+    let resp = http
+        .delete(format!("{endpoint}/docs"))
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        resp.status().is_success(),
+        "bucket empties: {}",
+        resp.status()
+    );
+}
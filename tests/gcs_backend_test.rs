@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! GCS backend integration tests
+//!
+//! Route one bucket to a named `type: gcs` backend and drive it through the
+//! S3 API: passthrough + delta round-trip, delimiter listing, and a
+//! compose-based multipart upload. Gated on a fake-gcs-server endpoint
+//! (`docker compose up fake-gcs`, or `DGP_TEST_GCS_ENDPOINT`) — skip
+//! gracefully without one.
+
+mod common;
+
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use common::{generate_binary, mutate_binary, put_and_get_storage_type, TestServer};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static BUCKET_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn gcs_endpoint() -> String {
+    std::env::var("DGP_TEST_GCS_ENDPOINT").unwrap_or_else(|_| "http://127.0.0.1:4443".to_string())
+}
+
+fn gcs_available() -> bool {
+    let endpoint = gcs_endpoint();
+    let authority = endpoint
+        .trim_start_matches("http://")
+        .trim_start_matches("https://")
+        .split('/')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    std::net::TcpStream::connect(authority).is_ok()
+}
+
+macro_rules! skip_unless_gcs {
+    () => {
+        if !gcs_available() {
+            eprintln!("fake-gcs-server not available, skipping test");
+            return;
+        }
+    };
+}
+
+/// Unique bucket name per test (lowercase, 3-63 chars).
+fn unique_bucket() -> String {
+    let counter = BUCKET_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    format!("dgp-gcs-{millis}-{counter}")
+}
+
+/// Test server with a named GCS backend; `bucket` routes to it and is
+/// created through the proxy. No service-account key: fake-gcs-server
+/// takes unauthenticated requests.
+async fn gcs_server(bucket: &str) -> TestServer {
+    let backends = format!(
+        "backends:\n  - name: gcs\n    type: gcs\n    project: test\n    \
+         endpoint: \"{}\"\n    allow_local: true\n",
+        gcs_endpoint()
+    );
+    let server = TestServer::builder()
+        .bucket_policy(bucket, "backend: gcs")
+        .extra_yaml_root(&backends)
+        .build()
+        .await;
+    server
+        .s3_client()
+        .await
+        .create_bucket()
+        .bucket(bucket)
+        .send()
+        .await
+        .expect("create bucket on GCS backend");
+    server
+}
+
+#[tokio::test]
+async fn test_gcs_passthrough_and_delta_roundtrip() {
+    skip_unless_gcs!();
+    let bucket = unique_bucket();
+    let server = gcs_server(&bucket).await;
+    let client = server.s3_client().await;
+    let http = reqwest::Client::new();
+
+    // Passthrough: bytes and user metadata survive the round-trip through
+    // the object's custom metadata map.
+    client
+        .put_object()
+        .bucket(&bucket)
+        .key("docs/readme.txt")
+        .metadata("build_owner", "data-team")
+        .body(ByteStream::from_static(b"hello gcs!!"))
+        .send()
+        .await
+        .unwrap();
+    let head = client
+        .head_object()
+        .bucket(&bucket)
+        .key("docs/readme.txt")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(head.content_length(), Some(11));
+    assert_eq!(
+        head.metadata()
+            .and_then(|m| m.get("build_owner"))
+            .map(String::as_str),
+        Some("data-team")
+    );
+
+    // Delta: a near-identical second version is stored as a delta and
+    // reconstructs byte-exact from the GCS-stored reference.
+    let base = generate_binary(200_000, 7);
+    let v2 = mutate_binary(&base, 0.01);
+    put_and_get_storage_type(
+        &http,
+        &server.endpoint(),
+        &bucket,
+        "releases/base.zip",
+        base.clone(),
+        "application/zip",
+    )
+    .await;
+    let storage_type = put_and_get_storage_type(
+        &http,
+        &server.endpoint(),
+        &bucket,
+        "releases/v2.zip",
+        v2.clone(),
+        "application/zip",
+    )
+    .await;
+    assert_eq!(storage_type, "delta");
+
+    for (key, expected) in [("releases/base.zip", &base), ("releases/v2.zip", &v2)] {
+        let body = client
+            .get_object()
+            .bucket(&bucket)
+            .key(key)
+            .send()
+            .await
+            .unwrap()
+            .body
+            .collect()
+            .await
+            .unwrap()
+            .into_bytes();
+        assert_eq!(body.as_ref(), expected.as_slice(), "{key} mismatch");
+    }
+
+    client
+        .delete_object()
+        .bucket(&bucket)
+        .key("docs/readme.txt")
+        .send()
+        .await
+        .unwrap();
+    let gone = client
+        .head_object()
+        .bucket(&bucket)
+        .key("docs/readme.txt")
+        .send()
+        .await;
+    assert!(gone.is_err(), "deleted object must 404");
+}
+
+#[tokio::test]
+async fn test_gcs_delimiter_listing_hides_internal_keys() {
+    skip_unless_gcs!();
+    let bucket = unique_bucket();
+    let server = gcs_server(&bucket).await;
+    let client = server.s3_client().await;
+
+    let base = generate_binary(100_000, 11);
+    for (key, data) in [
+        ("top.txt", b"top".to_vec()),
+        ("dir/a.txt", b"a".to_vec()),
+        ("builds/v1.zip", base.clone()),
+        ("builds/v2.zip", mutate_binary(&base, 0.01)),
+    ] {
+        client
+            .put_object()
+            .bucket(&bucket)
+            .key(key)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let root = client
+        .list_objects_v2()
+        .bucket(&bucket)
+        .delimiter("/")
+        .send()
+        .await
+        .unwrap();
+    let keys: Vec<&str> = root.contents().iter().filter_map(|o| o.key()).collect();
+    let prefixes: Vec<&str> = root
+        .common_prefixes()
+        .iter()
+        .filter_map(|p| p.prefix())
+        .collect();
+    assert_eq!(keys, vec!["top.txt"]);
+    assert_eq!(prefixes, vec!["builds/", "dir/"]);
+
+    let builds = client
+        .list_objects_v2()
+        .bucket(&bucket)
+        .prefix("builds/")
+        .send()
+        .await
+        .unwrap();
+    let keys: Vec<&str> = builds.contents().iter().filter_map(|o| o.key()).collect();
+    assert_eq!(keys, vec!["builds/v1.zip", "builds/v2.zip"]);
+}
+
+#[tokio::test]
+async fn test_gcs_multipart_composes_parts() {
+    skip_unless_gcs!();
+    let bucket = unique_bucket();
+    let server = gcs_server(&bucket).await;
+    let client = server.s3_client().await;
+
+    let part1 = generate_binary(5 * 1024 * 1024, 21);
+    let part2 = generate_binary(1024 * 1024, 22);
+    let upload = client
+        .create_multipart_upload()
+        .bucket(&bucket)
+        .key("big/archive.bin")
+        .send()
+        .await
+        .unwrap();
+    let upload_id = upload.upload_id().unwrap();
+
+    let mut parts = Vec::new();
+    for (n, data) in [(1, &part1), (2, &part2)] {
+        let resp = client
+            .upload_part()
+            .bucket(&bucket)
+            .key("big/archive.bin")
+            .upload_id(upload_id)
+            .part_number(n)
+            .body(ByteStream::from(data.clone()))
+            .send()
+            .await
+            .unwrap();
+        parts.push(
+            CompletedPart::builder()
+                .part_number(n)
+                .e_tag(resp.e_tag().unwrap())
+                .build(),
+        );
+    }
+    let done = client
+        .complete_multipart_upload()
+        .bucket(&bucket)
+        .key("big/archive.bin")
+        .upload_id(upload_id)
+        .multipart_upload(
+            CompletedMultipartUpload::builder()
+                .set_parts(Some(parts))
+                .build(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        done.e_tag().unwrap().trim_matches('"').ends_with("-2"),
+        "multipart ETag keeps the S3 shape: {:?}",
+        done.e_tag()
+    );
+
+    let body = client
+        .get_object()
+        .bucket(&bucket)
+        .key("big/archive.bin")
+        .send()
+        .await
+        .unwrap()
+        .body
+        .collect()
+        .await
+        .unwrap()
+        .into_bytes();
+    let mut expected = part1;
+    expected.extend_from_slice(&part2);
+    assert_eq!(body.len(), expected.len());
+    assert!(
+        body.as_ref() == expected.as_slice(),
+        "assembled bytes mismatch"
+    );
+}
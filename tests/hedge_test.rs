@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Hedged reads: with `hedge: { after_ms: 0 }` every passthrough GET is
+//! raced against the bucket's second copy. The answer is always the object
+//! the engine resolved — a replica holding a different version never wins.
+//!
+//! No MinIO needed: every copy is a filesystem backend.
+
+mod common;
+
+use common::{
+    generate_binary, get_bytes, metrics_text, prometheus_counter_has_labels, put_object, TestServer,
+};
+
+#[tokio::test]
+async fn hedged_reads_serve_the_resolved_object_from_either_copy() {
+    let dir_a = tempfile::tempdir().expect("tempdir");
+    let dir_b = tempfile::tempdir().expect("tempdir");
+    let server = TestServer::builder()
+        .extra_yaml_storage_section(&format!(
+            r#"
+default_backend: a
+backends:
+  - name: a
+    type: filesystem
+    path: {}
+  - name: b
+    type: filesystem
+    path: {}
+buckets:
+  mirrored:
+    mirror:
+      backend: b
+    hedge:
+      after_ms: 0
+  builds:
+    read_fallback:
+      bucket: builds-dr
+    hedge:
+      after_ms: 0
+  builds-dr:
+    backend: b
+"#,
+            dir_a.path().display(),
+            dir_b.path().display()
+        ))
+        .build()
+        .await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+
+    for bucket in ["mirrored", "builds", "builds-dr"] {
+        let resp = http
+            .put(format!("{endpoint}/{bucket}"))
+            .send()
+            .await
+            .unwrap();
+        assert!(
+            resp.status().is_success(),
+            "create {bucket}: {}",
+            resp.status()
+        );
+    }
+
+    // Mirrored: both copies hold the object, either may answer.
+    let data = generate_binary(200_000, 3);
+    put_object(
+        &http,
+        &endpoint,
+        "mirrored",
+        "blob.bin",
+        data.clone(),
+        "application/octet-stream",
+    )
+    .await;
+    for _ in 0..5 {
+        assert_eq!(
+            get_bytes(&http, &endpoint, "mirrored", "blob.bin").await,
+            data
+        );
+    }
+    let resp = http
+        .get(format!("{endpoint}/mirrored/blob.bin"))
+        .header("range", "bytes=100-199")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 206);
+    assert_eq!(
+        resp.bytes().await.unwrap().to_vec(),
+        data[100..200].to_vec()
+    );
+
+    // Replica behind: it holds another version of the key, which must
+    // never be served for the primary's object.
+    let current = generate_binary(50_000, 11);
+    let stale = generate_binary(50_000, 12);
+    put_object(
+        &http,
+        &endpoint,
+        "builds",
+        "build.bin",
+        current.clone(),
+        "application/octet-stream",
+    )
+    .await;
+    put_object(
+        &http,
+        &endpoint,
+        "builds-dr",
+        "build.bin",
+        stale,
+        "application/octet-stream",
+    )
+    .await;
+    for _ in 0..5 {
+        assert_eq!(
+            get_bytes(&http, &endpoint, "builds", "build.bin").await,
+            current
+        );
+    }
+
+    let metrics = metrics_text(&endpoint).await;
+    assert!(
+        prometheus_counter_has_labels(
+            &metrics,
+            "deltaglider_hedged_reads_total",
+            &["winner=\"primary\""]
+        ),
+        "the stale replica lost its races:\n{metrics}"
+    );
+    assert!(
+        metrics.contains("deltaglider_backend_first_byte_seconds_count{backend=\"a\"}"),
+        "first-byte latency recorded:\n{metrics}"
+    );
+}
@@ -671,3 +671,135 @@ async fn test_reader_cannot_delete_bucket() {
         "Reader should not be able to delete buckets"
     );
 }
+
+// ============================================================================
+// Policy simulator
+// ============================================================================
+
+/// `POST /_/api/admin/iam/simulate` must agree with the live path and name
+/// the rule that decided: deny_user's PUT is allowed by the `*` rule, its
+/// DELETE is blocked by the `Deny delete` rule.
+#[tokio::test]
+async fn test_simulator_matches_live_decision() {
+    let h = IamTestHarness::setup().await;
+    let admin = admin_http_client(&h.server.endpoint()).await;
+    let client = h.client_for(&h.deny_user).await;
+
+    let simulate = |action: &'static str| {
+        let admin = admin.clone();
+        let url = format!("{}/_/api/admin/iam/simulate", h.server.endpoint());
+        async move {
+            let resp = admin
+                .post(url)
+                .json(&json!({
+                    "user": "deny_user",
+                    "action": action,
+                    "bucket": "bucket-a",
+                    "key": "sim/file.txt"
+                }))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(resp.status().as_u16(), 200);
+            resp.json::<serde_json::Value>().await.unwrap()
+        }
+    };
+
+    let put = simulate("write").await;
+    assert_eq!(put["allowed"], true);
+    assert_eq!(put["operation"], "s3:PutObject");
+    assert_eq!(put["deciding_rules"][0]["index"], 0);
+    assert_eq!(put["deciding_rules"][0]["source"], "user:deny_user");
+    assert!(put_succeeds(&client, "bucket-a", "sim/file.txt").await);
+
+    let delete = simulate("s3:DeleteObject").await;
+    assert_eq!(delete["allowed"], false);
+    assert_eq!(delete["basis"], "explicit_deny");
+    assert_eq!(delete["deciding_rules"][0]["index"], 1);
+    assert_eq!(delete["deciding_rules"][0]["rule"]["effect"], "Deny");
+    assert!(!delete_succeeds(&client, "bucket-a", "sim/file.txt").await);
+
+    // Unknown principals are a 404, not a silent implicit deny.
+    let resp = admin
+        .post(format!("{}/_/api/admin/iam/simulate", h.server.endpoint()))
+        .json(&json!({ "user": "nobody", "action": "read", "bucket": "bucket-a" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
+
+// ============================================================================
+// Service accounts
+// ============================================================================
+
+#[tokio::test]
+async fn test_service_account_narrowed_by_session_policy() {
+    let h = IamTestHarness::setup().await;
+    let admin = admin_http_client(&h.server.endpoint()).await;
+    let accounts_url = format!(
+        "{}/_/api/admin/users/{}/service-accounts",
+        h.server.endpoint(),
+        h.admin_user.id
+    );
+
+    // The parent can do everything; the key may only write under `ci/`.
+    let resp = admin
+        .post(&accounts_url)
+        .json(&json!({
+            "name": "ci",
+            "policy": [{ "effect": "Allow", "actions": ["write"], "resources": ["bucket-a/ci/*"] }]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+    let created: serde_json::Value = resp.json().await.unwrap();
+    let sa = UserCreds {
+        access_key_id: created["access_key_id"].as_str().unwrap().to_string(),
+        secret_access_key: created["secret_access_key"].as_str().unwrap().to_string(),
+        id: created["id"].as_i64().unwrap(),
+    };
+    let client = h.client_for(&sa).await;
+    assert!(put_succeeds(&client, "bucket-a", "ci/build.txt").await);
+    assert!(!put_succeeds(&client, "bucket-a", "other/build.txt").await);
+    assert!(!put_succeeds(&client, "bucket-b", "ci/build.txt").await);
+    assert!(!get_succeeds(&client, "bucket-a", "ci/build.txt").await);
+
+    // Listing masks the secret.
+    let listed: serde_json::Value = admin
+        .get(&accounts_url)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listed[0]["access_key_id"], sa.access_key_id.as_str());
+    assert!(listed[0].get("secret_access_key").is_none());
+
+    // An expiry in the past is rejected; disabling the key stops it at once.
+    let account_url = format!(
+        "{}/_/api/admin/service-accounts/{}",
+        h.server.endpoint(),
+        sa.id
+    );
+    let resp = admin
+        .put(&account_url)
+        .json(&json!({ "expires_at": 1 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+    let resp = admin
+        .put(&account_url)
+        .json(&json!({ "enabled": false }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert!(!put_succeeds(&client, "bucket-a", "ci/build.txt").await);
+
+    let resp = admin.delete(&account_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+}
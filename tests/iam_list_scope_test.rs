@@ -515,3 +515,50 @@ async fn test_condition_scoped_beshu_reports_common_prefix_navigation() {
         "private prefix should be filtered empty"
     );
 }
+
+/// `?uploads` takes the same fallback, so it must filter the same way: a
+/// prefix-scoped user sees only in-progress uploads under their prefix.
+/// `?location` stays reachable for SDK region probes.
+#[tokio::test]
+async fn test_multipart_upload_listing_filtered_to_user_scope() {
+    let h = ScopeHarness::setup().await;
+    let admin = h.admin_client().await;
+    let _ = admin.create_bucket().bucket("prod").send().await;
+    for key in &["alice/big.bin", "bob/big.bin"] {
+        admin
+            .create_multipart_upload()
+            .bucket("prod")
+            .key(*key)
+            .send()
+            .await
+            .expect("create upload");
+    }
+
+    let (alice_key, alice_secret) = h
+        .create_user(
+            "alice-uploads",
+            vec![json!({
+                "effect": "Allow",
+                "actions": ["read", "list"],
+                "resources": ["prod/alice/*"],
+            })],
+        )
+        .await;
+    let alice_s3 = h.user_client(&alice_key, &alice_secret).await;
+
+    let resp = alice_s3
+        .list_multipart_uploads()
+        .bucket("prod")
+        .send()
+        .await
+        .expect("list uploads should succeed — middleware fallback admits");
+    let keys: Vec<&str> = resp.uploads().iter().filter_map(|u| u.key()).collect();
+    assert_eq!(keys, ["alice/big.bin"], "leaked upload keys: {keys:?}");
+
+    alice_s3
+        .get_bucket_location()
+        .bucket("prod")
+        .send()
+        .await
+        .expect("GetBucketLocation keeps the visibility fallback");
+}
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Write-through mirrors: a bucket with a `mirror` policy lands every write
+//! on both backends, reads fail over to the surviving copy, and with
+//! `write_quorum: 1` a dead copy doesn't block writes.
+//!
+//! No MinIO needed: both copies live on filesystem backends; the dead copy
+//! is a connection-refused local port.
+
+mod common;
+
+use common::TestServer;
+use std::path::{Path, PathBuf};
+
+/// Every stored file named `name` under `dir` (layout-agnostic).
+fn find_files(dir: &Path, name: &str) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return found;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            found.extend(find_files(&path, name));
+        } else if path.file_name().is_some_and(|n| n == name) {
+            found.push(path);
+        }
+    }
+    found
+}
+
+async fn mirrored_server(a: &Path, b: &Path) -> TestServer {
+    TestServer::builder()
+        .extra_yaml_storage_section(&format!(
+            r#"
+backends:
+  - name: disk-a
+    type: filesystem
+    path: {}
+  - name: disk-b
+    type: filesystem
+    path: {}
+buckets:
+  critical:
+    backend: disk-a
+    mirror:
+      backend: disk-b
+"#,
+            a.display(),
+            b.display()
+        ))
+        .build()
+        .await
+}
+
+#[tokio::test]
+async fn writes_land_on_both_copies_and_reads_survive_losing_one() {
+    let dir_a = tempfile::tempdir().expect("tempdir");
+    let dir_b = tempfile::tempdir().expect("tempdir");
+    let server = mirrored_server(dir_a.path(), dir_b.path()).await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+
+    let resp = http
+        .put(format!("{endpoint}/critical"))
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success(), "create: {}", resp.status());
+    common::put_object(
+        &http,
+        &endpoint,
+        "critical",
+        "ledger/2026.txt",
+        b"balance=42".to_vec(),
+        "text/plain",
+    )
+    .await;
+
+    let on_a = find_files(dir_a.path(), "2026.txt");
+    let on_b = find_files(dir_b.path(), "2026.txt");
+    assert_eq!(on_a.len(), 1, "primary copy: {on_a:?}");
+    assert_eq!(on_b.len(), 1, "mirror copy: {on_b:?}");
+
+    // Lose the primary copy out-of-band: the read is answered by the mirror.
+    std::fs::remove_file(&on_a[0]).unwrap();
+    let body = common::get_bytes(&http, &endpoint, "critical", "ledger/2026.txt").await;
+    assert_eq!(body, b"balance=42");
+
+    // Deletes reach both copies.
+    common::delete_object(&http, &endpoint, "critical", "ledger/2026.txt").await;
+    assert!(find_files(dir_b.path(), "2026.txt").is_empty());
+    let resp = http
+        .get(format!("{endpoint}/critical/ledger/2026.txt"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn quorum_of_one_acknowledges_writes_while_the_mirror_is_down() {
+    let dir_a = tempfile::tempdir().expect("tempdir");
+    let server = TestServer::builder()
+        .extra_yaml_storage_section(&format!(
+            r#"
+backends:
+  - name: disk-a
+    type: filesystem
+    path: {}
+  - name: deadb2
+    type: s3
+    endpoint: "http://127.0.0.1:1"
+    region: us-east-1
+    access_key_id: x
+    secret_access_key: y
+    allow_local: true
+buckets:
+  strict:
+    backend: disk-a
+    mirror:
+      backend: deadb2
+  relaxed:
+    backend: disk-a
+    mirror:
+      backend: deadb2
+      write_quorum: 1
+"#,
+            dir_a.path().display()
+        ))
+        .build()
+        .await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+
+    let resp = http
+        .put(format!("{endpoint}/relaxed/notes.txt"))
+        .body("hello")
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        resp.status().is_success(),
+        "quorum 1 write: {}",
+        resp.status()
+    );
+    assert_eq!(find_files(dir_a.path(), "notes.txt").len(), 1);
+    let body = common::get_bytes(&http, &endpoint, "relaxed", "notes.txt").await;
+    assert_eq!(body, b"hello");
+
+    // The repair record was in the outbox before the write was
+    // acknowledged, not just in memory until the next repair tick.
+    let admin = common::admin_http_client(&endpoint).await;
+    let outbox: serde_json::Value = admin
+        .get(format!("{endpoint}/_/api/admin/event-outbox?limit=500"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(
+        outbox["rows"].as_array().unwrap().iter().any(|r| {
+            r["kind"] == "MirrorRepair" && r["bucket"] == "relaxed" && r["key"] == "notes.txt"
+        }),
+        "repair journaled with the write: {outbox}"
+    );
+
+    let resp = http
+        .put(format!("{endpoint}/strict/notes.txt"))
+        .body("hello")
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        !resp.status().is_success(),
+        "default quorum needs both copies"
+    );
+
+    let metrics = http
+        .get(format!("{endpoint}/_/metrics"))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(
+        metrics.lines().any(
+            |l| l.starts_with("deltaglider_mirror_degraded_writes_total") && !l.ends_with(" 0")
+        ),
+        "degraded writes counted"
+    );
+}
@@ -0,0 +1,254 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! With `DGP_MULTIPART_STORE=durable`, multipart uploads to a bucket whose
+//! backend can't stage them itself (here a mirror over two filesystem
+//! backends) are tracked in the durable registry, with parts staged on
+//! both copies under `.deltaglider/uploads/<upload_id>/`: they survive a proxy
+//! restart, stay out of listings, and completing or aborting leaves no staged
+//! part behind.
+
+mod common;
+
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use common::{generate_binary, TestServer};
+use std::path::{Path, PathBuf};
+
+async fn start(a: &Path, b: &Path) -> TestServer {
+    TestServer::builder()
+        .extra_yaml_storage_section(&format!(
+            r#"
+backends:
+  - name: disk-a
+    type: filesystem
+    path: {}
+  - name: disk-b
+    type: filesystem
+    path: {}
+buckets:
+  media:
+    backend: disk-a
+    mirror:
+      backend: disk-b
+"#,
+            a.display(),
+            b.display()
+        ))
+        .env("DGP_MULTIPART_STORE", "durable")
+        .build()
+        .await
+}
+
+/// Every file under `dir` (recursively); empty when `dir` doesn't exist.
+fn files_under(dir: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return found;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            found.extend(files_under(&path));
+        } else {
+            found.push(path);
+        }
+    }
+    found
+}
+
+/// Staged part files of `upload_id` on one copy (layout-agnostic).
+fn staged_files(root: &Path, upload_id: &str) -> Vec<PathBuf> {
+    files_under(root)
+        .into_iter()
+        .filter(|p| {
+            p.to_string_lossy()
+                .contains(&format!(".deltaglider/uploads/{upload_id}/"))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn registry_upload_survives_restart_and_completes() {
+    let dir_a = tempfile::tempdir().expect("tempdir");
+    let dir_b = tempfile::tempdir().expect("tempdir");
+    let first = generate_binary(5 * 1024 * 1024, 7);
+    let second = generate_binary(300_000, 8);
+
+    let mut server = start(dir_a.path(), dir_b.path()).await;
+    let s3 = server.s3_client().await;
+    s3.create_bucket().bucket("media").send().await.unwrap();
+    let upload_id = s3
+        .create_multipart_upload()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .content_type("video/mp4")
+        .send()
+        .await
+        .unwrap()
+        .upload_id
+        .unwrap();
+    let etag1 = s3
+        .upload_part()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .upload_id(&upload_id)
+        .part_number(1)
+        .body(ByteStream::from(first.clone()))
+        .send()
+        .await
+        .unwrap()
+        .e_tag
+        .unwrap();
+    assert!(!staged_files(dir_a.path(), &upload_id).is_empty());
+    assert!(!staged_files(dir_b.path(), &upload_id).is_empty());
+
+    // Staged parts are not objects: neither a flat nor a delimited listing
+    // shows them (or the `.deltaglider/` prefix holding them).
+    for delimiter in [None, Some("/")] {
+        let listed = s3
+            .list_objects_v2()
+            .bucket("media")
+            .set_delimiter(delimiter.map(str::to_string))
+            .send()
+            .await
+            .unwrap();
+        assert!(listed.contents().is_empty(), "{delimiter:?}: {listed:?}");
+        assert!(
+            listed.common_prefixes().is_empty(),
+            "{delimiter:?}: {listed:?}"
+        );
+    }
+
+    // A new process finds the upload again.
+    server.respawn_with_env(&[]).await;
+    let s3 = server.s3_client().await;
+    let uploads = s3
+        .list_multipart_uploads()
+        .bucket("media")
+        .send()
+        .await
+        .unwrap();
+    let listed: Vec<_> = uploads
+        .uploads()
+        .iter()
+        .map(|u| (u.key().unwrap(), u.upload_id().unwrap()))
+        .collect();
+    assert_eq!(listed, vec![("clips/take.mp4", upload_id.as_str())]);
+    let parts = s3
+        .list_parts()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .upload_id(&upload_id)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(parts.parts().len(), 1);
+    assert_eq!(parts.parts()[0].e_tag(), Some(etag1.as_str()));
+    assert_eq!(parts.parts()[0].size(), Some(first.len() as i64));
+
+    let etag2 = s3
+        .upload_part()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .upload_id(&upload_id)
+        .part_number(2)
+        .body(ByteStream::from(second.clone()))
+        .send()
+        .await
+        .unwrap()
+        .e_tag
+        .unwrap();
+    let done = s3
+        .complete_multipart_upload()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .upload_id(&upload_id)
+        .multipart_upload(
+            CompletedMultipartUpload::builder()
+                .parts(CompletedPart::builder().part_number(1).e_tag(etag1).build())
+                .parts(CompletedPart::builder().part_number(2).e_tag(etag2).build())
+                .build(),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        done.e_tag().unwrap().ends_with("-2\""),
+        "{:?}",
+        done.e_tag()
+    );
+    assert!(staged_files(dir_a.path(), &upload_id).is_empty());
+    assert!(staged_files(dir_b.path(), &upload_id).is_empty());
+    let uploads = s3
+        .list_multipart_uploads()
+        .bucket("media")
+        .send()
+        .await
+        .unwrap();
+    assert!(uploads.uploads().is_empty());
+
+    let got = s3
+        .get_object()
+        .bucket("media")
+        .key("clips/take.mp4")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(got.e_tag(), done.e_tag());
+    assert_eq!(got.content_type(), Some("video/mp4"));
+    let body = got.body.collect().await.unwrap().into_bytes();
+    assert_eq!(body.as_ref(), [first, second].concat().as_slice());
+}
+
+#[tokio::test]
+async fn aborted_registry_upload_is_removed() {
+    let dir_a = tempfile::tempdir().expect("tempdir");
+    let dir_b = tempfile::tempdir().expect("tempdir");
+    let server = start(dir_a.path(), dir_b.path()).await;
+    let s3 = server.s3_client().await;
+    s3.create_bucket().bucket("media").send().await.unwrap();
+    let upload_id = s3
+        .create_multipart_upload()
+        .bucket("media")
+        .key("draft.bin")
+        .send()
+        .await
+        .unwrap()
+        .upload_id
+        .unwrap();
+    s3.upload_part()
+        .bucket("media")
+        .key("draft.bin")
+        .upload_id(&upload_id)
+        .part_number(1)
+        .body(ByteStream::from(generate_binary(10_000, 1)))
+        .send()
+        .await
+        .unwrap();
+
+    s3.abort_multipart_upload()
+        .bucket("media")
+        .key("draft.bin")
+        .upload_id(&upload_id)
+        .send()
+        .await
+        .unwrap();
+    assert!(staged_files(dir_a.path(), &upload_id).is_empty());
+    assert!(staged_files(dir_b.path(), &upload_id).is_empty());
+    let uploads = s3
+        .list_multipart_uploads()
+        .bucket("media")
+        .send()
+        .await
+        .unwrap();
+    assert!(uploads.uploads().is_empty());
+    let err = s3
+        .list_parts()
+        .bucket("media")
+        .key("draft.bin")
+        .upload_id(&upload_id)
+        .send()
+        .await
+        .unwrap_err();
+    assert_eq!(err.into_service_error().meta().code(), Some("NoSuchUpload"));
+}
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Read fallback: a bucket whose backend is down is served from the replica
+//! named in its `read_fallback` policy, with the stale-read header set;
+//! writes are blocked unless the policy queues them onto the replica.
+//!
+//! No MinIO needed: the dead backend is a connection-refused local port, the
+//! replicas live on a healthy filesystem backend.
+
+mod common;
+
+use common::TestServer;
+
+async fn fallback_server(good_dir: &std::path::Path) -> TestServer {
+    TestServer::builder()
+        .extra_yaml_storage_section(&format!(
+            r#"
+backends:
+  - name: deadb2
+    type: s3
+    endpoint: "http://127.0.0.1:1"
+    region: us-east-1
+    access_key_id: x
+    secret_access_key: y
+    allow_local: true
+  - name: gooddisk
+    type: filesystem
+    path: {}
+buckets:
+  releases:
+    backend: deadb2
+    read_fallback:
+      bucket: releases-dr
+  uploads:
+    backend: deadb2
+    read_fallback:
+      bucket: uploads-dr
+      writes: queue
+  releases-dr:
+    backend: gooddisk
+  uploads-dr:
+    backend: gooddisk
+"#,
+            good_dir.display()
+        ))
+        // Same opt-in as backend_health_test: the gate needs a verdict.
+        .env("DGP_BOOT_BACKEND_PROBE", "enforce")
+        .build()
+        .await
+}
+
+#[tokio::test]
+async fn reads_are_served_from_replica_while_primary_is_down() {
+    let good_dir = tempfile::tempdir().expect("tempdir");
+    let server = fallback_server(good_dir.path()).await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+
+    let resp = http
+        .put(format!("{endpoint}/releases-dr"))
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        resp.status().is_success(),
+        "create replica: {}",
+        resp.status()
+    );
+    common::put_object(
+        &http,
+        &endpoint,
+        "releases-dr",
+        "v1/app.bin",
+        b"replica bytes".to_vec(),
+        "application/octet-stream",
+    )
+    .await;
+
+    let resp = http
+        .get(format!("{endpoint}/releases/v1/app.bin"))
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success(), "GET: {}", resp.status());
+    assert_eq!(
+        resp.headers()
+            .get("x-deltaglider-stale-read")
+            .and_then(|v| v.to_str().ok()),
+        Some("releases-dr"),
+        "read served from the replica must say so"
+    );
+    assert_eq!(resp.bytes().await.unwrap().as_ref(), b"replica bytes");
+
+    let head = common::head_headers(&http, &endpoint, "releases", "v1/app.bin").await;
+    assert!(head.contains_key("x-deltaglider-stale-read"));
+
+    let listing = common::list_objects_raw(&http, &endpoint, "releases", "").await;
+    assert!(
+        listing.contains("v1/app.bin"),
+        "LIST via replica: {listing}"
+    );
+
+    // Default `writes: block`: the gate's 503 still applies to writes.
+    let resp = http
+        .put(format!("{endpoint}/releases/v2/app.bin"))
+        .body("new")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 503, "writes stay blocked");
+}
+
+#[tokio::test]
+async fn queued_writes_land_on_replica() {
+    let good_dir = tempfile::tempdir().expect("tempdir");
+    let server = fallback_server(good_dir.path()).await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+
+    let resp = http
+        .put(format!("{endpoint}/uploads-dr"))
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        resp.status().is_success(),
+        "create replica: {}",
+        resp.status()
+    );
+
+    let resp = common::put_object(
+        &http,
+        &endpoint,
+        "uploads",
+        "incoming/report.csv",
+        b"a,b\n1,2\n".to_vec(),
+        "text/csv",
+    )
+    .await;
+    assert_eq!(
+        resp.headers()
+            .get("x-deltaglider-queued-write")
+            .and_then(|v| v.to_str().ok()),
+        Some("uploads-dr"),
+        "write accepted through the replica must say so"
+    );
+
+    let body = common::get_bytes(&http, &endpoint, "uploads-dr", "incoming/report.csv").await;
+    assert_eq!(body, b"a,b\n1,2\n");
+    let body = common::get_bytes(&http, &endpoint, "uploads", "incoming/report.csv").await;
+    assert_eq!(body, b"a,b\n1,2\n", "read-your-write through the fallback");
+
+    // Multipart and other non-plain writes are never queued.
+    let resp = http
+        .post(format!("{endpoint}/uploads/big.bin?uploads"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 503);
+}
@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! End-to-end tests for bidirectional replication rules.
+//!
+//! The sweep test's rule is `enabled: false` so only the sweeps fired there
+//! touch it — the event consumer and scheduler skip disabled rules, while
+//! run-now still runs them once. That keeps each step's outcome deterministic.
+
+mod common;
+
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use common::{admin_http_client, latest_run_id, wait_for_run_after, TestServer};
+use serde_json::Value;
+
+const BIDI_RULE_YAML: &str = "
+replication:
+  enabled: true
+  tick_interval: \"30s\"
+  rules:
+    - name: offices
+      enabled: false
+      source:
+        bucket: office-a
+        prefix: \"\"
+      destination:
+        bucket: office-b
+        prefix: \"\"
+      interval: \"1h\"
+      batch_size: 100
+      direction: bidirectional
+      replicate_deletes: true
+";
+
+/// Fire run-now (tolerating the brief 409 while a prior run releases its
+/// lease) and return the settled new run row.
+async fn sweep(admin: &reqwest::Client, endpoint: &str) -> Value {
+    let before = latest_run_id(admin, endpoint, "offices").await;
+    let url = format!("{endpoint}/_/api/admin/jobs/replication:offices/run-now");
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    loop {
+        let code = admin.post(&url).send().await.unwrap().status().as_u16();
+        if code == 202 {
+            break;
+        }
+        assert_eq!(code, 409, "run-now: unexpected status {code}");
+        assert!(std::time::Instant::now() < deadline, "run-now kept 409ing");
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    let run = wait_for_run_after(admin, endpoint, "offices", before).await;
+    assert_eq!(run["status"].as_str(), Some("succeeded"), "run: {run}");
+    run
+}
+
+async fn put(client: &Client, bucket: &str, key: &str, body: &[u8]) {
+    client
+        .put_object()
+        .bucket(bucket)
+        .key(key)
+        .body(ByteStream::from(body.to_vec()))
+        .send()
+        .await
+        .unwrap();
+}
+
+async fn get(client: &Client, bucket: &str, key: &str) -> Option<Vec<u8>> {
+    let resp = client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .ok()?;
+    Some(resp.body.collect().await.unwrap().into_bytes().to_vec())
+}
+
+async fn conflicts(admin: &reqwest::Client, endpoint: &str) -> Value {
+    admin
+        .get(format!(
+            "{endpoint}/_/api/admin/jobs/replication:offices/conflicts"
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_bidirectional_sync_deletes_and_conflict_resolution() {
+    let server = TestServer::builder()
+        .auth("bootstrap_key", "bootstrap_secret")
+        .extra_yaml_storage_section(BIDI_RULE_YAML)
+        .build()
+        .await;
+    let client = server.s3_client().await;
+    let endpoint = server.endpoint();
+    for b in ["office-a", "office-b"] {
+        client.create_bucket().bucket(b).send().await.ok();
+    }
+    let admin = admin_http_client(&endpoint).await;
+
+    // Writes on either side reach the other.
+    put(&client, "office-a", "a.txt", b"from a").await;
+    put(&client, "office-b", "b.txt", b"from b").await;
+    let run = sweep(&admin, &endpoint).await;
+    assert_eq!(run["objects_processed"].as_i64(), Some(2), "run: {run}");
+    assert_eq!(
+        get(&client, "office-b", "a.txt").await.as_deref(),
+        Some(&b"from a"[..])
+    );
+    assert_eq!(
+        get(&client, "office-a", "b.txt").await.as_deref(),
+        Some(&b"from b"[..])
+    );
+
+    // A converged pair is left alone — replicas are never copied back.
+    let run = sweep(&admin, &endpoint).await;
+    assert_eq!(run["objects_processed"].as_i64(), Some(0), "run: {run}");
+
+    // One-sided edit flows back; one-sided delete propagates; independent
+    // writes of the same new key conflict.
+    put(&client, "office-b", "a.txt", b"edited at b").await;
+    client
+        .delete_object()
+        .bucket("office-a")
+        .key("b.txt")
+        .send()
+        .await
+        .unwrap();
+    put(&client, "office-a", "c.txt", b"c at a").await;
+    put(&client, "office-b", "c.txt", b"c at b, longer").await;
+    sweep(&admin, &endpoint).await;
+    assert_eq!(
+        get(&client, "office-a", "a.txt").await.as_deref(),
+        Some(&b"edited at b"[..])
+    );
+    assert!(get(&client, "office-b", "b.txt").await.is_none());
+    assert_eq!(
+        get(&client, "office-a", "c.txt").await.as_deref(),
+        Some(&b"c at a"[..])
+    );
+    assert_eq!(
+        get(&client, "office-b", "c.txt").await.as_deref(),
+        Some(&b"c at b, longer"[..])
+    );
+
+    let held = conflicts(&admin, &endpoint).await;
+    assert_eq!(held["total"].as_i64(), Some(1), "conflicts: {held}");
+    assert_eq!(held["conflicts"][0]["key"].as_str(), Some("c.txt"));
+    assert_eq!(held["conflicts"][0]["kind"].as_str(), Some("both-created"));
+
+    // The sweep leaves the conflicted key alone.
+    sweep(&admin, &endpoint).await;
+    assert_eq!(
+        get(&client, "office-a", "c.txt").await.as_deref(),
+        Some(&b"c at a"[..])
+    );
+
+    // Keeping the destination copies it over the source and clears the row.
+    let resp = admin
+        .post(format!(
+            "{endpoint}/_/api/admin/jobs/replication:offices/conflicts/resolve"
+        ))
+        .json(&serde_json::json!({"key": "c.txt", "keep": "destination"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["resolution"].as_str(), Some("copied"));
+    assert_eq!(
+        get(&client, "office-a", "c.txt").await.as_deref(),
+        Some(&b"c at b, longer"[..])
+    );
+    assert_eq!(
+        conflicts(&admin, &endpoint).await["total"].as_i64(),
+        Some(0)
+    );
+
+    // Everything converged.
+    let run = sweep(&admin, &endpoint).await;
+    assert_eq!(run["objects_processed"].as_i64(), Some(0), "run: {run}");
+    assert!(get(&client, "office-a", "b.txt").await.is_none());
+}
+
+const BIDI_EVENT_RULE_YAML: &str = "
+replication:
+  enabled: true
+  tick_interval: \"5s\"
+  rules:
+    - name: ev-offices
+      enabled: true
+      source:
+        bucket: ev-office-a
+        prefix: \"\"
+      destination:
+        bucket: ev-office-b
+        prefix: \"\"
+      interval: \"24h\"
+      batch_size: 100
+      direction: bidirectional
+      replicate_deletes: true
+";
+
+/// The event consumer syncs a bidirectional rule from EITHER side's writes,
+/// and never copies a replica back onto its origin.
+#[tokio::test]
+async fn test_bidirectional_event_driven_both_ways() {
+    let server = TestServer::builder()
+        .auth("bootstrap_key", "bootstrap_secret")
+        .extra_yaml_storage_section(BIDI_EVENT_RULE_YAML)
+        .build()
+        .await;
+    let client = server.s3_client().await;
+    let endpoint = server.endpoint();
+    for b in ["ev-office-a", "ev-office-b"] {
+        client.create_bucket().bucket(b).send().await.ok();
+    }
+    let http = reqwest::Client::new();
+
+    let before = common::get_replication_event_version(&http, &endpoint).await;
+    put(&client, "ev-office-b", "from-b.txt", b"written at b").await;
+    common::wait_for_replication_event(&http, &endpoint, before).await;
+    assert_eq!(
+        get(&client, "ev-office-a", "from-b.txt").await.as_deref(),
+        Some(&b"written at b"[..])
+    );
+
+    let before = common::get_replication_event_version(&http, &endpoint).await;
+    put(&client, "ev-office-a", "from-a.txt", b"written at a").await;
+    common::wait_for_replication_event(&http, &endpoint, before).await;
+    assert_eq!(
+        get(&client, "ev-office-b", "from-a.txt").await.as_deref(),
+        Some(&b"written at a"[..])
+    );
+
+    // The origin copies are still the client's writes: no replica came back.
+    for (bucket, key) in [("ev-office-a", "from-a.txt"), ("ev-office-b", "from-b.txt")] {
+        let head = client
+            .head_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .unwrap();
+        let meta = head.metadata().cloned().unwrap_or_default();
+        assert!(
+            !meta.contains_key("dg-replication-rule"),
+            "{bucket}/{key} was overwritten by a replica: {meta:?}"
+        );
+    }
+
+    // A delete on either side propagates.
+    let before = common::get_replication_event_version(&http, &endpoint).await;
+    client
+        .delete_object()
+        .bucket("ev-office-b")
+        .key("from-a.txt")
+        .send()
+        .await
+        .unwrap();
+    common::wait_for_replication_event(&http, &endpoint, before).await;
+    assert!(get(&client, "ev-office-a", "from-a.txt").await.is_none());
+}
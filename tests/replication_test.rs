@@ -2806,3 +2806,132 @@ async fn test_replication_kill_mid_walk_resumes_across_dirs() {
         .len();
     assert_eq!(total, 60, "full convergence after kill + resume");
 }
+
+/// Push to, then pull back from, an external S3 endpoint (MinIO, reached
+/// only by URL and its own credentials — not a proxy backend). The hydrated
+/// mirror must hold plain, byte-identical objects at their keys.
+#[tokio::test]
+async fn test_replication_external_endpoint_push_and_pull() {
+    if !common::minio_available().await {
+        eprintln!("Skipping external endpoint test: MinIO not available");
+        return;
+    }
+    let mirror = format!("ext-{}/", uuid::Uuid::new_v4());
+    let external = format!(
+        "{{endpoint: \"{}\", region: us-east-1, force_path_style: true, \
+         access_key_id: {}, secret_access_key: {}, allow_local: true}}",
+        common::minio_endpoint_url(),
+        common::MINIO_ACCESS_KEY,
+        common::MINIO_SECRET_KEY,
+    );
+    let yaml = format!(
+        "
+replication:
+  enabled: true
+  tick_interval: \"30s\"
+  rules:
+    - name: push-ext
+      source: {{bucket: ext-src}}
+      destination: {{bucket: {bucket}, prefix: \"{mirror}\", external: {external}}}
+      interval: \"1h\"
+    - name: pull-ext
+      source: {{bucket: {bucket}, prefix: \"{mirror}\", external: {external}}}
+      destination: {{bucket: ext-pulled}}
+      interval: \"1h\"
+",
+        bucket = common::MINIO_BUCKET,
+    );
+    let server = TestServer::builder()
+        .auth("bootstrap_key", "bootstrap_secret")
+        .extra_yaml_storage_section(&yaml)
+        .build()
+        .await;
+    let client = server.s3_client().await;
+    for b in ["ext-src", "ext-pulled"] {
+        client.create_bucket().bucket(b).send().await.ok();
+    }
+    // Two similar archives: the second stores as a delta on the proxy side.
+    let v1 = common::generate_binary(256 * 1024, 7);
+    let v2 = common::mutate_binary(&v1, 0.01);
+    for (key, body) in [("rel/app-1.zip", &v1), ("rel/app-2.zip", &v2)] {
+        client
+            .put_object()
+            .bucket("ext-src")
+            .key(key)
+            .body(ByteStream::from(body.clone()))
+            .send()
+            .await
+            .expect("seed");
+    }
+
+    let admin = admin_http_client(&server.endpoint()).await;
+    let ep = server.endpoint();
+    let run = fire_run_now(&admin, &ep, "push-ext").await;
+    assert_eq!(run["status"].as_str(), Some("succeeded"), "{run}");
+    assert_eq!(run["objects_processed"].as_i64(), Some(2), "{run}");
+
+    // Read the mirror directly, bypassing the proxy: plain objects only.
+    let minio = common::minio_client().await;
+    let listed = minio
+        .list_objects_v2()
+        .bucket(common::MINIO_BUCKET)
+        .prefix(&mirror)
+        .send()
+        .await
+        .unwrap();
+    let mut keys: Vec<String> = listed
+        .contents()
+        .iter()
+        .filter_map(|o| o.key().map(str::to_string))
+        .collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        vec![
+            format!("{mirror}rel/app-1.zip"),
+            format!("{mirror}rel/app-2.zip")
+        ],
+        "no delta artifacts on the mirror"
+    );
+    for (key, body) in [("rel/app-1.zip", &v1), ("rel/app-2.zip", &v2)] {
+        let got = minio
+            .get_object()
+            .bucket(common::MINIO_BUCKET)
+            .key(format!("{mirror}{key}"))
+            .send()
+            .await
+            .unwrap()
+            .body
+            .collect()
+            .await
+            .unwrap()
+            .into_bytes();
+        assert_eq!(&got[..], &body[..], "{key} is byte-identical on the mirror");
+    }
+
+    // Pull it back into a proxy bucket.
+    let run = fire_run_now(&admin, &ep, "pull-ext").await;
+    assert_eq!(run["status"].as_str(), Some("succeeded"), "{run}");
+    assert_eq!(run["objects_processed"].as_i64(), Some(2), "{run}");
+    let got = client
+        .get_object()
+        .bucket("ext-pulled")
+        .key("rel/app-2.zip")
+        .send()
+        .await
+        .unwrap()
+        .body
+        .collect()
+        .await
+        .unwrap()
+        .into_bytes();
+    assert_eq!(&got[..], &v2[..]);
+
+    // Parity verify is refused for external rules.
+    let verify = admin
+        .post(format!("{ep}/_/api/admin/jobs/replication:pull-ext/verify"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(verify.status().as_u16(), 400);
+}
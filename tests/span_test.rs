@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Capacity spanning: a bucket with a `span` policy fills its first backend
+//! up to `capacity_bytes`, then places new deltaspaces on the next one.
+//! Existing deltaspaces stay where they are, and LIST merges every member.
+//!
+//! No MinIO needed: both members are filesystem backends.
+
+mod common;
+
+use common::TestServer;
+use std::path::{Path, PathBuf};
+
+/// Every stored file named `name` under `dir` (layout-agnostic).
+fn find_files(dir: &Path, name: &str) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return found;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            found.extend(find_files(&path, name));
+        } else if path.file_name().is_some_and(|n| n == name) {
+            found.push(path);
+        }
+    }
+    found
+}
+
+#[tokio::test]
+async fn new_deltaspaces_spill_to_the_next_member_once_the_first_is_full() {
+    // Re-measure member usage on every placement.
+    std::env::set_var("DGP_SPAN_USAGE_TTL_SECS", "0");
+    let dir_a = tempfile::tempdir().expect("tempdir");
+    let dir_b = tempfile::tempdir().expect("tempdir");
+    let server = TestServer::builder()
+        .extra_yaml_storage_section(&format!(
+            r#"
+backends:
+  - name: small
+    type: filesystem
+    path: {}
+  - name: large
+    type: filesystem
+    path: {}
+buckets:
+  archive:
+    span:
+      backends:
+        - backend: small
+          capacity_bytes: 16
+        - backend: large
+"#,
+            dir_a.path().display(),
+            dir_b.path().display()
+        ))
+        .build()
+        .await;
+    let http = reqwest::Client::new();
+    let endpoint = server.endpoint();
+
+    let resp = http
+        .put(format!("{endpoint}/archive"))
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success(), "create: {}", resp.status());
+
+    // Empty first member: the first deltaspace lands there and overfills it.
+    common::put_object(
+        &http,
+        &endpoint,
+        "archive",
+        "2025/q1.txt",
+        vec![b'a'; 64],
+        "text/plain",
+    )
+    .await;
+    assert_eq!(find_files(dir_a.path(), "q1.txt").len(), 1);
+
+    // Same deltaspace: stays on the full member.
+    common::put_object(
+        &http,
+        &endpoint,
+        "archive",
+        "2025/q2.txt",
+        vec![b'b'; 64],
+        "text/plain",
+    )
+    .await;
+    assert_eq!(find_files(dir_a.path(), "q2.txt").len(), 1);
+
+    // New deltaspace: spills to the second member.
+    common::put_object(
+        &http,
+        &endpoint,
+        "archive",
+        "2026/q1.txt",
+        vec![b'c'; 64],
+        "text/plain",
+    )
+    .await;
+    assert!(find_files(dir_a.path(), "q1.txt")
+        .iter()
+        .all(|p| !p.to_string_lossy().contains("2026")));
+    assert_eq!(find_files(dir_b.path(), "q1.txt").len(), 1);
+
+    let body = common::get_bytes(&http, &endpoint, "archive", "2026/q1.txt").await;
+    assert_eq!(body, vec![b'c'; 64]);
+    let body = common::get_bytes(&http, &endpoint, "archive", "2025/q2.txt").await;
+    assert_eq!(body, vec![b'b'; 64]);
+
+    let listing = http
+        .get(format!("{endpoint}/archive?list-type=2"))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    for key in ["2025/q1.txt", "2025/q2.txt", "2026/q1.txt"] {
+        assert!(
+            listing.contains(&format!("<Key>{key}</Key>")),
+            "{key} listed"
+        );
+    }
+}
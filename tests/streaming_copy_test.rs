@@ -2,9 +2,9 @@
 
 //! Phase B: streaming multipart replication copy of a large passthrough
 //! object. Exercises the `transfer.rs` streaming branch end-to-end through
-//! the replication run-now path on the filesystem backend (the default
-//! buffering multipart impl, native=false — still drives create → parts →
-//! complete with per-part ranged GETs). Asserts the destination object is
+//! the replication run-now path on the filesystem backend (parts staged on
+//! disk and joined at complete — drives create → parts → complete with
+//! per-part ranged GETs). Asserts the destination object is
 //! byte-identical and correctly sized.
 //!
 //! The threshold + part size are lowered via env so the test object stays